@@ -17,29 +17,54 @@ limitations under the License.
 package main
 
 import (
+	"context"
 	"crypto/tls"
 	"flag"
+	"fmt"
+	"net"
 	"os"
+	"os/exec"
+	"path/filepath"
+	goruntime "runtime"
+	"strconv"
+	"strings"
+	"time"
 
 	// Import all Kubernetes client auth plugins (e.g. Azure, GCP, OIDC, etc.)
 	// to ensure that exec-entrypoint and run can make use of them.
 	_ "k8s.io/client-go/plugin/pkg/client/auth"
 
+	corev1 "k8s.io/api/core/v1"
 	"k8s.io/apimachinery/pkg/runtime"
 	utilruntime "k8s.io/apimachinery/pkg/util/runtime"
+	"k8s.io/apimachinery/pkg/util/wait"
 	clientgoscheme "k8s.io/client-go/kubernetes/scheme"
 	ctrl "sigs.k8s.io/controller-runtime"
+	"sigs.k8s.io/controller-runtime/pkg/cache"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	"sigs.k8s.io/controller-runtime/pkg/client/config"
 	"sigs.k8s.io/controller-runtime/pkg/healthz"
 	"sigs.k8s.io/controller-runtime/pkg/log/zap"
 	"sigs.k8s.io/controller-runtime/pkg/metrics/filters"
 	metricsserver "sigs.k8s.io/controller-runtime/pkg/metrics/server"
 	"sigs.k8s.io/controller-runtime/pkg/webhook"
+	gatewayv1 "sigs.k8s.io/gateway-api/apis/v1"
 	gatewayv1beta1 "sigs.k8s.io/gateway-api/apis/v1beta1"
 
 	healthv1alpha1 "github.com/kdwils/constellation/api/v1alpha1"
+	"github.com/kdwils/constellation/internal/broadcast"
+	"github.com/kdwils/constellation/internal/cli"
 	"github.com/kdwils/constellation/internal/controller"
-	"github.com/kdwils/constellation/internal/healthcheck"
+	"github.com/kdwils/constellation/internal/demo"
+	"github.com/kdwils/constellation/internal/follower"
+	"github.com/kdwils/constellation/internal/listen"
+	"github.com/kdwils/constellation/internal/loadgen"
+	"github.com/kdwils/constellation/internal/scrub"
 	"github.com/kdwils/constellation/internal/server"
+	"github.com/kdwils/constellation/internal/snapshot"
+	"github.com/kdwils/constellation/internal/traffic"
+	constellationclient "github.com/kdwils/constellation/pkg/client"
+	"github.com/kdwils/constellation/pkg/constellation"
 	// +kubebuilder:scaffold:imports
 )
 
@@ -50,6 +75,7 @@ var (
 
 func init() {
 	utilruntime.Must(clientgoscheme.AddToScheme(scheme))
+	utilruntime.Must(gatewayv1.Install(scheme))
 	utilruntime.Must(gatewayv1beta1.Install(scheme))
 
 	utilruntime.Must(healthv1alpha1.AddToScheme(scheme))
@@ -58,6 +84,24 @@ func init() {
 
 // nolint:gocyclo
 func main() {
+	if len(os.Args) > 1 && cli.IsCommand(os.Args[1]) {
+		if err := cli.Run(context.Background(), scheme, os.Args[1:], os.Stdout); err != nil {
+			fmt.Fprintln(os.Stderr, err)
+			os.Exit(1)
+		}
+		return
+	}
+
+	if len(os.Args) > 1 && os.Args[1] == "loadgen" {
+		runLoadgen(os.Args[2:])
+		return
+	}
+
+	// serve is the default mode and may be given explicitly
+	if len(os.Args) > 1 && os.Args[1] == "serve" {
+		os.Args = append(os.Args[:1], os.Args[2:]...)
+	}
+
 	var metricsAddr string
 	var metricsCertPath, metricsCertName, metricsCertKey string
 	var webhookCertPath, webhookCertName, webhookCertKey string
@@ -66,7 +110,64 @@ func main() {
 	var secureMetrics bool
 	var enableHTTP2 bool
 	var serverPort int
+	var listenAddr string
+	var httpConfig constellation.HTTPConfig
 	var staticDir string
+	var apiOnly bool
+	var fromSnapshot string
+	var demoMode bool
+	var followConfig follower.Config
+	var kubeContext string
+	var prometheus constellation.PrometheusConfig
+	var podUsage bool
+	var restartAnomaly constellation.RestartAnomalyConfig
+	var clusterDomain string
+	var inspectCertificates bool
+	var certificates constellation.CertificateConfig
+	var trackDisruptions bool
+	var disruptions constellation.DisruptionConfig
+	var trackChurn bool
+	var churn constellation.ChurnConfig
+	var trackRollouts bool
+	var rollouts constellation.RolloutConfig
+	var trackReplicaDrift bool
+	var replicaDrift constellation.ReplicaDriftConfig
+	var trackPodReplacements bool
+	var podReplacements constellation.PodReplacementConfig
+	var trackTimeline bool
+	var timeline constellation.TimelineConfig
+	var acceptAnnotations bool
+	var annotations constellation.AnnotationConfig
+	var probeAPIServer bool
+	var apiProbes constellation.APIProbeConfig
+	var ownerKeys string
+	var rateLimit constellation.RateLimitConfig
+	var accessLog bool
+	var kubeAPIQPS float64
+	var kubeAPIBurst int
+	var kubeAPIContentType string
+	var cacheSyncPeriod time.Duration
+	var watchBookmarks bool
+	var cacheSyncJitter float64
+	var retryBackoff constellation.RetryBackoff
+	var slimPods bool
+	var scrubAnnotations string
+	var maxChildren int
+	var disabledWatches string
+	var adminTokenFile string
+	var identityHeader string
+	var broadcastConfig constellation.BroadcastConfig
+	var snapshotFile string
+	var mutexProfileFraction int
+	var ui constellation.UIConfig
+	var uiNamespaces string
+	var anonymizeOutput bool
+	var anonymizeConfig constellation.AnonymizeConfig
+	var badgeConfig constellation.BadgeConfig
+	var enableFavorites bool
+	var favoritesConfigMap string
+	var messageSizeLimit int
+	var topologyMetrics bool
 	var tlsOpts []func(*tls.Config)
 	flag.StringVar(&metricsAddr, "metrics-bind-address", "0", "The address the metrics endpoint binds to. "+
 		"Use :8443 for HTTPS or :8080 for HTTP, or leave as 0 to disable the metrics service.")
@@ -86,7 +187,202 @@ func main() {
 	flag.BoolVar(&enableHTTP2, "enable-http2", false,
 		"If set, HTTP/2 will be enabled for the metrics and webhook servers")
 	flag.IntVar(&serverPort, "server-port", 8080, "The port for the constellation server")
+	bindListenFlag(flag.CommandLine, &listenAddr)
+	bindHTTPFlags(flag.CommandLine, &httpConfig)
 	flag.StringVar(&staticDir, "static-dir", "frontend/dist", "Directory containing static UI files")
+	flag.BoolVar(&apiOnly, "api-only", false,
+		"Serve only the API: no static files are served and unknown routes get a JSON 404. Ignores --static-dir.")
+	flag.StringVar(&fromSnapshot, "from-snapshot", "",
+		"Serve a snapshot file exported from /snapshot instead of connecting to a cluster.")
+	flag.BoolVar(&demoMode, "demo", false,
+		"Serve a synthetic, self-mutating demo cluster instead of connecting to a cluster.")
+	flag.StringVar(&followConfig.Leader, "follow", "",
+		"Base URL of another constellation to replicate and serve reads from instead of connecting to a cluster.")
+	flag.DurationVar(&followConfig.RefreshInterval, "follow-refresh-interval", 30*time.Second,
+		"How often a follower fetches the leader's raw objects again. Health and the hierarchy are streamed.")
+	flag.StringVar(&kubeContext, "context", "",
+		"The kubeconfig context to use when running as the kubectl constellation plugin.")
+	flag.StringVar(&prometheus.URL, "prometheus-url", "",
+		"Prometheus server to query for the traffic metrics overlay. Leave empty to disable it.")
+	flag.DurationVar(&prometheus.Interval, "prometheus-interval", 30*time.Second, "How often traffic metrics are refreshed.")
+	flag.StringVar(&prometheus.RequestRateQuery, "prometheus-request-rate-query", traffic.DefaultRequestRateQuery,
+		"PromQL returning requests per second, labelled by namespace and service.")
+	flag.StringVar(&prometheus.ErrorRateQuery, "prometheus-error-rate-query", traffic.DefaultErrorRateQuery,
+		"PromQL returning the failed request fraction, labelled by namespace and service.")
+	flag.StringVar(&prometheus.LatencyQuery, "prometheus-latency-query", traffic.DefaultLatencyQuery,
+		"PromQL returning latency in seconds, labelled by namespace and service.")
+	flag.BoolVar(&podUsage, "pod-usage", false,
+		"Attach live CPU and memory usage from metrics-server to pods. Requires metrics-server.")
+	flag.Func("restart-anomaly-threshold",
+		"Flag pods with at least this many restarts within --restart-anomaly-window. Disabled when unset.",
+		func(value string) error {
+			threshold, err := strconv.ParseInt(value, 10, 32)
+			restartAnomaly.Threshold = int32(threshold)
+			return err
+		})
+	flag.DurationVar(&restartAnomaly.Window, "restart-anomaly-window", 15*time.Minute,
+		"Sliding window restarts are counted over for --restart-anomaly-threshold.")
+	flag.StringVar(&clusterDomain, "cluster-domain", "cluster.local", "The cluster domain used for in-cluster DNS names.")
+	flag.BoolVar(&inspectCertificates, "inspect-certificates", false,
+		"Inspect the TLS certificates behind route hostnames. Requires get access to the referenced Secrets.")
+	flag.DurationVar(&certificates.Window, "certificate-expiry-window", 14*24*time.Hour,
+		"Flag certificates expiring within this duration when --inspect-certificates is set.")
+	flag.BoolVar(&trackDisruptions, "track-disruptions", false,
+		"Track evicted, preempted and OOM killed pods per workload and serve them at /disruptions.")
+	flag.IntVar(&disruptions.History, "disruption-history", 20,
+		"How many disruptions are kept per workload when --track-disruptions is set.")
+	flag.DurationVar(&disruptions.Retention, "disruption-retention", 24*time.Hour,
+		"How long a workload's disruptions are kept once it has no pods and no disruption events.")
+	flag.BoolVar(&trackChurn, "track-churn", false,
+		"Count pod creations and deletions per namespace and serve them as a heatmap at /churn.")
+	flag.DurationVar(&churn.Bucket, "churn-bucket", time.Minute, "Time covered by each column of the churn heatmap.")
+	flag.IntVar(&churn.Buckets, "churn-buckets", 60, "Columns kept in the churn heatmap when --track-churn is set.")
+	flag.BoolVar(&trackRollouts, "track-rollouts", false,
+		"Show the Deployment revision and change-cause on pods and serve rollouts as they start at /events.")
+	flag.IntVar(&rollouts.MaxEvents, "rollout-events", 100,
+		"How many rollout events are kept for /events when --track-rollouts is set.")
+	flag.BoolVar(&trackReplicaDrift, "track-replica-drift", false,
+		"Compare desired replicas of Deployments and StatefulSets against ready pods and alert on lasting drift.")
+	flag.DurationVar(&replicaDrift.After, "replica-drift-after", 5*time.Minute,
+		"How long a workload may run short of ready replicas before it is alerted on.")
+	flag.BoolVar(&trackPodReplacements, "track-pod-replacements", false,
+		"Serve an event at /events linking a pod recreated by its controller to the pod it replaces.")
+	flag.DurationVar(&podReplacements.Window, "pod-replacement-window", time.Minute,
+		"How long after a pod starts terminating a new pod of the same owner and template replaces it.")
+	flag.IntVar(&podReplacements.MaxEvents, "pod-replacement-events", 100,
+		"How many replacement events are kept for /events when --track-pod-replacements is set.")
+	flag.BoolVar(&trackTimeline, "track-timeline", false,
+		"Keep recent phase, endpoint and route attachment changes per node at /resources/{kind}/{ns}/{name}/timeline.")
+	flag.IntVar(&timeline.Length, "timeline-length", 20,
+		"How many transitions are kept per node when --track-timeline is set.")
+	flag.BoolVar(&acceptAnnotations, "accept-annotations", false,
+		"Accept CloudEvents at POST /annotations and show them as markers on the node their subject names.")
+	flag.IntVar(&annotations.MaxMarkers, "annotation-markers", 20,
+		"How many markers are kept per node when --accept-annotations is set.")
+	flag.BoolVar(&probeAPIServer, "probe-apiserver", false,
+		"Measure list and watch latency against the apiserver, serve it at /readyz and fail readiness while it is slow.")
+	flag.DurationVar(&apiProbes.Interval, "apiserver-probe-interval", 30*time.Second,
+		"How often the apiserver is probed when --probe-apiserver is set.")
+	flag.DurationVar(&apiProbes.Threshold, "apiserver-latency-threshold", 5*time.Second,
+		"List or watch latency above which the apiserver is reported slow when --probe-apiserver is set.")
+	flag.StringVar(&ownerKeys, "owner-keys", "owner,team",
+		"Comma separated label and annotation keys a resource's owner is read from, in order of preference.")
+	flag.Float64Var(&rateLimit.RequestsPerSecond, "rate-limit", 0,
+		"API requests per second allowed from each client IP. Disabled when 0.")
+	flag.IntVar(&rateLimit.Burst, "rate-limit-burst", 0,
+		"API requests a client IP may make at once, defaulting to --rate-limit rounded up.")
+	flag.IntVar(&rateLimit.MaxConcurrent, "max-concurrent-requests", 0,
+		"API requests served at once across all clients. Disabled when 0.")
+	flag.BoolVar(&accessLog, "access-log", false,
+		"Log every dashboard request with its route, status, latency and client IP.")
+	flag.Float64Var(&kubeAPIQPS, "kube-api-qps", 0,
+		"Requests per second the manager's client may send to the API server, defaulting to client-go's limit when 0.")
+	flag.IntVar(&kubeAPIBurst, "kube-api-burst", 0,
+		"Requests the manager's client may send to the API server at once, defaulting to client-go's limit when 0.")
+	flag.StringVar(&kubeAPIContentType, "kube-api-content-type", "protobuf",
+		"Encoding the manager's client lists and watches in, protobuf or json. Protobuf cuts bandwidth and decode CPU "+
+			"for built-in kinds; CRDs such as Gateway API are always read as JSON.")
+	flag.DurationVar(&cacheSyncPeriod, "cache-sync-period", 0,
+		"How often every watched object is reconciled again from the cache, defaulting to controller-runtime's 10 hours when 0.")
+	flag.BoolVar(&watchBookmarks, "watch-bookmarks", true,
+		"Ask the API server for watch bookmarks, so a dropped watch resumes from a recent resource version "+
+			"instead of relisting.")
+	flag.Float64Var(&cacheSyncJitter, "cache-sync-jitter", 0.1,
+		"Lengthen --cache-sync-period by a random fraction up to this, so replicas across a fleet do not relist "+
+			"against the control plane at the same time.")
+	flag.DurationVar(&retryBackoff.Base, "retry-backoff-base", 0,
+		"Delay before a failed reconcile is first retried, doubling per retry. Uses controller-runtime's 5ms when 0.")
+	flag.DurationVar(&retryBackoff.Max, "retry-backoff-max", 1000*time.Second,
+		"Longest delay between retries of a failed reconcile when --retry-backoff-base is set.")
+	flag.BoolVar(&slimPods, "slim-pods", false,
+		"Cache only pod metadata, scheduling, ports, liveness probes and status, dropping env, resources, volumes "+
+			"and security contexts to save memory on very large clusters. Security and resource views lose that detail.")
+	flag.StringVar(&scrubAnnotations, "scrub-annotations", "",
+		"Comma separated annotation keys stripped from every object before it is cached or served, in addition to "+
+			"kubectl.kubernetes.io/last-applied-configuration. A key ending in * matches every key with that prefix.")
+	flag.IntVar(&maxChildren, "max-children", 0,
+		"Children served under each hierarchy root before the rest are summarised in a Truncated node. Unlimited when 0.")
+	flag.StringVar(&disabledWatches, "disable-watches", "",
+		"Comma separated resources to leave unwatched and out of the hierarchy, e.g. httproutes,gateways,gatewayclasses.")
+	flag.StringVar(&adminTokenFile, "admin-token-file", "",
+		"File holding the bearer token for the /admin API, which switches watches, resyncs and inspects the cache. "+
+			"The API is off when unset.")
+	flag.StringVar(&identityHeader, "identity-header", "",
+		"Header an authenticating proxy sets to the signed in user, e.g. X-Forwarded-User, recorded with every "+
+			"change in /admin/audit. Only set it behind a proxy that overwrites the header.")
+	flag.StringVar(&broadcastConfig.URL, "broadcast-url", "",
+		"Redis or NATS server health updates are relayed between replicas through, as redis://host:port or "+
+			"nats://host:port, so every replica streams the same updates. Use with --leader-elect. "+
+			"rediss:// and tls:// connect over TLS.")
+	flag.StringVar(&broadcastConfig.Channel, "broadcast-channel", "constellation.health",
+		"Redis channel or NATS subject health updates are published on.")
+	flag.StringVar(&broadcastConfig.PasswordFile, "broadcast-password-file", "",
+		"File holding the password to authenticate to the broadcast server with, in place of one in --broadcast-url.")
+	flag.StringVar(&broadcastConfig.CAFile, "broadcast-ca-file", "",
+		"CA certificate file the broadcast server's certificate is verified against, in place of the system roots.")
+	flag.StringVar(&broadcastConfig.CertFile, "broadcast-cert-file", "",
+		"Client certificate file presented to the broadcast server. Use with --broadcast-key-file.")
+	flag.StringVar(&broadcastConfig.KeyFile, "broadcast-key-file", "",
+		"Client key file for --broadcast-cert-file.")
+	flag.IntVar(&broadcastConfig.MaxPayload, "broadcast-max-payload", broadcast.DefaultMaxPayload,
+		"Largest message in bytes published to or accepted from the broadcast server. NATS publishes are also "+
+			"held to the server's max_payload.")
+	flag.DurationVar(&broadcastConfig.WriteTimeout, "broadcast-write-timeout", broadcast.DefaultWriteTimeout,
+		"How long a write to the broadcast server may take before the connection is dropped.")
+	flag.StringVar(&snapshotFile, "snapshot-file", "",
+		"File the snapshot is persisted to every minute and on shutdown, and served from, marked stale, "+
+			"while the cache syncs after a restart. Put it on a volume that outlives the pod.")
+	flag.IntVar(&mutexProfileFraction, "mutex-profile-fraction", 0,
+		"Sample 1 in this many lock contention events for /debug/pprof/mutex, served with the /admin API. "+
+			"Disabled when 0.")
+	flag.IntVar(&messageSizeLimit, "ws-message-size-limit", 0,
+		"Split WebSocket messages larger than this many bytes into chunks, for proxies that drop frames above "+
+			"a size such as 1MiB. Messages are never split when 0.")
+	flag.BoolVar(&topologyMetrics, "topology-metrics", false,
+		"Export gauges such as constellation_service_ready_endpoints and constellation_route_resolvable on the "+
+			"metrics endpoint set by --metrics-bind-address.")
+	flag.StringVar(&ui.Title, "ui-title", "Constellation", "Title the dashboard shows, e.g. the cluster name.")
+	flag.DurationVar(&ui.RefreshInterval, "ui-refresh-interval", 0,
+		"How often the dashboard polls views that are not streamed. Uses the frontend's default when 0.")
+	flag.StringVar(&uiNamespaces, "ui-default-namespaces", "",
+		"Comma separated namespaces the dashboard filters to when it loads. Shows every namespace when unset.")
+	flag.Func("ui-feature",
+		"Frontend feature flag as name=true or name=false, e.g. showSystemNamespaces=true. May be repeated.",
+		func(value string) error {
+			name, enabled, found := strings.Cut(value, "=")
+			if !found {
+				return fmt.Errorf("expected name=true or name=false, got %q", value)
+			}
+			on, err := strconv.ParseBool(enabled)
+			if err != nil {
+				return err
+			}
+			if ui.Features == nil {
+				ui.Features = make(map[string]bool)
+			}
+			ui.Features[name] = on
+			return nil
+		})
+	flag.BoolVar(&anonymizeOutput, "anonymize", false,
+		"Replace names, hostnames and IPs in every API response with stable pseudonyms, so screenshots and "+
+			"snapshots can be shared publicly.")
+	flag.StringVar(&anonymizeConfig.Key, "anonymize-key", "",
+		"Key the --anonymize pseudonyms are derived from, keeping them stable across restarts. Random when unset.")
+	flag.Func("badge",
+		"Badge attached to every node whose labels match a selector, as selector:badge, e.g. tier=db:stateful or "+
+			"istio-injection=enabled:mesh. May be repeated.",
+		func(value string) error {
+			selector, badge, found := strings.Cut(value, ":")
+			if !found {
+				return fmt.Errorf("expected selector:badge, got %q", value)
+			}
+			badgeConfig.Rules = append(badgeConfig.Rules, constellation.BadgeRule{Selector: selector, Badge: badge})
+			return nil
+		})
+	flag.BoolVar(&enableFavorites, "favorites", false,
+		"Serve the nodes pinned on the dashboard at /favorites and the ?favorites=true filter, keeping pins in memory.")
+	flag.StringVar(&favoritesConfigMap, "favorites-configmap", "",
+		"ConfigMap pins are persisted in across restarts, as namespace/name. Implies --favorites.")
 	opts := zap.Options{
 		Development: true,
 	}
@@ -95,6 +391,36 @@ func main() {
 
 	ctrl.SetLogger(zap.New(zap.UseFlagOptions(&opts)))
 
+	if apiOnly {
+		staticDir = ""
+	}
+
+	listener, err := listen.Open(listenAddr)
+	if err != nil {
+		setupLog.Error(err, "unable to open listener", "listen", listenAddr)
+		os.Exit(1)
+	}
+
+	if fromSnapshot != "" {
+		serveSnapshot(fromSnapshot, frontendOpt(staticDir, apiOnly), serverPort, listener, httpConfig)
+		return
+	}
+
+	if demoMode {
+		serveDemo(frontendOpt(staticDir, apiOnly), serverPort, listener, httpConfig)
+		return
+	}
+
+	if followConfig.Leader != "" {
+		serveFollower(followConfig, frontendOpt(staticDir, apiOnly), serverPort, listener, httpConfig)
+		return
+	}
+
+	if isKubectlPlugin() {
+		runPlugin(staticDir, kubeContext)
+		return
+	}
+
 	disableHTTP2 := func(c *tls.Config) {
 		setupLog.Info("disabling http/2")
 		c.NextProtos = []string{"http/1.1"}
@@ -147,39 +473,161 @@ func main() {
 		metricsServerOptions.KeyName = metricsCertKey
 	}
 
-	mgr, err := ctrl.NewManager(ctrl.GetConfigOrDie(), ctrl.Options{
+	// Large clusters relist every watched kind on start, which can exceed client-go's default
+	// 20 QPS budget and get throttled by API priority and fairness
+	restConfig := ctrl.GetConfigOrDie()
+	if kubeAPIQPS > 0 {
+		restConfig.QPS = float32(kubeAPIQPS)
+	}
+	if kubeAPIBurst > 0 {
+		restConfig.Burst = kubeAPIBurst
+	}
+	switch kubeAPIContentType {
+	case "protobuf":
+		// controller-runtime only requests protobuf for built-in kinds, so JSON stays acceptable for CRDs
+		restConfig.AcceptContentTypes = runtime.ContentTypeProtobuf + "," + runtime.ContentTypeJSON
+	case "json":
+		restConfig.ContentType = runtime.ContentTypeJSON
+		restConfig.AcceptContentTypes = runtime.ContentTypeJSON
+	default:
+		setupLog.Error(fmt.Errorf("expected protobuf or json, got %q", kubeAPIContentType), "invalid --kube-api-content-type")
+		os.Exit(1)
+	}
+
+	var scrubbedAnnotations []string
+	if scrubAnnotations != "" {
+		scrubbedAnnotations = strings.Split(scrubAnnotations, ",")
+	}
+	scrubber := scrub.New(scrubbedAnnotations...)
+
+	// Informers make their initial list from the API server's watch cache, which does not page, and
+	// controller-runtime does not expose the reflector's page size, so list paging is left to the CLI's
+	// direct reads. Namespaces are watched as metadata only; the hierarchy reads the spec or status of
+	// every other kind.
+	cacheOptions := cache.Options{DefaultTransform: scrubber.Transform, DefaultEnableWatchBookmarks: &watchBookmarks}
+	if cacheSyncPeriod > 0 {
+		cacheSyncPeriod = wait.Jitter(cacheSyncPeriod, cacheSyncJitter)
+		cacheOptions.SyncPeriod = &cacheSyncPeriod
+	}
+	if slimPods {
+		cacheOptions.ByObject = map[client.Object]cache.ByObject{
+			&corev1.Pod{}: {Transform: scrubber.Then(controller.SlimPod)},
+		}
+	}
+
+	mgr, err := ctrl.NewManager(restConfig, ctrl.Options{
 		Scheme:                 scheme,
 		Metrics:                metricsServerOptions,
 		WebhookServer:          webhookServer,
 		HealthProbeBindAddress: probeAddr,
 		LeaderElection:         enableLeaderElection,
 		LeaderElectionID:       "5d5a2748.kyledev.co",
+		Cache:                  cacheOptions,
 	})
 	if err != nil {
 		setupLog.Error(err, "unable to start manager")
 		os.Exit(1)
 	}
 
-	healthChecker := healthcheck.NewHealthChecker()
-
-	serviceReconciler := controller.NewServiceReconciler(mgr, healthChecker)
-	if err = serviceReconciler.SetupWithManager(mgr); err != nil {
-		setupLog.Error(err, "unable to create controller", "controller", "Service")
-		os.Exit(1)
+	constellationOpts := constellation.Options{
+		Port:              serverPort,
+		StaticDir:         staticDir,
+		APIOnly:           apiOnly,
+		Listener:          listener,
+		HTTP:              httpConfig,
+		WatchHealthChecks: true,
+		PodUsage:          podUsage,
+		ClusterDomain:     clusterDomain,
+		AccessLog:         accessLog,
+		MaxChildren:       maxChildren,
+		MessageSizeLimit:  messageSizeLimit,
+		TopologyMetrics:   topologyMetrics,
 	}
-
-	podReconciler := controller.NewPodReconciler(mgr, healthChecker)
-	if err = podReconciler.SetupWithManager(mgr); err != nil {
-		setupLog.Error(err, "unable to create controller", "controller", "Pod")
-		os.Exit(1)
+	if retryBackoff.Base > 0 {
+		constellationOpts.RetryBackoff = &retryBackoff
 	}
-
-	if err := (&controller.HealthCheckReconciler{
-		Client:        mgr.GetClient(),
-		Scheme:        mgr.GetScheme(),
-		HealthChecker: healthChecker,
-	}).SetupWithManager(mgr); err != nil {
-		setupLog.Error(err, "unable to create controller", "controller", "HealthCheck")
+	if prometheus.URL != "" {
+		constellationOpts.Prometheus = &prometheus
+	}
+	if restartAnomaly.Threshold > 0 {
+		constellationOpts.RestartAnomaly = &restartAnomaly
+	}
+	if inspectCertificates {
+		constellationOpts.Certificates = &certificates
+	}
+	if trackDisruptions {
+		constellationOpts.Disruptions = &disruptions
+	}
+	if trackChurn {
+		constellationOpts.Churn = &churn
+	}
+	if trackRollouts {
+		constellationOpts.Rollouts = &rollouts
+	}
+	if trackReplicaDrift {
+		constellationOpts.ReplicaDrift = &replicaDrift
+	}
+	if trackPodReplacements {
+		constellationOpts.PodReplacements = &podReplacements
+	}
+	if trackTimeline {
+		constellationOpts.Timeline = &timeline
+	}
+	if acceptAnnotations {
+		constellationOpts.Annotations = &annotations
+	}
+	if probeAPIServer {
+		constellationOpts.APIProbes = &apiProbes
+	}
+	if ownerKeys != "" {
+		constellationOpts.OwnerKeys = strings.Split(ownerKeys, ",")
+	}
+	constellationOpts.ScrubAnnotations = scrubbedAnnotations
+	constellationOpts.IdentityHeader = identityHeader
+	if broadcastConfig.URL != "" {
+		constellationOpts.Broadcast = &broadcastConfig
+	}
+	constellationOpts.SnapshotFile = snapshotFile
+	if disabledWatches != "" {
+		constellationOpts.DisabledWatches = strings.Split(disabledWatches, ",")
+	}
+	if uiNamespaces != "" {
+		ui.DefaultNamespaces = strings.Split(uiNamespaces, ",")
+	}
+	constellationOpts.UI = &ui
+	if anonymizeOutput {
+		constellationOpts.Anonymize = &anonymizeConfig
+	}
+	if len(badgeConfig.Rules) > 0 {
+		constellationOpts.Badges = &badgeConfig
+	}
+	if enableFavorites {
+		constellationOpts.Favorites = &constellation.FavoritesConfig{}
+	}
+	if favoritesConfigMap != "" {
+		namespace, name, found := strings.Cut(favoritesConfigMap, "/")
+		if !found || namespace == "" || name == "" {
+			setupLog.Error(fmt.Errorf("expected namespace/name, got %q", favoritesConfigMap), "invalid --favorites-configmap")
+			os.Exit(1)
+		}
+		constellationOpts.Favorites = &constellation.FavoritesConfig{Namespace: namespace, Name: name}
+	}
+	if mutexProfileFraction > 0 {
+		goruntime.SetMutexProfileFraction(mutexProfileFraction)
+	}
+	if adminTokenFile != "" {
+		token, err := os.ReadFile(adminTokenFile)
+		if err != nil {
+			setupLog.Error(err, "unable to read admin token", "path", adminTokenFile)
+			os.Exit(1)
+		}
+		constellationOpts.AdminToken = strings.TrimSpace(string(token))
+	}
+	if rateLimit.RequestsPerSecond > 0 || rateLimit.MaxConcurrent > 0 {
+		constellationOpts.RateLimit = &rateLimit
+	}
+	if _, err := constellation.SetupWithManager(mgr, constellationOpts); err != nil {
+		setupLog.Error(err, "unable to set up constellation")
 		os.Exit(1)
 	}
 	// +kubebuilder:scaffold:builder
@@ -195,16 +643,7 @@ func main() {
 
 	ctx := ctrl.SetupSignalHandler()
 
-	// Start state manager immediately so it can process updates
-	go healthChecker.Start(ctx)
-
-	srv := server.NewServer(healthChecker, staticDir, serverPort)
-	go func() {
-		setupLog.Info("starting constellation server", "port", serverPort, "static-dir", staticDir)
-		if err := srv.Serve(ctx); err != nil {
-			setupLog.Error(err, "failed to start constellation server")
-		}
-	}()
+	setupLog.Info("starting constellation server", "port", serverPort, "listen", listenAddr, "static-dir", staticDir)
 
 	// Start manager in background and build initial state once cache is ready
 	go func() {
@@ -226,3 +665,252 @@ func main() {
 
 	<-ctx.Done()
 }
+
+// frontendOpt serves the frontend from staticDir, or only the API when apiOnly is set
+func frontendOpt(staticDir string, apiOnly bool) server.ServerOpt {
+	if apiOnly {
+		return server.WithAPIOnly()
+	}
+	return server.WithStaticDir(staticDir)
+}
+
+// serveSnapshot runs the server against a snapshot file with no cluster connection
+func serveSnapshot(path string, frontend server.ServerOpt, port int, listener net.Listener,
+	httpConfig server.HTTPConfig) {
+	provider, err := snapshot.Load(path)
+	if err != nil {
+		setupLog.Error(err, "unable to load snapshot", "path", path)
+		os.Exit(1)
+	}
+
+	srv := server.New(provider,
+		frontend,
+		server.WithPort(port),
+		server.WithListener(listener),
+		server.WithHTTPConfig(httpConfig),
+		server.WithHierarchyProvider(provider),
+		server.WithSnapshotProvider(provider),
+	)
+
+	setupLog.Info("serving constellation from snapshot", "path", path, "port", port)
+	if err := srv.Serve(ctrl.SetupSignalHandler()); err != nil {
+		setupLog.Error(err, "failed to start constellation server")
+		os.Exit(1)
+	}
+}
+
+// serveDemo runs the server against the bundled synthetic cluster
+func serveDemo(frontend server.ServerOpt, port int, listener net.Listener, httpConfig server.HTTPConfig) {
+	cluster, err := demo.NewCluster()
+	if err != nil {
+		setupLog.Error(err, "unable to load demo cluster")
+		os.Exit(1)
+	}
+
+	ctx := ctrl.SetupSignalHandler()
+	go cluster.Start(ctx)
+
+	srv := server.New(cluster,
+		frontend,
+		server.WithPort(port),
+		server.WithListener(listener),
+		server.WithHTTPConfig(httpConfig),
+		server.WithHierarchyProvider(cluster),
+		server.WithSnapshotProvider(cluster),
+	)
+
+	setupLog.Info("serving constellation demo cluster", "port", port)
+	if err := srv.Serve(ctx); err != nil {
+		setupLog.Error(err, "failed to start constellation server")
+		os.Exit(1)
+	}
+}
+
+// runLoadgen serves the API over a synthetic cluster churning at a fixed rate, to size constellation
+// for a big cluster without connecting to one
+func runLoadgen(args []string) {
+	fs := flag.NewFlagSet("loadgen", flag.ExitOnError)
+	var loadConfig loadgen.Config
+	var churn string
+	var staticDir string
+	var port int
+	var listenAddr string
+	var httpConfig server.HTTPConfig
+	fs.IntVar(&loadConfig.Pods, "pods", 5000, "Pods in the synthetic cluster, grouped ten to a Service.")
+	fs.StringVar(&churn, "churn", "50/s", "Pod events applied per second or minute, e.g. 50/s or 600/m.")
+	fs.IntVar(&port, "server-port", 8080, "The port for the constellation server")
+	bindListenFlag(fs, &listenAddr)
+	bindHTTPFlags(fs, &httpConfig)
+	fs.StringVar(&staticDir, "static-dir", "frontend/dist", "Directory containing static UI files")
+	opts := zap.Options{
+		Development: true,
+	}
+	opts.BindFlags(fs)
+	if err := fs.Parse(args); err != nil {
+		os.Exit(2)
+	}
+
+	ctrl.SetLogger(zap.New(zap.UseFlagOptions(&opts)))
+
+	rate, err := loadgen.ParseRate(churn)
+	if err != nil {
+		setupLog.Error(err, "invalid --churn")
+		os.Exit(1)
+	}
+	loadConfig.Churn = rate
+	generator := loadgen.New(loadConfig)
+
+	listener, err := listen.Open(listenAddr)
+	if err != nil {
+		setupLog.Error(err, "unable to open listener", "listen", listenAddr)
+		os.Exit(1)
+	}
+
+	ctx := ctrl.SetupSignalHandler()
+	go func() {
+		if err := generator.Start(ctx); err != nil {
+			setupLog.Error(err, "load generator stopped")
+			os.Exit(1)
+		}
+	}()
+
+	srv := server.New(generator,
+		server.WithStaticDir(staticDir),
+		server.WithPort(port),
+		server.WithListener(listener),
+		server.WithHTTPConfig(httpConfig),
+		server.WithHierarchyProvider(generator),
+		server.WithSnapshotProvider(generator),
+	)
+
+	setupLog.Info("serving constellation over a synthetic cluster",
+		"pods", loadConfig.Pods, "churn", churn, "port", port, "listen", listenAddr)
+	if err := srv.Serve(ctx); err != nil {
+		setupLog.Error(err, "failed to start constellation server")
+		os.Exit(1)
+	}
+}
+
+// bindListenFlag registers --listen, shared by the server and loadgen commands
+func bindListenFlag(fs *flag.FlagSet, address *string) {
+	fs.StringVar(address, "listen", "",
+		"Serve on unix:///path/to.sock or on the socket passed by systemd (systemd or systemd:name) "+
+			"instead of --server-port.")
+}
+
+// bindHTTPFlags registers the connection tuning flags, shared by the server and loadgen commands
+func bindHTTPFlags(fs *flag.FlagSet, config *server.HTTPConfig) {
+	fs.BoolVar(&config.HTTP2, "server-http2", false,
+		"Serve cleartext HTTP/2 (h2c) to clients that ask for it, next to HTTP/1.1. WebSockets stay on HTTP/1.1.")
+	fs.IntVar(&config.MaxConcurrentStreams, "server-max-concurrent-streams", 0,
+		"Streams each HTTP/2 connection may have open at once. net/http's default when 0.")
+	fs.BoolVar(&config.DisableKeepAlives, "server-disable-keep-alives", false,
+		"Close every dashboard connection after its first response.")
+	fs.DurationVar(&config.IdleTimeout, "server-idle-timeout", 0,
+		"How long an idle keep-alive connection is held open between requests. net/http's default when 0.")
+	fs.DurationVar(&config.TCPKeepAlive, "server-tcp-keep-alive", 0,
+		"Interval between TCP keep-alive probes on dashboard connections; negative disables them. "+
+			"Ignored with --listen.")
+}
+
+// serveFollower runs the server against the state replicated from another constellation instance
+func serveFollower(followConfig follower.Config, frontend server.ServerOpt, port int, listener net.Listener,
+	httpConfig server.HTTPConfig) {
+	leader, err := constellationclient.New(followConfig.Leader)
+	if err != nil {
+		setupLog.Error(err, "unable to create leader client", "leader", followConfig.Leader)
+		os.Exit(1)
+	}
+	replica := follower.New(leader, followConfig)
+
+	ctx := ctrl.SetupSignalHandler()
+	go replica.Start(ctx) //nolint:errcheck
+
+	srv := server.New(replica,
+		frontend,
+		server.WithPort(port),
+		server.WithListener(listener),
+		server.WithHTTPConfig(httpConfig),
+		server.WithHierarchyProvider(replica),
+		server.WithSnapshotProvider(replica),
+		server.WithSyncStatusProvider(replica),
+	)
+
+	setupLog.Info("serving constellation as a follower",
+		"leader", followConfig.Leader, "port", port)
+	if err := srv.Serve(ctx); err != nil {
+		setupLog.Error(err, "failed to start constellation server")
+		os.Exit(1)
+	}
+}
+
+// pluginBinary is the executable name kubectl looks for on PATH to run `kubectl constellation`
+const pluginBinary = "kubectl-constellation"
+
+// isKubectlPlugin reports whether the binary was invoked under its kubectl plugin name
+func isKubectlPlugin() bool {
+	name := strings.TrimSuffix(filepath.Base(os.Args[0]), ".exe")
+	return name == pluginBinary
+}
+
+// runPlugin serves the dashboard for the current kubeconfig context on a random loopback port,
+// opens it in the browser and exits on Ctrl-C
+func runPlugin(staticDir, kubeContext string) {
+	cfg, err := config.GetConfigWithContext(kubeContext)
+	if err != nil {
+		setupLog.Error(err, "unable to load kubeconfig", "context", kubeContext)
+		os.Exit(1)
+	}
+
+	mgr, err := ctrl.NewManager(cfg, ctrl.Options{
+		Scheme:                 scheme,
+		Metrics:                metricsserver.Options{BindAddress: "0"},
+		HealthProbeBindAddress: "0",
+	})
+	if err != nil {
+		setupLog.Error(err, "unable to start manager")
+		os.Exit(1)
+	}
+
+	listener, err := net.Listen("tcp", "127.0.0.1:0")
+	if err != nil {
+		setupLog.Error(err, "unable to bind local port")
+		os.Exit(1)
+	}
+
+	if _, err := constellation.SetupWithManager(mgr, constellation.Options{
+		Listener:  listener,
+		StaticDir: staticDir,
+	}); err != nil {
+		setupLog.Error(err, "unable to set up constellation")
+		os.Exit(1)
+	}
+
+	ctx := ctrl.SetupSignalHandler()
+	url := fmt.Sprintf("http://%s", listener.Addr())
+
+	go func() {
+		if !mgr.GetCache().WaitForCacheSync(ctx) {
+			return
+		}
+		fmt.Fprintf(os.Stderr, "constellation is available at %s, press Ctrl-C to stop\n", url)
+		if err := openBrowser(url); err != nil {
+			setupLog.Info("unable to open browser, visit the url manually", "url", url, "error", err.Error())
+		}
+	}()
+
+	if err := mgr.Start(ctx); err != nil {
+		setupLog.Error(err, "problem running manager")
+		os.Exit(1)
+	}
+}
+
+func openBrowser(url string) error {
+	switch goruntime.GOOS {
+	case "darwin":
+		return exec.Command("open", url).Start()
+	case "windows":
+		return exec.Command("rundll32", "url.dll,FileProtocolHandler", url).Start()
+	}
+	return exec.Command("xdg-open", url).Start()
+}
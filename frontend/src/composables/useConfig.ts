@@ -0,0 +1,36 @@
+import { ref } from 'vue'
+import type { UIConfig } from '../types'
+
+const defaultConfig: UIConfig = {
+  title: 'Constellation',
+  default_namespaces: [],
+  features: {}
+}
+
+// systemNamespaces are hidden when the showSystemNamespaces feature is turned off
+const systemNamespaces = ['kube-system', 'kube-public', 'kube-node-lease']
+
+// useConfig loads the per-install options served at /config.json, keeping the defaults if they cannot be read
+export function useConfig() {
+  const config = ref<UIConfig>(defaultConfig)
+
+  fetch('/config.json')
+    .then((response) => (response.ok ? response.json() : defaultConfig))
+    .then((loaded: UIConfig) => {
+      config.value = loaded
+      document.title = loaded.title
+    })
+    .catch((error) => {
+      console.error('Failed to load config:', error)
+    })
+
+  const showsNamespace = (namespace: string): boolean => {
+    const { default_namespaces: namespaces, features } = config.value
+    if (namespaces.length > 0 && !namespaces.includes(namespace)) {
+      return false
+    }
+    return features.showSystemNamespaces !== false || !systemNamespaces.includes(namespace)
+  }
+
+  return { config, showsNamespace }
+}
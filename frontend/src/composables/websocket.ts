@@ -1,5 +1,25 @@
 import { ref, onUnmounted } from 'vue'
-import type { ServiceHealthInfo } from '../types'
+import type { ChunkedMessage, ServiceHealthInfo } from '../types'
+
+// chunkAssembler collects the base64 parts of messages the server split to stay under its size limit,
+// returning the whole message once its last part arrives
+function chunkAssembler() {
+  const parts = new Map<number, string[]>()
+
+  return (message: ChunkedMessage): string | null => {
+    const { id, index, total, data } = message.chunk
+    const received = parts.get(id) ?? []
+    received[index] = data
+    parts.set(id, received)
+    if (received.filter((part) => part !== undefined).length < total) {
+      return null
+    }
+    parts.delete(id)
+
+    const bytes = Uint8Array.from(received.map((part) => atob(part)).join(''), (c) => c.charCodeAt(0))
+    return new TextDecoder().decode(bytes)
+  }
+}
 
 export function websocket() {
   const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:'
@@ -9,6 +29,7 @@ export function websocket() {
   const lastMessage = ref<ServiceHealthInfo[] | null>(null)
 
   const websocket = new WebSocket(wsUrl)
+  const reassemble = chunkAssembler()
 
   websocket.onopen = () => {
     isConnected.value = true
@@ -16,7 +37,15 @@ export function websocket() {
 
   websocket.onmessage = (event) => {
     try {
-      lastMessage.value = JSON.parse(event.data)
+      const message = JSON.parse(event.data)
+      if (message && !Array.isArray(message) && message.chunk) {
+        const whole = reassemble(message as ChunkedMessage)
+        if (whole !== null) {
+          lastMessage.value = JSON.parse(whole)
+        }
+        return
+      }
+      lastMessage.value = message
     } catch {
       lastMessage.value = null
     }
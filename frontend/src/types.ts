@@ -20,6 +20,15 @@ export interface ServiceHealthInfo {
   url: string
 }
 
+export interface ChunkedMessage {
+  chunk: {
+    id: number
+    index: number
+    total: number
+    data: string
+  }
+}
+
 export interface HierarchyNode {
   kind: string
   name: string
@@ -27,6 +36,7 @@ export interface HierarchyNode {
   relatives?: HierarchyNode[]
   hostnames?: string[]
   health_info?: ServiceHealthInfo
+  badges?: string[]
 }
 
 export interface ServiceHealthData {
@@ -43,3 +53,20 @@ export interface ServiceCardData {
   url: string
   serviceHealth: ServiceHealthData
 }
+
+export interface UIConfig {
+  title: string
+  refresh_interval?: number
+  default_namespaces: string[]
+  features: Record<string, boolean>
+}
+
+export interface Capabilities {
+  identity: string
+  authenticated: boolean
+  can_use_admin: boolean
+  can_resync: boolean
+  can_view_all_namespaces: boolean
+  can_pin_favorites: boolean
+  can_post_annotations: boolean
+}
@@ -17,6 +17,10 @@ export default defineConfig({
       '/state': {
         target: 'http://localhost:8080',
         changeOrigin: true,
+      },
+      '/config.json': {
+        target: 'http://localhost:8080',
+        changeOrigin: true,
       }
     }
   }
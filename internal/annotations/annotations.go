@@ -0,0 +1,275 @@
+// Package annotations attaches external context, such as a deployment started from CI or an incident
+// being opened, to nodes as time-stamped markers. Markers arrive as CloudEvents whose subject names the
+// node as kind/namespace/name, or kind/name for cluster scoped resources.
+package annotations
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"net/http"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const (
+	defaultMaxMarkers = 20
+
+	specVersion           = "1.0"
+	structuredContentType = "application/cloudevents+json"
+)
+
+// ErrInvalidEvent is returned for requests that are not a CloudEvent naming a node
+var ErrInvalidEvent = errors.New("invalid cloudevent")
+
+// Config sets how many markers are kept
+type Config struct {
+	// MaxMarkers is how many markers are kept per node, defaulting to 20
+	MaxMarkers int
+}
+
+// event is a CloudEvent in structured mode
+type event struct {
+	SpecVersion     string          `json:"specversion"`
+	ID              string          `json:"id"`
+	Source          string          `json:"source"`
+	Type            string          `json:"type"`
+	Subject         string          `json:"subject"`
+	Time            *time.Time      `json:"time"`
+	DataContentType string          `json:"datacontenttype"`
+	Data            json.RawMessage `json:"data"`
+}
+
+// Store keeps the markers of every node and notifies subscribers of each one added
+type Store struct {
+	config Config
+	now    func() time.Time
+
+	mu sync.RWMutex
+	// markers are keyed by nodeKey and hold at most config.MaxMarkers in the order they arrived
+	markers map[string][]types.Annotation
+
+	subMu       sync.RWMutex
+	subscribers map[chan []types.Annotation]bool
+}
+
+// NewStore creates an empty Store
+func NewStore(config Config, opts ...StoreOpt) *Store {
+	if config.MaxMarkers <= 0 {
+		config.MaxMarkers = defaultMaxMarkers
+	}
+
+	s := &Store{
+		config:      config,
+		now:         time.Now,
+		markers:     make(map[string][]types.Annotation),
+		subscribers: make(map[chan []types.Annotation]bool),
+	}
+
+	for _, opt := range opts {
+		opt(s)
+	}
+
+	return s
+}
+
+type StoreOpt func(*Store)
+
+// WithClock replaces the clock markers without a time are stamped with
+func WithClock(now func() time.Time) StoreOpt {
+	return func(s *Store) {
+		s.now = now
+	}
+}
+
+// Decode reads the CloudEvent in r, in structured mode when its content type is
+// application/cloudevents+json and in binary mode, from ce- headers, otherwise
+func Decode(r *http.Request) (types.Annotation, error) {
+	var e event
+	if strings.HasPrefix(r.Header.Get("Content-Type"), structuredContentType) {
+		if err := json.NewDecoder(r.Body).Decode(&e); err != nil {
+			return types.Annotation{}, fmt.Errorf("%w: %v", ErrInvalidEvent, err)
+		}
+		return fromEvent(e)
+	}
+
+	e = event{
+		SpecVersion:     r.Header.Get("ce-specversion"),
+		ID:              r.Header.Get("ce-id"),
+		Source:          r.Header.Get("ce-source"),
+		Type:            r.Header.Get("ce-type"),
+		Subject:         r.Header.Get("ce-subject"),
+		DataContentType: r.Header.Get("Content-Type"),
+	}
+	if value := r.Header.Get("ce-time"); value != "" {
+		at, err := time.Parse(time.RFC3339, value)
+		if err != nil {
+			return types.Annotation{}, fmt.Errorf("%w: time: %v", ErrInvalidEvent, err)
+		}
+		e.Time = &at
+	}
+	var body bytes.Buffer
+	if _, err := body.ReadFrom(r.Body); err != nil {
+		return types.Annotation{}, fmt.Errorf("%w: %v", ErrInvalidEvent, err)
+	}
+	data, err := binaryData(body.Bytes(), e.DataContentType)
+	if err != nil {
+		return types.Annotation{}, err
+	}
+	e.Data = data
+	return fromEvent(e)
+}
+
+// binaryData is the body of a binary mode event as JSON: as is when it is JSON, as a string otherwise
+func binaryData(body []byte, contentType string) (json.RawMessage, error) {
+	if len(body) == 0 {
+		return nil, nil
+	}
+	if strings.HasPrefix(contentType, "application/json") {
+		if !json.Valid(body) {
+			return nil, fmt.Errorf("%w: data is not valid JSON", ErrInvalidEvent)
+		}
+		return body, nil
+	}
+	return json.Marshal(string(body))
+}
+
+// fromEvent validates e and reads the node its subject names
+func fromEvent(e event) (types.Annotation, error) {
+	if e.SpecVersion != specVersion {
+		return types.Annotation{}, fmt.Errorf("%w: unsupported specversion %q", ErrInvalidEvent, e.SpecVersion)
+	}
+	if e.ID == "" || e.Source == "" || e.Type == "" {
+		return types.Annotation{}, fmt.Errorf("%w: id, source and type are required", ErrInvalidEvent)
+	}
+
+	parts := strings.Split(e.Subject, "/")
+	result := types.Annotation{
+		Kind:   types.ResourceKind(parts[0]),
+		Marker: types.Marker{ID: e.ID, Source: e.Source, Type: e.Type, Data: e.Data},
+	}
+	switch len(parts) {
+	case 2:
+		result.Name = parts[1]
+	case 3:
+		result.Namespace = parts[1]
+		result.Name = parts[2]
+	}
+	if result.Kind == "" || result.Name == "" {
+		return types.Annotation{}, fmt.Errorf("%w: subject %q is not kind/namespace/name or kind/name",
+			ErrInvalidEvent, e.Subject)
+	}
+	if e.Time != nil {
+		result.Marker.Time = e.Time.UTC()
+	}
+	return result, nil
+}
+
+// Add attaches the marker of annotation to its node, stamping it with the current time when the event had
+// none, and returns it as kept. The oldest marker is dropped once config.MaxMarkers are kept. A marker with
+// the id and source of one already kept is a redelivery and is ignored.
+func (s *Store) Add(annotation types.Annotation) types.Annotation {
+	if annotation.Marker.Time.IsZero() {
+		annotation.Marker.Time = s.now().UTC()
+	}
+	if !s.add(annotation) {
+		return annotation
+	}
+	s.notifySubscribers([]types.Annotation{annotation})
+	return annotation
+}
+
+func (s *Store) add(annotation types.Annotation) bool {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+
+	key := nodeKey(annotation.Kind, annotation.Namespace, annotation.Name)
+	for _, existing := range s.markers[key] {
+		if existing.Marker.ID == annotation.Marker.ID && existing.Marker.Source == annotation.Marker.Source {
+			return false
+		}
+	}
+	markers := append(s.markers[key], annotation)
+	if len(markers) > s.config.MaxMarkers {
+		markers = markers[len(markers)-s.config.MaxMarkers:]
+	}
+	s.markers[key] = markers
+	return true
+}
+
+// Annotations returns every kept annotation, oldest first
+func (s *Store) Annotations() []types.Annotation {
+	s.mu.RLock()
+	defer s.mu.RUnlock()
+
+	result := make([]types.Annotation, 0)
+	for _, markers := range s.markers {
+		result = append(result, markers...)
+	}
+	sort.SliceStable(result, func(i, j int) bool { return result[i].Marker.Time.Before(result[j].Marker.Time) })
+	return result
+}
+
+// Enrich sets the Markers of every node that has any, in the order they arrived
+func (s *Store) Enrich(nodes []types.HierarchyNode) {
+	s.mu.RLock()
+	defer s.mu.RUnlock()
+
+	hierarchy.Walk(nodes, func(node *types.HierarchyNode) {
+		namespace := ""
+		if node.Namespace != nil {
+			namespace = *node.Namespace
+		}
+		annotations := s.markers[nodeKey(node.Kind, namespace, node.Name)]
+		if len(annotations) == 0 {
+			return
+		}
+		markers := make([]types.Marker, 0, len(annotations))
+		for _, annotation := range annotations {
+			markers = append(markers, annotation.Marker)
+		}
+		node.Markers = markers
+	})
+}
+
+// Subscribe creates a channel that receives every annotation added from now on
+func (s *Store) Subscribe() chan []types.Annotation {
+	s.subMu.Lock()
+	defer s.subMu.Unlock()
+
+	ch := make(chan []types.Annotation, 16)
+	s.subscribers[ch] = true
+	return ch
+}
+
+// Unsubscribe removes a subscription channel
+func (s *Store) Unsubscribe(ch chan []types.Annotation) {
+	s.subMu.Lock()
+	defer s.subMu.Unlock()
+
+	delete(s.subscribers, ch)
+	close(ch)
+}
+
+// notifySubscribers sends annotations to every subscriber without blocking on slow readers
+func (s *Store) notifySubscribers(annotations []types.Annotation) {
+	s.subMu.RLock()
+	defer s.subMu.RUnlock()
+
+	for ch := range s.subscribers {
+		select {
+		case ch <- annotations:
+		default:
+		}
+	}
+}
+
+func nodeKey(kind types.ResourceKind, namespace, name string) string {
+	return strings.ToLower(string(kind)) + "/" + namespace + "/" + name
+}
@@ -0,0 +1,219 @@
+package annotations_test
+
+import (
+	"encoding/json"
+	"errors"
+	"net/http/httptest"
+	"reflect"
+	"strings"
+	"testing"
+	"time"
+
+	"k8s.io/utils/ptr"
+
+	"github.com/kdwils/constellation/internal/annotations"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func TestDecode(t *testing.T) {
+	at := time.Date(2025, 1, 2, 3, 4, 5, 0, time.UTC)
+
+	tests := []struct {
+		name    string
+		headers map[string]string
+		body    string
+		want    types.Annotation
+		wantErr error
+	}{
+		{
+			name:    "structured mode",
+			headers: map[string]string{"Content-Type": "application/cloudevents+json; charset=utf-8"},
+			body: `{"specversion":"1.0","id":"1","source":"ci","type":"deployment.started",` +
+				`"subject":"Service/shop/web","time":"2025-01-02T03:04:05Z","data":{"commit":"abc"}}`,
+			want: types.Annotation{
+				Kind:      types.ResourceKindService,
+				Namespace: "shop",
+				Name:      "web",
+				Marker: types.Marker{
+					ID:     "1",
+					Source: "ci",
+					Type:   "deployment.started",
+					Data:   json.RawMessage(`{"commit":"abc"}`),
+					Time:   at,
+				},
+			},
+		},
+		{
+			name: "binary mode with JSON data",
+			headers: map[string]string{
+				"Content-Type":   "application/json",
+				"ce-specversion": "1.0",
+				"ce-id":          "2",
+				"ce-source":      "pagerduty",
+				"ce-type":        "incident.opened",
+				"ce-subject":     "Node/worker-1",
+				"ce-time":        "2025-01-02T03:04:05Z",
+			},
+			body: `{"severity":"high"}`,
+			want: types.Annotation{
+				Kind: types.ResourceKindNode,
+				Name: "worker-1",
+				Marker: types.Marker{
+					ID:     "2",
+					Source: "pagerduty",
+					Type:   "incident.opened",
+					Data:   json.RawMessage(`{"severity":"high"}`),
+					Time:   at,
+				},
+			},
+		},
+		{
+			name: "binary mode with text data",
+			headers: map[string]string{
+				"Content-Type":   "text/plain",
+				"ce-specversion": "1.0",
+				"ce-id":          "3",
+				"ce-source":      "ci",
+				"ce-type":        "note",
+				"ce-subject":     "Pod/shop/web-0",
+			},
+			body: "restarted by hand",
+			want: types.Annotation{
+				Kind:      types.ResourceKindPod,
+				Namespace: "shop",
+				Name:      "web-0",
+				Marker:    types.Marker{ID: "3", Source: "ci", Type: "note", Data: json.RawMessage(`"restarted by hand"`)},
+			},
+		},
+		{
+			name:    "unsupported specversion",
+			headers: map[string]string{"Content-Type": "application/cloudevents+json"},
+			body:    `{"specversion":"0.3","id":"1","source":"ci","type":"note","subject":"Pod/shop/web-0"}`,
+			wantErr: annotations.ErrInvalidEvent,
+		},
+		{
+			name:    "missing required attributes",
+			headers: map[string]string{"Content-Type": "application/cloudevents+json"},
+			body:    `{"specversion":"1.0","id":"1","subject":"Pod/shop/web-0"}`,
+			wantErr: annotations.ErrInvalidEvent,
+		},
+		{
+			name:    "subject that names no node",
+			headers: map[string]string{"Content-Type": "application/cloudevents+json"},
+			body:    `{"specversion":"1.0","id":"1","source":"ci","type":"note","subject":"shop/web/0/1"}`,
+			wantErr: annotations.ErrInvalidEvent,
+		},
+		{
+			name: "invalid JSON data",
+			headers: map[string]string{
+				"Content-Type":   "application/json",
+				"ce-specversion": "1.0",
+				"ce-id":          "4",
+				"ce-source":      "ci",
+				"ce-type":        "note",
+				"ce-subject":     "Pod/shop/web-0",
+			},
+			body:    "{",
+			wantErr: annotations.ErrInvalidEvent,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			r := httptest.NewRequest("POST", "/annotations", strings.NewReader(tt.body))
+			for key, value := range tt.headers {
+				r.Header.Set(key, value)
+			}
+
+			got, err := annotations.Decode(r)
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("TestDecode() error = %v, want %v", err, tt.wantErr)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestDecode() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestStore(t *testing.T) {
+	now := time.Date(2025, 1, 2, 3, 0, 0, 0, time.UTC)
+	annotation := func(id string, at time.Time) types.Annotation {
+		return types.Annotation{
+			Kind:      types.ResourceKindService,
+			Namespace: "shop",
+			Name:      "web",
+			Marker:    types.Marker{ID: id, Source: "ci", Type: "deployment.started", Time: at},
+		}
+	}
+
+	tests := []struct {
+		name        string
+		maxMarkers  int
+		added       []types.Annotation
+		wantMarkers []types.Marker
+		wantStream  []string
+	}{
+		{
+			name:  "markers are attached to the node their subject names",
+			added: []types.Annotation{annotation("1", now.Add(-time.Minute)), annotation("2", time.Time{})},
+			wantMarkers: []types.Marker{
+				{ID: "1", Source: "ci", Type: "deployment.started", Time: now.Add(-time.Minute)},
+				{ID: "2", Source: "ci", Type: "deployment.started", Time: now},
+			},
+			wantStream: []string{"1", "2"},
+		},
+		{
+			name:       "only the last markers are kept",
+			maxMarkers: 1,
+			added:      []types.Annotation{annotation("1", now), annotation("2", now)},
+			wantMarkers: []types.Marker{
+				{ID: "2", Source: "ci", Type: "deployment.started", Time: now},
+			},
+			wantStream: []string{"1", "2"},
+		},
+		{
+			name:  "redelivered events are ignored",
+			added: []types.Annotation{annotation("1", now), annotation("1", now)},
+			wantMarkers: []types.Marker{
+				{ID: "1", Source: "ci", Type: "deployment.started", Time: now},
+			},
+			wantStream: []string{"1"},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			store := annotations.NewStore(
+				annotations.Config{MaxMarkers: tt.maxMarkers},
+				annotations.WithClock(func() time.Time { return now }),
+			)
+			updates := store.Subscribe()
+			defer store.Unsubscribe(updates)
+
+			for _, a := range tt.added {
+				store.Add(a)
+			}
+
+			nodes := []types.HierarchyNode{
+				{Kind: types.ResourceKindService, Name: "web", Namespace: ptr.To("shop")},
+				{Kind: types.ResourceKindService, Name: "api", Namespace: ptr.To("shop")},
+			}
+			store.Enrich(nodes)
+			if !reflect.DeepEqual(nodes[0].Markers, tt.wantMarkers) {
+				t.Errorf("TestStore() markers = %+v, want %+v", nodes[0].Markers, tt.wantMarkers)
+			}
+			if nodes[1].Markers != nil {
+				t.Errorf("TestStore() unrelated node markers = %+v, want none", nodes[1].Markers)
+			}
+
+			var streamed []string
+			for len(updates) > 0 {
+				for _, a := range <-updates {
+					streamed = append(streamed, a.Marker.ID)
+				}
+			}
+			if !reflect.DeepEqual(streamed, tt.wantStream) {
+				t.Errorf("TestStore() streamed = %v, want %v", streamed, tt.wantStream)
+			}
+		})
+	}
+}
@@ -0,0 +1,233 @@
+// Package anonymize replaces the names, hostnames and IPs in API output with stable pseudonyms, so a
+// topology can be shared, e.g. in a screenshot or a snapshot attached to a bug report, without exposing
+// the cluster's internals. Every occurrence of a value maps to the same pseudonym under one key, so the
+// relationships between resources survive.
+package anonymize
+
+import (
+	"bytes"
+	"crypto/hmac"
+	"crypto/rand"
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"net"
+	"net/netip"
+	"net/url"
+	"strings"
+)
+
+// names are the JSON keys whose string values name something in the cluster. Values holding a
+// namespace/name pair, such as a route's parent Gateways, have each part replaced. IPs under any of them,
+// such as the external_ips of a load balancer that also reports hostnames, are replaced as IPs.
+var names = map[string]bool{
+	"name":                true,
+	"namespace":           true,
+	"service_name":        true,
+	"display_name":        true,
+	"node_name":           true,
+	"owner":               true,
+	"hostname":            true,
+	"hostnames":           true,
+	"dns_names":           true,
+	"gateways":            true,
+	"backend_refs":        true,
+	"external_ips":        true,
+	"controllers":         true,
+	"priority_class_name": true,
+	"image_pull_secrets":  true,
+	"generateName":        true,
+	"nodeName":            true,
+	"serviceAccountName":  true,
+	"subdomain":           true,
+}
+
+// ids are the JSON keys holding node IDs such as Namespace/shop|Service/shop/web, whose kinds are kept
+var ids = map[string]bool{
+	"id":        true,
+	"parent_id": true,
+}
+
+// maps are the JSON keys of objects whose keys are kept and whose values are replaced
+var maps = map[string]bool{
+	"labels":      true,
+	"annotations": true,
+	"selectors":   true,
+	"selector":    true,
+	"matchLabels": true,
+}
+
+// Config holds the key pseudonyms are derived from
+type Config struct {
+	// Key keeps pseudonyms stable across restarts and replicas. A random key is used when empty, so
+	// pseudonyms change on every restart.
+	Key string
+}
+
+// Anonymizer derives pseudonyms from a key
+type Anonymizer struct {
+	key []byte
+}
+
+// New creates an Anonymizer
+func New(config Config) *Anonymizer {
+	key := []byte(config.Key)
+	if len(key) == 0 {
+		key = make([]byte, 32)
+		_, _ = rand.Read(key)
+	}
+	return &Anonymizer{key: key}
+}
+
+// JSON rewrites a JSON document, replacing names, node IDs, URLs, image registries and labels under the
+// keys above and every string that is an IP address
+func (a *Anonymizer) JSON(payload []byte) ([]byte, error) {
+	decoder := json.NewDecoder(bytes.NewReader(payload))
+	decoder.UseNumber()
+	var document any
+	if err := decoder.Decode(&document); err != nil {
+		return nil, err
+	}
+	return json.Marshal(a.value("", document))
+}
+
+func (a *Anonymizer) value(key string, v any) any {
+	switch value := v.(type) {
+	case map[string]any:
+		for k, child := range value {
+			if text, ok := child.(string); ok && maps[key] {
+				value[k] = a.Name(text)
+				continue
+			}
+			value[k] = a.value(k, child)
+		}
+		return value
+	case []any:
+		for i, item := range value {
+			value[i] = a.value(key, item)
+		}
+		return value
+	case string:
+		return a.text(key, value)
+	}
+	return v
+}
+
+func (a *Anonymizer) text(key, s string) string {
+	if s == "" {
+		return s
+	}
+	if addr, err := netip.ParseAddr(s); err == nil {
+		return a.IP(addr).String()
+	}
+	if ids[key] {
+		return a.id(s)
+	}
+	if key == "url" {
+		return a.url(s)
+	}
+	if key == "image" {
+		return a.image(s)
+	}
+	if key == "registry" {
+		return a.registry(s)
+	}
+	if names[key] {
+		return a.path(s)
+	}
+	return s
+}
+
+// id replaces the names of every resource along a node ID, the resources being separated by |
+func (a *Anonymizer) id(s string) string {
+	resources := strings.Split(s, "|")
+	for i, resource := range resources {
+		kind, rest, found := strings.Cut(resource, "/")
+		if !found {
+			continue
+		}
+		resources[i] = kind + "/" + a.path(rest)
+	}
+	return strings.Join(resources, "|")
+}
+
+// path replaces each part of a slash separated name, e.g. shop/web
+func (a *Anonymizer) path(s string) string {
+	parts := strings.Split(s, "/")
+	for i, part := range parts {
+		parts[i] = a.Name(part)
+	}
+	return strings.Join(parts, "/")
+}
+
+// url replaces the host of a URL, keeping its scheme, port and path
+func (a *Anonymizer) url(s string) string {
+	u, err := url.Parse(s)
+	if err != nil || u.Host == "" {
+		return a.Name(s)
+	}
+	u.Host = a.hostPort(u.Hostname(), u.Port())
+	return u.String()
+}
+
+// image replaces the registry host of a container image, e.g. registry.shop.internal:5000/web:1.2,
+// keeping its repository and tag. Images without one, such as nginx:1.27, are kept.
+func (a *Anonymizer) image(s string) string {
+	registry, repository, found := strings.Cut(s, "/")
+	if !found || (!strings.ContainsAny(registry, ".:[") && registry != "localhost") {
+		return s
+	}
+	return a.registry(registry) + "/" + repository
+}
+
+// registry replaces an image registry's host, keeping its port
+func (a *Anonymizer) registry(s string) string {
+	host, port, err := net.SplitHostPort(s)
+	if err != nil {
+		host, port = strings.Trim(s, "[]"), ""
+	}
+	return a.hostPort(host, port)
+}
+
+// hostPort replaces host, joining it with port when there is one. IPv6 addresses are bracketed either
+// way, so they can't be mistaken for a host and port.
+func (a *Anonymizer) hostPort(host, port string) string {
+	replaced := a.Name(host)
+	if addr, err := netip.ParseAddr(host); err == nil {
+		replaced = a.IP(addr).String()
+	}
+	if port != "" {
+		return net.JoinHostPort(replaced, port)
+	}
+	if strings.Contains(replaced, ":") {
+		return "[" + replaced + "]"
+	}
+	return replaced
+}
+
+// Name returns the pseudonym of s, e.g. x1f3a9c0e
+func (a *Anonymizer) Name(s string) string {
+	if s == "" {
+		return s
+	}
+	return "x" + hex.EncodeToString(a.sum(s)[:4])
+}
+
+// IP returns the pseudonym of addr: an address in 10.0.0.0/8 for IPv4 and in fd00::/8 for IPv6, so it
+// still reads as an IP of the same family
+func (a *Anonymizer) IP(addr netip.Addr) netip.Addr {
+	sum := a.sum(addr.String())
+	if addr.Is4() {
+		return netip.AddrFrom4([4]byte{10, sum[0], sum[1], sum[2]})
+	}
+	var ip [16]byte
+	ip[0] = 0xfd
+	copy(ip[1:], sum[:15])
+	return netip.AddrFrom16(ip)
+}
+
+func (a *Anonymizer) sum(s string) []byte {
+	mac := hmac.New(sha256.New, a.key)
+	mac.Write([]byte(s))
+	return mac.Sum(nil)
+}
@@ -0,0 +1,130 @@
+package anonymize_test
+
+import (
+	"net/netip"
+	"strings"
+	"testing"
+
+	"github.com/kdwils/constellation/internal/anonymize"
+)
+
+func TestAnonymizer_JSON(t *testing.T) {
+	a := anonymize.New(anonymize.Config{Key: "test"})
+	name := a.Name
+	ip := func(s string) string { return a.IP(netip.MustParseAddr(s)).String() }
+
+	tests := []struct {
+		name    string
+		payload string
+		want    string
+	}{
+		{
+			name: "names, ids and ips of a node",
+			payload: `{"kind":"Service","name":"web","namespace":"shop","id":"Service/shop/web",` +
+				`"parent_id":"Namespace/shop","cluster_ips":["10.96.0.12"],"service_type":"ClusterIP"}`,
+			want: `{"cluster_ips":["` + ip("10.96.0.12") + `"],"id":"Service/` + name("shop") + "/" + name("web") +
+				`","kind":"Service","name":"` + name("web") + `","namespace":"` + name("shop") +
+				`","parent_id":"Namespace/` + name("shop") + `","service_type":"ClusterIP"}`,
+		},
+		{
+			name:    "ids along a path keep every kind",
+			payload: `{"id":"Namespace/shop|Service/shop/web","parent_id":"Namespace/shop"}`,
+			want: `{"id":"Namespace/` + name("shop") + "|Service/" + name("shop") + "/" + name("web") +
+				`","parent_id":"Namespace/` + name("shop") + `"}`,
+		},
+		{
+			name:    "hostnames and parent gateways",
+			payload: `{"hostnames":["shop.example.com"],"gateways":["infra/public"]}`,
+			want: `{"gateways":["` + name("infra") + "/" + name("public") + `"],"hostnames":["` +
+				name("shop.example.com") + `"]}`,
+		},
+		{
+			name:    "route backend services",
+			payload: `{"kind":"HTTPRoute","backend_refs":["web","api"]}`,
+			want:    `{"backend_refs":["` + name("web") + `","` + name("api") + `"],"kind":"HTTPRoute"}`,
+		},
+		{
+			name:    "load balancer hostnames among external ips",
+			payload: `{"external_ips":["203.0.113.7","a1b2c3.elb.us-east-1.amazonaws.com"]}`,
+			want: `{"external_ips":["` + ip("203.0.113.7") + `","` + name("a1b2c3.elb.us-east-1.amazonaws.com") +
+				`"]}`,
+		},
+		{
+			name:    "label values are replaced and keys kept",
+			payload: `{"labels":{"app":"web","team":"payments"},"count":3}`,
+			want:    `{"count":3,"labels":{"app":"` + name("web") + `","team":"` + name("payments") + `"}}`,
+		},
+		{
+			name:    "url hosts are replaced, keeping scheme, port and path",
+			payload: `[{"service_name":"web","url":"http://web.shop.svc.cluster.local:8080/healthz"}]`,
+			want: `[{"service_name":"` + name("web") + `","url":"http://` + name("web.shop.svc.cluster.local") +
+				`:8080/healthz"}]`,
+		},
+		{
+			name:    "ipv6 url hosts keep their brackets with and without a port",
+			payload: `{"urls":[{"url":"http://[fd00::1]:8080/healthz"},{"url":"http://[fd00::1]/healthz"}]}`,
+			want: `{"urls":[{"url":"http://[` + ip("fd00::1") + `]:8080/healthz"},{"url":"http://[` + ip("fd00::1") +
+				`]/healthz"}]}`,
+		},
+		{
+			name: "image registries are replaced, keeping repository, tag and port",
+			payload: `{"image_pulls":[{"image":"registry.shop.internal:5000/team/web:1.2"},{"image":"nginx:1.27"},` +
+				`{"image":"library/redis"},{"image":"10.0.0.5/web"}],"registry":"registry.shop.internal"}`,
+			want: `{"image_pulls":[{"image":"` + name("registry.shop.internal") + `:5000/team/web:1.2"},` +
+				`{"image":"nginx:1.27"},{"image":"library/redis"},{"image":"` + ip("10.0.0.5") + `/web"}],` +
+				`"registry":"` + name("registry.shop.internal") + `"}`,
+		},
+		{
+			name:    "ipv6 stays ipv6",
+			payload: `{"pod_ips":["fd00:10:244::5"]}`,
+			want:    `{"pod_ips":["` + ip("fd00:10:244::5") + `"]}`,
+		},
+		{
+			name:    "other strings are kept",
+			payload: `{"status":"healthy","phase":"Running","name":""}`,
+			want:    `{"name":"","phase":"Running","status":"healthy"}`,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := a.JSON([]byte(tt.payload))
+			if err != nil {
+				t.Fatalf("TestAnonymizer_JSON() error = %v", err)
+			}
+			if string(got) != tt.want {
+				t.Errorf("TestAnonymizer_JSON() = %s, want %s", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestAnonymizer_Name(t *testing.T) {
+	a := anonymize.New(anonymize.Config{Key: "test"})
+
+	tests := []struct {
+		name  string
+		other *anonymize.Anonymizer
+		same  bool
+	}{
+		{
+			name:  "same key gives the same pseudonym",
+			other: anonymize.New(anonymize.Config{Key: "test"}),
+			same:  true,
+		},
+		{
+			name:  "another key gives another pseudonym",
+			other: anonymize.New(anonymize.Config{Key: "other"}),
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := a.Name("web")
+			if !strings.HasPrefix(got, "x") || len(got) != 9 {
+				t.Errorf("TestAnonymizer_Name() = %q, want x and 8 hex digits", got)
+			}
+			if (got == tt.other.Name("web")) != tt.same {
+				t.Errorf("TestAnonymizer_Name() %q vs %q, want same = %v", got, tt.other.Name("web"), tt.same)
+			}
+		})
+	}
+}
@@ -0,0 +1,187 @@
+// Package apiprobe measures list and watch latency against the apiserver per kind, so a slow dashboard
+// can be told apart from a slow apiserver. Probes bypass the informer cache.
+package apiprobe
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"net/http"
+	"sync"
+	"time"
+
+	"github.com/prometheus/client_golang/prometheus"
+	corev1 "k8s.io/api/core/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	"sigs.k8s.io/controller-runtime/pkg/log"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const (
+	defaultInterval  = 30 * time.Second
+	defaultTimeout   = 10 * time.Second
+	defaultThreshold = 5 * time.Second
+)
+
+// probes are the kinds measured, in the order they are reported
+var probes = []struct {
+	kind types.ResourceKind
+	list func() client.ObjectList
+}{
+	{kind: types.ResourceKindNamespace, list: func() client.ObjectList { return &corev1.NamespaceList{} }},
+	{kind: types.ResourceKindService, list: func() client.ObjectList { return &corev1.ServiceList{} }},
+	{kind: types.ResourceKindPod, list: func() client.ObjectList { return &corev1.PodList{} }},
+}
+
+// Config controls how often the apiserver is probed and when it counts as slow
+type Config struct {
+	// Interval between probes, defaulting to 30s
+	Interval time.Duration
+	// Timeout for each list and watch, defaulting to 10s
+	Timeout time.Duration
+	// Threshold is the latency above which the apiserver is reported not ready, defaulting to 5s
+	Threshold time.Duration
+}
+
+// Prober probes the apiserver on an interval and keeps the latest results. It is a prometheus.Collector
+// exporting every measurement as constellation_apiserver_request_duration_seconds.
+type Prober struct {
+	client  client.WithWatch
+	config  Config
+	now     func() time.Time
+	latency *prometheus.HistogramVec
+
+	mu     sync.RWMutex
+	status types.APIServerStatus
+}
+
+// NewProber creates a Prober. c should read from the apiserver rather than the cache.
+func NewProber(c client.WithWatch, config Config, opts ...ProberOpt) *Prober {
+	if config.Interval <= 0 {
+		config.Interval = defaultInterval
+	}
+	if config.Timeout <= 0 {
+		config.Timeout = defaultTimeout
+	}
+	if config.Threshold <= 0 {
+		config.Threshold = defaultThreshold
+	}
+
+	p := &Prober{
+		client: c,
+		config: config,
+		now:    time.Now,
+		latency: prometheus.NewHistogramVec(prometheus.HistogramOpts{
+			Name:    "constellation_apiserver_request_duration_seconds",
+			Help:    "Latency of the list and watch requests constellation sends the apiserver to probe it.",
+			Buckets: prometheus.ExponentialBuckets(0.005, 2, 12),
+		}, []string{"kind", "verb"}),
+	}
+
+	for _, opt := range opts {
+		opt(p)
+	}
+
+	return p
+}
+
+type ProberOpt func(*Prober)
+
+// WithClock replaces the clock latencies are measured with
+func WithClock(now func() time.Time) ProberOpt {
+	return func(p *Prober) {
+		p.now = now
+	}
+}
+
+// Start probes immediately and then on the configured interval until ctx is cancelled
+func (p *Prober) Start(ctx context.Context) {
+	logger := log.FromContext(ctx).WithName("apiprobe")
+	ticker := time.NewTicker(p.config.Interval)
+	defer ticker.Stop()
+
+	for {
+		if status := p.Probe(ctx); !status.Ready {
+			logger.Info("apiserver is slow or failing", "probes", status.Probes)
+		}
+		select {
+		case <-ctx.Done():
+			return
+		case <-ticker.C:
+		}
+	}
+}
+
+// Probe lists and watches every kind once and stores the results
+func (p *Prober) Probe(ctx context.Context) types.APIServerStatus {
+	status := types.APIServerStatus{Ready: true, Time: p.now().UTC()}
+	for _, probe := range probes {
+		result := p.probe(ctx, probe.kind, probe.list)
+		if result.Error != "" || result.ListLatency > p.config.Threshold || result.WatchLatency > p.config.Threshold {
+			status.Ready = false
+		}
+		status.Probes = append(status.Probes, result)
+	}
+
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	p.status = status
+	return status
+}
+
+// probe lists a single object of a kind, then opens a watch from the list's resource version so the
+// apiserver does not replay every object, and closes it as soon as it is established
+func (p *Prober) probe(ctx context.Context, kind types.ResourceKind, newList func() client.ObjectList) types.APIProbe {
+	result := types.APIProbe{Kind: kind}
+	ctx, cancel := context.WithTimeout(ctx, p.config.Timeout)
+	defer cancel()
+
+	list := newList()
+	start := p.now()
+	if err := p.client.List(ctx, list, client.Limit(1)); err != nil {
+		result.Error = fmt.Sprintf("list: %v", err)
+		return result
+	}
+	result.ListLatency = p.now().Sub(start)
+	p.latency.WithLabelValues(string(kind), "list").Observe(result.ListLatency.Seconds())
+
+	start = p.now()
+	watcher, err := p.client.Watch(ctx, newList(), &client.ListOptions{
+		Raw: &metav1.ListOptions{ResourceVersion: list.GetResourceVersion()},
+	})
+	if err != nil {
+		result.Error = fmt.Sprintf("watch: %v", err)
+		return result
+	}
+	watcher.Stop()
+	result.WatchLatency = p.now().Sub(start)
+	p.latency.WithLabelValues(string(kind), "watch").Observe(result.WatchLatency.Seconds())
+	return result
+}
+
+// Status returns the results of the latest probe
+func (p *Prober) Status() types.APIServerStatus {
+	p.mu.RLock()
+	defer p.mu.RUnlock()
+	return p.status
+}
+
+// Check is a readiness check failing while the apiserver is slow or failing, for the manager's /readyz
+func (p *Prober) Check(_ *http.Request) error {
+	if !p.Status().Ready {
+		return errors.New("apiserver is slow or failing, see /readyz on the dashboard for latencies")
+	}
+	return nil
+}
+
+// Describe sends the latency histogram's descriptor
+func (p *Prober) Describe(ch chan<- *prometheus.Desc) {
+	p.latency.Describe(ch)
+}
+
+// Collect sends the latency histogram
+func (p *Prober) Collect(ch chan<- prometheus.Metric) {
+	p.latency.Collect(ch)
+}
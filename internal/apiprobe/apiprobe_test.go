@@ -0,0 +1,90 @@
+package apiprobe_test
+
+import (
+	"context"
+	"errors"
+	"reflect"
+	"testing"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	"sigs.k8s.io/controller-runtime/pkg/client/fake"
+	"sigs.k8s.io/controller-runtime/pkg/client/interceptor"
+
+	"github.com/kdwils/constellation/internal/apiprobe"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func TestProber_Probe(t *testing.T) {
+	start := time.Date(2025, 1, 2, 3, 4, 5, 0, time.UTC)
+	failPods := interceptor.Funcs{
+		List: func(ctx context.Context, c client.WithWatch, list client.ObjectList, opts ...client.ListOption) error {
+			if _, ok := list.(*corev1.PodList); ok {
+				return errors.New("etcdserver: request timed out")
+			}
+			return c.List(ctx, list, opts...)
+		},
+	}
+
+	tests := []struct {
+		name         string
+		threshold    time.Duration
+		interceptors interceptor.Funcs
+		want         types.APIServerStatus
+	}{
+		{
+			name:      "fast apiserver is ready",
+			threshold: time.Second,
+			want: types.APIServerStatus{Ready: true, Time: start, Probes: []types.APIProbe{
+				{Kind: types.ResourceKindNamespace, ListLatency: 100 * time.Millisecond, WatchLatency: 100 * time.Millisecond},
+				{Kind: types.ResourceKindService, ListLatency: 100 * time.Millisecond, WatchLatency: 100 * time.Millisecond},
+				{Kind: types.ResourceKindPod, ListLatency: 100 * time.Millisecond, WatchLatency: 100 * time.Millisecond},
+			}},
+		},
+		{
+			name:      "latency above the threshold is not ready",
+			threshold: 50 * time.Millisecond,
+			want: types.APIServerStatus{Time: start, Probes: []types.APIProbe{
+				{Kind: types.ResourceKindNamespace, ListLatency: 100 * time.Millisecond, WatchLatency: 100 * time.Millisecond},
+				{Kind: types.ResourceKindService, ListLatency: 100 * time.Millisecond, WatchLatency: 100 * time.Millisecond},
+				{Kind: types.ResourceKindPod, ListLatency: 100 * time.Millisecond, WatchLatency: 100 * time.Millisecond},
+			}},
+		},
+		{
+			name:         "failed list is not ready",
+			threshold:    time.Second,
+			interceptors: failPods,
+			want: types.APIServerStatus{Time: start, Probes: []types.APIProbe{
+				{Kind: types.ResourceKindNamespace, ListLatency: 100 * time.Millisecond, WatchLatency: 100 * time.Millisecond},
+				{Kind: types.ResourceKindService, ListLatency: 100 * time.Millisecond, WatchLatency: 100 * time.Millisecond},
+				{Kind: types.ResourceKindPod, Error: "list: etcdserver: request timed out"},
+			}},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			calls := 0
+			clock := func() time.Time {
+				defer func() { calls++ }()
+				if calls == 0 {
+					return start
+				}
+				return start.Add(time.Duration(calls) * 100 * time.Millisecond)
+			}
+			c := fake.NewClientBuilder().WithInterceptorFuncs(tt.interceptors).Build()
+			prober := apiprobe.NewProber(c, apiprobe.Config{Threshold: tt.threshold}, apiprobe.WithClock(clock))
+
+			got := prober.Probe(context.Background())
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestProber_Probe() = %+v, want %+v", got, tt.want)
+			}
+			if status := prober.Status(); !reflect.DeepEqual(status, tt.want) {
+				t.Errorf("TestProber_Probe() Status() = %+v, want %+v", status, tt.want)
+			}
+			if err := prober.Check(nil); (err == nil) != tt.want.Ready {
+				t.Errorf("TestProber_Probe() Check() = %v, want ready %v", err, tt.want.Ready)
+			}
+		})
+	}
+}
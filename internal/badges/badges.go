@@ -0,0 +1,74 @@
+// Package badges tags hierarchy nodes with operator defined badges, e.g. "stateful" for every node labelled
+// tier=db, so the dashboard can render them as chips without knowing each cluster's labelling scheme.
+package badges
+
+import (
+	"fmt"
+
+	"k8s.io/apimachinery/pkg/labels"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// Rule attaches Badge to every node whose labels match Selector
+type Rule struct {
+	// Selector is a label selector, e.g. tier=db or istio-injection in (enabled)
+	Selector string
+	Badge    string
+}
+
+// Config holds the badge rules, evaluated in order
+type Config struct {
+	Rules []Rule
+}
+
+type rule struct {
+	selector labels.Selector
+	badge    string
+}
+
+// Rules evaluates badge rules against the hierarchy. It is an Enricher.
+type Rules struct {
+	rules []rule
+}
+
+// New parses the selector of every rule in config
+func New(config Config) (*Rules, error) {
+	r := &Rules{rules: make([]rule, 0, len(config.Rules))}
+	for _, configured := range config.Rules {
+		if configured.Badge == "" {
+			return nil, fmt.Errorf("badge rule %q has no badge", configured.Selector)
+		}
+		selector, err := labels.Parse(configured.Selector)
+		if err != nil {
+			return nil, fmt.Errorf("badge %q has an invalid selector: %w", configured.Badge, err)
+		}
+		if selector.Empty() {
+			return nil, fmt.Errorf("badge %q has an empty selector", configured.Badge)
+		}
+		r.rules = append(r.rules, rule{selector: selector, badge: configured.Badge})
+	}
+	return r, nil
+}
+
+// Enrich sets the Badges of every node whose labels match a rule, in rule order and without duplicates
+func (r *Rules) Enrich(nodes []types.HierarchyNode) {
+	hierarchy.Walk(nodes, func(node *types.HierarchyNode) {
+		node.Badges = r.Badges(node.Labels)
+	})
+}
+
+// Badges returns the badges of the rules matching set, nil when none match
+func (r *Rules) Badges(set map[string]string) []string {
+	var result []string
+	seen := make(map[string]bool)
+	for _, rule := range r.rules {
+		if seen[rule.badge] || !rule.selector.Matches(labels.Set(set)) {
+			continue
+		}
+		seen[rule.badge] = true
+		result = append(result, rule.badge)
+	}
+	return result
+}
@@ -0,0 +1,97 @@
+package badges_test
+
+import (
+	"reflect"
+	"testing"
+
+	"github.com/kdwils/constellation/internal/badges"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func TestNew(t *testing.T) {
+	tests := []struct {
+		name    string
+		config  badges.Config
+		wantErr string
+	}{
+		{
+			name:   "valid rules",
+			config: badges.Config{Rules: []badges.Rule{{Selector: "tier=db", Badge: "stateful"}}},
+		},
+		{
+			name:    "rule without a badge",
+			config:  badges.Config{Rules: []badges.Rule{{Selector: "tier=db"}}},
+			wantErr: `badge rule "tier=db" has no badge`,
+		},
+		{
+			name:    "empty selector would match every node",
+			config:  badges.Config{Rules: []badges.Rule{{Badge: "everything"}}},
+			wantErr: `badge "everything" has an empty selector`,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			_, err := badges.New(tt.config)
+			got := ""
+			if err != nil {
+				got = err.Error()
+			}
+			if got != tt.wantErr {
+				t.Errorf("TestNew() error = %q, want %q", got, tt.wantErr)
+			}
+		})
+	}
+}
+
+func TestRules_Enrich(t *testing.T) {
+	rules, err := badges.New(badges.Config{Rules: []badges.Rule{
+		{Selector: "tier=db", Badge: "stateful"},
+		{Selector: "istio-injection=enabled", Badge: "mesh"},
+		{Selector: "app in (postgres,mysql)", Badge: "stateful"},
+		{Selector: "tier=db,team=payments", Badge: "pci"},
+	}})
+	if err != nil {
+		t.Fatalf("TestRules_Enrich() error = %v", err)
+	}
+
+	tests := []struct {
+		name   string
+		labels map[string]string
+		want   []string
+	}{
+		{
+			name:   "namespace label",
+			labels: map[string]string{"istio-injection": "enabled"},
+			want:   []string{"mesh"},
+		},
+		{
+			name:   "badges follow rule order without duplicates",
+			labels: map[string]string{"tier": "db", "app": "postgres", "team": "payments"},
+			want:   []string{"stateful", "pci"},
+		},
+		{
+			name:   "no rule matches",
+			labels: map[string]string{"tier": "web"},
+		},
+		{
+			name: "unlabelled node",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			nodes := []types.HierarchyNode{{
+				Kind:      types.ResourceKindNamespace,
+				Name:      "shop",
+				Relatives: []types.HierarchyNode{{Kind: types.ResourceKindService, Name: "web", Labels: tt.labels}},
+			}}
+			rules.Enrich(nodes)
+
+			if nodes[0].Badges != nil {
+				t.Errorf("TestRules_Enrich() namespace badges = %v, want none", nodes[0].Badges)
+			}
+			if got := nodes[0].Relatives[0].Badges; !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestRules_Enrich() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,419 @@
+// Package broadcast relays health updates between replicas through a pub/sub backend, so every replica
+// behind a load balancer streams the same updates to its WebSocket clients whichever one runs the watchers.
+package broadcast
+
+import (
+	"bufio"
+	"context"
+	"crypto/tls"
+	"crypto/x509"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"net"
+	"net/url"
+	"os"
+	"strings"
+	"sync"
+	"time"
+
+	"sigs.k8s.io/controller-runtime/pkg/log"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const (
+	defaultChannel = "constellation.health"
+	resendInterval = 30 * time.Second
+	retryInterval  = 5 * time.Second
+
+	// DefaultMaxPayload is the largest message published to or read from a backend when Config.MaxPayload
+	// is unset, NATS's default max_payload
+	DefaultMaxPayload = 1 << 20
+	// DefaultWriteTimeout bounds writes to a backend when Config.WriteTimeout is unset
+	DefaultWriteTimeout = 10 * time.Second
+
+	maxLineLength  = 64 << 10
+	maxArrayLength = 16
+)
+
+// Config selects the backend health is relayed through
+type Config struct {
+	// URL of the backend, as redis://[user:password@]host:port or nats://[user:password@]host:port.
+	// The rediss and tls schemes connect to Redis and NATS over TLS.
+	URL string
+	// Channel health is published on, defaulting to constellation.health
+	Channel string
+	// PasswordFile holds the password to authenticate with in place of the one in URL, keeping it out of
+	// the process arguments
+	PasswordFile string
+	// CAFile verifies the server's certificate in place of the system roots
+	CAFile string
+	// CertFile and KeyFile are the client certificate presented to servers requiring one
+	CertFile string
+	KeyFile  string
+	// MaxPayload is the largest message published to or accepted from the backend in bytes, defaulting
+	// to 1MiB
+	MaxPayload int
+	// WriteTimeout bounds every write to the backend, defaulting to 10s
+	WriteTimeout time.Duration
+}
+
+// Backend publishes payloads to every subscriber of a channel, on any replica
+type Backend interface {
+	Publish(ctx context.Context, channel string, payload []byte) error
+	// Subscribe calls deliver with every payload published on channel until ctx is done or the
+	// connection fails
+	Subscribe(ctx context.Context, channel string, deliver func(payload []byte)) error
+}
+
+// New creates the backend for config's URL, picked by its scheme
+func New(config Config) (Backend, error) {
+	parsed, err := url.Parse(config.URL)
+	if err != nil {
+		return nil, fmt.Errorf("invalid broadcast url: %w", err)
+	}
+	if parsed.Host == "" {
+		return nil, fmt.Errorf("invalid broadcast url %q: missing host", config.URL)
+	}
+	if config.PasswordFile != "" {
+		password, err := os.ReadFile(config.PasswordFile)
+		if err != nil {
+			return nil, fmt.Errorf("read broadcast password: %w", err)
+		}
+		parsed.User = url.UserPassword(parsed.User.Username(), strings.TrimSpace(string(password)))
+	}
+
+	opts := []Option{WithMaxPayload(config.MaxPayload), WithWriteTimeout(config.WriteTimeout)}
+	tlsConfig, err := loadTLS(config)
+	if err != nil {
+		return nil, err
+	}
+	if tlsConfig != nil {
+		opts = append(opts, WithTLS(tlsConfig))
+	}
+
+	switch parsed.Scheme {
+	case "redis", "rediss":
+		return NewRedis(parsed, opts...), nil
+	case "nats", "tls":
+		return NewNATS(parsed, opts...), nil
+	}
+	return nil, fmt.Errorf("invalid broadcast url %q: scheme must be redis, rediss, nats or tls", config.URL)
+}
+
+// loadTLS builds the TLS config for config's CA and client certificate files, or nil when none are set
+func loadTLS(config Config) (*tls.Config, error) {
+	if config.CAFile == "" && config.CertFile == "" && config.KeyFile == "" {
+		return nil, nil
+	}
+
+	tlsConfig := &tls.Config{MinVersion: tls.VersionTLS12}
+	if config.CAFile != "" {
+		ca, err := os.ReadFile(config.CAFile)
+		if err != nil {
+			return nil, fmt.Errorf("read broadcast CA: %w", err)
+		}
+		tlsConfig.RootCAs = x509.NewCertPool()
+		if !tlsConfig.RootCAs.AppendCertsFromPEM(ca) {
+			return nil, fmt.Errorf("no certificates in broadcast CA %s", config.CAFile)
+		}
+	}
+	if config.CertFile == "" && config.KeyFile == "" {
+		return tlsConfig, nil
+	}
+
+	certificate, err := tls.LoadX509KeyPair(config.CertFile, config.KeyFile)
+	if err != nil {
+		return nil, fmt.Errorf("load broadcast client certificate: %w", err)
+	}
+	tlsConfig.Certificates = []tls.Certificate{certificate}
+	return tlsConfig, nil
+}
+
+// Option configures how a Redis or NATS backend reaches its server
+type Option func(*connection)
+
+// WithTLS connects over TLS with config
+func WithTLS(config *tls.Config) Option {
+	return func(c *connection) {
+		c.tlsConfig = config
+	}
+}
+
+// WithMaxPayload rejects messages larger than size bytes, published or received, in place of
+// DefaultMaxPayload
+func WithMaxPayload(size int) Option {
+	return func(c *connection) {
+		if size > 0 {
+			c.maxPayload = size
+		}
+	}
+}
+
+// WithWriteTimeout fails writes taking longer than timeout, in place of DefaultWriteTimeout
+func WithWriteTimeout(timeout time.Duration) Option {
+	return func(c *connection) {
+		if timeout > 0 {
+			c.writeTimeout = timeout
+		}
+	}
+}
+
+// connection is what the Redis and NATS backends share about reaching their server
+type connection struct {
+	dialer       net.Dialer
+	tlsConfig    *tls.Config
+	maxPayload   int
+	writeTimeout time.Duration
+}
+
+func newConnection(opts []Option) connection {
+	c := connection{maxPayload: DefaultMaxPayload, writeTimeout: DefaultWriteTimeout}
+	for _, opt := range opts {
+		opt(&c)
+	}
+	return c
+}
+
+// connect dials addr, over TLS when configured
+func (c *connection) connect(ctx context.Context, addr string) (net.Conn, error) {
+	if c.tlsConfig == nil {
+		return c.dialer.DialContext(ctx, "tcp", addr)
+	}
+	dialer := tls.Dialer{NetDialer: &c.dialer, Config: c.tlsConfig}
+	return dialer.DialContext(ctx, "tcp", addr)
+}
+
+// deadline is when a write started now has to finish: after the write timeout, or ctx's deadline if
+// that is sooner
+func (c *connection) deadline(ctx context.Context) time.Time {
+	deadline := time.Now().Add(c.writeTimeout)
+	if ctxDeadline, ok := ctx.Deadline(); ok && ctxDeadline.Before(deadline) {
+		return ctxDeadline
+	}
+	return deadline
+}
+
+// readLine reads a line without its CRLF, failing on lines longer than reader's buffer
+func readLine(reader *bufio.Reader) (string, error) {
+	line, err := reader.ReadSlice('\n')
+	if errors.Is(err, bufio.ErrBufferFull) {
+		return "", fmt.Errorf("line longer than %d bytes", reader.Size())
+	}
+	if err != nil {
+		return "", err
+	}
+	return strings.TrimSuffix(string(line), "\r\n"), nil
+}
+
+// Memory is a Backend within a single process
+type Memory struct {
+	mu          sync.RWMutex
+	next        int
+	subscribers map[string]map[int]func([]byte)
+}
+
+// NewMemory creates an in-process Backend
+func NewMemory() *Memory {
+	return &Memory{subscribers: make(map[string]map[int]func([]byte))}
+}
+
+// Publish delivers payload to the current subscribers of channel
+func (m *Memory) Publish(ctx context.Context, channel string, payload []byte) error {
+	m.mu.RLock()
+	defer m.mu.RUnlock()
+	for _, deliver := range m.subscribers[channel] {
+		deliver(payload)
+	}
+	return nil
+}
+
+// Subscribe calls deliver with every payload published on channel until ctx is done
+func (m *Memory) Subscribe(ctx context.Context, channel string, deliver func([]byte)) error {
+	m.mu.Lock()
+	id := m.next
+	m.next++
+	if m.subscribers[channel] == nil {
+		m.subscribers[channel] = make(map[int]func([]byte))
+	}
+	m.subscribers[channel][id] = deliver
+	m.mu.Unlock()
+
+	<-ctx.Done()
+
+	m.mu.Lock()
+	defer m.mu.Unlock()
+	delete(m.subscribers[channel], id)
+	return ctx.Err()
+}
+
+// Source is the local health checker whose results are published
+type Source interface {
+	GetAllHealthData() []*types.ServiceHealthInfo
+	Subscribe() chan []*types.ServiceHealthInfo
+	Unsubscribe(chan []*types.ServiceHealthInfo)
+}
+
+// Health is a health data provider serving what is published on the backend rather than the local
+// checker, which only has results on the replica running the watchers. Until the backend delivers and
+// while its subscription is down, it serves the local checker's results instead.
+type Health struct {
+	source  Source
+	backend Backend
+	channel string
+
+	mu     sync.RWMutex
+	health []*types.ServiceHealthInfo
+
+	subMu       sync.RWMutex
+	subscribers map[chan []*types.ServiceHealthInfo]bool
+}
+
+// NewHealth creates a Health relaying source's results through backend on channel
+func NewHealth(source Source, backend Backend, channel string) *Health {
+	if channel == "" {
+		channel = defaultChannel
+	}
+	return &Health{
+		source:      source,
+		backend:     backend,
+		channel:     channel,
+		subscribers: make(map[chan []*types.ServiceHealthInfo]bool),
+	}
+}
+
+// Start delivers the health published on the backend to subscribers and, once elected is closed,
+// publishes the local checker's results, until ctx is done. The full set is published again every
+// 30s so replicas started since catch up without waiting for a change.
+func (h *Health) Start(ctx context.Context, elected <-chan struct{}) {
+	go h.publish(ctx, elected)
+	h.receive(ctx)
+}
+
+func (h *Health) publish(ctx context.Context, elected <-chan struct{}) {
+	select {
+	case <-ctx.Done():
+		return
+	case <-elected:
+	}
+
+	updates := h.source.Subscribe()
+	defer h.source.Unsubscribe(updates)
+	ticker := time.NewTicker(resendInterval)
+	defer ticker.Stop()
+
+	h.send(ctx, h.source.GetAllHealthData())
+	for {
+		select {
+		case <-ctx.Done():
+			return
+		case health := <-updates:
+			h.send(ctx, health)
+			if !h.received() {
+				h.notifySubscribers()
+			}
+		case <-ticker.C:
+			h.send(ctx, h.source.GetAllHealthData())
+		}
+	}
+}
+
+func (h *Health) send(ctx context.Context, health []*types.ServiceHealthInfo) {
+	logger := log.FromContext(ctx).WithName("broadcast")
+	payload, err := json.Marshal(health)
+	if err != nil {
+		logger.Error(err, "failed to encode health")
+		return
+	}
+	if err := h.backend.Publish(ctx, h.channel, payload); err != nil {
+		logger.Error(err, "failed to publish health", "channel", h.channel)
+	}
+}
+
+// receive subscribes to the backend, resubscribing after retryInterval when the connection fails
+func (h *Health) receive(ctx context.Context) {
+	logger := log.FromContext(ctx).WithName("broadcast")
+	for {
+		err := h.backend.Subscribe(ctx, h.channel, func(payload []byte) {
+			var health []*types.ServiceHealthInfo
+			if err := json.Unmarshal(payload, &health); err != nil {
+				logger.Error(err, "ignoring undecodable health", "channel", h.channel)
+				return
+			}
+			h.update(health)
+		})
+		if ctx.Err() != nil {
+			return
+		}
+		logger.Error(err, "lost the broadcast subscription, resubscribing", "channel", h.channel)
+		h.update(nil)
+
+		select {
+		case <-ctx.Done():
+			return
+		case <-time.After(retryInterval):
+		}
+	}
+}
+
+func (h *Health) update(health []*types.ServiceHealthInfo) {
+	h.mu.Lock()
+	h.health = health
+	h.mu.Unlock()
+	h.notifySubscribers()
+}
+
+// received reports whether the health served came from the backend
+func (h *Health) received() bool {
+	h.mu.RLock()
+	defer h.mu.RUnlock()
+	return h.health != nil
+}
+
+// GetAllHealthData returns the health last published on the backend, or the local checker's while
+// there is none
+func (h *Health) GetAllHealthData() []*types.ServiceHealthInfo {
+	h.mu.RLock()
+	health := h.health
+	h.mu.RUnlock()
+	if health != nil {
+		return health
+	}
+	if local := h.source.GetAllHealthData(); local != nil {
+		return local
+	}
+	return []*types.ServiceHealthInfo{}
+}
+
+// Subscribe creates a new subscription channel for health data updates
+func (h *Health) Subscribe() chan []*types.ServiceHealthInfo {
+	h.subMu.Lock()
+	defer h.subMu.Unlock()
+
+	ch := make(chan []*types.ServiceHealthInfo, 1)
+	h.subscribers[ch] = true
+	return ch
+}
+
+// Unsubscribe removes a subscription channel
+func (h *Health) Unsubscribe(ch chan []*types.ServiceHealthInfo) {
+	h.subMu.Lock()
+	defer h.subMu.Unlock()
+
+	delete(h.subscribers, ch)
+	close(ch)
+}
+
+func (h *Health) notifySubscribers() {
+	h.subMu.RLock()
+	defer h.subMu.RUnlock()
+
+	data := h.GetAllHealthData()
+	for ch := range h.subscribers {
+		select {
+		case ch <- data:
+		default:
+		}
+	}
+}
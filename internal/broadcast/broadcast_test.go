@@ -0,0 +1,307 @@
+package broadcast_test
+
+import (
+	"bufio"
+	"context"
+	"crypto/ecdsa"
+	"crypto/elliptic"
+	"crypto/rand"
+	"crypto/tls"
+	"crypto/x509"
+	"crypto/x509/pkix"
+	"encoding/json"
+	"encoding/pem"
+	"errors"
+	"fmt"
+	"math/big"
+	"net"
+	"os"
+	"path/filepath"
+	"reflect"
+	"testing"
+	"time"
+
+	"github.com/kdwils/constellation/internal/broadcast"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// fakeSource is a local checker whose results never change
+type fakeSource struct {
+	health []*types.ServiceHealthInfo
+}
+
+func (s fakeSource) GetAllHealthData() []*types.ServiceHealthInfo { return s.health }
+
+func (s fakeSource) Subscribe() chan []*types.ServiceHealthInfo {
+	return make(chan []*types.ServiceHealthInfo)
+}
+
+func (s fakeSource) Unsubscribe(chan []*types.ServiceHealthInfo) {}
+
+// fakeBackend records what is published and hands the subscriber's deliver func to the test
+type fakeBackend struct {
+	published  chan []byte
+	subscribed chan func([]byte)
+}
+
+func (b fakeBackend) Publish(ctx context.Context, channel string, payload []byte) error {
+	b.published <- payload
+	return nil
+}
+
+func (b fakeBackend) Subscribe(ctx context.Context, channel string, deliver func([]byte)) error {
+	b.subscribed <- deliver
+	<-ctx.Done()
+	return ctx.Err()
+}
+
+// serve accepts one connection on a loopback listener and runs handle on it, returning the listener's address
+func serve(t *testing.T, handle func(conn net.Conn, reader *bufio.Reader)) string {
+	t.Helper()
+	listener, err := net.Listen("tcp", "127.0.0.1:0")
+	if err != nil {
+		t.Fatalf("listen error = %v", err)
+	}
+	return accept(t, listener, handle)
+}
+
+// selfSigned creates a certificate for 127.0.0.1, returning it and a CA file trusting it
+func selfSigned(t *testing.T) (tls.Certificate, string) {
+	t.Helper()
+	key, err := ecdsa.GenerateKey(elliptic.P256(), rand.Reader)
+	if err != nil {
+		t.Fatalf("selfSigned() key error = %v", err)
+	}
+	template := &x509.Certificate{
+		SerialNumber: big.NewInt(1),
+		Subject:      pkix.Name{CommonName: "broadcast"},
+		NotBefore:    time.Now().Add(-time.Hour),
+		NotAfter:     time.Now().Add(time.Hour),
+		IPAddresses:  []net.IP{net.ParseIP("127.0.0.1")},
+	}
+	der, err := x509.CreateCertificate(rand.Reader, template, template, &key.PublicKey, key)
+	if err != nil {
+		t.Fatalf("selfSigned() create error = %v", err)
+	}
+
+	caFile := filepath.Join(t.TempDir(), "ca.crt")
+	if err := os.WriteFile(caFile, pem.EncodeToMemory(&pem.Block{Type: "CERTIFICATE", Bytes: der}), 0o600); err != nil {
+		t.Fatalf("selfSigned() write error = %v", err)
+	}
+	return tls.Certificate{Certificate: [][]byte{der}, PrivateKey: key}, caFile
+}
+
+// accept runs handle on the first connection listener accepts, returning the listener's address
+func accept(t *testing.T, listener net.Listener, handle func(conn net.Conn, reader *bufio.Reader)) string {
+	t.Helper()
+	t.Cleanup(func() { listener.Close() }) //nolint:errcheck
+
+	go func() {
+		conn, err := listener.Accept()
+		if err != nil {
+			return
+		}
+		defer conn.Close() //nolint:errcheck
+		handle(conn, bufio.NewReader(conn))
+	}()
+	return listener.Addr().String()
+}
+
+func TestNew(t *testing.T) {
+	dir := t.TempDir()
+	notPEM := filepath.Join(dir, "ca.crt")
+	if err := os.WriteFile(notPEM, []byte("not a certificate"), 0o600); err != nil {
+		t.Fatalf("TestNew() write error = %v", err)
+	}
+
+	tests := []struct {
+		name    string
+		config  broadcast.Config
+		want    string
+		wantErr string
+	}{
+		{
+			name:   "redis",
+			config: broadcast.Config{URL: "redis://:secret@redis:6379"},
+			want:   "*broadcast.Redis",
+		},
+		{
+			name:   "redis over tls",
+			config: broadcast.Config{URL: "rediss://redis:6379"},
+			want:   "*broadcast.Redis",
+		},
+		{
+			name:   "nats",
+			config: broadcast.Config{URL: "nats://nats:4222"},
+			want:   "*broadcast.NATS",
+		},
+		{
+			name:   "nats over tls",
+			config: broadcast.Config{URL: "tls://nats:4222"},
+			want:   "*broadcast.NATS",
+		},
+		{
+			name:    "unknown scheme",
+			config:  broadcast.Config{URL: "kafka://kafka:9092"},
+			wantErr: `invalid broadcast url "kafka://kafka:9092": scheme must be redis, rediss, nats or tls`,
+		},
+		{
+			name:    "missing host",
+			config:  broadcast.Config{URL: "redis:6379"},
+			wantErr: `invalid broadcast url "redis:6379": missing host`,
+		},
+		{
+			name:    "missing password file",
+			config:  broadcast.Config{URL: "redis://redis:6379", PasswordFile: filepath.Join(dir, "missing")},
+			wantErr: "read broadcast password: open " + filepath.Join(dir, "missing") + ": no such file or directory",
+		},
+		{
+			name:    "CA without certificates",
+			config:  broadcast.Config{URL: "rediss://redis:6379", CAFile: notPEM},
+			wantErr: "no certificates in broadcast CA " + notPEM,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := broadcast.New(tt.config)
+			if tt.wantErr != "" {
+				if err == nil || err.Error() != tt.wantErr {
+					t.Fatalf("TestNew() error = %v, want %v", err, tt.wantErr)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("TestNew() error = %v", err)
+			}
+			if typ := fmt.Sprintf("%T", got); typ != tt.want {
+				t.Errorf("TestNew() = %s, want %s", typ, tt.want)
+			}
+		})
+	}
+}
+
+func TestHealth(t *testing.T) {
+	local := []*types.ServiceHealthInfo{{ServiceName: "web", Namespace: "default", Status: types.HealthStatusHealthy}}
+	remote := []*types.ServiceHealthInfo{{ServiceName: "api", Namespace: "default", Status: types.HealthStatusUnhealthy}}
+
+	tests := []struct {
+		name    string
+		elected bool
+	}{
+		{name: "the elected replica publishes and receives", elected: true},
+		{name: "other replicas only receive", elected: false},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			backend := fakeBackend{published: make(chan []byte, 1), subscribed: make(chan func([]byte))}
+			health := broadcast.NewHealth(fakeSource{health: local}, backend, "")
+			updates := health.Subscribe()
+			defer health.Unsubscribe(updates)
+
+			elected := make(chan struct{})
+			if tt.elected {
+				close(elected)
+			}
+			ctx, cancel := context.WithCancel(context.Background())
+			defer cancel()
+			go health.Start(ctx, elected)
+
+			deliver := <-backend.subscribed
+			payload, err := json.Marshal(remote)
+			if err != nil {
+				t.Fatalf("TestHealth() marshal error = %v", err)
+			}
+			deliver(payload)
+
+			if got := <-updates; !reflect.DeepEqual(got, remote) {
+				t.Errorf("TestHealth() update = %+v, want %+v", got, remote)
+			}
+			if got := health.GetAllHealthData(); !reflect.DeepEqual(got, remote) {
+				t.Errorf("TestHealth() GetAllHealthData() = %+v, want %+v", got, remote)
+			}
+			if !tt.elected {
+				if len(backend.published) != 0 {
+					t.Errorf("TestHealth() published %d updates, want none", len(backend.published))
+				}
+				return
+			}
+
+			var published []*types.ServiceHealthInfo
+			if err := json.Unmarshal(<-backend.published, &published); err != nil {
+				t.Fatalf("TestHealth() unmarshal error = %v", err)
+			}
+			if !reflect.DeepEqual(published, local) {
+				t.Errorf("TestHealth() published = %+v, want %+v", published, local)
+			}
+		})
+	}
+}
+
+// flakyBackend hands the subscriber's deliver func to the test and fails the subscription once fail is
+// closed. Publishes always fail.
+type flakyBackend struct {
+	subscribed chan func([]byte)
+	fail       chan struct{}
+}
+
+func (b flakyBackend) Publish(ctx context.Context, channel string, payload []byte) error {
+	return errors.New("connection refused")
+}
+
+func (b flakyBackend) Subscribe(ctx context.Context, channel string, deliver func([]byte)) error {
+	b.subscribed <- deliver
+	select {
+	case <-b.fail:
+		return errors.New("connection reset by peer")
+	case <-ctx.Done():
+		return ctx.Err()
+	}
+}
+
+func TestHealth_BackendDown(t *testing.T) {
+	local := []*types.ServiceHealthInfo{{ServiceName: "web", Namespace: "default", Status: types.HealthStatusHealthy}}
+	remote := []*types.ServiceHealthInfo{{ServiceName: "api", Namespace: "default", Status: types.HealthStatusUnhealthy}}
+
+	tests := []struct {
+		name    string
+		deliver bool
+		fail    bool
+		want    []*types.ServiceHealthInfo
+	}{
+		{name: "local health is served before the backend delivers", want: local},
+		{name: "backend health is served once delivered", deliver: true, want: remote},
+		{name: "local health is served again once the subscription fails", deliver: true, fail: true, want: local},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			backend := flakyBackend{subscribed: make(chan func([]byte)), fail: make(chan struct{})}
+			health := broadcast.NewHealth(fakeSource{health: local}, backend, "")
+			updates := health.Subscribe()
+			defer health.Unsubscribe(updates)
+
+			ctx, cancel := context.WithCancel(context.Background())
+			defer cancel()
+			go health.Start(ctx, make(chan struct{}))
+
+			deliver := <-backend.subscribed
+			if tt.deliver {
+				payload, err := json.Marshal(remote)
+				if err != nil {
+					t.Fatalf("TestHealth_BackendDown() marshal error = %v", err)
+				}
+				deliver(payload)
+				<-updates
+			}
+			if tt.fail {
+				close(backend.fail)
+				if got := <-updates; !reflect.DeepEqual(got, local) {
+					t.Errorf("TestHealth_BackendDown() update = %+v, want %+v", got, local)
+				}
+			}
+
+			if got := health.GetAllHealthData(); !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestHealth_BackendDown() GetAllHealthData() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,308 @@
+package broadcast
+
+import (
+	"bufio"
+	"context"
+	"crypto/tls"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"net"
+	"net/url"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+)
+
+// NATS is a Backend speaking the NATS client protocol's PUB and SUB to a NATS server
+type NATS struct {
+	connection
+	addr     string
+	host     string
+	user     string
+	password string
+
+	mu   sync.Mutex
+	conn *natsConn
+}
+
+// natsConn is the connection publishes are sent on. Its reader answers the server's pings and hands the
+// reply to each publish's PING, the -ERR sent before it, or the connection closing, to the publish
+// waiting for it.
+type natsConn struct {
+	net.Conn
+	maxPayload int
+
+	writeMu sync.Mutex
+	replies chan error
+}
+
+// natsInfo is the part of the server's INFO greeting the client acts on
+type natsInfo struct {
+	TLSRequired bool `json:"tls_required"`
+	MaxPayload  int  `json:"max_payload"`
+}
+
+// NewNATS creates a NATS backend for a nats://[user:password@]host:port URL, or tls:// for TLS.
+// Connections are made on first use and upgraded to TLS whenever the server requires it.
+func NewNATS(u *url.URL, opts ...Option) *NATS {
+	n := &NATS{connection: newConnection(opts), addr: u.Host, host: u.Hostname()}
+	if u.Scheme == "tls" && n.tlsConfig == nil {
+		n.tlsConfig = &tls.Config{MinVersion: tls.VersionTLS12}
+	}
+	if u.Port() == "" {
+		n.addr = net.JoinHostPort(u.Hostname(), "4222")
+	}
+	if password, ok := u.User.Password(); ok {
+		n.user = u.User.Username()
+		n.password = password
+	}
+	return n
+}
+
+// Publish sends payload to subject's subscribers over a connection kept open between calls. It follows
+// the PUB with a PING and waits for the server's reply, so a rejected publish, e.g. an authorization or
+// max payload violation, is returned rather than lost as the server closes the connection.
+func (n *NATS) Publish(ctx context.Context, subject string, payload []byte) error {
+	n.mu.Lock()
+	defer n.mu.Unlock()
+
+	if n.conn == nil {
+		conn, reader, info, err := n.dial(ctx)
+		if err != nil {
+			return err
+		}
+		n.conn = &natsConn{Conn: conn, maxPayload: n.maxPayload, replies: make(chan error, 1)}
+		if info.MaxPayload > 0 {
+			n.conn.maxPayload = min(n.maxPayload, info.MaxPayload)
+		}
+		go n.keepAlive(n.conn, reader)
+	}
+	conn := n.conn
+	if len(payload) > conn.maxPayload {
+		return fmt.Errorf("nats publish: payload of %d bytes exceeds the %d byte limit", len(payload), conn.maxPayload)
+	}
+
+	deadline := n.deadline(ctx)
+	message := fmt.Sprintf("PUB %s %d\r\n%s\r\nPING\r\n", subject, len(payload), payload)
+	if err := conn.write(deadline, message); err != nil {
+		n.drop(conn)
+		return fmt.Errorf("nats publish: %w", err)
+	}
+
+	timeout := time.NewTimer(time.Until(deadline))
+	defer timeout.Stop()
+	select {
+	case err := <-conn.replies:
+		if err != nil {
+			n.drop(conn)
+			return fmt.Errorf("nats publish: %w", err)
+		}
+		return nil
+	case <-timeout.C:
+		n.drop(conn)
+		return errors.New("nats publish: timed out waiting for the server to reply")
+	case <-ctx.Done():
+		n.drop(conn)
+		return ctx.Err()
+	}
+}
+
+// drop closes conn and forgets it, so the next publish reconnects
+func (n *NATS) drop(conn *natsConn) {
+	conn.Close() //nolint:errcheck
+	if n.conn == conn {
+		n.conn = nil
+	}
+}
+
+// keepAlive reads the publishing connection until it fails: answering the server's pings, which would
+// otherwise drop it as stale, and handing replies to the waiting publish
+func (n *NATS) keepAlive(conn *natsConn, reader *bufio.Reader) {
+	defer func() {
+		conn.reply(errors.New("connection closed before the server replied"))
+		n.mu.Lock()
+		defer n.mu.Unlock()
+		n.drop(conn)
+	}()
+
+	for {
+		line, err := readLine(reader)
+		if err != nil {
+			return
+		}
+		switch {
+		case line == "PING":
+			if err := conn.write(time.Now().Add(n.writeTimeout), "PONG\r\n"); err != nil {
+				return
+			}
+		case line == "PONG":
+			conn.reply(nil)
+		case strings.HasPrefix(line, "-ERR"):
+			conn.reply(errors.New(line))
+		}
+	}
+}
+
+// write sends data, finishing by deadline
+func (c *natsConn) write(deadline time.Time, data string) error {
+	c.writeMu.Lock()
+	defer c.writeMu.Unlock()
+	c.SetWriteDeadline(deadline) //nolint:errcheck
+	_, err := io.WriteString(c, data)
+	return err
+}
+
+// reply hands err to the waiting publish, keeping an earlier reply over the ones that follow it
+func (c *natsConn) reply(err error) {
+	select {
+	case c.replies <- err:
+	default:
+	}
+}
+
+// Subscribe calls deliver with every message published on subject until ctx is done or the connection
+// fails
+func (n *NATS) Subscribe(ctx context.Context, subject string, deliver func([]byte)) error {
+	conn, reader, _, err := n.dial(ctx)
+	if err != nil {
+		return err
+	}
+	defer conn.Close() //nolint:errcheck
+
+	stop := context.AfterFunc(ctx, func() { conn.Close() }) //nolint:errcheck
+	defer stop()
+
+	conn.SetWriteDeadline(n.deadline(ctx)) //nolint:errcheck
+	if _, err := fmt.Fprintf(conn, "SUB %s 1\r\n", subject); err != nil {
+		return fmt.Errorf("nats subscribe: %w", err)
+	}
+	for {
+		line, err := readLine(reader)
+		if ctx.Err() != nil {
+			return ctx.Err()
+		}
+		if err != nil {
+			return fmt.Errorf("nats subscribe: %w", err)
+		}
+
+		switch {
+		case line == "PING":
+			conn.SetWriteDeadline(n.deadline(ctx)) //nolint:errcheck
+			if _, err := io.WriteString(conn, "PONG\r\n"); err != nil {
+				return fmt.Errorf("nats subscribe: %w", err)
+			}
+		case strings.HasPrefix(line, "-ERR"):
+			return fmt.Errorf("nats subscribe: %s", line)
+		case strings.HasPrefix(line, "MSG "):
+			payload, err := readMessage(reader, line, n.maxPayload)
+			if err != nil {
+				return fmt.Errorf("nats subscribe: %w", err)
+			}
+			deliver(payload)
+		}
+	}
+}
+
+// dial connects and waits for the server to acknowledge CONNECT, so bad credentials fail here rather
+// than on the first publish. It returns the server's INFO along with the connection.
+func (n *NATS) dial(ctx context.Context) (net.Conn, *bufio.Reader, natsInfo, error) {
+	conn, err := n.dialer.DialContext(ctx, "tcp", n.addr)
+	if err != nil {
+		return nil, nil, natsInfo{}, fmt.Errorf("dial nats %s: %w", n.addr, err)
+	}
+	conn.SetDeadline(n.deadline(ctx)) //nolint:errcheck
+	conn, reader, info, err := n.handshake(conn)
+	if err != nil {
+		conn.Close() //nolint:errcheck
+		return nil, nil, natsInfo{}, fmt.Errorf("nats connect: %w", err)
+	}
+	conn.SetDeadline(time.Time{}) //nolint:errcheck
+	return conn, reader, info, nil
+}
+
+// handshake reads the server's INFO, upgrades to TLS when either side asks for it, then sends CONNECT.
+// The connection returned is the one to close, on error too.
+func (n *NATS) handshake(conn net.Conn) (net.Conn, *bufio.Reader, natsInfo, error) {
+	var info natsInfo
+	reader := bufio.NewReaderSize(conn, maxLineLength)
+	greeting, err := readLine(reader)
+	if err != nil {
+		return conn, nil, info, err
+	}
+	encodedInfo, ok := strings.CutPrefix(greeting, "INFO ")
+	if !ok {
+		return conn, nil, info, fmt.Errorf("unexpected greeting %q", greeting)
+	}
+	if err := json.Unmarshal([]byte(encodedInfo), &info); err != nil {
+		return conn, nil, info, fmt.Errorf("invalid greeting %q: %w", greeting, err)
+	}
+
+	if n.tlsConfig != nil || info.TLSRequired {
+		tlsConfig := &tls.Config{MinVersion: tls.VersionTLS12}
+		if n.tlsConfig != nil {
+			tlsConfig = n.tlsConfig.Clone()
+		}
+		if tlsConfig.ServerName == "" {
+			tlsConfig.ServerName = n.host
+		}
+		secure := tls.Client(conn, tlsConfig)
+		if err := secure.Handshake(); err != nil {
+			return conn, nil, info, fmt.Errorf("tls handshake: %w", err)
+		}
+		conn, reader = secure, bufio.NewReaderSize(secure, maxLineLength)
+	}
+
+	options := map[string]any{"verbose": false, "pedantic": false, "name": "constellation", "lang": "go"}
+	if n.password != "" {
+		options["user"] = n.user
+		options["pass"] = n.password
+	}
+	if info.TLSRequired || n.tlsConfig != nil {
+		options["tls_required"] = true
+	}
+	encoded, err := json.Marshal(options)
+	if err != nil {
+		return conn, nil, info, err
+	}
+	if _, err := fmt.Fprintf(conn, "CONNECT %s\r\nPING\r\n", encoded); err != nil {
+		return conn, nil, info, err
+	}
+
+	for {
+		line, err := readLine(reader)
+		if err != nil {
+			return conn, nil, info, err
+		}
+		if line == "PONG" {
+			return conn, reader, info, nil
+		}
+		if strings.HasPrefix(line, "-ERR") {
+			return conn, nil, info, errors.New(line)
+		}
+	}
+}
+
+// readMessage reads the payload of a MSG <subject> <sid> [reply-to] <size> line, rejecting payloads
+// larger than maxPayload
+func readMessage(reader *bufio.Reader, line string, maxPayload int) ([]byte, error) {
+	fields := strings.Fields(line)
+	if len(fields) < 4 {
+		return nil, fmt.Errorf("invalid message %q", line)
+	}
+	size, err := strconv.Atoi(fields[len(fields)-1])
+	if err != nil || size < 0 {
+		return nil, fmt.Errorf("invalid message %q", line)
+	}
+	if size > maxPayload {
+		return nil, fmt.Errorf("message of %d bytes exceeds the %d byte limit", size, maxPayload)
+	}
+
+	payload := make([]byte, size+2)
+	if _, err := io.ReadFull(reader, payload); err != nil {
+		return nil, err
+	}
+	return payload[:size], nil
+}
@@ -0,0 +1,219 @@
+package broadcast_test
+
+import (
+	"bufio"
+	"cmp"
+	"context"
+	"crypto/tls"
+	"errors"
+	"io"
+	"net"
+	"net/url"
+	"testing"
+
+	"github.com/kdwils/constellation/internal/broadcast"
+)
+
+const (
+	natsGreeting = "INFO {\"server_id\":\"test\"}\r\n"
+	natsConnect  = "CONNECT {\"lang\":\"go\",\"name\":\"constellation\",\"pedantic\":false,\"verbose\":false}\r\nPING\r\n"
+)
+
+func TestNATS_Publish(t *testing.T) {
+	publish := "PUB constellation.health 2\r\n[]\r\nPING\r\n"
+	tests := []struct {
+		name      string
+		url       string
+		greeting  string
+		opts      []broadcast.Option
+		exchanges []exchange
+		wantErr   string
+	}{
+		{
+			name: "publish follows the handshake and waits for the pong",
+			url:  "nats://",
+			exchanges: []exchange{
+				{request: natsConnect, reply: "PONG\r\n"},
+				{request: publish, reply: "PONG\r\n"},
+			},
+		},
+		{
+			name: "credentials are sent with connect",
+			url:  "nats://constellation:secret@",
+			exchanges: []exchange{
+				{
+					request: "CONNECT {\"lang\":\"go\",\"name\":\"constellation\",\"pass\":\"secret\",\"pedantic\":false," +
+						"\"user\":\"constellation\",\"verbose\":false}\r\nPING\r\n",
+					reply: "PONG\r\n",
+				},
+				{request: publish, reply: "PONG\r\n"},
+			},
+		},
+		{
+			name:      "rejected connects are returned",
+			url:       "nats://",
+			exchanges: []exchange{{request: natsConnect, reply: "-ERR 'Authorization Violation'\r\n"}},
+			wantErr:   "nats connect: -ERR 'Authorization Violation'",
+		},
+		{
+			name: "rejected publishes are returned",
+			url:  "nats://",
+			exchanges: []exchange{
+				{request: natsConnect, reply: "PONG\r\n"},
+				{request: publish, reply: "-ERR 'Permissions Violation for Publish to \"constellation.health\"'\r\n"},
+			},
+			wantErr: "nats publish: -ERR 'Permissions Violation for Publish to \"constellation.health\"'",
+		},
+		{
+			name: "closed connections are returned",
+			url:  "nats://",
+			exchanges: []exchange{
+				{request: natsConnect, reply: "PONG\r\n"},
+				{request: publish},
+			},
+			wantErr: "nats publish: connection closed before the server replied",
+		},
+		{
+			name:      "payloads over the server's max payload are not sent",
+			url:       "nats://",
+			greeting:  "INFO {\"server_id\":\"test\",\"max_payload\":1}\r\n",
+			exchanges: []exchange{{request: natsConnect, reply: "PONG\r\n"}},
+			wantErr:   "nats publish: payload of 2 bytes exceeds the 1 byte limit",
+		},
+		{
+			name:      "payloads over the max payload are not sent",
+			url:       "nats://",
+			opts:      []broadcast.Option{broadcast.WithMaxPayload(1)},
+			exchanges: []exchange{{request: natsConnect, reply: "PONG\r\n"}},
+			wantErr:   "nats publish: payload of 2 bytes exceeds the 1 byte limit",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			received := make(chan string, 1)
+			addr := serve(t, func(conn net.Conn, reader *bufio.Reader) {
+				io.WriteString(conn, cmp.Or(tt.greeting, natsGreeting)) //nolint:errcheck
+				converse(tt.exchanges, received)(conn, reader)
+			})
+
+			u, err := url.Parse(tt.url + addr)
+			if err != nil {
+				t.Fatalf("TestNATS_Publish() parse error = %v", err)
+			}
+			err = broadcast.NewNATS(u, tt.opts...).Publish(context.Background(), "constellation.health", []byte("[]"))
+			if tt.wantErr != "" {
+				if err == nil || err.Error() != tt.wantErr {
+					t.Fatalf("TestNATS_Publish() error = %v, want %v", err, tt.wantErr)
+				}
+			}
+			if tt.wantErr == "" && err != nil {
+				t.Fatalf("TestNATS_Publish() error = %v", err)
+			}
+
+			var want string
+			for _, ex := range tt.exchanges {
+				want += ex.request
+			}
+			if got := <-received; got != want {
+				t.Errorf("TestNATS_Publish() sent %q, want %q", got, want)
+			}
+		})
+	}
+}
+
+func TestNATS_Subscribe(t *testing.T) {
+	pong := make(chan string, 1)
+	addr := serve(t, func(conn net.Conn, reader *bufio.Reader) {
+		io.WriteString(conn, natsGreeting) //nolint:errcheck
+		for _, ex := range []exchange{
+			{request: natsConnect, reply: "PONG\r\n"},
+			{request: "SUB constellation.health 1\r\n", reply: "PING\r\n"},
+		} {
+			request := make([]byte, len(ex.request))
+			if _, err := io.ReadFull(reader, request); err != nil {
+				return
+			}
+			io.WriteString(conn, ex.reply) //nolint:errcheck
+		}
+
+		reply := make([]byte, len("PONG\r\n"))
+		if _, err := io.ReadFull(reader, reply); err != nil {
+			return
+		}
+		pong <- string(reply)
+		io.WriteString(conn, "MSG constellation.health 1 2\r\n[]\r\n") //nolint:errcheck
+
+		io.ReadAll(reader) //nolint:errcheck
+	})
+
+	u, err := url.Parse("nats://" + addr)
+	if err != nil {
+		t.Fatalf("TestNATS_Subscribe() parse error = %v", err)
+	}
+	ctx, cancel := context.WithCancel(context.Background())
+	defer cancel()
+
+	var got []string
+	err = broadcast.NewNATS(u).Subscribe(ctx, "constellation.health", func(payload []byte) {
+		got = append(got, string(payload))
+		cancel()
+	})
+	if !errors.Is(err, context.Canceled) {
+		t.Errorf("TestNATS_Subscribe() error = %v, want %v", err, context.Canceled)
+	}
+	if len(got) != 1 || got[0] != "[]" {
+		t.Errorf("TestNATS_Subscribe() delivered %q, want %q", got, []string{"[]"})
+	}
+	if reply := <-pong; reply != "PONG\r\n" {
+		t.Errorf("TestNATS_Subscribe() answered ping with %q, want %q", reply, "PONG\r\n")
+	}
+}
+
+func TestNATS_SubscribeMaxPayload(t *testing.T) {
+	addr := serve(t, func(conn net.Conn, reader *bufio.Reader) {
+		io.WriteString(conn, natsGreeting) //nolint:errcheck
+		converse([]exchange{
+			{request: natsConnect, reply: "PONG\r\n"},
+			{request: "SUB constellation.health 1\r\n", reply: "MSG constellation.health 1 5\r\nhello\r\n"},
+		}, make(chan string, 1))(conn, reader)
+		io.ReadAll(reader) //nolint:errcheck
+	})
+
+	u, err := url.Parse("nats://" + addr)
+	if err != nil {
+		t.Fatalf("TestNATS_SubscribeMaxPayload() parse error = %v", err)
+	}
+	nats := broadcast.NewNATS(u, broadcast.WithMaxPayload(4))
+	err = nats.Subscribe(context.Background(), "constellation.health", func([]byte) {
+		t.Error("TestNATS_SubscribeMaxPayload() delivered an oversized message")
+	})
+	want := "nats subscribe: message of 5 bytes exceeds the 4 byte limit"
+	if err == nil || err.Error() != want {
+		t.Errorf("TestNATS_SubscribeMaxPayload() error = %v, want %v", err, want)
+	}
+}
+
+func TestNATS_TLS(t *testing.T) {
+	certificate, caFile := selfSigned(t)
+	connect := "CONNECT {\"lang\":\"go\",\"name\":\"constellation\",\"pedantic\":false,\"tls_required\":true," +
+		"\"verbose\":false}\r\nPING\r\n"
+	publish := "PUB constellation.health 2\r\n[]\r\nPING\r\n"
+	received := make(chan string, 1)
+	addr := serve(t, func(conn net.Conn, reader *bufio.Reader) {
+		io.WriteString(conn, "INFO {\"server_id\":\"test\",\"tls_required\":true}\r\n") //nolint:errcheck
+		secure := tls.Server(conn, &tls.Config{Certificates: []tls.Certificate{certificate}})
+		converse([]exchange{{request: connect, reply: "PONG\r\n"}, {request: publish, reply: "PONG\r\n"}}, received)(
+			secure, bufio.NewReader(secure))
+	})
+
+	backend, err := broadcast.New(broadcast.Config{URL: "nats://" + addr, CAFile: caFile})
+	if err != nil {
+		t.Fatalf("TestNATS_TLS() new error = %v", err)
+	}
+	if err := backend.Publish(context.Background(), "constellation.health", []byte("[]")); err != nil {
+		t.Fatalf("TestNATS_TLS() error = %v", err)
+	}
+	if got := <-received; got != connect+publish {
+		t.Errorf("TestNATS_TLS() sent %q, want %q", got, connect+publish)
+	}
+}
@@ -0,0 +1,207 @@
+package broadcast
+
+import (
+	"bufio"
+	"context"
+	"crypto/tls"
+	"errors"
+	"fmt"
+	"io"
+	"net"
+	"net/url"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+)
+
+// Redis is a Backend speaking RESP to a Redis server's PUBLISH and SUBSCRIBE commands
+type Redis struct {
+	connection
+	addr     string
+	username string
+	password string
+
+	mu     sync.Mutex
+	conn   net.Conn
+	reader *bufio.Reader
+}
+
+// NewRedis creates a Redis backend for a redis://[user:password@]host:port URL, or rediss:// for TLS.
+// Connections are made on first use.
+func NewRedis(u *url.URL, opts ...Option) *Redis {
+	r := &Redis{connection: newConnection(opts), addr: u.Host}
+	if u.Scheme == "rediss" && r.tlsConfig == nil {
+		r.tlsConfig = &tls.Config{MinVersion: tls.VersionTLS12}
+	}
+	if u.Port() == "" {
+		r.addr = net.JoinHostPort(u.Hostname(), "6379")
+	}
+	if password, ok := u.User.Password(); ok {
+		r.username = u.User.Username()
+		r.password = password
+	}
+	return r
+}
+
+// Publish sends payload to channel's subscribers over a connection kept open between calls
+func (r *Redis) Publish(ctx context.Context, channel string, payload []byte) error {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+
+	if r.conn == nil {
+		conn, reader, err := r.dial(ctx)
+		if err != nil {
+			return err
+		}
+		r.conn, r.reader = conn, reader
+	}
+	if len(payload) > r.maxPayload {
+		return fmt.Errorf("redis publish: payload of %d bytes exceeds the %d byte limit", len(payload), r.maxPayload)
+	}
+	r.conn.SetDeadline(r.deadline(ctx)) //nolint:errcheck
+
+	err := writeCommand(r.conn, "PUBLISH", channel, string(payload))
+	if err == nil {
+		_, err = readReply(r.reader, r.maxPayload)
+	}
+	if err != nil {
+		r.conn.Close() //nolint:errcheck
+		r.conn, r.reader = nil, nil
+		return fmt.Errorf("redis publish: %w", err)
+	}
+	return nil
+}
+
+// Subscribe calls deliver with every message published on channel until ctx is done or the
+// connection fails
+func (r *Redis) Subscribe(ctx context.Context, channel string, deliver func([]byte)) error {
+	conn, reader, err := r.dial(ctx)
+	if err != nil {
+		return err
+	}
+	defer conn.Close() //nolint:errcheck
+
+	stop := context.AfterFunc(ctx, func() { conn.Close() }) //nolint:errcheck
+	defer stop()
+
+	conn.SetWriteDeadline(r.deadline(ctx)) //nolint:errcheck
+	if err := writeCommand(conn, "SUBSCRIBE", channel); err != nil {
+		return fmt.Errorf("redis subscribe: %w", err)
+	}
+	for {
+		reply, err := readReply(reader, r.maxPayload)
+		if ctx.Err() != nil {
+			return ctx.Err()
+		}
+		if err != nil {
+			return fmt.Errorf("redis subscribe: %w", err)
+		}
+
+		message, ok := reply.([]any)
+		if !ok || len(message) != 3 {
+			continue
+		}
+		kind, _ := message[0].([]byte)
+		payload, _ := message[2].([]byte)
+		if string(kind) != "message" {
+			continue
+		}
+		deliver(payload)
+	}
+}
+
+func (r *Redis) dial(ctx context.Context) (net.Conn, *bufio.Reader, error) {
+	conn, err := r.connect(ctx, r.addr)
+	if err != nil {
+		return nil, nil, fmt.Errorf("dial redis %s: %w", r.addr, err)
+	}
+	reader := bufio.NewReaderSize(conn, maxLineLength)
+	if r.password == "" {
+		return conn, reader, nil
+	}
+
+	args := []string{"AUTH", r.password}
+	if r.username != "" {
+		args = []string{"AUTH", r.username, r.password}
+	}
+	conn.SetDeadline(r.deadline(ctx)) //nolint:errcheck
+	err = writeCommand(conn, args...)
+	if err == nil {
+		_, err = readReply(reader, r.maxPayload)
+	}
+	if err != nil {
+		conn.Close() //nolint:errcheck
+		return nil, nil, fmt.Errorf("redis auth: %w", err)
+	}
+	conn.SetDeadline(time.Time{}) //nolint:errcheck
+	return conn, reader, nil
+}
+
+// writeCommand writes args as a RESP array of bulk strings
+func writeCommand(w io.Writer, args ...string) error {
+	var b strings.Builder
+	fmt.Fprintf(&b, "*%d\r\n", len(args))
+	for _, arg := range args {
+		fmt.Fprintf(&b, "$%d\r\n%s\r\n", len(arg), arg)
+	}
+	_, err := io.WriteString(w, b.String())
+	return err
+}
+
+// readReply reads one RESP value: a string for simple strings, int64 for integers, []byte or nil for
+// bulk strings and []any for arrays. Error replies are returned as errors, as are bulk strings longer
+// than maxPayload.
+func readReply(reader *bufio.Reader, maxPayload int) (any, error) {
+	line, err := readLine(reader)
+	if err != nil {
+		return nil, err
+	}
+	if line == "" {
+		return nil, errors.New("empty reply")
+	}
+
+	switch line[0] {
+	case '+':
+		return line[1:], nil
+	case '-':
+		return nil, errors.New(line[1:])
+	case ':':
+		return strconv.ParseInt(line[1:], 10, 64)
+	case '$':
+		size, err := strconv.Atoi(line[1:])
+		if err != nil {
+			return nil, fmt.Errorf("invalid bulk length %q", line)
+		}
+		if size < 0 {
+			return nil, nil
+		}
+		if size > maxPayload {
+			return nil, fmt.Errorf("bulk string of %d bytes exceeds the %d byte limit", size, maxPayload)
+		}
+		bulk := make([]byte, size+2)
+		if _, err := io.ReadFull(reader, bulk); err != nil {
+			return nil, err
+		}
+		return bulk[:size], nil
+	case '*':
+		size, err := strconv.Atoi(line[1:])
+		if err != nil {
+			return nil, fmt.Errorf("invalid array length %q", line)
+		}
+		if size < 0 {
+			return nil, nil
+		}
+		if size > maxArrayLength {
+			return nil, fmt.Errorf("array of %d values exceeds the %d value limit", size, maxArrayLength)
+		}
+		values := make([]any, size)
+		for i := range values {
+			if values[i], err = readReply(reader, maxPayload); err != nil {
+				return nil, err
+			}
+		}
+		return values, nil
+	}
+	return nil, fmt.Errorf("unexpected reply %q", line)
+}
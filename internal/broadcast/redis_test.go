@@ -0,0 +1,186 @@
+package broadcast_test
+
+import (
+	"bufio"
+	"context"
+	"crypto/tls"
+	"errors"
+	"io"
+	"net"
+	"net/url"
+	"os"
+	"testing"
+	"time"
+
+	"github.com/kdwils/constellation/internal/broadcast"
+)
+
+// exchange is one request the fake server expects and the reply it sends back
+type exchange struct {
+	request string
+	reply   string
+}
+
+// converse serves exchanges in order, sending everything it read on received once done
+func converse(exchanges []exchange, received chan<- string) func(net.Conn, *bufio.Reader) {
+	return func(conn net.Conn, reader *bufio.Reader) {
+		var all string
+		defer func() { received <- all }()
+		for _, ex := range exchanges {
+			request := make([]byte, len(ex.request))
+			if _, err := io.ReadFull(reader, request); err != nil {
+				return
+			}
+			all += string(request)
+			io.WriteString(conn, ex.reply) //nolint:errcheck
+		}
+	}
+}
+
+func TestRedis_Publish(t *testing.T) {
+	publish := "*3\r\n$7\r\nPUBLISH\r\n$20\r\nconstellation.health\r\n$2\r\n[]\r\n"
+	tests := []struct {
+		name      string
+		url       string
+		opts      []broadcast.Option
+		exchanges []exchange
+		wantErr   string
+	}{
+		{
+			name:      "publish is sent as a RESP array",
+			url:       "redis://",
+			exchanges: []exchange{{request: publish, reply: ":1\r\n"}},
+		},
+		{
+			name: "credentials authenticate first",
+			url:  "redis://default:secret@",
+			exchanges: []exchange{
+				{request: "*3\r\n$4\r\nAUTH\r\n$7\r\ndefault\r\n$6\r\nsecret\r\n", reply: "+OK\r\n"},
+				{request: publish, reply: ":1\r\n"},
+			},
+		},
+		{
+			name:      "error replies are returned",
+			url:       "redis://",
+			exchanges: []exchange{{request: publish, reply: "-NOPERM no permissions\r\n"}},
+			wantErr:   "redis publish: NOPERM no permissions",
+		},
+		{
+			name:      "bulk strings over the max payload are rejected",
+			url:       "redis://",
+			opts:      []broadcast.Option{broadcast.WithMaxPayload(4)},
+			exchanges: []exchange{{request: publish, reply: "$5\r\nhello\r\n"}},
+			wantErr:   "redis publish: bulk string of 5 bytes exceeds the 4 byte limit",
+		},
+		{
+			name:    "payloads over the max payload are not sent",
+			url:     "redis://",
+			opts:    []broadcast.Option{broadcast.WithMaxPayload(1)},
+			wantErr: "redis publish: payload of 2 bytes exceeds the 1 byte limit",
+		},
+		{
+			name:      "oversized arrays are rejected",
+			url:       "redis://",
+			exchanges: []exchange{{request: publish, reply: "*1000000000\r\n"}},
+			wantErr:   "redis publish: array of 1000000000 values exceeds the 16 value limit",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			received := make(chan string, 1)
+			addr := serve(t, converse(tt.exchanges, received))
+
+			u, err := url.Parse(tt.url + addr)
+			if err != nil {
+				t.Fatalf("TestRedis_Publish() parse error = %v", err)
+			}
+			err = broadcast.NewRedis(u, tt.opts...).Publish(context.Background(), "constellation.health", []byte("[]"))
+			if tt.wantErr != "" {
+				if err == nil || err.Error() != tt.wantErr {
+					t.Fatalf("TestRedis_Publish() error = %v, want %v", err, tt.wantErr)
+				}
+			}
+			if tt.wantErr == "" && err != nil {
+				t.Fatalf("TestRedis_Publish() error = %v", err)
+			}
+
+			var want string
+			for _, ex := range tt.exchanges {
+				want += ex.request
+			}
+			if got := <-received; got != want {
+				t.Errorf("TestRedis_Publish() sent %q, want %q", got, want)
+			}
+		})
+	}
+}
+
+func TestRedis_PublishTimeout(t *testing.T) {
+	addr := serve(t, func(conn net.Conn, reader *bufio.Reader) {
+		io.ReadAll(reader) //nolint:errcheck
+	})
+
+	u, err := url.Parse("redis://" + addr)
+	if err != nil {
+		t.Fatalf("TestRedis_PublishTimeout() parse error = %v", err)
+	}
+	redis := broadcast.NewRedis(u, broadcast.WithWriteTimeout(50*time.Millisecond))
+	err = redis.Publish(context.Background(), "constellation.health", []byte("[]"))
+	if !errors.Is(err, os.ErrDeadlineExceeded) {
+		t.Errorf("TestRedis_PublishTimeout() error = %v, want %v", err, os.ErrDeadlineExceeded)
+	}
+}
+
+func TestRedis_TLS(t *testing.T) {
+	certificate, caFile := selfSigned(t)
+	listener, err := tls.Listen("tcp", "127.0.0.1:0", &tls.Config{Certificates: []tls.Certificate{certificate}})
+	if err != nil {
+		t.Fatalf("TestRedis_TLS() listen error = %v", err)
+	}
+	publish := "*3\r\n$7\r\nPUBLISH\r\n$20\r\nconstellation.health\r\n$2\r\n[]\r\n"
+	received := make(chan string, 1)
+	addr := accept(t, listener, converse([]exchange{{request: publish, reply: ":1\r\n"}}, received))
+
+	backend, err := broadcast.New(broadcast.Config{URL: "rediss://" + addr, CAFile: caFile})
+	if err != nil {
+		t.Fatalf("TestRedis_TLS() new error = %v", err)
+	}
+	if err := backend.Publish(context.Background(), "constellation.health", []byte("[]")); err != nil {
+		t.Fatalf("TestRedis_TLS() error = %v", err)
+	}
+	if got := <-received; got != publish {
+		t.Errorf("TestRedis_TLS() sent %q, want %q", got, publish)
+	}
+}
+
+func TestRedis_Subscribe(t *testing.T) {
+	addr := serve(t, func(conn net.Conn, reader *bufio.Reader) {
+		request := make([]byte, len("*2\r\n$9\r\nSUBSCRIBE\r\n$20\r\nconstellation.health\r\n"))
+		if _, err := io.ReadFull(reader, request); err != nil {
+			return
+		}
+		io.WriteString(conn, "*3\r\n$9\r\nsubscribe\r\n$20\r\nconstellation.health\r\n:1\r\n"+ //nolint:errcheck
+			"*3\r\n$7\r\nmessage\r\n$20\r\nconstellation.health\r\n$2\r\n[]\r\n")
+
+		io.ReadAll(reader) //nolint:errcheck
+	})
+
+	u, err := url.Parse("redis://" + addr)
+	if err != nil {
+		t.Fatalf("TestRedis_Subscribe() parse error = %v", err)
+	}
+	ctx, cancel := context.WithCancel(context.Background())
+	defer cancel()
+
+	var got []string
+	err = broadcast.NewRedis(u).Subscribe(ctx, "constellation.health", func(payload []byte) {
+		got = append(got, string(payload))
+		cancel()
+	})
+	if !errors.Is(err, context.Canceled) {
+		t.Errorf("TestRedis_Subscribe() error = %v, want %v", err, context.Canceled)
+	}
+	if len(got) != 1 || got[0] != "[]" {
+		t.Errorf("TestRedis_Subscribe() delivered %q, want %q", got, []string{"[]"})
+	}
+}
@@ -0,0 +1,313 @@
+// Package certs inspects the TLS certificates served for HTTPRoute hostnames through their parent
+// Gateways. Only the certificate is parsed; the private key in the referenced Secret is ignored.
+package certs
+
+import (
+	"context"
+	"crypto/x509"
+	"encoding/pem"
+	"fmt"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	"k8s.io/apimachinery/pkg/api/meta"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	"sigs.k8s.io/controller-runtime/pkg/log"
+	"sigs.k8s.io/gateway-api/apis/v1beta1"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/types"
+	"github.com/kdwils/constellation/internal/versions"
+)
+
+const (
+	defaultWindow   = 14 * 24 * time.Hour
+	defaultInterval = time.Hour
+)
+
+// Config controls how often certificates are read and when they are flagged
+type Config struct {
+	// Window flags certificates expiring within this duration, defaulting to 14 days
+	Window time.Duration
+	// Interval between inspections, defaulting to 1h
+	Interval time.Duration
+}
+
+// Inspector reads the certificates behind route hostnames and attaches them to HTTPRoute nodes
+type Inspector struct {
+	reader   client.Reader
+	config   Config
+	now      func() time.Time
+	versions versions.Versions
+
+	mu          sync.RWMutex
+	routes      map[string][]types.CertificateInfo
+	inspectedAt time.Time
+}
+
+// NewInspector creates an Inspector. reader should bypass the cache, e.g. the manager's API reader,
+// so Secrets are not watched cluster-wide.
+func NewInspector(reader client.Reader, config Config, opts ...InspectorOpt) *Inspector {
+	if config.Window == 0 {
+		config.Window = defaultWindow
+	}
+	if config.Interval == 0 {
+		config.Interval = defaultInterval
+	}
+
+	i := &Inspector{
+		reader: reader,
+		config: config,
+		now:    time.Now,
+		routes: make(map[string][]types.CertificateInfo),
+	}
+
+	for _, opt := range opts {
+		opt(i)
+	}
+
+	return i
+}
+
+type InspectorOpt func(*Inspector)
+
+// WithClock replaces time.Now, for tests
+func WithClock(now func() time.Time) InspectorOpt {
+	return func(i *Inspector) {
+		i.now = now
+	}
+}
+
+// WithVersions reads routes and Gateways in the versions negotiated with the apiserver rather than v1beta1
+func WithVersions(served versions.Versions) InspectorOpt {
+	return func(i *Inspector) {
+		i.versions = served
+	}
+}
+
+// +kubebuilder:rbac:groups=gateway.networking.k8s.io,resources=gateways,verbs=get;list
+// +kubebuilder:rbac:groups="",resources=secrets,verbs=get
+
+// Start inspects certificates on the configured interval until ctx is cancelled
+func (i *Inspector) Start(ctx context.Context) {
+	logger := log.FromContext(ctx).WithName("certs")
+	ticker := time.NewTicker(i.config.Interval)
+	defer ticker.Stop()
+
+	for {
+		if err := i.Refresh(ctx); err != nil {
+			logger.Error(err, "failed to inspect route certificates")
+		}
+
+		select {
+		case <-ctx.Done():
+			return
+		case <-ticker.C:
+		}
+	}
+}
+
+// Refresh reads every route's parent Gateways and their certificates once
+func (i *Inspector) Refresh(ctx context.Context) error {
+	routes := i.versions.List("httproutes")
+	if err := i.reader.List(ctx, routes); err != nil {
+		if meta.IsNoMatchError(err) {
+			return nil
+		}
+		return fmt.Errorf("failed to list httproutes: %w", err)
+	}
+
+	gateways := i.versions.List("gateways")
+	if err := i.reader.List(ctx, gateways); err != nil {
+		return fmt.Errorf("failed to list gateways: %w", err)
+	}
+	gatewayItems := versions.Gateways(gateways)
+	gatewaysByKey := make(map[string]v1beta1.Gateway, len(gatewayItems))
+	for _, gateway := range gatewayItems {
+		gatewaysByKey[gateway.Namespace+"/"+gateway.Name] = gateway
+	}
+
+	certificates := make(map[string]*x509.Certificate)
+	result := make(map[string][]types.CertificateInfo)
+	for _, route := range versions.HTTPRoutes(routes) {
+		var infos []types.CertificateInfo
+		for _, listener := range routeListeners(route, gatewaysByKey) {
+			for _, ref := range listener.secrets {
+				cert, ok := certificates[ref]
+				if !ok {
+					var err error
+					cert, err = i.readCertificate(ctx, ref)
+					if err != nil {
+						return err
+					}
+					certificates[ref] = cert
+				}
+				if cert == nil {
+					continue
+				}
+				for _, hostname := range route.Spec.Hostnames {
+					if !listenerMatches(listener.hostname, string(hostname)) {
+						continue
+					}
+					infos = append(infos, i.certificateInfo(string(hostname), ref, cert))
+				}
+			}
+		}
+		result[route.Namespace+"/"+route.Name] = infos
+	}
+
+	i.mu.Lock()
+	i.routes = result
+	i.inspectedAt = i.now()
+	i.mu.Unlock()
+	return nil
+}
+
+type listener struct {
+	hostname string
+	secrets  []string
+}
+
+// routeListeners returns the HTTPS listeners a route attaches to, with their certificate Secret keys
+func routeListeners(route v1beta1.HTTPRoute, gateways map[string]v1beta1.Gateway) []listener {
+	var listeners []listener
+	for _, parent := range route.Spec.ParentRefs {
+		if parent.Kind != nil && *parent.Kind != "Gateway" {
+			continue
+		}
+		namespace := route.Namespace
+		if parent.Namespace != nil {
+			namespace = string(*parent.Namespace)
+		}
+		gateway, exists := gateways[namespace+"/"+string(parent.Name)]
+		if !exists {
+			continue
+		}
+
+		for _, l := range gateway.Spec.Listeners {
+			if parent.SectionName != nil && *parent.SectionName != l.Name {
+				continue
+			}
+			if l.TLS == nil {
+				continue
+			}
+			found := listener{}
+			if l.Hostname != nil {
+				found.hostname = string(*l.Hostname)
+			}
+			for _, ref := range l.TLS.CertificateRefs {
+				if ref.Kind != nil && *ref.Kind != "Secret" {
+					continue
+				}
+				secretNamespace := gateway.Namespace
+				if ref.Namespace != nil {
+					secretNamespace = string(*ref.Namespace)
+				}
+				found.secrets = append(found.secrets, secretNamespace+"/"+string(ref.Name))
+			}
+			listeners = append(listeners, found)
+		}
+	}
+	return listeners
+}
+
+// listenerMatches applies Gateway listener hostname matching, where a leading *. matches one or more labels
+func listenerMatches(listenerHostname, hostname string) bool {
+	if listenerHostname == "" || listenerHostname == hostname {
+		return true
+	}
+	suffix, wildcard := strings.CutPrefix(listenerHostname, "*")
+	return wildcard && strings.HasSuffix(hostname, suffix) && len(hostname) > len(suffix)
+}
+
+// readCertificate returns the leaf certificate in a Secret's tls.crt, or nil when the Secret is missing
+func (i *Inspector) readCertificate(ctx context.Context, ref string) (*x509.Certificate, error) {
+	namespace, name, _ := strings.Cut(ref, "/")
+	var secret corev1.Secret
+	if err := i.reader.Get(ctx, client.ObjectKey{Namespace: namespace, Name: name}, &secret); err != nil {
+		if client.IgnoreNotFound(err) == nil {
+			return nil, nil
+		}
+		return nil, fmt.Errorf("failed to get secret %s: %w", ref, err)
+	}
+
+	block, _ := pem.Decode(secret.Data[corev1.TLSCertKey])
+	if block == nil {
+		return nil, nil
+	}
+	cert, err := x509.ParseCertificate(block.Bytes)
+	if err != nil {
+		return nil, fmt.Errorf("invalid certificate in secret %s: %w", ref, err)
+	}
+	return cert, nil
+}
+
+func (i *Inspector) certificateInfo(hostname, secret string, cert *x509.Certificate) types.CertificateInfo {
+	return types.CertificateInfo{
+		Hostname:     hostname,
+		Secret:       secret,
+		Issuer:       cert.Issuer.String(),
+		NotAfter:     cert.NotAfter,
+		DNSNames:     cert.DNSNames,
+		Covered:      cert.VerifyHostname(hostname) == nil,
+		ExpiringSoon: cert.NotAfter.Before(i.now().Add(i.config.Window)),
+	}
+}
+
+// Get returns the certificates serving a route's hostnames
+func (i *Inspector) Get(namespace, route string) []types.CertificateInfo {
+	i.mu.RLock()
+	defer i.mu.RUnlock()
+	return i.routes[namespace+"/"+route]
+}
+
+// Enrich attaches certificates to HTTPRoute nodes
+func (i *Inspector) Enrich(nodes []types.HierarchyNode) {
+	hierarchy.Walk(nodes, func(node *types.HierarchyNode) {
+		if node.Kind != types.ResourceKindHTTPRoute || node.Namespace == nil {
+			return
+		}
+		node.Certificates = i.Get(*node.Namespace, node.Name)
+	})
+}
+
+// GetAlerts returns an alert for every route hostname whose certificate expires within the window
+// or does not cover the hostname
+func (i *Inspector) GetAlerts(ctx context.Context) ([]types.Alert, error) {
+	i.mu.RLock()
+	defer i.mu.RUnlock()
+
+	keys := make([]string, 0, len(i.routes))
+	for key := range i.routes {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+
+	alerts := make([]types.Alert, 0)
+	for _, key := range keys {
+		namespace, name, _ := strings.Cut(key, "/")
+		for _, info := range i.routes[key] {
+			alert := types.Alert{
+				Kind:      types.ResourceKindHTTPRoute,
+				Namespace: namespace,
+				Name:      name,
+				Since:     i.inspectedAt,
+			}
+			switch {
+			case !info.Covered:
+				alert.Type = types.AlertTypeCertificateMismatch
+				alert.Message = fmt.Sprintf("certificate in %s does not cover %s", info.Secret, info.Hostname)
+			case info.ExpiringSoon:
+				alert.Type = types.AlertTypeCertificateExpiring
+				alert.Message = fmt.Sprintf("certificate for %s in %s expires %s", info.Hostname, info.Secret, info.NotAfter.Format(time.RFC3339))
+			default:
+				continue
+			}
+			alerts = append(alerts, alert)
+		}
+	}
+	return alerts, nil
+}
@@ -0,0 +1,138 @@
+package certs_test
+
+import (
+	"context"
+	"crypto/ecdsa"
+	"crypto/elliptic"
+	"crypto/rand"
+	"crypto/x509"
+	"crypto/x509/pkix"
+	"encoding/pem"
+	"math/big"
+	"testing"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"k8s.io/apimachinery/pkg/runtime"
+	clientgoscheme "k8s.io/client-go/kubernetes/scheme"
+	"k8s.io/utils/ptr"
+	"sigs.k8s.io/controller-runtime/pkg/client/fake"
+	"sigs.k8s.io/gateway-api/apis/v1beta1"
+
+	"github.com/kdwils/constellation/internal/certs"
+)
+
+var now = time.Date(2025, 1, 1, 0, 0, 0, 0, time.UTC)
+
+func certificatePEM(t *testing.T, notAfter time.Time, dnsNames ...string) []byte {
+	t.Helper()
+
+	key, err := ecdsa.GenerateKey(elliptic.P256(), rand.Reader)
+	if err != nil {
+		t.Fatalf("certificatePEM() key error = %v", err)
+	}
+	template := &x509.Certificate{
+		SerialNumber: big.NewInt(1),
+		Subject:      pkix.Name{CommonName: dnsNames[0]},
+		NotBefore:    now.Add(-24 * time.Hour),
+		NotAfter:     notAfter,
+		DNSNames:     dnsNames,
+	}
+	der, err := x509.CreateCertificate(rand.Reader, template, template, &key.PublicKey, key)
+	if err != nil {
+		t.Fatalf("certificatePEM() create error = %v", err)
+	}
+	return pem.EncodeToMemory(&pem.Block{Type: "CERTIFICATE", Bytes: der})
+}
+
+func TestInspector_Refresh(t *testing.T) {
+	tests := []struct {
+		name         string
+		notAfter     time.Time
+		dnsNames     []string
+		wantCovered  bool
+		wantExpiring bool
+	}{
+		{
+			name:         "valid certificate",
+			notAfter:     now.Add(90 * 24 * time.Hour),
+			dnsNames:     []string{"shop.example.com"},
+			wantCovered:  true,
+			wantExpiring: false,
+		},
+		{
+			name:         "wildcard certificate expiring within window",
+			notAfter:     now.Add(7 * 24 * time.Hour),
+			dnsNames:     []string{"*.example.com"},
+			wantCovered:  true,
+			wantExpiring: true,
+		},
+		{
+			name:         "certificate for another hostname",
+			notAfter:     now.Add(90 * 24 * time.Hour),
+			dnsNames:     []string{"blog.example.com"},
+			wantCovered:  false,
+			wantExpiring: false,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			scheme := runtime.NewScheme()
+			if err := clientgoscheme.AddToScheme(scheme); err != nil {
+				t.Fatalf("TestInspector_Refresh() scheme error = %v", err)
+			}
+			if err := v1beta1.Install(scheme); err != nil {
+				t.Fatalf("TestInspector_Refresh() scheme error = %v", err)
+			}
+
+			secret := &corev1.Secret{
+				ObjectMeta: metav1.ObjectMeta{Name: "shop-tls", Namespace: "gateways"},
+				Data:       map[string][]byte{corev1.TLSCertKey: certificatePEM(t, tt.notAfter, tt.dnsNames...)},
+			}
+			gateway := &v1beta1.Gateway{
+				ObjectMeta: metav1.ObjectMeta{Name: "public", Namespace: "gateways"},
+				Spec: v1beta1.GatewaySpec{
+					GatewayClassName: "example",
+					Listeners: []v1beta1.Listener{{
+						Name:     "https",
+						Protocol: v1beta1.ProtocolType("HTTPS"),
+						Port:     443,
+						TLS: &v1beta1.GatewayTLSConfig{
+							CertificateRefs: []v1beta1.SecretObjectReference{{Name: "shop-tls"}},
+						},
+					}},
+				},
+			}
+			route := &v1beta1.HTTPRoute{
+				ObjectMeta: metav1.ObjectMeta{Name: "storefront", Namespace: "shop"},
+				Spec: v1beta1.HTTPRouteSpec{
+					CommonRouteSpec: v1beta1.CommonRouteSpec{
+						ParentRefs: []v1beta1.ParentReference{{Name: "public", Namespace: ptr.To(v1beta1.Namespace("gateways"))}},
+					},
+					Hostnames: []v1beta1.Hostname{"shop.example.com"},
+				},
+			}
+
+			reader := fake.NewClientBuilder().WithScheme(scheme).WithObjects(secret, gateway, route).Build()
+			inspector := certs.NewInspector(reader, certs.Config{Window: 14 * 24 * time.Hour}, certs.WithClock(func() time.Time { return now }))
+			if err := inspector.Refresh(context.Background()); err != nil {
+				t.Fatalf("TestInspector_Refresh() error = %v", err)
+			}
+
+			infos := inspector.Get("shop", "storefront")
+			if len(infos) != 1 {
+				t.Fatalf("TestInspector_Refresh() certificates = %v, want 1", infos)
+			}
+			if infos[0].Secret != "gateways/shop-tls" {
+				t.Errorf("TestInspector_Refresh() secret = %v, want %v", infos[0].Secret, "gateways/shop-tls")
+			}
+			if infos[0].Covered != tt.wantCovered {
+				t.Errorf("TestInspector_Refresh() covered = %v, want %v", infos[0].Covered, tt.wantCovered)
+			}
+			if infos[0].ExpiringSoon != tt.wantExpiring {
+				t.Errorf("TestInspector_Refresh() expiring = %v, want %v", infos[0].ExpiringSoon, tt.wantExpiring)
+			}
+		})
+	}
+}
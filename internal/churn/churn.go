@@ -0,0 +1,201 @@
+// Package churn counts pod creations and deletions per namespace over a sliding window, so namespaces
+// with pathological churn, such as an HPA flapping or a Job controller recreating failing pods, stand out.
+package churn
+
+import (
+	"context"
+	"sort"
+	"sync"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	toolscache "k8s.io/client-go/tools/cache"
+	"sigs.k8s.io/controller-runtime/pkg/log"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const (
+	defaultBucket  = time.Minute
+	defaultBuckets = 60
+)
+
+// Config sets the size of the window
+type Config struct {
+	// Bucket is the time each heatmap column covers, defaulting to 1m
+	Bucket time.Duration
+	// Buckets is how many columns are kept, defaulting to 60
+	Buckets int
+}
+
+// counts is the churn of one namespace in one bucket
+type counts struct {
+	added   int
+	deleted int
+}
+
+// Tracker counts pod events from the pod informer. It is a ResourceEventHandler.
+type Tracker struct {
+	informers types.Informers
+	config    Config
+	now       func() time.Time
+
+	mu sync.Mutex
+	// buckets is keyed by namespace, then by bucket number since the Unix epoch
+	buckets map[string]map[int64]counts
+	// pruned is the bucket the window last ended at, so it is only pruned when it moves
+	pruned int64
+}
+
+// NewTracker creates a Tracker that counts the pods seen by the pod informer in informers
+func NewTracker(informers types.Informers, config Config, opts ...TrackerOpt) *Tracker {
+	if config.Bucket <= 0 {
+		config.Bucket = defaultBucket
+	}
+	if config.Buckets <= 0 {
+		config.Buckets = defaultBuckets
+	}
+
+	t := &Tracker{
+		informers: informers,
+		config:    config,
+		now:       time.Now,
+		buckets:   make(map[string]map[int64]counts),
+	}
+
+	for _, opt := range opts {
+		opt(t)
+	}
+
+	return t
+}
+
+type TrackerOpt func(*Tracker)
+
+// WithClock replaces the clock events are bucketed by
+func WithClock(now func() time.Time) TrackerOpt {
+	return func(t *Tracker) {
+		t.now = now
+	}
+}
+
+// Start counts pod events until ctx is cancelled
+func (t *Tracker) Start(ctx context.Context) {
+	logger := log.FromContext(ctx).WithName("churn")
+	informer, err := t.informers.GetInformer(ctx, &corev1.Pod{})
+	if err != nil {
+		logger.Error(err, "failed to get pod informer")
+		return
+	}
+	registration, err := informer.AddEventHandler(t)
+	if err != nil {
+		logger.Error(err, "failed to watch pod events")
+		return
+	}
+
+	<-ctx.Done()
+	if err := informer.RemoveEventHandler(registration); err != nil {
+		logger.Error(err, "failed to stop watching pod events")
+	}
+}
+
+// OnAdd counts a created pod. Pods listed when the informer starts are not counted.
+func (t *Tracker) OnAdd(obj any, isInInitialList bool) {
+	if isInInitialList {
+		return
+	}
+	t.record(obj, func(c *counts) { c.added++ })
+}
+
+// OnUpdate ignores updates; a pod restarting its containers in place is not churn
+func (t *Tracker) OnUpdate(_, _ any) {}
+
+// OnDelete counts a deleted pod
+func (t *Tracker) OnDelete(obj any) {
+	t.record(obj, func(c *counts) { c.deleted++ })
+}
+
+func (t *Tracker) record(obj any, count func(*counts)) {
+	if tombstone, ok := obj.(toolscache.DeletedFinalStateUnknown); ok {
+		obj = tombstone.Obj
+	}
+	object, ok := obj.(metav1.Object)
+	if !ok || object.GetNamespace() == "" {
+		return
+	}
+
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	current := t.bucket(t.now())
+	t.prune(current)
+	namespace := t.buckets[object.GetNamespace()]
+	if namespace == nil {
+		namespace = make(map[int64]counts)
+		t.buckets[object.GetNamespace()] = namespace
+	}
+	c := namespace[current]
+	count(&c)
+	namespace[current] = c
+}
+
+// Churn returns the heatmap of the window ending now
+func (t *Tracker) Churn() types.ChurnReport {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	current := t.bucket(t.now())
+	t.prune(current)
+	oldest := current - int64(t.config.Buckets) + 1
+
+	report := types.ChurnReport{
+		Start:         time.Unix(0, oldest*int64(t.config.Bucket)).UTC(),
+		BucketSeconds: int(t.config.Bucket / time.Second),
+		Namespaces:    make([]types.NamespaceChurn, 0, len(t.buckets)),
+	}
+	for namespace, buckets := range t.buckets {
+		row := types.NamespaceChurn{
+			Namespace: namespace,
+			Added:     make([]int, t.config.Buckets),
+			Deleted:   make([]int, t.config.Buckets),
+		}
+		for bucket, c := range buckets {
+			row.Added[bucket-oldest] = c.added
+			row.Deleted[bucket-oldest] = c.deleted
+			row.Total += c.added + c.deleted
+		}
+		report.Namespaces = append(report.Namespaces, row)
+	}
+	sort.Slice(report.Namespaces, func(i, j int) bool {
+		if report.Namespaces[i].Total != report.Namespaces[j].Total {
+			return report.Namespaces[i].Total > report.Namespaces[j].Total
+		}
+		return report.Namespaces[i].Namespace < report.Namespaces[j].Namespace
+	})
+	return report
+}
+
+func (t *Tracker) bucket(at time.Time) int64 {
+	return at.UnixNano() / int64(t.config.Bucket)
+}
+
+// prune drops buckets that have left the window ending at current, and namespaces left without any
+func (t *Tracker) prune(current int64) {
+	if current == t.pruned {
+		return
+	}
+	t.pruned = current
+
+	oldest := current - int64(t.config.Buckets) + 1
+	for namespace, buckets := range t.buckets {
+		for bucket := range buckets {
+			if bucket < oldest {
+				delete(buckets, bucket)
+			}
+		}
+		if len(buckets) == 0 {
+			delete(t.buckets, namespace)
+		}
+	}
+}
@@ -0,0 +1,104 @@
+package churn_test
+
+import (
+	"reflect"
+	"testing"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	toolscache "k8s.io/client-go/tools/cache"
+
+	"github.com/kdwils/constellation/internal/churn"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func pod(namespace string) *corev1.Pod {
+	return &corev1.Pod{ObjectMeta: metav1.ObjectMeta{Name: "web", Namespace: namespace}}
+}
+
+func TestTracker_Churn(t *testing.T) {
+	start := time.Date(2025, 1, 2, 3, 0, 0, 0, time.UTC)
+
+	type event struct {
+		after   time.Duration
+		add     bool
+		initial bool
+		obj     any
+	}
+	tests := []struct {
+		name   string
+		events []event
+		now    time.Duration
+		want   types.ChurnReport
+	}{
+		{
+			name: "events are bucketed per namespace and ordered by total",
+			events: []event{
+				{after: 0, add: true, obj: pod("shop")},
+				{after: 30 * time.Second, obj: pod("shop")},
+				{after: time.Minute, add: true, obj: pod("batch")},
+				{after: time.Minute, add: true, obj: pod("batch")},
+				{after: 2 * time.Minute, obj: toolscache.DeletedFinalStateUnknown{Key: "batch/web", Obj: pod("batch")}},
+			},
+			now: 2 * time.Minute,
+			want: types.ChurnReport{
+				Start:         start,
+				BucketSeconds: 60,
+				Namespaces: []types.NamespaceChurn{
+					{Namespace: "batch", Added: []int{0, 2, 0}, Deleted: []int{0, 0, 1}, Total: 3},
+					{Namespace: "shop", Added: []int{1, 0, 0}, Deleted: []int{1, 0, 0}, Total: 2},
+				},
+			},
+		},
+		{
+			name: "buckets leave the window",
+			events: []event{
+				{after: 0, add: true, obj: pod("shop")},
+				{after: 3 * time.Minute, add: true, obj: pod("batch")},
+			},
+			now: 3 * time.Minute,
+			want: types.ChurnReport{
+				Start:         start.Add(time.Minute),
+				BucketSeconds: 60,
+				Namespaces: []types.NamespaceChurn{
+					{Namespace: "batch", Added: []int{0, 0, 1}, Deleted: []int{0, 0, 0}, Total: 1},
+				},
+			},
+		},
+		{
+			name: "initial list and cluster scoped objects are not counted",
+			events: []event{
+				{after: 0, add: true, initial: true, obj: pod("shop")},
+				{after: 0, add: true, obj: &corev1.Node{ObjectMeta: metav1.ObjectMeta{Name: "node-a"}}},
+			},
+			now: 0,
+			want: types.ChurnReport{
+				Start:         start.Add(-2 * time.Minute),
+				BucketSeconds: 60,
+				Namespaces:    []types.NamespaceChurn{},
+			},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			now := start
+			tracker := churn.NewTracker(nil, churn.Config{Bucket: time.Minute, Buckets: 3},
+				churn.WithClock(func() time.Time { return now }))
+
+			for _, e := range tt.events {
+				now = start.Add(e.after)
+				if e.add {
+					tracker.OnAdd(e.obj, e.initial)
+					continue
+				}
+				tracker.OnDelete(e.obj)
+			}
+
+			now = start.Add(tt.now)
+			if got := tracker.Churn(); !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestTracker_Churn() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,230 @@
+// Package cli implements the terminal subcommands that print the topology without the web UI.
+package cli
+
+import (
+	"context"
+	"encoding/json"
+	"flag"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"strings"
+
+	"github.com/gorilla/websocket"
+	"k8s.io/apimachinery/pkg/runtime"
+	"k8s.io/client-go/discovery"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	ctrlconfig "sigs.k8s.io/controller-runtime/pkg/client/config"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/tui"
+	"github.com/kdwils/constellation/internal/types"
+	"github.com/kdwils/constellation/internal/versions"
+	apiv1 "github.com/kdwils/constellation/pkg/api/v1"
+)
+
+// defaultPageSize matches kubectl's --chunk-size default
+const defaultPageSize = 500
+
+// Commands lists the subcommands handled by Run
+var Commands = []string{"tree", "get", "top", "tui", "schema"}
+
+// IsCommand reports whether name is a subcommand handled by Run
+func IsCommand(name string) bool {
+	for _, command := range Commands {
+		if command == name {
+			return true
+		}
+	}
+	return false
+}
+
+// Source supplies the data printed by the subcommands
+type Source interface {
+	GetHierarchy(ctx context.Context) ([]types.HierarchyNode, error)
+	GetAllHealthData(ctx context.Context) ([]*types.ServiceHealthInfo, error)
+}
+
+// Run executes a subcommand. args starts with the subcommand name.
+func Run(ctx context.Context, scheme *runtime.Scheme, args []string, out io.Writer) error {
+	if len(args) == 0 || !IsCommand(args[0]) {
+		return fmt.Errorf("expected one of %s", strings.Join(Commands, ", "))
+	}
+	if args[0] == "schema" {
+		return runSchema(args[1:], out)
+	}
+
+	fs := flag.NewFlagSet(args[0], flag.ContinueOnError)
+	var opts sourceOptions
+	fs.StringVar(&opts.serverURL, "server", "", "URL of a running constellation server; connects to the cluster directly when empty")
+	fs.StringVar(&opts.kubeContext, "context", "", "kubeconfig context to use; defaults to the current context")
+	fs.BoolVar(&opts.proxy, "proxy", false, "read from the in-cluster server through the apiserver service proxy")
+	fs.StringVar(&opts.service, "service", defaultService, "namespace/name:port of the in-cluster server used with --proxy")
+	fs.Int64Var(&opts.pageSize, "page-size", defaultPageSize, "objects per list request when reading the cluster directly; 0 lists each kind at once")
+	if err := fs.Parse(args[1:]); err != nil {
+		return err
+	}
+
+	source, err := newSource(scheme, opts)
+	if err != nil {
+		return err
+	}
+
+	switch args[0] {
+	case "tree":
+		nodes, err := source.GetHierarchy(ctx)
+		if err != nil {
+			return err
+		}
+		return WriteTree(out, nodes)
+	case "get":
+		if fs.NArg() != 1 {
+			return fmt.Errorf("usage: get [flags] <kind>")
+		}
+		nodes, err := source.GetHierarchy(ctx)
+		if err != nil {
+			return err
+		}
+		return WriteGet(out, nodes, fs.Arg(0))
+	case "tui":
+		return tui.Run(ctx, source, os.Stdin, os.Stdout)
+	}
+
+	health, err := source.GetAllHealthData(ctx)
+	if err != nil {
+		return err
+	}
+	return WriteTop(out, health)
+}
+
+type sourceOptions struct {
+	serverURL   string
+	kubeContext string
+	proxy       bool
+	service     string
+	pageSize    int64
+}
+
+func newSource(scheme *runtime.Scheme, opts sourceOptions) (Source, error) {
+	if opts.serverURL != "" {
+		return &ServerSource{BaseURL: strings.TrimSuffix(opts.serverURL, "/"), Client: http.DefaultClient}, nil
+	}
+
+	config, err := ctrlconfig.GetConfigWithContext(opts.kubeContext)
+	if err != nil {
+		return nil, fmt.Errorf("failed to load kubeconfig: %w", err)
+	}
+
+	if opts.proxy {
+		return newProxySource(config, opts.service)
+	}
+
+	c, err := client.New(config, client.Options{Scheme: scheme})
+	if err != nil {
+		return nil, fmt.Errorf("failed to create cluster client: %w", err)
+	}
+	discoveryClient, err := discovery.NewDiscoveryClientForConfig(config)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create discovery client: %w", err)
+	}
+	served, err := versions.Negotiate(discoveryClient)
+	if err != nil {
+		return nil, err
+	}
+	return &ClusterSource{Reader: c, PageSize: opts.pageSize, Versions: served}, nil
+}
+
+// ServerSource reads from a running constellation server
+type ServerSource struct {
+	BaseURL string
+	Client  *http.Client
+}
+
+// GetHierarchy fetches /api/v1/hierarchy
+func (s *ServerSource) GetHierarchy(ctx context.Context) ([]types.HierarchyNode, error) {
+	var nodes []apiv1.Node
+	if err := s.get(ctx, "/api/v1/hierarchy", &nodes); err != nil {
+		return nil, err
+	}
+	return apiv1.ToNodes(nodes), nil
+}
+
+// GetAllHealthData fetches /api/v1/state
+func (s *ServerSource) GetAllHealthData(ctx context.Context) ([]*types.ServiceHealthInfo, error) {
+	var state types.State
+	if err := s.get(ctx, "/api/v1/state", &state); err != nil {
+		return nil, err
+	}
+	return state.Services, nil
+}
+
+// Updates streams /ws and signals each health update the server broadcasts, so the tui reloads the tree
+// when it changes rather than polling. The channel is closed when the stream drops.
+func (s *ServerSource) Updates(ctx context.Context) (<-chan struct{}, error) {
+	url := "ws" + strings.TrimPrefix(s.BaseURL, "http") + "/ws"
+	conn, _, err := websocket.DefaultDialer.DialContext(ctx, url, nil)
+	if err != nil {
+		return nil, fmt.Errorf("failed to open the update stream: %w", err)
+	}
+
+	updates := make(chan struct{}, 1)
+	stop := context.AfterFunc(ctx, func() { conn.Close() }) //nolint:errcheck
+	go func() {
+		defer close(updates)
+		defer stop()
+		defer conn.Close() //nolint:errcheck
+		for {
+			if _, _, err := conn.NextReader(); err != nil {
+				return
+			}
+			select {
+			case updates <- struct{}{}:
+			default:
+			}
+		}
+	}()
+	return updates, nil
+}
+
+func (s *ServerSource) get(ctx context.Context, path string, v any) error {
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, s.BaseURL+path, nil)
+	if err != nil {
+		return err
+	}
+
+	resp, err := s.Client.Do(req)
+	if err != nil {
+		return fmt.Errorf("failed to reach constellation server: %w", err)
+	}
+	defer resp.Body.Close() //nolint:errcheck
+
+	if resp.StatusCode != http.StatusOK {
+		return fmt.Errorf("GET %s returned %s", path, resp.Status)
+	}
+	return json.NewDecoder(resp.Body).Decode(v)
+}
+
+// ClusterSource reads directly from the cluster using the current kubeconfig.
+// It has no health data since active checks only run inside a server.
+type ClusterSource struct {
+	Reader client.Reader
+	// PageSize chunks each list request, as kubectl does, to keep responses small on large clusters
+	PageSize int64
+	// Versions are the versions the Gateway API kinds are listed in, v1beta1 when unset
+	Versions versions.Versions
+}
+
+// GetHierarchy lists the cluster objects and builds the hierarchy locally
+func (s *ClusterSource) GetHierarchy(ctx context.Context) ([]types.HierarchyNode, error) {
+	objects, err := hierarchy.ListObjects(ctx, s.Reader, hierarchy.WithPageSize(s.PageSize), hierarchy.WithVersions(s.Versions))
+	if err != nil {
+		return nil, err
+	}
+	return hierarchy.Build(objects, nil), nil
+}
+
+// GetAllHealthData always fails; health data requires --server
+func (s *ClusterSource) GetAllHealthData(ctx context.Context) ([]*types.ServiceHealthInfo, error) {
+	return nil, fmt.Errorf("health data is only available from a running server, pass --server")
+}
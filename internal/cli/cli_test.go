@@ -0,0 +1,117 @@
+package cli_test
+
+import (
+	"context"
+	"net/http"
+	"net/http/httptest"
+	"reflect"
+	"testing"
+
+	"github.com/gorilla/websocket"
+
+	"github.com/kdwils/constellation/internal/cli"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func TestServerSource_Updates(t *testing.T) {
+	tests := []struct {
+		name     string
+		messages int
+		want     []bool
+	}{
+		{name: "message signals an update", messages: 1, want: []bool{true, false}},
+		{name: "closed stream closes the channel", messages: 0, want: []bool{false}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+				conn, err := (&websocket.Upgrader{}).Upgrade(w, r, nil)
+				if err != nil {
+					return
+				}
+				defer conn.Close() //nolint:errcheck
+				for range tt.messages {
+					conn.WriteMessage(websocket.TextMessage, []byte("[]")) //nolint:errcheck
+				}
+			}))
+			defer server.Close()
+
+			source := &cli.ServerSource{BaseURL: server.URL, Client: server.Client()}
+			updates, err := source.Updates(context.Background())
+			if err != nil {
+				t.Fatalf("TestServerSource_Updates() error = %v", err)
+			}
+			for i, want := range tt.want {
+				if _, got := <-updates; got != want {
+					t.Fatalf("TestServerSource_Updates() receive %d open = %v, want %v", i, got, want)
+				}
+			}
+		})
+	}
+}
+
+func TestServerSource_GetHierarchy(t *testing.T) {
+	tests := []struct {
+		name    string
+		path    string
+		body    string
+		want    []types.HierarchyNode
+		wantErr string
+	}{
+		{
+			name: "nodes are read from the versioned api",
+			path: "/api/v1/hierarchy",
+			body: `[{"kind":"Namespace","name":"shop","id":"Namespace/shop","relatives":[{"kind":"Service",` +
+				`"name":"web","id":"Namespace/shop|Service/shop/web","health_info":{"service_name":"web",` +
+				`"namespace":"shop","status":"healthy","uptime":100,"history":null,"url":"http://web"}}]}]`,
+			want: []types.HierarchyNode{{
+				Kind: types.ResourceKindNamespace,
+				Name: "shop",
+				ID:   "Namespace/shop",
+				Relatives: []types.HierarchyNode{{
+					Kind: types.ResourceKindService,
+					Name: "web",
+					ID:   "Namespace/shop|Service/shop/web",
+					HealthInfo: &types.ServiceHealthInfo{
+						ServiceName: "web",
+						Namespace:   "shop",
+						Status:      types.HealthStatusHealthy,
+						Uptime:      100,
+						URL:         "http://web",
+					},
+				}},
+			}},
+		},
+		{
+			name:    "the deprecated path is not used",
+			path:    "/hierarchy",
+			body:    `[]`,
+			wantErr: "GET /api/v1/hierarchy returned 404 Not Found",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			mux := http.NewServeMux()
+			mux.HandleFunc(tt.path, func(w http.ResponseWriter, r *http.Request) {
+				w.Write([]byte(tt.body)) //nolint:errcheck
+			})
+			server := httptest.NewServer(mux)
+			defer server.Close()
+
+			source := &cli.ServerSource{BaseURL: server.URL, Client: server.Client()}
+			got, err := source.GetHierarchy(context.Background())
+			if tt.wantErr != "" {
+				if err == nil || err.Error() != tt.wantErr {
+					t.Fatalf("TestServerSource_GetHierarchy() error = %v, want %v", err, tt.wantErr)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("TestServerSource_GetHierarchy() error = %v", err)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestServerSource_GetHierarchy() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,83 @@
+package cli
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"strings"
+
+	"k8s.io/client-go/kubernetes"
+	corev1client "k8s.io/client-go/kubernetes/typed/core/v1"
+	"k8s.io/client-go/rest"
+
+	"github.com/kdwils/constellation/internal/types"
+	apiv1 "github.com/kdwils/constellation/pkg/api/v1"
+)
+
+// defaultService is where the in-cluster server is expected when --service is not given
+const defaultService = "constellation/constellation:8080"
+
+// ProxySource reads from an in-cluster server through the apiserver's services/proxy
+// subresource, so no port-forward is needed. The caller needs get on services/proxy.
+type ProxySource struct {
+	Services corev1client.ServiceInterface
+	Name     string
+	Port     string
+}
+
+func newProxySource(config *rest.Config, service string) (*ProxySource, error) {
+	namespace, name, port, err := parseService(service)
+	if err != nil {
+		return nil, err
+	}
+
+	clientset, err := kubernetes.NewForConfig(config)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create cluster client: %w", err)
+	}
+
+	return &ProxySource{
+		Services: clientset.CoreV1().Services(namespace),
+		Name:     name,
+		Port:     port,
+	}, nil
+}
+
+// parseService splits namespace/name:port; the port may be a number or a port name
+func parseService(service string) (namespace, name, port string, err error) {
+	namespace, remainder, ok := strings.Cut(service, "/")
+	if !ok || namespace == "" {
+		return "", "", "", fmt.Errorf("service %q must be namespace/name:port", service)
+	}
+	name, port, ok = strings.Cut(remainder, ":")
+	if !ok || name == "" || port == "" {
+		return "", "", "", fmt.Errorf("service %q must be namespace/name:port", service)
+	}
+	return namespace, name, port, nil
+}
+
+// GetHierarchy fetches /api/v1/hierarchy through the service proxy
+func (s *ProxySource) GetHierarchy(ctx context.Context) ([]types.HierarchyNode, error) {
+	var nodes []apiv1.Node
+	if err := s.get(ctx, "/api/v1/hierarchy", &nodes); err != nil {
+		return nil, err
+	}
+	return apiv1.ToNodes(nodes), nil
+}
+
+// GetAllHealthData fetches /api/v1/state through the service proxy
+func (s *ProxySource) GetAllHealthData(ctx context.Context) ([]*types.ServiceHealthInfo, error) {
+	var state types.State
+	if err := s.get(ctx, "/api/v1/state", &state); err != nil {
+		return nil, err
+	}
+	return state.Services, nil
+}
+
+func (s *ProxySource) get(ctx context.Context, path string, v any) error {
+	data, err := s.Services.ProxyGet("http", s.Name, s.Port, path, nil).DoRaw(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to reach constellation service %s:%s through the apiserver: %w", s.Name, s.Port, err)
+	}
+	return json.Unmarshal(data, v)
+}
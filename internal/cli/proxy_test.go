@@ -0,0 +1,109 @@
+package cli_test
+
+import (
+	"context"
+	"errors"
+	"net/http"
+	"net/http/httptest"
+	"reflect"
+	"strings"
+	"testing"
+
+	apierrors "k8s.io/apimachinery/pkg/api/errors"
+	"k8s.io/client-go/kubernetes"
+	"k8s.io/client-go/rest"
+
+	"github.com/kdwils/constellation/internal/cli"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const proxyPath = "/api/v1/namespaces/constellation/services/http:constellation:8080/proxy"
+
+func TestProxySource(t *testing.T) {
+	type request struct {
+		method        string
+		path          string
+		authorization string
+	}
+	tests := []struct {
+		name     string
+		status   int
+		body     string
+		get      func(ctx context.Context, source *cli.ProxySource) (any, error)
+		want     any
+		wantReq  request
+		wantCode int32
+	}{
+		{
+			name:   "hierarchy is read through the service proxy",
+			status: http.StatusOK,
+			body:   `[{"kind":"Namespace","name":"shop","id":"Namespace/shop"}]`,
+			get: func(ctx context.Context, source *cli.ProxySource) (any, error) {
+				return source.GetHierarchy(ctx)
+			},
+			want:    []types.HierarchyNode{{Kind: types.ResourceKindNamespace, Name: "shop", ID: "Namespace/shop"}},
+			wantReq: request{method: http.MethodGet, path: proxyPath + "/api/v1/hierarchy", authorization: "Bearer token"},
+		},
+		{
+			name:   "state is read through the service proxy",
+			status: http.StatusOK,
+			body:   `{"services":[{"service_name":"web","namespace":"shop","status":"healthy"}]}`,
+			get: func(ctx context.Context, source *cli.ProxySource) (any, error) {
+				return source.GetAllHealthData(ctx)
+			},
+			want: []*types.ServiceHealthInfo{
+				{ServiceName: "web", Namespace: "shop", Status: types.HealthStatusHealthy},
+			},
+			wantReq: request{method: http.MethodGet, path: proxyPath + "/api/v1/state", authorization: "Bearer token"},
+		},
+		{
+			name:   "unreachable upstream is returned as a bad gateway",
+			status: http.StatusBadGateway,
+			body:   `Error trying to reach service: dial tcp 10.0.0.1:8080: connect: connection refused`,
+			get: func(ctx context.Context, source *cli.ProxySource) (any, error) {
+				return source.GetHierarchy(ctx)
+			},
+			wantReq:  request{method: http.MethodGet, path: proxyPath + "/api/v1/hierarchy", authorization: "Bearer token"},
+			wantCode: http.StatusBadGateway,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var got request
+			apiserver := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+				got = request{method: r.Method, path: r.URL.Path, authorization: r.Header.Get("Authorization")}
+				w.WriteHeader(tt.status)
+				w.Write([]byte(tt.body)) //nolint:errcheck
+			}))
+			defer apiserver.Close()
+
+			clientset, err := kubernetes.NewForConfig(&rest.Config{Host: apiserver.URL, BearerToken: "token"})
+			if err != nil {
+				t.Fatalf("TestProxySource() client error = %v", err)
+			}
+			source := &cli.ProxySource{Services: clientset.CoreV1().Services("constellation"), Name: "constellation", Port: "8080"}
+
+			result, err := tt.get(context.Background(), source)
+			if got != tt.wantReq {
+				t.Errorf("TestProxySource() request = %+v, want %+v", got, tt.wantReq)
+			}
+			if tt.wantCode != 0 {
+				var status apierrors.APIStatus
+				if !errors.As(err, &status) || status.Status().Code != tt.wantCode {
+					t.Fatalf("TestProxySource() error = %v, want status %d", err, tt.wantCode)
+				}
+				prefix := "failed to reach constellation service constellation:8080 through the apiserver: "
+				if !strings.HasPrefix(err.Error(), prefix) {
+					t.Errorf("TestProxySource() error = %q, want prefix %q", err.Error(), prefix)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("TestProxySource() error = %v", err)
+			}
+			if !reflect.DeepEqual(result, tt.want) {
+				t.Errorf("TestProxySource() = %+v, want %+v", result, tt.want)
+			}
+		})
+	}
+}
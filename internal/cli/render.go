@@ -0,0 +1,151 @@
+package cli
+
+import (
+	"fmt"
+	"io"
+	"sort"
+	"strings"
+	"text/tabwriter"
+	"time"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// WriteTree prints the hierarchy as an ASCII tree
+func WriteTree(w io.Writer, nodes []types.HierarchyNode) error {
+	for _, node := range nodes {
+		if _, err := fmt.Fprintln(w, describe(node)); err != nil {
+			return err
+		}
+		if err := writeRelatives(w, node.Relatives, ""); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+func writeRelatives(w io.Writer, nodes []types.HierarchyNode, prefix string) error {
+	for i, node := range nodes {
+		branch, indent := "├── ", "│   "
+		if i == len(nodes)-1 {
+			branch, indent = "└── ", "    "
+		}
+		if _, err := fmt.Fprintln(w, prefix+branch+describe(node)); err != nil {
+			return err
+		}
+		if err := writeRelatives(w, node.Relatives, prefix+indent); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+func describe(node types.HierarchyNode) string {
+	label := fmt.Sprintf("%s/%s", node.Kind, node.Name)
+	switch {
+	case node.TrafficPercent != nil:
+		return fmt.Sprintf("%s (%.0f%%)", label, *node.TrafficPercent)
+	case node.HealthInfo != nil:
+		return fmt.Sprintf("%s [%s]", label, node.HealthInfo.Status)
+	case node.Phase != nil:
+		return fmt.Sprintf("%s [%s]", label, *node.Phase)
+	case len(node.Hostnames) > 0:
+		return fmt.Sprintf("%s (%s)", label, strings.Join(node.Hostnames, ", "))
+	}
+	return label
+}
+
+// WriteGet prints every node of the given kind as a table. Kind matching is case-insensitive
+// and accepts plurals, so "pods" and "Pod" are equivalent.
+func WriteGet(w io.Writer, nodes []types.HierarchyNode, kind string) error {
+	matched := collectKind(nodes, kind)
+	if len(matched) == 0 {
+		_, err := fmt.Fprintf(w, "No %s resources found.\n", kind)
+		return err
+	}
+
+	tw := tabwriter.NewWriter(w, 0, 0, 3, ' ', 0)
+	if _, err := fmt.Fprintln(tw, "NAMESPACE\tNAME\tSTATUS"); err != nil {
+		return err
+	}
+	for _, node := range matched {
+		namespace := ""
+		if node.Namespace != nil {
+			namespace = *node.Namespace
+		}
+		if _, err := fmt.Fprintf(tw, "%s\t%s\t%s\n", namespace, node.Name, status(node)); err != nil {
+			return err
+		}
+	}
+	return tw.Flush()
+}
+
+// collectKind walks the tree and returns each matching node once, since a pod can sit under
+// several services
+func collectKind(nodes []types.HierarchyNode, kind string) []types.HierarchyNode {
+	seen := make(map[string]bool)
+	var matched []types.HierarchyNode
+	var walk func([]types.HierarchyNode)
+	walk = func(nodes []types.HierarchyNode) {
+		for _, node := range nodes {
+			key := fmt.Sprintf("%s/%s", node.Kind, node.Name)
+			if node.Namespace != nil {
+				key = fmt.Sprintf("%s/%s/%s", node.Kind, *node.Namespace, node.Name)
+			}
+			if kindMatches(node.Kind, kind) && !seen[key] {
+				seen[key] = true
+				matched = append(matched, node)
+			}
+			walk(node.Relatives)
+		}
+	}
+	walk(nodes)
+	return matched
+}
+
+func kindMatches(kind types.ResourceKind, query string) bool {
+	query = strings.ToLower(query)
+	want := strings.ToLower(string(kind))
+	return query == want || query == want+"s"
+}
+
+func status(node types.HierarchyNode) string {
+	switch {
+	case node.TrafficPercent != nil:
+		return fmt.Sprintf("%s (%.0f%%)", label, *node.TrafficPercent)
+	case node.HealthInfo != nil:
+		return string(node.HealthInfo.Status)
+	case node.Phase != nil:
+		return *node.Phase
+	case node.ServiceType != nil:
+		return *node.ServiceType
+	}
+	return "-"
+}
+
+// WriteTop prints health check results ordered by most recent latency, slowest first
+func WriteTop(w io.Writer, health []*types.ServiceHealthInfo) error {
+	sorted := append([]*types.ServiceHealthInfo(nil), health...)
+	sort.SliceStable(sorted, func(i, j int) bool {
+		return lastLatency(sorted[i]) > lastLatency(sorted[j])
+	})
+
+	tw := tabwriter.NewWriter(w, 0, 0, 3, ' ', 0)
+	if _, err := fmt.Fprintln(tw, "NAMESPACE\tSERVICE\tSTATUS\tLATENCY\tUPTIME"); err != nil {
+		return err
+	}
+	for _, info := range sorted {
+		if _, err := fmt.Fprintf(tw, "%s\t%s\t%s\t%s\t%.1f%%\n",
+			info.Namespace, info.ServiceName, info.Status, lastLatency(info), info.Uptime); err != nil {
+			return err
+		}
+	}
+	return tw.Flush()
+}
+
+func lastLatency(info *types.ServiceHealthInfo) time.Duration {
+	if len(info.History) == 0 {
+		return 0
+	}
+	return info.History[len(info.History)-1].Latency
+}
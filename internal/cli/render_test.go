@@ -0,0 +1,86 @@
+package cli_test
+
+import (
+	"bytes"
+	"testing"
+
+	"k8s.io/utils/ptr"
+
+	"github.com/kdwils/constellation/internal/cli"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func testHierarchy() []types.HierarchyNode {
+	pod := types.HierarchyNode{Kind: types.ResourceKindPod, Name: "web-0", Namespace: ptr.To("shop"), Phase: ptr.To("Running")}
+	return []types.HierarchyNode{
+		{
+			Kind: types.ResourceKindNamespace,
+			Name: "shop",
+			Relatives: []types.HierarchyNode{
+				{
+					Kind:      types.ResourceKindHTTPRoute,
+					Name:      "storefront",
+					Namespace: ptr.To("shop"),
+					Hostnames: []string{"shop.example.com"},
+					Relatives: []types.HierarchyNode{
+						{Kind: types.ResourceKindService, Name: "web", Namespace: ptr.To("shop"), Relatives: []types.HierarchyNode{pod}},
+					},
+				},
+				{Kind: types.ResourceKindService, Name: "web-canary", Namespace: ptr.To("shop"), Relatives: []types.HierarchyNode{pod}},
+			},
+		},
+	}
+}
+
+func TestWriteTree(t *testing.T) {
+	want := `Namespace/shop
+├── HTTPRoute/storefront (shop.example.com)
+│   └── Service/web
+│       └── Pod/web-0 [Running]
+└── Service/web-canary
+    └── Pod/web-0 [Running]
+`
+
+	var buf bytes.Buffer
+	if err := cli.WriteTree(&buf, testHierarchy()); err != nil {
+		t.Fatalf("TestWriteTree() error = %v", err)
+	}
+	if buf.String() != want {
+		t.Errorf("TestWriteTree() = \n%s, want \n%s", buf.String(), want)
+	}
+}
+
+func TestWriteGet(t *testing.T) {
+	tests := []struct {
+		name string
+		kind string
+		want string
+	}{
+		{
+			name: "plural kind deduplicates shared pods",
+			kind: "pods",
+			want: "NAMESPACE   NAME    STATUS\nshop        web-0   Running\n",
+		},
+		{
+			name: "case insensitive kind",
+			kind: "service",
+			want: "NAMESPACE   NAME         STATUS\nshop        web          -\nshop        web-canary   -\n",
+		},
+		{
+			name: "no matches",
+			kind: "deployment",
+			want: "No deployment resources found.\n",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var buf bytes.Buffer
+			if err := cli.WriteGet(&buf, testHierarchy(), tt.kind); err != nil {
+				t.Fatalf("TestWriteGet() error = %v", err)
+			}
+			if buf.String() != tt.want {
+				t.Errorf("TestWriteGet() = %q, want %q", buf.String(), tt.want)
+			}
+		})
+	}
+}
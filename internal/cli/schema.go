@@ -0,0 +1,32 @@
+package cli
+
+import (
+	"encoding/json"
+	"flag"
+	"io"
+	"reflect"
+
+	"github.com/kdwils/constellation/internal/schema"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// runSchema writes the JSON Schema of the state payload, or TypeScript definitions of every wire type
+// with --typescript, e.g. schema --typescript > frontend/src/api.gen.ts
+func runSchema(args []string, out io.Writer) error {
+	fs := flag.NewFlagSet("schema", flag.ContinueOnError)
+	typescript := fs.Bool("typescript", false, "write TypeScript definitions of the API types")
+	if err := fs.Parse(args); err != nil {
+		return err
+	}
+	if *typescript {
+		return schema.TypeScript(out, schema.Types...)
+	}
+
+	document, err := schema.JSONSchema(reflect.TypeFor[types.State]())
+	if err != nil {
+		return err
+	}
+	encoder := json.NewEncoder(out)
+	encoder.SetIndent("", "  ")
+	return encoder.Encode(document)
+}
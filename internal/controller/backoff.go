@@ -0,0 +1,27 @@
+package controller
+
+import (
+	"time"
+
+	"golang.org/x/time/rate"
+	"k8s.io/client-go/util/workqueue"
+	"sigs.k8s.io/controller-runtime/pkg/reconcile"
+)
+
+// Backoff is how long a failed reconcile waits before it is retried, doubling per object from Base up to Max
+type Backoff struct {
+	Base time.Duration
+	Max  time.Duration
+}
+
+// RateLimiter returns the retry rate limiter for b, or nil for controller-runtime's default of 5ms
+// doubling up to 1000s. Like the default, retries are also capped at 10 per second overall.
+func (b *Backoff) RateLimiter() workqueue.TypedRateLimiter[reconcile.Request] {
+	if b == nil {
+		return nil
+	}
+	return workqueue.NewTypedMaxOfRateLimiter(
+		workqueue.NewTypedItemExponentialFailureRateLimiter[reconcile.Request](b.Base, b.Max),
+		&workqueue.TypedBucketRateLimiter[reconcile.Request]{Limiter: rate.NewLimiter(rate.Limit(10), 100)},
+	)
+}
@@ -0,0 +1,47 @@
+package controller_test
+
+import (
+	"reflect"
+	"testing"
+	"time"
+
+	"k8s.io/apimachinery/pkg/types"
+	"sigs.k8s.io/controller-runtime/pkg/reconcile"
+
+	"github.com/kdwils/constellation/internal/controller"
+)
+
+func TestBackoff_RateLimiter(t *testing.T) {
+	tests := []struct {
+		name    string
+		backoff controller.Backoff
+		want    []time.Duration
+	}{
+		{
+			name:    "delay doubles up to the maximum",
+			backoff: controller.Backoff{Base: time.Second, Max: 5 * time.Second},
+			want:    []time.Duration{time.Second, 2 * time.Second, 4 * time.Second, 5 * time.Second},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			limiter := tt.backoff.RateLimiter()
+			request := reconcile.Request{NamespacedName: types.NamespacedName{Namespace: "default", Name: "web"}}
+
+			var got []time.Duration
+			for range tt.want {
+				got = append(got, limiter.When(request))
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestBackoff_RateLimiter() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestBackoff_RateLimiterDefault(t *testing.T) {
+	var backoff *controller.Backoff
+	if got := backoff.RateLimiter(); got != nil {
+		t.Errorf("TestBackoff_RateLimiterDefault() = %v, want nil", got)
+	}
+}
@@ -0,0 +1,42 @@
+package controller_test
+
+import (
+	"context"
+	"fmt"
+	"testing"
+
+	"sigs.k8s.io/controller-runtime/pkg/client"
+
+	"github.com/kdwils/constellation/internal/controller/controllertest"
+	"github.com/kdwils/constellation/internal/synthetic"
+)
+
+// BenchmarkApply measures one pod event through the pipeline: writing it to the store and reconciling
+// every Service in its namespace against the namespace's pods
+func BenchmarkApply(b *testing.B) {
+	for _, pods := range synthetic.Sizes {
+		objects := synthetic.Objects(pods)
+		seed := make([]client.Object, 0, len(objects.Services)+len(objects.Pods))
+		for i := range objects.Services {
+			seed = append(seed, &objects.Services[i])
+		}
+		for i := range objects.Pods {
+			seed = append(seed, &objects.Pods[i])
+		}
+
+		b.Run(fmt.Sprintf("pods=%d", pods), func(b *testing.B) {
+			h := controllertest.NewHarness(seed...)
+			ctx := context.Background()
+			b.ReportAllocs()
+			i := 0
+			for b.Loop() {
+				pod := synthetic.Pod(i % pods)
+				event := controllertest.Event{Type: controllertest.EventModified, Object: &pod}
+				if err := h.Apply(ctx, event); err != nil {
+					b.Fatalf("BenchmarkApply() error = %v", err)
+				}
+				i++
+			}
+		})
+	}
+}
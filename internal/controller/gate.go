@@ -0,0 +1,61 @@
+package controller
+
+import (
+	"sync"
+
+	"github.com/kdwils/constellation/internal/healthcheck"
+)
+
+// RegistryGate holds back the reconcilers' health target changes until the initial state has been
+// built from a full list, then replays them in order. Without it a change reconciled while the list
+// is being processed, such as a deletion, could be overwritten by the older listed object.
+type RegistryGate struct {
+	registry HealthTargetRegistry
+
+	mu      sync.Mutex
+	open    bool
+	pending []func(HealthTargetRegistry)
+}
+
+// NewRegistryGate creates a closed gate in front of registry
+func NewRegistryGate(registry HealthTargetRegistry) *RegistryGate {
+	return &RegistryGate{registry: registry}
+}
+
+// RegisterHealthTarget forwards the registration, or queues it while the gate is closed
+func (g *RegistryGate) RegisterHealthTarget(name string, checks []healthcheck.CheckConfig) {
+	g.apply(func(registry HealthTargetRegistry) {
+		registry.RegisterHealthTarget(name, checks)
+	})
+}
+
+// UnregisterHealthTarget forwards the removal, or queues it while the gate is closed
+func (g *RegistryGate) UnregisterHealthTarget(name string) {
+	g.apply(func(registry HealthTargetRegistry) {
+		registry.UnregisterHealthTarget(name)
+	})
+}
+
+// Open replays the queued changes and forwards every later change directly
+func (g *RegistryGate) Open() {
+	g.mu.Lock()
+	defer g.mu.Unlock()
+
+	for _, change := range g.pending {
+		change(g.registry)
+	}
+	g.pending = nil
+	g.open = true
+}
+
+// apply holds the lock while forwarding so a change cannot overtake the replay
+func (g *RegistryGate) apply(change func(HealthTargetRegistry)) {
+	g.mu.Lock()
+	defer g.mu.Unlock()
+
+	if !g.open {
+		g.pending = append(g.pending, change)
+		return
+	}
+	change(g.registry)
+}
@@ -0,0 +1,87 @@
+package controller_test
+
+import (
+	"reflect"
+	"sort"
+	"testing"
+
+	"github.com/kdwils/constellation/internal/controller"
+	"github.com/kdwils/constellation/internal/controller/controllertest"
+	"github.com/kdwils/constellation/internal/healthcheck"
+)
+
+func TestRegistryGate_Open(t *testing.T) {
+	checks := []healthcheck.CheckConfig{{Name: "default/web", URL: "http://web.default.svc.cluster.local:80/healthz"}}
+
+	tests := []struct {
+		name       string
+		initial    func(registry *controllertest.Registry)
+		gated      func(gate *controller.RegistryGate)
+		wantBefore []string
+		wantAfter  []string
+	}{
+		{
+			name:    "registration is held until open",
+			initial: func(registry *controllertest.Registry) {},
+			gated: func(gate *controller.RegistryGate) {
+				gate.RegisterHealthTarget("default/web", checks)
+			},
+			wantBefore: []string{},
+			wantAfter:  []string{"default/web"},
+		},
+		{
+			name: "deletion during the initial build is not undone by the listed object",
+			initial: func(registry *controllertest.Registry) {
+				registry.RegisterHealthTarget("default/web", checks)
+				registry.RegisterHealthTarget("default/api", checks)
+			},
+			gated: func(gate *controller.RegistryGate) {
+				gate.UnregisterHealthTarget("default/web")
+			},
+			wantBefore: []string{"default/api", "default/web"},
+			wantAfter:  []string{"default/api"},
+		},
+		{
+			name:    "changes replay in order",
+			initial: func(registry *controllertest.Registry) {},
+			gated: func(gate *controller.RegistryGate) {
+				gate.RegisterHealthTarget("default/web", checks)
+				gate.UnregisterHealthTarget("default/web")
+				gate.RegisterHealthTarget("default/api", checks)
+			},
+			wantBefore: []string{},
+			wantAfter:  []string{"default/api"},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			registry := controllertest.NewRegistry()
+			gate := controller.NewRegistryGate(registry)
+
+			tt.gated(gate)
+			tt.initial(registry)
+			if got := targetNames(registry); !reflect.DeepEqual(got, tt.wantBefore) {
+				t.Errorf("TestRegistryGate_Open() before open = %v, want %v", got, tt.wantBefore)
+			}
+
+			gate.Open()
+			if got := targetNames(registry); !reflect.DeepEqual(got, tt.wantAfter) {
+				t.Errorf("TestRegistryGate_Open() after open = %v, want %v", got, tt.wantAfter)
+			}
+
+			gate.UnregisterHealthTarget("default/api")
+			if _, exists := registry.Targets()["default/api"]; exists {
+				t.Errorf("TestRegistryGate_Open() change after open was not forwarded")
+			}
+		})
+	}
+}
+
+func targetNames(registry *controllertest.Registry) []string {
+	names := []string{}
+	for name := range registry.Targets() {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	return names
+}
@@ -36,7 +36,7 @@ const healthCheckFinalizer = "health.kyledev.co/finalizer"
 type HealthCheckReconciler struct {
 	client.Client
 	Scheme        *runtime.Scheme
-	HealthChecker *healthcheck.HealthChecker
+	HealthChecker HealthTargetRegistry
 }
 
 // +kubebuilder:rbac:groups=health.kyledev.co,resources=healthchecks,verbs=get;list;watch;create;update;patch;delete
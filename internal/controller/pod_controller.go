@@ -4,23 +4,29 @@ import (
 	"context"
 	"fmt"
 
-	"github.com/kdwils/constellation/internal/healthcheck"
 	corev1 "k8s.io/api/core/v1"
 	"k8s.io/apimachinery/pkg/runtime"
 	ctrl "sigs.k8s.io/controller-runtime"
 	"sigs.k8s.io/controller-runtime/pkg/client"
+	crcontroller "sigs.k8s.io/controller-runtime/pkg/controller"
 	"sigs.k8s.io/controller-runtime/pkg/log"
+
+	"github.com/kdwils/constellation/internal/types"
 )
 
 // PodReconciler reconciles Pod objects
 type PodReconciler struct {
 	client.Client
 	Scheme        *runtime.Scheme
-	HealthChecker *healthcheck.HealthChecker
+	HealthChecker HealthTargetRegistry
+	// Sync is optional and observes every Pod event
+	Sync SyncObserver
+	// Backoff is optional and replaces the default delay before a failed reconcile is retried
+	Backoff *Backoff
 }
 
 // NewPodReconciler creates a new PodReconciler
-func NewPodReconciler(mgr ctrl.Manager, healthChecker *healthcheck.HealthChecker) *PodReconciler {
+func NewPodReconciler(mgr ctrl.Manager, healthChecker HealthTargetRegistry) *PodReconciler {
 	return &PodReconciler{
 		Client:        mgr.GetClient(),
 		Scheme:        mgr.GetScheme(),
@@ -42,6 +48,7 @@ func (r *PodReconciler) Reconcile(ctx context.Context, req ctrl.Request) (ctrl.R
 			return ctrl.Result{}, err
 		}
 	}
+	observe(r.Sync, types.ResourceKindPod, pod.ResourceVersion)
 
 	var services corev1.ServiceList
 	if err := r.List(ctx, &services, client.InNamespace(req.Namespace)); err != nil {
@@ -64,7 +71,7 @@ func (r *PodReconciler) Reconcile(ctx context.Context, req ctrl.Request) (ctrl.R
 			continue
 		}
 
-		checks := extractHealthChecksFromPods(service, pods.Items)
+		checks := ExtractHealthChecksFromPods(service, pods.Items)
 		if len(checks) > 0 {
 			serviceKey := fmt.Sprintf("%s/%s", service.Namespace, service.Name)
 			logger.Info("updating health check from pod change", "service", serviceKey, "pod", req.Name, "checks", len(checks))
@@ -80,5 +87,6 @@ func (r *PodReconciler) SetupWithManager(mgr ctrl.Manager) error {
 	return ctrl.NewControllerManagedBy(mgr).
 		For(&corev1.Pod{}).
 		Named("pod").
+		WithOptions(crcontroller.Options{RateLimiter: r.Backoff.RateLimiter()}).
 		Complete(r)
 }
@@ -7,24 +7,41 @@ import (
 	"time"
 
 	"github.com/kdwils/constellation/internal/healthcheck"
+	"github.com/kdwils/constellation/internal/types"
 	corev1 "k8s.io/api/core/v1"
 	"k8s.io/apimachinery/pkg/runtime"
 	ctrl "sigs.k8s.io/controller-runtime"
 	"sigs.k8s.io/controller-runtime/pkg/client"
+	crcontroller "sigs.k8s.io/controller-runtime/pkg/controller"
 	"sigs.k8s.io/controller-runtime/pkg/log"
 )
 
 const ignoreAnnotation = "constellation.kyledev.co/ignore"
 
+// HealthTargetRegistry receives the health checks discovered by the reconcilers
+type HealthTargetRegistry interface {
+	RegisterHealthTarget(name string, checks []healthcheck.CheckConfig)
+	UnregisterHealthTarget(name string)
+}
+
+// SyncObserver is told about every reconciled event so API responses can report how current they are
+type SyncObserver interface {
+	Observe(kind types.ResourceKind, resourceVersion string)
+}
+
 // ServiceReconciler reconciles Service objects
 type ServiceReconciler struct {
 	client.Client
 	Scheme        *runtime.Scheme
-	HealthChecker *healthcheck.HealthChecker
+	HealthChecker HealthTargetRegistry
+	// Sync is optional and observes every Service event
+	Sync SyncObserver
+	// Backoff is optional and replaces the default delay before a failed reconcile is retried
+	Backoff *Backoff
 }
 
 // NewServiceReconciler creates a new ServiceReconciler
-func NewServiceReconciler(mgr ctrl.Manager, healthChecker *healthcheck.HealthChecker) *ServiceReconciler {
+func NewServiceReconciler(mgr ctrl.Manager, healthChecker HealthTargetRegistry) *ServiceReconciler {
 	return &ServiceReconciler{
 		Client:        mgr.GetClient(),
 		Scheme:        mgr.GetScheme(),
@@ -42,6 +59,7 @@ func (r *ServiceReconciler) Reconcile(ctx context.Context, req ctrl.Request) (ct
 	var service corev1.Service
 	if err := r.Get(ctx, req.NamespacedName, &service); err != nil {
 		if client.IgnoreNotFound(err) == nil {
+			observe(r.Sync, types.ResourceKindService, "")
 			serviceKey := fmt.Sprintf("%s/%s", req.Namespace, req.Name)
 			logger.Info("service deleted, unregistering health check", "service", serviceKey)
 			r.HealthChecker.UnregisterHealthTarget(serviceKey)
@@ -50,6 +68,7 @@ func (r *ServiceReconciler) Reconcile(ctx context.Context, req ctrl.Request) (ct
 		logger.Error(err, "failed to get service")
 		return ctrl.Result{}, err
 	}
+	observe(r.Sync, types.ResourceKindService, service.ResourceVersion)
 
 	if shouldIgnoreResource(service.Annotations) {
 		return ctrl.Result{}, nil
@@ -61,7 +80,7 @@ func (r *ServiceReconciler) Reconcile(ctx context.Context, req ctrl.Request) (ct
 		return ctrl.Result{}, err
 	}
 
-	checks := extractHealthChecksFromPods(service, pods.Items)
+	checks := ExtractHealthChecksFromPods(service, pods.Items)
 	if len(checks) > 0 {
 		serviceKey := fmt.Sprintf("%s/%s", service.Namespace, service.Name)
 		logger.Info("registering discovered service health check", "identifier", serviceKey, "checks", len(checks))
@@ -71,8 +90,15 @@ func (r *ServiceReconciler) Reconcile(ctx context.Context, req ctrl.Request) (ct
 	return ctrl.Result{}, nil
 }
 
-// extractHealthChecksFromPods extracts health check configurations from pod liveness probes
-func extractHealthChecksFromPods(service corev1.Service, pods []corev1.Pod) []healthcheck.CheckConfig {
+func observe(observer SyncObserver, kind types.ResourceKind, resourceVersion string) {
+	if observer == nil {
+		return
+	}
+	observer.Observe(kind, resourceVersion)
+}
+
+// ExtractHealthChecksFromPods extracts health check configurations from pod liveness probes
+func ExtractHealthChecksFromPods(service corev1.Service, pods []corev1.Pod) []healthcheck.CheckConfig {
 	checkName := fmt.Sprintf("%s/%s", service.Namespace, service.Name)
 	var checks []healthcheck.CheckConfig
 	seenURLs := make(map[string]bool)
@@ -210,5 +236,6 @@ func (r *ServiceReconciler) SetupWithManager(mgr ctrl.Manager) error {
 	return ctrl.NewControllerManagedBy(mgr).
 		For(&corev1.Service{}).
 		Named("service").
+		WithOptions(crcontroller.Options{RateLimiter: r.Backoff.RateLimiter()}).
 		Complete(r)
 }
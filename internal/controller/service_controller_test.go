@@ -0,0 +1,213 @@
+package controller_test
+
+import (
+	"context"
+	"errors"
+	"slices"
+	"testing"
+
+	corev1 "k8s.io/api/core/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"k8s.io/apimachinery/pkg/util/intstr"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	"sigs.k8s.io/controller-runtime/pkg/client/fake"
+	"sigs.k8s.io/controller-runtime/pkg/client/interceptor"
+
+	"github.com/kdwils/constellation/internal/controller/controllertest"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func testService() *corev1.Service {
+	return &corev1.Service{
+		ObjectMeta: metav1.ObjectMeta{Name: "web", Namespace: "default"},
+		Spec: corev1.ServiceSpec{
+			Selector: map[string]string{"app": "web"},
+			Ports:    []corev1.ServicePort{{Port: 80, TargetPort: intstr.FromInt32(8080)}},
+		},
+	}
+}
+
+func testPod() *corev1.Pod {
+	return &corev1.Pod{
+		ObjectMeta: metav1.ObjectMeta{Name: "web-0", Namespace: "default", Labels: map[string]string{"app": "web"}},
+		Spec: corev1.PodSpec{
+			Containers: []corev1.Container{{
+				Name:  "web",
+				Ports: []corev1.ContainerPort{{ContainerPort: 8080}},
+				LivenessProbe: &corev1.Probe{
+					ProbeHandler: corev1.ProbeHandler{
+						HTTPGet: &corev1.HTTPGetAction{Path: "/healthz", Port: intstr.FromInt32(8080), Scheme: corev1.URISchemeHTTP},
+					},
+					PeriodSeconds:  10,
+					TimeoutSeconds: 1,
+				},
+			}},
+		},
+		Status: corev1.PodStatus{Phase: corev1.PodRunning},
+	}
+}
+
+func TestHarness_Run(t *testing.T) {
+	tests := []struct {
+		name        string
+		services    []controllertest.Event
+		pods        []controllertest.Event
+		nodes       []controllertest.Event
+		wantTargets map[string]string
+		wantNodes   []string
+	}{
+		{
+			name:     "pod added after service registers check",
+			services: []controllertest.Event{{Type: controllertest.EventAdded, Object: testService()}},
+			pods:     []controllertest.Event{{Type: controllertest.EventAdded, Object: testPod()}},
+			wantTargets: map[string]string{
+				"default/web": "http://web.default.svc.cluster.local:80/healthz",
+			},
+		},
+		{
+			name: "deleted service unregisters check",
+			services: []controllertest.Event{
+				{Type: controllertest.EventAdded, Object: testService()},
+				{Type: controllertest.EventDeleted, Object: testService()},
+			},
+			pods:        []controllertest.Event{{Type: controllertest.EventAdded, Object: testPod()}},
+			wantTargets: map[string]string{},
+		},
+		{
+			name:     "streams of kinds outside the fixed order are drained",
+			services: []controllertest.Event{{Type: controllertest.EventAdded, Object: testService()}},
+			nodes: []controllertest.Event{{
+				Type:   controllertest.EventAdded,
+				Object: &corev1.Node{ObjectMeta: metav1.ObjectMeta{Name: "node-a"}},
+			}},
+			wantTargets: map[string]string{
+				"default/web": "http://web.default.svc.cluster.local:80/healthz",
+			},
+			wantNodes: []string{"node-a"},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := controllertest.NewHarness()
+			streams := map[types.ResourceKind]<-chan controllertest.Event{
+				types.ResourceKindService: feed(tt.services),
+				types.ResourceKindPod:     feed(tt.pods),
+				types.ResourceKindNode:    feed(tt.nodes),
+			}
+
+			if err := h.Run(context.Background(), streams); err != nil {
+				t.Fatalf("TestHarness_Run() error = %v", err)
+			}
+
+			var nodes corev1.NodeList
+			if err := h.Client.List(context.Background(), &nodes); err != nil {
+				t.Fatalf("TestHarness_Run() list error = %v", err)
+			}
+			gotNodes := make([]string, 0, len(nodes.Items))
+			for _, node := range nodes.Items {
+				gotNodes = append(gotNodes, node.Name)
+			}
+			if !slices.Equal(gotNodes, tt.wantNodes) {
+				t.Errorf("TestHarness_Run() nodes = %v, want %v", gotNodes, tt.wantNodes)
+			}
+
+			targets := h.Registry.Targets()
+			if len(targets) != len(tt.wantTargets) {
+				t.Fatalf("TestHarness_Run() targets = %v, want %v", targets, tt.wantTargets)
+			}
+			for name, wantURL := range tt.wantTargets {
+				checks := targets[name]
+				if len(checks) != 1 {
+					t.Fatalf("TestHarness_Run() target %s checks = %v, want 1", name, checks)
+				}
+				if checks[0].URL != wantURL {
+					t.Errorf("TestHarness_Run() target %s url = %v, want %v", name, checks[0].URL, wantURL)
+				}
+			}
+		})
+	}
+}
+
+func TestNewHarnessWithClient(t *testing.T) {
+	failPodLists := interceptor.Funcs{
+		List: func(ctx context.Context, c client.WithWatch, list client.ObjectList, opts ...client.ListOption) error {
+			if _, ok := list.(*corev1.PodList); ok {
+				return errors.New("pod store unavailable")
+			}
+			return c.List(ctx, list, opts...)
+		},
+	}
+
+	tests := []struct {
+		name        string
+		client      client.Client
+		event       controllertest.Event
+		wantErr     string
+		wantTargets map[string]string
+	}{
+		{
+			name: "pod change registers checks from the prebuilt service and pod stores",
+			client: fake.NewClientBuilder().WithScheme(controllertest.NewScheme()).
+				WithObjects(testService(), testPod()).Build(),
+			event: controllertest.Event{Type: controllertest.EventModified, Object: testPod()},
+			wantTargets: map[string]string{
+				"default/web": "http://web.default.svc.cluster.local:80/healthz",
+			},
+		},
+		{
+			name: "service change reads pods from the prebuilt store",
+			client: fake.NewClientBuilder().WithScheme(controllertest.NewScheme()).
+				WithObjects(testService(), testPod()).Build(),
+			event: controllertest.Event{Type: controllertest.EventModified, Object: testService()},
+			wantTargets: map[string]string{
+				"default/web": "http://web.default.svc.cluster.local:80/healthz",
+			},
+		},
+		{
+			name: "failing pod store",
+			client: fake.NewClientBuilder().WithScheme(controllertest.NewScheme()).
+				WithObjects(testService(), testPod()).WithInterceptorFuncs(failPodLists).Build(),
+			event:       controllertest.Event{Type: controllertest.EventModified, Object: testService()},
+			wantErr:     "pod store unavailable",
+			wantTargets: map[string]string{},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := controllertest.NewHarnessWithClient(tt.client)
+
+			err := h.Apply(context.Background(), tt.event)
+			if tt.wantErr != "" {
+				if err == nil || err.Error() != tt.wantErr {
+					t.Fatalf("TestNewHarnessWithClient() error = %v, want %v", err, tt.wantErr)
+				}
+			}
+			if tt.wantErr == "" && err != nil {
+				t.Fatalf("TestNewHarnessWithClient() error = %v", err)
+			}
+
+			targets := h.Registry.Targets()
+			if len(targets) != len(tt.wantTargets) {
+				t.Fatalf("TestNewHarnessWithClient() targets = %v, want %v", targets, tt.wantTargets)
+			}
+			for name, wantURL := range tt.wantTargets {
+				checks := targets[name]
+				if len(checks) != 1 {
+					t.Fatalf("TestNewHarnessWithClient() target %s checks = %v, want 1", name, checks)
+				}
+				if checks[0].URL != wantURL {
+					t.Errorf("TestNewHarnessWithClient() target %s url = %v, want %v", name, checks[0].URL, wantURL)
+				}
+			}
+		})
+	}
+}
+
+func feed(events []controllertest.Event) <-chan controllertest.Event {
+	ch := make(chan controllertest.Event, len(events))
+	for _, event := range events {
+		ch <- event
+	}
+	close(ch)
+	return ch
+}
@@ -0,0 +1,94 @@
+package controller
+
+import (
+	corev1 "k8s.io/api/core/v1"
+)
+
+// lastAppliedAnnotation holds a full copy of the applied manifest and is often the largest field on a pod
+const lastAppliedAnnotation = "kubectl.kubernetes.io/last-applied-configuration"
+
+// SlimPod is a cache transform that keeps a pod's metadata and a reduced spec and status projection:
+// scheduling fields, container names, images, ports and liveness probes, and the phase, conditions, IPs
+// and container states. Environment, resources, volumes, affinity and security contexts are dropped,
+// which cuts cache memory on clusters with tens of thousands of pods at the cost of the views built on them.
+func SlimPod(obj any) (any, error) {
+	pod, ok := obj.(*corev1.Pod)
+	if !ok {
+		return obj, nil
+	}
+
+	meta := *pod.ObjectMeta.DeepCopy()
+	meta.ManagedFields = nil
+	delete(meta.Annotations, lastAppliedAnnotation)
+
+	return &corev1.Pod{
+		TypeMeta:   pod.TypeMeta,
+		ObjectMeta: meta,
+		Spec: corev1.PodSpec{
+			InitContainers:    slimContainers(pod.Spec.InitContainers),
+			Containers:        slimContainers(pod.Spec.Containers),
+			NodeName:          pod.Spec.NodeName,
+			NodeSelector:      pod.Spec.NodeSelector,
+			Hostname:          pod.Spec.Hostname,
+			Subdomain:         pod.Spec.Subdomain,
+			HostNetwork:       pod.Spec.HostNetwork,
+			HostPID:           pod.Spec.HostPID,
+			HostIPC:           pod.Spec.HostIPC,
+			PriorityClassName: pod.Spec.PriorityClassName,
+			Priority:          pod.Spec.Priority,
+			OS:                pod.Spec.OS,
+		},
+		Status: corev1.PodStatus{
+			Phase:                 pod.Status.Phase,
+			Conditions:            pod.Status.Conditions,
+			Message:               pod.Status.Message,
+			Reason:                pod.Status.Reason,
+			HostIP:                pod.Status.HostIP,
+			PodIP:                 pod.Status.PodIP,
+			PodIPs:                pod.Status.PodIPs,
+			StartTime:             pod.Status.StartTime,
+			QOSClass:              pod.Status.QOSClass,
+			InitContainerStatuses: slimContainerStatuses(pod.Status.InitContainerStatuses),
+			ContainerStatuses:     slimContainerStatuses(pod.Status.ContainerStatuses),
+		},
+	}, nil
+}
+
+// slimContainers keeps what health checks, ports, sidecars and image pulls are read from
+func slimContainers(containers []corev1.Container) []corev1.Container {
+	if containers == nil {
+		return nil
+	}
+	slim := make([]corev1.Container, 0, len(containers))
+	for _, container := range containers {
+		slim = append(slim, corev1.Container{
+			Name:            container.Name,
+			Image:           container.Image,
+			ImagePullPolicy: container.ImagePullPolicy,
+			Ports:           container.Ports,
+			LivenessProbe:   container.LivenessProbe,
+			RestartPolicy:   container.RestartPolicy,
+		})
+	}
+	return slim
+}
+
+// slimContainerStatuses keeps what restarts, disruptions and image pull failures are read from
+func slimContainerStatuses(statuses []corev1.ContainerStatus) []corev1.ContainerStatus {
+	if statuses == nil {
+		return nil
+	}
+	slim := make([]corev1.ContainerStatus, 0, len(statuses))
+	for _, status := range statuses {
+		slim = append(slim, corev1.ContainerStatus{
+			Name:                 status.Name,
+			Image:                status.Image,
+			Ready:                status.Ready,
+			Started:              status.Started,
+			RestartCount:         status.RestartCount,
+			State:                status.State,
+			LastTerminationState: status.LastTerminationState,
+		})
+	}
+	return slim
+}
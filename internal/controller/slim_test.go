@@ -0,0 +1,100 @@
+package controller_test
+
+import (
+	"reflect"
+	"testing"
+
+	corev1 "k8s.io/api/core/v1"
+	"k8s.io/apimachinery/pkg/api/resource"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+
+	"github.com/kdwils/constellation/internal/controller"
+)
+
+func TestSlimPod(t *testing.T) {
+	tests := []struct {
+		name string
+		obj  any
+		want any
+	}{
+		{
+			name: "pod keeps metadata, scheduling, containers and status",
+			obj: &corev1.Pod{
+				ObjectMeta: metav1.ObjectMeta{
+					Name:      "web-0",
+					Namespace: "default",
+					Labels:    map[string]string{"app": "web"},
+					Annotations: map[string]string{
+						"kubectl.kubernetes.io/last-applied-configuration": "{}",
+						"owner": "payments",
+					},
+					ManagedFields: []metav1.ManagedFieldsEntry{{Manager: "kubectl"}},
+				},
+				Spec: corev1.PodSpec{
+					NodeName: "node-a",
+					Volumes:  []corev1.Volume{{Name: "data"}},
+					Containers: []corev1.Container{{
+						Name:  "web",
+						Image: "nginx:1.27",
+						Ports: []corev1.ContainerPort{{ContainerPort: 8080}},
+						Env:   []corev1.EnvVar{{Name: "MODE", Value: "production"}},
+						Resources: corev1.ResourceRequirements{
+							Requests: corev1.ResourceList{corev1.ResourceCPU: resource.MustParse("100m")},
+						},
+					}},
+				},
+				Status: corev1.PodStatus{
+					Phase: corev1.PodRunning,
+					PodIP: "10.0.0.1",
+					ContainerStatuses: []corev1.ContainerStatus{{
+						Name:         "web",
+						Ready:        true,
+						RestartCount: 2,
+						ImageID:      "docker.io/library/nginx@sha256:abc",
+					}},
+				},
+			},
+			want: &corev1.Pod{
+				ObjectMeta: metav1.ObjectMeta{
+					Name:        "web-0",
+					Namespace:   "default",
+					Labels:      map[string]string{"app": "web"},
+					Annotations: map[string]string{"owner": "payments"},
+				},
+				Spec: corev1.PodSpec{
+					NodeName: "node-a",
+					Containers: []corev1.Container{{
+						Name:  "web",
+						Image: "nginx:1.27",
+						Ports: []corev1.ContainerPort{{ContainerPort: 8080}},
+					}},
+				},
+				Status: corev1.PodStatus{
+					Phase: corev1.PodRunning,
+					PodIP: "10.0.0.1",
+					ContainerStatuses: []corev1.ContainerStatus{{
+						Name:         "web",
+						Ready:        true,
+						RestartCount: 2,
+					}},
+				},
+			},
+		},
+		{
+			name: "other objects pass through",
+			obj:  &corev1.Service{ObjectMeta: metav1.ObjectMeta{Name: "web"}},
+			want: &corev1.Service{ObjectMeta: metav1.ObjectMeta{Name: "web"}},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := controller.SlimPod(tt.obj)
+			if err != nil {
+				t.Fatalf("TestSlimPod() error = %v", err)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestSlimPod() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,170 @@
+package demo
+
+import (
+	"context"
+	_ "embed"
+	"fmt"
+	"math/rand/v2"
+	"net/http"
+	"sync"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	"sigs.k8s.io/controller-runtime/pkg/log"
+	"sigs.k8s.io/yaml"
+
+	"github.com/kdwils/constellation/internal/controller"
+	"github.com/kdwils/constellation/internal/healthcheck"
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+//go:embed fixtures/cluster.yaml
+var fixture []byte
+
+const (
+	mutationInterval = 5 * time.Second
+	failureRate      = 0.1
+	maxLatency       = 250 * time.Millisecond
+)
+
+// Cluster is a synthetic cluster built from bundled fixtures that mutates itself over time.
+// Health checks run through the real HealthChecker against a fake HTTP client.
+type Cluster struct {
+	*healthcheck.HealthChecker
+	mu      sync.RWMutex
+	objects types.ClusterObjects
+	random  *rand.Rand
+}
+
+type ClusterOpt func(*Cluster)
+
+// WithSeed picks the pods mutate flips from seed, so every cluster with the same seed changes the same way
+func WithSeed(seed uint64) ClusterOpt {
+	return func(c *Cluster) {
+		c.random = rand.New(rand.NewPCG(seed, seed))
+	}
+}
+
+// NewCluster loads the bundled fixtures into a new synthetic cluster
+func NewCluster(opts ...ClusterOpt) (*Cluster, error) {
+	var objects types.ClusterObjects
+	if err := yaml.Unmarshal(fixture, &objects); err != nil {
+		return nil, fmt.Errorf("failed to decode demo fixtures: %w", err)
+	}
+
+	c := &Cluster{
+		HealthChecker: healthcheck.NewHealthChecker(healthcheck.WithHTTPClient(randomClient{})),
+		objects:       objects,
+		random:        rand.New(rand.NewPCG(rand.Uint64(), rand.Uint64())),
+	}
+	for _, opt := range opts {
+		opt(c)
+	}
+	return c, nil
+}
+
+// Start registers health checks for the fixture services and mutates pods until the context is cancelled
+func (c *Cluster) Start(ctx context.Context) error {
+	logger := log.FromContext(ctx)
+	logger.Info("Starting demo cluster")
+
+	c.registerHealthTargets()
+	go c.HealthChecker.Start(ctx)
+
+	ticker := time.NewTicker(mutationInterval)
+	defer ticker.Stop()
+
+	for {
+		select {
+		case <-ticker.C:
+			c.mutate()
+		case <-ctx.Done():
+			return nil
+		}
+	}
+}
+
+func (c *Cluster) registerHealthTargets() {
+	c.mu.RLock()
+	defer c.mu.RUnlock()
+
+	for _, service := range c.objects.Services {
+		checks := controller.ExtractHealthChecksFromPods(service, c.objects.Pods)
+		if len(checks) == 0 {
+			continue
+		}
+		c.RegisterHealthTarget(fmt.Sprintf("%s/%s", service.Namespace, service.Name), checks)
+	}
+}
+
+// mutate flips a random pod between Running and Pending to simulate restarts
+func (c *Cluster) mutate() {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	if len(c.objects.Pods) == 0 {
+		return
+	}
+
+	pod := &c.objects.Pods[c.random.IntN(len(c.objects.Pods))]
+	if pod.Status.Phase == corev1.PodRunning {
+		pod.Status.Phase = corev1.PodPending
+		return
+	}
+	pod.Status.Phase = corev1.PodRunning
+}
+
+// GetHierarchy builds the hierarchy from the current fixture state
+func (c *Cluster) GetHierarchy(ctx context.Context) ([]types.HierarchyNode, error) {
+	return hierarchy.Build(c.copyObjects(), c.GetAllHealthData()), nil
+}
+
+// Snapshot captures the current fixture state
+func (c *Cluster) Snapshot(ctx context.Context) (*types.Snapshot, error) {
+	objects := c.copyObjects()
+	health := c.GetAllHealthData()
+	return &types.Snapshot{
+		Version:   types.SnapshotVersion,
+		Timestamp: time.Now().UTC(),
+		Hierarchy: hierarchy.Build(objects, health),
+		Health:    health,
+		Objects:   objects,
+	}, nil
+}
+
+// copyObjects returns the fixtures with pods deep copied since mutate writes to them
+func (c *Cluster) copyObjects() types.ClusterObjects {
+	c.mu.RLock()
+	defer c.mu.RUnlock()
+
+	objects := c.objects
+	objects.Pods = make([]corev1.Pod, len(c.objects.Pods))
+	for i := range c.objects.Pods {
+		objects.Pods[i] = *c.objects.Pods[i].DeepCopy()
+	}
+	return objects
+}
+
+// randomClient answers health checks with random latency and occasional failures
+type randomClient struct{}
+
+func (randomClient) Do(req *http.Request) (*http.Response, error) {
+	latency := time.Duration(rand.Int64N(int64(maxLatency)))
+	select {
+	case <-time.After(latency):
+	case <-req.Context().Done():
+		return nil, req.Context().Err()
+	}
+
+	status := http.StatusOK
+	if rand.Float64() < failureRate {
+		status = http.StatusServiceUnavailable
+	}
+
+	return &http.Response{
+		StatusCode: status,
+		Body:       http.NoBody,
+		Request:    req,
+	}, nil
+}
@@ -0,0 +1,70 @@
+package demo
+
+import (
+	"context"
+	"reflect"
+	"testing"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func TestCluster_Mutate(t *testing.T) {
+	tests := []struct {
+		name      string
+		seed      uint64
+		mutations int
+	}{
+		{name: "fixtures as loaded", seed: 1},
+		{name: "after many mutations", seed: 1, mutations: 50},
+		{name: "another seed", seed: 42, mutations: 50},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			first, err := NewCluster(WithSeed(tt.seed))
+			if err != nil {
+				t.Fatalf("TestCluster_Mutate() error = %v", err)
+			}
+			second, err := NewCluster(WithSeed(tt.seed))
+			if err != nil {
+				t.Fatalf("TestCluster_Mutate() error = %v", err)
+			}
+			for range tt.mutations {
+				first.mutate()
+				second.mutate()
+			}
+
+			if got, want := first.copyObjects(), second.copyObjects(); !reflect.DeepEqual(got, want) {
+				t.Errorf("TestCluster_Mutate() objects = %+v, want %+v", got, want)
+			}
+
+			nodes, err := first.GetHierarchy(context.Background())
+			if err != nil {
+				t.Fatalf("TestCluster_Mutate() GetHierarchy error = %v", err)
+			}
+			names := make([]string, 0, len(nodes))
+			for _, node := range nodes {
+				if node.Kind != types.ResourceKindNamespace {
+					t.Errorf("TestCluster_Mutate() top level node %s is a %s, want a Namespace", node.ID, node.Kind)
+				}
+				names = append(names, node.Name)
+			}
+			if want := []string{"blog", "monitoring", "shop"}; !reflect.DeepEqual(names, want) {
+				t.Errorf("TestCluster_Mutate() namespaces = %v, want %v", names, want)
+			}
+
+			ids := make(map[string]bool)
+			hierarchy.Walk(nodes, func(node *types.HierarchyNode) {
+				if node.ID == "" || ids[node.ID] {
+					t.Errorf("TestCluster_Mutate() node %s/%s has a missing or duplicate id %q", node.Kind, node.Name, node.ID)
+				}
+				ids[node.ID] = true
+				for _, relative := range node.Relatives {
+					if relative.ParentID != node.ID {
+						t.Errorf("TestCluster_Mutate() node %s has parent %q, want %q", relative.ID, relative.ParentID, node.ID)
+					}
+				}
+			})
+		})
+	}
+}
@@ -0,0 +1,345 @@
+// Package disruptions records pods that were evicted, preempted or OOM killed, keeping a bounded
+// history per workload so disruptions caused by node pressure remain visible after the pods are gone.
+package disruptions
+
+import (
+	"context"
+	"fmt"
+	"slices"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	"sigs.k8s.io/controller-runtime/pkg/log"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const (
+	defaultHistory   = 20
+	defaultInterval  = 30 * time.Second
+	defaultRetention = 24 * time.Hour
+
+	reasonEvicted   = "Evicted"
+	reasonPreempted = "Preempted"
+	reasonOOMKilled = "OOMKilled"
+
+	// conditionDisruptionTarget is set on pods about to be terminated by the kubelet, scheduler or eviction API
+	conditionDisruptionTarget = "DisruptionTarget"
+	preemptionByScheduler     = "PreemptionByScheduler"
+)
+
+// Config controls how much history is kept
+type Config struct {
+	// History is how many disruptions are kept per workload, defaulting to 20
+	History int
+	// Interval between pod and event samples, defaulting to 30s
+	Interval time.Duration
+	// Retention is how long a workload's history is kept once it has no pods and no disruption events,
+	// defaulting to 24h
+	Retention time.Duration
+}
+
+// Tracker samples pod statuses and events for disruptions and keeps the most recent per workload
+type Tracker struct {
+	pods   client.Reader
+	events client.Reader
+	config Config
+	now    func() time.Time
+
+	mu sync.RWMutex
+	// history is keyed by namespace/kind/name of the owning workload. Its slices are replaced rather than
+	// modified, so they can be handed out without copying.
+	history map[string][]types.Disruption
+	// workloads maps namespace/pod to its workload key, kept while events still reference the pod
+	workloads map[string]string
+	// seen is when each workload last had pods or disruption events, so idle ones are pruned
+	seen map[string]time.Time
+}
+
+// NewTracker creates a Tracker listing pods from pods, e.g. the manager's cached client, and Events
+// from events, which should bypass the cache, e.g. the manager's API reader, so Events are not watched
+// cluster-wide.
+func NewTracker(pods, events client.Reader, config Config, opts ...TrackerOpt) *Tracker {
+	if config.History == 0 {
+		config.History = defaultHistory
+	}
+	if config.Interval == 0 {
+		config.Interval = defaultInterval
+	}
+	if config.Retention == 0 {
+		config.Retention = defaultRetention
+	}
+
+	t := &Tracker{
+		pods:      pods,
+		events:    events,
+		config:    config,
+		now:       time.Now,
+		history:   make(map[string][]types.Disruption),
+		workloads: make(map[string]string),
+		seen:      make(map[string]time.Time),
+	}
+
+	for _, opt := range opts {
+		opt(t)
+	}
+
+	return t
+}
+
+type TrackerOpt func(*Tracker)
+
+// WithClock replaces the clock idle workloads are pruned by
+func WithClock(now func() time.Time) TrackerOpt {
+	return func(t *Tracker) {
+		t.now = now
+	}
+}
+
+// +kubebuilder:rbac:groups="",resources=events,verbs=list
+
+// Start samples disruptions on the configured interval until ctx is cancelled
+func (t *Tracker) Start(ctx context.Context) {
+	logger := log.FromContext(ctx).WithName("disruptions")
+	ticker := time.NewTicker(t.config.Interval)
+	defer ticker.Stop()
+
+	for {
+		if err := t.Sample(ctx); err != nil {
+			logger.Error(err, "failed to sample pod disruptions")
+		}
+
+		select {
+		case <-ctx.Done():
+			return
+		case <-ticker.C:
+		}
+	}
+}
+
+// Sample reads pods and pod events once and records any new disruptions
+func (t *Tracker) Sample(ctx context.Context) error {
+	var pods corev1.PodList
+	if err := t.pods.List(ctx, &pods); err != nil {
+		return fmt.Errorf("failed to list pods: %w", err)
+	}
+
+	var events corev1.EventList
+	if err := t.events.List(ctx, &events, client.MatchingFields{"involvedObject.kind": "Pod"}); err != nil {
+		return fmt.Errorf("failed to list events: %w", err)
+	}
+
+	t.Observe(pods.Items, events.Items)
+	return nil
+}
+
+// Observe records the disruptions visible in pod statuses and events. Evictions and preemptions are
+// recorded once per pod; OOM kills once per container termination. Workloads without pods or events
+// for longer than the retention are forgotten.
+func (t *Tracker) Observe(pods []corev1.Pod, events []corev1.Event) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	workloads := make(map[string]string, len(pods))
+	for _, pod := range pods {
+		workload := workloadKey(pod)
+		workloads[pod.Namespace+"/"+pod.Name] = workload
+		for _, disruption := range podDisruptions(pod) {
+			t.record(workload, disruption)
+		}
+	}
+
+	for _, event := range events {
+		disruption, ok := eventDisruption(event)
+		if !ok {
+			continue
+		}
+		podKey := event.InvolvedObject.Namespace + "/" + event.InvolvedObject.Name
+		workload, exists := workloads[podKey]
+		if !exists {
+			workload, exists = t.workloads[podKey]
+		}
+		if !exists {
+			workload = event.InvolvedObject.Namespace + "/Pod/" + event.InvolvedObject.Name
+		}
+		workloads[podKey] = workload
+		t.record(workload, disruption)
+	}
+
+	t.workloads = workloads
+	t.prune(workloads)
+}
+
+// prune marks the workloads still referenced as seen and drops those unseen for longer than the retention
+func (t *Tracker) prune(workloads map[string]string) {
+	now := t.now()
+	for _, workload := range workloads {
+		t.seen[workload] = now
+	}
+	for workload, seen := range t.seen {
+		if now.Sub(seen) <= t.config.Retention {
+			continue
+		}
+		delete(t.seen, workload)
+		delete(t.history, workload)
+	}
+}
+
+// record adds a disruption to a workload's history unless it is already known, keeping the newest entries.
+// The history is copied, leaving the slice readers may hold untouched.
+func (t *Tracker) record(workload string, disruption types.Disruption) {
+	for _, existing := range t.history[workload] {
+		if sameDisruption(existing, disruption) {
+			return
+		}
+	}
+
+	history := append(slices.Clone(t.history[workload]), disruption)
+	sort.SliceStable(history, func(i, j int) bool { return history[i].Time.Before(history[j].Time) })
+	if len(history) > t.config.History {
+		history = history[len(history)-t.config.History:]
+	}
+	t.history[workload] = history
+}
+
+func sameDisruption(a, b types.Disruption) bool {
+	if a.Reason != b.Reason || a.Pod != b.Pod || a.Container != b.Container {
+		return false
+	}
+	return a.Reason != reasonOOMKilled || a.Time.Equal(b.Time)
+}
+
+// podDisruptions reads evictions, scheduler preemptions and OOM kills from a pod's status
+func podDisruptions(pod corev1.Pod) []types.Disruption {
+	var disruptions []types.Disruption
+
+	target := disruptionTarget(pod)
+	if pod.Status.Reason == reasonEvicted {
+		disruption := types.Disruption{Reason: reasonEvicted, Pod: pod.Name, Message: pod.Status.Message, Time: pod.CreationTimestamp.Time}
+		if target != nil {
+			disruption.Time = target.LastTransitionTime.Time
+		}
+		disruptions = append(disruptions, disruption)
+	}
+	if target != nil && target.Reason == preemptionByScheduler {
+		disruptions = append(disruptions, types.Disruption{
+			Reason:  reasonPreempted,
+			Pod:     pod.Name,
+			Message: target.Message,
+			Time:    target.LastTransitionTime.Time,
+		})
+	}
+
+	statuses := append(append([]corev1.ContainerStatus{}, pod.Status.InitContainerStatuses...), pod.Status.ContainerStatuses...)
+	for _, status := range statuses {
+		for _, terminated := range []*corev1.ContainerStateTerminated{status.State.Terminated, status.LastTerminationState.Terminated} {
+			if terminated == nil || terminated.Reason != reasonOOMKilled {
+				continue
+			}
+			disruptions = append(disruptions, types.Disruption{
+				Reason:    reasonOOMKilled,
+				Pod:       pod.Name,
+				Container: status.Name,
+				Time:      terminated.FinishedAt.Time,
+			})
+		}
+	}
+	return disruptions
+}
+
+func disruptionTarget(pod corev1.Pod) *corev1.PodCondition {
+	for i := range pod.Status.Conditions {
+		if pod.Status.Conditions[i].Type == conditionDisruptionTarget && pod.Status.Conditions[i].Status == corev1.ConditionTrue {
+			return &pod.Status.Conditions[i]
+		}
+	}
+	return nil
+}
+
+// eventDisruption converts Evicted and Preempted pod events, which outlive preempted pods
+func eventDisruption(event corev1.Event) (types.Disruption, bool) {
+	if event.InvolvedObject.Kind != "Pod" {
+		return types.Disruption{}, false
+	}
+	if event.Reason != reasonEvicted && event.Reason != reasonPreempted {
+		return types.Disruption{}, false
+	}
+
+	at := event.LastTimestamp.Time
+	if at.IsZero() {
+		at = event.EventTime.Time
+	}
+	return types.Disruption{
+		Reason:  event.Reason,
+		Pod:     event.InvolvedObject.Name,
+		Message: event.Message,
+		Time:    at,
+	}, true
+}
+
+// workloadKey identifies the workload a pod belongs to, resolving ReplicaSets to their Deployment
+// through the pod-template-hash suffix. Pods without a controller are their own workload.
+func workloadKey(pod corev1.Pod) string {
+	for _, owner := range pod.OwnerReferences {
+		if owner.Controller == nil || !*owner.Controller {
+			continue
+		}
+		hash := pod.Labels["pod-template-hash"]
+		if owner.Kind == "ReplicaSet" && hash != "" && strings.HasSuffix(owner.Name, "-"+hash) {
+			return pod.Namespace + "/Deployment/" + strings.TrimSuffix(owner.Name, "-"+hash)
+		}
+		return pod.Namespace + "/" + owner.Kind + "/" + owner.Name
+	}
+	return pod.Namespace + "/Pod/" + pod.Name
+}
+
+// Get returns the disruption history of the workload a pod belongs to
+func (t *Tracker) Get(namespace, pod string) []types.Disruption {
+	t.mu.RLock()
+	defer t.mu.RUnlock()
+
+	workload, exists := t.workloads[namespace+"/"+pod]
+	if !exists {
+		return nil
+	}
+	return t.history[workload]
+}
+
+// Enrich attaches each Pod node's workload disruption history
+func (t *Tracker) Enrich(nodes []types.HierarchyNode) {
+	hierarchy.Walk(nodes, func(node *types.HierarchyNode) {
+		if node.Kind != types.ResourceKindPod || node.Namespace == nil {
+			return
+		}
+		node.Disruptions = t.Get(*node.Namespace, node.Name)
+	})
+}
+
+// GetDisruptions returns the history of every disrupted workload, ordered by namespace, kind and name
+func (t *Tracker) GetDisruptions(ctx context.Context) ([]types.WorkloadDisruptions, error) {
+	t.mu.RLock()
+	defer t.mu.RUnlock()
+
+	keys := make([]string, 0, len(t.history))
+	for key := range t.history {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+
+	workloads := make([]types.WorkloadDisruptions, 0, len(keys))
+	for _, key := range keys {
+		parts := strings.SplitN(key, "/", 3)
+		workloads = append(workloads, types.WorkloadDisruptions{
+			Namespace:   parts[0],
+			Kind:        parts[1],
+			Name:        parts[2],
+			Disruptions: t.history[key],
+		})
+	}
+	return workloads, nil
+}
@@ -0,0 +1,181 @@
+package disruptions_test
+
+import (
+	"context"
+	"reflect"
+	"testing"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"k8s.io/utils/ptr"
+
+	"github.com/kdwils/constellation/internal/disruptions"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+var now = time.Date(2025, 1, 1, 0, 0, 0, 0, time.UTC)
+
+func deploymentPod(name string) corev1.Pod {
+	return corev1.Pod{
+		ObjectMeta: metav1.ObjectMeta{
+			Name:            name,
+			Namespace:       "shop",
+			Labels:          map[string]string{"pod-template-hash": "7d9f"},
+			OwnerReferences: []metav1.OwnerReference{{Kind: "ReplicaSet", Name: "api-7d9f", Controller: ptr.To(true)}},
+		},
+	}
+}
+
+func TestTracker_Observe(t *testing.T) {
+	oomKilled := deploymentPod("api-7d9f-a")
+	oomKilled.Status.ContainerStatuses = []corev1.ContainerStatus{{
+		Name: "api",
+		LastTerminationState: corev1.ContainerState{
+			Terminated: &corev1.ContainerStateTerminated{Reason: "OOMKilled", FinishedAt: metav1.NewTime(now)},
+		},
+	}}
+
+	evicted := deploymentPod("api-7d9f-b")
+	evicted.Status.Reason = "Evicted"
+	evicted.Status.Message = "The node was low on resource: memory."
+	evicted.Status.Conditions = []corev1.PodCondition{{
+		Type:               "DisruptionTarget",
+		Status:             corev1.ConditionTrue,
+		Reason:             "TerminationByKubelet",
+		LastTransitionTime: metav1.NewTime(now.Add(time.Minute)),
+	}}
+
+	preempted := corev1.Event{
+		InvolvedObject: corev1.ObjectReference{Kind: "Pod", Namespace: "batch", Name: "report-0"},
+		Reason:         "Preempted",
+		Message:        "Preempted in order to admit critical pod",
+		LastTimestamp:  metav1.NewTime(now.Add(2 * time.Minute)),
+	}
+
+	tests := []struct {
+		name    string
+		history int
+		pods    []corev1.Pod
+		events  []corev1.Event
+		want    []types.WorkloadDisruptions
+	}{
+		{
+			name:   "no disruptions",
+			pods:   []corev1.Pod{deploymentPod("api-7d9f-c")},
+			events: nil,
+			want:   []types.WorkloadDisruptions{},
+		},
+		{
+			name:   "status and event disruptions grouped by workload",
+			pods:   []corev1.Pod{oomKilled, evicted},
+			events: []corev1.Event{preempted},
+			want: []types.WorkloadDisruptions{
+				{
+					Kind:      "Pod",
+					Namespace: "batch",
+					Name:      "report-0",
+					Disruptions: []types.Disruption{
+						{Reason: "Preempted", Pod: "report-0", Message: "Preempted in order to admit critical pod", Time: now.Add(2 * time.Minute)},
+					},
+				},
+				{
+					Kind:      "Deployment",
+					Namespace: "shop",
+					Name:      "api",
+					Disruptions: []types.Disruption{
+						{Reason: "OOMKilled", Pod: "api-7d9f-a", Container: "api", Time: now},
+						{Reason: "Evicted", Pod: "api-7d9f-b", Message: "The node was low on resource: memory.", Time: now.Add(time.Minute)},
+					},
+				},
+			},
+		},
+		{
+			name:    "history keeps the newest entries",
+			history: 1,
+			pods:    []corev1.Pod{oomKilled, evicted},
+			events:  nil,
+			want: []types.WorkloadDisruptions{
+				{
+					Kind:      "Deployment",
+					Namespace: "shop",
+					Name:      "api",
+					Disruptions: []types.Disruption{
+						{Reason: "Evicted", Pod: "api-7d9f-b", Message: "The node was low on resource: memory.", Time: now.Add(time.Minute)},
+					},
+				},
+			},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			tracker := disruptions.NewTracker(nil, nil, disruptions.Config{History: tt.history})
+			tracker.Observe(tt.pods, tt.events)
+			tracker.Observe(tt.pods, tt.events)
+
+			got, err := tracker.GetDisruptions(context.Background())
+			if err != nil {
+				t.Fatalf("TestTracker_Observe() error = %v", err)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestTracker_Observe() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestTracker_Prune(t *testing.T) {
+	evicted := deploymentPod("api-7d9f-b")
+	evicted.Status.Reason = "Evicted"
+	evicted.CreationTimestamp = metav1.NewTime(now)
+
+	want := []types.WorkloadDisruptions{{
+		Kind:        "Deployment",
+		Namespace:   "shop",
+		Name:        "api",
+		Disruptions: []types.Disruption{{Reason: "Evicted", Pod: "api-7d9f-b", Time: now}},
+	}}
+
+	tests := []struct {
+		name  string
+		later []corev1.Pod
+		after time.Duration
+		want  []types.WorkloadDisruptions
+	}{
+		{
+			name:  "workload with pods is kept",
+			later: []corev1.Pod{deploymentPod("api-7d9f-c")},
+			after: 48 * time.Hour,
+			want:  want,
+		},
+		{
+			name:  "workload gone within the retention is kept",
+			after: time.Hour,
+			want:  want,
+		},
+		{
+			name:  "workload gone for longer than the retention is pruned",
+			after: 25 * time.Hour,
+			want:  []types.WorkloadDisruptions{},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			clock := now
+			tracker := disruptions.NewTracker(nil, nil, disruptions.Config{Retention: 24 * time.Hour},
+				disruptions.WithClock(func() time.Time { return clock }))
+			tracker.Observe([]corev1.Pod{evicted}, nil)
+
+			clock = now.Add(tt.after)
+			tracker.Observe(tt.later, nil)
+
+			got, err := tracker.GetDisruptions(context.Background())
+			if err != nil {
+				t.Fatalf("TestTracker_Prune() error = %v", err)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestTracker_Prune() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,239 @@
+// Package drift compares the replicas Deployments and StatefulSets ask for against their ready pods, so
+// a scale-up starved by a quota or stuck on unschedulable pods is reported instead of quietly running short.
+package drift
+
+import (
+	"context"
+	"fmt"
+	"sort"
+	"sync"
+	"time"
+
+	appsv1 "k8s.io/api/apps/v1"
+	toolscache "k8s.io/client-go/tools/cache"
+	"k8s.io/utils/ptr"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	"sigs.k8s.io/controller-runtime/pkg/log"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const defaultAfter = 5 * time.Minute
+
+// Config controls when drift is alerted on
+type Config struct {
+	// After is how long a workload must stay short of ready replicas before it is alerted on, defaulting to 5m
+	After time.Duration
+}
+
+// workload is the replica state of one Deployment or StatefulSet
+type workload struct {
+	kind      types.ResourceKind
+	namespace string
+	name      string
+	desired   int32
+	ready     int32
+	// since is when the workload fell short of its desired replicas, zero while it is not
+	since time.Time
+}
+
+// Tracker follows Deployments and StatefulSets from their informers. It is a ResourceEventHandler.
+type Tracker struct {
+	informers types.Informers
+	config    Config
+	now       func() time.Time
+
+	mu        sync.RWMutex
+	workloads map[string]workload
+}
+
+// NewTracker creates a Tracker that follows the workloads seen by the informers in informers
+func NewTracker(informers types.Informers, config Config, opts ...TrackerOpt) *Tracker {
+	if config.After <= 0 {
+		config.After = defaultAfter
+	}
+
+	t := &Tracker{
+		informers: informers,
+		config:    config,
+		now:       time.Now,
+		workloads: make(map[string]workload),
+	}
+
+	for _, opt := range opts {
+		opt(t)
+	}
+
+	return t
+}
+
+type TrackerOpt func(*Tracker)
+
+// WithClock replaces the clock drift is timed by
+func WithClock(now func() time.Time) TrackerOpt {
+	return func(t *Tracker) {
+		t.now = now
+	}
+}
+
+// +kubebuilder:rbac:groups=apps,resources=deployments;statefulsets,verbs=get;list;watch
+
+// Start follows Deployment and StatefulSet events until ctx is cancelled
+func (t *Tracker) Start(ctx context.Context) {
+	logger := log.FromContext(ctx).WithName("drift")
+	for _, obj := range []client.Object{&appsv1.Deployment{}, &appsv1.StatefulSet{}} {
+		informer, err := t.informers.GetInformer(ctx, obj)
+		if err != nil {
+			logger.Error(err, "failed to get workload informer", "kind", fmt.Sprintf("%T", obj))
+			return
+		}
+		registration, err := informer.AddEventHandler(t)
+		if err != nil {
+			logger.Error(err, "failed to watch workload events", "kind", fmt.Sprintf("%T", obj))
+			return
+		}
+		defer func() {
+			if err := informer.RemoveEventHandler(registration); err != nil {
+				logger.Error(err, "failed to stop watching workload events")
+			}
+		}()
+	}
+
+	<-ctx.Done()
+}
+
+// OnAdd records a workload's replicas
+func (t *Tracker) OnAdd(obj any, _ bool) {
+	t.record(obj)
+}
+
+// OnUpdate records a workload's replicas
+func (t *Tracker) OnUpdate(_, newObj any) {
+	t.record(newObj)
+}
+
+// OnDelete forgets a workload
+func (t *Tracker) OnDelete(obj any) {
+	if tombstone, ok := obj.(toolscache.DeletedFinalStateUnknown); ok {
+		obj = tombstone.Obj
+	}
+	current, ok := replicas(obj)
+	if !ok {
+		return
+	}
+
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	delete(t.workloads, current.key())
+}
+
+func (t *Tracker) record(obj any) {
+	current, ok := replicas(obj)
+	if !ok {
+		return
+	}
+
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	if current.ready < current.desired {
+		current.since = t.workloads[current.key()].since
+		if current.since.IsZero() {
+			current.since = t.now()
+		}
+	}
+	t.workloads[current.key()] = current
+}
+
+// replicas reads the desired and ready replicas of a Deployment or StatefulSet. Unset spec replicas
+// default to 1, as the apiserver does.
+func replicas(obj any) (workload, bool) {
+	switch o := obj.(type) {
+	case *appsv1.Deployment:
+		return workload{
+			kind:      types.ResourceKindDeployment,
+			namespace: o.Namespace,
+			name:      o.Name,
+			desired:   ptr.Deref(o.Spec.Replicas, 1),
+			ready:     o.Status.ReadyReplicas,
+		}, true
+	case *appsv1.StatefulSet:
+		return workload{
+			kind:      types.ResourceKindStatefulSet,
+			namespace: o.Namespace,
+			name:      o.Name,
+			desired:   ptr.Deref(o.Spec.Replicas, 1),
+			ready:     o.Status.ReadyReplicas,
+		}, true
+	}
+	return workload{}, false
+}
+
+func (w workload) key() string {
+	return fmt.Sprintf("%s/%s/%s", w.namespace, w.kind, w.name)
+}
+
+func (w workload) drift() types.ReplicaDrift {
+	return types.ReplicaDrift{
+		Kind:    w.kind,
+		Name:    w.name,
+		Desired: w.desired,
+		Ready:   w.ready,
+		Since:   w.since,
+	}
+}
+
+// drifting returns the workloads short of ready replicas, ordered by namespace, kind and name
+func (t *Tracker) drifting() []workload {
+	var result []workload
+	for _, w := range t.workloads {
+		if !w.since.IsZero() {
+			result = append(result, w)
+		}
+	}
+	sort.Slice(result, func(i, j int) bool { return result[i].key() < result[j].key() })
+	return result
+}
+
+// Enrich lists the workloads short of ready replicas on their Namespace node
+func (t *Tracker) Enrich(nodes []types.HierarchyNode) {
+	t.mu.RLock()
+	defer t.mu.RUnlock()
+
+	byNamespace := make(map[string][]types.ReplicaDrift)
+	for _, w := range t.drifting() {
+		byNamespace[w.namespace] = append(byNamespace[w.namespace], w.drift())
+	}
+
+	hierarchy.Walk(nodes, func(node *types.HierarchyNode) {
+		if node.Kind != types.ResourceKindNamespace {
+			return
+		}
+		node.ReplicaDrift = byNamespace[node.Name]
+	})
+}
+
+// GetAlerts returns an alert for every workload that has been short of ready replicas for longer than
+// config.After, ordered by namespace, kind and name
+func (t *Tracker) GetAlerts(ctx context.Context) ([]types.Alert, error) {
+	t.mu.RLock()
+	defer t.mu.RUnlock()
+
+	alerts := make([]types.Alert, 0)
+	now := t.now()
+	for _, w := range t.drifting() {
+		if now.Sub(w.since) < t.config.After {
+			continue
+		}
+		alerts = append(alerts, types.Alert{
+			Type:      types.AlertTypeReplicaDrift,
+			Kind:      w.kind,
+			Namespace: w.namespace,
+			Name:      w.name,
+			Message:   fmt.Sprintf("%d of %d replicas ready", w.ready, w.desired),
+			Since:     w.since,
+		})
+	}
+	return alerts, nil
+}
@@ -0,0 +1,133 @@
+package drift_test
+
+import (
+	"context"
+	"reflect"
+	"testing"
+	"time"
+
+	appsv1 "k8s.io/api/apps/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	toolscache "k8s.io/client-go/tools/cache"
+	"k8s.io/utils/ptr"
+
+	"github.com/kdwils/constellation/internal/drift"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func deployment(name string, desired *int32, ready int32) *appsv1.Deployment {
+	return &appsv1.Deployment{
+		ObjectMeta: metav1.ObjectMeta{Name: name, Namespace: "shop"},
+		Spec:       appsv1.DeploymentSpec{Replicas: desired},
+		Status:     appsv1.DeploymentStatus{ReadyReplicas: ready},
+	}
+}
+
+func statefulSet(name string, desired, ready int32) *appsv1.StatefulSet {
+	return &appsv1.StatefulSet{
+		ObjectMeta: metav1.ObjectMeta{Name: name, Namespace: "shop"},
+		Spec:       appsv1.StatefulSetSpec{Replicas: ptr.To(desired)},
+		Status:     appsv1.StatefulSetStatus{ReadyReplicas: ready},
+	}
+}
+
+func TestTracker(t *testing.T) {
+	start := time.Date(2025, 1, 2, 3, 0, 0, 0, time.UTC)
+
+	type event struct {
+		after  time.Duration
+		delete bool
+		obj    any
+	}
+	tests := []struct {
+		name       string
+		events     []event
+		now        time.Duration
+		wantDrift  []types.ReplicaDrift
+		wantAlerts []types.Alert
+	}{
+		{
+			name: "drift is alerted once it outlasts the threshold",
+			events: []event{
+				{after: 0, obj: deployment("web", ptr.To[int32](5), 3)},
+				{after: 2 * time.Minute, obj: deployment("web", ptr.To[int32](5), 4)},
+				{after: 2 * time.Minute, obj: statefulSet("db", 3, 1)},
+			},
+			now: 6 * time.Minute,
+			wantDrift: []types.ReplicaDrift{
+				{Kind: types.ResourceKindDeployment, Name: "web", Desired: 5, Ready: 4, Since: start},
+				{Kind: types.ResourceKindStatefulSet, Name: "db", Desired: 3, Ready: 1, Since: start.Add(2 * time.Minute)},
+			},
+			wantAlerts: []types.Alert{{
+				Type:      types.AlertTypeReplicaDrift,
+				Kind:      types.ResourceKindDeployment,
+				Namespace: "shop",
+				Name:      "web",
+				Message:   "4 of 5 replicas ready",
+				Since:     start,
+			}},
+		},
+		{
+			name: "recovering resets the drift",
+			events: []event{
+				{after: 0, obj: deployment("web", ptr.To[int32](2), 1)},
+				{after: time.Minute, obj: deployment("web", ptr.To[int32](2), 2)},
+				{after: 4 * time.Minute, obj: deployment("web", ptr.To[int32](3), 2)},
+			},
+			now: 6 * time.Minute,
+			wantDrift: []types.ReplicaDrift{
+				{Kind: types.ResourceKindDeployment, Name: "web", Desired: 3, Ready: 2, Since: start.Add(4 * time.Minute)},
+			},
+			wantAlerts: []types.Alert{},
+		},
+		{
+			name: "unset replicas default to one and deleted workloads are forgotten",
+			events: []event{
+				{after: 0, obj: deployment("web", nil, 0)},
+				{after: 0, obj: statefulSet("db", 1, 0)},
+				{after: time.Minute, delete: true, obj: toolscache.DeletedFinalStateUnknown{Key: "shop/db", Obj: statefulSet("db", 1, 0)}},
+			},
+			now: 10 * time.Minute,
+			wantDrift: []types.ReplicaDrift{
+				{Kind: types.ResourceKindDeployment, Name: "web", Desired: 1, Ready: 0, Since: start},
+			},
+			wantAlerts: []types.Alert{{
+				Type:      types.AlertTypeReplicaDrift,
+				Kind:      types.ResourceKindDeployment,
+				Namespace: "shop",
+				Name:      "web",
+				Message:   "0 of 1 replicas ready",
+				Since:     start,
+			}},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			now := start
+			tracker := drift.NewTracker(nil, drift.Config{}, drift.WithClock(func() time.Time { return now }))
+			for _, e := range tt.events {
+				now = start.Add(e.after)
+				if e.delete {
+					tracker.OnDelete(e.obj)
+					continue
+				}
+				tracker.OnUpdate(nil, e.obj)
+			}
+			now = start.Add(tt.now)
+
+			nodes := []types.HierarchyNode{{Kind: types.ResourceKindNamespace, Name: "shop"}}
+			tracker.Enrich(nodes)
+			if !reflect.DeepEqual(nodes[0].ReplicaDrift, tt.wantDrift) {
+				t.Errorf("TestTracker() drift = %+v, want %+v", nodes[0].ReplicaDrift, tt.wantDrift)
+			}
+
+			alerts, err := tracker.GetAlerts(context.Background())
+			if err != nil {
+				t.Fatalf("TestTracker() GetAlerts error = %v", err)
+			}
+			if !reflect.DeepEqual(alerts, tt.wantAlerts) {
+				t.Errorf("TestTracker() alerts = %+v, want %+v", alerts, tt.wantAlerts)
+			}
+		})
+	}
+}
@@ -0,0 +1,179 @@
+// Package endpoints counts the ready and not ready endpoints behind each Service from its
+// EndpointSlices, and streams the counts to subscribers whenever they change.
+package endpoints
+
+import (
+	"context"
+	"fmt"
+	"maps"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+
+	discoveryv1 "k8s.io/api/discovery/v1"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	"sigs.k8s.io/controller-runtime/pkg/log"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const defaultInterval = 5 * time.Second
+
+// Count totals endpoint readiness per Service, keyed by namespace/name. An endpoint without a
+// ready condition is counted as ready, as the EndpointSlice API specifies for an unknown state.
+func Count(slices []discoveryv1.EndpointSlice) map[string]types.EndpointCounts {
+	counts := make(map[string]types.EndpointCounts)
+	for _, slice := range slices {
+		service, exists := slice.Labels[discoveryv1.LabelServiceName]
+		if !exists {
+			continue
+		}
+		key := slice.Namespace + "/" + service
+		count := counts[key]
+		for _, endpoint := range slice.Endpoints {
+			if endpoint.Conditions.Ready == nil || *endpoint.Conditions.Ready {
+				count.Ready++
+				continue
+			}
+			count.NotReady++
+		}
+		counts[key] = count
+	}
+	return counts
+}
+
+// Config controls how often EndpointSlices are read
+type Config struct {
+	// Interval between EndpointSlice reads, defaulting to 5s
+	Interval time.Duration
+}
+
+// Watcher reads EndpointSlices on an interval and notifies subscribers when any Service's counts change
+type Watcher struct {
+	reader client.Reader
+	config Config
+
+	mu     sync.RWMutex
+	counts map[string]types.EndpointCounts
+
+	subMu       sync.RWMutex
+	subscribers map[chan []types.ServiceEndpoints]bool
+}
+
+// NewWatcher creates a Watcher. reader is expected to be the manager's cached client so
+// repeated reads are served from the informer.
+func NewWatcher(reader client.Reader, config Config) *Watcher {
+	if config.Interval == 0 {
+		config.Interval = defaultInterval
+	}
+
+	return &Watcher{
+		reader:      reader,
+		config:      config,
+		counts:      make(map[string]types.EndpointCounts),
+		subscribers: make(map[chan []types.ServiceEndpoints]bool),
+	}
+}
+
+// +kubebuilder:rbac:groups=discovery.k8s.io,resources=endpointslices,verbs=get;list;watch
+
+// Start reads EndpointSlices on the configured interval until ctx is cancelled
+func (w *Watcher) Start(ctx context.Context) {
+	logger := log.FromContext(ctx).WithName("endpoints")
+	ticker := time.NewTicker(w.config.Interval)
+	defer ticker.Stop()
+
+	for {
+		if err := w.Refresh(ctx); err != nil {
+			logger.Error(err, "failed to read endpointslices")
+		}
+
+		select {
+		case <-ctx.Done():
+			return
+		case <-ticker.C:
+		}
+	}
+}
+
+// Refresh reads every EndpointSlice once and notifies subscribers if the counts changed
+func (w *Watcher) Refresh(ctx context.Context) error {
+	var slices discoveryv1.EndpointSliceList
+	if err := w.reader.List(ctx, &slices); err != nil {
+		return fmt.Errorf("failed to list endpointslices: %w", err)
+	}
+	w.Observe(slices.Items)
+	return nil
+}
+
+// Observe replaces the counts with those of slices and notifies subscribers if they changed
+func (w *Watcher) Observe(slices []discoveryv1.EndpointSlice) {
+	counts := Count(slices)
+
+	w.mu.Lock()
+	changed := !maps.Equal(w.counts, counts)
+	w.counts = counts
+	w.mu.Unlock()
+
+	if changed {
+		w.notifySubscribers()
+	}
+}
+
+// GetEndpoints returns the current counts of every Service, sorted by namespace and name
+func (w *Watcher) GetEndpoints() []types.ServiceEndpoints {
+	w.mu.RLock()
+	defer w.mu.RUnlock()
+
+	result := make([]types.ServiceEndpoints, 0, len(w.counts))
+	for key, count := range w.counts {
+		namespace, name, _ := strings.Cut(key, "/")
+		result = append(result, types.ServiceEndpoints{
+			Namespace: namespace,
+			Name:      name,
+			Ready:     count.Ready,
+			NotReady:  count.NotReady,
+		})
+	}
+	sort.Slice(result, func(i, j int) bool {
+		if result[i].Namespace != result[j].Namespace {
+			return result[i].Namespace < result[j].Namespace
+		}
+		return result[i].Name < result[j].Name
+	})
+	return result
+}
+
+// Subscribe creates a new subscription channel for endpoint count updates
+func (w *Watcher) Subscribe() chan []types.ServiceEndpoints {
+	w.subMu.Lock()
+	defer w.subMu.Unlock()
+
+	ch := make(chan []types.ServiceEndpoints, 1)
+	w.subscribers[ch] = true
+	return ch
+}
+
+// Unsubscribe removes a subscription channel
+func (w *Watcher) Unsubscribe(ch chan []types.ServiceEndpoints) {
+	w.subMu.Lock()
+	defer w.subMu.Unlock()
+
+	delete(w.subscribers, ch)
+	close(ch)
+}
+
+// notifySubscribers sends the current counts to every subscriber without blocking on slow readers
+func (w *Watcher) notifySubscribers() {
+	w.subMu.RLock()
+	defer w.subMu.RUnlock()
+
+	data := w.GetEndpoints()
+	for ch := range w.subscribers {
+		select {
+		case ch <- data:
+		default:
+		}
+	}
+}
@@ -0,0 +1,84 @@
+package endpoints_test
+
+import (
+	"reflect"
+	"testing"
+
+	discoveryv1 "k8s.io/api/discovery/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"k8s.io/utils/ptr"
+
+	"github.com/kdwils/constellation/internal/endpoints"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func slice(name, namespace, service string, ready ...*bool) discoveryv1.EndpointSlice {
+	s := discoveryv1.EndpointSlice{ObjectMeta: metav1.ObjectMeta{Name: name, Namespace: namespace}}
+	if service != "" {
+		s.Labels = map[string]string{discoveryv1.LabelServiceName: service}
+	}
+	for _, r := range ready {
+		s.Endpoints = append(s.Endpoints, discoveryv1.Endpoint{Conditions: discoveryv1.EndpointConditions{Ready: r}})
+	}
+	return s
+}
+
+func TestCount(t *testing.T) {
+	tests := []struct {
+		name   string
+		slices []discoveryv1.EndpointSlice
+		want   map[string]types.EndpointCounts
+	}{
+		{
+			name: "slices of one service are summed",
+			slices: []discoveryv1.EndpointSlice{
+				slice("api-a", "shop", "api", ptr.To(true), ptr.To(false)),
+				slice("api-b", "shop", "api", ptr.To(false)),
+			},
+			want: map[string]types.EndpointCounts{"shop/api": {Ready: 1, NotReady: 2}},
+		},
+		{
+			name:   "unknown readiness counts as ready",
+			slices: []discoveryv1.EndpointSlice{slice("web-a", "shop", "web", nil)},
+			want:   map[string]types.EndpointCounts{"shop/web": {Ready: 1}},
+		},
+		{
+			name:   "service with no endpoints",
+			slices: []discoveryv1.EndpointSlice{slice("idle-a", "shop", "idle")},
+			want:   map[string]types.EndpointCounts{"shop/idle": {}},
+		},
+		{
+			name:   "slices without a service label are skipped",
+			slices: []discoveryv1.EndpointSlice{slice("manual", "shop", "", ptr.To(true))},
+			want:   map[string]types.EndpointCounts{},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := endpoints.Count(tt.slices)
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestCount() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestWatcher_Observe(t *testing.T) {
+	w := endpoints.NewWatcher(nil, endpoints.Config{})
+	updates := w.Subscribe()
+	defer w.Unsubscribe(updates)
+
+	w.Observe([]discoveryv1.EndpointSlice{slice("api-a", "shop", "api", ptr.To(false))})
+	want := []types.ServiceEndpoints{{Namespace: "shop", Name: "api", NotReady: 1}}
+	got := <-updates
+	if !reflect.DeepEqual(got, want) {
+		t.Errorf("TestWatcher_Observe() = %v, want %v", got, want)
+	}
+
+	w.Observe([]discoveryv1.EndpointSlice{slice("api-b", "shop", "api", ptr.To(false))})
+	select {
+	case got := <-updates:
+		t.Errorf("TestWatcher_Observe() sent %v for unchanged counts", got)
+	default:
+	}
+}
@@ -0,0 +1,129 @@
+// Package export renders the hierarchy as diagram source for tools teams already keep their architecture
+// diagrams in. Every resource is drawn once, even where it appears under several parents, with an edge
+// from each parent to it.
+package export
+
+import (
+	"errors"
+	"fmt"
+	"io"
+	"strings"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// Format is a diagram language the hierarchy can be exported to
+type Format string
+
+const (
+	FormatD2       Format = "d2"
+	FormatPlantUML Format = "plantuml"
+)
+
+// ErrUnknownFormat is returned for a format that is not supported
+var ErrUnknownFormat = errors.New("unknown export format")
+
+// vertex is one resource in the diagram
+type vertex struct {
+	alias string
+	node  types.HierarchyNode
+}
+
+// edge joins the aliases of a parent and a child
+type edge struct {
+	from string
+	to   string
+}
+
+// graph is the hierarchy flattened into resources and the edges between them, in the order they are
+// first reached
+type graph struct {
+	vertices []vertex
+	edges    []edge
+}
+
+func newGraph(nodes []types.HierarchyNode) graph {
+	var g graph
+	aliases := make(map[string]string)
+	seen := make(map[edge]bool)
+	var walk func(nodes []types.HierarchyNode, parent string)
+	walk = func(nodes []types.HierarchyNode, parent string) {
+		for _, node := range nodes {
+			id := hierarchy.NodeID(node.Kind, node.Namespace, node.Name)
+			alias, exists := aliases[id]
+			if !exists {
+				alias = fmt.Sprintf("n%d", len(g.vertices))
+				aliases[id] = alias
+				g.vertices = append(g.vertices, vertex{alias: alias, node: node})
+			}
+			if parent != "" && !seen[edge{from: parent, to: alias}] {
+				seen[edge{from: parent, to: alias}] = true
+				g.edges = append(g.edges, edge{from: parent, to: alias})
+			}
+			walk(node.Relatives, alias)
+		}
+	}
+	walk(nodes, "")
+	return g
+}
+
+// Write renders nodes to w in format
+func Write(w io.Writer, format Format, nodes []types.HierarchyNode) error {
+	switch format {
+	case FormatD2:
+		return writeD2(w, newGraph(nodes))
+	case FormatPlantUML:
+		return writePlantUML(w, newGraph(nodes))
+	}
+	return fmt.Errorf("%w: %q", ErrUnknownFormat, format)
+}
+
+// ContentType is the media type of a diagram written in format
+func ContentType(format Format) string {
+	if format == FormatD2 {
+		return "text/vnd.d2; charset=utf-8"
+	}
+	return "text/plain; charset=utf-8"
+}
+
+func writeD2(w io.Writer, g graph) error {
+	var b strings.Builder
+	b.WriteString("direction: right\n")
+	for _, v := range g.vertices {
+		fmt.Fprintf(&b, "%s: %s\n", v.alias, quote(label(v.node)))
+	}
+	for _, e := range g.edges {
+		fmt.Fprintf(&b, "%s -> %s\n", e.from, e.to)
+	}
+	_, err := io.WriteString(w, b.String())
+	return err
+}
+
+func writePlantUML(w io.Writer, g graph) error {
+	var b strings.Builder
+	b.WriteString("@startuml\nleft to right direction\n")
+	for _, v := range g.vertices {
+		fmt.Fprintf(&b, "rectangle %s as %s\n", quote(label(v.node)), v.alias)
+	}
+	for _, e := range g.edges {
+		fmt.Fprintf(&b, "%s --> %s\n", e.from, e.to)
+	}
+	b.WriteString("@enduml\n")
+	_, err := io.WriteString(w, b.String())
+	return err
+}
+
+// label is the kind and name of a node on two lines, written with the \n escape both languages read
+func label(node types.HierarchyNode) string {
+	name := node.Name
+	if node.DisplayName != "" {
+		name = node.DisplayName
+	}
+	return string(node.Kind) + `\n` + name
+}
+
+// quote wraps s in double quotes, dropping quotes inside it, which neither language can escape in a label
+func quote(s string) string {
+	return `"` + strings.ReplaceAll(s, `"`, "") + `"`
+}
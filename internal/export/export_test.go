@@ -0,0 +1,84 @@
+package export_test
+
+import (
+	"bytes"
+	"errors"
+	"testing"
+
+	"k8s.io/utils/ptr"
+
+	"github.com/kdwils/constellation/internal/export"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func TestWrite(t *testing.T) {
+	pod := types.HierarchyNode{Kind: types.ResourceKindPod, Name: "web-0", Namespace: ptr.To("shop")}
+	nodes := []types.HierarchyNode{{
+		Kind: types.ResourceKindNamespace,
+		Name: "shop",
+		Relatives: []types.HierarchyNode{
+			{Kind: types.ResourceKindService, Name: "web", Namespace: ptr.To("shop"), Relatives: []types.HierarchyNode{pod}},
+			{Kind: types.ResourceKindService, Name: "web-canary", Namespace: ptr.To("shop"), Relatives: []types.HierarchyNode{pod}},
+			{Kind: types.ResourceKindTrack, Name: "web-canary", DisplayName: "canary", Namespace: ptr.To("shop")},
+		},
+	}}
+
+	tests := []struct {
+		name    string
+		format  export.Format
+		want    string
+		wantErr error
+	}{
+		{
+			name:   "d2",
+			format: export.FormatD2,
+			want: `direction: right
+n0: "Namespace\nshop"
+n1: "Service\nweb"
+n2: "Pod\nweb-0"
+n3: "Service\nweb-canary"
+n4: "Track\ncanary"
+n0 -> n1
+n1 -> n2
+n0 -> n3
+n3 -> n2
+n0 -> n4
+`,
+		},
+		{
+			name:   "plantuml",
+			format: export.FormatPlantUML,
+			want: `@startuml
+left to right direction
+rectangle "Namespace\nshop" as n0
+rectangle "Service\nweb" as n1
+rectangle "Pod\nweb-0" as n2
+rectangle "Service\nweb-canary" as n3
+rectangle "Track\ncanary" as n4
+n0 --> n1
+n1 --> n2
+n0 --> n3
+n3 --> n2
+n0 --> n4
+@enduml
+`,
+		},
+		{
+			name:    "unknown format",
+			format:  "dot",
+			wantErr: export.ErrUnknownFormat,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var got bytes.Buffer
+			err := export.Write(&got, tt.format, nodes)
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("TestWrite() error = %v, want %v", err, tt.wantErr)
+			}
+			if got.String() != tt.want {
+				t.Errorf("TestWrite() =\n%s\nwant\n%s", got.String(), tt.want)
+			}
+		})
+	}
+}
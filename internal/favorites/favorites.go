@@ -0,0 +1,176 @@
+// Package favorites keeps the nodes pinned on the dashboard, by node ID, so frequently watched resources
+// are one click away. Pins are kept in a ConfigMap when one is configured and survive restarts, otherwise
+// in memory.
+package favorites
+
+import (
+	"context"
+	"fmt"
+	"slices"
+	"sort"
+	"strings"
+	"sync"
+
+	corev1 "k8s.io/api/core/v1"
+	apierrors "k8s.io/apimachinery/pkg/api/errors"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+)
+
+// dataKey is the ConfigMap key holding the pinned node IDs, one per line
+const dataKey = "favorites"
+
+// Config names the ConfigMap pins are persisted in
+type Config struct {
+	Namespace string
+	// Name of the ConfigMap, created on the first pin. Pins are only kept in memory when empty.
+	Name string
+}
+
+// Backend loads and saves the pinned node IDs
+type Backend interface {
+	Load(ctx context.Context) ([]string, error)
+	Save(ctx context.Context, ids []string) error
+}
+
+// Store holds the pinned node IDs. They are read from its backend by Load, or by the first pin when Load
+// failed, so a pin never overwrites favorites that were not read.
+type Store struct {
+	backend Backend
+
+	mu     sync.Mutex
+	loaded bool
+	ids    []string
+}
+
+// NewStore creates a Store persisting pins to backend, or only in memory when backend is nil
+func NewStore(backend Backend) *Store {
+	return &Store{backend: backend}
+}
+
+// Load reads the pinned node IDs from the backend
+func (s *Store) Load(ctx context.Context) error {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+
+	return s.load(ctx)
+}
+
+// Favorites returns the pinned node IDs in ID order
+func (s *Store) Favorites() []string {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+
+	return append([]string{}, s.ids...)
+}
+
+// Pin adds id to the favorites
+func (s *Store) Pin(ctx context.Context, id string) error {
+	return s.update(ctx, func(ids []string) []string {
+		if slices.Contains(ids, id) {
+			return ids
+		}
+		ids = append(ids, id)
+		sort.Strings(ids)
+		return ids
+	})
+}
+
+// Unpin removes id from the favorites
+func (s *Store) Unpin(ctx context.Context, id string) error {
+	return s.update(ctx, func(ids []string) []string {
+		return slices.DeleteFunc(ids, func(pinned string) bool { return pinned == id })
+	})
+}
+
+// update saves the IDs fn returns before keeping them, so a failed save leaves the favorites unchanged
+func (s *Store) update(ctx context.Context, fn func(ids []string) []string) error {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+
+	if err := s.load(ctx); err != nil {
+		return err
+	}
+	ids := fn(slices.Clone(s.ids))
+	if s.backend != nil {
+		if err := s.backend.Save(ctx, ids); err != nil {
+			return err
+		}
+	}
+	s.ids = ids
+	return nil
+}
+
+func (s *Store) load(ctx context.Context) error {
+	if s.loaded || s.backend == nil {
+		return nil
+	}
+	ids, err := s.backend.Load(ctx)
+	if err != nil {
+		return err
+	}
+	sort.Strings(ids)
+	s.ids = ids
+	s.loaded = true
+	return nil
+}
+
+// +kubebuilder:rbac:groups="",resources=configmaps,verbs=get;create;update
+
+// ConfigMap persists pins in a ConfigMap. It reads through reader, e.g. the manager's API reader, so
+// ConfigMaps are not added to the cache.
+type ConfigMap struct {
+	reader client.Reader
+	writer client.Writer
+	key    client.ObjectKey
+}
+
+// NewConfigMap creates a ConfigMap backend for the ConfigMap named in config
+func NewConfigMap(reader client.Reader, writer client.Writer, config Config) *ConfigMap {
+	return &ConfigMap{
+		reader: reader,
+		writer: writer,
+		key:    client.ObjectKey{Namespace: config.Namespace, Name: config.Name},
+	}
+}
+
+// Load reads the pinned IDs, none when the ConfigMap does not exist yet
+func (c *ConfigMap) Load(ctx context.Context) ([]string, error) {
+	var configMap corev1.ConfigMap
+	err := c.reader.Get(ctx, c.key, &configMap)
+	if apierrors.IsNotFound(err) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, fmt.Errorf("failed to read favorites from %s: %w", c.key, err)
+	}
+	return strings.Fields(configMap.Data[dataKey]), nil
+}
+
+// Save writes ids, creating the ConfigMap if it does not exist
+func (c *ConfigMap) Save(ctx context.Context, ids []string) error {
+	var configMap corev1.ConfigMap
+	err := c.reader.Get(ctx, c.key, &configMap)
+	if apierrors.IsNotFound(err) {
+		configMap = corev1.ConfigMap{
+			ObjectMeta: metav1.ObjectMeta{Namespace: c.key.Namespace, Name: c.key.Name},
+			Data:       map[string]string{dataKey: strings.Join(ids, "\n")},
+		}
+		if err := c.writer.Create(ctx, &configMap); err != nil {
+			return fmt.Errorf("failed to create favorites in %s: %w", c.key, err)
+		}
+		return nil
+	}
+	if err != nil {
+		return fmt.Errorf("failed to read favorites from %s: %w", c.key, err)
+	}
+
+	if configMap.Data == nil {
+		configMap.Data = make(map[string]string)
+	}
+	configMap.Data[dataKey] = strings.Join(ids, "\n")
+	if err := c.writer.Update(ctx, &configMap); err != nil {
+		return fmt.Errorf("failed to save favorites to %s: %w", c.key, err)
+	}
+	return nil
+}
@@ -0,0 +1,91 @@
+package favorites_test
+
+import (
+	"context"
+	"reflect"
+	"testing"
+
+	corev1 "k8s.io/api/core/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"k8s.io/apimachinery/pkg/runtime"
+	utilruntime "k8s.io/apimachinery/pkg/util/runtime"
+	clientgoscheme "k8s.io/client-go/kubernetes/scheme"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	"sigs.k8s.io/controller-runtime/pkg/client/fake"
+
+	"github.com/kdwils/constellation/internal/favorites"
+)
+
+func TestStore_ConfigMap(t *testing.T) {
+	config := favorites.Config{Namespace: "constellation", Name: "favorites"}
+	configMap := func(data string) *corev1.ConfigMap {
+		return &corev1.ConfigMap{
+			ObjectMeta: metav1.ObjectMeta{Namespace: config.Namespace, Name: config.Name},
+			Data:       map[string]string{"favorites": data},
+		}
+	}
+
+	tests := []struct {
+		name     string
+		existing []client.Object
+		pin      []string
+		unpin    []string
+		want     []string
+		wantData string
+	}{
+		{
+			name:     "first pin creates the configmap",
+			pin:      []string{"Service/shop/web"},
+			want:     []string{"Service/shop/web"},
+			wantData: "Service/shop/web",
+		},
+		{
+			name:     "pins are added to the persisted ones",
+			existing: []client.Object{configMap("Service/shop/web")},
+			pin:      []string{"Namespace/ops"},
+			want:     []string{"Namespace/ops", "Service/shop/web"},
+			wantData: "Namespace/ops\nService/shop/web",
+		},
+		{
+			name:     "unpin",
+			existing: []client.Object{configMap("Namespace/ops\nService/shop/web")},
+			unpin:    []string{"Namespace/ops"},
+			want:     []string{"Service/shop/web"},
+			wantData: "Service/shop/web",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			ctx := context.Background()
+			scheme := runtime.NewScheme()
+			utilruntime.Must(clientgoscheme.AddToScheme(scheme))
+			c := fake.NewClientBuilder().WithScheme(scheme).WithObjects(tt.existing...).Build()
+			store := favorites.NewStore(favorites.NewConfigMap(c, c, config))
+
+			if err := store.Load(ctx); err != nil {
+				t.Fatalf("TestStore_ConfigMap() error = %v", err)
+			}
+			for _, id := range tt.pin {
+				if err := store.Pin(ctx, id); err != nil {
+					t.Fatalf("TestStore_ConfigMap() error = %v", err)
+				}
+			}
+			for _, id := range tt.unpin {
+				if err := store.Unpin(ctx, id); err != nil {
+					t.Fatalf("TestStore_ConfigMap() error = %v", err)
+				}
+			}
+
+			if got := store.Favorites(); !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestStore_ConfigMap() = %v, want %v", got, tt.want)
+			}
+			var persisted corev1.ConfigMap
+			if err := c.Get(ctx, client.ObjectKey{Namespace: config.Namespace, Name: config.Name}, &persisted); err != nil {
+				t.Fatalf("TestStore_ConfigMap() error = %v", err)
+			}
+			if got := persisted.Data["favorites"]; got != tt.wantData {
+				t.Errorf("TestStore_ConfigMap() persisted = %q, want %q", got, tt.wantData)
+			}
+		})
+	}
+}
@@ -0,0 +1,267 @@
+// Package follower serves constellation from another instance's state instead of the apiserver. Health and
+// the hierarchy are streamed from the leader as they change and the raw objects behind /snapshot are fetched
+// again on an interval, so read replicas scale the dashboard out without each adding its own watches on the
+// apiserver.
+package follower
+
+import (
+	"context"
+	"sync"
+	"time"
+
+	"sigs.k8s.io/controller-runtime/pkg/log"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const (
+	defaultRefreshInterval = 30 * time.Second
+	defaultRetryInterval   = 5 * time.Second
+)
+
+// Config controls how the leader is followed
+type Config struct {
+	// Leader is the base URL of the instance followed, e.g. http://constellation.monitoring:8080
+	Leader string
+	// RefreshInterval is how often the leader's snapshot is fetched again for its raw objects, defaulting to 30s
+	RefreshInterval time.Duration
+	// RetryInterval is how long to wait before reconnecting to one of the leader's streams, defaulting to 5s
+	RetryInterval time.Duration
+}
+
+// Leader is the API of the instance followed, e.g. a pkg/client Client
+type Leader interface {
+	GetSnapshot(ctx context.Context) (types.Snapshot, error)
+	StreamUpdates(ctx context.Context, update func([]*types.ServiceHealthInfo) error) error
+	StreamHierarchy(ctx context.Context, update func([]types.HierarchyNode) error) error
+}
+
+// Follower holds the state replicated from the leader. It is a health, hierarchy, snapshot and sync
+// status provider for the server.
+type Follower struct {
+	leader Leader
+	config Config
+
+	mu          sync.RWMutex
+	snapshot    types.Snapshot
+	health      []*types.ServiceHealthInfo
+	synced      bool
+	unreachable bool
+	// streaming is set while the hierarchy stream is connected, so fetched snapshots, which may be older,
+	// do not replace the streamed hierarchy
+	streaming bool
+
+	subMu       sync.RWMutex
+	subscribers map[chan []*types.ServiceHealthInfo]bool
+}
+
+// New creates a Follower replicating leader
+func New(leader Leader, config Config) *Follower {
+	if config.RefreshInterval <= 0 {
+		config.RefreshInterval = defaultRefreshInterval
+	}
+	if config.RetryInterval <= 0 {
+		config.RetryInterval = defaultRetryInterval
+	}
+	return &Follower{
+		leader:      leader,
+		config:      config,
+		subscribers: make(map[chan []*types.ServiceHealthInfo]bool),
+	}
+}
+
+// Start streams health and the hierarchy from the leader and refreshes its raw objects until ctx is cancelled
+func (f *Follower) Start(ctx context.Context) error {
+	go f.follow(ctx, "health", func(ctx context.Context) error {
+		return f.leader.StreamUpdates(ctx, func(health []*types.ServiceHealthInfo) error {
+			f.update(health)
+			return nil
+		})
+	})
+	go f.follow(ctx, "hierarchy", func(ctx context.Context) error {
+		defer f.setStreaming(false)
+		return f.leader.StreamHierarchy(ctx, func(nodes []types.HierarchyNode) error {
+			f.updateHierarchy(nodes)
+			return nil
+		})
+	})
+
+	ticker := time.NewTicker(f.config.RefreshInterval)
+	defer ticker.Stop()
+	for {
+		f.refresh(ctx)
+		select {
+		case <-ctx.Done():
+			return nil
+		case <-ticker.C:
+		}
+	}
+}
+
+// follow runs one of the leader's streams, reconnecting after config.RetryInterval when it drops
+func (f *Follower) follow(ctx context.Context, name string, stream func(ctx context.Context) error) {
+	logger := log.FromContext(ctx).WithName("follower")
+	for {
+		err := stream(ctx)
+		if ctx.Err() != nil {
+			return
+		}
+		logger.Error(err, "lost a stream from the leader, reconnecting", "stream", name, "leader", f.config.Leader)
+		f.setUnreachable(true)
+
+		select {
+		case <-ctx.Done():
+			return
+		case <-time.After(f.config.RetryInterval):
+		}
+	}
+}
+
+func (f *Follower) refresh(ctx context.Context) {
+	snapshot, err := f.leader.GetSnapshot(ctx)
+	if err != nil {
+		logger := log.FromContext(ctx).WithName("follower")
+		logger.Error(err, "failed to fetch the leader's snapshot", "leader", f.config.Leader)
+		f.setUnreachable(true)
+		return
+	}
+
+	f.mu.Lock()
+	if f.streaming {
+		snapshot.Hierarchy = f.snapshot.Hierarchy
+	}
+	f.snapshot = snapshot
+	if f.health == nil {
+		f.health = snapshot.Health
+	}
+	f.synced = true
+	f.unreachable = false
+	f.mu.Unlock()
+	f.notifySubscribers()
+}
+
+func (f *Follower) update(health []*types.ServiceHealthInfo) {
+	f.mu.Lock()
+	f.health = health
+	f.unreachable = false
+	f.mu.Unlock()
+	f.notifySubscribers()
+}
+
+func (f *Follower) updateHierarchy(nodes []types.HierarchyNode) {
+	f.mu.Lock()
+	f.snapshot.Hierarchy = nodes
+	f.streaming = true
+	f.unreachable = false
+	f.mu.Unlock()
+	f.notifySubscribers()
+}
+
+func (f *Follower) setStreaming(streaming bool) {
+	f.mu.Lock()
+	defer f.mu.Unlock()
+	f.streaming = streaming
+}
+
+func (f *Follower) setUnreachable(unreachable bool) {
+	f.mu.Lock()
+	defer f.mu.Unlock()
+	f.unreachable = unreachable
+}
+
+// GetAllHealthData returns the health last received from the leader
+func (f *Follower) GetAllHealthData() []*types.ServiceHealthInfo {
+	f.mu.RLock()
+	defer f.mu.RUnlock()
+	if f.health == nil {
+		return []*types.ServiceHealthInfo{}
+	}
+	return f.health
+}
+
+// Subscribe creates a new subscription channel for health data updates
+func (f *Follower) Subscribe() chan []*types.ServiceHealthInfo {
+	f.subMu.Lock()
+	defer f.subMu.Unlock()
+
+	ch := make(chan []*types.ServiceHealthInfo, 1)
+	f.subscribers[ch] = true
+	return ch
+}
+
+// Unsubscribe removes a subscription channel
+func (f *Follower) Unsubscribe(ch chan []*types.ServiceHealthInfo) {
+	f.subMu.Lock()
+	defer f.subMu.Unlock()
+
+	delete(f.subscribers, ch)
+	close(ch)
+}
+
+func (f *Follower) notifySubscribers() {
+	f.subMu.RLock()
+	defer f.subMu.RUnlock()
+
+	data := f.GetAllHealthData()
+	for ch := range f.subscribers {
+		select {
+		case ch <- data:
+		default:
+		}
+	}
+}
+
+// GetHierarchy returns the leader's last hierarchy with the latest streamed health attached to its
+// Service nodes
+func (f *Follower) GetHierarchy(ctx context.Context) ([]types.HierarchyNode, error) {
+	f.mu.RLock()
+	defer f.mu.RUnlock()
+
+	health := make(map[string]*types.ServiceHealthInfo, len(f.health))
+	for _, info := range f.health {
+		health[info.Namespace+"/"+info.ServiceName] = info
+	}
+	return withHealth(f.snapshot.Hierarchy, health), nil
+}
+
+// Snapshot returns the leader's last snapshot with the health streamed since
+func (f *Follower) Snapshot(ctx context.Context) (*types.Snapshot, error) {
+	nodes, err := f.GetHierarchy(ctx)
+	if err != nil {
+		return nil, err
+	}
+
+	f.mu.RLock()
+	defer f.mu.RUnlock()
+	return &types.Snapshot{
+		Version:   types.SnapshotVersion,
+		Timestamp: f.snapshot.Timestamp,
+		Hierarchy: nodes,
+		Health:    f.health,
+		Objects:   f.snapshot.Objects,
+	}, nil
+}
+
+// SyncStatus reports syncing until the first snapshot arrives and degraded while the leader is unreachable
+func (f *Follower) SyncStatus(ctx context.Context) types.SyncStatus {
+	f.mu.RLock()
+	defer f.mu.RUnlock()
+	return types.SyncStatus{Syncing: !f.synced, Degraded: f.unreachable}
+}
+
+// withHealth copies nodes, so the shared snapshot is never modified by callers, setting the health
+// of every Service node from health
+func withHealth(nodes []types.HierarchyNode, health map[string]*types.ServiceHealthInfo) []types.HierarchyNode {
+	if nodes == nil {
+		return nil
+	}
+	result := make([]types.HierarchyNode, len(nodes))
+	for i, node := range nodes {
+		if node.Kind == types.ResourceKindService && node.Namespace != nil {
+			node.HealthInfo = health[*node.Namespace+"/"+node.Name]
+		}
+		node.Relatives = withHealth(node.Relatives, health)
+		result[i] = node
+	}
+	return result
+}
@@ -0,0 +1,158 @@
+package follower_test
+
+import (
+	"context"
+	"reflect"
+	"sync/atomic"
+	"testing"
+	"time"
+
+	"github.com/kdwils/constellation/internal/follower"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// fakeLeader serves snapshot and streams health and, when set, hierarchy once. refreshed is closed on the
+// second fetch, once the follower has stored the first, streamed once the follower has the health and
+// hierarchyStreamed once it has the hierarchy.
+type fakeLeader struct {
+	snapshot          types.Snapshot
+	health            []*types.ServiceHealthInfo
+	hierarchy         []types.HierarchyNode
+	fetches           atomic.Int32
+	refreshed         chan struct{}
+	streamed          chan struct{}
+	hierarchyStreamed chan struct{}
+}
+
+func (l *fakeLeader) GetSnapshot(ctx context.Context) (types.Snapshot, error) {
+	if l.fetches.Add(1) == 2 {
+		close(l.refreshed)
+	}
+	return l.snapshot, nil
+}
+
+func (l *fakeLeader) StreamUpdates(ctx context.Context, update func([]*types.ServiceHealthInfo) error) error {
+	if err := update(l.health); err != nil {
+		return err
+	}
+	close(l.streamed)
+	<-ctx.Done()
+	return ctx.Err()
+}
+
+func (l *fakeLeader) StreamHierarchy(ctx context.Context, update func([]types.HierarchyNode) error) error {
+	if l.hierarchy != nil {
+		if err := update(l.hierarchy); err != nil {
+			return err
+		}
+	}
+	close(l.hierarchyStreamed)
+	<-ctx.Done()
+	return ctx.Err()
+}
+
+func TestFollower_GetHierarchy(t *testing.T) {
+	ns := "default"
+	stale := &types.ServiceHealthInfo{ServiceName: "web", Namespace: ns, Status: types.HealthStatusUnhealthy}
+	fresh := &types.ServiceHealthInfo{ServiceName: "web", Namespace: ns, Status: types.HealthStatusHealthy}
+
+	tests := []struct {
+		name      string
+		health    []*types.ServiceHealthInfo
+		hierarchy []types.HierarchyNode
+		want      []types.HierarchyNode
+	}{
+		{
+			name:   "streamed health replaces the snapshot's",
+			health: []*types.ServiceHealthInfo{fresh},
+			want: []types.HierarchyNode{{
+				Kind: types.ResourceKindNamespace,
+				Name: ns,
+				Relatives: []types.HierarchyNode{
+					{Kind: types.ResourceKindService, Name: "web", Namespace: &ns, HealthInfo: fresh},
+				},
+			}},
+		},
+		{
+			name:   "streamed hierarchy replaces the snapshot's",
+			health: []*types.ServiceHealthInfo{fresh},
+			hierarchy: []types.HierarchyNode{{
+				Kind: types.ResourceKindNamespace,
+				Name: ns,
+				Relatives: []types.HierarchyNode{
+					{Kind: types.ResourceKindService, Name: "web", Namespace: &ns},
+					{Kind: types.ResourceKindService, Name: "api", Namespace: &ns},
+				},
+			}},
+			want: []types.HierarchyNode{{
+				Kind: types.ResourceKindNamespace,
+				Name: ns,
+				Relatives: []types.HierarchyNode{
+					{Kind: types.ResourceKindService, Name: "web", Namespace: &ns, HealthInfo: fresh},
+					{Kind: types.ResourceKindService, Name: "api", Namespace: &ns},
+				},
+			}},
+		},
+		{
+			name:   "services missing from the stream have no health",
+			health: []*types.ServiceHealthInfo{},
+			want: []types.HierarchyNode{{
+				Kind: types.ResourceKindNamespace,
+				Name: ns,
+				Relatives: []types.HierarchyNode{
+					{Kind: types.ResourceKindService, Name: "web", Namespace: &ns},
+				},
+			}},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			leader := &fakeLeader{
+				snapshot: types.Snapshot{
+					Hierarchy: []types.HierarchyNode{{
+						Kind: types.ResourceKindNamespace,
+						Name: ns,
+						Relatives: []types.HierarchyNode{
+							{Kind: types.ResourceKindService, Name: "web", Namespace: &ns, HealthInfo: stale},
+						},
+					}},
+					Health: []*types.ServiceHealthInfo{stale},
+				},
+				health:            tt.health,
+				hierarchy:         tt.hierarchy,
+				refreshed:         make(chan struct{}),
+				streamed:          make(chan struct{}),
+				hierarchyStreamed: make(chan struct{}),
+			}
+			f := follower.New(leader, follower.Config{Leader: "http://leader:8080", RefreshInterval: time.Millisecond})
+
+			ctx, cancel := context.WithCancel(context.Background())
+			defer cancel()
+			go f.Start(ctx) //nolint:errcheck
+			<-leader.streamed
+			<-leader.hierarchyStreamed
+			<-leader.refreshed
+
+			got, err := f.GetHierarchy(ctx)
+			if err != nil {
+				t.Fatalf("TestFollower_GetHierarchy() error = %v", err)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestFollower_GetHierarchy() = %+v, want %+v", got, tt.want)
+			}
+			if status := f.SyncStatus(ctx); !reflect.DeepEqual(status, types.SyncStatus{}) {
+				t.Errorf("TestFollower_GetHierarchy() SyncStatus = %+v, want %+v", status, types.SyncStatus{})
+			}
+		})
+	}
+}
+
+func TestFollower_SyncStatus(t *testing.T) {
+	f := follower.New(&fakeLeader{}, follower.Config{})
+
+	got := f.SyncStatus(context.Background())
+	want := types.SyncStatus{Syncing: true}
+	if !reflect.DeepEqual(got, want) {
+		t.Errorf("TestFollower_SyncStatus() = %+v, want %+v", got, want)
+	}
+}
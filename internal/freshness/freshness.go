@@ -0,0 +1,94 @@
+// Package freshness records when constellation last saw a change to each kind of resource and whether
+// the informers feeding it are still running, so API consumers can tell live data from stale data.
+package freshness
+
+import (
+	"context"
+	"sync"
+	"time"
+
+	"sigs.k8s.io/controller-runtime/pkg/cache"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// Tracker records the latest event per kind and checks the health of the informers watching them
+type Tracker struct {
+	informers types.Informers
+	watched   map[types.ResourceKind]client.Object
+	now       func() time.Time
+
+	mu     sync.RWMutex
+	kinds  map[types.ResourceKind]types.KindSync
+	synced bool
+}
+
+// NewTracker creates a Tracker reporting on the informers in informers for the watched kinds
+func NewTracker(informers types.Informers, watched map[types.ResourceKind]client.Object, opts ...TrackerOpt) *Tracker {
+	t := &Tracker{
+		informers: informers,
+		watched:   watched,
+		now:       time.Now,
+		kinds:     make(map[types.ResourceKind]types.KindSync),
+	}
+
+	for _, opt := range opts {
+		opt(t)
+	}
+
+	return t
+}
+
+type TrackerOpt func(*Tracker)
+
+// WithClock replaces the clock used to timestamp events
+func WithClock(now func() time.Time) TrackerOpt {
+	return func(t *Tracker) {
+		t.now = now
+	}
+}
+
+// Observe records an event for kind. resourceVersion is kept unless empty, as it is for deletions.
+func (t *Tracker) Observe(kind types.ResourceKind, resourceVersion string) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	current := t.kinds[kind]
+	current.LastEventTime = t.now().UTC()
+	if resourceVersion != "" {
+		current.ResourceVersion = resourceVersion
+	}
+	t.kinds[kind] = current
+}
+
+// MarkSynced records that the initial state has been built. Until then SyncStatus reports syncing.
+func (t *Tracker) MarkSynced() {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	t.synced = true
+}
+
+// SyncStatus returns the latest event per kind. It is degraded when an informer for a watched kind
+// cannot be found, has stopped, or has not finished its initial list.
+func (t *Tracker) SyncStatus(ctx context.Context) types.SyncStatus {
+	t.mu.RLock()
+	status := types.SyncStatus{
+		Kinds:   make(map[types.ResourceKind]types.KindSync, len(t.kinds)),
+		Syncing: !t.synced,
+	}
+	for kind, kindSync := range t.kinds {
+		status.Kinds[kind] = kindSync
+	}
+	t.mu.RUnlock()
+
+	for _, obj := range t.watched {
+		informer, err := t.informers.GetInformer(ctx, obj, cache.BlockUntilSynced(false))
+		if err != nil || informer.IsStopped() || !informer.HasSynced() {
+			status.Degraded = true
+			break
+		}
+	}
+	return status
+}
@@ -0,0 +1,95 @@
+package freshness_test
+
+import (
+	"context"
+	"errors"
+	"reflect"
+	"testing"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	"sigs.k8s.io/controller-runtime/pkg/cache"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+
+	"github.com/kdwils/constellation/internal/freshness"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+type fakeInformer struct {
+	cache.Informer
+	synced  bool
+	stopped bool
+}
+
+func (f fakeInformer) HasSynced() bool { return f.synced }
+func (f fakeInformer) IsStopped() bool { return f.stopped }
+
+type fakeInformers struct {
+	informer fakeInformer
+	err      error
+}
+
+func (f fakeInformers) GetInformer(context.Context, client.Object, ...cache.InformerGetOption) (cache.Informer, error) {
+	return f.informer, f.err
+}
+
+func TestTracker_SyncStatus(t *testing.T) {
+	now := time.Date(2025, 1, 2, 3, 4, 5, 0, time.UTC)
+	kinds := map[types.ResourceKind]types.KindSync{
+		types.ResourceKindService: {LastEventTime: now, ResourceVersion: "42"},
+	}
+
+	tests := []struct {
+		name       string
+		informers  fakeInformers
+		markSynced bool
+		want       types.SyncStatus
+	}{
+		{
+			name:       "synced informers",
+			informers:  fakeInformers{informer: fakeInformer{synced: true}},
+			markSynced: true,
+			want:       types.SyncStatus{Kinds: kinds},
+		},
+		{
+			name:       "informer still listing",
+			informers:  fakeInformers{informer: fakeInformer{}},
+			markSynced: true,
+			want:       types.SyncStatus{Kinds: kinds, Degraded: true},
+		},
+		{
+			name:       "informer stopped",
+			informers:  fakeInformers{informer: fakeInformer{synced: true, stopped: true}},
+			markSynced: true,
+			want:       types.SyncStatus{Kinds: kinds, Degraded: true},
+		},
+		{
+			name:       "informer missing",
+			informers:  fakeInformers{err: errors.New("no informer")},
+			markSynced: true,
+			want:       types.SyncStatus{Kinds: kinds, Degraded: true},
+		},
+		{
+			name:      "initial state still being built",
+			informers: fakeInformers{informer: fakeInformer{synced: true}},
+			want:      types.SyncStatus{Kinds: kinds, Syncing: true},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			tracker := freshness.NewTracker(tt.informers, map[types.ResourceKind]client.Object{
+				types.ResourceKindService: &corev1.Service{},
+			}, freshness.WithClock(func() time.Time { return now }))
+			tracker.Observe(types.ResourceKindService, "42")
+			tracker.Observe(types.ResourceKindService, "")
+			if tt.markSynced {
+				tracker.MarkSynced()
+			}
+
+			got := tracker.SyncStatus(context.Background())
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestTracker_SyncStatus() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,282 @@
+// Package graphql implements a subset of GraphQL over the resource hierarchy and its health updates.
+// Queries resolve against the hierarchy, which has a type per ResourceKind: each kind has a root field
+// listing its nodes and inline fragments (... on Pod { phase }) select fields for one kind. Subscriptions
+// stream the health updates the server broadcasts. Operations may declare variables and use them as
+// arguments. Named fragments, mutations, directives and introspection are not supported.
+package graphql
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// HierarchySource supplies the hierarchy that queries are resolved against
+type HierarchySource interface {
+	GetHierarchy(ctx context.Context) ([]types.HierarchyNode, error)
+}
+
+// Request is a GraphQL request body
+type Request struct {
+	Query     string         `json:"query"`
+	Variables map[string]any `json:"variables,omitempty"`
+}
+
+// Response is a GraphQL response body
+type Response struct {
+	Data   map[string]any `json:"data,omitempty"`
+	Errors []Error        `json:"errors,omitempty"`
+}
+
+// Error is a single GraphQL error entry
+type Error struct {
+	Message string `json:"message"`
+}
+
+// kindFields are the root fields listing every node of one kind, wherever it sits in the tree
+var kindFields = map[string]types.ResourceKind{
+	"namespaces":    types.ResourceKindNamespace,
+	"services":      types.ResourceKindService,
+	"pods":          types.ResourceKindPod,
+	"httpRoutes":    types.ResourceKindHTTPRoute,
+	"trafficSplits": types.ResourceKindTrafficSplit,
+	"tracks":        types.ResourceKindTrack,
+	"nodes":         types.ResourceKindNode,
+	"gateways":      types.ResourceKindGateway,
+	"applications":  types.ResourceKindApplication,
+	"deployments":   types.ResourceKindDeployment,
+	"statefulSets":  types.ResourceKindStatefulSet,
+	"hostnames":     types.ResourceKindHostname,
+}
+
+// Execute parses and resolves a query against the current hierarchy.
+//
+// Root fields:
+//
+//	hierarchy(namespace: String, kind: String): [Node]
+//	node(kind: String!, namespace: String, name: String!): Node
+//	namespaces, services, pods, httpRoutes, trafficSplits, tracks, nodes, gateways, applications,
+//	deployments, statefulSets, hostnames (namespace: String, name: String): [<Kind>]
+//
+// Node fields are the JSON field names of a hierarchy node, with relatives resolving to nested nodes.
+// Subscriptions are rejected; they are run with Subscribe.
+func Execute(ctx context.Context, source HierarchySource, request Request) Response {
+	op, err := parse(request.Query, request.Variables)
+	if err != nil {
+		return errorResponse(err)
+	}
+	if op.kind == operationSubscription {
+		return errorResponse(fmt.Errorf("subscriptions must be streamed over a WebSocket"))
+	}
+
+	nodes, err := source.GetHierarchy(ctx)
+	if err != nil {
+		return errorResponse(err)
+	}
+
+	data := make(map[string]any, len(op.selections))
+	for _, sel := range op.selections {
+		value, err := resolveRoot(nodes, sel)
+		if err != nil {
+			return errorResponse(err)
+		}
+		projected, err := project(value, sel.selections)
+		if err != nil {
+			return errorResponse(err)
+		}
+		data[sel.responseKey()] = projected
+	}
+	return Response{Data: data}
+}
+
+// Subscription is a parsed subscription operation, resolved against each health update it is given
+type Subscription struct {
+	selections []selection
+}
+
+// Subscribe parses a subscription operation.
+//
+// Root fields:
+//
+//	health(namespace: String, service: String, status: String): [ServiceHealth]
+//
+// ServiceHealth fields are the JSON field names of a service's health info.
+func Subscribe(request Request) (*Subscription, error) {
+	op, err := parse(request.Query, request.Variables)
+	if err != nil {
+		return nil, err
+	}
+	if op.kind != operationSubscription {
+		return nil, fmt.Errorf("only subscription operations can be streamed")
+	}
+	for _, sel := range op.selections {
+		if sel.name != "health" {
+			return nil, fmt.Errorf("unknown subscription field %q", sel.name)
+		}
+	}
+	return &Subscription{selections: op.selections}, nil
+}
+
+// Resolve projects one health update through the subscription's selections
+func (s *Subscription) Resolve(health []*types.ServiceHealthInfo) Response {
+	data := make(map[string]any, len(s.selections))
+	for _, sel := range s.selections {
+		matched := make([]*types.ServiceHealthInfo, 0, len(health))
+		for _, info := range health {
+			if matchHealth(info, sel.args) {
+				matched = append(matched, info)
+			}
+		}
+		value, err := toGeneric(matched)
+		if err != nil {
+			return errorResponse(err)
+		}
+		projected, err := project(value, sel.selections)
+		if err != nil {
+			return errorResponse(err)
+		}
+		data[sel.responseKey()] = projected
+	}
+	return Response{Data: data}
+}
+
+func matchHealth(info *types.ServiceHealthInfo, args map[string]string) bool {
+	if namespace, ok := args["namespace"]; ok && info.Namespace != namespace {
+		return false
+	}
+	if service, ok := args["service"]; ok && info.ServiceName != service {
+		return false
+	}
+	if status, ok := args["status"]; ok && string(info.Status) != status {
+		return false
+	}
+	return true
+}
+
+func errorResponse(err error) Response {
+	return Response{Errors: []Error{{Message: err.Error()}}}
+}
+
+func resolveRoot(nodes []types.HierarchyNode, sel selection) (any, error) {
+	if kind, ok := kindFields[sel.name]; ok {
+		return toGeneric(collect(nodes, func(node types.HierarchyNode) bool {
+			return node.Kind == kind && matchNode(node, sel.args)
+		}))
+	}
+
+	switch sel.name {
+	case "hierarchy":
+		var matched []types.HierarchyNode
+		for _, node := range nodes {
+			if namespace, ok := sel.args["namespace"]; ok && node.Name != namespace {
+				continue
+			}
+			matched = append(matched, node)
+		}
+		if kind, ok := sel.args["kind"]; ok {
+			matched = collect(matched, func(node types.HierarchyNode) bool { return string(node.Kind) == kind })
+		}
+		return toGeneric(matched)
+	case "node":
+		kind, name := sel.args["kind"], sel.args["name"]
+		if kind == "" || name == "" {
+			return nil, fmt.Errorf("node requires kind and name arguments")
+		}
+		found := collect(nodes, func(node types.HierarchyNode) bool {
+			return string(node.Kind) == kind && matchNode(node, sel.args)
+		})
+		if len(found) == 0 {
+			return nil, nil
+		}
+		return toGeneric(found[0])
+	}
+	return nil, fmt.Errorf("unknown root field %q", sel.name)
+}
+
+// matchNode reports whether node has the namespace and name arguments, where given
+func matchNode(node types.HierarchyNode, args map[string]string) bool {
+	if name, ok := args["name"]; ok && node.Name != name {
+		return false
+	}
+	namespace, ok := args["namespace"]
+	return !ok || (node.Namespace != nil && *node.Namespace == namespace)
+}
+
+// collect walks the whole tree and returns every node matching the predicate
+func collect(nodes []types.HierarchyNode, match func(types.HierarchyNode) bool) []types.HierarchyNode {
+	var found []types.HierarchyNode
+	for _, node := range nodes {
+		if match(node) {
+			found = append(found, node)
+		}
+		found = append(found, collect(node.Relatives, match)...)
+	}
+	return found
+}
+
+func toGeneric(value any) (any, error) {
+	data, err := json.Marshal(value)
+	if err != nil {
+		return nil, err
+	}
+	var generic any
+	if err := json.Unmarshal(data, &generic); err != nil {
+		return nil, err
+	}
+	return generic, nil
+}
+
+// project keeps only the selected fields of a resolved value.
+// Fields that are absent on a node resolve to null, matching GraphQL nullable semantics.
+func project(value any, selections []selection) (any, error) {
+	if value == nil || len(selections) == 0 {
+		return value, nil
+	}
+
+	switch v := value.(type) {
+	case []any:
+		result := make([]any, 0, len(v))
+		for _, item := range v {
+			projected, err := project(item, selections)
+			if err != nil {
+				return nil, err
+			}
+			result = append(result, projected)
+		}
+		return result, nil
+	case map[string]any:
+		result := make(map[string]any, len(selections))
+		for _, sel := range selections {
+			if sel.typeCondition != "" {
+				if err := projectFragment(v, sel, result); err != nil {
+					return nil, err
+				}
+				continue
+			}
+			projected, err := project(v[sel.name], sel.selections)
+			if err != nil {
+				return nil, err
+			}
+			result[sel.responseKey()] = projected
+		}
+		return result, nil
+	}
+	return nil, fmt.Errorf("cannot select fields on a scalar value")
+}
+
+// projectFragment adds the fields an inline fragment selects to result when node is of its kind
+func projectFragment(node map[string]any, fragment selection, result map[string]any) error {
+	if node["kind"] != fragment.typeCondition {
+		return nil
+	}
+	projected, err := project(node, fragment.selections)
+	if err != nil {
+		return err
+	}
+	for key, value := range projected.(map[string]any) {
+		result[key] = value
+	}
+	return nil
+}
@@ -0,0 +1,187 @@
+package graphql_test
+
+import (
+	"context"
+	"encoding/json"
+	"testing"
+
+	"k8s.io/utils/ptr"
+
+	"github.com/kdwils/constellation/internal/graphql"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+type staticSource []types.HierarchyNode
+
+func (s staticSource) GetHierarchy(ctx context.Context) ([]types.HierarchyNode, error) {
+	return s, nil
+}
+
+func TestExecute(t *testing.T) {
+	source := staticSource{
+		{
+			Kind: types.ResourceKindNamespace,
+			Name: "shop",
+			Relatives: []types.HierarchyNode{
+				{
+					Kind:        types.ResourceKindService,
+					Name:        "cart",
+					Namespace:   ptr.To("shop"),
+					ServiceType: ptr.To("ClusterIP"),
+					Relatives: []types.HierarchyNode{
+						{Kind: types.ResourceKindPod, Name: "cart-0", Namespace: ptr.To("shop"), Phase: ptr.To("Running")},
+					},
+				},
+			},
+		},
+	}
+
+	tests := []struct {
+		name      string
+		query     string
+		variables map[string]any
+		want      string
+	}{
+		{
+			name:  "nested selection",
+			query: `{ hierarchy { name relatives { kind name } } }`,
+			want:  `{"data":{"hierarchy":[{"name":"shop","relatives":[{"kind":"Service","name":"cart"}]}]}}`,
+		},
+		{
+			name:  "kind filter with alias",
+			query: `query Pods { pods: hierarchy(kind: "Pod") { name phase } }`,
+			want:  `{"data":{"pods":[{"name":"cart-0","phase":"Running"}]}}`,
+		},
+		{
+			name:  "single node lookup",
+			query: `{ node(kind: "Service", namespace: "shop", name: "cart") { service_type relatives { name } } }`,
+			want:  `{"data":{"node":{"relatives":[{"name":"cart-0"}],"service_type":"ClusterIP"}}}`,
+		},
+		{
+			name:  "kind root field",
+			query: `{ pods(namespace: "shop") { name } services { name } }`,
+			want:  `{"data":{"pods":[{"name":"cart-0"}],"services":[{"name":"cart"}]}}`,
+		},
+		{
+			name:  "inline fragments select by kind",
+			query: `{ hierarchy { relatives { name ... on Service { service_type } ... on Pod { phase } } } }`,
+			want:  `{"data":{"hierarchy":[{"relatives":[{"name":"cart","service_type":"ClusterIP"}]}]}}`,
+		},
+		{
+			name:      "variables as arguments",
+			query:     `query Find($kind: String!, $name: String!) { node(kind: $kind, name: $name) { phase } }`,
+			variables: map[string]any{"kind": "Pod", "name": "cart-0"},
+			want:      `{"data":{"node":{"phase":"Running"}}}`,
+		},
+		{
+			name:  "variable default",
+			query: `query ($kind: String = "Service") { hierarchy(kind: $kind) { name } }`,
+			want:  `{"data":{"hierarchy":[{"name":"cart"}]}}`,
+		},
+		{
+			name:  "unbound optional variable leaves the argument out",
+			query: `query ($namespace: String) { pods(namespace: $namespace) { name } }`,
+			want:  `{"data":{"pods":[{"name":"cart-0"}]}}`,
+		},
+		{
+			name:  "missing required variable",
+			query: `query ($name: String!) { pods(name: $name) { name } }`,
+			want:  `{"errors":[{"message":"variable $name is required"}]}`,
+		},
+		{
+			name:  "undeclared variable",
+			query: `{ pods(name: $name) { name } }`,
+			want:  `{"errors":[{"message":"variable $name is not declared"}]}`,
+		},
+		{
+			name:  "subscription is not executed",
+			query: `subscription { health { status } }`,
+			want:  `{"errors":[{"message":"subscriptions must be streamed over a WebSocket"}]}`,
+		},
+		{
+			name:  "mutation",
+			query: `mutation { pin(id: "x") }`,
+			want:  `{"errors":[{"message":"mutations are not supported"}]}`,
+		},
+		{
+			name:  "unknown root field",
+			query: `{ pods { name } }`,
+			want:  `{"errors":[{"message":"unknown root field \"pods\""}]}`,
+		},
+		{
+			name:  "syntax error",
+			query: `{ hierarchy { name }`,
+			want:  `{"errors":[{"message":"expected field name, got \"\""}]}`,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := json.Marshal(graphql.Execute(context.Background(), source, graphql.Request{Query: tt.query, Variables: tt.variables}))
+			if err != nil {
+				t.Fatalf("TestExecute() marshal error = %v", err)
+			}
+			if string(got) != tt.want {
+				t.Errorf("TestExecute() = %s, want %s", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestSubscription(t *testing.T) {
+	health := []*types.ServiceHealthInfo{
+		{ServiceName: "cart", Namespace: "shop", Status: types.HealthStatusHealthy},
+		{ServiceName: "web", Namespace: "shop", Status: types.HealthStatusUnhealthy},
+		{ServiceName: "api", Namespace: "admin", Status: types.HealthStatusHealthy},
+	}
+
+	tests := []struct {
+		name      string
+		query     string
+		variables map[string]any
+		want      string
+		wantErr   string
+	}{
+		{
+			name:  "every service",
+			query: `subscription { health { service_name } }`,
+			want:  `{"data":{"health":[{"service_name":"cart"},{"service_name":"web"},{"service_name":"api"}]}}`,
+		},
+		{
+			name:      "filtered by variable",
+			query:     `subscription Shop($namespace: String!) { down: health(namespace: $namespace, status: "unhealthy") { service_name status } }`,
+			variables: map[string]any{"namespace": "shop"},
+			want:      `{"data":{"down":[{"service_name":"web","status":"unhealthy"}]}}`,
+		},
+		{
+			name:    "query is not streamed",
+			query:   `{ hierarchy { name } }`,
+			wantErr: "only subscription operations can be streamed",
+		},
+		{
+			name:    "unknown subscription field",
+			query:   `subscription { pods { name } }`,
+			wantErr: `unknown subscription field "pods"`,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			subscription, err := graphql.Subscribe(graphql.Request{Query: tt.query, Variables: tt.variables})
+			if tt.wantErr != "" {
+				if err == nil || err.Error() != tt.wantErr {
+					t.Fatalf("TestSubscription() error = %v, want %v", err, tt.wantErr)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("TestSubscription() error = %v", err)
+			}
+			got, err := json.Marshal(subscription.Resolve(health))
+			if err != nil {
+				t.Fatalf("TestSubscription() marshal error = %v", err)
+			}
+			if string(got) != tt.want {
+				t.Errorf("TestSubscription() = %s, want %s", got, tt.want)
+			}
+		})
+	}
+}
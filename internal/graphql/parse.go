@@ -0,0 +1,357 @@
+package graphql
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+	"unicode"
+)
+
+const (
+	operationQuery        = "query"
+	operationSubscription = "subscription"
+)
+
+type operation struct {
+	kind       string
+	selections []selection
+}
+
+type selection struct {
+	alias string
+	name  string
+	args  map[string]string
+	// typeCondition is set on an inline fragment, which applies its selections only to nodes of that kind
+	typeCondition string
+	selections    []selection
+}
+
+func (s selection) responseKey() string {
+	if s.alias != "" {
+		return s.alias
+	}
+	return s.name
+}
+
+type tokenKind int
+
+const (
+	tokenName tokenKind = iota
+	tokenString
+	tokenNumber
+	tokenPunct
+	tokenEOF
+)
+
+type token struct {
+	kind  tokenKind
+	value string
+}
+
+func (t token) literal() bool {
+	return t.kind == tokenString || t.kind == tokenName || t.kind == tokenNumber
+}
+
+type parser struct {
+	tokens []token
+	pos    int
+	// declared holds the variables the operation declares and bound those given a value or a default
+	declared map[string]bool
+	bound    map[string]string
+}
+
+// parse reads a query or subscription operation, binding its variables from values, and returns it
+func parse(query string, values map[string]any) (operation, error) {
+	tokens, err := lex(query)
+	if err != nil {
+		return operation{}, err
+	}
+
+	p := &parser{tokens: tokens, declared: make(map[string]bool), bound: make(map[string]string)}
+	op := operation{kind: operationQuery}
+	if p.peek().kind == tokenName && p.peek().value == "mutation" {
+		return operation{}, fmt.Errorf("mutations are not supported")
+	}
+	if p.peek().kind == tokenName && (p.peek().value == operationQuery || p.peek().value == operationSubscription) {
+		op.kind = p.next().value
+		if p.peek().kind == tokenName {
+			p.next()
+		}
+		if p.peekPunct("(") {
+			if err := p.variableDefinitions(values); err != nil {
+				return operation{}, err
+			}
+		}
+	}
+
+	op.selections, err = p.selectionSet()
+	if err != nil {
+		return operation{}, err
+	}
+	if p.peek().kind != tokenEOF {
+		return operation{}, fmt.Errorf("unexpected %q after query", p.peek().value)
+	}
+	return op, nil
+}
+
+func (p *parser) peek() token {
+	return p.tokens[p.pos]
+}
+
+func (p *parser) peekPunct(punct string) bool {
+	return p.peek().kind == tokenPunct && p.peek().value == punct
+}
+
+func (p *parser) next() token {
+	t := p.tokens[p.pos]
+	if t.kind != tokenEOF {
+		p.pos++
+	}
+	return t
+}
+
+func (p *parser) expect(punct string) error {
+	t := p.next()
+	if t.kind != tokenPunct || t.value != punct {
+		return fmt.Errorf("expected %q, got %q", punct, t.value)
+	}
+	return nil
+}
+
+// variableDefinitions reads ($name: Type = default, ...) and binds each variable to the value given with
+// the request, falling back to its default. A variable whose type ends in ! must have one or the other.
+func (p *parser) variableDefinitions(values map[string]any) error {
+	p.next()
+	for !p.peekPunct(")") {
+		if err := p.expect("$"); err != nil {
+			return err
+		}
+		name := p.next()
+		if name.kind != tokenName {
+			return fmt.Errorf("expected variable name, got %q", name.value)
+		}
+		if err := p.expect(":"); err != nil {
+			return err
+		}
+		required, err := p.variableType()
+		if err != nil {
+			return err
+		}
+
+		p.declared[name.value] = true
+		if value := values[name.value]; value != nil {
+			literal, err := scalar(value)
+			if err != nil {
+				return fmt.Errorf("variable $%s %w", name.value, err)
+			}
+			p.bound[name.value] = literal
+		}
+		if p.peekPunct("=") {
+			p.next()
+			def := p.next()
+			if !def.literal() {
+				return fmt.Errorf("default of variable $%s must be a literal", name.value)
+			}
+			if _, ok := p.bound[name.value]; !ok {
+				p.bound[name.value] = def.value
+			}
+		}
+		if _, ok := p.bound[name.value]; required && !ok {
+			return fmt.Errorf("variable $%s is required", name.value)
+		}
+	}
+	p.next()
+	return nil
+}
+
+// variableType reads a type such as String, [String] or String! and reports whether it is non-null
+func (p *parser) variableType() (bool, error) {
+	t := p.next()
+	list := t.kind == tokenPunct && t.value == "["
+	if list {
+		if _, err := p.variableType(); err != nil {
+			return false, err
+		}
+		if err := p.expect("]"); err != nil {
+			return false, err
+		}
+	}
+	if !list && t.kind != tokenName {
+		return false, fmt.Errorf("expected variable type, got %q", t.value)
+	}
+	if !p.peekPunct("!") {
+		return false, nil
+	}
+	p.next()
+	return true, nil
+}
+
+func (p *parser) selectionSet() ([]selection, error) {
+	if err := p.expect("{"); err != nil {
+		return nil, err
+	}
+
+	var selections []selection
+	for !p.peekPunct("}") {
+		sel, err := p.selection()
+		if err != nil {
+			return nil, err
+		}
+		selections = append(selections, sel)
+	}
+	p.next()
+
+	if len(selections) == 0 {
+		return nil, fmt.Errorf("empty selection set")
+	}
+	return selections, nil
+}
+
+func (p *parser) selection() (selection, error) {
+	if !p.peekPunct("...") {
+		return p.field()
+	}
+	p.next()
+	if on := p.next(); on.kind != tokenName || on.value != "on" {
+		return selection{}, fmt.Errorf("named fragments are not supported, got ...%s", on.value)
+	}
+	typeName := p.next()
+	if typeName.kind != tokenName {
+		return selection{}, fmt.Errorf("expected type name after on, got %q", typeName.value)
+	}
+	selections, err := p.selectionSet()
+	if err != nil {
+		return selection{}, err
+	}
+	return selection{typeCondition: typeName.value, selections: selections}, nil
+}
+
+func (p *parser) field() (selection, error) {
+	t := p.next()
+	if t.kind != tokenName {
+		return selection{}, fmt.Errorf("expected field name, got %q", t.value)
+	}
+
+	sel := selection{name: t.value}
+	if p.peekPunct(":") {
+		p.next()
+		name := p.next()
+		if name.kind != tokenName {
+			return selection{}, fmt.Errorf("expected field name after alias %q", t.value)
+		}
+		sel.alias = t.value
+		sel.name = name.value
+	}
+
+	if p.peekPunct("(") {
+		args, err := p.arguments()
+		if err != nil {
+			return selection{}, err
+		}
+		sel.args = args
+	}
+
+	if p.peekPunct("{") {
+		selections, err := p.selectionSet()
+		if err != nil {
+			return selection{}, err
+		}
+		sel.selections = selections
+	}
+	return sel, nil
+}
+
+// arguments reads (name: value, ...). An argument given an optional variable that is unbound is left out,
+// as if it had not been given.
+func (p *parser) arguments() (map[string]string, error) {
+	p.next()
+	args := make(map[string]string)
+	for !p.peekPunct(")") {
+		name := p.next()
+		if name.kind != tokenName {
+			return nil, fmt.Errorf("expected argument name, got %q", name.value)
+		}
+		if err := p.expect(":"); err != nil {
+			return nil, err
+		}
+		value := p.next()
+		if value.kind == tokenPunct && value.value == "$" {
+			variable := p.next()
+			if !p.declared[variable.value] {
+				return nil, fmt.Errorf("variable $%s is not declared", variable.value)
+			}
+			if bound, ok := p.bound[variable.value]; ok {
+				args[name.value] = bound
+			}
+			continue
+		}
+		if !value.literal() {
+			return nil, fmt.Errorf("argument %q must be a literal or a variable", name.value)
+		}
+		args[name.value] = value.value
+	}
+	p.next()
+	return args, nil
+}
+
+// scalar renders a variable value from the request's JSON as an argument literal
+func scalar(value any) (string, error) {
+	switch v := value.(type) {
+	case string:
+		return v, nil
+	case float64:
+		return strconv.FormatFloat(v, 'f', -1, 64), nil
+	case bool:
+		return strconv.FormatBool(v), nil
+	}
+	return "", fmt.Errorf("must be a string, number or boolean")
+}
+
+// lex splits a query into tokens, skipping whitespace, commas and comments as insignificant
+func lex(query string) ([]token, error) {
+	var tokens []token
+	runes := []rune(query)
+	for i := 0; i < len(runes); {
+		r := runes[i]
+		switch {
+		case unicode.IsSpace(r) || r == ',':
+			i++
+		case r == '#':
+			for i < len(runes) && runes[i] != '\n' {
+				i++
+			}
+		case strings.HasPrefix(string(runes[i:min(i+3, len(runes))]), "..."):
+			tokens = append(tokens, token{kind: tokenPunct, value: "..."})
+			i += 3
+		case strings.ContainsRune("{}():$!=[]", r):
+			tokens = append(tokens, token{kind: tokenPunct, value: string(r)})
+			i++
+		case r == '"':
+			end := i + 1
+			for end < len(runes) && runes[end] != '"' {
+				end++
+			}
+			if end == len(runes) {
+				return nil, fmt.Errorf("unterminated string")
+			}
+			tokens = append(tokens, token{kind: tokenString, value: string(runes[i+1 : end])})
+			i = end + 1
+		case r == '_' || unicode.IsLetter(r):
+			start := i
+			for i < len(runes) && (runes[i] == '_' || unicode.IsLetter(runes[i]) || unicode.IsDigit(runes[i])) {
+				i++
+			}
+			tokens = append(tokens, token{kind: tokenName, value: string(runes[start:i])})
+		case r == '-' || unicode.IsDigit(r):
+			start := i
+			i++
+			for i < len(runes) && unicode.IsDigit(runes[i]) {
+				i++
+			}
+			tokens = append(tokens, token{kind: tokenNumber, value: string(runes[start:i])})
+		default:
+			return nil, fmt.Errorf("unexpected character %q", r)
+		}
+	}
+	return append(tokens, token{kind: tokenEOF}), nil
+}
@@ -0,0 +1,115 @@
+package hierarchy
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+
+	"sigs.k8s.io/gateway-api/apis/v1beta1"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// routeClaim is a hostname and match a route asks one Gateway to send to it. Gateway API breaks a tie
+// between identical claims by route age and name, so the losing route silently receives no traffic.
+type routeClaim struct {
+	gateway  string
+	hostname string
+	match    string
+}
+
+// routeClaimOwners indexes every claim made by a route attached to a Gateway
+func routeClaimOwners(routes []v1beta1.HTTPRoute) map[routeClaim][]hostnameOwner {
+	owners := make(map[routeClaim][]hostnameOwner)
+	for _, route := range routes {
+		if shouldIgnore(route.Annotations) {
+			continue
+		}
+		owner := hostnameOwner{kind: types.ResourceKindHTTPRoute, namespace: route.Namespace, name: route.Name}
+		for _, claim := range routeClaims(route) {
+			owners[claim] = append(owners[claim], owner)
+		}
+	}
+	return owners
+}
+
+// routeClaims returns a route's claims in spec order. A route without hostnames claims every
+// hostname, and a rule without matches claims every path.
+func routeClaims(route v1beta1.HTTPRoute) []routeClaim {
+	hostnames := []string{"*"}
+	if len(route.Spec.Hostnames) > 0 {
+		hostnames = hostnames[:0]
+		for _, hostname := range route.Spec.Hostnames {
+			hostnames = append(hostnames, strings.ToLower(string(hostname)))
+		}
+	}
+
+	var matches []string
+	for _, rule := range route.Spec.Rules {
+		if len(rule.Matches) == 0 {
+			matches = append(matches, claimMatch(types.RouteMatch{}))
+		}
+		for _, match := range rule.Matches {
+			matches = append(matches, claimMatch(routeMatch(match)))
+		}
+	}
+
+	seen := make(map[routeClaim]bool)
+	var claims []routeClaim
+	for _, gateway := range ParentGateways(route) {
+		for _, hostname := range hostnames {
+			for _, match := range matches {
+				claim := routeClaim{gateway: gateway, hostname: hostname, match: match}
+				if seen[claim] {
+					continue
+				}
+				seen[claim] = true
+				claims = append(claims, claim)
+			}
+		}
+	}
+	return claims
+}
+
+// claimMatch renders a match so that equivalent matches compare equal, defaulting an unset path to
+// the PathPrefix / that Gateway API applies
+func claimMatch(match types.RouteMatch) string {
+	if match.PathType == "" && match.Path == "" {
+		match.PathType = string(v1beta1.PathMatchPathPrefix)
+		match.Path = "/"
+	}
+
+	parts := []string{match.PathType, match.Path}
+	if match.Method != "" {
+		parts = append(parts, match.Method)
+	}
+	headers := append([]string(nil), match.Headers...)
+	sort.Strings(headers)
+	parts = append(parts, headers...)
+	params := append([]string(nil), match.QueryParams...)
+	sort.Strings(params)
+	for _, param := range params {
+		parts = append(parts, "?"+param)
+	}
+	return strings.Join(parts, " ")
+}
+
+// routeConflicts warns about every other route making one of this route's claims on the same Gateway,
+// naming the first claim they share
+func (b *builder) routeConflicts(route v1beta1.HTTPRoute) []types.Warning {
+	self := hostnameOwner{kind: types.ResourceKindHTTPRoute, namespace: route.Namespace, name: route.Name}
+	seen := make(map[hostnameOwner]bool)
+	var warnings []types.Warning
+	for _, claim := range routeClaims(route) {
+		for _, owner := range b.routeClaims[claim] {
+			if owner == self || seen[owner] {
+				continue
+			}
+			seen[owner] = true
+			message := fmt.Sprintf("%s also claims %s %s on gateway %s", owner, claim.hostname, claim.match, claim.gateway)
+			warnings = append(warnings, types.Warning{Type: types.WarningTypeRouteConflict, Message: message})
+		}
+	}
+	sort.Slice(warnings, func(i, j int) bool { return warnings[i].Message < warnings[j].Message })
+	return warnings
+}
@@ -0,0 +1,153 @@
+package hierarchy
+
+import (
+	"fmt"
+	"net"
+	"sort"
+	"strings"
+
+	corev1 "k8s.io/api/core/v1"
+	"sigs.k8s.io/gateway-api/apis/v1beta1"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const (
+	externalDNSHostnameAnnotation = "external-dns.alpha.kubernetes.io/hostname"
+	externalDNSTargetAnnotation   = "external-dns.alpha.kubernetes.io/target"
+
+	dnsRecordSourceSpec       = "spec"
+	dnsRecordSourceAnnotation = "annotation"
+)
+
+// hostnameOwner is a resource that implies a DNS record for a hostname
+type hostnameOwner struct {
+	kind      types.ResourceKind
+	namespace string
+	name      string
+}
+
+func (o hostnameOwner) String() string {
+	return fmt.Sprintf("%s/%s/%s", o.namespace, o.kind, o.name)
+}
+
+// hostnameOwners indexes every hostname claimed by a route spec or an external-dns annotation
+func hostnameOwners(objects types.ClusterObjects, services map[string]corev1.Service) map[string][]hostnameOwner {
+	owners := make(map[string][]hostnameOwner)
+	for _, route := range objects.HTTPRoutes {
+		if shouldIgnore(route.Annotations) {
+			continue
+		}
+		owner := hostnameOwner{kind: types.ResourceKindHTTPRoute, namespace: route.Namespace, name: route.Name}
+		for _, record := range routeDNSRecords(route) {
+			owners[record.Hostname] = append(owners[record.Hostname], owner)
+		}
+	}
+	for _, service := range services {
+		owner := hostnameOwner{kind: types.ResourceKindService, namespace: service.Namespace, name: service.Name}
+		for _, record := range serviceDNSRecords(service) {
+			owners[record.Hostname] = append(owners[record.Hostname], owner)
+		}
+	}
+	return owners
+}
+
+// routeDNSRecords returns the records implied by a route's hostnames and external-dns annotations.
+// Targets come from the target annotation since the route's Gateway address is not tracked.
+func routeDNSRecords(route v1beta1.HTTPRoute) []types.DNSRecord {
+	targets := splitAnnotation(route.Annotations[externalDNSTargetAnnotation])
+
+	var records []types.DNSRecord
+	for _, hostname := range route.Spec.Hostnames {
+		records = append(records, newDNSRecord(string(hostname), targets, dnsRecordSourceSpec))
+	}
+	for _, hostname := range splitAnnotation(route.Annotations[externalDNSHostnameAnnotation]) {
+		records = append(records, newDNSRecord(hostname, targets, dnsRecordSourceAnnotation))
+	}
+	return dedupeRecords(records)
+}
+
+// serviceDNSRecords returns the records external-dns creates for an annotated Service,
+// pointing at the target annotation or the load balancer ingress addresses
+func serviceDNSRecords(service corev1.Service) []types.DNSRecord {
+	hostnames := splitAnnotation(service.Annotations[externalDNSHostnameAnnotation])
+	if len(hostnames) == 0 {
+		return nil
+	}
+
+	targets := splitAnnotation(service.Annotations[externalDNSTargetAnnotation])
+	if len(targets) == 0 {
+		targets = externalIPs(service)
+	}
+
+	records := make([]types.DNSRecord, 0, len(hostnames))
+	for _, hostname := range hostnames {
+		records = append(records, newDNSRecord(hostname, targets, dnsRecordSourceAnnotation))
+	}
+	return dedupeRecords(records)
+}
+
+func newDNSRecord(hostname string, targets []string, source string) types.DNSRecord {
+	return types.DNSRecord{
+		Hostname: strings.TrimSuffix(strings.ToLower(hostname), "."),
+		Type:     recordType(targets),
+		Targets:  targets,
+		Source:   source,
+	}
+}
+
+// recordType follows external-dns: IP targets produce A or AAAA records, anything else a CNAME
+func recordType(targets []string) string {
+	if len(targets) == 0 {
+		return ""
+	}
+	ip := net.ParseIP(targets[0])
+	switch {
+	case ip == nil:
+		return "CNAME"
+	case ip.To4() == nil:
+		return "AAAA"
+	}
+	return "A"
+}
+
+func dedupeRecords(records []types.DNSRecord) []types.DNSRecord {
+	seen := make(map[string]bool)
+	var result []types.DNSRecord
+	for _, record := range records {
+		if seen[record.Hostname] {
+			continue
+		}
+		seen[record.Hostname] = true
+		result = append(result, record)
+	}
+	return result
+}
+
+// hostnameConflicts lists owners in other namespaces that claim any of the given records' hostnames
+func (b *builder) hostnameConflicts(namespace string, records []types.DNSRecord) []string {
+	seen := make(map[string]bool)
+	var conflicts []string
+	for _, record := range records {
+		for _, owner := range b.hostnames[record.Hostname] {
+			if owner.namespace == namespace || seen[owner.String()] {
+				continue
+			}
+			seen[owner.String()] = true
+			conflicts = append(conflicts, owner.String())
+		}
+	}
+	sort.Strings(conflicts)
+	return conflicts
+}
+
+func splitAnnotation(value string) []string {
+	var parts []string
+	for _, part := range strings.Split(value, ",") {
+		part = strings.TrimSpace(part)
+		if part != "" {
+			parts = append(parts, part)
+		}
+	}
+	return parts
+}
@@ -0,0 +1,46 @@
+package hierarchy
+
+import (
+	"net/netip"
+
+	corev1 "k8s.io/api/core/v1"
+)
+
+// ipFamily reports whether ip is an IPv4 or IPv6 address; IPv4-mapped IPv6 addresses count as IPv4
+func ipFamily(ip string) (corev1.IPFamily, bool) {
+	addr, err := netip.ParseAddr(ip)
+	if err != nil {
+		return "", false
+	}
+	if addr.Unmap().Is4() {
+		return corev1.IPv4Protocol, true
+	}
+	return corev1.IPv6Protocol, true
+}
+
+// addressesByFamily groups IPs by address family. Entries that are not IPs, such as the None
+// cluster IP of headless Services or load balancer hostnames, are left out.
+func addressesByFamily(groups ...[]string) map[string][]string {
+	result := make(map[string][]string)
+	for _, ips := range groups {
+		for _, ip := range ips {
+			family, ok := ipFamily(ip)
+			if !ok {
+				continue
+			}
+			result[string(family)] = append(result[string(family)], ip)
+		}
+	}
+	if len(result) == 0 {
+		return nil
+	}
+	return result
+}
+
+func serviceIPFamilies(service corev1.Service) []string {
+	var families []string
+	for _, family := range service.Spec.IPFamilies {
+		families = append(families, string(family))
+	}
+	return families
+}
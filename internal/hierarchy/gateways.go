@@ -0,0 +1,69 @@
+package hierarchy
+
+import (
+	"sort"
+
+	"sigs.k8s.io/gateway-api/apis/v1beta1"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// gatewayControllers maps each Gateway key to the controllerName of its GatewayClass
+func gatewayControllers(objects types.ClusterObjects) map[string]string {
+	classes := make(map[string]string, len(objects.GatewayClasses))
+	for _, class := range objects.GatewayClasses {
+		classes[class.Name] = string(class.Spec.ControllerName)
+	}
+
+	controllers := make(map[string]string, len(objects.Gateways))
+	for _, gateway := range objects.Gateways {
+		controller, exists := classes[string(gateway.Spec.GatewayClassName)]
+		if !exists {
+			continue
+		}
+		controllers[key(gateway.Namespace, gateway.Name)] = controller
+	}
+	return controllers
+}
+
+// routeControllers returns the controllers serving a route through its parent Gateways. A route
+// attached to Gateways of different classes is served by each of them.
+func (b *builder) routeControllers(route v1beta1.HTTPRoute) []string {
+	seen := make(map[string]bool)
+	var controllers []string
+	for _, gateway := range ParentGateways(route) {
+		controller, exists := b.controllers[gateway]
+		if !exists || seen[controller] {
+			continue
+		}
+		seen[controller] = true
+		controllers = append(controllers, controller)
+	}
+	sort.Strings(controllers)
+	return controllers
+}
+
+// ParentGateways returns the keys of the Gateways a route attaches to, in parentRef order
+func ParentGateways(route v1beta1.HTTPRoute) []string {
+	seen := make(map[string]bool)
+	var gateways []string
+	for _, parent := range route.Spec.ParentRefs {
+		if parent.Group != nil && *parent.Group != v1beta1.GroupName {
+			continue
+		}
+		if parent.Kind != nil && *parent.Kind != "Gateway" {
+			continue
+		}
+		namespace := route.Namespace
+		if parent.Namespace != nil {
+			namespace = string(*parent.Namespace)
+		}
+		gateway := key(namespace, string(parent.Name))
+		if seen[gateway] {
+			continue
+		}
+		seen[gateway] = true
+		gateways = append(gateways, gateway)
+	}
+	return gateways
+}
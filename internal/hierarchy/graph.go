@@ -0,0 +1,208 @@
+package hierarchy
+
+import (
+	"sort"
+	"strings"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// Graph is the hierarchy as dependencies between resources, each drawn once however many parents it has.
+// Edges point from a resource to what it depends on: hostname and Gateway to HTTPRoute, HTTPRoute to
+// Service, Service to Pod and Pod to the Node it runs on. Namespaces only contain resources, and traffic
+// splits and tracks only group them, so they are left out.
+type Graph struct {
+	refs       map[string]types.NodeRef
+	dependsOn  map[string][]string
+	dependents map[string][]string
+	// ids maps lower cased IDs to IDs, so lookups ignore case
+	ids map[string]string
+}
+
+// NewGraph builds the dependency graph of nodes
+func NewGraph(nodes []types.HierarchyNode) *Graph {
+	g := &Graph{
+		refs:       make(map[string]types.NodeRef),
+		dependsOn:  make(map[string][]string),
+		dependents: make(map[string][]string),
+		ids:        make(map[string]string),
+	}
+	g.add(nodes, "")
+	return g
+}
+
+func (g *Graph) add(nodes []types.HierarchyNode, parent string) {
+	for _, node := range nodes {
+		switch node.Kind {
+		case types.ResourceKindNamespace, types.ResourceKindTruncated:
+			g.add(node.Relatives, "")
+			continue
+		case types.ResourceKindTrafficSplit, types.ResourceKindTrack:
+			g.add(node.Relatives, parent)
+			continue
+		}
+
+		id := g.vertex(node.Kind, node.Namespace, node.Name)
+		if parent != "" {
+			g.edge(parent, id)
+		}
+		if node.Kind == types.ResourceKindHTTPRoute {
+			for _, gateway := range node.Gateways {
+				namespace, name, _ := strings.Cut(gateway, "/")
+				g.edge(g.vertex(types.ResourceKindGateway, &namespace, name), id)
+			}
+			for _, hostname := range node.Hostnames {
+				g.edge(g.vertex(types.ResourceKindHostname, nil, hostname), id)
+			}
+		}
+		if node.Kind == types.ResourceKindPod && node.NodeName != "" {
+			g.edge(id, g.vertex(types.ResourceKindNode, nil, node.NodeName))
+		}
+		g.add(node.Relatives, id)
+	}
+}
+
+func (g *Graph) vertex(kind types.ResourceKind, namespace *string, name string) string {
+	id := NodeID(kind, namespace, name)
+	if _, exists := g.refs[id]; exists {
+		return id
+	}
+	ref := types.NodeRef{ID: id, Kind: kind, Name: name}
+	if namespace != nil {
+		ref.Namespace = *namespace
+	}
+	g.refs[id] = ref
+	g.ids[strings.ToLower(id)] = id
+	return id
+}
+
+func (g *Graph) edge(from, to string) {
+	for _, existing := range g.dependsOn[from] {
+		if existing == to {
+			return
+		}
+	}
+	g.dependsOn[from] = append(g.dependsOn[from], to)
+	g.dependents[to] = append(g.dependents[to], from)
+}
+
+// Lookup returns the resource with id, matched case-insensitively, and false when the graph has none
+func (g *Graph) Lookup(id string) (types.NodeRef, bool) {
+	ref, exists := g.refs[g.ids[strings.ToLower(id)]]
+	return ref, exists
+}
+
+// Impact returns everything that depends on the resource with id, directly or through others, and
+// everything it depends on, each sorted by ID. It returns false when the graph has no such resource.
+func (g *Graph) Impact(id string) (types.Impact, bool) {
+	target, exists := g.Lookup(id)
+	if !exists {
+		return types.Impact{}, false
+	}
+	return types.Impact{
+		Target:     target,
+		Upstream:   g.reachable(target.ID, g.dependents),
+		Downstream: g.reachable(target.ID, g.dependsOn),
+	}, true
+}
+
+// maxPaths caps how many equally short paths Paths returns
+const maxPaths = 10
+
+// Paths returns the shortest chains of dependencies from the resource with from to the one with to, or
+// from to to from when to depends on from instead, up to 10 of them ordered by the IDs along them. It
+// returns false when the graph has no resource with either ID.
+func (g *Graph) Paths(from, to string) (types.Paths, bool) {
+	source, sourceExists := g.Lookup(from)
+	target, targetExists := g.Lookup(to)
+	if !sourceExists || !targetExists {
+		return types.Paths{}, false
+	}
+
+	result := types.Paths{From: source, To: target, Paths: make([][]types.NodeRef, 0)}
+	paths := g.shortestPaths(source.ID, target.ID)
+	if len(paths) == 0 {
+		paths = g.shortestPaths(target.ID, source.ID)
+	}
+	for _, path := range paths {
+		refs := make([]types.NodeRef, 0, len(path))
+		for _, id := range path {
+			refs = append(refs, g.refs[id])
+		}
+		result.Paths = append(result.Paths, refs)
+	}
+	result.Related = len(result.Paths) > 0
+	return result, true
+}
+
+// shortestPaths returns every shortest path of IDs from source to target along dependsOn edges, capped at
+// maxPaths
+func (g *Graph) shortestPaths(source, target string) [][]string {
+	if source == target {
+		return [][]string{{source}}
+	}
+
+	depth := map[string]int{source: 0}
+	previous := make(map[string][]string)
+	frontier := []string{source}
+	for len(frontier) > 0 && depth[target] == 0 {
+		var next []string
+		for _, current := range frontier {
+			for _, neighbour := range g.dependsOn[current] {
+				seenAt, seen := depth[neighbour]
+				if !seen {
+					depth[neighbour] = depth[current] + 1
+					next = append(next, neighbour)
+				}
+				if !seen || seenAt == depth[current]+1 {
+					previous[neighbour] = append(previous[neighbour], current)
+				}
+			}
+		}
+		frontier = next
+	}
+	if _, reached := depth[target]; !reached {
+		return nil
+	}
+
+	var paths [][]string
+	var walk func(id string, suffix []string)
+	walk = func(id string, suffix []string) {
+		path := append([]string{id}, suffix...)
+		if id == source {
+			paths = append(paths, path)
+			return
+		}
+		for _, before := range previous[id] {
+			walk(before, path)
+		}
+	}
+	walk(target, nil)
+
+	sort.Slice(paths, func(i, j int) bool { return strings.Join(paths[i], " ") < strings.Join(paths[j], " ") })
+	if len(paths) > maxPaths {
+		paths = paths[:maxPaths]
+	}
+	return paths
+}
+
+// reachable returns the resources reached from id through edges, excluding id itself
+func (g *Graph) reachable(id string, edges map[string][]string) []types.NodeRef {
+	seen := map[string]bool{id: true}
+	queue := []string{id}
+	result := make([]types.NodeRef, 0)
+	for len(queue) > 0 {
+		current := queue[0]
+		queue = queue[1:]
+		for _, next := range edges[current] {
+			if seen[next] {
+				continue
+			}
+			seen[next] = true
+			queue = append(queue, next)
+			result = append(result, g.refs[next])
+		}
+	}
+	sort.Slice(result, func(i, j int) bool { return result[i].ID < result[j].ID })
+	return result
+}
@@ -0,0 +1,497 @@
+package hierarchy
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	"k8s.io/apimachinery/pkg/labels"
+	"k8s.io/utils/ptr"
+	"sigs.k8s.io/gateway-api/apis/v1beta1"
+
+	"github.com/kdwils/constellation/internal/endpoints"
+	"github.com/kdwils/constellation/internal/images"
+	"github.com/kdwils/constellation/internal/scrub"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const ignoreAnnotation = "constellation.kyledev.co/ignore"
+
+const defaultClusterDomain = "cluster.local"
+
+type buildConfig struct {
+	clusterDomain string
+	ownerKeys     []string
+	scrubber      *scrub.Scrubber
+	now           time.Time
+}
+
+type BuildOpt func(*buildConfig)
+
+// WithClusterDomain sets the domain used for in-cluster DNS names, defaulting to cluster.local
+func WithClusterDomain(domain string) BuildOpt {
+	return func(c *buildConfig) {
+		c.clusterDomain = domain
+	}
+}
+
+// WithNow sets the time terminating pods count their remaining grace period from, defaulting to the
+// time Build is called
+func WithNow(now time.Time) BuildOpt {
+	return func(c *buildConfig) {
+		c.now = now
+	}
+}
+
+// WithOwnerKeys sets the label and annotation keys an owner is read from, in order of preference,
+// defaulting to owner and team
+func WithOwnerKeys(keys ...string) BuildOpt {
+	return func(c *buildConfig) {
+		c.ownerKeys = keys
+	}
+}
+
+// WithScrubbedAnnotations sets the annotation keys left out of the hierarchy in addition to kubectl's last
+// applied configuration. A key ending in * matches every annotation with that prefix.
+func WithScrubbedAnnotations(keys ...string) BuildOpt {
+	return func(c *buildConfig) {
+		c.scrubber = scrub.New(keys...)
+	}
+}
+
+type builder struct {
+	objects     types.ClusterObjects
+	services    map[string]corev1.Service
+	health      map[string]*types.ServiceHealthInfo
+	selected    map[string]bool
+	hostnames   map[string][]hostnameOwner
+	routeClaims map[routeClaim][]hostnameOwner
+	roles       map[string][]roleService
+	priorities  priorities
+	controllers map[string]string
+	endpoints   map[string]types.EndpointCounts
+	nodeLabels  map[string]map[string]string
+	namespaces  map[string]corev1.Namespace
+	config      buildConfig
+}
+
+// Build assembles the namespace → httproute → service → pod tree from raw cluster objects.
+// Services not referenced by a route and pods not selected by a service hang directly off their namespace.
+func Build(objects types.ClusterObjects, health []*types.ServiceHealthInfo, opts ...BuildOpt) []types.HierarchyNode {
+	b := &builder{
+		objects:     objects,
+		services:    make(map[string]corev1.Service),
+		health:      make(map[string]*types.ServiceHealthInfo, len(health)),
+		selected:    make(map[string]bool),
+		priorities:  newPriorities(objects.PriorityClasses),
+		controllers: gatewayControllers(objects),
+		endpoints:   endpoints.Count(objects.EndpointSlices),
+		nodeLabels:  make(map[string]map[string]string, len(objects.Nodes)),
+		namespaces:  make(map[string]corev1.Namespace, len(objects.Namespaces)),
+		config: buildConfig{
+			clusterDomain: defaultClusterDomain,
+			ownerKeys:     defaultOwnerKeys,
+			scrubber:      scrub.New(),
+			now:           time.Now(),
+		},
+	}
+	for _, opt := range opts {
+		opt(&b.config)
+	}
+
+	for _, info := range health {
+		b.health[info.Namespace+"/"+info.ServiceName] = info
+	}
+
+	for _, node := range objects.Nodes {
+		b.nodeLabels[node.Name] = node.Labels
+	}
+
+	for _, namespace := range objects.Namespaces {
+		b.namespaces[namespace.Name] = namespace
+	}
+
+	for _, service := range objects.Services {
+		if shouldIgnore(service.Annotations) {
+			continue
+		}
+		b.services[key(service.Namespace, service.Name)] = service
+	}
+	b.hostnames = hostnameOwners(objects, b.services)
+	b.routeClaims = routeClaimOwners(objects.HTTPRoutes)
+	b.roles = roleServices(b.services)
+
+	namespaces := namespaceNames(objects)
+	nodes := make([]types.HierarchyNode, 0, len(namespaces))
+	for _, namespace := range namespaces {
+		nodes = append(nodes, b.namespace(namespace))
+	}
+	assignIDs(nodes, "")
+	return nodes
+}
+
+// Walk calls fn for every node in the tree, children before their parent, so fn may aggregate
+// values its relatives already received
+func Walk(nodes []types.HierarchyNode, fn func(node *types.HierarchyNode)) {
+	for i := range nodes {
+		Walk(nodes[i].Relatives, fn)
+		fn(&nodes[i])
+	}
+}
+
+func (b *builder) namespace(namespace string) types.HierarchyNode {
+	node := newNamespaceNode(namespace, b.namespaces[namespace], b.config.scrubber)
+	b.terminating(&node, b.namespaces[namespace].ObjectMeta)
+
+	pods := podsInNamespace(namespace, b.objects.Pods)
+	services := sortedServices(namespace, b.services)
+	node.Counts = &types.ResourceCounts{Pods: len(pods), Services: len(services)}
+	routed := make(map[string]bool)
+
+	for _, route := range sortedRoutes(namespace, b.objects.HTTPRoutes) {
+		routeNode := b.route(route)
+		routeNode.Relatives = b.routeBackends(route, routed)
+		node.Relatives = append(node.Relatives, routeNode)
+	}
+
+	for _, service := range services {
+		if routed[key(service.Namespace, service.Name)] {
+			continue
+		}
+		node.Relatives = append(node.Relatives, b.service(service, pods))
+	}
+
+	for _, pod := range pods {
+		if b.selected[key(pod.Namespace, pod.Name)] {
+			continue
+		}
+		node.Relatives = append(node.Relatives, b.pod(pod))
+	}
+
+	node.Owner = b.owner(node.Labels, b.namespaces[namespace].Annotations)
+	inheritOwner(node.Relatives, node.Owner)
+	node.PrioritySummary = prioritySummary(node.Relatives)
+	node.SecuritySummary = securitySummary(node.Relatives)
+	return node
+}
+
+func (b *builder) route(route v1beta1.HTTPRoute) types.HierarchyNode {
+	node := newRouteNode(route)
+	node.Owner = b.owner(route.Labels, route.Annotations)
+	node.DNSRecords = routeDNSRecords(route)
+	node.HostnameConflicts = b.hostnameConflicts(route.Namespace, node.DNSRecords)
+	node.Controllers = b.routeControllers(route)
+	node.Gateways = ParentGateways(route)
+	node.Warnings = b.routeConflicts(route)
+	b.terminating(&node, route.ObjectMeta)
+	return node
+}
+
+func (b *builder) service(service corev1.Service, pods []corev1.Pod) types.HierarchyNode {
+	node := newServiceNode(service)
+	node.Owner = b.owner(service.Labels, service.Annotations)
+	node.HealthInfo = b.health[key(service.Namespace, service.Name)]
+	b.terminating(&node, service.ObjectMeta)
+	node.DNSNames = []string{fmt.Sprintf("%s.%s.svc.%s", service.Name, service.Namespace, b.config.clusterDomain)}
+	node.DNSRecords = serviceDNSRecords(service)
+	node.HostnameConflicts = b.hostnameConflicts(service.Namespace, node.DNSRecords)
+	if counts, exists := b.endpoints[key(service.Namespace, service.Name)]; exists {
+		node.Endpoints = &counts
+	}
+
+	var backends []corev1.Pod
+	for _, pod := range pods {
+		if !selectorMatches(service.Spec.Selector, pod.Labels) {
+			continue
+		}
+		b.selected[key(pod.Namespace, pod.Name)] = true
+		backends = append(backends, pod)
+		node.Relatives = append(node.Relatives, b.pod(pod))
+	}
+	node.Meshed = meshed(node.Relatives)
+	node.Warnings = portMismatches(service, backends)
+	node.Relatives = groupTracks(service, node.Relatives)
+	return node
+}
+
+func (b *builder) pod(pod corev1.Pod) types.HierarchyNode {
+	node := newPodNode(pod)
+	node.Owner = b.owner(pod.Labels, pod.Annotations)
+	node.DNSNames = b.podDNSNames(pod)
+	node.UnschedulableReason = unschedulableReason(pod, b.objects.Nodes)
+	node.OS, node.Arch = b.podPlatform(pod)
+	node.Track = b.podTrack(pod)
+	b.terminating(&node, pod.ObjectMeta)
+
+	// Priorities are only meaningful once PriorityClasses are known; every real cluster has the system ones
+	if len(b.objects.PriorityClasses) == 0 {
+		return node
+	}
+	className, priority := b.priorities.resolve(pod)
+	node.PriorityClassName = className
+	node.Priority = ptr.To(priority)
+	node.Preemptible = b.priorities.preemptible(priority)
+	return node
+}
+
+// podDNSNames returns the IP-based pod records, plus the hostname record a headless Service
+// creates for pods that set hostname and subdomain, as StatefulSet pods do
+func (b *builder) podDNSNames(pod corev1.Pod) []string {
+	var names []string
+	for _, ip := range pod.Status.PodIPs {
+		dashed := strings.NewReplacer(".", "-", ":", "-").Replace(ip.IP)
+		names = append(names, fmt.Sprintf("%s.%s.pod.%s", dashed, pod.Namespace, b.config.clusterDomain))
+	}
+
+	if pod.Spec.Hostname == "" || pod.Spec.Subdomain == "" {
+		return names
+	}
+	service, exists := b.services[key(pod.Namespace, pod.Spec.Subdomain)]
+	if !exists || service.Spec.ClusterIP != corev1.ClusterIPNone {
+		return names
+	}
+	return append(names, fmt.Sprintf("%s.%s.%s.svc.%s", pod.Spec.Hostname, pod.Spec.Subdomain, pod.Namespace, b.config.clusterDomain))
+}
+
+// newNamespaceNode describes a namespace from its object, which is the zero value when the
+// namespace is only known from the objects in it
+func newNamespaceNode(name string, namespace corev1.Namespace, scrubber *scrub.Scrubber) types.HierarchyNode {
+	node := types.HierarchyNode{
+		Kind:        types.ResourceKindNamespace,
+		Name:        name,
+		Labels:      namespace.Labels,
+		Annotations: scrubber.Annotations(namespace.Annotations),
+	}
+	if namespace.Status.Phase != "" {
+		node.Phase = ptr.To(string(namespace.Status.Phase))
+	}
+	return node
+}
+
+func newRouteNode(route v1beta1.HTTPRoute) types.HierarchyNode {
+	hostnames := make([]string, 0, len(route.Spec.Hostnames))
+	for _, hostname := range route.Spec.Hostnames {
+		hostnames = append(hostnames, string(hostname))
+	}
+
+	var backendRefs []string
+	for _, rule := range route.Spec.Rules {
+		for _, ref := range rule.BackendRefs {
+			backendRefs = append(backendRefs, backendRefName(route, ref))
+		}
+	}
+
+	return types.HierarchyNode{
+		Kind:        types.ResourceKindHTTPRoute,
+		Name:        route.Name,
+		Namespace:   ptr.To(route.Namespace),
+		Hostnames:   hostnames,
+		BackendRefs: backendRefs,
+		Labels:      route.Labels,
+		Rules:       routeRules(route),
+	}
+}
+
+// backendRefName is the name of a backendRef, qualified by its namespace when it leaves the route's
+// namespace so that same-named Services in different namespaces stay distinct
+func backendRefName(route v1beta1.HTTPRoute, ref v1beta1.HTTPBackendRef) string {
+	if ref.Namespace == nil || string(*ref.Namespace) == route.Namespace {
+		return string(ref.Name)
+	}
+	return key(string(*ref.Namespace), string(ref.Name))
+}
+
+func newServiceNode(service corev1.Service) types.HierarchyNode {
+	node := types.HierarchyNode{
+		Kind:          types.ResourceKindService,
+		Name:          service.Name,
+		Namespace:     ptr.To(service.Namespace),
+		Selectors:     service.Spec.Selector,
+		Labels:        service.Labels,
+		ServiceType:   ptr.To(string(service.Spec.Type)),
+		ClusterIPs:    service.Spec.ClusterIPs,
+		ExternalIPs:   externalIPs(service),
+		TrafficPolicy: trafficPolicy(service),
+		IPFamilies:    serviceIPFamilies(service),
+	}
+	node.Addresses = addressesByFamily(node.ClusterIPs, node.ExternalIPs)
+	if service.Spec.IPFamilyPolicy != nil {
+		node.IPFamilyPolicy = ptr.To(string(*service.Spec.IPFamilyPolicy))
+	}
+
+	for _, port := range service.Spec.Ports {
+		node.Ports = append(node.Ports, port.Port)
+		node.PortMappings = append(node.PortMappings, fmt.Sprintf("%d:%s/%s", port.Port, port.TargetPort.String(), port.Protocol))
+		if port.TargetPort.StrVal != "" {
+			node.TargetPortNames = append(node.TargetPortNames, port.TargetPort.StrVal)
+			continue
+		}
+		node.TargetPorts = append(node.TargetPorts, port.TargetPort.IntVal)
+	}
+	return node
+}
+
+func newPodNode(pod corev1.Pod) types.HierarchyNode {
+	node := types.HierarchyNode{
+		Kind:      types.ResourceKindPod,
+		Name:      pod.Name,
+		Namespace: ptr.To(pod.Namespace),
+		Labels:    pod.Labels,
+		Phase:     ptr.To(string(pod.Status.Phase)),
+		NodeName:  pod.Spec.NodeName,
+	}
+
+	for _, ip := range pod.Status.PodIPs {
+		node.PodIPs = append(node.PodIPs, ip.IP)
+	}
+	node.Addresses = addressesByFamily(node.PodIPs)
+
+	for _, container := range pod.Spec.Containers {
+		for _, port := range container.Ports {
+			info := types.ContainerPortInfo{
+				Port:     port.ContainerPort,
+				Protocol: ptr.To(string(port.Protocol)),
+			}
+			if port.Name != "" {
+				info.Name = ptr.To(port.Name)
+			}
+			node.ContainerPorts = append(node.ContainerPorts, info)
+		}
+	}
+
+	node.ImagePulls = images.ContainerPulls(pod)
+	node.ImagePullSecrets = len(pod.Spec.ImagePullSecrets) > 0
+	node.Sidecars = podSidecars(pod)
+	node.Probes = containerProbes(pod)
+	node.SecurityFlags = securityFlags(pod)
+	return node
+}
+
+// externalIPs merges spec external IPs with load balancer ingress addresses
+func externalIPs(service corev1.Service) []string {
+	ips := append([]string{}, service.Spec.ExternalIPs...)
+	for _, ingress := range service.Status.LoadBalancer.Ingress {
+		if ingress.IP != "" {
+			ips = append(ips, ingress.IP)
+			continue
+		}
+		if ingress.Hostname != "" {
+			ips = append(ips, ingress.Hostname)
+		}
+	}
+	if len(ips) == 0 {
+		return nil
+	}
+	return ips
+}
+
+// serviceBackendRefs returns the keys and weights of a rule's Service backendRefs
+func serviceBackendRefs(route v1beta1.HTTPRoute, rule v1beta1.HTTPRouteRule) []weightedRef {
+	var refs []weightedRef
+	for _, ref := range rule.BackendRefs {
+		if ref.Group != nil && *ref.Group != "" {
+			continue
+		}
+		if ref.Kind != nil && *ref.Kind != "Service" {
+			continue
+		}
+		namespace := route.Namespace
+		if ref.Namespace != nil {
+			namespace = string(*ref.Namespace)
+		}
+		weight := int32(defaultBackendWeight)
+		if ref.Weight != nil {
+			weight = *ref.Weight
+		}
+		refs = append(refs, weightedRef{key: key(namespace, string(ref.Name)), weight: weight})
+	}
+	return refs
+}
+
+func namespaceNames(objects types.ClusterObjects) []string {
+	seen := make(map[string]bool)
+	for _, namespace := range objects.Namespaces {
+		seen[namespace.Name] = true
+	}
+	for _, service := range objects.Services {
+		seen[service.Namespace] = true
+	}
+	for _, pod := range objects.Pods {
+		seen[pod.Namespace] = true
+	}
+	for _, route := range objects.HTTPRoutes {
+		seen[route.Namespace] = true
+	}
+
+	for _, namespace := range objects.Namespaces {
+		if shouldIgnore(namespace.Annotations) {
+			delete(seen, namespace.Name)
+		}
+	}
+
+	names := make([]string, 0, len(seen))
+	for name := range seen {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	return names
+}
+
+func podsInNamespace(namespace string, pods []corev1.Pod) []corev1.Pod {
+	var result []corev1.Pod
+	for _, pod := range pods {
+		if pod.Namespace != namespace {
+			continue
+		}
+		if shouldIgnore(pod.Annotations) {
+			continue
+		}
+		result = append(result, pod)
+	}
+	sort.Slice(result, func(i, j int) bool { return result[i].Name < result[j].Name })
+	return result
+}
+
+func sortedServices(namespace string, services map[string]corev1.Service) []corev1.Service {
+	var result []corev1.Service
+	for _, service := range services {
+		if service.Namespace == namespace {
+			result = append(result, service)
+		}
+	}
+	sort.Slice(result, func(i, j int) bool { return result[i].Name < result[j].Name })
+	return result
+}
+
+func sortedRoutes(namespace string, routes []v1beta1.HTTPRoute) []v1beta1.HTTPRoute {
+	var result []v1beta1.HTTPRoute
+	for _, route := range routes {
+		if route.Namespace != namespace {
+			continue
+		}
+		if shouldIgnore(route.Annotations) {
+			continue
+		}
+		result = append(result, route)
+	}
+	sort.Slice(result, func(i, j int) bool { return result[i].Name < result[j].Name })
+	return result
+}
+
+// selectorMatches reports whether a non-empty service selector matches the given labels
+func selectorMatches(selector, podLabels map[string]string) bool {
+	if len(selector) == 0 {
+		return false
+	}
+	return labels.SelectorFromSet(selector).Matches(labels.Set(podLabels))
+}
+
+func shouldIgnore(annotations map[string]string) bool {
+	return annotations[ignoreAnnotation] == "true"
+}
+
+func key(namespace, name string) string {
+	return namespace + "/" + name
+}
@@ -0,0 +1,22 @@
+package hierarchy_test
+
+import (
+	"fmt"
+	"testing"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/synthetic"
+)
+
+func BenchmarkBuild(b *testing.B) {
+	for _, pods := range synthetic.Sizes {
+		objects := synthetic.Objects(pods)
+		health := synthetic.Health(pods)
+		b.Run(fmt.Sprintf("pods=%d", pods), func(b *testing.B) {
+			b.ReportAllocs()
+			for b.Loop() {
+				hierarchy.Build(objects, health)
+			}
+		})
+	}
+}
@@ -0,0 +1,947 @@
+package hierarchy_test
+
+import (
+	"bytes"
+	"encoding/json"
+	"flag"
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"k8s.io/apimachinery/pkg/labels"
+	"k8s.io/utils/ptr"
+	"sigs.k8s.io/yaml"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+var update = flag.Bool("update", false, "regenerate golden files in testdata")
+
+// TestBuild_Golden builds the hierarchy for every testdata/*.yaml fixture and compares it
+// against testdata/<name>.golden.json. Run `go test ./internal/hierarchy -update` to regenerate.
+func TestBuild_Golden(t *testing.T) {
+	fixtures, err := filepath.Glob(filepath.Join("testdata", "*.yaml"))
+	if err != nil {
+		t.Fatalf("TestBuild_Golden() glob error = %v", err)
+	}
+	if len(fixtures) == 0 {
+		t.Fatal("TestBuild_Golden() no fixtures found in testdata")
+	}
+
+	for _, fixture := range fixtures {
+		name := strings.TrimSuffix(filepath.Base(fixture), ".yaml")
+		t.Run(name, func(t *testing.T) {
+			objects := loadFixture(t, fixture)
+
+			got, err := json.MarshalIndent(hierarchy.Build(objects, nil), "", "  ")
+			if err != nil {
+				t.Fatalf("TestBuild_Golden() marshal error = %v", err)
+			}
+			got = append(got, '\n')
+
+			golden := filepath.Join("testdata", name+".golden.json")
+			if *update {
+				if err := os.WriteFile(golden, got, 0o644); err != nil {
+					t.Fatalf("TestBuild_Golden() write golden error = %v", err)
+				}
+				return
+			}
+
+			want, err := os.ReadFile(golden)
+			if err != nil {
+				t.Fatalf("TestBuild_Golden() read golden error = %v", err)
+			}
+			if !bytes.Equal(got, want) {
+				t.Errorf("TestBuild_Golden() %s mismatch\ngot:\n%s\nwant:\n%s", name, got, want)
+			}
+		})
+	}
+}
+
+func loadFixture(t *testing.T, path string) types.ClusterObjects {
+	t.Helper()
+
+	data, err := os.ReadFile(path)
+	if err != nil {
+		t.Fatalf("loadFixture() read error = %v", err)
+	}
+
+	var objects types.ClusterObjects
+	if err := yaml.Unmarshal(data, &objects); err != nil {
+		t.Fatalf("loadFixture() decode error = %v", err)
+	}
+	return objects
+}
+
+func TestFilterByOS(t *testing.T) {
+	tests := []struct {
+		name   string
+		osName string
+		want   []string
+	}{
+		{
+			name:   "windows keeps the service leading to windows pods",
+			osName: "windows",
+			want:   []string{"Pod/iis-0", "Pod/iis-1", "Service/iis", "Namespace/mixed"},
+		},
+		{
+			name:   "matching is case insensitive",
+			osName: "Linux",
+			want:   []string{"Pod/web-0", "Namespace/mixed"},
+		},
+		{
+			name:   "no matching pods",
+			osName: "plan9",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			nodes := hierarchy.Build(loadFixture(t, filepath.Join("testdata", "platform.yaml")), nil)
+
+			var got []string
+			hierarchy.Walk(hierarchy.FilterByOS(nodes, tt.osName), func(node *types.HierarchyNode) {
+				got = append(got, string(node.Kind)+"/"+node.Name)
+			})
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestFilterByOS() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestGroupByOwner(t *testing.T) {
+	tests := []struct {
+		name string
+		opts []hierarchy.BuildOpt
+		want []types.OwnerGroup
+	}{
+		{
+			name: "service label overrides the namespace owner",
+			want: []types.OwnerGroup{
+				{Owner: "payments", Resources: []types.ResourceRef{
+					{Kind: types.ResourceKindPod, Namespace: "shop", Name: "payments-a"},
+					{Kind: types.ResourceKindService, Namespace: "shop", Name: "payments"},
+				}},
+				{Owner: "storefront@example.com", Resources: []types.ResourceRef{
+					{Kind: types.ResourceKindPod, Namespace: "shop", Name: "frontend-a"},
+					{Kind: types.ResourceKindService, Namespace: "shop", Name: "frontend"},
+					{Kind: types.ResourceKindHTTPRoute, Namespace: "shop", Name: "storefront"},
+					{Kind: types.ResourceKindPod, Namespace: "shop", Name: "debug"},
+					{Kind: types.ResourceKindNamespace, Name: "shop"},
+				}},
+			},
+		},
+		{
+			name: "configured keys take precedence in order",
+			opts: []hierarchy.BuildOpt{hierarchy.WithOwnerKeys("team")},
+			want: []types.OwnerGroup{
+				{Owner: "payments", Resources: []types.ResourceRef{
+					{Kind: types.ResourceKindPod, Namespace: "shop", Name: "payments-a"},
+					{Kind: types.ResourceKindService, Namespace: "shop", Name: "payments"},
+				}},
+				{Owner: "storefront", Resources: []types.ResourceRef{
+					{Kind: types.ResourceKindPod, Namespace: "shop", Name: "frontend-a"},
+					{Kind: types.ResourceKindService, Namespace: "shop", Name: "frontend"},
+					{Kind: types.ResourceKindHTTPRoute, Namespace: "shop", Name: "storefront"},
+					{Kind: types.ResourceKindPod, Namespace: "shop", Name: "debug"},
+					{Kind: types.ResourceKindNamespace, Name: "shop"},
+				}},
+			},
+		},
+		{
+			name: "no owner keys",
+			opts: []hierarchy.BuildOpt{hierarchy.WithOwnerKeys()},
+			want: []types.OwnerGroup{},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			nodes := hierarchy.Build(loadFixture(t, filepath.Join("testdata", "routes.yaml")), nil, tt.opts...)
+
+			got := hierarchy.GroupByOwner(nodes)
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestGroupByOwner() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestReroot(t *testing.T) {
+	tests := []struct {
+		name    string
+		fixture string
+		root    types.HierarchyRoot
+		want    []string
+		wantErr bool
+	}{
+		{
+			name:    "gateway root leads through routes to pods",
+			fixture: "routes.yaml",
+			root:    types.HierarchyRootGateway,
+			want:    []string{"Pod/frontend-a", "Service/frontend", "HTTPRoute/storefront", "Gateway/public"},
+		},
+		{
+			name:    "app root groups by label and routes by their backend",
+			fixture: "routes.yaml",
+			root:    types.HierarchyRootApp,
+			want: []string{
+				"Pod/frontend-a", "Service/frontend", "HTTPRoute/storefront", "Application/frontend",
+				"Pod/payments-a", "Service/payments", "Application/payments",
+			},
+		},
+		{
+			name:    "node root skips unscheduled pods",
+			fixture: "platform.yaml",
+			root:    types.HierarchyRootNode,
+			want:    []string{"Pod/web-0", "Node/linux-a", "Pod/iis-0", "Node/windows-a"},
+		},
+		{
+			name:    "unknown root",
+			fixture: "platform.yaml",
+			root:    "cluster",
+			wantErr: true,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			nodes := hierarchy.Build(loadFixture(t, filepath.Join("testdata", tt.fixture)), nil)
+
+			rerooted, err := hierarchy.Reroot(nodes, tt.root)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("TestReroot() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			var got []string
+			hierarchy.Walk(rerooted, func(node *types.HierarchyNode) {
+				got = append(got, string(node.Kind)+"/"+node.Name)
+			})
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestReroot() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestFindNode(t *testing.T) {
+	tests := []struct {
+		name       string
+		fixture    string
+		id         string
+		wantParent string
+		wantFound  bool
+	}{
+		{
+			name:       "nested service",
+			fixture:    "routes.yaml",
+			id:         "Service/shop/frontend",
+			wantParent: "Namespace/shop|HTTPRoute/shop/storefront",
+			wantFound:  true,
+		},
+		{
+			name:      "namespace",
+			fixture:   "routes.yaml",
+			id:        "Namespace/shop",
+			wantFound: true,
+		},
+		{
+			name:    "missing node",
+			fixture: "routes.yaml",
+			id:      "Service/shop/missing",
+		},
+		{
+			name:       "resource id matches the first copy",
+			fixture:    "collisions.yaml",
+			id:         "Pod/team-a/api-0",
+			wantParent: "Namespace/team-a|HTTPRoute/team-a/web|Service/team-a/api",
+			wantFound:  true,
+		},
+		{
+			name:       "path id picks one copy",
+			fixture:    "collisions.yaml",
+			id:         "Namespace/team-b|HTTPRoute/team-b/edge|Service/team-a/api|Pod/team-a/api-0",
+			wantParent: "Namespace/team-b|HTTPRoute/team-b/edge|Service/team-a/api",
+			wantFound:  true,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			nodes := hierarchy.Build(loadFixture(t, filepath.Join("testdata", tt.fixture)), nil)
+
+			got := hierarchy.FindNode(nodes, tt.id)
+			if (got != nil) != tt.wantFound {
+				t.Fatalf("TestFindNode() = %v, wantFound %v", got, tt.wantFound)
+			}
+			if got != nil && got.ParentID != tt.wantParent {
+				t.Errorf("TestFindNode() parent = %v, want %v", got.ParentID, tt.wantParent)
+			}
+		})
+	}
+}
+
+func TestRank(t *testing.T) {
+	tests := []struct {
+		name    string
+		fixture string
+		limit   int
+		want    types.Stats
+	}{
+		{
+			name:    "services ranked by endpoints",
+			fixture: "routes.yaml",
+			limit:   10,
+			want: types.Stats{
+				NamespacesByPods: []types.RankedResource{{Kind: types.ResourceKindNamespace, Name: "shop", Count: 3}},
+				ServicesByEndpoints: []types.RankedResource{
+					{Kind: types.ResourceKindService, Namespace: "shop", Name: "frontend", Count: 2},
+					{Kind: types.ResourceKindService, Namespace: "shop", Name: "payments", Count: 0},
+				},
+				RoutesByBackends: []types.RankedResource{{Kind: types.ResourceKindHTTPRoute, Namespace: "shop", Name: "storefront", Count: 1}},
+			},
+		},
+		{
+			name:    "limit truncates each ranking",
+			fixture: "routes.yaml",
+			limit:   1,
+			want: types.Stats{
+				NamespacesByPods:    []types.RankedResource{{Kind: types.ResourceKindNamespace, Name: "shop", Count: 3}},
+				ServicesByEndpoints: []types.RankedResource{{Kind: types.ResourceKindService, Namespace: "shop", Name: "frontend", Count: 2}},
+				RoutesByBackends:    []types.RankedResource{{Kind: types.ResourceKindHTTPRoute, Namespace: "shop", Name: "storefront", Count: 1}},
+			},
+		},
+		{
+			name:    "route backends behind a traffic split",
+			fixture: "split.yaml",
+			limit:   10,
+			want: types.Stats{
+				NamespacesByPods: []types.RankedResource{{Kind: types.ResourceKindNamespace, Name: "canary", Count: 2}},
+				ServicesByEndpoints: []types.RankedResource{
+					{Kind: types.ResourceKindService, Namespace: "canary", Name: "checkout-canary", Count: 0},
+					{Kind: types.ResourceKindService, Namespace: "canary", Name: "checkout-stable", Count: 0},
+				},
+				RoutesByBackends: []types.RankedResource{{Kind: types.ResourceKindHTTPRoute, Namespace: "canary", Name: "checkout", Count: 2}},
+			},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			nodes := hierarchy.Build(loadFixture(t, filepath.Join("testdata", tt.fixture)), nil)
+
+			got := hierarchy.Rank(nodes, tt.limit)
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestRank() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestLabelFacets(t *testing.T) {
+	nodes := hierarchy.Build(loadFixture(t, filepath.Join("testdata", "routes.yaml")), nil)
+
+	want := []types.LabelFacet{
+		{Key: "app", Count: 2, Values: []types.LabelValue{{Value: "frontend", Count: 1}, {Value: "payments", Count: 1}}},
+		{Key: "team", Count: 2, Values: []types.LabelValue{{Value: "Payments", Count: 1}, {Value: "storefront", Count: 1}}},
+	}
+	got := hierarchy.LabelFacets(nodes)
+	if !reflect.DeepEqual(got, want) {
+		t.Errorf("TestLabelFacets() = %+v, want %+v", got, want)
+	}
+}
+
+func TestFilterBySelector(t *testing.T) {
+	tests := []struct {
+		name     string
+		selector string
+		want     []string
+	}{
+		{
+			name:     "equality keeps the path to the matching pod",
+			selector: "app=payments",
+			want:     []string{"Pod/payments-a", "Service/payments", "Namespace/shop"},
+		},
+		{
+			name:     "set based selector",
+			selector: "app in (frontend)",
+			want:     []string{"Pod/frontend-a", "Service/frontend", "HTTPRoute/storefront", "Namespace/shop"},
+		},
+		{
+			name:     "matching namespace keeps its whole tree",
+			selector: "team",
+			want: []string{
+				"Pod/frontend-a", "Service/frontend", "HTTPRoute/storefront",
+				"Pod/payments-a", "Service/payments", "Pod/debug", "Namespace/shop",
+			},
+		},
+		{
+			name:     "does not exist combined with exists",
+			selector: "!team,app",
+			want: []string{
+				"Pod/frontend-a", "Service/frontend", "HTTPRoute/storefront",
+				"Pod/payments-a", "Service/payments", "Namespace/shop",
+			},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			selector, err := labels.Parse(tt.selector)
+			if err != nil {
+				t.Fatalf("TestFilterBySelector() parse error = %v", err)
+			}
+			nodes := hierarchy.Build(loadFixture(t, filepath.Join("testdata", "routes.yaml")), nil)
+
+			var got []string
+			hierarchy.Walk(hierarchy.FilterBySelector(nodes, selector), func(node *types.HierarchyNode) {
+				got = append(got, string(node.Kind)+"/"+node.Name)
+			})
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestFilterBySelector() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestFilterByNamespace(t *testing.T) {
+	nodes := []types.HierarchyNode{
+		{Kind: types.ResourceKindNamespace, Name: "shop", Relatives: []types.HierarchyNode{
+			{Kind: types.ResourceKindService, Name: "web", Namespace: ptr.To("shop")},
+		}},
+		{Kind: types.ResourceKindNamespace, Name: "ops", Relatives: []types.HierarchyNode{
+			{Kind: types.ResourceKindService, Name: "grafana", Namespace: ptr.To("ops")},
+		}},
+		{Kind: types.ResourceKindGateway, Name: "public", Namespace: ptr.To("infra"), Relatives: []types.HierarchyNode{
+			{Kind: types.ResourceKindHTTPRoute, Name: "storefront", Namespace: ptr.To("shop")},
+			{Kind: types.ResourceKindHTTPRoute, Name: "dashboards", Namespace: ptr.To("ops")},
+		}},
+	}
+
+	tests := []struct {
+		name       string
+		namespaces []string
+		want       []string
+	}{
+		{
+			name:       "namespace roots and the resources in them are kept",
+			namespaces: []string{"shop"},
+			want:       []string{"Service/web", "Namespace/shop", "HTTPRoute/storefront", "Gateway/public"},
+		},
+		{
+			name:       "several namespaces",
+			namespaces: []string{"shop", "ops"},
+			want: []string{
+				"Service/web", "Namespace/shop", "Service/grafana", "Namespace/ops",
+				"HTTPRoute/storefront", "HTTPRoute/dashboards", "Gateway/public",
+			},
+		},
+		{
+			name:       "a namespaced root keeps its whole tree",
+			namespaces: []string{"infra"},
+			want:       []string{"HTTPRoute/storefront", "HTTPRoute/dashboards", "Gateway/public"},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var got []string
+			hierarchy.Walk(hierarchy.FilterByNamespace(nodes, tt.namespaces...), func(node *types.HierarchyNode) {
+				got = append(got, string(node.Kind)+"/"+node.Name)
+			})
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestFilterByNamespace() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestFilterByIDs(t *testing.T) {
+	nodes := []types.HierarchyNode{
+		{ID: "Namespace/shop", Kind: types.ResourceKindNamespace, Name: "shop", Relatives: []types.HierarchyNode{
+			{ID: "Service/shop/web", Kind: types.ResourceKindService, Name: "web", Relatives: []types.HierarchyNode{
+				{ID: "Pod/shop/web-0", Kind: types.ResourceKindPod, Name: "web-0"},
+			}},
+			{ID: "Service/shop/api", Kind: types.ResourceKindService, Name: "api"},
+		}},
+		{ID: "Namespace/ops", Kind: types.ResourceKindNamespace, Name: "ops", Relatives: []types.HierarchyNode{
+			{ID: "Service/ops/grafana", Kind: types.ResourceKindService, Name: "grafana"},
+		}},
+	}
+
+	tests := []struct {
+		name string
+		ids  []string
+		want []string
+	}{
+		{
+			name: "pinned service keeps its pods and namespace",
+			ids:  []string{"Service/shop/web"},
+			want: []string{"Pod/web-0", "Service/web", "Namespace/shop"},
+		},
+		{
+			name: "pins across namespaces",
+			ids:  []string{"Pod/shop/web-0", "Service/ops/grafana"},
+			want: []string{"Pod/web-0", "Service/web", "Namespace/shop", "Service/grafana", "Namespace/ops"},
+		},
+		{
+			name: "nothing pinned",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var got []string
+			hierarchy.Walk(hierarchy.FilterByIDs(nodes, tt.ids...), func(node *types.HierarchyNode) {
+				got = append(got, string(node.Kind)+"/"+node.Name)
+			})
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestFilterByIDs() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
+
+func graphFixture() []types.HierarchyNode {
+	shop := ptr.To("shop")
+	pod := func(name, nodeName string) types.HierarchyNode {
+		return types.HierarchyNode{Kind: types.ResourceKindPod, Name: name, Namespace: shop, NodeName: nodeName}
+	}
+	return []types.HierarchyNode{{
+		Kind: types.ResourceKindNamespace,
+		Name: "shop",
+		Relatives: []types.HierarchyNode{
+			{
+				Kind:      types.ResourceKindHTTPRoute,
+				Name:      "storefront",
+				Namespace: shop,
+				Hostnames: []string{"shop.example.com"},
+				Gateways:  []string{"infra/public"},
+				Relatives: []types.HierarchyNode{{
+					Kind:      types.ResourceKindTrafficSplit,
+					Name:      "storefront-rule-0",
+					Namespace: shop,
+					Relatives: []types.HierarchyNode{{
+						Kind:      types.ResourceKindService,
+						Name:      "web",
+						Namespace: shop,
+						Relatives: []types.HierarchyNode{pod("web-0", "worker-1"), pod("web-1", "worker-2")},
+					}},
+				}},
+			},
+			{
+				Kind:      types.ResourceKindService,
+				Name:      "api",
+				Namespace: shop,
+				Relatives: []types.HierarchyNode{pod("web-0", "worker-1")},
+			},
+		},
+	}}
+}
+
+func graphRef(kind types.ResourceKind, namespace, name string) types.NodeRef {
+	id := string(kind) + "/" + name
+	if namespace != "" {
+		id = string(kind) + "/" + namespace + "/" + name
+	}
+	return types.NodeRef{ID: id, Kind: kind, Namespace: namespace, Name: name}
+}
+
+func TestGraph_Impact(t *testing.T) {
+	gateway := graphRef(types.ResourceKindGateway, "infra", "public")
+	route := graphRef(types.ResourceKindHTTPRoute, "shop", "storefront")
+	hostname := graphRef(types.ResourceKindHostname, "", "shop.example.com")
+	api := graphRef(types.ResourceKindService, "shop", "api")
+	web := graphRef(types.ResourceKindService, "shop", "web")
+	web0 := graphRef(types.ResourceKindPod, "shop", "web-0")
+	web1 := graphRef(types.ResourceKindPod, "shop", "web-1")
+	worker1 := graphRef(types.ResourceKindNode, "", "worker-1")
+	worker2 := graphRef(types.ResourceKindNode, "", "worker-2")
+
+	tests := []struct {
+		name      string
+		id        string
+		want      types.Impact
+		wantFound bool
+	}{
+		{
+			name: "a pod is depended on by every route and hostname that reaches it",
+			id:   "Pod/shop/web-0",
+			want: types.Impact{
+				Target:     web0,
+				Upstream:   []types.NodeRef{gateway, route, hostname, api, web},
+				Downstream: []types.NodeRef{worker1},
+			},
+			wantFound: true,
+		},
+		{
+			name: "a route reaches through traffic splits, matched ignoring case",
+			id:   "httproute/shop/storefront",
+			want: types.Impact{
+				Target:     route,
+				Upstream:   []types.NodeRef{gateway, hostname},
+				Downstream: []types.NodeRef{worker1, worker2, web0, web1, web},
+			},
+			wantFound: true,
+		},
+		{
+			name: "a node takes down what runs on it",
+			id:   "Node/worker-2",
+			want: types.Impact{
+				Target:     worker2,
+				Upstream:   []types.NodeRef{gateway, route, hostname, web1, web},
+				Downstream: []types.NodeRef{},
+			},
+			wantFound: true,
+		},
+		{
+			name: "unknown resource",
+			id:   "Pod/shop/missing",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, found := hierarchy.NewGraph(graphFixture()).Impact(tt.id)
+			if found != tt.wantFound {
+				t.Fatalf("TestGraph_Impact() found = %v, want %v", found, tt.wantFound)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestGraph_Impact() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestGraph_Paths(t *testing.T) {
+	hostname := graphRef(types.ResourceKindHostname, "", "shop.example.com")
+	route := graphRef(types.ResourceKindHTTPRoute, "shop", "storefront")
+	api := graphRef(types.ResourceKindService, "shop", "api")
+	web := graphRef(types.ResourceKindService, "shop", "web")
+	web0 := graphRef(types.ResourceKindPod, "shop", "web-0")
+	worker1 := graphRef(types.ResourceKindNode, "", "worker-1")
+
+	tests := []struct {
+		name      string
+		from      string
+		to        string
+		want      types.Paths
+		wantFound bool
+	}{
+		{
+			name: "a hostname reaches the node serving it",
+			from: "Hostname/shop.example.com",
+			to:   "Node/worker-1",
+			want: types.Paths{
+				From:    hostname,
+				To:      worker1,
+				Related: true,
+				Paths:   [][]types.NodeRef{{hostname, route, web, web0, worker1}},
+			},
+			wantFound: true,
+		},
+		{
+			name: "asking from the dependency walks the same chain",
+			from: "node/worker-1",
+			to:   "Hostname/shop.example.com",
+			want: types.Paths{
+				From:    worker1,
+				To:      hostname,
+				Related: true,
+				Paths:   [][]types.NodeRef{{hostname, route, web, web0, worker1}},
+			},
+			wantFound: true,
+		},
+		{
+			name: "a resource is related to itself",
+			from: "Pod/shop/web-0",
+			to:   "Pod/shop/web-0",
+			want: types.Paths{
+				From:    web0,
+				To:      web0,
+				Related: true,
+				Paths:   [][]types.NodeRef{{web0}},
+			},
+			wantFound: true,
+		},
+		{
+			name: "services sharing a pod do not depend on each other",
+			from: "Service/shop/api",
+			to:   "Service/shop/web",
+			want: types.Paths{
+				From:  api,
+				To:    web,
+				Paths: [][]types.NodeRef{},
+			},
+			wantFound: true,
+		},
+		{
+			name: "unknown resource",
+			from: "Service/shop/api",
+			to:   "Pod/shop/missing",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, found := hierarchy.NewGraph(graphFixture()).Paths(tt.from, tt.to)
+			if found != tt.wantFound {
+				t.Fatalf("TestGraph_Paths() found = %v, want %v", found, tt.wantFound)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestGraph_Paths() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestPage(t *testing.T) {
+	namespace := types.HierarchyNode{
+		Kind: types.ResourceKindNamespace,
+		Name: "jobs",
+		ID:   "Namespace/jobs",
+		Relatives: []types.HierarchyNode{
+			{Kind: types.ResourceKindPod, Name: "job-0"},
+			{Kind: types.ResourceKindPod, Name: "job-1"},
+			{Kind: types.ResourceKindPod, Name: "job-2"},
+			{Kind: types.ResourceKindPod, Name: "job-3"},
+			{Kind: types.ResourceKindPod, Name: "job-4"},
+		},
+	}
+	truncated := func(count int) types.HierarchyNode {
+		return types.HierarchyNode{
+			Kind:     types.ResourceKindTruncated,
+			ID:       "Truncated/Namespace/jobs",
+			ParentID: "Namespace/jobs",
+			Count:    count,
+		}
+	}
+
+	tests := []struct {
+		name   string
+		offset int
+		limit  int
+		want   []string
+		marker *types.HierarchyNode
+	}{
+		{
+			name:   "first page is followed by a marker counting the rest",
+			limit:  2,
+			want:   []string{"job-0", "job-1"},
+			marker: ptr.To(truncated(3)),
+		},
+		{
+			name:   "middle page",
+			offset: 2,
+			limit:  2,
+			want:   []string{"job-2", "job-3"},
+			marker: ptr.To(truncated(1)),
+		},
+		{
+			name:   "last page has no marker",
+			offset: 4,
+			limit:  2,
+			want:   []string{"job-4"},
+		},
+		{
+			name:  "children within the limit are untouched",
+			limit: 5,
+			want:  []string{"job-0", "job-1", "job-2", "job-3", "job-4"},
+		},
+		{
+			name: "zero limit disables the guard",
+			want: []string{"job-0", "job-1", "job-2", "job-3", "job-4"},
+		},
+		{
+			name:   "offset past the end is empty",
+			offset: 9,
+			limit:  2,
+			want:   []string{},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := hierarchy.Page(namespace, tt.offset, tt.limit)
+
+			names := []string{}
+			var marker *types.HierarchyNode
+			for _, relative := range got.Relatives {
+				if relative.Kind == types.ResourceKindTruncated {
+					marker = &relative
+					continue
+				}
+				names = append(names, relative.Name)
+			}
+			if !reflect.DeepEqual(names, tt.want) {
+				t.Errorf("TestPage() names = %v, want %v", names, tt.want)
+			}
+			if !reflect.DeepEqual(marker, tt.marker) {
+				t.Errorf("TestPage() marker = %+v, want %+v", marker, tt.marker)
+			}
+			if len(namespace.Relatives) != 5 {
+				t.Errorf("TestPage() modified the input, %d children left", len(namespace.Relatives))
+			}
+		})
+	}
+}
+
+func TestBuild_Terminating(t *testing.T) {
+	now := time.Date(2025, 1, 1, 12, 0, 0, 0, time.UTC)
+	deadline := metav1.NewTime(now.Add(25 * time.Second))
+
+	type termination struct {
+		terminating bool
+		deadline    *metav1.Time
+		grace       *int64
+	}
+
+	tests := []struct {
+		name string
+		pod  corev1.Pod
+		want termination
+	}{
+		{
+			name: "pod counts down its grace period",
+			pod: corev1.Pod{ObjectMeta: metav1.ObjectMeta{
+				Name:                       "web-0",
+				Namespace:                  "shop",
+				DeletionTimestamp:          &deadline,
+				DeletionGracePeriodSeconds: ptr.To[int64](30),
+			}},
+			want: termination{terminating: true, deadline: &deadline, grace: ptr.To[int64](25)},
+		},
+		{
+			name: "overdue pod has no grace left",
+			pod: corev1.Pod{ObjectMeta: metav1.ObjectMeta{
+				Name:                       "web-0",
+				Namespace:                  "shop",
+				DeletionTimestamp:          ptr.To(metav1.NewTime(now.Add(-time.Minute))),
+				DeletionGracePeriodSeconds: ptr.To[int64](30),
+			}},
+			want: termination{
+				terminating: true,
+				deadline:    ptr.To(metav1.NewTime(now.Add(-time.Minute))),
+				grace:       ptr.To[int64](0),
+			},
+		},
+		{
+			name: "pod held by a finalizer has no grace period",
+			pod: corev1.Pod{ObjectMeta: metav1.ObjectMeta{
+				Name:                       "web-0",
+				Namespace:                  "shop",
+				DeletionTimestamp:          &deadline,
+				DeletionGracePeriodSeconds: ptr.To[int64](0),
+				Finalizers:                 []string{"example.com/drain"},
+			}},
+			want: termination{terminating: true},
+		},
+		{
+			name: "running pod",
+			pod:  corev1.Pod{ObjectMeta: metav1.ObjectMeta{Name: "web-0", Namespace: "shop"}},
+			want: termination{},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			objects := types.ClusterObjects{Pods: []corev1.Pod{tt.pod}}
+			nodes := hierarchy.Build(objects, nil, hierarchy.WithNow(now))
+
+			pod := hierarchy.FindNode(nodes, "Pod/shop/web-0")
+			if pod == nil {
+				t.Fatal("TestBuild_Terminating() pod not found")
+			}
+			got := termination{terminating: pod.Terminating, deadline: pod.TerminationDeadline, grace: pod.GracePeriodSeconds}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestBuild_Terminating() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestBuild_Tracks(t *testing.T) {
+	service := func(name string, selector map[string]string) corev1.Service {
+		return corev1.Service{
+			ObjectMeta: metav1.ObjectMeta{Name: name, Namespace: "shop"},
+			Spec:       corev1.ServiceSpec{Selector: selector},
+		}
+	}
+	pod := func(name string, labels map[string]string) corev1.Pod {
+		return corev1.Pod{ObjectMeta: metav1.ObjectMeta{Name: name, Namespace: "shop", Labels: labels}}
+	}
+	objects := types.ClusterObjects{
+		Services: []corev1.Service{
+			service("web", map[string]string{"app": "web"}),
+			service("web-canary", map[string]string{"app": "web", "rollouts-pod-template-hash": "bbb"}),
+			service("web-stable", map[string]string{"app": "web", "rollouts-pod-template-hash": "aaa"}),
+			service("api", map[string]string{"app": "api"}),
+			service("db", map[string]string{"app": "db"}),
+		},
+		Pods: []corev1.Pod{
+			pod("web-aaa", map[string]string{"app": "web", "rollouts-pod-template-hash": "aaa"}),
+			pod("web-bbb", map[string]string{"app": "web", "rollouts-pod-template-hash": "bbb"}),
+			pod("api-0", map[string]string{"app": "api", "track": "stable"}),
+			pod("api-1", map[string]string{"app": "api", "track": "canary"}),
+			pod("api-2", map[string]string{"app": "api"}),
+			pod("db-0", map[string]string{"app": "db", "track": "stable"}),
+		},
+	}
+
+	tests := []struct {
+		name    string
+		service string
+		want    []string
+	}{
+		{
+			name:    "rollout services split the root service by track",
+			service: "Service/shop/web",
+			want: []string{
+				"Track/shop/web-canary canary",
+				"  Pod/shop/web-bbb canary",
+				"Track/shop/web-stable stable",
+				"  Pod/shop/web-aaa stable",
+			},
+		},
+		{
+			name:    "track labels group pods and untracked pods stay direct children",
+			service: "Service/shop/api",
+			want: []string{
+				"Track/shop/api-canary canary",
+				"  Pod/shop/api-1 canary",
+				"Track/shop/api-stable stable",
+				"  Pod/shop/api-0 stable",
+				"Pod/shop/api-2 ",
+			},
+		},
+		{
+			name:    "a service on one track is not grouped",
+			service: "Service/shop/web-canary",
+			want:    []string{"Pod/shop/web-bbb canary"},
+		},
+		{
+			name:    "a single tracked pod is not grouped",
+			service: "Service/shop/db",
+			want:    []string{"Pod/shop/db-0 stable"},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			node := hierarchy.FindNode(hierarchy.Build(objects, nil), tt.service)
+			if node == nil {
+				t.Fatalf("TestBuild_Tracks() %s not found", tt.service)
+			}
+			if got := outline(node.Relatives, ""); !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestBuild_Tracks() = %q, want %q", got, tt.want)
+			}
+		})
+	}
+}
+
+// outline lists each node's resource ID with its display name or track, indenting children under their parent
+func outline(nodes []types.HierarchyNode, indent string) []string {
+	var lines []string
+	for _, node := range nodes {
+		label := node.Track
+		if node.Kind == types.ResourceKindTrack {
+			label = node.DisplayName
+		}
+		lines = append(lines, indent+hierarchy.ResourceID(&node)+" "+label)
+		lines = append(lines, outline(node.Relatives, indent+"  ")...)
+	}
+	return lines
+}
@@ -0,0 +1,71 @@
+package hierarchy
+
+import (
+	"slices"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// pathSeparator joins the resource IDs along a node's path into its ID
+const pathSeparator = "|"
+
+// NodeID identifies a resource across builds as kind/namespace/name, or kind/name when it is cluster scoped
+func NodeID(kind types.ResourceKind, namespace *string, name string) string {
+	if namespace == nil {
+		return string(kind) + "/" + name
+	}
+	return string(kind) + "/" + *namespace + "/" + name
+}
+
+// ResourceID returns the NodeID of the resource node shows, which is the same for every copy of it
+func ResourceID(node *types.HierarchyNode) string {
+	return NodeID(node.Kind, node.Namespace, node.Name)
+}
+
+// assignIDs gives every node the path of resource IDs from its root, joined by pathSeparator, so each copy
+// of a resource appearing under several parents, like a pod selected by several Services, has its own ID.
+// Roots keep their plain resource ID.
+func assignIDs(nodes []types.HierarchyNode, parentID string) {
+	for i := range nodes {
+		nodes[i].ID = pathID(&nodes[i], parentID)
+		nodes[i].ParentID = parentID
+		assignIDs(nodes[i].Relatives, nodes[i].ID)
+	}
+}
+
+func pathID(node *types.HierarchyNode, parentID string) string {
+	if parentID == "" {
+		return ResourceID(node)
+	}
+	return parentID + pathSeparator + ResourceID(node)
+}
+
+// withIDs returns a copy of nodes with IDs assigned as assignIDs does, leaving nodes and the subtrees they
+// share with the hierarchy they were taken from untouched
+func withIDs(nodes []types.HierarchyNode, parentID string) []types.HierarchyNode {
+	result := slices.Clone(nodes)
+	for i := range result {
+		result[i].ID = pathID(&result[i], parentID)
+		result[i].ParentID = parentID
+		result[i].Relatives = withIDs(result[i].Relatives, result[i].ID)
+	}
+	return result
+}
+
+// FindNode returns the node with id. An id naming a resource rather than a path matches its first copy,
+// searching each subtree before its root. It returns nil when there is none.
+func FindNode(nodes []types.HierarchyNode, id string) *types.HierarchyNode {
+	var exact, resource *types.HierarchyNode
+	Walk(nodes, func(node *types.HierarchyNode) {
+		if exact == nil && node.ID == id {
+			exact = node
+		}
+		if resource == nil && ResourceID(node) == id {
+			resource = node
+		}
+	})
+	if exact != nil {
+		return exact
+	}
+	return resource
+}
@@ -0,0 +1,89 @@
+package hierarchy
+
+import (
+	"slices"
+	"sort"
+
+	"k8s.io/apimachinery/pkg/labels"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// LabelFacets indexes the label keys and values of every resource in the tree, counting each resource
+// once even where it appears under several parents
+func LabelFacets(nodes []types.HierarchyNode) []types.LabelFacet {
+	index := make(map[string]map[string]int)
+	seen := make(map[string]bool)
+	Walk(nodes, func(node *types.HierarchyNode) {
+		id := ResourceID(node)
+		if seen[id] {
+			return
+		}
+		seen[id] = true
+		for labelKey, value := range node.Labels {
+			if index[labelKey] == nil {
+				index[labelKey] = make(map[string]int)
+			}
+			index[labelKey][value]++
+		}
+	})
+
+	facets := make([]types.LabelFacet, 0, len(index))
+	for labelKey, values := range index {
+		facet := types.LabelFacet{Key: labelKey, Values: make([]types.LabelValue, 0, len(values))}
+		for value, count := range values {
+			facet.Values = append(facet.Values, types.LabelValue{Value: value, Count: count})
+			facet.Count += count
+		}
+		sort.Slice(facet.Values, func(i, j int) bool { return facet.Values[i].Value < facet.Values[j].Value })
+		facets = append(facets, facet)
+	}
+	sort.Slice(facets, func(i, j int) bool { return facets[i].Key < facets[j].Key })
+	return facets
+}
+
+// FilterBySelector keeps the resources whose labels match selector, with everything below them,
+// and the resources leading to them. Selectors follow Kubernetes semantics, e.g. "app in (web,api),!canary".
+func FilterBySelector(nodes []types.HierarchyNode, selector labels.Selector) []types.HierarchyNode {
+	return filter(nodes, func(node *types.HierarchyNode) bool {
+		return selector.Matches(labels.Set(node.Labels))
+	})
+}
+
+// FilterByNamespace keeps the resources in one of namespaces, with everything below them, and the
+// resources leading to them
+func FilterByNamespace(nodes []types.HierarchyNode, namespaces ...string) []types.HierarchyNode {
+	return filter(nodes, func(node *types.HierarchyNode) bool {
+		if node.Kind == types.ResourceKindNamespace {
+			return slices.Contains(namespaces, node.Name)
+		}
+		return node.Namespace != nil && slices.Contains(namespaces, *node.Namespace)
+	})
+}
+
+// FilterByIDs keeps the nodes with one of ids, with everything below them, and the resources leading to them.
+// An id naming a resource rather than a path keeps every copy of it.
+func FilterByIDs(nodes []types.HierarchyNode, ids ...string) []types.HierarchyNode {
+	return filter(nodes, func(node *types.HierarchyNode) bool {
+		return slices.Contains(ids, node.ID) || slices.Contains(ids, ResourceID(node))
+	})
+}
+
+// filter keeps matching nodes whole and the ancestors of matching nodes, dropping other branches
+func filter(nodes []types.HierarchyNode, matches func(node *types.HierarchyNode) bool) []types.HierarchyNode {
+	var result []types.HierarchyNode
+	for _, node := range nodes {
+		if matches(&node) {
+			result = append(result, node)
+			continue
+		}
+
+		relatives := filter(node.Relatives, matches)
+		if len(relatives) == 0 {
+			continue
+		}
+		node.Relatives = relatives
+		result = append(result, node)
+	}
+	return result
+}
@@ -0,0 +1,62 @@
+package hierarchy
+
+import (
+	"sort"
+	"strings"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+var defaultOwnerKeys = []string{"owner", "team"}
+
+// owner returns the normalized value of the first owner key set on an object, checking labels before
+// annotations for each key
+func (b *builder) owner(objectLabels, objectAnnotations map[string]string) string {
+	for _, key := range b.config.ownerKeys {
+		if value := strings.TrimSpace(objectLabels[key]); value != "" {
+			return strings.ToLower(value)
+		}
+		if value := strings.TrimSpace(objectAnnotations[key]); value != "" {
+			return strings.ToLower(value)
+		}
+	}
+	return ""
+}
+
+// inheritOwner gives every resource without an owner of its own the owner of its nearest owned ancestor
+func inheritOwner(nodes []types.HierarchyNode, owner string) {
+	for i := range nodes {
+		if nodes[i].Owner == "" {
+			nodes[i].Owner = owner
+		}
+		inheritOwner(nodes[i].Relatives, nodes[i].Owner)
+	}
+}
+
+// GroupByOwner lists the resources of each owner, sorted by owner. Resources without an owner are
+// left out, and a pod selected by several services is listed once.
+func GroupByOwner(nodes []types.HierarchyNode) []types.OwnerGroup {
+	resources := make(map[string][]types.ResourceRef)
+	seen := make(map[types.ResourceRef]bool)
+	Walk(nodes, func(node *types.HierarchyNode) {
+		if node.Owner == "" {
+			return
+		}
+		ref := types.ResourceRef{Kind: node.Kind, Name: node.Name}
+		if node.Namespace != nil {
+			ref.Namespace = *node.Namespace
+		}
+		if seen[ref] {
+			return
+		}
+		seen[ref] = true
+		resources[node.Owner] = append(resources[node.Owner], ref)
+	})
+
+	groups := make([]types.OwnerGroup, 0, len(resources))
+	for owner, refs := range resources {
+		groups = append(groups, types.OwnerGroup{Owner: owner, Resources: refs})
+	}
+	sort.Slice(groups, func(i, j int) bool { return groups[i].Owner < groups[j].Owner })
+	return groups
+}
@@ -0,0 +1,31 @@
+package hierarchy
+
+import (
+	"strings"
+
+	corev1 "k8s.io/api/core/v1"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// podPlatform returns the operating system and architecture a pod runs on. A scheduled pod takes
+// them from its node's labels; otherwise they are implied by spec.os and the pod's node selector.
+func (b *builder) podPlatform(pod corev1.Pod) (string, string) {
+	if nodeLabels, exists := b.nodeLabels[pod.Spec.NodeName]; exists {
+		return nodeLabels[corev1.LabelOSStable], nodeLabels[corev1.LabelArchStable]
+	}
+
+	osName := pod.Spec.NodeSelector[corev1.LabelOSStable]
+	if pod.Spec.OS != nil {
+		osName = string(pod.Spec.OS.Name)
+	}
+	return osName, pod.Spec.NodeSelector[corev1.LabelArchStable]
+}
+
+// FilterByOS keeps the pods running on osName and the resources leading to them, dropping branches
+// without a matching pod. Pods whose operating system is unknown are dropped as well.
+func FilterByOS(nodes []types.HierarchyNode, osName string) []types.HierarchyNode {
+	return filter(nodes, func(node *types.HierarchyNode) bool {
+		return node.Kind == types.ResourceKindPod && strings.EqualFold(node.OS, osName)
+	})
+}
@@ -0,0 +1,67 @@
+package hierarchy
+
+import (
+	"fmt"
+
+	corev1 "k8s.io/api/core/v1"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// portMismatches checks every service port's target against the container ports of its backends.
+// Named target ports must exist on each pod. Numeric targets are only checked on pods that declare
+// container ports at all, since declaring them is optional for unnamed ports.
+func portMismatches(service corev1.Service, backends []corev1.Pod) []types.Warning {
+	var warnings []types.Warning
+	for _, port := range service.Spec.Ports {
+		for _, pod := range backends {
+			declared := podContainerPorts(pod)
+			if port.TargetPort.StrVal != "" {
+				if _, found := declared[port.TargetPort.StrVal]; !found {
+					warnings = append(warnings, types.Warning{
+						Type:    types.WarningTypePortMismatch,
+						Message: fmt.Sprintf("port %d targets named port %q, which pod %s does not define", port.Port, port.TargetPort.StrVal, pod.Name),
+					})
+				}
+				continue
+			}
+
+			target := port.TargetPort.IntVal
+			if target == 0 {
+				target = port.Port
+			}
+			if len(declared) == 0 || declaresNumber(declared, target) {
+				continue
+			}
+			warnings = append(warnings, types.Warning{
+				Type:    types.WarningTypePortMismatch,
+				Message: fmt.Sprintf("port %d targets %d, which no container in pod %s exposes", port.Port, target, pod.Name),
+			})
+		}
+	}
+	return warnings
+}
+
+// podContainerPorts maps container port names, or the number for unnamed ports, to their number
+func podContainerPorts(pod corev1.Pod) map[string]int32 {
+	ports := make(map[string]int32)
+	for _, container := range pod.Spec.Containers {
+		for _, port := range container.Ports {
+			name := port.Name
+			if name == "" {
+				name = fmt.Sprint(port.ContainerPort)
+			}
+			ports[name] = port.ContainerPort
+		}
+	}
+	return ports
+}
+
+func declaresNumber(ports map[string]int32, number int32) bool {
+	for _, declared := range ports {
+		if declared == number {
+			return true
+		}
+	}
+	return false
+}
@@ -0,0 +1,91 @@
+package hierarchy
+
+import (
+	corev1 "k8s.io/api/core/v1"
+	schedulingv1 "k8s.io/api/scheduling/v1"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// noPriorityClass is the summary key for pods without a priority class when no global default exists
+const noPriorityClass = "none"
+
+// priorities resolves pod priorities against the cluster's PriorityClasses
+type priorities struct {
+	classes map[string]schedulingv1.PriorityClass
+	// globalDefault is the class assigned to pods that do not name one, if any
+	globalDefault *schedulingv1.PriorityClass
+	// highestPreempting is the highest value of any class allowed to preempt lower priority pods
+	highestPreempting *int32
+}
+
+func newPriorities(classes []schedulingv1.PriorityClass) priorities {
+	p := priorities{classes: make(map[string]schedulingv1.PriorityClass, len(classes))}
+	for _, class := range classes {
+		p.classes[class.Name] = class
+		if class.GlobalDefault {
+			p.globalDefault = &class
+		}
+		if class.PreemptionPolicy != nil && *class.PreemptionPolicy == corev1.PreemptNever {
+			continue
+		}
+		if p.highestPreempting == nil || class.Value > *p.highestPreempting {
+			p.highestPreempting = &class.Value
+		}
+	}
+	return p
+}
+
+// resolve returns the pod's priority class and value. The Priority admission plugin normally fills
+// spec.priority; the class value is used when it has not, and pods without a class get the global
+// default or zero.
+func (p priorities) resolve(pod corev1.Pod) (string, int32) {
+	className := pod.Spec.PriorityClassName
+	if className == "" && p.globalDefault != nil {
+		className = p.globalDefault.Name
+	}
+	if pod.Spec.Priority != nil {
+		return className, *pod.Spec.Priority
+	}
+	class, exists := p.classes[className]
+	if !exists {
+		return className, 0
+	}
+	return className, class.Value
+}
+
+// preemptible reports whether a pending pod of a higher priority class could evict this one
+func (p priorities) preemptible(priority int32) bool {
+	return p.highestPreempting != nil && priority < *p.highestPreempting
+}
+
+// prioritySummary counts the pods in a namespace tree by priority class
+func prioritySummary(relatives []types.HierarchyNode) *types.PrioritySummary {
+	summary := &types.PrioritySummary{Classes: make(map[string]int)}
+	seen := make(map[string]bool)
+	Walk(relatives, func(node *types.HierarchyNode) {
+		if node.Kind != types.ResourceKindPod || node.Priority == nil {
+			return
+		}
+		id := NodeID(node.Kind, node.Namespace, node.Name)
+		if seen[id] {
+			return
+		}
+		seen[id] = true
+		className := node.PriorityClassName
+		if className == "" {
+			className = noPriorityClass
+		}
+		summary.Classes[className]++
+		if node.Preemptible {
+			summary.Preemptible++
+		}
+		if summary.Lowest == nil || *node.Priority < *summary.Lowest {
+			summary.Lowest = node.Priority
+		}
+	})
+	if len(seen) == 0 {
+		return nil
+	}
+	return summary
+}
@@ -0,0 +1,78 @@
+package hierarchy
+
+import (
+	"fmt"
+
+	corev1 "k8s.io/api/core/v1"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// Probe timing defaults applied by the apiserver when a field is left unset
+const (
+	defaultProbePeriodSeconds    = 10
+	defaultProbeTimeoutSeconds   = 1
+	defaultProbeSuccessThreshold = 1
+	defaultProbeFailureThreshold = 3
+)
+
+// containerProbes summarizes the startup, liveness and readiness probes of a pod's containers.
+// Init containers are included since native sidecars may define probes.
+func containerProbes(pod corev1.Pod) []types.ProbeInfo {
+	containers := append([]corev1.Container{}, pod.Spec.InitContainers...)
+	containers = append(containers, pod.Spec.Containers...)
+
+	var probes []types.ProbeInfo
+	for _, container := range containers {
+		for _, probe := range []struct {
+			kind  types.ProbeType
+			probe *corev1.Probe
+		}{
+			{types.ProbeTypeStartup, container.StartupProbe},
+			{types.ProbeTypeLiveness, container.LivenessProbe},
+			{types.ProbeTypeReadiness, container.ReadinessProbe},
+		} {
+			if probe.probe == nil {
+				continue
+			}
+			probes = append(probes, newProbeInfo(container.Name, probe.kind, probe.probe))
+		}
+	}
+	return probes
+}
+
+func newProbeInfo(container string, kind types.ProbeType, probe *corev1.Probe) types.ProbeInfo {
+	info := types.ProbeInfo{
+		Container:           container,
+		Type:                kind,
+		InitialDelaySeconds: probe.InitialDelaySeconds,
+		PeriodSeconds:       valueOrDefault(probe.PeriodSeconds, defaultProbePeriodSeconds),
+		TimeoutSeconds:      valueOrDefault(probe.TimeoutSeconds, defaultProbeTimeoutSeconds),
+		SuccessThreshold:    valueOrDefault(probe.SuccessThreshold, defaultProbeSuccessThreshold),
+		FailureThreshold:    valueOrDefault(probe.FailureThreshold, defaultProbeFailureThreshold),
+	}
+
+	switch {
+	case probe.HTTPGet != nil:
+		info.Handler = "http"
+		info.Path = probe.HTTPGet.Path
+		info.Port = probe.HTTPGet.Port.String()
+	case probe.TCPSocket != nil:
+		info.Handler = "tcp"
+		info.Port = probe.TCPSocket.Port.String()
+	case probe.GRPC != nil:
+		info.Handler = "grpc"
+		info.Port = fmt.Sprint(probe.GRPC.Port)
+	case probe.Exec != nil:
+		info.Handler = "exec"
+		info.Command = probe.Exec.Command
+	}
+	return info
+}
+
+func valueOrDefault(value, fallback int32) int32 {
+	if value == 0 {
+		return fallback
+	}
+	return value
+}
@@ -0,0 +1,308 @@
+package hierarchy
+
+import (
+	"context"
+	"fmt"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	discoveryv1 "k8s.io/api/discovery/v1"
+	networkingv1 "k8s.io/api/networking/v1"
+	schedulingv1 "k8s.io/api/scheduling/v1"
+	"k8s.io/apimachinery/pkg/api/meta"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"k8s.io/apimachinery/pkg/runtime"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+
+	"github.com/kdwils/constellation/internal/scrub"
+	"github.com/kdwils/constellation/internal/types"
+	"github.com/kdwils/constellation/internal/versions"
+)
+
+// HealthSource supplies the active health check results attached to service nodes
+type HealthSource interface {
+	GetAllHealthData() []*types.ServiceHealthInfo
+}
+
+// Enricher attaches data from an external source to nodes after the tree is built
+type Enricher interface {
+	Enrich(nodes []types.HierarchyNode)
+}
+
+// Provider builds the hierarchy on demand from the manager's cached view of the cluster
+type Provider struct {
+	reader    client.Reader
+	health    HealthSource
+	enrichers []Enricher
+	buildOpts []BuildOpt
+	listOpts  []ListOpt
+}
+
+// NewProvider creates a new Provider
+func NewProvider(reader client.Reader, health HealthSource, opts ...ProviderOpt) *Provider {
+	p := &Provider{
+		reader: reader,
+		health: health,
+	}
+
+	for _, opt := range opts {
+		opt(p)
+	}
+
+	return p
+}
+
+type ProviderOpt func(*Provider)
+
+// WithBuildOptions passes opts to every Build call
+func WithBuildOptions(opts ...BuildOpt) ProviderOpt {
+	return func(p *Provider) {
+		p.buildOpts = append(p.buildOpts, opts...)
+	}
+}
+
+// WithListOptions passes opts to every ListObjects call
+func WithListOptions(opts ...ListOpt) ProviderOpt {
+	return func(p *Provider) {
+		p.listOpts = append(p.listOpts, opts...)
+	}
+}
+
+// WithEnricher runs e over every hierarchy the provider builds, in the order given
+func WithEnricher(e Enricher) ProviderOpt {
+	return func(p *Provider) {
+		p.enrichers = append(p.enrichers, e)
+	}
+}
+
+// +kubebuilder:rbac:groups="",resources=namespaces,verbs=get;list;watch
+// +kubebuilder:rbac:groups=gateway.networking.k8s.io,resources=httproutes,verbs=get;list;watch
+// +kubebuilder:rbac:groups=gateway.networking.k8s.io,resources=gateways;gatewayclasses,verbs=get;list;watch
+// +kubebuilder:rbac:groups="",resources=nodes,verbs=get;list;watch
+// +kubebuilder:rbac:groups=discovery.k8s.io,resources=endpointslices,verbs=get;list;watch
+// +kubebuilder:rbac:groups=networking.k8s.io,resources=networkpolicies,verbs=get;list;watch
+// +kubebuilder:rbac:groups=scheduling.k8s.io,resources=priorityclasses,verbs=get;list;watch
+
+// GetHierarchy returns the current resource hierarchy
+func (p *Provider) GetHierarchy(ctx context.Context) ([]types.HierarchyNode, error) {
+	objects, err := ListObjects(ctx, p.reader, p.listOpts...)
+	if err != nil {
+		return nil, err
+	}
+	return p.build(objects, p.health.GetAllHealthData()), nil
+}
+
+// Snapshot captures the hierarchy, health data and raw objects at a single point in time
+func (p *Provider) Snapshot(ctx context.Context) (*types.Snapshot, error) {
+	objects, err := ListObjects(ctx, p.reader, p.listOpts...)
+	if err != nil {
+		return nil, err
+	}
+
+	health := p.health.GetAllHealthData()
+	return &types.Snapshot{
+		Version:   types.SnapshotVersion,
+		Timestamp: time.Now().UTC(),
+		Hierarchy: p.build(objects, health),
+		Health:    health,
+		Objects:   objects,
+	}, nil
+}
+
+func (p *Provider) build(objects types.ClusterObjects, health []*types.ServiceHealthInfo) []types.HierarchyNode {
+	nodes := Build(objects, health, p.buildOpts...)
+	for _, e := range p.enrichers {
+		e.Enrich(nodes)
+	}
+	return nodes
+}
+
+// WatchToggles decides which kinds are listed, so a kind whose watch is disabled is left empty
+type WatchToggles interface {
+	IfEnabled(resource string, list func() error) error
+}
+
+type listConfig struct {
+	pageSize int64
+	toggles  WatchToggles
+	versions versions.Versions
+	scrubber *scrub.Scrubber
+}
+
+type ListOpt func(*listConfig)
+
+// WithPageSize lists each kind in chunks of pageSize objects, so a direct read from the apiserver is
+// served in pages rather than one large response. Only use it with readers that return continue tokens;
+// the manager's cache does not, and would return only the first page.
+func WithPageSize(pageSize int64) ListOpt {
+	return func(c *listConfig) {
+		c.pageSize = pageSize
+	}
+}
+
+// WithWatchToggles leaves out the kinds whose watch toggles has disabled
+func WithWatchToggles(toggles WatchToggles) ListOpt {
+	return func(c *listConfig) {
+		c.toggles = toggles
+	}
+}
+
+// WithVersions reads the Gateway API kinds in the versions negotiated with the apiserver rather than v1beta1
+func WithVersions(served versions.Versions) ListOpt {
+	return func(c *listConfig) {
+		c.versions = served
+	}
+}
+
+// WithScrubber removes the annotations scrubber scrubs from every listed object, so they never reach a
+// snapshot even when the reader is not a cache that scrubs them on the way in
+func WithScrubber(scrubber *scrub.Scrubber) ListOpt {
+	return func(c *listConfig) {
+		c.scrubber = scrubber
+	}
+}
+
+// ListObjects reads every object kind the hierarchy is built from.
+// Gateway API kinds are optional since the CRDs may not be installed.
+func ListObjects(ctx context.Context, reader client.Reader, opts ...ListOpt) (types.ClusterObjects, error) {
+	var config listConfig
+	for _, opt := range opts {
+		opt(&config)
+	}
+
+	namespaces := namespaceMetadataList()
+	if err := config.list(ctx, reader, "namespaces", namespaces); err != nil {
+		return types.ClusterObjects{}, fmt.Errorf("failed to list namespaces: %w", err)
+	}
+
+	var services corev1.ServiceList
+	if err := config.list(ctx, reader, "services", &services); err != nil {
+		return types.ClusterObjects{}, fmt.Errorf("failed to list services: %w", err)
+	}
+
+	var pods corev1.PodList
+	if err := config.list(ctx, reader, "pods", &pods); err != nil {
+		return types.ClusterObjects{}, fmt.Errorf("failed to list pods: %w", err)
+	}
+
+	routes := config.versions.List("httproutes")
+	if err := config.list(ctx, reader, "httproutes", routes); err != nil && !meta.IsNoMatchError(err) {
+		return types.ClusterObjects{}, fmt.Errorf("failed to list httproutes: %w", err)
+	}
+
+	gateways := config.versions.List("gateways")
+	if err := config.list(ctx, reader, "gateways", gateways); err != nil && !meta.IsNoMatchError(err) {
+		return types.ClusterObjects{}, fmt.Errorf("failed to list gateways: %w", err)
+	}
+
+	gatewayClasses := config.versions.List("gatewayclasses")
+	if err := config.list(ctx, reader, "gatewayclasses", gatewayClasses); err != nil && !meta.IsNoMatchError(err) {
+		return types.ClusterObjects{}, fmt.Errorf("failed to list gatewayclasses: %w", err)
+	}
+
+	var priorityClasses schedulingv1.PriorityClassList
+	if err := config.list(ctx, reader, "priorityclasses", &priorityClasses); err != nil {
+		return types.ClusterObjects{}, fmt.Errorf("failed to list priorityclasses: %w", err)
+	}
+
+	var nodes corev1.NodeList
+	if err := config.list(ctx, reader, "nodes", &nodes); err != nil {
+		return types.ClusterObjects{}, fmt.Errorf("failed to list nodes: %w", err)
+	}
+
+	var policies networkingv1.NetworkPolicyList
+	if err := config.list(ctx, reader, "networkpolicies", &policies); err != nil {
+		return types.ClusterObjects{}, fmt.Errorf("failed to list networkpolicies: %w", err)
+	}
+
+	var slices discoveryv1.EndpointSliceList
+	if err := config.list(ctx, reader, "endpointslices", &slices); err != nil {
+		return types.ClusterObjects{}, fmt.Errorf("failed to list endpointslices: %w", err)
+	}
+
+	return types.ClusterObjects{
+		Namespaces:      namespacesFromMetadata(namespaces.Items),
+		Services:        services.Items,
+		Pods:            pods.Items,
+		HTTPRoutes:      versions.HTTPRoutes(routes),
+		PriorityClasses: priorityClasses.Items,
+		Nodes:           nodes.Items,
+		NetworkPolicies: policies.Items,
+		Gateways:        versions.Gateways(gateways),
+		GatewayClasses:  versions.GatewayClasses(gatewayClasses),
+		EndpointSlices:  slices.Items,
+	}, nil
+}
+
+// namespaceMetadataList returns an empty list of namespace metadata. Only the labels, annotations and phase
+// of a namespace are read, so namespaces are listed and watched as metadata rather than whole objects.
+func namespaceMetadataList() *metav1.PartialObjectMetadataList {
+	list := &metav1.PartialObjectMetadataList{}
+	list.SetGroupVersionKind(corev1.SchemeGroupVersion.WithKind("NamespaceList"))
+	return list
+}
+
+// namespacesFromMetadata restores the phase the apiserver would report: Terminating once a namespace is
+// deleted and Active before
+func namespacesFromMetadata(items []metav1.PartialObjectMetadata) []corev1.Namespace {
+	var namespaces []corev1.Namespace
+	for _, item := range items {
+		phase := corev1.NamespaceActive
+		if item.DeletionTimestamp != nil {
+			phase = corev1.NamespaceTerminating
+		}
+		namespaces = append(namespaces, corev1.Namespace{
+			ObjectMeta: item.ObjectMeta,
+			Status:     corev1.NamespaceStatus{Phase: phase},
+		})
+	}
+	return namespaces
+}
+
+// list reads every object of resource into objects, following continue tokens when a page size is set.
+// A resource whose watch is disabled is left empty.
+func (c listConfig) list(ctx context.Context, reader client.Reader, resource string, objects client.ObjectList) error {
+	if c.toggles == nil {
+		return c.scrubbed(ctx, reader, objects)
+	}
+	return c.toggles.IfEnabled(resource, func() error {
+		return c.scrubbed(ctx, reader, objects)
+	})
+}
+
+func (c listConfig) scrubbed(ctx context.Context, reader client.Reader, objects client.ObjectList) error {
+	if err := c.page(ctx, reader, objects); err != nil {
+		return err
+	}
+	if c.scrubber == nil {
+		return nil
+	}
+	return meta.EachListItem(objects, func(object runtime.Object) error {
+		_, err := c.scrubber.Transform(object)
+		return err
+	})
+}
+
+func (c listConfig) page(ctx context.Context, reader client.Reader, objects client.ObjectList) error {
+	if c.pageSize <= 0 {
+		return reader.List(ctx, objects)
+	}
+
+	var items []runtime.Object
+	opts := &client.ListOptions{Limit: c.pageSize}
+	for {
+		if err := reader.List(ctx, objects, opts); err != nil {
+			return err
+		}
+		page, err := meta.ExtractList(objects)
+		if err != nil {
+			return err
+		}
+		items = append(items, page...)
+		if objects.GetContinue() == "" {
+			break
+		}
+		opts.Continue = objects.GetContinue()
+	}
+	return meta.SetList(objects, items)
+}
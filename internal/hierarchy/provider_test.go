@@ -0,0 +1,214 @@
+package hierarchy_test
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"maps"
+	"reflect"
+	"strconv"
+	"testing"
+
+	corev1 "k8s.io/api/core/v1"
+	"k8s.io/apimachinery/pkg/api/meta"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"k8s.io/apimachinery/pkg/runtime"
+	utilruntime "k8s.io/apimachinery/pkg/util/runtime"
+	clientgoscheme "k8s.io/client-go/kubernetes/scheme"
+	"k8s.io/utils/ptr"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	"sigs.k8s.io/controller-runtime/pkg/client/fake"
+	gatewayv1beta1 "sigs.k8s.io/gateway-api/apis/v1beta1"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/scrub"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// pagedReader serves lists in pages of the requested limit, as the apiserver does
+type pagedReader struct {
+	client.Reader
+	podLists int
+}
+
+func (r *pagedReader) List(ctx context.Context, list client.ObjectList, opts ...client.ListOption) error {
+	options := client.ListOptions{}
+	options.ApplyOptions(opts)
+	if err := r.Reader.List(ctx, list); err != nil {
+		return err
+	}
+	if _, isPods := list.(*corev1.PodList); isPods {
+		r.podLists++
+	}
+	if options.Limit == 0 {
+		return nil
+	}
+
+	items, err := meta.ExtractList(list)
+	if err != nil {
+		return err
+	}
+	start := 0
+	if options.Continue != "" {
+		start, _ = strconv.Atoi(options.Continue)
+	}
+	end := min(start+int(options.Limit), len(items))
+	list.SetContinue("")
+	if end < len(items) {
+		list.SetContinue(strconv.Itoa(end))
+	}
+	return meta.SetList(list, items[start:end])
+}
+
+func TestListObjects_PageSize(t *testing.T) {
+	tests := []struct {
+		name         string
+		opts         []hierarchy.ListOpt
+		wantPods     int
+		wantPodLists int
+	}{
+		{
+			name:         "single list without a page size",
+			wantPods:     5,
+			wantPodLists: 1,
+		},
+		{
+			name:         "pages are followed to the end",
+			opts:         []hierarchy.ListOpt{hierarchy.WithPageSize(2)},
+			wantPods:     5,
+			wantPodLists: 3,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			scheme := runtime.NewScheme()
+			utilruntime.Must(clientgoscheme.AddToScheme(scheme))
+			utilruntime.Must(gatewayv1beta1.Install(scheme))
+
+			var objects []client.Object
+			for i := range 5 {
+				objects = append(objects, &corev1.Pod{ObjectMeta: metav1.ObjectMeta{Name: "web-" + strconv.Itoa(i), Namespace: "default"}})
+			}
+			reader := &pagedReader{Reader: fake.NewClientBuilder().WithScheme(scheme).WithObjects(objects...).Build()}
+
+			got, err := hierarchy.ListObjects(context.Background(), reader, tt.opts...)
+			if err != nil {
+				t.Fatalf("TestListObjects_PageSize() error = %v", err)
+			}
+			if len(got.Pods) != tt.wantPods {
+				t.Errorf("TestListObjects_PageSize() pods = %d, want %d", len(got.Pods), tt.wantPods)
+			}
+			if reader.podLists != tt.wantPodLists {
+				t.Errorf("TestListObjects_PageSize() pod lists = %d, want %d", reader.podLists, tt.wantPodLists)
+			}
+		})
+	}
+}
+
+func TestListObjects_NamespacePhase(t *testing.T) {
+	tests := []struct {
+		name      string
+		namespace *corev1.Namespace
+		want      corev1.NamespacePhase
+	}{
+		{
+			name:      "namespace is active",
+			namespace: &corev1.Namespace{ObjectMeta: metav1.ObjectMeta{Name: "shop"}},
+			want:      corev1.NamespaceActive,
+		},
+		{
+			name: "deleted namespace is terminating",
+			namespace: &corev1.Namespace{ObjectMeta: metav1.ObjectMeta{
+				Name:              "shop",
+				DeletionTimestamp: ptr.To(metav1.Now()),
+				Finalizers:        []string{"kubernetes"},
+			}},
+			want: corev1.NamespaceTerminating,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			scheme := runtime.NewScheme()
+			utilruntime.Must(clientgoscheme.AddToScheme(scheme))
+			utilruntime.Must(gatewayv1beta1.Install(scheme))
+			reader := fake.NewClientBuilder().WithScheme(scheme).WithObjects(tt.namespace).Build()
+
+			got, err := hierarchy.ListObjects(context.Background(), reader)
+			if err != nil {
+				t.Fatalf("TestListObjects_NamespacePhase() error = %v", err)
+			}
+			if len(got.Namespaces) != 1 {
+				t.Fatalf("TestListObjects_NamespacePhase() namespaces = %d, want 1", len(got.Namespaces))
+			}
+			if got.Namespaces[0].Name != "shop" || got.Namespaces[0].Status.Phase != tt.want {
+				t.Errorf("TestListObjects_NamespacePhase() = %s in phase %s, want shop in phase %s",
+					got.Namespaces[0].Name, got.Namespaces[0].Status.Phase, tt.want)
+			}
+		})
+	}
+}
+
+type noHealth struct{}
+
+func (noHealth) GetAllHealthData() []*types.ServiceHealthInfo { return nil }
+
+func TestProvider_Snapshot_ScrubbedAnnotations(t *testing.T) {
+	annotations := map[string]string{
+		corev1.LastAppliedConfigAnnotation: "{}",
+		"example.com/token":                "s3cret",
+		"owner":                            "payments",
+	}
+
+	tests := []struct {
+		name     string
+		opts     []hierarchy.ProviderOpt
+		want     map[string]string
+		scrubbed []string
+	}{
+		{
+			name: "configured keys never reach a snapshot",
+			opts: []hierarchy.ProviderOpt{
+				hierarchy.WithListOptions(hierarchy.WithScrubber(scrub.New("example.com/token"))),
+				hierarchy.WithBuildOptions(hierarchy.WithScrubbedAnnotations("example.com/token")),
+			},
+			want:     map[string]string{"owner": "payments"},
+			scrubbed: []string{"s3cret", corev1.LastAppliedConfigAnnotation},
+		},
+		{
+			name: "last applied configuration is left out of nodes by default",
+			want: map[string]string{"example.com/token": "s3cret", "owner": "payments"},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			scheme := runtime.NewScheme()
+			utilruntime.Must(clientgoscheme.AddToScheme(scheme))
+			utilruntime.Must(gatewayv1beta1.Install(scheme))
+			reader := fake.NewClientBuilder().WithScheme(scheme).WithObjects(
+				&corev1.Namespace{ObjectMeta: metav1.ObjectMeta{Name: "shop", Annotations: maps.Clone(annotations)}},
+			).Build()
+
+			snapshot, err := hierarchy.NewProvider(reader, noHealth{}, tt.opts...).Snapshot(context.Background())
+			if err != nil {
+				t.Fatalf("TestProvider_Snapshot_ScrubbedAnnotations() error = %v", err)
+			}
+			namespace := hierarchy.FindNode(snapshot.Hierarchy, "Namespace/shop")
+			if namespace == nil {
+				t.Fatal("TestProvider_Snapshot_ScrubbedAnnotations() namespace not found")
+			}
+			if !reflect.DeepEqual(namespace.Annotations, tt.want) {
+				t.Errorf("TestProvider_Snapshot_ScrubbedAnnotations() node annotations = %v, want %v",
+					namespace.Annotations, tt.want)
+			}
+			payload, err := json.Marshal(snapshot)
+			if err != nil {
+				t.Fatalf("TestProvider_Snapshot_ScrubbedAnnotations() error = %v", err)
+			}
+			for _, leaked := range tt.scrubbed {
+				if bytes.Contains(payload, []byte(leaked)) {
+					t.Errorf("TestProvider_Snapshot_ScrubbedAnnotations() snapshot contains %q", leaked)
+				}
+			}
+		})
+	}
+}
@@ -0,0 +1,131 @@
+package hierarchy
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+
+	"k8s.io/utils/ptr"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// appLabels are the labels an application name is read from, in order of preference
+var appLabels = []string{"app.kubernetes.io/name", "app"}
+
+// Reroot regroups a namespace rooted hierarchy under another kind of root, reusing the built subtrees.
+// Resources that have no place under the new root are left out: routes without a parent Gateway,
+// resources without an app label and pods not yet scheduled to a node.
+func Reroot(nodes []types.HierarchyNode, root types.HierarchyRoot) ([]types.HierarchyNode, error) {
+	switch root {
+	case "", types.HierarchyRootNamespace:
+		return nodes, nil
+	case types.HierarchyRootGateway:
+		return byGateway(nodes), nil
+	case types.HierarchyRootApp:
+		return byApp(nodes), nil
+	case types.HierarchyRootNode:
+		return byNode(nodes), nil
+	}
+	return nil, fmt.Errorf("unknown hierarchy root %q, expected namespace, gateway, app or node", root)
+}
+
+// byGateway places every route under each Gateway it attaches to: Gateway → HTTPRoute → Service → Pod
+func byGateway(nodes []types.HierarchyNode) []types.HierarchyNode {
+	roots := make(map[string]types.HierarchyNode)
+	Walk(nodes, func(node *types.HierarchyNode) {
+		if node.Kind != types.ResourceKindHTTPRoute {
+			return
+		}
+		for _, gateway := range node.Gateways {
+			addToRoot(roots, types.ResourceKindGateway, gateway, *node)
+		}
+	})
+	return sortedRoots(roots)
+}
+
+// byApp groups the top level resources of each namespace by their app label. A route without one
+// takes the app of the first Service behind it.
+func byApp(nodes []types.HierarchyNode) []types.HierarchyNode {
+	roots := make(map[string]types.HierarchyNode)
+	for _, namespace := range nodes {
+		for _, relative := range namespace.Relatives {
+			app := appName(relative)
+			if app == "" {
+				continue
+			}
+			addToRoot(roots, types.ResourceKindApplication, key(namespace.Name, app), relative)
+		}
+	}
+	return sortedRoots(roots)
+}
+
+// byNode places every scheduled pod under the node it runs on, once even when several Services select it
+func byNode(nodes []types.HierarchyNode) []types.HierarchyNode {
+	roots := make(map[string]types.HierarchyNode)
+	seen := make(map[string]bool)
+	Walk(nodes, func(node *types.HierarchyNode) {
+		if node.Kind != types.ResourceKindPod || node.NodeName == "" {
+			return
+		}
+		podKey := key(ptr.Deref(node.Namespace, ""), node.Name)
+		if seen[podKey] {
+			return
+		}
+		seen[podKey] = true
+		addToRoot(roots, types.ResourceKindNode, node.NodeName, *node)
+	})
+	return sortedRoots(roots)
+}
+
+// addToRoot appends node to the root for rootKey, a namespace/name key or a bare cluster scoped name
+func addToRoot(roots map[string]types.HierarchyNode, kind types.ResourceKind, rootKey string, node types.HierarchyNode) {
+	root, exists := roots[rootKey]
+	if !exists {
+		root = types.HierarchyNode{Kind: kind, Name: rootKey}
+		if namespace, name, namespaced := strings.Cut(rootKey, "/"); namespaced {
+			root.Namespace = ptr.To(namespace)
+			root.Name = name
+		}
+	}
+	root.Relatives = append(root.Relatives, node)
+	roots[rootKey] = root
+}
+
+func sortedRoots(roots map[string]types.HierarchyNode) []types.HierarchyNode {
+	keys := make([]string, 0, len(roots))
+	for rootKey := range roots {
+		keys = append(keys, rootKey)
+	}
+	sort.Strings(keys)
+
+	result := make([]types.HierarchyNode, 0, len(keys))
+	for _, rootKey := range keys {
+		result = append(result, roots[rootKey])
+	}
+	return withIDs(result, "")
+}
+
+// appName reads the app from a resource's labels, then from a Service's selector
+func appName(node types.HierarchyNode) string {
+	for _, label := range appLabels {
+		if value := node.Labels[label]; value != "" {
+			return value
+		}
+		if value := node.Selectors[label]; value != "" {
+			return value
+		}
+	}
+	if node.Kind != types.ResourceKindHTTPRoute {
+		return ""
+	}
+
+	var app string
+	Walk(node.Relatives, func(relative *types.HierarchyNode) {
+		if app != "" || relative.Kind != types.ResourceKindService {
+			return
+		}
+		app = appName(*relative)
+	})
+	return app
+}
@@ -0,0 +1,134 @@
+package hierarchy
+
+import (
+	"fmt"
+
+	"k8s.io/utils/ptr"
+	"sigs.k8s.io/gateway-api/apis/v1beta1"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// defaultBackendWeight is the weight Gateway API assigns to backendRefs that do not set one
+const defaultBackendWeight = 1
+
+// routeRules extracts each rule's matches and weighted backends so traffic splits are visible
+func routeRules(route v1beta1.HTTPRoute) []types.RouteRule {
+	var rules []types.RouteRule
+	for _, rule := range route.Spec.Rules {
+		var result types.RouteRule
+		for _, match := range rule.Matches {
+			result.Matches = append(result.Matches, routeMatch(match))
+		}
+		for _, ref := range rule.BackendRefs {
+			backend := types.RouteBackend{
+				Name:   string(ref.Name),
+				Weight: defaultBackendWeight,
+			}
+			if ref.Namespace != nil {
+				backend.Namespace = string(*ref.Namespace)
+			}
+			if ref.Port != nil {
+				backend.Port = (*int32)(ref.Port)
+			}
+			if ref.Weight != nil {
+				backend.Weight = *ref.Weight
+			}
+			result.Backends = append(result.Backends, backend)
+		}
+		rules = append(rules, result)
+	}
+	return rules
+}
+
+// routeMatch flattens a match; headers and query params render as name=value, or name~value for regular expressions
+func routeMatch(match v1beta1.HTTPRouteMatch) types.RouteMatch {
+	var result types.RouteMatch
+	if match.Path != nil {
+		if match.Path.Type != nil {
+			result.PathType = string(*match.Path.Type)
+		}
+		if match.Path.Value != nil {
+			result.Path = *match.Path.Value
+		}
+	}
+	if match.Method != nil {
+		result.Method = string(*match.Method)
+	}
+	for _, header := range match.Headers {
+		result.Headers = append(result.Headers, matchString(string(header.Name), header.Value, header.Type != nil && *header.Type == v1beta1.HeaderMatchRegularExpression))
+	}
+	for _, param := range match.QueryParams {
+		result.QueryParams = append(result.QueryParams, matchString(string(param.Name), param.Value, param.Type != nil && *param.Type == v1beta1.QueryParamMatchRegularExpression))
+	}
+	return result
+}
+
+func matchString(name, value string, regex bool) string {
+	if regex {
+		return name + "~" + value
+	}
+	return name + "=" + value
+}
+
+type weightedRef struct {
+	key    string
+	weight int32
+}
+
+// routeBackends returns the service nodes a route forwards to. A rule splitting traffic across several
+// Services is grouped under a synthetic TrafficSplit node whose children carry their share of traffic.
+func (b *builder) routeBackends(route v1beta1.HTTPRoute, routed map[string]bool) []types.HierarchyNode {
+	var nodes []types.HierarchyNode
+	seen := make(map[string]bool)
+	for i, rule := range route.Spec.Rules {
+		var refs []weightedRef
+		for _, ref := range serviceBackendRefs(route, rule) {
+			if _, exists := b.services[ref.key]; !exists {
+				continue
+			}
+			routed[ref.key] = true
+			refs = append(refs, ref)
+		}
+
+		if len(refs) > 1 {
+			nodes = append(nodes, b.split(route, i, refs))
+			continue
+		}
+		for _, ref := range refs {
+			if seen[ref.key] {
+				continue
+			}
+			seen[ref.key] = true
+			service := b.services[ref.key]
+			nodes = append(nodes, b.service(service, podsInNamespace(service.Namespace, b.objects.Pods)))
+		}
+	}
+	return nodes
+}
+
+// split builds the TrafficSplit node for one rule, with each backend's weight as a percentage of the rule total
+func (b *builder) split(route v1beta1.HTTPRoute, rule int, refs []weightedRef) types.HierarchyNode {
+	var total int32
+	for _, ref := range refs {
+		total += ref.weight
+	}
+
+	node := types.HierarchyNode{
+		Kind:      types.ResourceKindTrafficSplit,
+		Name:      fmt.Sprintf("%s-rule-%d", route.Name, rule),
+		Namespace: ptr.To(route.Namespace),
+	}
+	for _, ref := range refs {
+		service := b.services[ref.key]
+		backend := b.service(service, podsInNamespace(service.Namespace, b.objects.Pods))
+		backend.Weight = ptr.To(ref.weight)
+		percent := 0.0
+		if total > 0 {
+			percent = float64(ref.weight) * 100 / float64(total)
+		}
+		backend.TrafficPercent = ptr.To(percent)
+		node.Relatives = append(node.Relatives, backend)
+	}
+	return node
+}
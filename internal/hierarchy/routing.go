@@ -0,0 +1,38 @@
+package hierarchy
+
+import (
+	corev1 "k8s.io/api/core/v1"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// trafficPolicy extracts the Service settings that quietly change which endpoints receive traffic.
+// Settings left at their defaults are omitted, so nil means the Service routes like any other.
+func trafficPolicy(service corev1.Service) *types.TrafficPolicy {
+	var policy types.TrafficPolicy
+	if service.Spec.SessionAffinity == corev1.ServiceAffinityClientIP {
+		policy.SessionAffinity = string(service.Spec.SessionAffinity)
+		if config := service.Spec.SessionAffinityConfig; config != nil && config.ClientIP != nil {
+			policy.SessionAffinityTimeoutSeconds = config.ClientIP.TimeoutSeconds
+		}
+	}
+	if service.Spec.InternalTrafficPolicy != nil && *service.Spec.InternalTrafficPolicy != corev1.ServiceInternalTrafficPolicyCluster {
+		policy.InternalTrafficPolicy = string(*service.Spec.InternalTrafficPolicy)
+	}
+	if service.Spec.ExternalTrafficPolicy != "" && service.Spec.ExternalTrafficPolicy != corev1.ServiceExternalTrafficPolicyCluster {
+		policy.ExternalTrafficPolicy = string(service.Spec.ExternalTrafficPolicy)
+	}
+	if service.Spec.TrafficDistribution != nil {
+		policy.TrafficDistribution = *service.Spec.TrafficDistribution
+	}
+
+	policy.TopologyMode = service.Annotations[corev1.AnnotationTopologyMode]
+	if policy.TopologyMode == "" {
+		policy.TopologyMode = service.Annotations[corev1.DeprecatedAnnotationTopologyAwareHints]
+	}
+
+	if policy == (types.TrafficPolicy{}) {
+		return nil
+	}
+	return &policy
+}
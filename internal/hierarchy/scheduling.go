@@ -0,0 +1,151 @@
+package hierarchy
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+
+	corev1 "k8s.io/api/core/v1"
+	"k8s.io/apimachinery/pkg/labels"
+	"k8s.io/apimachinery/pkg/selection"
+)
+
+// nodeSelectorOperators maps node affinity operators onto label selector operators
+var nodeSelectorOperators = map[corev1.NodeSelectorOperator]selection.Operator{
+	corev1.NodeSelectorOpIn:           selection.In,
+	corev1.NodeSelectorOpNotIn:        selection.NotIn,
+	corev1.NodeSelectorOpExists:       selection.Exists,
+	corev1.NodeSelectorOpDoesNotExist: selection.DoesNotExist,
+	corev1.NodeSelectorOpGt:           selection.GreaterThan,
+	corev1.NodeSelectorOpLt:           selection.LessThan,
+}
+
+// unschedulableReason explains why no node can take a pending pod based on its node selector,
+// required node affinity and tolerations. Resource fit is left to the scheduler, so an empty
+// result means some node passes these checks.
+func unschedulableReason(pod corev1.Pod, nodes []corev1.Node) string {
+	if pod.Status.Phase != corev1.PodPending || pod.Spec.NodeName != "" || len(nodes) == 0 {
+		return ""
+	}
+
+	var selected []corev1.Node
+	for _, node := range nodes {
+		if len(pod.Spec.NodeSelector) > 0 && !labels.SelectorFromSet(pod.Spec.NodeSelector).Matches(labels.Set(node.Labels)) {
+			continue
+		}
+		selected = append(selected, node)
+	}
+	if len(selected) == 0 {
+		return fmt.Sprintf("no node matches node selector %s", labels.SelectorFromSet(pod.Spec.NodeSelector))
+	}
+
+	var matching []corev1.Node
+	for _, node := range selected {
+		if !affinityMatches(pod, node) {
+			continue
+		}
+		matching = append(matching, node)
+	}
+	if len(matching) == 0 {
+		return "no node matches the required node affinity"
+	}
+
+	cordoned := 0
+	taints := make(map[string]bool)
+	for _, node := range matching {
+		if node.Spec.Unschedulable {
+			cordoned++
+			continue
+		}
+		untolerated := untoleratedTaints(pod, node)
+		if len(untolerated) == 0 {
+			return ""
+		}
+		for _, taint := range untolerated {
+			taints[taint] = true
+		}
+	}
+
+	if len(taints) == 0 {
+		return fmt.Sprintf("all %d matching nodes are cordoned", cordoned)
+	}
+	names := make([]string, 0, len(taints))
+	for taint := range taints {
+		names = append(names, taint)
+	}
+	sort.Strings(names)
+
+	reason := fmt.Sprintf("untolerated taints on every matching node: %s", strings.Join(names, ", "))
+	if cordoned > 0 {
+		reason += fmt.Sprintf(" (%d cordoned)", cordoned)
+	}
+	return reason
+}
+
+// affinityMatches evaluates required node affinity: terms are ORed and their expressions ANDed
+func affinityMatches(pod corev1.Pod, node corev1.Node) bool {
+	if pod.Spec.Affinity == nil || pod.Spec.Affinity.NodeAffinity == nil {
+		return true
+	}
+	required := pod.Spec.Affinity.NodeAffinity.RequiredDuringSchedulingIgnoredDuringExecution
+	if required == nil || len(required.NodeSelectorTerms) == 0 {
+		return true
+	}
+
+	for _, term := range required.NodeSelectorTerms {
+		if termMatches(term, node) {
+			return true
+		}
+	}
+	return false
+}
+
+func termMatches(term corev1.NodeSelectorTerm, node corev1.Node) bool {
+	if len(term.MatchExpressions) == 0 && len(term.MatchFields) == 0 {
+		return false
+	}
+
+	selector := labels.NewSelector()
+	for _, expression := range term.MatchExpressions {
+		requirement, err := labels.NewRequirement(expression.Key, nodeSelectorOperators[expression.Operator], expression.Values)
+		if err != nil {
+			return false
+		}
+		selector = selector.Add(*requirement)
+	}
+	if !selector.Matches(labels.Set(node.Labels)) {
+		return false
+	}
+
+	fieldSelector := labels.NewSelector()
+	for _, field := range term.MatchFields {
+		requirement, err := labels.NewRequirement(field.Key, nodeSelectorOperators[field.Operator], field.Values)
+		if err != nil {
+			return false
+		}
+		fieldSelector = fieldSelector.Add(*requirement)
+	}
+	return fieldSelector.Matches(labels.Set{"metadata.name": node.Name})
+}
+
+// untoleratedTaints lists the node's scheduling taints that none of the pod's tolerations tolerate
+func untoleratedTaints(pod corev1.Pod, node corev1.Node) []string {
+	var untolerated []string
+	for i := range node.Spec.Taints {
+		taint := &node.Spec.Taints[i]
+		if taint.Effect == corev1.TaintEffectPreferNoSchedule {
+			continue
+		}
+		tolerated := false
+		for j := range pod.Spec.Tolerations {
+			if pod.Spec.Tolerations[j].ToleratesTaint(taint) {
+				tolerated = true
+				break
+			}
+		}
+		if !tolerated {
+			untolerated = append(untolerated, taint.ToString())
+		}
+	}
+	return untolerated
+}
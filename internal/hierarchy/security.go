@@ -0,0 +1,84 @@
+package hierarchy
+
+import (
+	corev1 "k8s.io/api/core/v1"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// securityFlags reports the risky settings of a pod. Containers without runAsUser or runAsNonRoot
+// fall back to the image user, which is usually root, so they are flagged too. The service account's
+// own automountServiceAccountToken is not read, so only an explicit false on the pod clears that flag.
+func securityFlags(pod corev1.Pod) []types.SecurityFlag {
+	podContext := pod.Spec.SecurityContext
+	if podContext == nil {
+		podContext = &corev1.PodSecurityContext{}
+	}
+
+	runsAsRoot, privileged, missingLimits := false, false, false
+	containers := append(append([]corev1.Container{}, pod.Spec.InitContainers...), pod.Spec.Containers...)
+	for _, container := range containers {
+		runAsUser, runAsNonRoot := podContext.RunAsUser, podContext.RunAsNonRoot
+		if sc := container.SecurityContext; sc != nil {
+			if sc.RunAsUser != nil {
+				runAsUser = sc.RunAsUser
+			}
+			if sc.RunAsNonRoot != nil {
+				runAsNonRoot = sc.RunAsNonRoot
+			}
+			privileged = privileged || (sc.Privileged != nil && *sc.Privileged)
+		}
+
+		switch {
+		case runAsUser != nil:
+			runsAsRoot = runsAsRoot || *runAsUser == 0
+		case runAsNonRoot == nil || !*runAsNonRoot:
+			runsAsRoot = true
+		}
+
+		limits := container.Resources.Limits
+		missingLimits = missingLimits || limits.Cpu().IsZero() || limits.Memory().IsZero()
+	}
+
+	var flags []types.SecurityFlag
+	for _, check := range []struct {
+		flag types.SecurityFlag
+		set  bool
+	}{
+		{types.SecurityFlagRunAsRoot, runsAsRoot},
+		{types.SecurityFlagPrivileged, privileged},
+		{types.SecurityFlagHostNetwork, pod.Spec.HostNetwork},
+		{types.SecurityFlagHostPID, pod.Spec.HostPID},
+		{types.SecurityFlagHostIPC, pod.Spec.HostIPC},
+		{types.SecurityFlagMissingLimits, missingLimits},
+		{types.SecurityFlagAutomountToken, pod.Spec.AutomountServiceAccountToken == nil || *pod.Spec.AutomountServiceAccountToken},
+	} {
+		if check.set {
+			flags = append(flags, check.flag)
+		}
+	}
+	return flags
+}
+
+// securitySummary counts the pods in a namespace tree carrying each security flag
+func securitySummary(relatives []types.HierarchyNode) map[types.SecurityFlag]int {
+	summary := make(map[types.SecurityFlag]int)
+	seen := make(map[string]bool)
+	Walk(relatives, func(node *types.HierarchyNode) {
+		if node.Kind != types.ResourceKindPod {
+			return
+		}
+		id := NodeID(node.Kind, node.Namespace, node.Name)
+		if seen[id] {
+			return
+		}
+		seen[id] = true
+		for _, flag := range node.SecurityFlags {
+			summary[flag]++
+		}
+	})
+	if len(summary) == 0 {
+		return nil
+	}
+	return summary
+}
@@ -0,0 +1,83 @@
+package hierarchy
+
+import (
+	"strings"
+
+	corev1 "k8s.io/api/core/v1"
+
+	"github.com/kdwils/constellation/internal/images"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// sidecar is a well-known injected container, recognised by container name or image repository
+type sidecar struct {
+	name         string
+	mesh         bool
+	containers   []string
+	repositories []string
+}
+
+var knownSidecars = []sidecar{
+	{name: "istio-proxy", mesh: true, containers: []string{"istio-proxy", "istio-init"}, repositories: []string{"istio/proxyv2"}},
+	{name: "linkerd-proxy", mesh: true, containers: []string{"linkerd-proxy", "linkerd-init"}, repositories: []string{"linkerd/proxy", "linkerd/proxy-init"}},
+	{name: "envoy", mesh: true, containers: []string{"envoy", "envoy-sidecar"}, repositories: []string{"envoyproxy/envoy"}},
+	{name: "vault-agent", containers: []string{"vault-agent", "vault-agent-init"}},
+}
+
+func (s sidecar) matches(container corev1.Container) bool {
+	for _, name := range s.containers {
+		if container.Name == name {
+			return true
+		}
+	}
+	repository := images.Parse(container.Image).Repository
+	for _, suffix := range s.repositories {
+		if repository == suffix || strings.HasSuffix(repository, "/"+suffix) {
+			return true
+		}
+	}
+	return false
+}
+
+// podSidecars lists the well-known sidecars injected into a pod, including init and native sidecars
+func podSidecars(pod corev1.Pod) []string {
+	containers := append(append([]corev1.Container{}, pod.Spec.InitContainers...), pod.Spec.Containers...)
+
+	var found []string
+	for _, s := range knownSidecars {
+		for _, container := range containers {
+			if s.matches(container) {
+				found = append(found, s.name)
+				break
+			}
+		}
+	}
+	return found
+}
+
+// meshed reports whether every pod behind a service runs a service mesh proxy
+func meshed(pods []types.HierarchyNode) bool {
+	if len(pods) == 0 {
+		return false
+	}
+	for _, pod := range pods {
+		if !hasMeshProxy(pod.Sidecars) {
+			return false
+		}
+	}
+	return true
+}
+
+func hasMeshProxy(sidecars []string) bool {
+	for _, s := range knownSidecars {
+		if !s.mesh {
+			continue
+		}
+		for _, name := range sidecars {
+			if name == s.name {
+				return true
+			}
+		}
+	}
+	return false
+}
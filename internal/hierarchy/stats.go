@@ -0,0 +1,83 @@
+package hierarchy
+
+import (
+	"sort"
+
+	"k8s.io/utils/ptr"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// Rank returns the limit largest namespaces by pod count, Services by endpoint count and routes by the
+// number of Services behind them. Ties are broken by namespace and name so rankings are stable.
+func Rank(nodes []types.HierarchyNode, limit int) types.Stats {
+	var namespaces, services, routes []types.RankedResource
+	seen := make(map[string]bool)
+	Walk(nodes, func(node *types.HierarchyNode) {
+		id := ResourceID(node)
+		if seen[id] {
+			return
+		}
+		seen[id] = true
+
+		switch node.Kind {
+		case types.ResourceKindNamespace:
+			if node.Counts != nil {
+				namespaces = append(namespaces, ranked(node, node.Counts.Pods))
+			}
+		case types.ResourceKindService:
+			count := 0
+			if node.Endpoints != nil {
+				count = node.Endpoints.Ready + node.Endpoints.NotReady
+			}
+			services = append(services, ranked(node, count))
+		case types.ResourceKindHTTPRoute:
+			routes = append(routes, ranked(node, countServices(node.Relatives)))
+		}
+	})
+
+	return types.Stats{
+		NamespacesByPods:    top(namespaces, limit),
+		ServicesByEndpoints: top(services, limit),
+		RoutesByBackends:    top(routes, limit),
+	}
+}
+
+func ranked(node *types.HierarchyNode, count int) types.RankedResource {
+	return types.RankedResource{
+		Kind:      node.Kind,
+		Namespace: ptr.Deref(node.Namespace, ""),
+		Name:      node.Name,
+		Count:     count,
+	}
+}
+
+// countServices counts the distinct Services in a route's subtree, including those behind traffic splits
+func countServices(relatives []types.HierarchyNode) int {
+	services := make(map[string]bool)
+	Walk(relatives, func(node *types.HierarchyNode) {
+		if node.Kind == types.ResourceKindService {
+			services[ResourceID(node)] = true
+		}
+	})
+	return len(services)
+}
+
+func top(resources []types.RankedResource, limit int) []types.RankedResource {
+	sort.Slice(resources, func(i, j int) bool {
+		if resources[i].Count != resources[j].Count {
+			return resources[i].Count > resources[j].Count
+		}
+		if resources[i].Namespace != resources[j].Namespace {
+			return resources[i].Namespace < resources[j].Namespace
+		}
+		return resources[i].Name < resources[j].Name
+	})
+	if len(resources) > limit {
+		resources = resources[:limit]
+	}
+	if resources == nil {
+		return []types.RankedResource{}
+	}
+	return resources
+}
@@ -0,0 +1,28 @@
+package hierarchy
+
+import (
+	"time"
+
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"k8s.io/utils/ptr"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// terminating marks a node whose object has been deleted but is still in the cache, e.g. a pod draining
+// connections or a namespace held by finalizers. A pod's deletionTimestamp is the end of its grace
+// period, so the time left until it is killed is counted down from it.
+func (b *builder) terminating(node *types.HierarchyNode, meta metav1.ObjectMeta) {
+	if meta.DeletionTimestamp == nil {
+		return
+	}
+	node.Terminating = true
+	if meta.DeletionGracePeriodSeconds == nil || *meta.DeletionGracePeriodSeconds == 0 {
+		return
+	}
+
+	deadline := *meta.DeletionTimestamp
+	node.TerminationDeadline = &deadline
+	remaining := max(deadline.Sub(b.config.now), 0)
+	node.GracePeriodSeconds = ptr.To(int64(remaining / time.Second))
+}
@@ -0,0 +1,97 @@
+package hierarchy
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+
+	corev1 "k8s.io/api/core/v1"
+	"k8s.io/utils/ptr"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// trackLabel is the track: canary / track: stable convention from the Kubernetes documentation
+const trackLabel = "track"
+
+// trackRoles are the Service name suffixes progressive delivery tools select each track with: Argo
+// Rollouts' canary and stable or blue/green active and preview Services, and Flagger's primary and canary
+var trackRoles = []string{"canary", "stable", "active", "preview", "primary"}
+
+// roleService is a Service whose name marks the track of the pods it selects
+type roleService struct {
+	role     string
+	selector map[string]string
+}
+
+// roleServices indexes the Services named for a track by namespace, in name order
+func roleServices(services map[string]corev1.Service) map[string][]roleService {
+	names := make([]string, 0, len(services))
+	for name := range services {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+
+	roles := make(map[string][]roleService)
+	for _, name := range names {
+		service := services[name]
+		for _, role := range trackRoles {
+			if !strings.HasSuffix(service.Name, "-"+role) {
+				continue
+			}
+			roles[service.Namespace] = append(roles[service.Namespace], roleService{role: role, selector: service.Spec.Selector})
+			break
+		}
+	}
+	return roles
+}
+
+// podTrack returns the delivery track of a pod from its track label, or else from the first Service
+// named for a track that selects it. Pods outside progressive delivery have no track.
+func (b *builder) podTrack(pod corev1.Pod) string {
+	if track := pod.Labels[trackLabel]; track != "" {
+		return track
+	}
+	for _, service := range b.roles[pod.Namespace] {
+		if selectorMatches(service.selector, pod.Labels) {
+			return service.role
+		}
+	}
+	return ""
+}
+
+// groupTracks puts a Service's pods under a Track node per delivery track when they span more than one,
+// e.g. the stable and canary pods behind the Service a rollout shifts traffic through. Pods without
+// a track stay direct children.
+func groupTracks(service corev1.Service, pods []types.HierarchyNode) []types.HierarchyNode {
+	byTrack := make(map[string][]types.HierarchyNode)
+	var untracked []types.HierarchyNode
+	for _, pod := range pods {
+		if pod.Track == "" {
+			untracked = append(untracked, pod)
+			continue
+		}
+		byTrack[pod.Track] = append(byTrack[pod.Track], pod)
+	}
+	if len(byTrack) < 2 {
+		return pods
+	}
+
+	tracks := make([]string, 0, len(byTrack))
+	for track := range byTrack {
+		tracks = append(tracks, track)
+	}
+	sort.Strings(tracks)
+
+	result := make([]types.HierarchyNode, 0, len(tracks)+len(untracked))
+	for _, track := range tracks {
+		result = append(result, types.HierarchyNode{
+			Kind:        types.ResourceKindTrack,
+			Name:        fmt.Sprintf("%s-%s", service.Name, track),
+			Namespace:   ptr.To(service.Namespace),
+			DisplayName: track,
+			Relatives:   byTrack[track],
+		})
+	}
+	return append(result, untracked...)
+}
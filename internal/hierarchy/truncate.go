@@ -0,0 +1,37 @@
+package hierarchy
+
+import (
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// Truncate keeps the first limit children of every root and replaces the rest with a single Truncated node
+// counting them, so a namespace flooded with pods by a runaway Job stays small enough for clients to render.
+// A limit of zero or less leaves the hierarchy unchanged.
+func Truncate(nodes []types.HierarchyNode, limit int) []types.HierarchyNode {
+	if limit <= 0 {
+		return nodes
+	}
+	result := make([]types.HierarchyNode, 0, len(nodes))
+	for _, node := range nodes {
+		result = append(result, Page(node, 0, limit))
+	}
+	return result
+}
+
+// Page returns node with its children from offset onwards, at most limit of them followed by a Truncated
+// node when more remain. The remainder is fetched by paging again from offset+limit.
+func Page(node types.HierarchyNode, offset, limit int) types.HierarchyNode {
+	children := node.Relatives[min(max(offset, 0), len(node.Relatives)):]
+	if limit <= 0 || len(children) <= limit {
+		node.Relatives = children
+		return node
+	}
+
+	node.Relatives = append(children[:limit:limit], types.HierarchyNode{
+		Kind:     types.ResourceKindTruncated,
+		ID:       NodeID(types.ResourceKindTruncated, nil, node.ID),
+		ParentID: node.ID,
+		Count:    len(children) - limit,
+	})
+	return node
+}
@@ -0,0 +1,152 @@
+// Package images builds an inventory of the container images running across pods.
+package images
+
+import (
+	"sort"
+	"strings"
+
+	corev1 "k8s.io/api/core/v1"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const (
+	defaultRegistry = "docker.io"
+	defaultTag      = "latest"
+)
+
+// Reference is a parsed image reference
+type Reference struct {
+	Registry   string
+	Repository string
+	Tag        string
+	Digest     string
+}
+
+// Parse splits an image reference into registry, repository, tag and digest using the same
+// defaults as the container runtime: docker.io for registry-less names, library/ for official
+// images, and latest when neither tag nor digest is given.
+func Parse(image string) Reference {
+	var ref Reference
+
+	name, digest, _ := strings.Cut(image, "@")
+	ref.Digest = digest
+
+	lastSlash := strings.LastIndex(name, "/")
+	if colon := strings.LastIndex(name, ":"); colon > lastSlash {
+		ref.Tag = name[colon+1:]
+		name = name[:colon]
+	}
+	if ref.Tag == "" && ref.Digest == "" {
+		ref.Tag = defaultTag
+	}
+
+	first, rest, found := strings.Cut(name, "/")
+	if found && (strings.ContainsAny(first, ".:") || first == "localhost") {
+		ref.Registry = first
+		ref.Repository = rest
+		return ref
+	}
+
+	ref.Registry = defaultRegistry
+	ref.Repository = name
+	if !found {
+		ref.Repository = "library/" + name
+	}
+	return ref
+}
+
+// Inventory groups every container and init container image by its reference as written in the
+// pod spec. Digests are the ones the kubelet reports as running, so a mutable tag resolving to
+// several digests across pods is visible.
+func Inventory(pods []corev1.Pod) []types.ImageSummary {
+	type entry struct {
+		summary    types.ImageSummary
+		pods       map[string]bool
+		namespaces map[string]bool
+		digests    map[string]bool
+	}
+	entries := make(map[string]*entry)
+
+	for _, pod := range pods {
+		running := runningDigests(pod)
+		containers := append(append([]corev1.Container{}, pod.Spec.InitContainers...), pod.Spec.Containers...)
+		for _, container := range containers {
+			e, exists := entries[container.Image]
+			if !exists {
+				ref := Parse(container.Image)
+				e = &entry{
+					summary: types.ImageSummary{
+						Image:      container.Image,
+						Registry:   ref.Registry,
+						Repository: ref.Repository,
+						Tag:        ref.Tag,
+					},
+					pods:       make(map[string]bool),
+					namespaces: make(map[string]bool),
+					digests:    make(map[string]bool),
+				}
+				if ref.Digest != "" {
+					e.digests[ref.Digest] = true
+				}
+				entries[container.Image] = e
+			}
+
+			e.pods[pod.Namespace+"/"+pod.Name] = true
+			e.namespaces[pod.Namespace] = true
+			if digest := running[container.Name]; digest != "" {
+				e.digests[digest] = true
+			}
+		}
+	}
+
+	summaries := make([]types.ImageSummary, 0, len(entries))
+	for _, e := range entries {
+		e.summary.PodCount = len(e.pods)
+		e.summary.Namespaces = sortedKeys(e.namespaces)
+		e.summary.Digests = sortedKeys(e.digests)
+		summaries = append(summaries, e.summary)
+	}
+	sort.Slice(summaries, func(i, j int) bool { return summaries[i].Image < summaries[j].Image })
+	return summaries
+}
+
+// runningDigests maps container names to the digest in their status imageID
+func runningDigests(pod corev1.Pod) map[string]string {
+	digests := make(map[string]string)
+	statuses := append(append([]corev1.ContainerStatus{}, pod.Status.InitContainerStatuses...), pod.Status.ContainerStatuses...)
+	for _, status := range statuses {
+		if _, digest, found := strings.Cut(status.ImageID, "@"); found {
+			digests[status.Name] = digest
+		}
+	}
+	return digests
+}
+
+// Filter keeps summaries matching repository and tag; an empty value matches everything.
+// Repository matches either the full repository or its last path segment, so "nginx" finds library/nginx.
+func Filter(summaries []types.ImageSummary, repository, tag string) []types.ImageSummary {
+	filtered := make([]types.ImageSummary, 0, len(summaries))
+	for _, summary := range summaries {
+		if repository != "" && summary.Repository != repository && !strings.HasSuffix(summary.Repository, "/"+repository) {
+			continue
+		}
+		if tag != "" && summary.Tag != tag {
+			continue
+		}
+		filtered = append(filtered, summary)
+	}
+	return filtered
+}
+
+func sortedKeys(set map[string]bool) []string {
+	if len(set) == 0 {
+		return nil
+	}
+	keys := make([]string, 0, len(set))
+	for key := range set {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	return keys
+}
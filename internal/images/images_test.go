@@ -0,0 +1,156 @@
+package images_test
+
+import (
+	"reflect"
+	"testing"
+
+	corev1 "k8s.io/api/core/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+
+	"github.com/kdwils/constellation/internal/images"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func TestParse(t *testing.T) {
+	tests := []struct {
+		name  string
+		image string
+		want  images.Reference
+	}{
+		{
+			name:  "official image without tag",
+			image: "nginx",
+			want:  images.Reference{Registry: "docker.io", Repository: "library/nginx", Tag: "latest"},
+		},
+		{
+			name:  "docker hub user image with tag",
+			image: "bitnami/redis:7.2",
+			want:  images.Reference{Registry: "docker.io", Repository: "bitnami/redis", Tag: "7.2"},
+		},
+		{
+			name:  "registry with port",
+			image: "registry.local:5000/team/api:v1.4.0",
+			want:  images.Reference{Registry: "registry.local:5000", Repository: "team/api", Tag: "v1.4.0"},
+		},
+		{
+			name:  "digest only",
+			image: "ghcr.io/kdwils/constellation@sha256:abc123",
+			want:  images.Reference{Registry: "ghcr.io", Repository: "kdwils/constellation", Digest: "sha256:abc123"},
+		},
+		{
+			name:  "tag and digest",
+			image: "localhost/app:dev@sha256:def456",
+			want:  images.Reference{Registry: "localhost", Repository: "app", Tag: "dev", Digest: "sha256:def456"},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := images.Parse(tt.image); got != tt.want {
+				t.Errorf("TestParse() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
+
+func pod(namespace, name, image, imageID string) corev1.Pod {
+	return corev1.Pod{
+		ObjectMeta: metav1.ObjectMeta{Namespace: namespace, Name: name},
+		Spec:       corev1.PodSpec{Containers: []corev1.Container{{Name: "app", Image: image}}},
+		Status: corev1.PodStatus{
+			ContainerStatuses: []corev1.ContainerStatus{{Name: "app", ImageID: imageID}},
+		},
+	}
+}
+
+func TestInventory(t *testing.T) {
+	tests := []struct {
+		name string
+		pods []corev1.Pod
+		want []types.ImageSummary
+	}{
+		{
+			name: "no pods",
+			pods: nil,
+			want: []types.ImageSummary{},
+		},
+		{
+			name: "mutable tag running two digests across namespaces",
+			pods: []corev1.Pod{
+				pod("shop", "web-1", "nginx:1.27", "docker.io/library/nginx@sha256:aaa"),
+				pod("blog", "web-1", "nginx:1.27", "docker.io/library/nginx@sha256:bbb"),
+				pod("shop", "api-1", "ghcr.io/example/api:v2", ""),
+			},
+			want: []types.ImageSummary{
+				{
+					Image:      "ghcr.io/example/api:v2",
+					Registry:   "ghcr.io",
+					Repository: "example/api",
+					Tag:        "v2",
+					PodCount:   1,
+					Namespaces: []string{"shop"},
+				},
+				{
+					Image:      "nginx:1.27",
+					Registry:   "docker.io",
+					Repository: "library/nginx",
+					Tag:        "1.27",
+					Digests:    []string{"sha256:aaa", "sha256:bbb"},
+					PodCount:   2,
+					Namespaces: []string{"blog", "shop"},
+				},
+			},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := images.Inventory(tt.pods); !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestInventory() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestPullFailures(t *testing.T) {
+	failing := pod("shop", "api-1", "ghcr.io/example/api", "")
+	failing.Spec.ImagePullSecrets = []corev1.LocalObjectReference{{Name: "ghcr"}}
+	failing.Status.ContainerStatuses[0].State.Waiting = &corev1.ContainerStateWaiting{Reason: "ErrImagePull", Message: "unauthorized"}
+
+	crashing := pod("shop", "web-1", "nginx:1.27", "")
+	crashing.Status.ContainerStatuses[0].State.Waiting = &corev1.ContainerStateWaiting{Reason: "CrashLoopBackOff"}
+
+	tests := []struct {
+		name string
+		pods []corev1.Pod
+		want types.ImagePullSummary
+	}{
+		{
+			name: "no failures",
+			pods: []corev1.Pod{crashing},
+			want: types.ImagePullSummary{Reasons: map[string]int{}, Failures: []types.ImagePullFailure{}},
+		},
+		{
+			name: "pull failure with pull secrets",
+			pods: []corev1.Pod{failing, crashing},
+			want: types.ImagePullSummary{
+				Pods:    1,
+				Reasons: map[string]int{"ErrImagePull": 1},
+				Failures: []types.ImagePullFailure{{
+					Namespace:      "shop",
+					Pod:            "api-1",
+					Container:      "app",
+					Image:          "ghcr.io/example/api",
+					Reason:         "ErrImagePull",
+					Message:        "unauthorized",
+					HasPullSecrets: true,
+				}},
+			},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := images.PullFailures(tt.pods); !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestPullFailures() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
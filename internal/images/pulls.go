@@ -0,0 +1,101 @@
+package images
+
+import (
+	"sort"
+
+	corev1 "k8s.io/api/core/v1"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// pullFailureReasons are the container waiting reasons the kubelet reports when an image cannot be pulled
+var pullFailureReasons = map[string]bool{
+	"ErrImagePull":      true,
+	"ImagePullBackOff":  true,
+	"InvalidImageName":  true,
+	"ErrImageNeverPull": true,
+}
+
+// PullPolicy returns the container's pull policy, applying the apiserver default when it is unset:
+// Always for latest or untagged images, IfNotPresent otherwise
+func PullPolicy(container corev1.Container) corev1.PullPolicy {
+	if container.ImagePullPolicy != "" {
+		return container.ImagePullPolicy
+	}
+	if Parse(container.Image).Tag == defaultTag {
+		return corev1.PullAlways
+	}
+	return corev1.PullIfNotPresent
+}
+
+// ContainerPulls describes how each container and init container of a pod pulls its image, including
+// any pull failure the kubelet is currently reporting
+func ContainerPulls(pod corev1.Pod) []types.ContainerImagePull {
+	waiting := make(map[string]*corev1.ContainerStateWaiting)
+	statuses := append(append([]corev1.ContainerStatus{}, pod.Status.InitContainerStatuses...), pod.Status.ContainerStatuses...)
+	for _, status := range statuses {
+		if status.State.Waiting != nil && pullFailureReasons[status.State.Waiting.Reason] {
+			waiting[status.Name] = status.State.Waiting
+		}
+	}
+
+	var pulls []types.ContainerImagePull
+	containers := append(append([]corev1.Container{}, pod.Spec.InitContainers...), pod.Spec.Containers...)
+	for _, container := range containers {
+		if container.Image == "" {
+			continue
+		}
+		pull := types.ContainerImagePull{
+			Container:  container.Name,
+			Image:      container.Image,
+			PullPolicy: string(PullPolicy(container)),
+		}
+		if state, failing := waiting[container.Name]; failing {
+			pull.Reason = state.Reason
+			pull.Message = state.Message
+		}
+		pulls = append(pulls, pull)
+	}
+	return pulls
+}
+
+// PullFailures summarizes every container across the cluster that is failing to pull its image
+func PullFailures(pods []corev1.Pod) types.ImagePullSummary {
+	summary := types.ImagePullSummary{
+		Reasons:  make(map[string]int),
+		Failures: make([]types.ImagePullFailure, 0),
+	}
+
+	failingPods := make(map[string]bool)
+	for _, pod := range pods {
+		for _, pull := range ContainerPulls(pod) {
+			if pull.Reason == "" {
+				continue
+			}
+			failingPods[pod.Namespace+"/"+pod.Name] = true
+			summary.Reasons[pull.Reason]++
+			summary.Failures = append(summary.Failures, types.ImagePullFailure{
+				Namespace:      pod.Namespace,
+				Pod:            pod.Name,
+				Container:      pull.Container,
+				Image:          pull.Image,
+				Reason:         pull.Reason,
+				Message:        pull.Message,
+				HasPullSecrets: len(pod.Spec.ImagePullSecrets) > 0,
+			})
+		}
+	}
+	summary.Pods = len(failingPods)
+
+	sort.Slice(summary.Failures, func(i, j int) bool {
+		a, b := summary.Failures[i], summary.Failures[j]
+		if a.Namespace != b.Namespace {
+			return a.Namespace < b.Namespace
+		}
+		if a.Pod != b.Pod {
+			return a.Pod < b.Pod
+		}
+		return a.Container < b.Container
+	})
+	return summary
+}
@@ -0,0 +1,84 @@
+// Package listen opens the listener the server is bound to when it should not open a TCP port: a Unix
+// domain socket, or a socket passed down by systemd socket activation.
+package listen
+
+import (
+	"errors"
+	"fmt"
+	"net"
+	"os"
+	"slices"
+	"strconv"
+	"strings"
+)
+
+const (
+	unixScheme = "unix://"
+	systemd    = "systemd"
+	// firstFD is the first descriptor systemd passes, after stdin, stdout and stderr
+	firstFD = 3
+)
+
+// Open returns the listener for address: unix:///path/to.sock, or systemd to take the socket systemd
+// passed, optionally as systemd:name to pick one by its FileDescriptorName. An empty address returns
+// nil, leaving the server to bind its port.
+func Open(address string) (net.Listener, error) {
+	if address == "" {
+		return nil, nil
+	}
+	if path, found := strings.CutPrefix(address, unixScheme); found {
+		return Unix(path)
+	}
+	if address == systemd {
+		return Systemd("")
+	}
+	if name, found := strings.CutPrefix(address, systemd+":"); found {
+		return Systemd(name)
+	}
+	return nil, fmt.Errorf("unsupported listen address %q: want unix:///path/to.sock or systemd", address)
+}
+
+// Unix listens on a Unix domain socket at path. A socket left behind by a run that did not shut down
+// cleanly is replaced; any other file at path is an error.
+func Unix(path string) (net.Listener, error) {
+	if path == "" {
+		return nil, errors.New("unix socket path is empty")
+	}
+	info, err := os.Lstat(path)
+	if err == nil && info.Mode()&os.ModeSocket != 0 {
+		if err := os.Remove(path); err != nil {
+			return nil, err
+		}
+	}
+	return net.Listen("unix", path)
+}
+
+// Systemd returns a socket systemd passed to this process per sd_listen_fds(3): the first one, or the one
+// named name. The LISTEN_* variables are cleared so they are not inherited by child processes.
+func Systemd(name string) (net.Listener, error) {
+	pid, err := strconv.Atoi(os.Getenv("LISTEN_PID"))
+	if err != nil || pid != os.Getpid() {
+		return nil, errors.New("systemd passed no sockets: LISTEN_PID is not this process")
+	}
+	count, err := strconv.Atoi(os.Getenv("LISTEN_FDS"))
+	if err != nil || count < 1 {
+		return nil, errors.New("systemd passed no sockets: LISTEN_FDS is not a positive count")
+	}
+	names := strings.Split(os.Getenv("LISTEN_FDNAMES"), ":")
+	for _, variable := range []string{"LISTEN_PID", "LISTEN_FDS", "LISTEN_FDNAMES"} {
+		os.Unsetenv(variable) //nolint:errcheck
+	}
+
+	index := 0
+	if name != "" {
+		index = slices.Index(names[:min(count, len(names))], name)
+	}
+	if index < 0 {
+		return nil, fmt.Errorf("systemd passed no socket named %q", name)
+	}
+
+	// FileListener duplicates the descriptor, so the original is closed once the listener holds its copy
+	file := os.NewFile(uintptr(firstFD+index), systemd)
+	defer file.Close() //nolint:errcheck
+	return net.FileListener(file)
+}
@@ -0,0 +1,155 @@
+package listen_test
+
+import (
+	"net"
+	"os"
+	"path/filepath"
+	"strconv"
+	"testing"
+
+	"github.com/kdwils/constellation/internal/listen"
+)
+
+func TestOpen(t *testing.T) {
+	tests := []struct {
+		name         string
+		address      string
+		wantListener bool
+		wantErr      string
+	}{
+		{name: "empty address binds the port", address: ""},
+		{name: "unix socket", address: "unix://" + filepath.Join(t.TempDir(), "constellation.sock"), wantListener: true},
+		{
+			name:    "tcp is not a listen address",
+			address: "tcp://127.0.0.1:8080",
+			wantErr: `unsupported listen address "tcp://127.0.0.1:8080": want unix:///path/to.sock or systemd`,
+		},
+		{name: "empty unix path", address: "unix://", wantErr: "unix socket path is empty"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			listener, err := listen.Open(tt.address)
+			if tt.wantErr != "" {
+				if err == nil || err.Error() != tt.wantErr {
+					t.Fatalf("TestOpen() error = %v, want %v", err, tt.wantErr)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("TestOpen() error = %v", err)
+			}
+			if got := listener != nil; got != tt.wantListener {
+				t.Fatalf("TestOpen() listener = %v, want listener %v", listener, tt.wantListener)
+			}
+			if listener != nil {
+				listener.Close() //nolint:errcheck
+			}
+		})
+	}
+}
+
+func TestUnix(t *testing.T) {
+	tests := []struct {
+		name    string
+		setup   func(t *testing.T, path string)
+		wantErr bool
+	}{
+		{name: "fresh path", setup: func(*testing.T, string) {}},
+		{
+			name: "stale socket is replaced",
+			setup: func(t *testing.T, path string) {
+				stale, err := net.ListenUnix("unix", &net.UnixAddr{Name: path, Net: "unix"})
+				if err != nil {
+					t.Fatalf("TestUnix() stale listen error = %v", err)
+				}
+				stale.SetUnlinkOnClose(false)
+				stale.Close() //nolint:errcheck
+			},
+		},
+		{
+			name: "regular file is kept",
+			setup: func(t *testing.T, path string) {
+				if err := os.WriteFile(path, []byte("data"), 0o644); err != nil {
+					t.Fatalf("TestUnix() write error = %v", err)
+				}
+			},
+			wantErr: true,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			path := filepath.Join(t.TempDir(), "constellation.sock")
+			tt.setup(t, path)
+
+			listener, err := listen.Unix(path)
+			if tt.wantErr {
+				want := "listen unix " + path + ": bind: address already in use"
+				if err == nil || err.Error() != want {
+					t.Fatalf("TestUnix() error = %v, want %v", err, want)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("TestUnix() error = %v", err)
+			}
+			defer listener.Close() //nolint:errcheck
+
+			conn, err := net.Dial("unix", path)
+			if err != nil {
+				t.Fatalf("TestUnix() dial error = %v", err)
+			}
+			conn.Close() //nolint:errcheck
+			if got := listener.Addr().String(); got != path {
+				t.Errorf("TestUnix() addr = %v, want %v", got, path)
+			}
+		})
+	}
+}
+
+func TestSystemd(t *testing.T) {
+	pid := strconv.Itoa(os.Getpid())
+
+	tests := []struct {
+		name    string
+		env     map[string]string
+		socket  string
+		wantErr string
+	}{
+		{
+			name:    "not activated",
+			env:     map[string]string{"LISTEN_PID": "", "LISTEN_FDS": ""},
+			wantErr: "systemd passed no sockets: LISTEN_PID is not this process",
+		},
+		{
+			name:    "sockets passed to another process",
+			env:     map[string]string{"LISTEN_PID": "1", "LISTEN_FDS": "1"},
+			wantErr: "systemd passed no sockets: LISTEN_PID is not this process",
+		},
+		{
+			name:    "no sockets",
+			env:     map[string]string{"LISTEN_PID": pid, "LISTEN_FDS": "0"},
+			wantErr: "systemd passed no sockets: LISTEN_FDS is not a positive count",
+		},
+		{
+			name:    "no socket with the name",
+			env:     map[string]string{"LISTEN_PID": pid, "LISTEN_FDS": "1", "LISTEN_FDNAMES": "metrics"},
+			socket:  "http",
+			wantErr: `systemd passed no socket named "http"`,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			for key, value := range tt.env {
+				t.Setenv(key, value)
+			}
+
+			_, err := listen.Systemd(tt.socket)
+			if err == nil || err.Error() != tt.wantErr {
+				t.Fatalf("TestSystemd() error = %v, want %v", err, tt.wantErr)
+			}
+			if got := os.Getenv("LISTEN_FDS"); tt.env["LISTEN_PID"] == pid && got != "" {
+				t.Errorf("TestSystemd() LISTEN_FDS = %q, want it cleared", got)
+			}
+		})
+	}
+}
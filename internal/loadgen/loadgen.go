@@ -0,0 +1,236 @@
+// Package loadgen runs the discovery pipeline against a synthetic cluster that churns at a fixed rate,
+// so the API can be sized for a big cluster before deploying to one. Pods are written to an in-memory
+// client and reconciled by the real reconcilers, checked by the real health checker and built into the
+// hierarchy by the real provider; only the apiserver and the checked services are faked.
+package loadgen
+
+import (
+	"context"
+	"fmt"
+	"math/rand/v2"
+	"net/http"
+	"strconv"
+	"strings"
+	"sync/atomic"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	"k8s.io/apimachinery/pkg/runtime"
+	utilruntime "k8s.io/apimachinery/pkg/util/runtime"
+	clientgoscheme "k8s.io/client-go/kubernetes/scheme"
+	ctrl "sigs.k8s.io/controller-runtime"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	"sigs.k8s.io/controller-runtime/pkg/client/fake"
+	"sigs.k8s.io/controller-runtime/pkg/log"
+	gatewayv1beta1 "sigs.k8s.io/gateway-api/apis/v1beta1"
+
+	"github.com/kdwils/constellation/internal/controller"
+	"github.com/kdwils/constellation/internal/healthcheck"
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/synthetic"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const (
+	// tick is how often due events are applied, so any rate is spread evenly across each second
+	tick           = 100 * time.Millisecond
+	reportInterval = 10 * time.Second
+)
+
+// Config sizes the synthetic cluster and its churn
+type Config struct {
+	// Pods in the cluster, grouped ten to a Service and a hundred to a namespace
+	Pods int
+	// Churn is how many pod events are applied per second
+	Churn float64
+}
+
+// ParseRate reads a rate such as 50/s or 600/m as events per second
+func ParseRate(rate string) (float64, error) {
+	count, unit, found := strings.Cut(rate, "/")
+	if !found {
+		unit = "s"
+	}
+	value, err := strconv.ParseFloat(count, 64)
+	if err != nil || value < 0 {
+		return 0, fmt.Errorf("invalid rate %q: want a count per second or minute, e.g. 50/s", rate)
+	}
+
+	switch unit {
+	case "s":
+		return value, nil
+	case "m":
+		return value / 60, nil
+	}
+	return 0, fmt.Errorf("invalid rate %q: unit must be s or m", rate)
+}
+
+// Generator is a synthetic cluster fed through the pipeline. It is a health, hierarchy and snapshot
+// provider for the server.
+type Generator struct {
+	*healthcheck.HealthChecker
+	config    Config
+	client    client.Client
+	hierarchy *hierarchy.Provider
+	services  *controller.ServiceReconciler
+	pods      *controller.PodReconciler
+	rand      *rand.Rand
+
+	applied atomic.Int64
+	latency atomic.Int64
+}
+
+// New creates a Generator holding a cluster of config.Pods pods
+func New(config Config) *Generator {
+	scheme := runtime.NewScheme()
+	utilruntime.Must(clientgoscheme.AddToScheme(scheme))
+	utilruntime.Must(gatewayv1beta1.Install(scheme))
+
+	objects := synthetic.Objects(config.Pods)
+	seed := make([]client.Object, 0, len(objects.Namespaces)+len(objects.Services)+len(objects.Pods))
+	for i := range objects.Namespaces {
+		seed = append(seed, &objects.Namespaces[i])
+	}
+	for i := range objects.Services {
+		seed = append(seed, &objects.Services[i])
+	}
+	for i := range objects.Pods {
+		seed = append(seed, &objects.Pods[i])
+	}
+	c := fake.NewClientBuilder().WithScheme(scheme).WithObjects(seed...).Build()
+
+	checker := healthcheck.NewHealthChecker(healthcheck.WithHTTPClient(okClient{}))
+	return &Generator{
+		HealthChecker: checker,
+		config:        config,
+		client:        c,
+		hierarchy:     hierarchy.NewProvider(c, checker),
+		services:      &controller.ServiceReconciler{Client: c, Scheme: scheme, HealthChecker: checker},
+		pods:          &controller.PodReconciler{Client: c, Scheme: scheme, HealthChecker: checker},
+		rand:          rand.New(rand.NewPCG(uint64(config.Pods), 0)),
+	}
+}
+
+// Start reconciles every Service once, then applies config.Churn pod events per second until ctx is
+// cancelled, logging the rate achieved and the mean cost of an event every 10s
+func (g *Generator) Start(ctx context.Context) error {
+	logger := log.FromContext(ctx).WithName("loadgen")
+	go g.HealthChecker.Start(ctx)
+
+	services, err := g.Sync(ctx)
+	if err != nil {
+		return err
+	}
+	logger.Info("synthetic cluster ready", "pods", g.config.Pods, "services", services, "churn", g.config.Churn)
+
+	ticker := time.NewTicker(tick)
+	defer ticker.Stop()
+	report := time.NewTicker(reportInterval)
+	defer report.Stop()
+
+	due := 0.0
+	for {
+		select {
+		case <-ctx.Done():
+			return nil
+		case <-report.C:
+			g.report(ctx)
+		case <-ticker.C:
+			due += g.config.Churn * tick.Seconds()
+			for ; due >= 1; due-- {
+				if err := g.churn(ctx); err != nil {
+					logger.Error(err, "failed to apply event")
+				}
+			}
+		}
+	}
+}
+
+// Sync reconciles every Service once, as the controllers do when their caches first sync, and returns
+// how many there were
+func (g *Generator) Sync(ctx context.Context) (int, error) {
+	var services corev1.ServiceList
+	if err := g.client.List(ctx, &services); err != nil {
+		return 0, err
+	}
+	for _, service := range services.Items {
+		req := ctrl.Request{NamespacedName: client.ObjectKeyFromObject(&service)}
+		if _, err := g.services.Reconcile(ctx, req); err != nil {
+			return 0, err
+		}
+	}
+	return len(services.Items), nil
+}
+
+// Apply applies n pod events back to back. The events are drawn from a generator seeded by the cluster
+// size, so two Generators of the same size replay the same sequence.
+func (g *Generator) Apply(ctx context.Context, n int) error {
+	for range n {
+		if err := g.churn(ctx); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// churn restarts a random pod: its phase flips and its restart count goes up, then it is reconciled
+func (g *Generator) churn(ctx context.Context) error {
+	if g.config.Pods == 0 {
+		return nil
+	}
+	start := time.Now()
+	defer func() {
+		g.applied.Add(1)
+		g.latency.Add(int64(time.Since(start)))
+	}()
+
+	template := synthetic.Pod(g.rand.IntN(g.config.Pods))
+	var pod corev1.Pod
+	if err := g.client.Get(ctx, client.ObjectKeyFromObject(&template), &pod); err != nil {
+		return err
+	}
+
+	pod.Status.Phase = corev1.PodPending
+	if g.rand.IntN(2) == 0 {
+		pod.Status.Phase = corev1.PodRunning
+	}
+	if len(pod.Status.ContainerStatuses) == 0 {
+		pod.Status.ContainerStatuses = []corev1.ContainerStatus{{Name: pod.Spec.Containers[0].Name}}
+	}
+	pod.Status.ContainerStatuses[0].RestartCount++
+	if err := g.client.Status().Update(ctx, &pod); err != nil {
+		return err
+	}
+
+	_, err := g.pods.Reconcile(ctx, ctrl.Request{NamespacedName: client.ObjectKeyFromObject(&pod)})
+	return err
+}
+
+// report logs the events applied since the last report and their mean cost
+func (g *Generator) report(ctx context.Context) {
+	applied := g.applied.Swap(0)
+	latency := time.Duration(g.latency.Swap(0))
+	mean := time.Duration(0)
+	if applied > 0 {
+		mean = latency / time.Duration(applied)
+	}
+	log.FromContext(ctx).WithName("loadgen").Info("applied events",
+		"events", applied, "rate", float64(applied)/reportInterval.Seconds(), "mean", mean)
+}
+
+// GetHierarchy builds the hierarchy from the synthetic cluster's current state
+func (g *Generator) GetHierarchy(ctx context.Context) ([]types.HierarchyNode, error) {
+	return g.hierarchy.GetHierarchy(ctx)
+}
+
+// Snapshot captures the synthetic cluster's current state
+func (g *Generator) Snapshot(ctx context.Context) (*types.Snapshot, error) {
+	return g.hierarchy.Snapshot(ctx)
+}
+
+// okClient answers every health check as healthy, so checks cost constellation but nothing else
+type okClient struct{}
+
+func (okClient) Do(req *http.Request) (*http.Response, error) {
+	return &http.Response{StatusCode: http.StatusOK, Body: http.NoBody, Request: req}, nil
+}
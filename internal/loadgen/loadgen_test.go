@@ -0,0 +1,92 @@
+package loadgen_test
+
+import (
+	"context"
+	"reflect"
+	"testing"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/loadgen"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func TestParseRate(t *testing.T) {
+	tests := []struct {
+		name    string
+		rate    string
+		want    float64
+		wantErr string
+	}{
+		{name: "per second", rate: "50/s", want: 50},
+		{name: "per minute", rate: "600/m", want: 10},
+		{name: "bare count is per second", rate: "5", want: 5},
+		{
+			name:    "negative count",
+			rate:    "-1/s",
+			wantErr: `invalid rate "-1/s": want a count per second or minute, e.g. 50/s`,
+		},
+		{name: "unknown unit", rate: "50/h", wantErr: `invalid rate "50/h": unit must be s or m`},
+		{
+			name:    "not a number",
+			rate:    "fast/s",
+			wantErr: `invalid rate "fast/s": want a count per second or minute, e.g. 50/s`,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := loadgen.ParseRate(tt.rate)
+			if tt.wantErr != "" {
+				if err == nil || err.Error() != tt.wantErr {
+					t.Fatalf("TestParseRate() error = %v, want %v", err, tt.wantErr)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("TestParseRate() error = %v", err)
+			}
+			if got != tt.want {
+				t.Errorf("TestParseRate() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestGenerator_GetHierarchy(t *testing.T) {
+	generator := loadgen.New(loadgen.Config{Pods: 20})
+
+	nodes, err := generator.GetHierarchy(context.Background())
+	if err != nil {
+		t.Fatalf("TestGenerator_GetHierarchy() error = %v", err)
+	}
+	if len(nodes) != 1 {
+		t.Fatalf("TestGenerator_GetHierarchy() namespaces = %d, want 1", len(nodes))
+	}
+	for _, id := range []string{"Service/ns-0/svc-0", "Service/ns-0/svc-1", "Pod/ns-0/svc-1-9"} {
+		if hierarchy.FindNode(nodes, id) == nil {
+			t.Errorf("TestGenerator_GetHierarchy() missing %s", id)
+		}
+	}
+}
+
+func TestGenerator_Apply(t *testing.T) {
+	ctx := context.Background()
+	replay := func() []types.HierarchyNode {
+		generator := loadgen.New(loadgen.Config{Pods: 20})
+		if _, err := generator.Sync(ctx); err != nil {
+			t.Fatalf("TestGenerator_Apply() sync error = %v", err)
+		}
+		if err := generator.Apply(ctx, 30); err != nil {
+			t.Fatalf("TestGenerator_Apply() error = %v", err)
+		}
+		nodes, err := generator.GetHierarchy(ctx)
+		if err != nil {
+			t.Fatalf("TestGenerator_Apply() hierarchy error = %v", err)
+		}
+		return nodes
+	}
+
+	first, second := replay(), replay()
+	if !reflect.DeepEqual(first, second) {
+		t.Errorf("TestGenerator_Apply() replays differ:\n%+v\n%+v", first, second)
+	}
+}
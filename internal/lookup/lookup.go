@@ -0,0 +1,75 @@
+// Package lookup finds the cluster objects that own an IP address. IPv4 and IPv6 addresses are
+// both accepted, and addresses are compared parsed so equivalent spellings of an IPv6 address match.
+package lookup
+
+import (
+	"fmt"
+	"net/netip"
+
+	corev1 "k8s.io/api/core/v1"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// IP returns every Pod, Service and Node in objects that holds ip, in that order
+func IP(objects types.ClusterObjects, ip string) (types.IPLookup, error) {
+	addr, err := netip.ParseAddr(ip)
+	if err != nil {
+		return types.IPLookup{}, fmt.Errorf("invalid ip %q: %w", ip, err)
+	}
+	addr = addr.Unmap()
+
+	result := types.IPLookup{IP: addr.String(), Family: string(corev1.IPv6Protocol), Owners: []types.IPOwner{}}
+	if addr.Is4() {
+		result.Family = string(corev1.IPv4Protocol)
+	}
+
+	for _, pod := range objects.Pods {
+		for _, podIP := range pod.Status.PodIPs {
+			if matches(podIP.IP, addr) {
+				result.Owners = append(result.Owners, types.IPOwner{Kind: types.ResourceKindPod, Namespace: pod.Namespace, Name: pod.Name, Source: "pod_ip"})
+				break
+			}
+		}
+	}
+
+	for _, service := range objects.Services {
+		if source, found := serviceSource(service, addr); found {
+			result.Owners = append(result.Owners, types.IPOwner{Kind: types.ResourceKindService, Namespace: service.Namespace, Name: service.Name, Source: source})
+		}
+	}
+
+	for _, node := range objects.Nodes {
+		for _, address := range node.Status.Addresses {
+			if matches(address.Address, addr) {
+				result.Owners = append(result.Owners, types.IPOwner{Kind: types.ResourceKindNode, Name: node.Name, Source: string(address.Type)})
+				break
+			}
+		}
+	}
+	return result, nil
+}
+
+func serviceSource(service corev1.Service, addr netip.Addr) (string, bool) {
+	for _, clusterIP := range service.Spec.ClusterIPs {
+		if matches(clusterIP, addr) {
+			return "cluster_ip", true
+		}
+	}
+	for _, externalIP := range service.Spec.ExternalIPs {
+		if matches(externalIP, addr) {
+			return "external_ip", true
+		}
+	}
+	for _, ingress := range service.Status.LoadBalancer.Ingress {
+		if matches(ingress.IP, addr) {
+			return "load_balancer", true
+		}
+	}
+	return "", false
+}
+
+func matches(ip string, addr netip.Addr) bool {
+	parsed, err := netip.ParseAddr(ip)
+	return err == nil && parsed.Unmap() == addr
+}
@@ -0,0 +1,166 @@
+package lookup_test
+
+import (
+	"reflect"
+	"testing"
+
+	corev1 "k8s.io/api/core/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+
+	"github.com/kdwils/constellation/internal/lookup"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func objects() types.ClusterObjects {
+	return types.ClusterObjects{
+		Pods: []corev1.Pod{{
+			ObjectMeta: metav1.ObjectMeta{Name: "api-0", Namespace: "shop"},
+			Status:     corev1.PodStatus{PodIPs: []corev1.PodIP{{IP: "10.244.0.5"}, {IP: "fd00:10:244::5"}}},
+		}},
+		Services: []corev1.Service{{
+			ObjectMeta: metav1.ObjectMeta{Name: "api", Namespace: "shop"},
+			Spec:       corev1.ServiceSpec{ClusterIPs: []string{"10.96.0.10", "fd00:10:96::a"}},
+			Status: corev1.ServiceStatus{LoadBalancer: corev1.LoadBalancerStatus{
+				Ingress: []corev1.LoadBalancerIngress{{IP: "203.0.113.7"}},
+			}},
+		}},
+		Nodes: []corev1.Node{{
+			ObjectMeta: metav1.ObjectMeta{Name: "worker-1"},
+			Status: corev1.NodeStatus{Addresses: []corev1.NodeAddress{
+				{Type: corev1.NodeInternalIP, Address: "192.168.1.10"},
+				{Type: corev1.NodeInternalIP, Address: "2001:db8::10"},
+			}},
+		}},
+	}
+}
+
+func TestIP(t *testing.T) {
+	tests := []struct {
+		name    string
+		ip      string
+		want    types.IPLookup
+		wantErr bool
+	}{
+		{
+			name: "ipv4 pod",
+			ip:   "10.244.0.5",
+			want: types.IPLookup{IP: "10.244.0.5", Family: "IPv4", Owners: []types.IPOwner{
+				{Kind: types.ResourceKindPod, Namespace: "shop", Name: "api-0", Source: "pod_ip"},
+			}},
+		},
+		{
+			name: "ipv6 cluster ip in expanded form",
+			ip:   "fd00:10:96:0:0:0:0:a",
+			want: types.IPLookup{IP: "fd00:10:96::a", Family: "IPv6", Owners: []types.IPOwner{
+				{Kind: types.ResourceKindService, Namespace: "shop", Name: "api", Source: "cluster_ip"},
+			}},
+		},
+		{
+			name: "ipv4-mapped ipv6 load balancer",
+			ip:   "::ffff:203.0.113.7",
+			want: types.IPLookup{IP: "203.0.113.7", Family: "IPv4", Owners: []types.IPOwner{
+				{Kind: types.ResourceKindService, Namespace: "shop", Name: "api", Source: "load_balancer"},
+			}},
+		},
+		{
+			name: "ipv6 node address",
+			ip:   "2001:db8::10",
+			want: types.IPLookup{IP: "2001:db8::10", Family: "IPv6", Owners: []types.IPOwner{
+				{Kind: types.ResourceKindNode, Name: "worker-1", Source: "InternalIP"},
+			}},
+		},
+		{
+			name: "unknown address",
+			ip:   "10.0.0.1",
+			want: types.IPLookup{IP: "10.0.0.1", Family: "IPv4", Owners: []types.IPOwner{}},
+		},
+		{
+			name:    "invalid address",
+			ip:      "not-an-ip",
+			wantErr: true,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := lookup.IP(objects(), tt.ip)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("TestIP() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestIP() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestSelector(t *testing.T) {
+	cluster := types.ClusterObjects{
+		Pods: []corev1.Pod{
+			{ObjectMeta: metav1.ObjectMeta{Name: "api-0", Namespace: "shop", Labels: map[string]string{"app": "api"}}},
+			{ObjectMeta: metav1.ObjectMeta{Name: "api-0", Namespace: "staging", Labels: map[string]string{"app": "api"}}},
+			{ObjectMeta: metav1.ObjectMeta{Name: "web-0", Namespace: "shop", Labels: map[string]string{"app": "web"}}},
+		},
+		Services: []corev1.Service{
+			{ObjectMeta: metav1.ObjectMeta{Name: "api", Namespace: "shop", Labels: map[string]string{"app": "api"}}},
+		},
+	}
+
+	tests := []struct {
+		name    string
+		test    types.SelectorTest
+		want    types.SelectorMatches
+		wantErr bool
+	}{
+		{
+			name: "selector in one namespace",
+			test: types.SelectorTest{Namespace: "shop", Selector: "app=api"},
+			want: types.SelectorMatches{
+				Selector: "app=api",
+				Pods:     []types.SelectedObject{{Namespace: "shop", Name: "api-0", Labels: map[string]string{"app": "api"}}},
+				Services: []types.SelectedObject{{Namespace: "shop", Name: "api", Labels: map[string]string{"app": "api"}}},
+			},
+		},
+		{
+			name: "label selector across namespaces",
+			test: types.SelectorTest{LabelSelector: &metav1.LabelSelector{
+				MatchExpressions: []metav1.LabelSelectorRequirement{
+					{Key: "app", Operator: metav1.LabelSelectorOpNotIn, Values: []string{"web"}},
+				},
+			}},
+			want: types.SelectorMatches{
+				Selector: "app notin (web)",
+				Pods: []types.SelectedObject{
+					{Namespace: "shop", Name: "api-0", Labels: map[string]string{"app": "api"}},
+					{Namespace: "staging", Name: "api-0", Labels: map[string]string{"app": "api"}},
+				},
+				Services: []types.SelectedObject{{Namespace: "shop", Name: "api", Labels: map[string]string{"app": "api"}}},
+			},
+		},
+		{
+			name: "no matches",
+			test: types.SelectorTest{Selector: "app=db"},
+			want: types.SelectorMatches{Selector: "app=db", Pods: []types.SelectedObject{}, Services: []types.SelectedObject{}},
+		},
+		{
+			name:    "invalid selector",
+			test:    types.SelectorTest{Selector: "app in"},
+			wantErr: true,
+		},
+		{
+			name:    "both forms set",
+			test:    types.SelectorTest{Selector: "app=api", LabelSelector: &metav1.LabelSelector{}},
+			wantErr: true,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := lookup.Selector(cluster, tt.test)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("TestSelector() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestSelector() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,67 @@
+package lookup
+
+import (
+	"errors"
+	"fmt"
+
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"k8s.io/apimachinery/pkg/labels"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// Selector returns the Pods and Services in objects whose labels match test, for checking a selector
+// before it is written into a Service or NetworkPolicy. An empty selector matches everything, as it
+// does in a NetworkPolicy.
+func Selector(objects types.ClusterObjects, test types.SelectorTest) (types.SelectorMatches, error) {
+	selector, err := parseSelector(test)
+	if err != nil {
+		return types.SelectorMatches{}, err
+	}
+
+	result := types.SelectorMatches{
+		Selector: selector.String(),
+		Pods:     []types.SelectedObject{},
+		Services: []types.SelectedObject{},
+	}
+	for _, pod := range objects.Pods {
+		if selected(pod.ObjectMeta, test.Namespace, selector) {
+			result.Pods = append(result.Pods, types.SelectedObject{Namespace: pod.Namespace, Name: pod.Name, Labels: pod.Labels})
+		}
+	}
+	for _, service := range objects.Services {
+		if selected(service.ObjectMeta, test.Namespace, selector) {
+			result.Services = append(result.Services, types.SelectedObject{
+				Namespace: service.Namespace,
+				Name:      service.Name,
+				Labels:    service.Labels,
+			})
+		}
+	}
+	return result, nil
+}
+
+func parseSelector(test types.SelectorTest) (labels.Selector, error) {
+	if test.Selector != "" && test.LabelSelector != nil {
+		return nil, errors.New("set either selector or label_selector, not both")
+	}
+	if test.LabelSelector != nil {
+		selector, err := metav1.LabelSelectorAsSelector(test.LabelSelector)
+		if err != nil {
+			return nil, fmt.Errorf("invalid label_selector: %w", err)
+		}
+		return selector, nil
+	}
+	selector, err := labels.Parse(test.Selector)
+	if err != nil {
+		return nil, fmt.Errorf("invalid selector: %w", err)
+	}
+	return selector, nil
+}
+
+func selected(meta metav1.ObjectMeta, namespace string, selector labels.Selector) bool {
+	if namespace != "" && meta.Namespace != namespace {
+		return false
+	}
+	return selector.Matches(labels.Set(meta.Labels))
+}
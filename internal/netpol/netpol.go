@@ -0,0 +1,361 @@
+// Package netpol evaluates NetworkPolicies to answer whether traffic between two endpoints is allowed.
+// Only the policies themselves are considered; CNI specific policy extensions are not.
+package netpol
+
+import (
+	"fmt"
+	"net"
+
+	corev1 "k8s.io/api/core/v1"
+	networkingv1 "k8s.io/api/networking/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"k8s.io/apimachinery/pkg/labels"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const namespaceNameLabel = "kubernetes.io/metadata.name"
+
+// peer is one side of a connection as NetworkPolicy rules see it
+type peer struct {
+	namespace string
+	labels    map[string]string
+	ips       []string
+}
+
+// target is a destination pod and the container port traffic arrives on
+type target struct {
+	pod      corev1.Pod
+	port     int32
+	portName string
+}
+
+type evaluator struct {
+	objects    types.ClusterObjects
+	namespaces map[string]map[string]string
+	protocol   corev1.Protocol
+}
+
+// CanReach evaluates the NetworkPolicies in objects for query. A Service destination is resolved to
+// its backend pods and the query port to each pod's target port.
+func CanReach(objects types.ClusterObjects, query types.ReachQuery) (types.ReachResult, error) {
+	e := evaluator{
+		objects:    objects,
+		namespaces: make(map[string]map[string]string, len(objects.Namespaces)),
+		protocol:   corev1.ProtocolTCP,
+	}
+	if query.Protocol != "" {
+		e.protocol = corev1.Protocol(query.Protocol)
+	}
+	for _, namespace := range objects.Namespaces {
+		e.namespaces[namespace.Name] = namespace.Labels
+	}
+
+	source, err := e.source(query.From)
+	if err != nil {
+		return types.ReachResult{}, err
+	}
+	targets, err := e.targets(query.To, query.Port)
+	if err != nil {
+		return types.ReachResult{}, err
+	}
+
+	result := types.ReachResult{Targets: make([]types.TargetVerdict, 0, len(targets))}
+	for _, t := range targets {
+		destination := podPeer(t.pod)
+		verdict := types.TargetVerdict{
+			Pod:     t.pod.Name,
+			Port:    t.port,
+			Egress:  e.verdict(source, networkingv1.PolicyTypeEgress, destination, t),
+			Ingress: e.verdict(destination, networkingv1.PolicyTypeIngress, source, t),
+		}
+		verdict.Allowed = verdict.Egress.Allowed && verdict.Ingress.Allowed
+		result.Allowed = result.Allowed || verdict.Allowed
+		result.Targets = append(result.Targets, verdict)
+	}
+	return result, nil
+}
+
+// source resolves a named pod, or a namespace and selector describing a hypothetical pod
+func (e evaluator) source(endpoint types.Endpoint) (peer, error) {
+	if endpoint.Kind != "" && endpoint.Kind != types.ResourceKindPod {
+		return peer{}, fmt.Errorf("unsupported source kind %q, expected Pod or a selector", endpoint.Kind)
+	}
+	if endpoint.Name == "" {
+		return peer{namespace: endpoint.Namespace, labels: endpoint.Selector}, nil
+	}
+	pod, exists := e.pod(endpoint.Namespace, endpoint.Name)
+	if !exists {
+		return peer{}, fmt.Errorf("pod %s/%s not found", endpoint.Namespace, endpoint.Name)
+	}
+	return podPeer(pod), nil
+}
+
+func (e evaluator) targets(endpoint types.Endpoint, port int32) ([]target, error) {
+	switch endpoint.Kind {
+	case types.ResourceKindPod:
+		pod, exists := e.pod(endpoint.Namespace, endpoint.Name)
+		if !exists {
+			return nil, fmt.Errorf("pod %s/%s not found", endpoint.Namespace, endpoint.Name)
+		}
+		return []target{{pod: pod, port: port, portName: containerPortName(pod, port)}}, nil
+	case types.ResourceKindService:
+		return e.serviceTargets(endpoint.Namespace, endpoint.Name, port)
+	}
+	return nil, fmt.Errorf("unsupported destination kind %q, expected Pod or Service", endpoint.Kind)
+}
+
+func (e evaluator) serviceTargets(namespace, name string, port int32) ([]target, error) {
+	var service *corev1.Service
+	for i := range e.objects.Services {
+		if e.objects.Services[i].Namespace == namespace && e.objects.Services[i].Name == name {
+			service = &e.objects.Services[i]
+			break
+		}
+	}
+	if service == nil {
+		return nil, fmt.Errorf("service %s/%s not found", namespace, name)
+	}
+
+	var servicePort *corev1.ServicePort
+	for i := range service.Spec.Ports {
+		protocol := service.Spec.Ports[i].Protocol
+		if protocol == "" {
+			protocol = corev1.ProtocolTCP
+		}
+		if service.Spec.Ports[i].Port == port && protocol == e.protocol {
+			servicePort = &service.Spec.Ports[i]
+			break
+		}
+	}
+	if servicePort == nil {
+		return nil, fmt.Errorf("service %s/%s has no %s port %d", namespace, name, e.protocol, port)
+	}
+
+	var targets []target
+	for _, pod := range e.objects.Pods {
+		if pod.Namespace != namespace || len(service.Spec.Selector) == 0 {
+			continue
+		}
+		if !labels.SelectorFromSet(service.Spec.Selector).Matches(labels.Set(pod.Labels)) {
+			continue
+		}
+		if servicePort.TargetPort.StrVal != "" {
+			number, found := containerPortNumber(pod, servicePort.TargetPort.StrVal)
+			if !found {
+				continue
+			}
+			targets = append(targets, target{pod: pod, port: number, portName: servicePort.TargetPort.StrVal})
+			continue
+		}
+		number := servicePort.TargetPort.IntVal
+		if number == 0 {
+			number = servicePort.Port
+		}
+		targets = append(targets, target{pod: pod, port: number, portName: containerPortName(pod, number)})
+	}
+	if len(targets) == 0 {
+		return nil, fmt.Errorf("service %s/%s has no backend pods for port %d", namespace, name, port)
+	}
+	return targets, nil
+}
+
+func (e evaluator) pod(namespace, name string) (corev1.Pod, bool) {
+	for _, pod := range e.objects.Pods {
+		if pod.Namespace == namespace && pod.Name == name {
+			return pod, true
+		}
+	}
+	return corev1.Pod{}, false
+}
+
+// verdict evaluates the policies isolating subject in one direction against the remote peer.
+// An isolated pod only accepts traffic some isolating policy permits.
+func (e evaluator) verdict(subject peer, direction networkingv1.PolicyType, remote peer, t target) types.PolicyVerdict {
+	var verdict types.PolicyVerdict
+	var denied []string
+	for _, policy := range e.objects.NetworkPolicies {
+		if policy.Namespace != subject.namespace || !appliesTo(policy, direction) {
+			continue
+		}
+		if !selectorMatches(&policy.Spec.PodSelector, subject.labels) {
+			continue
+		}
+
+		verdict.Isolated = true
+		name := policy.Namespace + "/" + policy.Name
+		if e.permits(policy, direction, remote, t) {
+			verdict.AllowedBy = append(verdict.AllowedBy, name)
+			continue
+		}
+		denied = append(denied, name)
+	}
+
+	verdict.Allowed = !verdict.Isolated || len(verdict.AllowedBy) > 0
+	if !verdict.Allowed {
+		verdict.DeniedBy = denied
+	}
+	return verdict
+}
+
+// appliesTo follows the policyTypes defaulting: Ingress always, Egress only when egress rules exist
+func appliesTo(policy networkingv1.NetworkPolicy, direction networkingv1.PolicyType) bool {
+	if len(policy.Spec.PolicyTypes) == 0 {
+		return direction == networkingv1.PolicyTypeIngress || len(policy.Spec.Egress) > 0
+	}
+	for _, policyType := range policy.Spec.PolicyTypes {
+		if policyType == direction {
+			return true
+		}
+	}
+	return false
+}
+
+func (e evaluator) permits(policy networkingv1.NetworkPolicy, direction networkingv1.PolicyType, remote peer, t target) bool {
+	if direction == networkingv1.PolicyTypeIngress {
+		for _, rule := range policy.Spec.Ingress {
+			if e.peersMatch(policy.Namespace, rule.From, remote) && e.portsMatch(rule.Ports, t) {
+				return true
+			}
+		}
+		return false
+	}
+
+	for _, rule := range policy.Spec.Egress {
+		if e.peersMatch(policy.Namespace, rule.To, remote) && e.portsMatch(rule.Ports, t) {
+			return true
+		}
+	}
+	return false
+}
+
+// peersMatch reports whether remote is one of a rule's peers; a rule without peers matches everything
+func (e evaluator) peersMatch(namespace string, peers []networkingv1.NetworkPolicyPeer, remote peer) bool {
+	if len(peers) == 0 {
+		return true
+	}
+	for _, p := range peers {
+		if e.peerMatches(namespace, p, remote) {
+			return true
+		}
+	}
+	return false
+}
+
+func (e evaluator) peerMatches(namespace string, p networkingv1.NetworkPolicyPeer, remote peer) bool {
+	if p.IPBlock != nil {
+		return ipBlockMatches(*p.IPBlock, remote.ips)
+	}
+	if p.NamespaceSelector == nil && remote.namespace != namespace {
+		return false
+	}
+	if p.NamespaceSelector != nil && !selectorMatches(p.NamespaceSelector, e.namespaceLabels(remote.namespace)) {
+		return false
+	}
+	return p.PodSelector == nil || selectorMatches(p.PodSelector, remote.labels)
+}
+
+// namespaceLabels includes the name label the apiserver sets on every namespace
+func (e evaluator) namespaceLabels(namespace string) map[string]string {
+	result := map[string]string{namespaceNameLabel: namespace}
+	for key, value := range e.namespaces[namespace] {
+		result[key] = value
+	}
+	return result
+}
+
+// portsMatch reports whether a rule's ports include the target; a rule without ports matches every port
+func (e evaluator) portsMatch(ports []networkingv1.NetworkPolicyPort, t target) bool {
+	if len(ports) == 0 {
+		return true
+	}
+	for _, port := range ports {
+		protocol := corev1.ProtocolTCP
+		if port.Protocol != nil {
+			protocol = *port.Protocol
+		}
+		if protocol != e.protocol {
+			continue
+		}
+		if port.Port == nil {
+			return true
+		}
+		if port.Port.StrVal != "" {
+			if port.Port.StrVal == t.portName {
+				return true
+			}
+			continue
+		}
+		end := port.Port.IntVal
+		if port.EndPort != nil {
+			end = *port.EndPort
+		}
+		if t.port >= port.Port.IntVal && t.port <= end {
+			return true
+		}
+	}
+	return false
+}
+
+func ipBlockMatches(block networkingv1.IPBlock, ips []string) bool {
+	_, cidr, err := net.ParseCIDR(block.CIDR)
+	if err != nil {
+		return false
+	}
+	for _, value := range ips {
+		ip := net.ParseIP(value)
+		if ip == nil || !cidr.Contains(ip) || excluded(block.Except, ip) {
+			continue
+		}
+		return true
+	}
+	return false
+}
+
+func excluded(except []string, ip net.IP) bool {
+	for _, value := range except {
+		_, cidr, err := net.ParseCIDR(value)
+		if err == nil && cidr.Contains(ip) {
+			return true
+		}
+	}
+	return false
+}
+
+func selectorMatches(selector *metav1.LabelSelector, set map[string]string) bool {
+	parsed, err := metav1.LabelSelectorAsSelector(selector)
+	if err != nil {
+		return false
+	}
+	return parsed.Matches(labels.Set(set))
+}
+
+func podPeer(pod corev1.Pod) peer {
+	p := peer{namespace: pod.Namespace, labels: pod.Labels}
+	for _, ip := range pod.Status.PodIPs {
+		p.ips = append(p.ips, ip.IP)
+	}
+	return p
+}
+
+func containerPortName(pod corev1.Pod, number int32) string {
+	for _, container := range pod.Spec.Containers {
+		for _, port := range container.Ports {
+			if port.ContainerPort == number {
+				return port.Name
+			}
+		}
+	}
+	return ""
+}
+
+func containerPortNumber(pod corev1.Pod, name string) (int32, bool) {
+	for _, container := range pod.Spec.Containers {
+		for _, port := range container.Ports {
+			if port.Name == name {
+				return port.ContainerPort, true
+			}
+		}
+	}
+	return 0, false
+}
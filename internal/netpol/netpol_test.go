@@ -0,0 +1,129 @@
+package netpol_test
+
+import (
+	"reflect"
+	"testing"
+
+	corev1 "k8s.io/api/core/v1"
+	networkingv1 "k8s.io/api/networking/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"k8s.io/apimachinery/pkg/util/intstr"
+	"k8s.io/utils/ptr"
+
+	"github.com/kdwils/constellation/internal/netpol"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func objects() types.ClusterObjects {
+	return types.ClusterObjects{
+		Namespaces: []corev1.Namespace{
+			{ObjectMeta: metav1.ObjectMeta{Name: "shop"}},
+			{ObjectMeta: metav1.ObjectMeta{Name: "monitoring"}},
+		},
+		Services: []corev1.Service{{
+			ObjectMeta: metav1.ObjectMeta{Name: "payments", Namespace: "shop"},
+			Spec: corev1.ServiceSpec{
+				Selector: map[string]string{"app": "payments"},
+				Ports:    []corev1.ServicePort{{Port: 80, TargetPort: intstr.FromString("http")}},
+			},
+		}},
+		Pods: []corev1.Pod{
+			{
+				ObjectMeta: metav1.ObjectMeta{Name: "frontend-0", Namespace: "shop", Labels: map[string]string{"app": "frontend"}},
+			},
+			{
+				ObjectMeta: metav1.ObjectMeta{Name: "payments-0", Namespace: "shop", Labels: map[string]string{"app": "payments"}},
+				Spec: corev1.PodSpec{Containers: []corev1.Container{{
+					Name:  "payments",
+					Ports: []corev1.ContainerPort{{Name: "http", ContainerPort: 8080}},
+				}}},
+			},
+		},
+		NetworkPolicies: []networkingv1.NetworkPolicy{
+			{
+				ObjectMeta: metav1.ObjectMeta{Name: "default-deny", Namespace: "shop"},
+				Spec:       networkingv1.NetworkPolicySpec{PolicyTypes: []networkingv1.PolicyType{networkingv1.PolicyTypeIngress}},
+			},
+			{
+				ObjectMeta: metav1.ObjectMeta{Name: "payments-from-frontend", Namespace: "shop"},
+				Spec: networkingv1.NetworkPolicySpec{
+					PodSelector: metav1.LabelSelector{MatchLabels: map[string]string{"app": "payments"}},
+					Ingress: []networkingv1.NetworkPolicyIngressRule{{
+						From:  []networkingv1.NetworkPolicyPeer{{PodSelector: &metav1.LabelSelector{MatchLabels: map[string]string{"app": "frontend"}}}},
+						Ports: []networkingv1.NetworkPolicyPort{{Port: ptr.To(intstr.FromString("http"))}},
+					}},
+				},
+			},
+		},
+	}
+}
+
+func TestCanReach(t *testing.T) {
+	tests := []struct {
+		name    string
+		query   types.ReachQuery
+		want    types.ReachResult
+		wantErr bool
+	}{
+		{
+			name: "allowed through service named port",
+			query: types.ReachQuery{
+				From: types.Endpoint{Kind: types.ResourceKindPod, Namespace: "shop", Name: "frontend-0"},
+				To:   types.Endpoint{Kind: types.ResourceKindService, Namespace: "shop", Name: "payments"},
+				Port: 80,
+			},
+			want: types.ReachResult{
+				Allowed: true,
+				Targets: []types.TargetVerdict{{
+					Pod:     "payments-0",
+					Port:    8080,
+					Allowed: true,
+					Egress:  types.PolicyVerdict{Allowed: true},
+					Ingress: types.PolicyVerdict{Allowed: true, Isolated: true, AllowedBy: []string{"shop/payments-from-frontend"}},
+				}},
+			},
+		},
+		{
+			name: "denied from another namespace",
+			query: types.ReachQuery{
+				From: types.Endpoint{Namespace: "monitoring", Selector: map[string]string{"app": "prometheus"}},
+				To:   types.Endpoint{Kind: types.ResourceKindPod, Namespace: "shop", Name: "payments-0"},
+				Port: 8080,
+			},
+			want: types.ReachResult{
+				Allowed: false,
+				Targets: []types.TargetVerdict{{
+					Pod:     "payments-0",
+					Port:    8080,
+					Allowed: false,
+					Egress:  types.PolicyVerdict{Allowed: true},
+					Ingress: types.PolicyVerdict{
+						Allowed:  false,
+						Isolated: true,
+						DeniedBy: []string{"shop/default-deny", "shop/payments-from-frontend"},
+					},
+				}},
+			},
+		},
+		{
+			name: "unknown service port",
+			query: types.ReachQuery{
+				From: types.Endpoint{Kind: types.ResourceKindPod, Namespace: "shop", Name: "frontend-0"},
+				To:   types.Endpoint{Kind: types.ResourceKindService, Namespace: "shop", Name: "payments"},
+				Port: 443,
+			},
+			wantErr: true,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := netpol.CanReach(objects(), tt.query)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("TestCanReach() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestCanReach() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,147 @@
+// Package ratelimit bounds how hard clients can hit the dashboard API, so an aggressive refresh
+// loop cannot starve the controllers and collectors running in the same process.
+package ratelimit
+
+import (
+	"math"
+	"net"
+	"net/http"
+	"strconv"
+	"sync"
+	"time"
+
+	"golang.org/x/time/rate"
+)
+
+// idleTimeout is how long a client may go without requests before its limiter is forgotten
+const idleTimeout = 5 * time.Minute
+
+// Config controls the per-client rate limit and the global concurrency cap.
+// Zero values disable the corresponding limit.
+type Config struct {
+	// RequestsPerSecond each client IP may sustain
+	RequestsPerSecond float64
+	// Burst is how many requests a client may make at once, defaulting to RequestsPerSecond rounded up
+	Burst int
+	// MaxConcurrent caps the requests being served at once across all clients
+	MaxConcurrent int
+}
+
+type visitor struct {
+	limiter  *rate.Limiter
+	lastSeen time.Time
+}
+
+// Limiter rejects requests over a client's rate with 429 and requests over the concurrency cap with 503
+type Limiter struct {
+	config Config
+	now    func() time.Time
+
+	mu        sync.Mutex
+	visitors  map[string]*visitor
+	lastSweep time.Time
+
+	inflight chan struct{}
+}
+
+// NewLimiter creates a Limiter, filling an unset burst from the rate
+func NewLimiter(config Config, opts ...LimiterOpt) *Limiter {
+	if config.RequestsPerSecond > 0 && config.Burst == 0 {
+		config.Burst = int(math.Ceil(config.RequestsPerSecond))
+	}
+
+	l := &Limiter{
+		config:   config,
+		now:      time.Now,
+		visitors: make(map[string]*visitor),
+	}
+	if config.MaxConcurrent > 0 {
+		l.inflight = make(chan struct{}, config.MaxConcurrent)
+	}
+
+	for _, opt := range opts {
+		opt(l)
+	}
+
+	return l
+}
+
+type LimiterOpt func(*Limiter)
+
+// WithClock replaces time.Now, for tests
+func WithClock(now func() time.Time) LimiterOpt {
+	return func(l *Limiter) {
+		l.now = now
+	}
+}
+
+// Middleware applies the limits in front of next
+func (l *Limiter) Middleware(next http.Handler) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if wait := l.reserve(clientIP(r)); wait > 0 {
+			w.Header().Set("Retry-After", strconv.Itoa(int(math.Ceil(wait.Seconds()))))
+			http.Error(w, "rate limit exceeded", http.StatusTooManyRequests)
+			return
+		}
+
+		if l.inflight == nil {
+			next.ServeHTTP(w, r)
+			return
+		}
+		select {
+		case l.inflight <- struct{}{}:
+			defer func() { <-l.inflight }()
+			next.ServeHTTP(w, r)
+		default:
+			http.Error(w, "too many concurrent requests", http.StatusServiceUnavailable)
+		}
+	})
+}
+
+// reserve takes a token for ip, returning how long the client must wait when none is available
+func (l *Limiter) reserve(ip string) time.Duration {
+	if l.config.RequestsPerSecond <= 0 {
+		return 0
+	}
+
+	now := l.now()
+	l.mu.Lock()
+	defer l.mu.Unlock()
+
+	l.sweep(now)
+	v, exists := l.visitors[ip]
+	if !exists {
+		v = &visitor{limiter: rate.NewLimiter(rate.Limit(l.config.RequestsPerSecond), l.config.Burst)}
+		l.visitors[ip] = v
+	}
+	v.lastSeen = now
+
+	reservation := v.limiter.ReserveN(now, 1)
+	wait := reservation.DelayFrom(now)
+	if wait > 0 {
+		reservation.CancelAt(now)
+	}
+	return wait
+}
+
+// sweep forgets clients idle for longer than idleTimeout, at most once per idleTimeout
+func (l *Limiter) sweep(now time.Time) {
+	if now.Sub(l.lastSweep) < idleTimeout {
+		return
+	}
+	l.lastSweep = now
+	for ip, v := range l.visitors {
+		if now.Sub(v.lastSeen) > idleTimeout {
+			delete(l.visitors, ip)
+		}
+	}
+}
+
+// clientIP is the connection's remote address; forwarding headers are not trusted
+func clientIP(r *http.Request) string {
+	host, _, err := net.SplitHostPort(r.RemoteAddr)
+	if err != nil {
+		return r.RemoteAddr
+	}
+	return host
+}
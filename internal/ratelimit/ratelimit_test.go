@@ -0,0 +1,111 @@
+package ratelimit_test
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"reflect"
+	"testing"
+	"time"
+
+	"github.com/kdwils/constellation/internal/ratelimit"
+)
+
+func TestLimiter_RateLimit(t *testing.T) {
+	tests := []struct {
+		name    string
+		config  ratelimit.Config
+		clients []string
+		want    []int
+	}{
+		{
+			name:    "requests within burst are served",
+			config:  ratelimit.Config{RequestsPerSecond: 1, Burst: 2},
+			clients: []string{"10.0.0.1:1000", "10.0.0.1:1001"},
+			want:    []int{http.StatusOK, http.StatusOK},
+		},
+		{
+			name:    "requests over burst are rejected",
+			config:  ratelimit.Config{RequestsPerSecond: 1, Burst: 2},
+			clients: []string{"10.0.0.1:1000", "10.0.0.1:1001", "10.0.0.1:1002"},
+			want:    []int{http.StatusOK, http.StatusOK, http.StatusTooManyRequests},
+		},
+		{
+			name:    "clients are limited separately",
+			config:  ratelimit.Config{RequestsPerSecond: 1},
+			clients: []string{"10.0.0.1:1000", "10.0.0.2:1000", "10.0.0.1:1001"},
+			want:    []int{http.StatusOK, http.StatusOK, http.StatusTooManyRequests},
+		},
+		{
+			name:    "zero rate disables the limit",
+			config:  ratelimit.Config{},
+			clients: []string{"10.0.0.1:1000", "10.0.0.1:1001", "10.0.0.1:1002"},
+			want:    []int{http.StatusOK, http.StatusOK, http.StatusOK},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			now := time.Date(2025, 1, 1, 0, 0, 0, 0, time.UTC)
+			limiter := ratelimit.NewLimiter(tt.config, ratelimit.WithClock(func() time.Time { return now }))
+			handler := limiter.Middleware(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {}))
+
+			var got []int
+			for _, client := range tt.clients {
+				request := httptest.NewRequest(http.MethodGet, "/state", nil)
+				request.RemoteAddr = client
+				recorder := httptest.NewRecorder()
+				handler.ServeHTTP(recorder, request)
+				got = append(got, recorder.Code)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestLimiter_RateLimit() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestLimiter_MaxConcurrent(t *testing.T) {
+	tests := []struct {
+		name          string
+		maxConcurrent int
+		want          int
+	}{
+		{
+			name:          "request over the cap is rejected",
+			maxConcurrent: 1,
+			want:          http.StatusServiceUnavailable,
+		},
+		{
+			name:          "request under the cap is served",
+			maxConcurrent: 2,
+			want:          http.StatusOK,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			limiter := ratelimit.NewLimiter(ratelimit.Config{MaxConcurrent: tt.maxConcurrent})
+			started := make(chan struct{})
+			release := make(chan struct{})
+			blocking := limiter.Middleware(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+				close(started)
+				<-release
+			}))
+			handler := limiter.Middleware(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {}))
+
+			done := make(chan struct{})
+			go func() {
+				blocking.ServeHTTP(httptest.NewRecorder(), httptest.NewRequest(http.MethodGet, "/state", nil))
+				close(done)
+			}()
+			<-started
+
+			recorder := httptest.NewRecorder()
+			handler.ServeHTTP(recorder, httptest.NewRequest(http.MethodGet, "/state", nil))
+			close(release)
+			<-done
+
+			if recorder.Code != tt.want {
+				t.Errorf("TestLimiter_MaxConcurrent() = %v, want %v", recorder.Code, tt.want)
+			}
+		})
+	}
+}
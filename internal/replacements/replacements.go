@@ -0,0 +1,265 @@
+// Package replacements links pods recreated by their controller to the pods they replace, so a consumer
+// of /events can tell a pod being replaced from one removed for good. A pod replaces another when both are
+// controlled by the same owner and created from the same template hash.
+package replacements
+
+import (
+	"context"
+	"fmt"
+	"sync"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	apitypes "k8s.io/apimachinery/pkg/types"
+	toolscache "k8s.io/client-go/tools/cache"
+	"sigs.k8s.io/controller-runtime/pkg/log"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const (
+	defaultWindow    = time.Minute
+	defaultMaxEvents = 100
+)
+
+// templateHashLabels are the labels controllers stamp pods with to identify the template they were
+// created from: ReplicaSets use the first, StatefulSets and DaemonSets the second
+var templateHashLabels = []string{"pod-template-hash", "controller-revision-hash"}
+
+// Config controls how long a departed pod waits for its replacement
+type Config struct {
+	// Window is how long after a pod starts terminating a new pod of the same owner and template may
+	// replace it, defaulting to 1m
+	Window time.Duration
+	// MaxEvents is how many replacement events are kept for /events, defaulting to 100
+	MaxEvents int
+}
+
+// departure is a pod that started terminating and has not been replaced yet
+type departure struct {
+	name string
+	uid  apitypes.UID
+	at   time.Time
+}
+
+// Tracker follows pods from the pod informer. It is a ResourceEventHandler.
+type Tracker struct {
+	informers types.Informers
+	config    Config
+	now       func() time.Time
+
+	mu sync.Mutex
+	// pending holds departures oldest first, keyed by namespace, owner and template hash
+	pending map[string][]departure
+	// departed is every pod seen departing within the window, so its deletion after it started
+	// terminating, or after it was replaced, is not a second departure
+	departed map[apitypes.UID]time.Time
+	// events is oldest first and holds at most config.MaxEvents
+	events []types.Event
+}
+
+// NewTracker creates a Tracker that follows the pods seen by the pod informer in informers
+func NewTracker(informers types.Informers, config Config, opts ...TrackerOpt) *Tracker {
+	if config.Window <= 0 {
+		config.Window = defaultWindow
+	}
+	if config.MaxEvents <= 0 {
+		config.MaxEvents = defaultMaxEvents
+	}
+
+	t := &Tracker{
+		informers: informers,
+		config:    config,
+		now:       time.Now,
+		pending:   make(map[string][]departure),
+		departed:  make(map[apitypes.UID]time.Time),
+	}
+
+	for _, opt := range opts {
+		opt(t)
+	}
+
+	return t
+}
+
+type TrackerOpt func(*Tracker)
+
+// WithClock replaces the clock replacements are timed by
+func WithClock(now func() time.Time) TrackerOpt {
+	return func(t *Tracker) {
+		t.now = now
+	}
+}
+
+// Start follows pod events until ctx is cancelled
+func (t *Tracker) Start(ctx context.Context) {
+	logger := log.FromContext(ctx).WithName("replacements")
+	informer, err := t.informers.GetInformer(ctx, &corev1.Pod{})
+	if err != nil {
+		logger.Error(err, "failed to get pod informer")
+		return
+	}
+	registration, err := informer.AddEventHandler(t)
+	if err != nil {
+		logger.Error(err, "failed to watch pod events")
+		return
+	}
+
+	<-ctx.Done()
+	if err := informer.RemoveEventHandler(registration); err != nil {
+		logger.Error(err, "failed to stop watching pod events")
+	}
+}
+
+// OnAdd links a created pod to the oldest pending departure of its owner and template. Pods listed when
+// the informer starts replace nothing.
+func (t *Tracker) OnAdd(obj any, isInInitialList bool) {
+	if isInInitialList {
+		return
+	}
+	pod, ok := obj.(*corev1.Pod)
+	if !ok {
+		return
+	}
+	owner, hash, ok := identity(pod)
+	if !ok {
+		return
+	}
+
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	now := t.now()
+	t.prune(now)
+	group := groupKey(pod.Namespace, owner, hash)
+	pending := t.pending[group]
+	if len(pending) == 0 {
+		return
+	}
+	previous := pending[0]
+	t.pending[group] = pending[1:]
+	if len(t.pending[group]) == 0 {
+		delete(t.pending, group)
+	}
+	t.emit(types.Event{
+		Type:      types.EventTypePodReplaced,
+		Namespace: pod.Namespace,
+		Name:      pod.Name,
+		Message:   fmt.Sprintf("replaces pod %s of %s %s", previous.name, owner.Kind, owner.Name),
+		Replacement: &types.Replacement{
+			OwnerKind:    owner.Kind,
+			Owner:        owner.Name,
+			TemplateHash: hash,
+			Previous:     previous.name,
+			PreviousUID:  string(previous.uid),
+			Current:      pod.Name,
+			CurrentUID:   string(pod.UID),
+		},
+		Time: now,
+	})
+}
+
+// OnUpdate records a pod departing once it starts terminating, as its controller creates the
+// replacement then rather than when the pod is finally deleted
+func (t *Tracker) OnUpdate(_, newObj any) {
+	pod, ok := newObj.(*corev1.Pod)
+	if !ok || pod.DeletionTimestamp == nil {
+		return
+	}
+	t.depart(pod)
+}
+
+// OnDelete records a pod departing, unless it already did when it started terminating
+func (t *Tracker) OnDelete(obj any) {
+	if tombstone, ok := obj.(toolscache.DeletedFinalStateUnknown); ok {
+		obj = tombstone.Obj
+	}
+	pod, ok := obj.(*corev1.Pod)
+	if !ok {
+		return
+	}
+	t.depart(pod)
+}
+
+func (t *Tracker) depart(pod *corev1.Pod) {
+	owner, hash, ok := identity(pod)
+	if !ok {
+		return
+	}
+
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	now := t.now()
+	t.prune(now)
+	if _, seen := t.departed[pod.UID]; seen {
+		return
+	}
+	t.departed[pod.UID] = now
+	group := groupKey(pod.Namespace, owner, hash)
+	t.pending[group] = append(t.pending[group], departure{name: pod.Name, uid: pod.UID, at: now})
+}
+
+// prune forgets departures older than the window; those pods were removed rather than replaced
+func (t *Tracker) prune(now time.Time) {
+	cutoff := now.Add(-t.config.Window)
+	for group, pending := range t.pending {
+		kept := pending[:0]
+		for _, d := range pending {
+			if d.at.Before(cutoff) {
+				continue
+			}
+			kept = append(kept, d)
+		}
+		if len(kept) == 0 {
+			delete(t.pending, group)
+			continue
+		}
+		t.pending[group] = kept
+	}
+	for uid, at := range t.departed {
+		if at.Before(cutoff) {
+			delete(t.departed, uid)
+		}
+	}
+}
+
+// emit records a replacement, dropping the oldest event once config.MaxEvents are kept
+func (t *Tracker) emit(event types.Event) {
+	t.events = append(t.events, event)
+	if len(t.events) > t.config.MaxEvents {
+		t.events = t.events[len(t.events)-t.config.MaxEvents:]
+	}
+}
+
+// Events returns the recorded replacement events, newest first
+func (t *Tracker) Events() []types.Event {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	events := make([]types.Event, 0, len(t.events))
+	for i := len(t.events) - 1; i >= 0; i-- {
+		events = append(events, t.events[i])
+	}
+	return events
+}
+
+// identity returns the controller and template hash of pod, and false for pods without either, which
+// nothing recreates
+func identity(pod *corev1.Pod) (metav1.OwnerReference, string, bool) {
+	controller := metav1.GetControllerOf(pod)
+	if controller == nil {
+		return metav1.OwnerReference{}, "", false
+	}
+	for _, label := range templateHashLabels {
+		if hash := pod.Labels[label]; hash != "" {
+			return *controller, hash, true
+		}
+	}
+	return metav1.OwnerReference{}, "", false
+}
+
+func groupKey(namespace string, owner metav1.OwnerReference, hash string) string {
+	return namespace + "/" + owner.Kind + "/" + owner.Name + "/" + hash
+}
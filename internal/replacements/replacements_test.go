@@ -0,0 +1,163 @@
+package replacements_test
+
+import (
+	"reflect"
+	"testing"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	apitypes "k8s.io/apimachinery/pkg/types"
+	toolscache "k8s.io/client-go/tools/cache"
+	"k8s.io/utils/ptr"
+
+	"github.com/kdwils/constellation/internal/replacements"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func pod(name, owner, hash string) *corev1.Pod {
+	return &corev1.Pod{ObjectMeta: metav1.ObjectMeta{
+		Name:            name,
+		Namespace:       "shop",
+		UID:             apitypes.UID(name + "-uid"),
+		Labels:          map[string]string{"pod-template-hash": hash},
+		OwnerReferences: []metav1.OwnerReference{{Kind: "ReplicaSet", Name: owner, Controller: ptr.To(true)}},
+	}}
+}
+
+func terminating(p *corev1.Pod) *corev1.Pod {
+	p = p.DeepCopy()
+	p.DeletionTimestamp = ptr.To(metav1.Now())
+	return p
+}
+
+func TestTracker(t *testing.T) {
+	start := time.Date(2025, 1, 2, 3, 0, 0, 0, time.UTC)
+
+	type event struct {
+		after   time.Duration
+		initial bool
+		update  bool
+		delete  bool
+		obj     any
+	}
+	tests := []struct {
+		name   string
+		events []event
+		want   []types.Event
+	}{
+		{
+			name: "a pod recreated after its predecessor started terminating replaces it",
+			events: []event{
+				{initial: true, obj: pod("web-aaa-1", "web-aaa", "aaa")},
+				{after: time.Second, update: true, obj: terminating(pod("web-aaa-1", "web-aaa", "aaa"))},
+				{after: 2 * time.Second, obj: pod("web-aaa-2", "web-aaa", "aaa")},
+				{after: 30 * time.Second, delete: true, obj: pod("web-aaa-1", "web-aaa", "aaa")},
+				{after: 31 * time.Second, obj: pod("web-aaa-3", "web-aaa", "aaa")},
+			},
+			want: []types.Event{{
+				Type:      types.EventTypePodReplaced,
+				Namespace: "shop",
+				Name:      "web-aaa-2",
+				Message:   "replaces pod web-aaa-1 of ReplicaSet web-aaa",
+				Replacement: &types.Replacement{
+					OwnerKind:    "ReplicaSet",
+					Owner:        "web-aaa",
+					TemplateHash: "aaa",
+					Previous:     "web-aaa-1",
+					PreviousUID:  "web-aaa-1-uid",
+					Current:      "web-aaa-2",
+					CurrentUID:   "web-aaa-2-uid",
+				},
+				Time: start.Add(2 * time.Second),
+			}},
+		},
+		{
+			name: "departures are matched oldest first and tombstones count",
+			events: []event{
+				{delete: true, obj: pod("web-aaa-1", "web-aaa", "aaa")},
+				{after: time.Second, delete: true, obj: toolscache.DeletedFinalStateUnknown{
+					Key: "shop/web-aaa-2",
+					Obj: pod("web-aaa-2", "web-aaa", "aaa"),
+				}},
+				{after: 2 * time.Second, obj: pod("web-aaa-3", "web-aaa", "aaa")},
+				{after: 3 * time.Second, obj: pod("web-aaa-4", "web-aaa", "aaa")},
+			},
+			want: []types.Event{
+				{
+					Type:      types.EventTypePodReplaced,
+					Namespace: "shop",
+					Name:      "web-aaa-4",
+					Message:   "replaces pod web-aaa-2 of ReplicaSet web-aaa",
+					Replacement: &types.Replacement{
+						OwnerKind:    "ReplicaSet",
+						Owner:        "web-aaa",
+						TemplateHash: "aaa",
+						Previous:     "web-aaa-2",
+						PreviousUID:  "web-aaa-2-uid",
+						Current:      "web-aaa-4",
+						CurrentUID:   "web-aaa-4-uid",
+					},
+					Time: start.Add(3 * time.Second),
+				},
+				{
+					Type:      types.EventTypePodReplaced,
+					Namespace: "shop",
+					Name:      "web-aaa-3",
+					Message:   "replaces pod web-aaa-1 of ReplicaSet web-aaa",
+					Replacement: &types.Replacement{
+						OwnerKind:    "ReplicaSet",
+						Owner:        "web-aaa",
+						TemplateHash: "aaa",
+						Previous:     "web-aaa-1",
+						PreviousUID:  "web-aaa-1-uid",
+						Current:      "web-aaa-3",
+						CurrentUID:   "web-aaa-3-uid",
+					},
+					Time: start.Add(2 * time.Second),
+				},
+			},
+		},
+		{
+			name: "a different template, owner or a late pod is not a replacement",
+			events: []event{
+				{delete: true, obj: pod("web-aaa-1", "web-aaa", "aaa")},
+				{after: time.Second, obj: pod("web-bbb-1", "web-bbb", "bbb")},
+				{after: time.Second, obj: pod("api-aaa-1", "api-aaa", "aaa")},
+				{after: 2 * time.Minute, obj: pod("web-aaa-2", "web-aaa", "aaa")},
+			},
+			want: []types.Event{},
+		},
+		{
+			name: "pods listed at startup and pods without a controller replace nothing",
+			events: []event{
+				{delete: true, obj: pod("web-aaa-1", "web-aaa", "aaa")},
+				{initial: true, obj: pod("web-aaa-2", "web-aaa", "aaa")},
+				{delete: true, obj: &corev1.Pod{ObjectMeta: metav1.ObjectMeta{Name: "debug", Namespace: "shop"}}},
+				{obj: &corev1.Pod{ObjectMeta: metav1.ObjectMeta{Name: "debug", Namespace: "shop"}}},
+			},
+			want: []types.Event{},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			now := start
+			tracker := replacements.NewTracker(nil, replacements.Config{}, replacements.WithClock(func() time.Time { return now }))
+			for _, e := range tt.events {
+				now = start.Add(e.after)
+				switch {
+				case e.delete:
+					tracker.OnDelete(e.obj)
+				case e.update:
+					tracker.OnUpdate(nil, e.obj)
+				default:
+					tracker.OnAdd(e.obj, e.initial)
+				}
+			}
+
+			if got := tracker.Events(); !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestTracker() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
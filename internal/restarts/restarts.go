@@ -0,0 +1,209 @@
+// Package restarts tracks container restart counts over time and flags pods that restart too often,
+// without relying on an external monitoring stack.
+package restarts
+
+import (
+	"context"
+	"fmt"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	"sigs.k8s.io/controller-runtime/pkg/log"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const (
+	defaultThreshold = 3
+	defaultWindow    = 15 * time.Minute
+	defaultInterval  = 30 * time.Second
+)
+
+// Config controls when a pod is flagged
+type Config struct {
+	// Threshold is how many restarts within Window flag a pod, defaulting to 3
+	Threshold int32
+	// Window is the sliding period restarts are counted over, defaulting to 15m
+	Window time.Duration
+	// Interval between restart count samples, defaulting to 30s
+	Interval time.Duration
+}
+
+type sample struct {
+	at       time.Time
+	restarts int32
+}
+
+// Tracker samples pod restart counts and reports pods whose restarts within the window reach the threshold
+type Tracker struct {
+	reader client.Reader
+	config Config
+	now    func() time.Time
+
+	mu      sync.RWMutex
+	samples map[string][]sample
+}
+
+// NewTracker creates a Tracker, filling unset config fields with defaults
+func NewTracker(reader client.Reader, config Config, opts ...TrackerOpt) *Tracker {
+	if config.Threshold == 0 {
+		config.Threshold = defaultThreshold
+	}
+	if config.Window == 0 {
+		config.Window = defaultWindow
+	}
+	if config.Interval == 0 {
+		config.Interval = defaultInterval
+	}
+
+	t := &Tracker{
+		reader:  reader,
+		config:  config,
+		now:     time.Now,
+		samples: make(map[string][]sample),
+	}
+
+	for _, opt := range opts {
+		opt(t)
+	}
+
+	return t
+}
+
+type TrackerOpt func(*Tracker)
+
+// WithClock replaces time.Now, for tests
+func WithClock(now func() time.Time) TrackerOpt {
+	return func(t *Tracker) {
+		t.now = now
+	}
+}
+
+// Start samples restart counts on the configured interval until ctx is cancelled
+func (t *Tracker) Start(ctx context.Context) {
+	logger := log.FromContext(ctx).WithName("restarts")
+	ticker := time.NewTicker(t.config.Interval)
+	defer ticker.Stop()
+
+	for {
+		if err := t.Sample(ctx); err != nil {
+			logger.Error(err, "failed to sample pod restarts")
+		}
+
+		select {
+		case <-ctx.Done():
+			return
+		case <-ticker.C:
+		}
+	}
+}
+
+// Sample records the current restart count of every pod
+func (t *Tracker) Sample(ctx context.Context) error {
+	var pods corev1.PodList
+	if err := t.reader.List(ctx, &pods); err != nil {
+		return fmt.Errorf("failed to list pods: %w", err)
+	}
+	t.Observe(pods.Items)
+	return nil
+}
+
+// Observe records the restart counts of pods. Pods missing from the list are forgotten.
+func (t *Tracker) Observe(pods []corev1.Pod) {
+	now := t.now()
+	cutoff := now.Add(-t.config.Window)
+
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	samples := make(map[string][]sample, len(pods))
+	for _, pod := range pods {
+		key := pod.Namespace + "/" + pod.Name
+		var kept []sample
+		for _, s := range t.samples[key] {
+			if s.at.Before(cutoff) {
+				continue
+			}
+			kept = append(kept, s)
+		}
+		samples[key] = append(kept, sample{at: now, restarts: restartCount(pod)})
+	}
+	t.samples = samples
+}
+
+func restartCount(pod corev1.Pod) int32 {
+	var total int32
+	for _, status := range pod.Status.InitContainerStatuses {
+		total += status.RestartCount
+	}
+	for _, status := range pod.Status.ContainerStatuses {
+		total += status.RestartCount
+	}
+	return total
+}
+
+// restartsInWindow is the increase across the retained samples. A decrease means the pod was
+// recreated under the same name, so only the latest count is meaningful.
+func restartsInWindow(samples []sample) int32 {
+	if len(samples) == 0 {
+		return 0
+	}
+	first, last := samples[0].restarts, samples[len(samples)-1].restarts
+	if last < first {
+		return last
+	}
+	return last - first
+}
+
+// Anomalous reports whether a pod reached the restart threshold within the window
+func (t *Tracker) Anomalous(namespace, pod string) bool {
+	t.mu.RLock()
+	defer t.mu.RUnlock()
+	return restartsInWindow(t.samples[namespace+"/"+pod]) >= t.config.Threshold
+}
+
+// Enrich sets RestartAnomaly on flagged Pod nodes
+func (t *Tracker) Enrich(nodes []types.HierarchyNode) {
+	hierarchy.Walk(nodes, func(node *types.HierarchyNode) {
+		if node.Kind != types.ResourceKindPod || node.Namespace == nil {
+			return
+		}
+		node.RestartAnomaly = t.Anomalous(*node.Namespace, node.Name)
+	})
+}
+
+// GetAlerts returns an alert for every flagged pod, ordered by namespace and name
+func (t *Tracker) GetAlerts(ctx context.Context) ([]types.Alert, error) {
+	t.mu.RLock()
+	defer t.mu.RUnlock()
+
+	keys := make([]string, 0, len(t.samples))
+	for key := range t.samples {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+
+	alerts := make([]types.Alert, 0)
+	for _, key := range keys {
+		samples := t.samples[key]
+		restarts := restartsInWindow(samples)
+		if restarts < t.config.Threshold {
+			continue
+		}
+		namespace, name, _ := strings.Cut(key, "/")
+		alerts = append(alerts, types.Alert{
+			Type:      types.AlertTypeRestartAnomaly,
+			Kind:      types.ResourceKindPod,
+			Namespace: namespace,
+			Name:      name,
+			Message:   fmt.Sprintf("%d restarts in the last %s", restarts, t.config.Window),
+			Since:     samples[0].at,
+		})
+	}
+	return alerts, nil
+}
@@ -0,0 +1,70 @@
+package restarts_test
+
+import (
+	"testing"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+
+	"github.com/kdwils/constellation/internal/restarts"
+)
+
+func pod(restartCount int32) corev1.Pod {
+	return corev1.Pod{
+		ObjectMeta: metav1.ObjectMeta{Name: "web-0", Namespace: "default"},
+		Status: corev1.PodStatus{
+			ContainerStatuses: []corev1.ContainerStatus{{Name: "web", RestartCount: restartCount}},
+		},
+	}
+}
+
+func TestTracker_Anomalous(t *testing.T) {
+	tests := []struct {
+		name     string
+		counts   []int32
+		interval time.Duration
+		want     bool
+	}{
+		{
+			name:     "restarts within window reach threshold",
+			counts:   []int32{1, 2, 4},
+			interval: time.Minute,
+			want:     true,
+		},
+		{
+			name:     "restarts below threshold",
+			counts:   []int32{5, 6, 7},
+			interval: time.Minute,
+			want:     false,
+		},
+		{
+			name:     "old restarts fall out of the window",
+			counts:   []int32{0, 3, 4},
+			interval: 10 * time.Minute,
+			want:     false,
+		},
+		{
+			name:     "recreated pod counts from its latest value",
+			counts:   []int32{9, 3},
+			interval: time.Minute,
+			want:     true,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			now := time.Date(2025, 1, 1, 0, 0, 0, 0, time.UTC)
+			tracker := restarts.NewTracker(nil, restarts.Config{Threshold: 3, Window: 15 * time.Minute},
+				restarts.WithClock(func() time.Time { return now }))
+
+			for _, count := range tt.counts {
+				tracker.Observe([]corev1.Pod{pod(count)})
+				now = now.Add(tt.interval)
+			}
+
+			if got := tracker.Anomalous("default", "web-0"); got != tt.want {
+				t.Errorf("TestTracker_Anomalous() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,280 @@
+// Package rollouts correlates pods with the Deployment revision they were created from, so the topology
+// shows which revision a pod runs, its change-cause and how long ago that revision started rolling.
+// Revisions are read from ReplicaSets, which carry the Deployment's revision and change-cause annotations.
+package rollouts
+
+import (
+	"context"
+	"fmt"
+	"strconv"
+	"sync"
+	"time"
+
+	appsv1 "k8s.io/api/apps/v1"
+	toolscache "k8s.io/client-go/tools/cache"
+	"sigs.k8s.io/controller-runtime/pkg/log"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const (
+	revisionAnnotation    = "deployment.kubernetes.io/revision"
+	changeCauseAnnotation = "kubernetes.io/change-cause"
+	podTemplateHashLabel  = "pod-template-hash"
+
+	defaultMaxEvents = 100
+)
+
+// Config sets how much rollout history is kept
+type Config struct {
+	// MaxEvents is how many rollout events are kept for /events, defaulting to 100
+	MaxEvents int
+}
+
+// replicaSet is the revision of a Deployment one ReplicaSet holds
+type replicaSet struct {
+	namespace   string
+	deployment  string
+	hash        string
+	revision    int64
+	changeCause string
+	created     time.Time
+	// started is when the revision was seen to start rolling, zero when it was listed at startup
+	started time.Time
+}
+
+// Tracker follows ReplicaSets from the ReplicaSet informer. It is a ResourceEventHandler.
+type Tracker struct {
+	informers types.Informers
+	config    Config
+	now       func() time.Time
+
+	mu          sync.RWMutex
+	replicaSets map[string]replicaSet
+	// latest is the highest revision seen per Deployment key
+	latest map[string]int64
+	// events is oldest first and holds at most config.MaxEvents
+	events []types.Event
+}
+
+// NewTracker creates a Tracker that follows the ReplicaSets seen by the ReplicaSet informer in informers
+func NewTracker(informers types.Informers, config Config, opts ...TrackerOpt) *Tracker {
+	if config.MaxEvents <= 0 {
+		config.MaxEvents = defaultMaxEvents
+	}
+
+	t := &Tracker{
+		informers:   informers,
+		config:      config,
+		now:         time.Now,
+		replicaSets: make(map[string]replicaSet),
+		latest:      make(map[string]int64),
+	}
+
+	for _, opt := range opts {
+		opt(t)
+	}
+
+	return t
+}
+
+type TrackerOpt func(*Tracker)
+
+// WithClock replaces the clock rollouts are timed by
+func WithClock(now func() time.Time) TrackerOpt {
+	return func(t *Tracker) {
+		t.now = now
+	}
+}
+
+// +kubebuilder:rbac:groups=apps,resources=replicasets,verbs=get;list;watch
+
+// Start follows ReplicaSet events until ctx is cancelled
+func (t *Tracker) Start(ctx context.Context) {
+	logger := log.FromContext(ctx).WithName("rollouts")
+	informer, err := t.informers.GetInformer(ctx, &appsv1.ReplicaSet{})
+	if err != nil {
+		logger.Error(err, "failed to get replicaset informer")
+		return
+	}
+	registration, err := informer.AddEventHandler(t)
+	if err != nil {
+		logger.Error(err, "failed to watch replicaset events")
+		return
+	}
+
+	<-ctx.Done()
+	if err := informer.RemoveEventHandler(registration); err != nil {
+		logger.Error(err, "failed to stop watching replicaset events")
+	}
+}
+
+// OnAdd records a ReplicaSet. Revisions listed when the informer starts were already rolling, so they
+// do not produce events.
+func (t *Tracker) OnAdd(obj any, isInInitialList bool) {
+	t.record(obj, !isInInitialList)
+}
+
+// OnUpdate records a ReplicaSet whose revision may have changed, as it does when a Deployment is rolled
+// back to the ReplicaSet's template
+func (t *Tracker) OnUpdate(_, newObj any) {
+	t.record(newObj, true)
+}
+
+// OnDelete forgets a ReplicaSet, and its Deployment once none of its ReplicaSets remain
+func (t *Tracker) OnDelete(obj any) {
+	if tombstone, ok := obj.(toolscache.DeletedFinalStateUnknown); ok {
+		obj = tombstone.Obj
+	}
+	rs, ok := obj.(*appsv1.ReplicaSet)
+	if !ok {
+		return
+	}
+
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	removed, exists := t.replicaSets[key(rs.Namespace, rs.Name)]
+	if !exists {
+		return
+	}
+	delete(t.replicaSets, key(rs.Namespace, rs.Name))
+	for _, remaining := range t.replicaSets {
+		if remaining.namespace == removed.namespace && remaining.deployment == removed.deployment {
+			return
+		}
+	}
+	delete(t.latest, key(removed.namespace, removed.deployment))
+}
+
+func (t *Tracker) record(obj any, live bool) {
+	rs, ok := obj.(*appsv1.ReplicaSet)
+	if !ok {
+		return
+	}
+	deployment := controllingDeployment(rs)
+	if deployment == "" {
+		return
+	}
+	revision, err := strconv.ParseInt(rs.Annotations[revisionAnnotation], 10, 64)
+	if err != nil {
+		return
+	}
+
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	current := replicaSet{
+		namespace:   rs.Namespace,
+		deployment:  deployment,
+		hash:        rs.Labels[podTemplateHashLabel],
+		revision:    revision,
+		changeCause: rs.Annotations[changeCauseAnnotation],
+		created:     rs.CreationTimestamp.Time,
+	}
+	if previous, exists := t.replicaSets[key(rs.Namespace, rs.Name)]; exists && previous.revision == revision {
+		current.started = previous.started
+	}
+
+	deploymentKey := key(rs.Namespace, deployment)
+	if revision > t.latest[deploymentKey] {
+		t.latest[deploymentKey] = revision
+		if live {
+			current.started = t.now()
+			t.emit(current)
+		}
+	}
+	t.replicaSets[key(rs.Namespace, rs.Name)] = current
+}
+
+// emit records the start of a rollout, dropping the oldest event once config.MaxEvents are kept
+func (t *Tracker) emit(rs replicaSet) {
+	message := fmt.Sprintf("revision %d started rolling", rs.revision)
+	if rs.changeCause != "" {
+		message += ": " + rs.changeCause
+	}
+	t.events = append(t.events, types.Event{
+		Type:      types.EventTypeRolloutStarted,
+		Namespace: rs.namespace,
+		Name:      rs.deployment,
+		Message:   message,
+		Rollout:   rs.rollout(),
+		Time:      rs.started,
+	})
+	if len(t.events) > t.config.MaxEvents {
+		t.events = t.events[len(t.events)-t.config.MaxEvents:]
+	}
+}
+
+// Events returns the recorded rollout events, newest first
+func (t *Tracker) Events() []types.Event {
+	t.mu.RLock()
+	defer t.mu.RUnlock()
+
+	events := make([]types.Event, 0, len(t.events))
+	for i := len(t.events) - 1; i >= 0; i-- {
+		events = append(events, t.events[i])
+	}
+	return events
+}
+
+// Enrich sets the Rollout of Pod nodes created by a Deployment, matched to their ReplicaSet by the
+// pod-template-hash label
+func (t *Tracker) Enrich(nodes []types.HierarchyNode) {
+	t.mu.RLock()
+	defer t.mu.RUnlock()
+
+	byHash := make(map[string]replicaSet, len(t.replicaSets))
+	for _, rs := range t.replicaSets {
+		if rs.hash != "" {
+			byHash[key(rs.namespace, rs.hash)] = rs
+		}
+	}
+
+	hierarchy.Walk(nodes, func(node *types.HierarchyNode) {
+		if node.Kind != types.ResourceKindPod || node.Namespace == nil {
+			return
+		}
+		hash := node.Labels[podTemplateHashLabel]
+		if hash == "" {
+			return
+		}
+		rs, exists := byHash[key(*node.Namespace, hash)]
+		if !exists {
+			return
+		}
+		node.Rollout = rs.rollout()
+	})
+}
+
+func (rs replicaSet) rollout() *types.Rollout {
+	started := rs.started
+	if started.IsZero() {
+		started = rs.created
+	}
+	return &types.Rollout{
+		Deployment:  rs.deployment,
+		Revision:    rs.revision,
+		ChangeCause: rs.changeCause,
+		StartedAt:   started,
+	}
+}
+
+// controllingDeployment returns the name of the Deployment controlling rs, or "" when it has none
+func controllingDeployment(rs *appsv1.ReplicaSet) string {
+	for _, owner := range rs.OwnerReferences {
+		if owner.Controller == nil || !*owner.Controller {
+			continue
+		}
+		if owner.Kind != "Deployment" {
+			return ""
+		}
+		return owner.Name
+	}
+	return ""
+}
+
+func key(namespace, name string) string {
+	return namespace + "/" + name
+}
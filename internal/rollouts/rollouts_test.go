@@ -0,0 +1,193 @@
+package rollouts_test
+
+import (
+	"reflect"
+	"testing"
+	"time"
+
+	appsv1 "k8s.io/api/apps/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	toolscache "k8s.io/client-go/tools/cache"
+	"k8s.io/utils/ptr"
+
+	"github.com/kdwils/constellation/internal/rollouts"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+var created = time.Date(2025, 1, 1, 0, 0, 0, 0, time.UTC)
+
+func replicaSet(hash, revision, changeCause string) *appsv1.ReplicaSet {
+	return &appsv1.ReplicaSet{ObjectMeta: metav1.ObjectMeta{
+		Name:              "web-" + hash,
+		Namespace:         "shop",
+		Labels:            map[string]string{"pod-template-hash": hash},
+		Annotations:       map[string]string{"deployment.kubernetes.io/revision": revision, "kubernetes.io/change-cause": changeCause},
+		CreationTimestamp: metav1.NewTime(created),
+		OwnerReferences:   []metav1.OwnerReference{{Kind: "Deployment", Name: "web", Controller: ptr.To(true)}},
+	}}
+}
+
+func pods(hashes ...string) []types.HierarchyNode {
+	var nodes []types.HierarchyNode
+	for _, hash := range hashes {
+		nodes = append(nodes, types.HierarchyNode{
+			Kind:      types.ResourceKindPod,
+			Name:      "web-" + hash + "-abcde",
+			Namespace: ptr.To("shop"),
+			Labels:    map[string]string{"pod-template-hash": hash},
+		})
+	}
+	return nodes
+}
+
+func TestTracker(t *testing.T) {
+	now := time.Date(2025, 1, 2, 3, 0, 0, 0, time.UTC)
+
+	type event struct {
+		initial bool
+		update  bool
+		delete  bool
+		obj     any
+	}
+	tests := []struct {
+		name         string
+		events       []event
+		pods         []types.HierarchyNode
+		wantEvents   []types.Event
+		wantRollouts []*types.Rollout
+	}{
+		{
+			name: "revisions listed at startup are not events",
+			events: []event{
+				{initial: true, obj: replicaSet("aaa", "1", "")},
+				{initial: true, obj: replicaSet("bbb", "2", "image web:v2")},
+			},
+			pods:       pods("aaa", "bbb"),
+			wantEvents: []types.Event{},
+			wantRollouts: []*types.Rollout{
+				{Deployment: "web", Revision: 1, StartedAt: created},
+				{Deployment: "web", Revision: 2, ChangeCause: "image web:v2", StartedAt: created},
+			},
+		},
+		{
+			name: "a new revision starts rolling",
+			events: []event{
+				{initial: true, obj: replicaSet("aaa", "1", "")},
+				{obj: replicaSet("bbb", "2", "image web:v2")},
+			},
+			pods: pods("bbb"),
+			wantEvents: []types.Event{{
+				Type:      types.EventTypeRolloutStarted,
+				Namespace: "shop",
+				Name:      "web",
+				Message:   "revision 2 started rolling: image web:v2",
+				Rollout:   &types.Rollout{Deployment: "web", Revision: 2, ChangeCause: "image web:v2", StartedAt: now},
+				Time:      now,
+			}},
+			wantRollouts: []*types.Rollout{
+				{Deployment: "web", Revision: 2, ChangeCause: "image web:v2", StartedAt: now},
+			},
+		},
+		{
+			name: "a rollback bumps an old replicaset's revision",
+			events: []event{
+				{initial: true, obj: replicaSet("aaa", "1", "")},
+				{initial: true, obj: replicaSet("bbb", "2", "")},
+				{update: true, obj: replicaSet("aaa", "3", "")},
+				{update: true, obj: replicaSet("aaa", "3", "")},
+			},
+			pods: pods("aaa"),
+			wantEvents: []types.Event{{
+				Type:      types.EventTypeRolloutStarted,
+				Namespace: "shop",
+				Name:      "web",
+				Message:   "revision 3 started rolling",
+				Rollout:   &types.Rollout{Deployment: "web", Revision: 3, StartedAt: now},
+				Time:      now,
+			}},
+			wantRollouts: []*types.Rollout{{Deployment: "web", Revision: 3, StartedAt: now}},
+		},
+		{
+			name: "deleted and unowned replicasets are not correlated",
+			events: []event{
+				{initial: true, obj: replicaSet("aaa", "1", "")},
+				{delete: true, obj: toolscache.DeletedFinalStateUnknown{Key: "shop/web-aaa", Obj: replicaSet("aaa", "1", "")}},
+				{initial: true, obj: &appsv1.ReplicaSet{ObjectMeta: metav1.ObjectMeta{
+					Name:        "standalone",
+					Namespace:   "shop",
+					Labels:      map[string]string{"pod-template-hash": "ccc"},
+					Annotations: map[string]string{"deployment.kubernetes.io/revision": "1"},
+				}}},
+			},
+			pods:         pods("aaa", "ccc"),
+			wantEvents:   []types.Event{},
+			wantRollouts: []*types.Rollout{nil, nil},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			tracker := rollouts.NewTracker(nil, rollouts.Config{}, rollouts.WithClock(func() time.Time { return now }))
+			for _, e := range tt.events {
+				switch {
+				case e.delete:
+					tracker.OnDelete(e.obj)
+				case e.update:
+					tracker.OnUpdate(nil, e.obj)
+				default:
+					tracker.OnAdd(e.obj, e.initial)
+				}
+			}
+
+			if got := tracker.Events(); !reflect.DeepEqual(got, tt.wantEvents) {
+				t.Errorf("TestTracker() events = %+v, want %+v", got, tt.wantEvents)
+			}
+
+			tracker.Enrich(tt.pods)
+			var got []*types.Rollout
+			for _, pod := range tt.pods {
+				got = append(got, pod.Rollout)
+			}
+			if !reflect.DeepEqual(got, tt.wantRollouts) {
+				t.Errorf("TestTracker() rollouts = %+v, want %+v", got, tt.wantRollouts)
+			}
+		})
+	}
+}
+
+func TestTracker_MaxEvents(t *testing.T) {
+	tests := []struct {
+		name      string
+		maxEvents int
+		revisions []string
+		want      []string
+	}{
+		{
+			name:      "oldest events are dropped",
+			maxEvents: 2,
+			revisions: []string{"1", "2", "3"},
+			want:      []string{"revision 3 started rolling", "revision 2 started rolling"},
+		},
+		{
+			name:      "older revisions are not events",
+			maxEvents: 5,
+			revisions: []string{"2", "1"},
+			want:      []string{"revision 2 started rolling"},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			tracker := rollouts.NewTracker(nil, rollouts.Config{MaxEvents: tt.maxEvents})
+			for _, revision := range tt.revisions {
+				tracker.OnAdd(replicaSet("hash"+revision, revision, ""), false)
+			}
+
+			got := make([]string, 0)
+			for _, event := range tracker.Events() {
+				got = append(got, event.Message)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestTracker_MaxEvents() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,127 @@
+package schema
+
+import (
+	"reflect"
+)
+
+// Version is the version of the wire schema. Bump it whenever a field of one of the Types is added,
+// removed or changes type; TestJSONSchema fails until testdata is regenerated alongside the bump.
+const Version = 1
+
+// draft is the JSON Schema dialect documents are written in
+const draft = "https://json-schema.org/draft/2020-12/schema"
+
+// Document is a JSON Schema of one payload, with every named struct it reaches under $defs
+type Document struct {
+	Schema        string             `json:"$schema"`
+	Title         string             `json:"title"`
+	SchemaVersion int                `json:"schema_version"`
+	Ref           string             `json:"$ref"`
+	Defs          map[string]*Schema `json:"$defs"`
+}
+
+// Schema describes one JSON value. Type is a single type name, or a list when the value may also be null.
+type Schema struct {
+	Type                 any                `json:"type,omitempty"`
+	Format               string             `json:"format,omitempty"`
+	ContentEncoding      string             `json:"contentEncoding,omitempty"`
+	Ref                  string             `json:"$ref,omitempty"`
+	AnyOf                []*Schema          `json:"anyOf,omitempty"`
+	Items                *Schema            `json:"items,omitempty"`
+	AdditionalProperties *Schema            `json:"additionalProperties,omitempty"`
+	Properties           map[string]*Schema `json:"properties,omitempty"`
+	Required             []string           `json:"required,omitempty"`
+}
+
+// JSONSchema describes the JSON encoding of root. Fields without omitempty are required, and slices,
+// maps and pointers among them may be null. Encoding a Document is deterministic.
+func JSONSchema(root reflect.Type) (Document, error) {
+	structs, err := named([]reflect.Type{root})
+	if err != nil {
+		return Document{}, err
+	}
+
+	defs := make(map[string]*Schema, len(structs))
+	for _, typ := range structs {
+		defs[typ.Name()] = jsonObject(typ)
+	}
+	return Document{
+		Schema:        draft,
+		Title:         indirect(root).Name(),
+		SchemaVersion: Version,
+		Ref:           ref(indirect(root)),
+		Defs:          defs,
+	}, nil
+}
+
+func ref(typ reflect.Type) string {
+	return "#/$defs/" + typ.Name()
+}
+
+// jsonObject describes the fields of struct typ
+func jsonObject(typ reflect.Type) *Schema {
+	object := &Schema{Type: "object", Properties: make(map[string]*Schema)}
+	for _, f := range fieldsOf(typ) {
+		property := jsonType(f.typ)
+		if !f.optional {
+			object.Required = append(object.Required, f.name)
+			property = nullable(f.typ, property)
+		}
+		object.Properties[f.name] = property
+	}
+	return object
+}
+
+// nullable lets property be null when Go encodes the zero value of typ as null
+func nullable(typ reflect.Type, property *Schema) *Schema {
+	switch typ.Kind() {
+	case reflect.Pointer, reflect.Slice, reflect.Map:
+	default:
+		return property
+	}
+	if typ.Kind() == reflect.Slice && typ.Elem().Kind() == reflect.Uint8 {
+		return property
+	}
+	if property.Ref != "" {
+		return &Schema{AnyOf: []*Schema{property, {Type: "null"}}}
+	}
+	if name, ok := property.Type.(string); ok {
+		property.Type = []string{name, "null"}
+	}
+	return property
+}
+
+func jsonType(typ reflect.Type) *Schema {
+	typ = indirect(typ)
+	if isTime(typ) {
+		return &Schema{Type: "string", Format: "date-time"}
+	}
+	if implementsMarshaler(typ) {
+		return &Schema{}
+	}
+
+	switch typ.Kind() {
+	case reflect.String:
+		return &Schema{Type: "string"}
+	case reflect.Bool:
+		return &Schema{Type: "boolean"}
+	case reflect.Int, reflect.Int8, reflect.Int16, reflect.Int32, reflect.Int64,
+		reflect.Uint, reflect.Uint8, reflect.Uint16, reflect.Uint32, reflect.Uint64:
+		return &Schema{Type: "integer"}
+	case reflect.Float32, reflect.Float64:
+		return &Schema{Type: "number"}
+	case reflect.Slice, reflect.Array:
+		if typ.Kind() == reflect.Slice && typ.Elem().Kind() == reflect.Uint8 {
+			return &Schema{Type: "string", ContentEncoding: "base64"}
+		}
+		return &Schema{Type: "array", Items: jsonType(typ.Elem())}
+	case reflect.Map:
+		return &Schema{Type: "object", AdditionalProperties: jsonType(typ.Elem())}
+	case reflect.Struct:
+		if typ.Name() == "" {
+			return jsonObject(typ)
+		}
+		return &Schema{Ref: ref(typ)}
+	}
+	return &Schema{}
+}
@@ -0,0 +1,78 @@
+package schema
+
+import (
+	"bytes"
+	"encoding/json"
+	"flag"
+	"fmt"
+	"os"
+	"path/filepath"
+	"reflect"
+	"testing"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+var update = flag.Bool("update", false, "write the golden file of a new schema version in testdata")
+
+// TestJSONSchema compares the state schema against testdata/state.v<Version>.golden.json. A changed schema
+// needs a new Version; run `go test ./internal/schema -update` after bumping it to write the new golden.
+func TestJSONSchema(t *testing.T) {
+	document, err := JSONSchema(reflect.TypeFor[types.State]())
+	if err != nil {
+		t.Fatalf("TestJSONSchema() error = %v", err)
+	}
+	got, err := json.MarshalIndent(document, "", "  ")
+	if err != nil {
+		t.Fatalf("TestJSONSchema() marshal error = %v", err)
+	}
+	got = append(got, '\n')
+
+	golden := filepath.Join("testdata", fmt.Sprintf("state.v%d.golden.json", Version))
+	want, err := os.ReadFile(golden)
+	if *update && os.IsNotExist(err) {
+		if err := os.WriteFile(golden, got, 0o644); err != nil {
+			t.Fatalf("TestJSONSchema() write golden error = %v", err)
+		}
+		return
+	}
+	if err != nil {
+		t.Fatalf("TestJSONSchema() read golden error = %v", err)
+	}
+	if !bytes.Equal(got, want) {
+		t.Errorf("TestJSONSchema() schema changed without bumping Version\ngot:\n%s\nwant:\n%s", got, want)
+	}
+}
+
+func TestJSONSchema_Nullable(t *testing.T) {
+	type item struct {
+		Name string `json:"name"`
+	}
+	type payload struct {
+		Items   []item            `json:"items"`
+		Item    *item             `json:"item"`
+		Labels  map[string]string `json:"labels,omitempty"`
+		Comment *string           `json:"comment"`
+		Data    []byte            `json:"data"`
+	}
+
+	document, err := JSONSchema(reflect.TypeFor[payload]())
+	if err != nil {
+		t.Fatalf("TestJSONSchema_Nullable() error = %v", err)
+	}
+
+	want := &Schema{
+		Type: "object",
+		Properties: map[string]*Schema{
+			"items":   {Type: []string{"array", "null"}, Items: &Schema{Ref: "#/$defs/item"}},
+			"item":    {AnyOf: []*Schema{{Ref: "#/$defs/item"}, {Type: "null"}}},
+			"labels":  {Type: "object", AdditionalProperties: &Schema{Type: "string"}},
+			"comment": {Type: []string{"string", "null"}},
+			"data":    {Type: "string", ContentEncoding: "base64"},
+		},
+		Required: []string{"items", "item", "comment", "data"},
+	}
+	if got := document.Defs["payload"]; !reflect.DeepEqual(got, want) {
+		t.Errorf("TestJSONSchema_Nullable() = %+v, want %+v", got, want)
+	}
+}
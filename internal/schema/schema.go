@@ -0,0 +1,158 @@
+// Package schema describes the JSON the API serves, derived from the Go types it is encoded from, so the
+// frontend and other clients can be generated against the same shape as the backend.
+package schema
+
+import (
+	"encoding/json"
+	"fmt"
+	"reflect"
+	"strings"
+	"time"
+
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+
+	"github.com/kdwils/constellation/internal/types"
+	apiv1 "github.com/kdwils/constellation/pkg/api/v1"
+)
+
+// Types are the roots of the wire schema: the JSON endpoints and the messages on the WebSocket streams
+var Types = []reflect.Type{
+	reflect.TypeFor[apiv1.Node](),
+	reflect.TypeFor[types.State](),
+	reflect.TypeFor[types.ChunkedMessage](),
+	reflect.TypeFor[types.WatchRequest](),
+	reflect.TypeFor[types.NodeUpdate](),
+	reflect.TypeFor[types.ServiceEndpoints](),
+	reflect.TypeFor[types.Annotation](),
+}
+
+var (
+	timeTypes     = []reflect.Type{reflect.TypeFor[time.Time](), reflect.TypeFor[metav1.Time]()}
+	marshalerType = reflect.TypeFor[json.Marshaler]()
+)
+
+// field is a struct field as it appears in JSON
+type field struct {
+	name     string
+	typ      reflect.Type
+	optional bool
+}
+
+// fieldsOf lists the JSON fields of struct typ in encoding order. Fields of embedded structs are promoted
+// unless a shallower field has the same name, as encoding/json does.
+func fieldsOf(typ reflect.Type) []field {
+	type candidate struct {
+		field
+		depth int
+	}
+	var candidates []candidate
+	shallowest := make(map[string]int)
+	var walk func(typ reflect.Type, depth int)
+	walk = func(typ reflect.Type, depth int) {
+		for i := range typ.NumField() {
+			structField := typ.Field(i)
+			tag := structField.Tag.Get("json")
+			if tag == "-" {
+				continue
+			}
+			name, options, _ := strings.Cut(tag, ",")
+			if structField.Anonymous && name == "" && indirect(structField.Type).Kind() == reflect.Struct {
+				walk(indirect(structField.Type), depth+1)
+				continue
+			}
+			if !structField.IsExported() {
+				continue
+			}
+			if name == "" {
+				name = structField.Name
+			}
+			if current, ok := shallowest[name]; !ok || depth < current {
+				shallowest[name] = depth
+			}
+			candidates = append(candidates, candidate{
+				field: field{
+					name:     name,
+					typ:      structField.Type,
+					optional: strings.Contains(options, "omitempty") || strings.Contains(options, "omitzero"),
+				},
+				depth: depth,
+			})
+		}
+	}
+	walk(typ, 0)
+
+	result := make([]field, 0, len(candidates))
+	emitted := make(map[string]bool, len(candidates))
+	for _, c := range candidates {
+		if c.depth != shallowest[c.name] || emitted[c.name] {
+			continue
+		}
+		emitted[c.name] = true
+		result = append(result, c.field)
+	}
+	return result
+}
+
+// named collects the named structs reachable from roots in the order they are first reached. Structs
+// from different packages may share a name when they encode the same, as the /api/v1 types mirror the
+// internal ones.
+func named(roots []reflect.Type) ([]reflect.Type, error) {
+	var result []reflect.Type
+	seen := make(map[reflect.Type]bool)
+	names := make(map[string]reflect.Type)
+	var visit func(typ reflect.Type) error
+	visit = func(typ reflect.Type) error {
+		typ = indirect(typ)
+		switch typ.Kind() {
+		case reflect.Slice, reflect.Array, reflect.Map:
+			return visit(typ.Elem())
+		case reflect.Struct:
+		default:
+			return nil
+		}
+		if seen[typ] || isTime(typ) || implementsMarshaler(typ) {
+			return nil
+		}
+		seen[typ] = true
+		other, taken := names[typ.Name()]
+		if taken && tsObject(typ, "") != tsObject(other, "") {
+			return fmt.Errorf("%s and %s share the name %s", typ.PkgPath(), other.PkgPath(), typ.Name())
+		}
+		if typ.Name() != "" && !taken {
+			names[typ.Name()] = typ
+			result = append(result, typ)
+		}
+		for _, f := range fieldsOf(typ) {
+			if err := visit(f.typ); err != nil {
+				return err
+			}
+		}
+		return nil
+	}
+	for _, root := range roots {
+		if err := visit(root); err != nil {
+			return nil, err
+		}
+	}
+	return result, nil
+}
+
+func indirect(typ reflect.Type) reflect.Type {
+	for typ.Kind() == reflect.Pointer {
+		typ = typ.Elem()
+	}
+	return typ
+}
+
+func isTime(typ reflect.Type) bool {
+	for _, timeType := range timeTypes {
+		if typ == timeType {
+			return true
+		}
+	}
+	return false
+}
+
+func implementsMarshaler(typ reflect.Type) bool {
+	return typ.Implements(marshalerType) || reflect.PointerTo(typ).Implements(marshalerType)
+}
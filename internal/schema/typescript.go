@@ -0,0 +1,78 @@
+package schema
+
+import (
+	"fmt"
+	"io"
+	"reflect"
+	"strings"
+)
+
+// TypeScript writes an interface for every named struct reachable from roots. Named strings and numbers
+// are written as their underlying type, times as ISO 8601 strings and byte slices as base64 strings.
+func TypeScript(w io.Writer, roots ...reflect.Type) error {
+	structs, err := named(roots)
+	if err != nil {
+		return err
+	}
+
+	var b strings.Builder
+	b.WriteString("// Code generated by constellation schema --typescript. DO NOT EDIT.\n")
+	for _, typ := range structs {
+		fmt.Fprintf(&b, "\nexport interface %s %s\n", typ.Name(), tsObject(typ, ""))
+	}
+	_, err = io.WriteString(w, b.String())
+	return err
+}
+
+// tsObject writes the fields of struct typ as an object type, indented under indent
+func tsObject(typ reflect.Type, indent string) string {
+	var b strings.Builder
+	b.WriteString("{\n")
+	for _, f := range fieldsOf(typ) {
+		optional := ""
+		if f.optional || f.typ.Kind() == reflect.Pointer {
+			optional = "?"
+		}
+		fmt.Fprintf(&b, "%s  %s%s: %s\n", indent, f.name, optional, tsType(f.typ, indent+"  "))
+	}
+	b.WriteString(indent + "}")
+	return b.String()
+}
+
+func tsType(typ reflect.Type, indent string) string {
+	typ = indirect(typ)
+	if isTime(typ) {
+		return "string"
+	}
+	if implementsMarshaler(typ) {
+		return "unknown"
+	}
+
+	switch typ.Kind() {
+	case reflect.String:
+		return "string"
+	case reflect.Bool:
+		return "boolean"
+	case reflect.Int, reflect.Int8, reflect.Int16, reflect.Int32, reflect.Int64,
+		reflect.Uint, reflect.Uint8, reflect.Uint16, reflect.Uint32, reflect.Uint64,
+		reflect.Float32, reflect.Float64:
+		return "number"
+	case reflect.Slice, reflect.Array:
+		if typ.Kind() == reflect.Slice && typ.Elem().Kind() == reflect.Uint8 {
+			return "string"
+		}
+		elem := tsType(typ.Elem(), indent)
+		if strings.ContainsAny(elem, " |") {
+			return "Array<" + elem + ">"
+		}
+		return elem + "[]"
+	case reflect.Map:
+		return "Record<string, " + tsType(typ.Elem(), indent) + ">"
+	case reflect.Struct:
+		if typ.Name() == "" {
+			return tsObject(typ, indent)
+		}
+		return typ.Name()
+	}
+	return "unknown"
+}
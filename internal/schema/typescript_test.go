@@ -0,0 +1,149 @@
+package schema
+
+import (
+	"reflect"
+	"strings"
+	"testing"
+	"time"
+)
+
+type testPort struct {
+	Port int32 `json:"port"`
+}
+
+type testBase struct {
+	Name  string `json:"name"`
+	Owner string `json:"owner"`
+}
+
+type testKind string
+
+type testResource struct {
+	testBase
+	Owner    bool                `json:"owner,omitempty"`
+	Kind     testKind            `json:"kind"`
+	Ports    []testPort          `json:"ports,omitempty"`
+	Parent   *testResource       `json:"parent"`
+	Labels   map[string]string   `json:"labels,omitempty"`
+	Seen     time.Time           `json:"seen,omitzero"`
+	Data     []byte              `json:"data"`
+	Matrix   [][]float64         `json:"matrix"`
+	Inline   struct{ ID string } `json:"inline"`
+	Internal string              `json:"-"`
+}
+
+func TestTypeScript(t *testing.T) {
+	tests := []struct {
+		name  string
+		roots []reflect.Type
+		want  string
+	}{
+		{
+			name:  "structs are written in the order they are reached",
+			roots: []reflect.Type{reflect.TypeFor[testResource]()},
+			want: `// Code generated by constellation schema --typescript. DO NOT EDIT.
+
+export interface testResource {
+  name: string
+  owner?: boolean
+  kind: string
+  ports?: testPort[]
+  parent?: testResource
+  labels?: Record<string, string>
+  seen?: string
+  data: string
+  matrix: number[][]
+  inline: {
+    ID: string
+  }
+}
+
+export interface testPort {
+  port: number
+}
+`,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var b strings.Builder
+			if err := TypeScript(&b, tt.roots...); err != nil {
+				t.Fatalf("TestTypeScript() error = %v", err)
+			}
+			if got := b.String(); got != tt.want {
+				t.Errorf("TestTypeScript() = %s, want %s", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestTypeScript_WireTypes(t *testing.T) {
+	var b strings.Builder
+	if err := TypeScript(&b, Types...); err != nil {
+		t.Fatalf("TestTypeScript_WireTypes() error = %v", err)
+	}
+}
+
+func TestNamed_SharedNames(t *testing.T) {
+	type packagePorts struct {
+		Ports []testPort `json:"ports"`
+	}
+	sameShape := func() reflect.Type {
+		type testPort struct {
+			Port int32 `json:"port"`
+		}
+		return reflect.TypeFor[struct {
+			Package packagePorts `json:"package"`
+			Local   testPort     `json:"local"`
+		}]()
+	}()
+	otherShape := func() reflect.Type {
+		type testPort struct {
+			Port string `json:"port"`
+		}
+		return reflect.TypeFor[struct {
+			Package packagePorts `json:"package"`
+			Local   testPort     `json:"local"`
+		}]()
+	}()
+
+	tests := []struct {
+		name    string
+		root    reflect.Type
+		want    []string
+		wantErr string
+	}{
+		{
+			name: "structs encoding the same are written once",
+			root: sameShape,
+			want: []string{"packagePorts", "testPort"},
+		},
+		{
+			name: "structs encoding differently fail",
+			root: otherShape,
+			wantErr: "github.com/kdwils/constellation/internal/schema and github.com/kdwils/constellation/internal/schema " +
+				"share the name testPort",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := named([]reflect.Type{tt.root})
+			if tt.wantErr != "" {
+				if err == nil || err.Error() != tt.wantErr {
+					t.Fatalf("TestNamed_SharedNames() error = %v, want %v", err, tt.wantErr)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("TestNamed_SharedNames() error = %v", err)
+			}
+			var names []string
+			for _, typ := range got {
+				names = append(names, typ.Name())
+			}
+			if !reflect.DeepEqual(names, tt.want) {
+				t.Errorf("TestNamed_SharedNames() = %v, want %v", names, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,92 @@
+// Package scrub strips annotations that may carry secrets or large copies of the object, such as the
+// last applied configuration kubectl stores, from cluster objects before they are cached or served
+package scrub
+
+import (
+	"strings"
+
+	"k8s.io/apimachinery/pkg/api/meta"
+	toolscache "k8s.io/client-go/tools/cache"
+)
+
+// DefaultKeys are always scrubbed. kubectl's last applied configuration repeats the whole object, including
+// the data of a Secret applied with kubectl.
+var DefaultKeys = []string{"kubectl.kubernetes.io/last-applied-configuration"}
+
+// Scrubber removes a set of annotation keys. A key ending in * matches every annotation with that prefix,
+// e.g. vault.hashicorp.com/*.
+type Scrubber struct {
+	keys     map[string]bool
+	prefixes []string
+}
+
+// New creates a Scrubber for keys in addition to DefaultKeys
+func New(keys ...string) *Scrubber {
+	s := &Scrubber{keys: make(map[string]bool)}
+	for _, key := range append(append([]string{}, DefaultKeys...), keys...) {
+		key = strings.TrimSpace(key)
+		if prefix, ok := strings.CutSuffix(key, "*"); ok {
+			s.prefixes = append(s.prefixes, prefix)
+			continue
+		}
+		if key != "" {
+			s.keys[key] = true
+		}
+	}
+	return s
+}
+
+// Scrubbed reports whether the annotation key is removed
+func (s *Scrubber) Scrubbed(key string) bool {
+	if s.keys[key] {
+		return true
+	}
+	for _, prefix := range s.prefixes {
+		if strings.HasPrefix(key, prefix) {
+			return true
+		}
+	}
+	return false
+}
+
+// Annotations returns a copy of annotations without the scrubbed keys, or nil when none are left
+func (s *Scrubber) Annotations(annotations map[string]string) map[string]string {
+	result := make(map[string]string, len(annotations))
+	for key, value := range annotations {
+		if s.Scrubbed(key) {
+			continue
+		}
+		result[key] = value
+	}
+	if len(result) == 0 {
+		return nil
+	}
+	return result
+}
+
+// Transform is a cache transform removing the scrubbed annotations from every object before it is stored,
+// so they are never held in memory or read back by the hierarchy
+func (s *Scrubber) Transform(obj any) (any, error) {
+	return s.scrub(obj), nil
+}
+
+// Then scrubs objects before passing them to next, for kinds whose cache transform replaces Transform
+func (s *Scrubber) Then(next toolscache.TransformFunc) toolscache.TransformFunc {
+	return func(obj any) (any, error) {
+		return next(s.scrub(obj))
+	}
+}
+
+func (s *Scrubber) scrub(obj any) any {
+	object, err := meta.Accessor(obj)
+	if err != nil {
+		return obj
+	}
+	annotations := object.GetAnnotations()
+	for key := range annotations {
+		if s.Scrubbed(key) {
+			delete(annotations, key)
+		}
+	}
+	return obj
+}
@@ -0,0 +1,106 @@
+package scrub_test
+
+import (
+	"reflect"
+	"testing"
+
+	corev1 "k8s.io/api/core/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+
+	"github.com/kdwils/constellation/internal/scrub"
+)
+
+func TestScrubber_Annotations(t *testing.T) {
+	tests := []struct {
+		name        string
+		keys        []string
+		annotations map[string]string
+		want        map[string]string
+	}{
+		{
+			name: "last applied configuration is always scrubbed",
+			annotations: map[string]string{
+				corev1.LastAppliedConfigAnnotation: "{}",
+				"owner":                            "payments",
+			},
+			want: map[string]string{"owner": "payments"},
+		},
+		{
+			name: "configured keys and prefixes are scrubbed",
+			keys: []string{"example.com/token", "vault.hashicorp.com/*"},
+			annotations: map[string]string{
+				"example.com/token":                  "s3cret",
+				"example.com/team":                   "payments",
+				"vault.hashicorp.com/agent-inject":   "true",
+				"vault.hashicorp.com/secret-payload": "s3cret",
+			},
+			want: map[string]string{"example.com/team": "payments"},
+		},
+		{
+			name:        "nothing left is nil",
+			keys:        []string{"example.com/token"},
+			annotations: map[string]string{"example.com/token": "s3cret"},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := scrub.New(tt.keys...).Annotations(tt.annotations)
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestScrubber_Annotations() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestScrubber_Transform(t *testing.T) {
+	pod := func(annotations map[string]string) *corev1.Pod {
+		return &corev1.Pod{ObjectMeta: metav1.ObjectMeta{Name: "web-0", Namespace: "shop", Annotations: annotations}}
+	}
+	labelled := func(obj any) (any, error) {
+		pod := obj.(*corev1.Pod).DeepCopy()
+		pod.Labels = map[string]string{"slim": "true"}
+		return pod, nil
+	}
+
+	tests := []struct {
+		name      string
+		transform func(scrubber *scrub.Scrubber) func(any) (any, error)
+		obj       any
+		want      any
+	}{
+		{
+			name:      "annotations are removed before the object is cached",
+			transform: func(s *scrub.Scrubber) func(any) (any, error) { return s.Transform },
+			obj:       pod(map[string]string{"example.com/token": "s3cret", "owner": "payments"}),
+			want:      pod(map[string]string{"owner": "payments"}),
+		},
+		{
+			name:      "annotations are removed before the next transform",
+			transform: func(s *scrub.Scrubber) func(any) (any, error) { return s.Then(labelled) },
+			obj:       pod(map[string]string{"example.com/token": "s3cret"}),
+			want: &corev1.Pod{ObjectMeta: metav1.ObjectMeta{
+				Name:        "web-0",
+				Namespace:   "shop",
+				Labels:      map[string]string{"slim": "true"},
+				Annotations: map[string]string{},
+			}},
+		},
+		{
+			name:      "objects without metadata are passed through",
+			transform: func(s *scrub.Scrubber) func(any) (any, error) { return s.Transform },
+			obj:       "tombstone",
+			want:      "tombstone",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := tt.transform(scrub.New("example.com/token"))(tt.obj)
+			if err != nil {
+				t.Fatalf("TestScrubber_Transform() error = %v", err)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestScrubber_Transform() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,128 @@
+package server
+
+import (
+	"bytes"
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"reflect"
+	"strings"
+	"testing"
+
+	"github.com/gorilla/websocket"
+	"sigs.k8s.io/controller-runtime/pkg/log"
+	"sigs.k8s.io/controller-runtime/pkg/log/zap"
+)
+
+func TestAccessLog(t *testing.T) {
+	tests := []struct {
+		name       string
+		path       string
+		websocket  bool
+		wantRoute  string
+		wantPath   string
+		wantStatus int
+	}{
+		{
+			name:       "status written by the handler",
+			path:       "/items/7?verbose=true",
+			wantRoute:  "GET /items/{id}",
+			wantPath:   "/items/7",
+			wantStatus: http.StatusCreated,
+		},
+		{
+			name:       "body without a status is logged as ok",
+			path:       "/body",
+			wantRoute:  "/body",
+			wantPath:   "/body",
+			wantStatus: http.StatusOK,
+		},
+		{
+			name:       "unmatched path has no route",
+			path:       "/missing",
+			wantRoute:  "",
+			wantPath:   "/missing",
+			wantStatus: http.StatusNotFound,
+		},
+		{
+			name:       "hijacked websocket upgrade is logged as switching protocols",
+			path:       "/ws",
+			websocket:  true,
+			wantRoute:  "/ws",
+			wantPath:   "/ws",
+			wantStatus: http.StatusSwitchingProtocols,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			mux := http.NewServeMux()
+			mux.HandleFunc("GET /items/{id}", func(w http.ResponseWriter, r *http.Request) {
+				w.WriteHeader(http.StatusCreated)
+			})
+			mux.HandleFunc("/body", func(w http.ResponseWriter, r *http.Request) {
+				w.Write([]byte("ok")) //nolint:errcheck
+			})
+			mux.HandleFunc("/ws", func(w http.ResponseWriter, r *http.Request) {
+				conn, err := (&websocket.Upgrader{}).Upgrade(w, r, nil)
+				if err != nil {
+					return
+				}
+				conn.Close() //nolint:errcheck
+			})
+
+			var buf bytes.Buffer
+			logger := zap.New(zap.WriteTo(&buf))
+			logged := make(chan struct{})
+			handler := accessLog(mux, mux)
+			server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+				defer close(logged)
+				handler.ServeHTTP(w, r.WithContext(log.IntoContext(r.Context(), logger)))
+			}))
+			defer server.Close()
+
+			if tt.websocket {
+				conn, _, err := websocket.DefaultDialer.Dial("ws"+strings.TrimPrefix(server.URL, "http")+tt.path, nil)
+				if err != nil {
+					t.Fatalf("TestAccessLog() dial error = %v", err)
+				}
+				conn.Close() //nolint:errcheck
+			}
+			if !tt.websocket {
+				resp, err := http.Get(server.URL + tt.path)
+				if err != nil {
+					t.Fatalf("TestAccessLog() request error = %v", err)
+				}
+				resp.Body.Close() //nolint:errcheck
+			}
+			<-logged
+
+			lines := strings.Split(strings.TrimSpace(buf.String()), "\n")
+			if len(lines) != 1 {
+				t.Fatalf("TestAccessLog() logged %d lines, want 1: %s", len(lines), buf.String())
+			}
+			var entry map[string]any
+			if err := json.Unmarshal([]byte(lines[0]), &entry); err != nil {
+				t.Fatalf("TestAccessLog() log line %q is not JSON: %v", lines[0], err)
+			}
+			if latency, ok := entry["latency"].(float64); !ok || latency < 0 {
+				t.Errorf("TestAccessLog() latency = %v, want a non-negative duration", entry["latency"])
+			}
+			want := map[string]any{
+				"logger": "access",
+				"msg":    "request",
+				"method": http.MethodGet,
+				"route":  tt.wantRoute,
+				"path":   tt.wantPath,
+				"status": float64(tt.wantStatus),
+				"client": "127.0.0.1",
+			}
+			got := make(map[string]any, len(want))
+			for key := range want {
+				got[key] = entry[key]
+			}
+			if !reflect.DeepEqual(got, want) {
+				t.Errorf("TestAccessLog() = %v, want %v", got, want)
+			}
+		})
+	}
+}
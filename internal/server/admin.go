@@ -0,0 +1,216 @@
+package server
+
+import (
+	"crypto/subtle"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"net/http"
+	"strings"
+
+	"github.com/kdwils/constellation/internal/types"
+	"github.com/kdwils/constellation/internal/watches"
+)
+
+// admin rate limits handler and requires the admin bearer token
+func (s *Server) admin(handler http.HandlerFunc) http.Handler {
+	return s.limit(func(w http.ResponseWriter, r *http.Request) {
+		if !s.authorized(r) {
+			w.Header().Set("WWW-Authenticate", "Bearer")
+			http.Error(w, "unauthorized", http.StatusUnauthorized)
+			return
+		}
+		handler(w, r)
+	})
+}
+
+// authorized reports whether r carries the admin bearer token of an enabled /admin API
+func (s *Server) authorized(r *http.Request) bool {
+	if s.adminProvider == nil || s.adminToken == "" {
+		return false
+	}
+	token, found := strings.CutPrefix(r.Header.Get("Authorization"), "Bearer ")
+	return found && subtle.ConstantTimeCompare([]byte(token), []byte(s.adminToken)) == 1
+}
+
+// handleMe reports what the caller may do, so the frontend can hide the controls they cannot use. The
+// admin capabilities need the admin bearer token; every caller sees all namespaces, since nothing is
+// scoped per caller.
+func (s *Server) handleMe(w http.ResponseWriter, r *http.Request) {
+	admin := s.authorized(r)
+	identity := s.identity(r)
+	capabilities := types.Capabilities{
+		Identity:             identity,
+		Authenticated:        identity != anonymous,
+		CanUseAdmin:          admin,
+		CanResync:            admin,
+		CanViewAllNamespaces: true,
+		CanPinFavorites:      s.favoritesProvider != nil,
+		CanPostAnnotations:   s.annotationProvider != nil,
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	w.Header().Set("Cache-Control", "no-store")
+	if err := json.NewEncoder(w).Encode(capabilities); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+// handleWatches lists the optional resource watches and whether each is enabled
+func (s *Server) handleWatches(w http.ResponseWriter, r *http.Request) {
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(s.adminProvider.Status()); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+// handleSetWatch enables or disables one watch from a PUT types.WatchStatus body, e.g. {"enabled": false}
+func (s *Server) handleSetWatch(w http.ResponseWriter, r *http.Request) {
+	if r.Method != http.MethodPut {
+		http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+		return
+	}
+
+	var toggle types.WatchStatus
+	if err := json.NewDecoder(r.Body).Decode(&toggle); err != nil {
+		http.Error(w, fmt.Sprintf("invalid watch toggle: %v", err), http.StatusBadRequest)
+		return
+	}
+
+	err := s.adminProvider.Set(r.Context(), r.PathValue("resource"), toggle.Enabled)
+	if errors.Is(err, watches.ErrUnknownResource) {
+		http.Error(w, err.Error(), http.StatusNotFound)
+		return
+	}
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	s.handleWatches(w, r)
+}
+
+// handleResync restarts the optional watches and reconciles every Service again
+func (s *Server) handleResync(w http.ResponseWriter, r *http.Request) {
+	if r.Method != http.MethodPost {
+		http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+		return
+	}
+
+	result, err := s.adminProvider.Resync(r.Context())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(result); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+// handleStores reports the count and readiness of every cached resource
+func (s *Server) handleStores(w http.ResponseWriter, r *http.Request) {
+	statuses := make([]types.StoreStatus, 0, len(watches.Stores()))
+	for _, resource := range watches.Stores() {
+		status, err := s.adminProvider.Inspect(r.Context(), resource, false)
+		if err != nil {
+			http.Error(w, err.Error(), http.StatusInternalServerError)
+			return
+		}
+		statuses = append(statuses, status)
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(statuses); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+// handleStore reports one cached resource, with ?objects=true adding every object's resourceVersion
+func (s *Server) handleStore(w http.ResponseWriter, r *http.Request) {
+	status, err := s.adminProvider.Inspect(r.Context(), r.PathValue("resource"), r.URL.Query().Get("objects") == "true")
+	if errors.Is(err, watches.ErrUnknownResource) {
+		http.Error(w, err.Error(), http.StatusNotFound)
+		return
+	}
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(status); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+// handleClients lists the connected WebSocket clients with their heartbeat and backlog
+func (s *Server) handleClients(w http.ResponseWriter, r *http.Request) {
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(s.clients.list()); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+// handleMemory estimates the memory held by every cached resource
+func (s *Server) handleMemory(w http.ResponseWriter, r *http.Request) {
+	stores := make([]types.StoreMemory, 0, len(watches.Stores()))
+	for _, resource := range watches.Stores() {
+		memory, err := s.adminProvider.Memory(r.Context(), resource)
+		if err != nil {
+			http.Error(w, err.Error(), http.StatusInternalServerError)
+			return
+		}
+		stores = append(stores, memory)
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(stores); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+// handleStoreMemory estimates the memory held by one cached resource. A POST first drops the store and
+// starts listing it again, so the estimate that follows is of the store as it refills.
+func (s *Server) handleStoreMemory(w http.ResponseWriter, r *http.Request) {
+	resource := r.PathValue("resource")
+	if r.Method == http.MethodPost {
+		err := s.adminProvider.Shrink(r.Context(), resource)
+		if errors.Is(err, watches.ErrUnknownResource) {
+			http.Error(w, err.Error(), http.StatusNotFound)
+			return
+		}
+		if errors.Is(err, watches.ErrAlwaysWatched) {
+			http.Error(w, err.Error(), http.StatusConflict)
+			return
+		}
+		if err != nil {
+			http.Error(w, err.Error(), http.StatusInternalServerError)
+			return
+		}
+	}
+
+	memory, err := s.adminProvider.Memory(r.Context(), resource)
+	if errors.Is(err, watches.ErrUnknownResource) {
+		http.Error(w, err.Error(), http.StatusNotFound)
+		return
+	}
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(memory); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
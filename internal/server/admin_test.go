@@ -0,0 +1,65 @@
+package server
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"testing"
+
+	"github.com/kdwils/constellation/internal/favorites"
+)
+
+// fakeAdmin enables the /admin API without serving any of it
+type fakeAdmin struct{ AdminProvider }
+
+func TestHandleMe(t *testing.T) {
+	tests := []struct {
+		name          string
+		opts          []ServerOpt
+		authorization string
+		want          string
+	}{
+		{
+			name: "anonymous caller without optional features",
+			want: `{"identity":"anonymous","authenticated":false,"can_use_admin":false,"can_resync":false,` +
+				`"can_view_all_namespaces":true,"can_pin_favorites":false,"can_post_annotations":false}` + "\n",
+		},
+		{
+			name:          "admin token",
+			opts:          []ServerOpt{WithAdminProvider(fakeAdmin{}, "s3cret")},
+			authorization: "Bearer s3cret",
+			want: `{"identity":"admin","authenticated":true,"can_use_admin":true,"can_resync":true,` +
+				`"can_view_all_namespaces":true,"can_pin_favorites":false,"can_post_annotations":false}` + "\n",
+		},
+		{
+			name:          "admin token while the admin API is disabled",
+			opts:          []ServerOpt{WithAdminProvider(nil, "s3cret")},
+			authorization: "Bearer s3cret",
+			want: `{"identity":"anonymous","authenticated":false,"can_use_admin":false,"can_resync":false,` +
+				`"can_view_all_namespaces":true,"can_pin_favorites":false,"can_post_annotations":false}` + "\n",
+		},
+		{
+			name:          "wrong token",
+			opts:          []ServerOpt{WithAdminProvider(fakeAdmin{}, "s3cret"), WithFavoritesProvider(favorites.NewStore(nil))},
+			authorization: "Bearer guess",
+			want: `{"identity":"anonymous","authenticated":false,"can_use_admin":false,"can_resync":false,` +
+				`"can_view_all_namespaces":true,"can_pin_favorites":true,"can_post_annotations":false}` + "\n",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			req := httptest.NewRequest(http.MethodGet, "/api/v1/me", nil)
+			if tt.authorization != "" {
+				req.Header.Set("Authorization", tt.authorization)
+			}
+			recorder := httptest.NewRecorder()
+			New(fakeHealth{}, tt.opts...).Handler().ServeHTTP(recorder, req)
+
+			if recorder.Code != http.StatusOK {
+				t.Fatalf("TestHandleMe() status = %v, want %v", recorder.Code, http.StatusOK)
+			}
+			if got := recorder.Body.String(); got != tt.want {
+				t.Errorf("TestHandleMe() = %s, want %s", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,70 @@
+package server
+
+import (
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// fakeAnnotations keeps nothing and returns each added annotation with data as its marker's data
+type fakeAnnotations struct {
+	data json.RawMessage
+}
+
+func (f fakeAnnotations) Add(annotation types.Annotation) types.Annotation {
+	annotation.Marker.Data = f.data
+	annotation.Marker.Time = time.Date(2025, 1, 1, 0, 0, 0, 0, time.UTC)
+	return annotation
+}
+func (fakeAnnotations) Annotations() []types.Annotation     { return nil }
+func (fakeAnnotations) Subscribe() chan []types.Annotation  { return make(chan []types.Annotation) }
+func (fakeAnnotations) Unsubscribe(chan []types.Annotation) {}
+
+func TestHandleAnnotations_Post(t *testing.T) {
+	tests := []struct {
+		name       string
+		data       json.RawMessage
+		wantStatus int
+		wantBody   string
+	}{
+		{
+			name:       "added annotation is accepted",
+			data:       json.RawMessage(`"deployed"`),
+			wantStatus: http.StatusAccepted,
+			wantBody: `{"kind":"Deployment","namespace":"shop","name":"web","marker":{"id":"1","source":"ci",` +
+				`"type":"deploy","data":"deployed","time":"2025-01-01T00:00:00Z"}}` + "\n",
+		},
+		{
+			name:       "unencodable annotation is a server error",
+			data:       json.RawMessage(`{`),
+			wantStatus: http.StatusInternalServerError,
+			wantBody:   "json: error calling MarshalJSON for type json.RawMessage: unexpected end of JSON input\n",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			handler := New(fakeHealth{}, WithAnnotationProvider(fakeAnnotations{data: tt.data})).Handler()
+			req := httptest.NewRequest(http.MethodPost, "/api/v1/annotations", strings.NewReader("deployed"))
+			req.Header.Set("ce-specversion", "1.0")
+			req.Header.Set("ce-id", "1")
+			req.Header.Set("ce-source", "ci")
+			req.Header.Set("ce-type", "deploy")
+			req.Header.Set("ce-subject", "Deployment/shop/web")
+			req.Header.Set("Content-Type", "text/plain")
+			recorder := httptest.NewRecorder()
+			handler.ServeHTTP(recorder, req)
+
+			if recorder.Code != tt.wantStatus {
+				t.Errorf("TestHandleAnnotations_Post() status = %v, want %v", recorder.Code, tt.wantStatus)
+			}
+			if got := recorder.Body.String(); got != tt.wantBody {
+				t.Errorf("TestHandleAnnotations_Post() body = %q, want %q", got, tt.wantBody)
+			}
+		})
+	}
+}
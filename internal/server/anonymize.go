@@ -0,0 +1,83 @@
+package server
+
+import (
+	"bytes"
+	"encoding/json"
+	"net/http"
+	"strconv"
+	"strings"
+
+	"github.com/gorilla/websocket"
+
+	"github.com/kdwils/constellation/internal/anonymize"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// bufferedWriter holds a response back so its body can be rewritten before it is sent
+type bufferedWriter struct {
+	http.ResponseWriter
+	status int
+	body   bytes.Buffer
+}
+
+func (w *bufferedWriter) WriteHeader(status int) {
+	if w.status == 0 {
+		w.status = status
+	}
+}
+
+func (w *bufferedWriter) Write(data []byte) (int, error) {
+	return w.body.Write(data)
+}
+
+// anonymizeResponses replaces the names, hostnames and IPs in every JSON response. WebSocket upgrades
+// pass through, their messages are anonymized as they are written and followed nodes as the node feed
+// builds them.
+func (s *Server) anonymizeResponses(next http.Handler) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if websocket.IsWebSocketUpgrade(r) {
+			next.ServeHTTP(w, r)
+			return
+		}
+
+		buffered := &bufferedWriter{ResponseWriter: w}
+		next.ServeHTTP(buffered, r)
+		if buffered.status == 0 {
+			buffered.status = http.StatusOK
+		}
+
+		body := buffered.body.Bytes()
+		if strings.HasPrefix(w.Header().Get("Content-Type"), "application/json") && len(body) > 0 {
+			anonymized, err := s.anonymizer.JSON(body)
+			if err != nil {
+				http.Error(w, err.Error(), http.StatusInternalServerError)
+				return
+			}
+			body = append(anonymized, '\n')
+		}
+		w.Header().Set("Content-Length", strconv.Itoa(len(body)))
+		w.WriteHeader(buffered.status)
+		w.Write(body) //nolint:errcheck
+	})
+}
+
+// anonymizeNodes replaces the names, hostnames and IPs in nodes, for output that is not JSON such as
+// the diagram exports, and for the node feed, whose clients follow the anonymized IDs
+func anonymizeNodes(anonymizer *anonymize.Anonymizer, nodes []types.HierarchyNode) ([]types.HierarchyNode, error) {
+	if anonymizer == nil {
+		return nodes, nil
+	}
+	payload, err := json.Marshal(nodes)
+	if err != nil {
+		return nil, err
+	}
+	anonymized, err := anonymizer.JSON(payload)
+	if err != nil {
+		return nil, err
+	}
+	var result []types.HierarchyNode
+	if err := json.Unmarshal(anonymized, &result); err != nil {
+		return nil, err
+	}
+	return result, nil
+}
@@ -0,0 +1,110 @@
+package server
+
+import (
+	"encoding/json"
+	"net/http"
+	"sync"
+	"time"
+
+	"sigs.k8s.io/controller-runtime/pkg/log"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// auditCapacity is how many audit entries /admin/audit keeps before dropping the oldest
+const auditCapacity = 1000
+
+// anonymous is the identity of callers without the admin token or an identity header
+const anonymous = "anonymous"
+
+// auditTrail keeps the most recent changes made through the API for /admin/audit
+type auditTrail struct {
+	now func() time.Time
+
+	mu      sync.Mutex
+	entries []types.AuditEntry
+}
+
+func newAuditTrail(now func() time.Time) *auditTrail {
+	return &auditTrail{now: now}
+}
+
+func (a *auditTrail) record(entry types.AuditEntry) {
+	a.mu.Lock()
+	defer a.mu.Unlock()
+
+	entry.Time = a.now()
+	a.entries = append(a.entries, entry)
+	if len(a.entries) > auditCapacity {
+		a.entries = a.entries[len(a.entries)-auditCapacity:]
+	}
+}
+
+// list returns the kept entries, oldest first
+func (a *auditTrail) list() []types.AuditEntry {
+	a.mu.Lock()
+	defer a.mu.Unlock()
+	return append([]types.AuditEntry{}, a.entries...)
+}
+
+// identity names the caller of r: the user an authenticating proxy set in the identity header, admin for
+// the admin bearer token, or anonymous. The header is only read when the admin API is enabled, since it is
+// only trustworthy behind a proxy that sets it.
+func (s *Server) identity(r *http.Request) string {
+	if s.adminToken == "" {
+		return anonymous
+	}
+	if s.identityHeader != "" {
+		if user := r.Header.Get(s.identityHeader); user != "" {
+			return user
+		}
+	}
+	if s.authorized(r) {
+		return "admin"
+	}
+	return anonymous
+}
+
+// audited records every change handler makes, i.e. each request other than a GET or HEAD, with the
+// caller's identity, and adds the identity to the request's logger. It passes requests straight through
+// while the admin API, which serves the trail, is off.
+func (s *Server) audited(action string, handler http.HandlerFunc) http.HandlerFunc {
+	if s.adminProvider == nil || s.adminToken == "" {
+		return handler
+	}
+	return func(w http.ResponseWriter, r *http.Request) {
+		if r.Method == http.MethodGet || r.Method == http.MethodHead {
+			handler(w, r)
+			return
+		}
+
+		identity := s.identity(r)
+		logger := log.FromContext(r.Context()).WithValues("identity", identity)
+		r = r.WithContext(log.IntoContext(r.Context(), logger))
+		recorder := &statusRecorder{ResponseWriter: w}
+		handler(recorder, r)
+
+		status := recorder.status
+		if status == 0 {
+			status = http.StatusOK
+		}
+		s.audit.record(types.AuditEntry{
+			Identity: identity,
+			Action:   action,
+			Method:   r.Method,
+			Path:     r.URL.Path,
+			Status:   status,
+			Client:   remoteIP(r),
+		})
+		logger.WithName("audit").Info(action, "method", r.Method, "path", r.URL.Path, "status", status)
+	}
+}
+
+// handleAudit lists the recorded changes, oldest first
+func (s *Server) handleAudit(w http.ResponseWriter, r *http.Request) {
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(s.audit.list()); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
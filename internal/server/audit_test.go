@@ -0,0 +1,123 @@
+package server
+
+import (
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"reflect"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/kdwils/constellation/internal/favorites"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func TestAudited(t *testing.T) {
+	now := time.Date(2025, 1, 1, 12, 0, 0, 0, time.UTC)
+
+	type request struct {
+		method  string
+		path    string
+		body    string
+		headers map[string]string
+	}
+
+	tests := []struct {
+		name     string
+		requests []request
+		want     []types.AuditEntry
+	}{
+		{
+			name: "changes are recorded with the proxy's user",
+			requests: []request{
+				{
+					method:  http.MethodPost,
+					path:    "/favorites",
+					body:    `{"id":"Service/shop/web"}`,
+					headers: map[string]string{"X-Forwarded-User": "alice@example.com"},
+				},
+				{method: http.MethodGet, path: "/favorites"},
+				{method: http.MethodDelete, path: "/favorites/Service/shop/web"},
+			},
+			want: []types.AuditEntry{
+				{
+					Time:     now,
+					Identity: "alice@example.com",
+					Action:   "pin",
+					Method:   http.MethodPost,
+					Path:     "/favorites",
+					Status:   http.StatusOK,
+					Client:   "192.0.2.1",
+				},
+				{
+					Time:     now,
+					Identity: anonymous,
+					Action:   "unpin",
+					Method:   http.MethodDelete,
+					Path:     "/favorites/Service/shop/web",
+					Status:   http.StatusOK,
+					Client:   "192.0.2.1",
+				},
+			},
+		},
+		{
+			name: "admin token without a proxy user",
+			requests: []request{{
+				method:  http.MethodPost,
+				path:    "/favorites",
+				body:    `{}`,
+				headers: map[string]string{"Authorization": "Bearer s3cret"},
+			}},
+			want: []types.AuditEntry{{
+				Time:     now,
+				Identity: "admin",
+				Action:   "pin",
+				Method:   http.MethodPost,
+				Path:     "/favorites",
+				Status:   http.StatusBadRequest,
+				Client:   "192.0.2.1",
+			}},
+		},
+		{
+			name:     "reads are not recorded",
+			requests: []request{{method: http.MethodGet, path: "/favorites"}},
+			want:     []types.AuditEntry{},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			s := New(fakeHealth{},
+				WithAdminProvider(fakeAdmin{}, "s3cret"),
+				WithIdentityHeader("X-Forwarded-User"),
+				WithFavoritesProvider(favorites.NewStore(nil)),
+			)
+			s.audit = newAuditTrail(func() time.Time { return now })
+			handler := s.Handler()
+
+			for _, req := range tt.requests {
+				r := httptest.NewRequest(req.method, req.path, strings.NewReader(req.body))
+				for name, value := range req.headers {
+					r.Header.Set(name, value)
+				}
+				handler.ServeHTTP(httptest.NewRecorder(), r)
+			}
+
+			r := httptest.NewRequest(http.MethodGet, "/admin/audit", nil)
+			r.Header.Set("Authorization", "Bearer s3cret")
+			recorder := httptest.NewRecorder()
+			handler.ServeHTTP(recorder, r)
+			if recorder.Code != http.StatusOK {
+				t.Fatalf("TestAudited() status = %v, want %v", recorder.Code, http.StatusOK)
+			}
+
+			var got []types.AuditEntry
+			if err := json.Unmarshal(recorder.Body.Bytes(), &got); err != nil {
+				t.Fatalf("TestAudited() error = %v", err)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestAudited() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
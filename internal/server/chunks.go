@@ -0,0 +1,58 @@
+package server
+
+import (
+	"encoding/json"
+	"time"
+
+	"github.com/gorilla/websocket"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const (
+	// chunkOverhead is room for the ChunkedMessage envelope around the encoded data
+	chunkOverhead = 128
+	// minMessageSizeLimit keeps chunks large enough to make progress
+	minMessageSizeLimit = 1024
+)
+
+// splitMessage splits payload into ChunkedMessages no larger than limit once encoded. The data is
+// base64 encoded, so each chunk carries three bytes of payload for every four of its limit.
+// A payload within the limit, or a limit of zero, is returned as the only message.
+func splitMessage(id uint64, payload []byte, limit int) ([][]byte, error) {
+	if limit <= 0 || len(payload) <= limit {
+		return [][]byte{payload}, nil
+	}
+
+	size := (max(limit, minMessageSizeLimit) - chunkOverhead) / 4 * 3
+	total := (len(payload) + size - 1) / size
+	messages := make([][]byte, 0, total)
+	for index := range total {
+		end := min((index+1)*size, len(payload))
+		message, err := json.Marshal(types.ChunkedMessage{Chunk: types.MessageChunk{
+			ID:    id,
+			Index: index,
+			Total: total,
+			Data:  payload[index*size : end],
+		}})
+		if err != nil {
+			return nil, err
+		}
+		messages = append(messages, message)
+	}
+	return messages, nil
+}
+
+// writePayload writes payload as one text message, or as chunks when it exceeds the message size limit
+func (s *Server) writePayload(conn *websocket.Conn, payload []byte) error {
+	messages, err := splitMessage(s.chunkIDs.Add(1), payload, s.messageSizeLimit)
+	if err != nil {
+		return err
+	}
+	for _, message := range messages {
+		conn.SetWriteDeadline(time.Now().Add(writeWait))
+		if err := conn.WriteMessage(websocket.TextMessage, message); err != nil {
+			return err
+		}
+	}
+	return nil
+}
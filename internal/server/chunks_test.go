@@ -0,0 +1,84 @@
+package server
+
+import (
+	"bytes"
+	"encoding/json"
+	"reflect"
+	"testing"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func TestSplitMessage(t *testing.T) {
+	payload := bytes.Repeat([]byte(`{"service_name":"web","status":"healthy"}`), 100)
+
+	tests := []struct {
+		name       string
+		payload    []byte
+		limit      int
+		wantTotal  int
+		wantChunks bool
+	}{
+		{
+			name:      "no limit sends the payload whole",
+			payload:   payload,
+			wantTotal: 1,
+		},
+		{
+			name:      "payload within the limit is sent whole",
+			payload:   payload,
+			limit:     len(payload),
+			wantTotal: 1,
+		},
+		{
+			name:       "payload over the limit is chunked",
+			payload:    payload,
+			limit:      1024,
+			wantTotal:  7,
+			wantChunks: true,
+		},
+		{
+			name:       "limit below the minimum is raised",
+			payload:    payload,
+			limit:      10,
+			wantTotal:  7,
+			wantChunks: true,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			messages, err := splitMessage(7, tt.payload, tt.limit)
+			if err != nil {
+				t.Fatalf("TestSplitMessage() error = %v", err)
+			}
+			if len(messages) != tt.wantTotal {
+				t.Fatalf("TestSplitMessage() messages = %d, want %d", len(messages), tt.wantTotal)
+			}
+			if !tt.wantChunks {
+				if !bytes.Equal(messages[0], tt.payload) {
+					t.Errorf("TestSplitMessage() = %s, want the payload", messages[0])
+				}
+				return
+			}
+
+			var reassembled []byte
+			for index, message := range messages {
+				if len(message) > max(tt.limit, minMessageSizeLimit) {
+					t.Errorf("TestSplitMessage() chunk %d is %d bytes, over the limit", index, len(message))
+				}
+				var chunked types.ChunkedMessage
+				if err := json.Unmarshal(message, &chunked); err != nil {
+					t.Fatalf("TestSplitMessage() unmarshal error = %v", err)
+				}
+				want := types.MessageChunk{ID: 7, Index: index, Total: tt.wantTotal, Data: chunked.Chunk.Data}
+				if !reflect.DeepEqual(chunked.Chunk, want) {
+					t.Errorf("TestSplitMessage() chunk = %+v, want %+v", chunked.Chunk, want)
+				}
+				reassembled = append(reassembled, chunked.Chunk.Data...)
+			}
+			if !bytes.Equal(reassembled, tt.payload) {
+				t.Errorf("TestSplitMessage() reassembled %d bytes, want %d", len(reassembled), len(tt.payload))
+			}
+		})
+	}
+}
@@ -0,0 +1,125 @@
+package server
+
+import (
+	"sort"
+	"strconv"
+	"sync"
+	"time"
+
+	"github.com/gorilla/websocket"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// clients tracks the connected WebSocket clients for /admin/clients
+type clients struct {
+	now func() time.Time
+
+	mu        sync.Mutex
+	nextID    uint64
+	connected map[uint64]*client
+}
+
+// client is one WebSocket connection. queued reports how many updates wait in its subscription.
+type client struct {
+	now    func() time.Time
+	queued func() int
+
+	mu   sync.Mutex
+	info types.ClientInfo
+}
+
+func newClients(now func() time.Time) *clients {
+	return &clients{now: now, connected: make(map[uint64]*client)}
+}
+
+// connect registers a client on stream; the caller must disconnect it when the connection ends
+func (c *clients) connect(stream, remote string, queued func() int) *client {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	c.nextID++
+	connection := &client{
+		now:    c.now,
+		queued: queued,
+		info:   types.ClientInfo{ID: c.nextID, Stream: stream, Remote: remote, ConnectedAt: c.now()},
+	}
+	c.connected[connection.info.ID] = connection
+	return connection
+}
+
+func (c *clients) disconnect(connection *client) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	delete(c.connected, connection.info.ID)
+}
+
+// list reports every connected client in connection order
+func (c *clients) list() []types.ClientInfo {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	infos := make([]types.ClientInfo, 0, len(c.connected))
+	for _, connection := range c.connected {
+		infos = append(infos, connection.snapshot())
+	}
+	sort.Slice(infos, func(i, j int) bool { return infos[i].ID < infos[j].ID })
+	return infos
+}
+
+func (c *client) snapshot() types.ClientInfo {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	info := c.info
+	if c.queued != nil {
+		info.Queued = c.queued()
+	}
+	return info
+}
+
+// sent records a message delivered to the client
+func (c *client) sent() {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	c.info.MessagesSent++
+	c.info.LastSentAt = c.now()
+}
+
+// filter records what the client subscribed to, e.g. the node a /ws/node client follows
+func (c *client) filter(filter string) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	c.info.Filter = filter
+}
+
+// pong records a heartbeat answer. The ping carried its send time, so the round trip is measured
+// without keeping state per ping.
+func (c *client) pong(data string) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	now := c.now()
+	c.info.LastPongAt = now
+	sentAt, err := strconv.ParseInt(data, 10, 64)
+	if err != nil {
+		return
+	}
+	c.info.PingRTT = now.Sub(time.Unix(0, sentAt))
+}
+
+// heartbeat applies the read limits and extends the read deadline on every pong from the client
+func (s *Server) heartbeat(conn *websocket.Conn, connection *client) {
+	conn.SetReadLimit(maxMessageSize)
+	conn.SetReadDeadline(time.Now().Add(pongWait))
+	conn.SetPongHandler(func(data string) error {
+		connection.pong(data)
+		conn.SetReadDeadline(time.Now().Add(pongWait))
+		return nil
+	})
+}
+
+// ping sends a heartbeat carrying its send time for the client to echo back
+func (s *Server) ping(conn *websocket.Conn) error {
+	conn.SetWriteDeadline(time.Now().Add(writeWait))
+	return conn.WriteMessage(websocket.PingMessage, []byte(strconv.FormatInt(s.clients.now().UnixNano(), 10)))
+}
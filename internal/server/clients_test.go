@@ -0,0 +1,73 @@
+package server
+
+import (
+	"reflect"
+	"strconv"
+	"testing"
+	"time"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func TestClients_List(t *testing.T) {
+	connectedAt := time.Date(2025, 1, 1, 0, 0, 0, 0, time.UTC)
+	later := connectedAt.Add(time.Minute)
+
+	tests := []struct {
+		name   string
+		events func(registry *clients, now *time.Time)
+		want   []types.ClientInfo
+	}{
+		{
+			name: "connected clients are listed in connection order",
+			events: func(registry *clients, now *time.Time) {
+				registry.connect("/ws", "10.0.0.1", nil)
+				registry.connect("/ws/endpoints", "10.0.0.2", func() int { return 1 })
+			},
+			want: []types.ClientInfo{
+				{ID: 1, Stream: "/ws", Remote: "10.0.0.1", ConnectedAt: connectedAt},
+				{ID: 2, Stream: "/ws/endpoints", Remote: "10.0.0.2", ConnectedAt: connectedAt, Queued: 1},
+			},
+		},
+		{
+			name: "messages, filters and heartbeats are recorded",
+			events: func(registry *clients, now *time.Time) {
+				connection := registry.connect("/ws/node", "10.0.0.1", nil)
+				connection.filter("Service/prod/api")
+				sentAt := *now
+				*now = later
+				connection.sent()
+				connection.pong(strconv.FormatInt(sentAt.Add(59*time.Second).UnixNano(), 10))
+			},
+			want: []types.ClientInfo{{
+				ID:           1,
+				Stream:       "/ws/node",
+				Remote:       "10.0.0.1",
+				ConnectedAt:  connectedAt,
+				Filter:       "Service/prod/api",
+				MessagesSent: 1,
+				LastSentAt:   later,
+				LastPongAt:   later,
+				PingRTT:      time.Second,
+			}},
+		},
+		{
+			name: "disconnected clients are removed",
+			events: func(registry *clients, now *time.Time) {
+				registry.disconnect(registry.connect("/ws", "10.0.0.1", nil))
+			},
+			want: []types.ClientInfo{},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			now := connectedAt
+			registry := newClients(func() time.Time { return now })
+			tt.events(registry, &now)
+
+			if got := registry.list(); !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestClients_List() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,55 @@
+package server
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"testing"
+	"time"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func TestHandleUIConfig(t *testing.T) {
+	tests := []struct {
+		name string
+		opts []ServerOpt
+		want string
+	}{
+		{
+			name: "defaults",
+			want: `{"title":"Constellation","default_namespaces":[],"features":{}}` + "\n",
+		},
+		{
+			name: "configured options",
+			opts: []ServerOpt{WithUIConfig(types.UIConfig{
+				Title:             "Staging",
+				RefreshInterval:   30 * time.Second,
+				DefaultNamespaces: []string{"shop"},
+				Features:          map[string]bool{"showSystemNamespaces": true},
+			})},
+			want: `{"title":"Staging","refresh_interval":30000000000,"default_namespaces":["shop"],` +
+				`"features":{"showSystemNamespaces":true}}` + "\n",
+		},
+		{
+			name: "empty title keeps the default",
+			opts: []ServerOpt{WithUIConfig(types.UIConfig{DefaultNamespaces: []string{"shop"}})},
+			want: `{"title":"Constellation","default_namespaces":["shop"],"features":{}}` + "\n",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			recorder := httptest.NewRecorder()
+			New(fakeHealth{}, tt.opts...).Handler().ServeHTTP(recorder, httptest.NewRequest(http.MethodGet, "/config.json", nil))
+
+			if recorder.Code != http.StatusOK {
+				t.Fatalf("TestHandleUIConfig() status = %v, want %v", recorder.Code, http.StatusOK)
+			}
+			if got := recorder.Body.String(); got != tt.want {
+				t.Errorf("TestHandleUIConfig() = %s, want %s", got, tt.want)
+			}
+			if got := recorder.Header().Get("Cache-Control"); got != revalidateCacheControl {
+				t.Errorf("TestHandleUIConfig() Cache-Control = %q, want %q", got, revalidateCacheControl)
+			}
+		})
+	}
+}
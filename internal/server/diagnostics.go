@@ -0,0 +1,76 @@
+package server
+
+import (
+	"encoding/json"
+	"net/http"
+	"net/http/pprof"
+	"runtime"
+	"runtime/metrics"
+	"sort"
+	"time"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// mutexWait is the runtime metric totalling the time goroutines have spent blocked on a sync.Mutex or
+// sync.RWMutex, such as the health checker's and the trackers' locks
+const mutexWait = "/sync/mutex/wait/total:seconds"
+
+// debugRoutes serves the runtime diagnostics and the pprof profiles under /debug. Mutex and block profiles
+// stay empty unless sampling was enabled, e.g. with runtime.SetMutexProfileFraction.
+func (s *Server) debugRoutes(mux *http.ServeMux) {
+	mux.Handle("/debug/tasks", s.admin(s.handleTasks))
+	mux.Handle("/debug/pprof/", s.admin(pprof.Index))
+	mux.Handle("/debug/pprof/cmdline", s.admin(pprof.Cmdline))
+	mux.Handle("/debug/pprof/profile", s.admin(pprof.Profile))
+	mux.Handle("/debug/pprof/symbol", s.admin(pprof.Symbol))
+	mux.Handle("/debug/pprof/trace", s.admin(pprof.Trace))
+}
+
+// handleTasks reports goroutines, lock contention, heap size and the backlog of every WebSocket stream
+func (s *Server) handleTasks(w http.ResponseWriter, r *http.Request) {
+	samples := []metrics.Sample{{Name: mutexWait}}
+	metrics.Read(samples)
+
+	var memory runtime.MemStats
+	runtime.ReadMemStats(&memory)
+
+	diagnostics := types.RuntimeDiagnostics{
+		Goroutines: runtime.NumGoroutine(),
+		GOMAXPROCS: runtime.GOMAXPROCS(0),
+		HeapBytes:  memory.HeapAlloc,
+		GCCycles:   memory.NumGC,
+		Streams:    streamDiagnostics(s.clients.list()),
+	}
+	if samples[0].Value.Kind() == metrics.KindFloat64 {
+		diagnostics.MutexWait = time.Duration(samples[0].Value.Float64() * float64(time.Second))
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(diagnostics); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+// streamDiagnostics totals the connected clients and their queued updates per stream, in stream order
+func streamDiagnostics(infos []types.ClientInfo) []types.StreamDiagnostics {
+	byStream := make(map[string]*types.StreamDiagnostics)
+	for _, info := range infos {
+		stream, exists := byStream[info.Stream]
+		if !exists {
+			stream = &types.StreamDiagnostics{Stream: info.Stream}
+			byStream[info.Stream] = stream
+		}
+		stream.Clients++
+		stream.Queued += info.Queued
+		stream.MaxQueued = max(stream.MaxQueued, info.Queued)
+	}
+
+	streams := make([]types.StreamDiagnostics, 0, len(byStream))
+	for _, stream := range byStream {
+		streams = append(streams, *stream)
+	}
+	sort.Slice(streams, func(i, j int) bool { return streams[i].Stream < streams[j].Stream })
+	return streams
+}
@@ -0,0 +1,41 @@
+package server
+
+import (
+	"reflect"
+	"testing"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func TestStreamDiagnostics(t *testing.T) {
+	tests := []struct {
+		name  string
+		infos []types.ClientInfo
+		want  []types.StreamDiagnostics
+	}{
+		{
+			name: "clients are totalled per stream",
+			infos: []types.ClientInfo{
+				{ID: 1, Stream: "/ws/endpoints", Queued: 2},
+				{ID: 2, Stream: "/ws", Queued: 0},
+				{ID: 3, Stream: "/ws/endpoints", Queued: 5},
+			},
+			want: []types.StreamDiagnostics{
+				{Stream: "/ws", Clients: 1},
+				{Stream: "/ws/endpoints", Clients: 2, Queued: 7, MaxQueued: 5},
+			},
+		},
+		{
+			name: "no clients",
+			want: []types.StreamDiagnostics{},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := streamDiagnostics(tt.infos)
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestStreamDiagnostics() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
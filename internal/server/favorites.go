@@ -0,0 +1,72 @@
+package server
+
+import (
+	"encoding/json"
+	"fmt"
+	"net/http"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// handleFavorites lists the pinned node IDs on GET and pins the node in a POST types.Favorite body,
+// e.g. {"id": "Service/shop/web"}
+func (s *Server) handleFavorites(w http.ResponseWriter, r *http.Request) {
+	if r.Method == http.MethodGet {
+		s.writeFavorites(w)
+		return
+	}
+	if r.Method != http.MethodPost {
+		http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+		return
+	}
+
+	var favorite types.Favorite
+	if err := json.NewDecoder(r.Body).Decode(&favorite); err != nil {
+		http.Error(w, fmt.Sprintf("invalid favorite: %v", err), http.StatusBadRequest)
+		return
+	}
+	if favorite.ID == "" {
+		http.Error(w, "id is required", http.StatusBadRequest)
+		return
+	}
+	if s.hierarchyProvider != nil {
+		nodes, err := s.hierarchyProvider.GetHierarchy(r.Context())
+		if err != nil {
+			http.Error(w, err.Error(), http.StatusInternalServerError)
+			return
+		}
+		if hierarchy.FindNode(nodes, favorite.ID) == nil {
+			http.Error(w, fmt.Sprintf("node %q not found", favorite.ID), http.StatusNotFound)
+			return
+		}
+	}
+
+	if err := s.favoritesProvider.Pin(r.Context(), favorite.ID); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+	s.writeFavorites(w)
+}
+
+// handleUnpin removes the node in the path from the favorites, e.g. DELETE /favorites/Service/shop/web
+func (s *Server) handleUnpin(w http.ResponseWriter, r *http.Request) {
+	if r.Method != http.MethodDelete {
+		http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+		return
+	}
+
+	if err := s.favoritesProvider.Unpin(r.Context(), r.PathValue("id")); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+	s.writeFavorites(w)
+}
+
+func (s *Server) writeFavorites(w http.ResponseWriter) {
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(s.favoritesProvider.Favorites()); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
@@ -0,0 +1,164 @@
+package server
+
+import (
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/kdwils/constellation/internal/favorites"
+	"github.com/kdwils/constellation/internal/types"
+	apiv1 "github.com/kdwils/constellation/pkg/api/v1"
+)
+
+func TestHandleFavorites(t *testing.T) {
+	nodes := fakeHierarchy{
+		{Kind: types.ResourceKindNamespace, Name: "shop", ID: "Namespace/shop", Relatives: []types.HierarchyNode{
+			{Kind: types.ResourceKindService, Name: "web", ID: "Service/shop/web", ParentID: "Namespace/shop"},
+			{Kind: types.ResourceKindService, Name: "api", ID: "Service/shop/api", ParentID: "Namespace/shop"},
+		}},
+	}
+
+	type request struct {
+		method string
+		path   string
+		body   string
+	}
+
+	tests := []struct {
+		name       string
+		requests   []request
+		wantStatus int
+		want       string
+	}{
+		{
+			name:       "nothing pinned",
+			requests:   []request{{method: http.MethodGet, path: "/favorites"}},
+			wantStatus: http.StatusOK,
+			want:       `[]` + "\n",
+		},
+		{
+			name: "pins are listed in ID order",
+			requests: []request{
+				{method: http.MethodPost, path: "/favorites", body: `{"id":"Service/shop/web"}`},
+				{method: http.MethodPost, path: "/favorites", body: `{"id":"Service/shop/api"}`},
+				{method: http.MethodPost, path: "/favorites", body: `{"id":"Service/shop/web"}`},
+			},
+			wantStatus: http.StatusOK,
+			want:       `["Service/shop/api","Service/shop/web"]` + "\n",
+		},
+		{
+			name: "unpin",
+			requests: []request{
+				{method: http.MethodPost, path: "/favorites", body: `{"id":"Service/shop/web"}`},
+				{method: http.MethodDelete, path: "/favorites/Service/shop/web"},
+			},
+			wantStatus: http.StatusOK,
+			want:       `[]` + "\n",
+		},
+		{
+			name:       "unknown node",
+			requests:   []request{{method: http.MethodPost, path: "/favorites", body: `{"id":"Service/shop/db"}`}},
+			wantStatus: http.StatusNotFound,
+			want:       `node "Service/shop/db" not found` + "\n",
+		},
+		{
+			name:       "missing id",
+			requests:   []request{{method: http.MethodPost, path: "/favorites", body: `{}`}},
+			wantStatus: http.StatusBadRequest,
+			want:       "id is required\n",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			handler := New(fakeHealth{}, WithHierarchyProvider(nodes), WithFavoritesProvider(favorites.NewStore(nil))).Handler()
+
+			var recorder *httptest.ResponseRecorder
+			for _, req := range tt.requests {
+				recorder = httptest.NewRecorder()
+				handler.ServeHTTP(recorder, httptest.NewRequest(req.method, req.path, strings.NewReader(req.body)))
+			}
+
+			if recorder.Code != tt.wantStatus {
+				t.Errorf("TestHandleFavorites() status = %v, want %v", recorder.Code, tt.wantStatus)
+			}
+			if got := recorder.Body.String(); got != tt.want {
+				t.Errorf("TestHandleFavorites() = %s, want %s", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestHandleHierarchy_Favorites(t *testing.T) {
+	nodes := fakeHierarchy{
+		{Kind: types.ResourceKindNamespace, Name: "shop", ID: "Namespace/shop", Relatives: []types.HierarchyNode{
+			{Kind: types.ResourceKindService, Name: "web", ID: "Service/shop/web", ParentID: "Namespace/shop"},
+			{Kind: types.ResourceKindService, Name: "api", ID: "Service/shop/api", ParentID: "Namespace/shop"},
+		}},
+		{Kind: types.ResourceKindNamespace, Name: "ops", ID: "Namespace/ops"},
+	}
+
+	tests := []struct {
+		name       string
+		opts       []ServerOpt
+		query      string
+		wantStatus int
+		want       []string
+	}{
+		{
+			name:       "pinned nodes and their ancestors",
+			opts:       []ServerOpt{WithFavoritesProvider(favorites.NewStore(nil))},
+			query:      "?favorites=true",
+			wantStatus: http.StatusOK,
+			want:       []string{"Namespace/shop", "Service/shop/web"},
+		},
+		{
+			name:       "favorites=false leaves the hierarchy whole",
+			opts:       []ServerOpt{WithFavoritesProvider(favorites.NewStore(nil))},
+			query:      "?favorites=false",
+			wantStatus: http.StatusOK,
+			want:       []string{"Namespace/shop", "Service/shop/web", "Service/shop/api", "Namespace/ops"},
+		},
+		{
+			name:       "favorites not enabled",
+			query:      "?favorites=true",
+			wantStatus: http.StatusBadRequest,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			handler := New(fakeHealth{}, append(tt.opts, WithHierarchyProvider(nodes))...).Handler()
+			if tt.opts != nil {
+				pin := httptest.NewRequest(http.MethodPost, "/favorites", strings.NewReader(`{"id":"Service/shop/web"}`))
+				handler.ServeHTTP(httptest.NewRecorder(), pin)
+			}
+
+			recorder := httptest.NewRecorder()
+			handler.ServeHTTP(recorder, httptest.NewRequest(http.MethodGet, "/hierarchy"+tt.query, nil))
+			if recorder.Code != tt.wantStatus {
+				t.Fatalf("TestHandleHierarchy_Favorites() status = %v, want %v", recorder.Code, tt.wantStatus)
+			}
+			if tt.wantStatus != http.StatusOK {
+				return
+			}
+
+			var served []apiv1.Node
+			if err := json.Unmarshal(recorder.Body.Bytes(), &served); err != nil {
+				t.Fatalf("TestHandleHierarchy_Favorites() error = %v", err)
+			}
+			var got []string
+			var walk func(nodes []apiv1.Node)
+			walk = func(nodes []apiv1.Node) {
+				for _, node := range nodes {
+					got = append(got, node.ID)
+					walk(node.Relatives)
+				}
+			}
+			walk(served)
+			if strings.Join(got, ",") != strings.Join(tt.want, ",") {
+				t.Errorf("TestHandleHierarchy_Favorites() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,133 @@
+package server
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"net/url"
+	"reflect"
+	"strings"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// projection keeps the requested JSON fields of every object it is applied to, along with the fields
+// that identify it. Objects under the nested field are projected too, so a whole tree is cut down.
+type projection struct {
+	keep   map[string]bool
+	nested string
+}
+
+var (
+	// nodeProjection keeps what is needed to still draw a projected hierarchy as a tree
+	nodeProjection    = newProjection("relatives", "kind", "name", "namespace", "id", "parent_id", "relatives")
+	serviceProjection = newProjection("", "service_name", "namespace")
+)
+
+// projectedState is a types.State with its services cut down to the requested fields. The outer
+// Services field takes precedence over the embedded one when encoding.
+type projectedState struct {
+	types.State
+	Services any `json:"services"`
+}
+
+// newProjection builds a projection that always keeps the always fields
+func newProjection(nested string, always ...string) projection {
+	keep := make(map[string]bool, len(always))
+	for _, field := range always {
+		keep[field] = true
+	}
+	return projection{keep: keep, nested: nested}
+}
+
+// fields reads ?fields=phase,ports,labels. It returns nil when the parameter is absent, and an error
+// naming any field typ does not have.
+func fields(query url.Values, typ reflect.Type) ([]string, error) {
+	value := query.Get("fields")
+	if value == "" {
+		return nil, nil
+	}
+
+	known := jsonFields(typ)
+	requested := strings.Split(value, ",")
+	for _, field := range requested {
+		if !known[field] {
+			return nil, fmt.Errorf("unknown field %q", field)
+		}
+	}
+	return requested, nil
+}
+
+// jsonFields are the JSON names of the fields of struct typ
+func jsonFields(typ reflect.Type) map[string]bool {
+	names := make(map[string]bool, typ.NumField())
+	for i := range typ.NumField() {
+		name, _, _ := strings.Cut(typ.Field(i).Tag.Get("json"), ",")
+		if name == "" || name == "-" {
+			continue
+		}
+		names[name] = true
+	}
+	return names
+}
+
+// with returns a copy of p that also keeps fields
+func (p projection) with(fields []string) projection {
+	keep := make(map[string]bool, len(p.keep)+len(fields))
+	for field := range p.keep {
+		keep[field] = true
+	}
+	for _, field := range fields {
+		keep[field] = true
+	}
+	return projection{keep: keep, nested: p.nested}
+}
+
+// apply removes the unkept fields from value, a decoded JSON object or array of objects
+func (p projection) apply(value any) any {
+	switch typed := value.(type) {
+	case []any:
+		for i := range typed {
+			typed[i] = p.apply(typed[i])
+		}
+	case map[string]any:
+		for key, field := range typed {
+			if !p.keep[key] {
+				delete(typed, key)
+				continue
+			}
+			if key == p.nested {
+				typed[key] = p.apply(field)
+			}
+		}
+	}
+	return value
+}
+
+// project cuts v down to the requested fields, or returns it unchanged when none were requested
+func project(v any, p projection, requested []string) (any, error) {
+	if requested == nil {
+		return v, nil
+	}
+	value, err := decode(v)
+	if err != nil {
+		return nil, err
+	}
+	return p.with(requested).apply(value), nil
+}
+
+// decode round trips v through JSON into maps and slices that a projection can cut down.
+// Numbers are kept as written.
+func decode(v any) (any, error) {
+	data, err := json.Marshal(v)
+	if err != nil {
+		return nil, err
+	}
+	decoder := json.NewDecoder(bytes.NewReader(data))
+	decoder.UseNumber()
+	var value any
+	if err := decoder.Decode(&value); err != nil {
+		return nil, err
+	}
+	return value, nil
+}
@@ -0,0 +1,82 @@
+package server
+
+import (
+	"encoding/json"
+	"net/url"
+	"reflect"
+	"testing"
+
+	"k8s.io/utils/ptr"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func TestProject(t *testing.T) {
+	nodes := []types.HierarchyNode{{
+		Kind:   types.ResourceKindNamespace,
+		Name:   "shop",
+		ID:     "Namespace/shop",
+		Labels: map[string]string{"team": "payments"},
+		Relatives: []types.HierarchyNode{{
+			Kind:      types.ResourceKindPod,
+			Name:      "web-0",
+			Namespace: ptr.To("shop"),
+			ID:        "Pod/shop/web-0",
+			ParentID:  "Namespace/shop",
+			Phase:     ptr.To("Running"),
+			PodIPs:    []string{"10.0.0.1"},
+		}},
+	}}
+
+	tests := []struct {
+		name    string
+		query   string
+		want    string
+		wantErr bool
+	}{
+		{
+			name:  "requested fields are kept with the tree fields",
+			query: "fields=phase",
+			want: `[{"id":"Namespace/shop","kind":"Namespace","name":"shop","relatives":[` +
+				`{"id":"Pod/shop/web-0","kind":"Pod","name":"web-0","namespace":"shop","parent_id":"Namespace/shop","phase":"Running"}]}]`,
+		},
+		{
+			name:  "several fields",
+			query: "fields=labels,pod_ips",
+			want: `[{"id":"Namespace/shop","kind":"Namespace","labels":{"team":"payments"},"name":"shop","relatives":[` +
+				`{"id":"Pod/shop/web-0","kind":"Pod","name":"web-0","namespace":"shop","parent_id":"Namespace/shop","pod_ips":["10.0.0.1"]}]}]`,
+		},
+		{
+			name:    "unknown field is rejected",
+			query:   "fields=phase,colour",
+			wantErr: true,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			query, err := url.ParseQuery(tt.query)
+			if err != nil {
+				t.Fatalf("TestProject() query error = %v", err)
+			}
+			requested, err := fields(query, reflect.TypeFor[types.HierarchyNode]())
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("TestProject() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if err != nil {
+				return
+			}
+
+			projected, err := project(nodes, nodeProjection, requested)
+			if err != nil {
+				t.Fatalf("TestProject() project error = %v", err)
+			}
+			got, err := json.Marshal(projected)
+			if err != nil {
+				t.Fatalf("TestProject() marshal error = %v", err)
+			}
+			if string(got) != tt.want {
+				t.Errorf("TestProject() = %s, want %s", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,154 @@
+package server
+
+import (
+	"context"
+	"encoding/json"
+	"sync"
+	"time"
+
+	"github.com/kdwils/constellation/internal/anonymize"
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// wholeHierarchy is the id a subscriber follows to receive the whole hierarchy rather than one node's update
+const wholeHierarchy = "*"
+
+// nodeFeed builds the hierarchy once per health update and nodeRefreshInterval for every client following
+// a node, and fans the update for each followed node out to its clients. Clients following the same node
+// share one encoded update, so the cost of a build does not grow with the number of clients. The feed
+// only builds while it has subscribers. With an anonymizer every build is anonymized before it is
+// encoded, so clients follow and receive only pseudonyms.
+type nodeFeed struct {
+	hierarchy  HierarchyProvider
+	health     HealthDataProvider
+	anonymizer *anonymize.Anonymizer
+
+	mu          sync.Mutex
+	subscribers map[*nodeSubscriber]struct{}
+	nodes       []types.HierarchyNode
+	cancel      context.CancelFunc
+}
+
+// nodeSubscriber receives the latest update of the node it follows. Updates it has not read yet are
+// replaced, since only the newest subtree matters.
+type nodeSubscriber struct {
+	id      string
+	updates chan []byte
+}
+
+func newNodeFeed(provider HierarchyProvider, health HealthDataProvider, anonymizer *anonymize.Anonymizer) *nodeFeed {
+	return &nodeFeed{
+		hierarchy:   provider,
+		health:      health,
+		anonymizer:  anonymizer,
+		subscribers: make(map[*nodeSubscriber]struct{}),
+	}
+}
+
+// subscribe adds a subscriber following nothing until watch is called, starting the builds for the first
+func (f *nodeFeed) subscribe() *nodeSubscriber {
+	f.mu.Lock()
+	defer f.mu.Unlock()
+	sub := &nodeSubscriber{updates: make(chan []byte, 1)}
+	f.subscribers[sub] = struct{}{}
+	if f.cancel == nil {
+		ctx, cancel := context.WithCancel(context.Background())
+		f.cancel = cancel
+		go f.run(ctx)
+	}
+	return sub
+}
+
+// unsubscribe removes sub, stopping the builds after the last one
+func (f *nodeFeed) unsubscribe(sub *nodeSubscriber) {
+	f.mu.Lock()
+	defer f.mu.Unlock()
+	delete(f.subscribers, sub)
+	if len(f.subscribers) > 0 || f.cancel == nil {
+		return
+	}
+	f.cancel()
+	f.cancel, f.nodes = nil, nil
+}
+
+// watch switches sub to the node with id and sends it that node from the last build, if there was one
+func (f *nodeFeed) watch(sub *nodeSubscriber, id string) {
+	f.mu.Lock()
+	defer f.mu.Unlock()
+	sub.id = id
+	select {
+	case <-sub.updates:
+	default:
+	}
+	if f.nodes == nil {
+		return
+	}
+	if payload, err := encodeNode(f.nodes, id); err == nil {
+		sub.offer(payload)
+	}
+}
+
+func (f *nodeFeed) run(ctx context.Context) {
+	healthUpdates := f.health.Subscribe()
+	defer f.health.Unsubscribe(healthUpdates)
+	ticker := time.NewTicker(nodeRefreshInterval)
+	defer ticker.Stop()
+
+	for {
+		f.build(ctx)
+		select {
+		case <-ctx.Done():
+			return
+		case <-healthUpdates:
+		case <-ticker.C:
+		}
+	}
+}
+
+// build fetches the hierarchy and sends every subscriber the node it follows, encoding each node once
+func (f *nodeFeed) build(ctx context.Context) {
+	nodes, err := f.hierarchy.GetHierarchy(ctx)
+	if err != nil || ctx.Err() != nil {
+		return
+	}
+	nodes, err = anonymizeNodes(f.anonymizer, nodes)
+	if err != nil {
+		return
+	}
+
+	f.mu.Lock()
+	defer f.mu.Unlock()
+	f.nodes = nodes
+	payloads := make(map[string][]byte)
+	for sub := range f.subscribers {
+		if sub.id == "" {
+			continue
+		}
+		payload, encoded := payloads[sub.id]
+		if !encoded {
+			payload, err = encodeNode(nodes, sub.id)
+			if err != nil {
+				continue
+			}
+			payloads[sub.id] = payload
+		}
+		sub.offer(payload)
+	}
+}
+
+func encodeNode(nodes []types.HierarchyNode, id string) ([]byte, error) {
+	if id == wholeHierarchy {
+		return json.Marshal(nodes)
+	}
+	return json.Marshal(types.NodeUpdate{ID: id, Node: hierarchy.FindNode(nodes, id)})
+}
+
+// offer replaces any unread update with payload
+func (s *nodeSubscriber) offer(payload []byte) {
+	select {
+	case <-s.updates:
+	default:
+	}
+	s.updates <- payload
+}
@@ -0,0 +1,86 @@
+package server
+
+import (
+	"context"
+	"encoding/json"
+	"reflect"
+	"sync/atomic"
+	"testing"
+	"time"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+type countingHierarchy struct {
+	nodes []types.HierarchyNode
+	calls atomic.Int32
+}
+
+func (c *countingHierarchy) GetHierarchy(context.Context) ([]types.HierarchyNode, error) {
+	c.calls.Add(1)
+	return c.nodes, nil
+}
+
+func TestNodeFeed(t *testing.T) {
+	nodes := []types.HierarchyNode{
+		{Kind: types.ResourceKindNamespace, Name: "payments", ID: "Namespace/payments"},
+		{Kind: types.ResourceKindNamespace, Name: "shop", ID: "Namespace/shop"},
+	}
+
+	tests := []struct {
+		name      string
+		watches   []string
+		want      map[string]types.NodeUpdate
+		wantCalls int32
+	}{
+		{
+			name:      "clients following the same node share one build",
+			watches:   []string{"Namespace/shop", "Namespace/shop", "Namespace/shop"},
+			want:      map[string]types.NodeUpdate{"Namespace/shop": {ID: "Namespace/shop", Node: &nodes[1]}},
+			wantCalls: 1,
+		},
+		{
+			name:    "clients following different nodes share one build",
+			watches: []string{"Namespace/shop", "Namespace/payments"},
+			want: map[string]types.NodeUpdate{
+				"Namespace/shop":     {ID: "Namespace/shop", Node: &nodes[1]},
+				"Namespace/payments": {ID: "Namespace/payments", Node: &nodes[0]},
+			},
+			wantCalls: 1,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			provider := &countingHierarchy{nodes: nodes}
+			feed := newNodeFeed(provider, fakeHealth{}, nil)
+
+			subscribers := make([]*nodeSubscriber, len(tt.watches))
+			for i, id := range tt.watches {
+				subscribers[i] = feed.subscribe()
+				defer feed.unsubscribe(subscribers[i])
+				feed.watch(subscribers[i], id)
+			}
+
+			got := make(map[string]types.NodeUpdate)
+			for _, sub := range subscribers {
+				select {
+				case payload := <-sub.updates:
+					var update types.NodeUpdate
+					if err := json.Unmarshal(payload, &update); err != nil {
+						t.Fatalf("TestNodeFeed() unmarshal error = %v", err)
+					}
+					got[sub.id] = update
+				case <-time.After(time.Second):
+					t.Fatalf("TestNodeFeed() subscriber following %s got no update", sub.id)
+				}
+			}
+
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestNodeFeed() = %+v, want %+v", got, tt.want)
+			}
+			if calls := provider.calls.Load(); calls != tt.wantCalls {
+				t.Errorf("TestNodeFeed() GetHierarchy calls = %d, want %d", calls, tt.wantCalls)
+			}
+		})
+	}
+}
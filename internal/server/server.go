@@ -1,14 +1,35 @@
 package server
 
 import (
+	"bytes"
 	"context"
 	"encoding/json"
 	"fmt"
+	"net"
 	"net/http"
+	"net/url"
+	"reflect"
+	"sort"
+	"strconv"
+	"strings"
+	"sync/atomic"
 	"time"
 
 	"github.com/gorilla/websocket"
+	"github.com/kdwils/constellation/internal/annotations"
+	"github.com/kdwils/constellation/internal/anonymize"
+	"github.com/kdwils/constellation/internal/export"
+	"github.com/kdwils/constellation/internal/graphql"
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/images"
+	"github.com/kdwils/constellation/internal/lookup"
+	"github.com/kdwils/constellation/internal/netpol"
+	"github.com/kdwils/constellation/internal/ratelimit"
+	"github.com/kdwils/constellation/internal/schema"
 	"github.com/kdwils/constellation/internal/types"
+	apiv1 "github.com/kdwils/constellation/pkg/api/v1"
+	"k8s.io/apimachinery/pkg/labels"
+	"sigs.k8s.io/controller-runtime/pkg/log"
 )
 
 const (
@@ -16,106 +37,1372 @@ const (
 	pongWait       = 60 * time.Second
 	pingPeriod     = (pongWait * 9) / 10
 	maxMessageSize = 512
+
+	nodeRefreshInterval = 5 * time.Second
+	defaultStatsLimit   = 10
+
+	defaultPort            = 8080
+	defaultShutdownTimeout = 5 * time.Second
+	defaultTitle           = "Constellation"
 )
 
-var upgrader = websocket.Upgrader{
-	CheckOrigin: func(r *http.Request) bool {
-		return true
-	},
-	HandshakeTimeout: 5 * time.Second,
+var upgrader = websocket.Upgrader{
+	CheckOrigin: func(r *http.Request) bool {
+		return true
+	},
+	HandshakeTimeout: 5 * time.Second,
+}
+
+type HealthDataProvider interface {
+	GetAllHealthData() []*types.ServiceHealthInfo
+	Subscribe() chan []*types.ServiceHealthInfo
+	Unsubscribe(chan []*types.ServiceHealthInfo)
+}
+
+type HierarchyProvider interface {
+	GetHierarchy(ctx context.Context) ([]types.HierarchyNode, error)
+}
+
+type SnapshotProvider interface {
+	Snapshot(ctx context.Context) (*types.Snapshot, error)
+}
+
+type AlertProvider interface {
+	GetAlerts(ctx context.Context) ([]types.Alert, error)
+}
+
+type DisruptionProvider interface {
+	GetDisruptions(ctx context.Context) ([]types.WorkloadDisruptions, error)
+}
+
+type ChurnProvider interface {
+	Churn() types.ChurnReport
+}
+
+type EventProvider interface {
+	Events() []types.Event
+}
+
+type TimelineProvider interface {
+	Timeline(kind types.ResourceKind, namespace, name string) []types.Transition
+}
+
+type AnnotationProvider interface {
+	Add(annotation types.Annotation) types.Annotation
+	Annotations() []types.Annotation
+	Subscribe() chan []types.Annotation
+	Unsubscribe(chan []types.Annotation)
+}
+
+type APIProbeProvider interface {
+	Status() types.APIServerStatus
+}
+
+type EndpointProvider interface {
+	GetEndpoints() []types.ServiceEndpoints
+	Subscribe() chan []types.ServiceEndpoints
+	Unsubscribe(chan []types.ServiceEndpoints)
+}
+
+// FavoritesProvider keeps the node IDs pinned on the dashboard
+type FavoritesProvider interface {
+	Favorites() []string
+	Pin(ctx context.Context, id string) error
+	Unpin(ctx context.Context, id string) error
+}
+
+type SyncStatusProvider interface {
+	SyncStatus(ctx context.Context) types.SyncStatus
+}
+
+// AdminProvider switches watches, resyncs and inspects the cache behind the /admin routes
+type AdminProvider interface {
+	Status() []types.WatchStatus
+	Set(ctx context.Context, resource string, enabled bool) error
+	Resync(ctx context.Context) (types.ResyncResult, error)
+	Inspect(ctx context.Context, resource string, objects bool) (types.StoreStatus, error)
+	Memory(ctx context.Context, resource string) (types.StoreMemory, error)
+	Shrink(ctx context.Context, resource string) error
+}
+
+type Server struct {
+	healthProvider     HealthDataProvider
+	hierarchyProvider  HierarchyProvider
+	snapshotProvider   SnapshotProvider
+	alertProviders     []AlertProvider
+	disruptionProvider DisruptionProvider
+	churnProvider      ChurnProvider
+	eventProviders     []EventProvider
+	timelineProvider   TimelineProvider
+	annotationProvider AnnotationProvider
+	apiProbeProvider   APIProbeProvider
+	endpointProvider   EndpointProvider
+	syncStatusProvider SyncStatusProvider
+	favoritesProvider  FavoritesProvider
+	adminProvider      AdminProvider
+	adminToken         string
+	identityHeader     string
+	audit              *auditTrail
+	staticDir          string
+	apiOnly            bool
+	addr               string
+	listener           net.Listener
+	shutdownTimeout    time.Duration
+	httpConfig         HTTPConfig
+	routes             []route
+	middleware         []Middleware
+	limiter            *ratelimit.Limiter
+	accessLog          bool
+	maxChildren        int
+	messageSizeLimit   int
+	uiConfig           types.UIConfig
+	anonymizer         *anonymize.Anonymizer
+	chunkIDs           atomic.Uint64
+	clients            *clients
+	nodes              *nodeFeed
+}
+
+// Middleware wraps the server's handler, e.g. to add authentication in front of every route
+type Middleware func(http.Handler) http.Handler
+
+type route struct {
+	pattern string
+	handler http.Handler
+}
+
+// New creates a server for the given health provider, configured through options
+func New(healthProvider HealthDataProvider, opts ...ServerOpt) *Server {
+	s := &Server{
+		healthProvider:  healthProvider,
+		addr:            fmt.Sprintf(":%d", defaultPort),
+		shutdownTimeout: defaultShutdownTimeout,
+		clients:         newClients(time.Now),
+		audit:           newAuditTrail(time.Now),
+		uiConfig:        types.UIConfig{Title: defaultTitle},
+	}
+
+	for _, opt := range opts {
+		opt(s)
+	}
+	if s.hierarchyProvider != nil {
+		s.nodes = newNodeFeed(s.hierarchyProvider, healthProvider, s.anonymizer)
+	}
+
+	return s
+}
+
+// NewServer creates a server listening on all interfaces at the given port.
+// It is kept for compatibility; prefer New with WithStaticDir and WithPort.
+func NewServer(healthProvider HealthDataProvider, staticDir string, port int, opts ...ServerOpt) *Server {
+	return New(healthProvider, append([]ServerOpt{WithStaticDir(staticDir), WithPort(port)}, opts...)...)
+}
+
+type ServerOpt func(*Server)
+
+// WithStaticDir serves the frontend from dir; an empty dir disables static file serving
+func WithStaticDir(dir string) ServerOpt {
+	return func(s *Server) {
+		s.staticDir = dir
+	}
+}
+
+// WithAPIOnly serves no frontend, even when a static dir is set, and answers every unknown route with a
+// JSON 404, for embedding constellation as a topology API behind another UI
+func WithAPIOnly() ServerOpt {
+	return func(s *Server) {
+		s.apiOnly = true
+	}
+}
+
+// WithPort listens on all interfaces at the given port
+func WithPort(port int) ServerOpt {
+	return func(s *Server) {
+		s.addr = fmt.Sprintf(":%d", port)
+	}
+}
+
+// WithAddr listens on an explicit host:port, e.g. 127.0.0.1:8080
+func WithAddr(addr string) ServerOpt {
+	return func(s *Server) {
+		s.addr = addr
+	}
+}
+
+// WithListener serves on an already bound listener instead of the configured address.
+// Binding port 0 beforehand lets the caller learn the chosen port before the server starts.
+func WithListener(listener net.Listener) ServerOpt {
+	return func(s *Server) {
+		s.listener = listener
+	}
+}
+
+// WithShutdownTimeout bounds how long in-flight requests may take to finish after the context is cancelled
+func WithShutdownTimeout(timeout time.Duration) ServerOpt {
+	return func(s *Server) {
+		s.shutdownTimeout = timeout
+	}
+}
+
+// WithHTTPConfig tunes HTTP/2, keep-alives and timeouts on the connections the server accepts
+func WithHTTPConfig(config HTTPConfig) ServerOpt {
+	return func(s *Server) {
+		s.httpConfig = config
+	}
+}
+
+// WithRoute registers an additional handler alongside the built-in routes.
+// Patterns follow http.ServeMux rules and must not collide with a built-in route.
+func WithRoute(pattern string, handler http.Handler) ServerOpt {
+	return func(s *Server) {
+		s.routes = append(s.routes, route{pattern: pattern, handler: handler})
+	}
+}
+
+// WithMiddleware wraps every route, including ones added through WithRoute.
+// The first middleware given is the outermost.
+func WithMiddleware(middleware ...Middleware) ServerOpt {
+	return func(s *Server) {
+		s.middleware = append(s.middleware, middleware...)
+	}
+}
+
+// WithRateLimit limits the request/response API routes per client IP and caps how many are served at once.
+// WebSocket streams, /healthz and static files are not limited.
+func WithRateLimit(config ratelimit.Config) ServerOpt {
+	return func(s *Server) {
+		s.limiter = ratelimit.NewLimiter(config)
+	}
+}
+
+// WithAccessLog logs the method, matched route, status, latency and client IP of every request.
+// It is the outermost handler, so requests rejected by middleware are logged too.
+func WithAccessLog() ServerOpt {
+	return func(s *Server) {
+		s.accessLog = true
+	}
+}
+
+// WithMaxChildren caps the children served under each hierarchy root, replacing the rest with a Truncated
+// node that counts them. The remainder of a namespace is paged through /hierarchy/{namespace}?offset=.
+func WithMaxChildren(limit int) ServerOpt {
+	return func(s *Server) {
+		s.maxChildren = limit
+	}
+}
+
+// WithMessageSizeLimit splits outgoing WebSocket messages larger than limit bytes into types.ChunkedMessage
+// parts for the client to reassemble, for proxies that drop large frames. Limits below 1KiB are raised to it.
+func WithMessageSizeLimit(limit int) ServerOpt {
+	return func(s *Server) {
+		s.messageSizeLimit = limit
+	}
+}
+
+// WithUIConfig serves config at /config.json for the frontend to read when it loads. An empty title keeps
+// the default.
+func WithUIConfig(config types.UIConfig) ServerOpt {
+	return func(s *Server) {
+		if config.Title == "" {
+			config.Title = defaultTitle
+		}
+		s.uiConfig = config
+	}
+}
+
+// WithAnonymizer replaces the names, hostnames and IPs in every JSON response, WebSocket message and
+// diagram export with stable pseudonyms, so the output can be shared publicly
+func WithAnonymizer(anonymizer *anonymize.Anonymizer) ServerOpt {
+	return func(s *Server) {
+		s.anonymizer = anonymizer
+	}
+}
+
+func WithHierarchyProvider(provider HierarchyProvider) ServerOpt {
+	return func(s *Server) {
+		s.hierarchyProvider = provider
+	}
+}
+
+func WithSnapshotProvider(provider SnapshotProvider) ServerOpt {
+	return func(s *Server) {
+		s.snapshotProvider = provider
+	}
+}
+
+// WithAlertProvider adds a source of alerts served at /alerts; results from every provider are merged
+func WithAlertProvider(provider AlertProvider) ServerOpt {
+	return func(s *Server) {
+		s.alertProviders = append(s.alertProviders, provider)
+	}
+}
+
+// WithDisruptionProvider serves the pod disruption history at /disruptions
+func WithDisruptionProvider(provider DisruptionProvider) ServerOpt {
+	return func(s *Server) {
+		s.disruptionProvider = provider
+	}
+}
+
+// WithChurnProvider serves the pod churn heatmap at /churn
+func WithChurnProvider(provider ChurnProvider) ServerOpt {
+	return func(s *Server) {
+		s.churnProvider = provider
+	}
+}
+
+// WithEventProvider adds a source of recent cluster events, such as rollouts starting, served at /events;
+// events from every provider are merged newest first
+func WithEventProvider(provider EventProvider) ServerOpt {
+	return func(s *Server) {
+		s.eventProviders = append(s.eventProviders, provider)
+	}
+}
+
+// WithTimelineProvider serves the recent state transitions of a node at
+// /resources/{kind}/{namespace}/{name}/timeline
+func WithTimelineProvider(provider TimelineProvider) ServerOpt {
+	return func(s *Server) {
+		s.timelineProvider = provider
+	}
+}
+
+// WithAnnotationProvider accepts CloudEvents at POST /annotations, attaching them as markers to the node
+// their subject names, lists the markers at GET /annotations and streams new ones at /ws/annotations
+func WithAnnotationProvider(provider AnnotationProvider) ServerOpt {
+	return func(s *Server) {
+		s.annotationProvider = provider
+	}
+}
+
+// WithAPIProbeProvider serves apiserver list and watch latencies at /readyz, failing while it is slow
+func WithAPIProbeProvider(provider APIProbeProvider) ServerOpt {
+	return func(s *Server) {
+		s.apiProbeProvider = provider
+	}
+}
+
+// WithEndpointProvider streams Service endpoint readiness at /ws/endpoints whenever it changes
+func WithEndpointProvider(provider EndpointProvider) ServerOpt {
+	return func(s *Server) {
+		s.endpointProvider = provider
+	}
+}
+
+// WithSyncStatusProvider adds the last event per kind and the degraded flag to /state
+func WithSyncStatusProvider(provider SyncStatusProvider) ServerOpt {
+	return func(s *Server) {
+		s.syncStatusProvider = provider
+	}
+}
+
+// WithFavoritesProvider lists the pinned nodes at GET /favorites, pins one with POST /favorites and unpins
+// it with DELETE /favorites/{id}, and enables the ?favorites=true filter
+func WithFavoritesProvider(provider FavoritesProvider) ServerOpt {
+	return func(s *Server) {
+		s.favoritesProvider = provider
+	}
+}
+
+// WithAdminProvider serves the /admin routes, each requiring token as a bearer token:
+// GET /admin/watches and PUT /admin/watches/{resource} to switch the optional watches,
+// POST /admin/resync and GET /admin/stores/{resource} to debug stale state without a restart,
+// GET /admin/clients to see the connected WebSocket clients and which of them are lagging,
+// and GET /admin/memory to estimate the memory of each store, with POST /admin/memory/{resource}
+// dropping a store and listing it again. GET /debug/tasks reports goroutines, lock contention and
+// stream backlogs, next to the pprof profiles under /debug/pprof/. GET /admin/audit lists the changes
+// made through the API with the identity of each caller.
+// The routes are not served when token is empty.
+func WithAdminProvider(provider AdminProvider, token string) ServerOpt {
+	return func(s *Server) {
+		s.adminProvider = provider
+		s.adminToken = token
+	}
+}
+
+// WithIdentityHeader names callers by the user an authenticating proxy sets in header, e.g.
+// X-Forwarded-User, in /me and the /admin/audit trail. Only set it behind a proxy that overwrites the header.
+func WithIdentityHeader(header string) ServerOpt {
+	return func(s *Server) {
+		s.identityHeader = header
+	}
+}
+
+// Serve serves until ctx is cancelled, then waits up to the shutdown timeout for in-flight requests to
+// finish, returning an error if any were cut off
+func (s *Server) Serve(ctx context.Context) error {
+	httpServer := &http.Server{
+		Addr:    s.addr,
+		Handler: s.Handler(),
+	}
+	s.httpConfig.apply(httpServer)
+
+	shutdown := make(chan error, 1)
+	go func() {
+		<-ctx.Done()
+		shutdownCtx, cancel := context.WithTimeout(context.Background(), s.shutdownTimeout)
+		defer cancel()
+		shutdown <- httpServer.Shutdown(shutdownCtx)
+	}()
+
+	if err := s.serve(httpServer); err != nil && err != http.ErrServerClosed {
+		return fmt.Errorf("HTTP server failed: %v", err)
+	}
+	if err := <-shutdown; err != nil {
+		return fmt.Errorf("HTTP server shutdown: %w", err)
+	}
+	return nil
+}
+
+func (s *Server) serve(httpServer *http.Server) error {
+	if s.listener != nil {
+		return httpServer.Serve(s.listener)
+	}
+	if s.httpConfig.TCPKeepAlive == 0 {
+		return httpServer.ListenAndServe()
+	}
+	listener, err := s.httpConfig.listen(httpServer.Addr)
+	if err != nil {
+		return err
+	}
+	return httpServer.Serve(listener)
+}
+
+// Handler returns the routes served by the server so they can be mounted on another mux
+func (s *Server) Handler() http.Handler {
+	mux := http.NewServeMux()
+
+	api := versioned{mux}
+	api.Handle("/state", s.limit(s.handleState))
+	api.Handle("/schema", s.limit(s.handleSchema))
+	api.Handle("/me", s.limit(s.handleMe))
+	mux.HandleFunc("/ws", s.handleWebSocket)
+	mux.HandleFunc("/ws/graphql", s.handleGraphQLStream)
+	mux.HandleFunc("/healthz", s.handleHealth)
+	mux.HandleFunc("/config.json", s.handleUIConfig)
+
+	if s.hierarchyProvider != nil {
+		api.Handle("/hierarchy", s.limit(s.handleHierarchy))
+		api.Handle("/hierarchy/{namespace}", s.limit(s.handleNamespace))
+		api.Handle("/graphql", s.limit(s.handleGraphQL))
+		api.Handle("/owners", s.limit(s.handleOwners))
+		api.Handle("/stats", s.limit(s.handleStats))
+		api.Handle("/labels", s.limit(s.handleLabels))
+		api.Handle("/export/{format}", s.limit(s.handleExport))
+		api.Handle("/impact/{kind}/{name}", s.limit(s.handleImpact))
+		api.Handle("/impact/{kind}/{namespace}/{name}", s.limit(s.handleImpact))
+		api.Handle("/path", s.limit(s.handlePath))
+		mux.HandleFunc("/ws/node", s.handleNodeStream)
+		mux.HandleFunc("/state/namespaces/{namespace}/stream", s.handleNamespaceStream)
+		mux.HandleFunc("/ws/hierarchy", s.handleHierarchyStream)
+	}
+	if s.snapshotProvider != nil {
+		api.Handle("/snapshot", s.limit(s.handleSnapshot))
+		api.Handle("/images", s.limit(s.handleImages))
+		api.Handle("/images/pull-failures", s.limit(s.handlePullFailures))
+		api.Handle("/can-reach", s.limit(s.handleCanReach))
+		api.Handle("/lookup/ip", s.limit(s.handleLookupIP))
+		api.Handle("/selectors/test", s.limit(s.handleSelectorTest))
+	}
+	if len(s.alertProviders) > 0 {
+		api.Handle("/alerts", s.limit(s.handleAlerts))
+	}
+	if s.disruptionProvider != nil {
+		api.Handle("/disruptions", s.limit(s.handleDisruptions))
+	}
+	if s.churnProvider != nil {
+		api.Handle("/churn", s.limit(s.handleChurn))
+	}
+	if len(s.eventProviders) > 0 {
+		api.Handle("/events", s.limit(s.handleEvents))
+	}
+	if s.timelineProvider != nil {
+		api.Handle("/resources/{kind}/{namespace}/{name}/timeline", s.limit(s.handleTimeline))
+	}
+	if s.annotationProvider != nil {
+		api.Handle("/annotations", s.limit(s.audited("annotate", s.handleAnnotations)))
+		mux.HandleFunc("/ws/annotations", s.handleAnnotationStream)
+	}
+	if s.apiProbeProvider != nil {
+		mux.HandleFunc("/readyz", s.handleReady)
+	}
+	if s.favoritesProvider != nil {
+		api.Handle("/favorites", s.limit(s.audited("pin", s.handleFavorites)))
+		api.Handle("/favorites/{id...}", s.limit(s.audited("unpin", s.handleUnpin)))
+	}
+	if s.adminProvider != nil && s.adminToken != "" {
+		api.Handle("/admin/watches", s.admin(s.handleWatches))
+		api.Handle("/admin/watches/{resource}", s.admin(s.audited("set-watch", s.handleSetWatch)))
+		api.Handle("/admin/resync", s.admin(s.audited("resync", s.handleResync)))
+		api.Handle("/admin/stores", s.admin(s.handleStores))
+		api.Handle("/admin/stores/{resource}", s.admin(s.handleStore))
+		api.Handle("/admin/clients", s.admin(s.handleClients))
+		api.Handle("/admin/memory", s.admin(s.handleMemory))
+		api.Handle("/admin/memory/{resource}", s.admin(s.audited("shrink", s.handleStoreMemory)))
+		api.Handle("/admin/audit", s.admin(s.handleAudit))
+		s.debugRoutes(mux)
+	}
+	if s.endpointProvider != nil {
+		mux.HandleFunc("/ws/endpoints", s.handleEndpointStream)
+	}
+
+	switch {
+	case s.apiOnly:
+		mux.Handle("/", notFoundHandler())
+	case s.staticDir != "":
+		mux.Handle("/", staticHandler(s.staticDir))
+	}
+
+	for _, r := range s.routes {
+		mux.Handle(r.pattern, r.handler)
+	}
+
+	var handler http.Handler = mux
+	if s.anonymizer != nil {
+		handler = s.anonymizeResponses(handler)
+	}
+	for i := len(s.middleware) - 1; i >= 0; i-- {
+		handler = s.middleware[i](handler)
+	}
+	if s.accessLog {
+		handler = accessLog(mux, handler)
+	}
+	return handler
+}
+
+// limit applies the configured rate and concurrency limits to handler
+func (s *Server) limit(handler http.HandlerFunc) http.Handler {
+	if s.limiter == nil {
+		return handler
+	}
+	return s.limiter.Middleware(handler)
+}
+
+// handleState serves the health data of every service, or with ?os= and ?selector= only of the
+// services left in the filtered hierarchy, along with when each kind last changed. ?fields= limits
+// each service to the listed fields besides its name and namespace.
+// It responds 503 with syncing set until the initial state has been built.
+func (s *Server) handleState(w http.ResponseWriter, r *http.Request) {
+	requested, err := fields(r.URL.Query(), reflect.TypeFor[types.ServiceHealthInfo]())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusBadRequest)
+		return
+	}
+	w.Header().Set("X-Schema-Version", strconv.Itoa(schema.Version))
+
+	var state types.State
+	if s.syncStatusProvider != nil {
+		status := s.syncStatusProvider.SyncStatus(r.Context())
+		state.Kinds = status.Kinds
+		state.Degraded = status.Degraded
+		state.Syncing = status.Syncing
+		state.Stale = status.Stale
+	}
+	if state.Syncing && !state.Stale {
+		state.Services = []*types.ServiceHealthInfo{}
+		w.Header().Set("Content-Type", "application/json")
+		w.WriteHeader(http.StatusServiceUnavailable)
+		json.NewEncoder(w).Encode(state) //nolint:errcheck
+		return
+	}
+
+	state.Services = s.healthProvider.GetAllHealthData()
+	if r.URL.Query().Get("os") != "" || r.URL.Query().Get("selector") != "" || r.URL.Query().Get("favorites") != "" {
+		if s.hierarchyProvider == nil {
+			http.Error(w, "the os, selector and favorites filters need a hierarchy provider", http.StatusBadRequest)
+			return
+		}
+		nodes, err := s.hierarchyProvider.GetHierarchy(r.Context())
+		if err != nil {
+			http.Error(w, err.Error(), http.StatusInternalServerError)
+			return
+		}
+		nodes, err = s.filterNodes(nodes, r.URL.Query())
+		if err != nil {
+			http.Error(w, err.Error(), http.StatusBadRequest)
+			return
+		}
+		state.Services = healthForServices(state.Services, nodes)
+	}
+
+	services, err := project(state.Services, serviceProjection, requested)
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(projectedState{State: state, Services: services}); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+// handleSchema serves the JSON Schema of the /state payload. Its schema_version is also sent with every
+// /state response in the X-Schema-Version header, so clients can check they were built against it.
+func (s *Server) handleSchema(w http.ResponseWriter, r *http.Request) {
+	document, err := schema.JSONSchema(reflect.TypeFor[types.State]())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	w.Header().Set("Content-Type", "application/schema+json")
+	if err := json.NewEncoder(w).Encode(document); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+// handleHierarchy serves the resource tree, optionally filtered with ?os= and ?selector=, regrouped
+// under ?root=gateway, app or node instead of namespaces and cut down to the ?fields= of each node
+func (s *Server) handleHierarchy(w http.ResponseWriter, r *http.Request) {
+	requested, err := fields(r.URL.Query(), reflect.TypeFor[apiv1.Node]())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusBadRequest)
+		return
+	}
+
+	nodes, err := s.hierarchyProvider.GetHierarchy(r.Context())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+	nodes, err = s.filterNodes(nodes, r.URL.Query())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusBadRequest)
+		return
+	}
+	nodes, err = hierarchy.Reroot(nodes, types.HierarchyRoot(r.URL.Query().Get("root")))
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusBadRequest)
+		return
+	}
+
+	projected, err := project(apiv1.FromNodes(hierarchy.Truncate(nodes, s.maxChildren)), nodeProjection, requested)
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(projected); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+// handleNamespace serves one namespace with its children from ?offset= onwards, fetching the children
+// a Truncated node stands in for. It takes the same ?os= and ?selector= filters as /hierarchy.
+func (s *Server) handleNamespace(w http.ResponseWriter, r *http.Request) {
+	offset := 0
+	if value := r.URL.Query().Get("offset"); value != "" {
+		parsed, err := strconv.Atoi(value)
+		if err != nil || parsed < 0 {
+			http.Error(w, "offset must be a non-negative integer", http.StatusBadRequest)
+			return
+		}
+		offset = parsed
+	}
+
+	nodes, err := s.hierarchyProvider.GetHierarchy(r.Context())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+	nodes, err = s.filterNodes(nodes, r.URL.Query())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusBadRequest)
+		return
+	}
+
+	namespace := r.PathValue("namespace")
+	for _, node := range nodes {
+		if node.Kind != types.ResourceKindNamespace || node.Name != namespace {
+			continue
+		}
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(apiv1.FromNode(hierarchy.Page(node, offset, s.maxChildren))); err != nil {
+			http.Error(w, err.Error(), http.StatusInternalServerError)
+		}
+		return
+	}
+	http.Error(w, fmt.Sprintf("namespace %q not found", namespace), http.StatusNotFound)
+}
+
+// filterNodes applies the ?os= filter, the ?selector= label selector, e.g. "tier in (web,api),!canary",
+// and ?favorites=true, which keeps the pinned nodes
+func (s *Server) filterNodes(nodes []types.HierarchyNode, query url.Values) ([]types.HierarchyNode, error) {
+	if osName := query.Get("os"); osName != "" {
+		nodes = hierarchy.FilterByOS(nodes, osName)
+	}
+	if value := query.Get("favorites"); value != "" {
+		favorites, err := strconv.ParseBool(value)
+		if err != nil {
+			return nil, fmt.Errorf("invalid favorites filter: %w", err)
+		}
+		if favorites {
+			if s.favoritesProvider == nil {
+				return nil, fmt.Errorf("favorites are not enabled")
+			}
+			nodes = hierarchy.FilterByIDs(nodes, s.favoritesProvider.Favorites()...)
+		}
+	}
+	if value := query.Get("selector"); value != "" {
+		selector, err := labels.Parse(value)
+		if err != nil {
+			return nil, fmt.Errorf("invalid selector: %w", err)
+		}
+		nodes = hierarchy.FilterBySelector(nodes, selector)
+	}
+	return nodes, nil
+}
+
+// handleExport serves the hierarchy as diagram source in the format in the path, d2 or plantuml. It is
+// scoped to the namespaces given by ?namespace=, repeated or comma separated, and takes the /hierarchy filters.
+func (s *Server) handleExport(w http.ResponseWriter, r *http.Request) {
+	nodes, err := s.hierarchyProvider.GetHierarchy(r.Context())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+	nodes, err = s.filterNodes(nodes, r.URL.Query())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusBadRequest)
+		return
+	}
+	var namespaces []string
+	for _, value := range r.URL.Query()["namespace"] {
+		namespaces = append(namespaces, strings.Split(value, ",")...)
+	}
+	if len(namespaces) > 0 {
+		nodes = hierarchy.FilterByNamespace(nodes, namespaces...)
+	}
+	nodes, err = anonymizeNodes(s.anonymizer, nodes)
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	format := export.Format(r.PathValue("format"))
+	var diagram bytes.Buffer
+	if err := export.Write(&diagram, format, nodes); err != nil {
+		http.Error(w, err.Error(), http.StatusNotFound)
+		return
+	}
+	w.Header().Set("Content-Type", export.ContentType(format))
+	if _, err := diagram.WriteTo(w); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+// handleImpact serves the blast radius of the resource in the path: what depends on it and what it depends
+// on. Cluster scoped resources, such as Nodes and hostnames, are addressed without a namespace.
+func (s *Server) handleImpact(w http.ResponseWriter, r *http.Request) {
+	nodes, err := s.hierarchyProvider.GetHierarchy(r.Context())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	var namespace *string
+	if value := r.PathValue("namespace"); value != "" {
+		namespace = &value
+	}
+	id := hierarchy.NodeID(types.ResourceKind(r.PathValue("kind")), namespace, r.PathValue("name"))
+	impact, found := hierarchy.NewGraph(nodes).Impact(id)
+	if !found {
+		http.Error(w, fmt.Sprintf("%s not found", id), http.StatusNotFound)
+		return
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(impact); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+// handlePath serves the chains of dependencies between ?from= and ?to=, both node IDs such as
+// Hostname/shop.example.com or Pod/shop/web-0, answering related=false when there are none
+func (s *Server) handlePath(w http.ResponseWriter, r *http.Request) {
+	from, to := r.URL.Query().Get("from"), r.URL.Query().Get("to")
+	if from == "" || to == "" {
+		http.Error(w, "from and to are required", http.StatusBadRequest)
+		return
+	}
+
+	nodes, err := s.hierarchyProvider.GetHierarchy(r.Context())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	paths, found := hierarchy.NewGraph(nodes).Paths(from, to)
+	if !found {
+		http.Error(w, fmt.Sprintf("%s or %s not found", from, to), http.StatusNotFound)
+		return
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(paths); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+// handleLabels serves every label key with the number of resources carrying each value
+func (s *Server) handleLabels(w http.ResponseWriter, r *http.Request) {
+	nodes, err := s.hierarchyProvider.GetHierarchy(r.Context())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(hierarchy.LabelFacets(nodes)); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+// handleStats serves the largest namespaces, Services and routes, ?limit= of each defaulting to 10
+func (s *Server) handleStats(w http.ResponseWriter, r *http.Request) {
+	limit := defaultStatsLimit
+	if value := r.URL.Query().Get("limit"); value != "" {
+		parsed, err := strconv.Atoi(value)
+		if err != nil || parsed < 1 {
+			http.Error(w, "limit must be a positive integer", http.StatusBadRequest)
+			return
+		}
+		limit = parsed
+	}
+
+	nodes, err := s.hierarchyProvider.GetHierarchy(r.Context())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(hierarchy.Rank(nodes, limit)); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+// handleOwners serves the resources in the hierarchy grouped by their owner
+func (s *Server) handleOwners(w http.ResponseWriter, r *http.Request) {
+	nodes, err := s.hierarchyProvider.GetHierarchy(r.Context())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(hierarchy.GroupByOwner(nodes)); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+// healthForServices keeps the health data of the Service nodes in the tree
+func healthForServices(healthData []*types.ServiceHealthInfo, nodes []types.HierarchyNode) []*types.ServiceHealthInfo {
+	services := make(map[string]bool)
+	hierarchy.Walk(nodes, func(node *types.HierarchyNode) {
+		if node.Kind == types.ResourceKindService && node.Namespace != nil {
+			services[*node.Namespace+"/"+node.Name] = true
+		}
+	})
+
+	result := make([]*types.ServiceHealthInfo, 0, len(healthData))
+	for _, info := range healthData {
+		if services[info.Namespace+"/"+info.ServiceName] {
+			result = append(result, info)
+		}
+	}
+	return result
+}
+
+func (s *Server) handleGraphQL(w http.ResponseWriter, r *http.Request) {
+	req := graphql.Request{Query: r.URL.Query().Get("query")}
+	if variables := r.URL.Query().Get("variables"); variables != "" {
+		if err := json.Unmarshal([]byte(variables), &req.Variables); err != nil {
+			http.Error(w, fmt.Sprintf("invalid graphql variables: %v", err), http.StatusBadRequest)
+			return
+		}
+	}
+	if r.Method == http.MethodPost {
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, fmt.Sprintf("invalid graphql request: %v", err), http.StatusBadRequest)
+			return
+		}
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(graphql.Execute(r.Context(), s.hierarchyProvider, req)); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+func (s *Server) handleSnapshot(w http.ResponseWriter, r *http.Request) {
+	snapshot, err := s.snapshotProvider.Snapshot(r.Context())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	filename := fmt.Sprintf("constellation-snapshot-%s.json", snapshot.Timestamp.Format("20060102T150405Z"))
+	w.Header().Set("Content-Type", "application/json")
+	w.Header().Set("Content-Disposition", fmt.Sprintf("attachment; filename=%q", filename))
+	if err := json.NewEncoder(w).Encode(snapshot); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
 }
 
-type HealthDataProvider interface {
-	GetAllHealthData() []*types.ServiceHealthInfo
-	Subscribe() chan []*types.ServiceHealthInfo
-	Unsubscribe(chan []*types.ServiceHealthInfo)
+// handleImages serves the image inventory of the snapshot's pods, optionally filtered by
+// ?repository= and ?tag=
+func (s *Server) handleImages(w http.ResponseWriter, r *http.Request) {
+	snapshot, err := s.snapshotProvider.Snapshot(r.Context())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	query := r.URL.Query()
+	inventory := images.Filter(images.Inventory(snapshot.Objects.Pods), query.Get("repository"), query.Get("tag"))
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(inventory); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
 }
 
-type Server struct {
-	healthProvider HealthDataProvider
-	staticDir      string
-	port           int
+func (s *Server) handlePullFailures(w http.ResponseWriter, r *http.Request) {
+	snapshot, err := s.snapshotProvider.Snapshot(r.Context())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(images.PullFailures(snapshot.Objects.Pods)); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
 }
 
-func NewServer(healthProvider HealthDataProvider, staticDir string, port int) *Server {
-	return &Server{
-		healthProvider: healthProvider,
-		staticDir:      staticDir,
-		port:           port,
+// handleCanReach evaluates NetworkPolicies for a POSTed types.ReachQuery
+func (s *Server) handleCanReach(w http.ResponseWriter, r *http.Request) {
+	if r.Method != http.MethodPost {
+		http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+		return
+	}
+
+	var query types.ReachQuery
+	if err := json.NewDecoder(r.Body).Decode(&query); err != nil {
+		http.Error(w, fmt.Sprintf("invalid reachability query: %v", err), http.StatusBadRequest)
+		return
+	}
+
+	snapshot, err := s.snapshotProvider.Snapshot(r.Context())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	result, err := netpol.CanReach(snapshot.Objects, query)
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusBadRequest)
+		return
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(result); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
 	}
 }
 
-func (s *Server) Serve(ctx context.Context) error {
-	mux := http.NewServeMux()
+// handleLookupIP finds the pods, services and nodes holding the IPv4 or IPv6 address in ?ip=
+func (s *Server) handleLookupIP(w http.ResponseWriter, r *http.Request) {
+	ip := r.URL.Query().Get("ip")
+	if ip == "" {
+		http.Error(w, "missing ip query parameter", http.StatusBadRequest)
+		return
+	}
 
-	mux.HandleFunc("/state", s.handleState)
-	mux.HandleFunc("/ws", s.handleWebSocket)
-	mux.HandleFunc("/healthz", s.handleHealth)
+	snapshot, err := s.snapshotProvider.Snapshot(r.Context())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
 
-	if s.staticDir != "" {
-		fileServer := http.FileServer(http.Dir(s.staticDir))
-		mux.Handle("/", s.staticFileHandler(fileServer))
+	result, err := lookup.IP(snapshot.Objects, ip)
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusBadRequest)
+		return
 	}
 
-	httpServer := &http.Server{
-		Addr:    fmt.Sprintf(":%d", s.port),
-		Handler: mux,
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(result); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
 	}
+}
 
-	go func() {
-		<-ctx.Done()
-		httpServer.Shutdown(context.Background())
-	}()
+// handleSelectorTest returns the pods and services matching a POSTed types.SelectorTest
+func (s *Server) handleSelectorTest(w http.ResponseWriter, r *http.Request) {
+	if r.Method != http.MethodPost {
+		http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+		return
+	}
 
-	if err := httpServer.ListenAndServe(); err != nil && err != http.ErrServerClosed {
-		return fmt.Errorf("HTTP server failed: %v", err)
+	var test types.SelectorTest
+	if err := json.NewDecoder(r.Body).Decode(&test); err != nil {
+		http.Error(w, fmt.Sprintf("invalid selector test: %v", err), http.StatusBadRequest)
+		return
+	}
+
+	snapshot, err := s.snapshotProvider.Snapshot(r.Context())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	result, err := lookup.Selector(snapshot.Objects, test)
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusBadRequest)
+		return
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(result); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
 	}
-	return nil
 }
 
-func (s *Server) handleState(w http.ResponseWriter, r *http.Request) {
-	healthData := s.healthProvider.GetAllHealthData()
+func (s *Server) handleAlerts(w http.ResponseWriter, r *http.Request) {
+	alerts := make([]types.Alert, 0)
+	for _, provider := range s.alertProviders {
+		providerAlerts, err := provider.GetAlerts(r.Context())
+		if err != nil {
+			http.Error(w, err.Error(), http.StatusInternalServerError)
+			return
+		}
+		alerts = append(alerts, providerAlerts...)
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(alerts); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+func (s *Server) handleDisruptions(w http.ResponseWriter, r *http.Request) {
+	disruptions, err := s.disruptionProvider.GetDisruptions(r.Context())
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(disruptions); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+func (s *Server) handleChurn(w http.ResponseWriter, r *http.Request) {
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(s.churnProvider.Churn()); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+func (s *Server) handleEvents(w http.ResponseWriter, r *http.Request) {
+	events := make([]types.Event, 0)
+	for _, provider := range s.eventProviders {
+		events = append(events, provider.Events()...)
+	}
+	sort.SliceStable(events, func(i, j int) bool { return events[i].Time.After(events[j].Time) })
+
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(events); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+func (s *Server) handleTimeline(w http.ResponseWriter, r *http.Request) {
+	kind := types.ResourceKind(r.PathValue("kind"))
+	timeline := s.timelineProvider.Timeline(kind, r.PathValue("namespace"), r.PathValue("name"))
 
 	w.Header().Set("Content-Type", "application/json")
-	if err := json.NewEncoder(w).Encode(healthData); err != nil {
+	if err := json.NewEncoder(w).Encode(timeline); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+func (s *Server) handleAnnotations(w http.ResponseWriter, r *http.Request) {
+	if r.Method == http.MethodGet {
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(s.annotationProvider.Annotations()); err != nil {
+			http.Error(w, err.Error(), http.StatusInternalServerError)
+		}
+		return
+	}
+	if r.Method != http.MethodPost {
+		http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+		return
+	}
+
+	annotation, err := annotations.Decode(r)
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusBadRequest)
+		return
+	}
+
+	// encoded before the status is written, so a failure can still be reported as a 500
+	var body bytes.Buffer
+	if err := json.NewEncoder(&body).Encode(s.annotationProvider.Add(annotation)); err != nil {
 		http.Error(w, err.Error(), http.StatusInternalServerError)
 		return
 	}
+	w.Header().Set("Content-Type", "application/json")
+	w.WriteHeader(http.StatusAccepted)
+	w.Write(body.Bytes()) //nolint:errcheck
+}
+
+func (s *Server) handleAnnotationStream(w http.ResponseWriter, r *http.Request) {
+	updates := s.annotationProvider.Subscribe()
+	defer s.annotationProvider.Unsubscribe(updates)
+	stream(s, w, r, s.annotationProvider.Annotations(), updates)
 }
 
 func (s *Server) handleWebSocket(w http.ResponseWriter, r *http.Request) {
+	healthChan := s.healthProvider.Subscribe()
+	defer s.healthProvider.Unsubscribe(healthChan)
+	stream(s, w, r, s.healthProvider.GetAllHealthData(), healthChan)
+}
+
+// handleGraphQLStream runs a GraphQL subscription over a WebSocket. The client sends the request as its
+// first message and gets a response resolved from the current health data, then one per health update.
+func (s *Server) handleGraphQLStream(w http.ResponseWriter, r *http.Request) {
 	conn, err := upgrader.Upgrade(w, r, nil)
 	if err != nil {
 		http.Error(w, fmt.Sprintf("WebSocket upgrade error: %v", err), http.StatusBadRequest)
 		return
 	}
-	defer func() {
-		fmt.Printf("WebSocket connection closed\n")
+
+	var request graphql.Request
+	if err := conn.ReadJSON(&request); err != nil {
 		conn.Close()
+		return
+	}
+	subscription, err := graphql.Subscribe(request)
+	if err != nil {
+		s.writeMessage(conn, graphql.Response{Errors: []graphql.Error{{Message: err.Error()}}}) //nolint:errcheck
+		conn.Close()
+		return
+	}
+
+	healthChan := s.healthProvider.Subscribe()
+	defer s.healthProvider.Unsubscribe(healthChan)
+	ctx, cancel := context.WithCancel(r.Context())
+	defer cancel()
+	responses := make(chan graphql.Response, 1)
+	go func() {
+		for {
+			select {
+			case health := <-healthChan:
+				select {
+				case responses <- subscription.Resolve(health):
+				case <-ctx.Done():
+					return
+				}
+			case <-ctx.Done():
+				return
+			}
+		}
 	}()
+	relay(s, conn, r, subscription.Resolve(s.healthProvider.GetAllHealthData()), responses)
+}
 
-	fmt.Printf("WebSocket connection established\n")
+func (s *Server) handleEndpointStream(w http.ResponseWriter, r *http.Request) {
+	updates := s.endpointProvider.Subscribe()
+	defer s.endpointProvider.Unsubscribe(updates)
+	stream(s, w, r, s.endpointProvider.GetEndpoints(), updates)
+}
 
-	conn.SetReadLimit(maxMessageSize)
-	conn.SetReadDeadline(time.Now().Add(pongWait))
-	conn.SetPongHandler(func(string) error {
-		conn.SetReadDeadline(time.Now().Add(pongWait))
-		return nil
+// handleNodeStream streams the subtree of a single node. The client picks the node by sending
+// {"watch": {"kind": "Service", "namespace": "prod", "name": "api"}}, or the node's id to pick one copy of a
+// resource with several parents, and may switch at any time.
+// The hierarchy is built once for all followers on every health update and every nodeRefreshInterval, and
+// the subtree is only sent when it changed.
+func (s *Server) handleNodeStream(w http.ResponseWriter, r *http.Request) {
+	s.followNode(w, r, func(conn *websocket.Conn, done <-chan struct{}) <-chan string {
+		watches := make(chan string)
+		go func() {
+			defer close(watches)
+			for {
+				var request types.WatchRequest
+				if err := conn.ReadJSON(&request); err != nil {
+					return
+				}
+				if request.Watch == nil {
+					continue
+				}
+				select {
+				case watches <- watchedID(request.Watch):
+				case <-done:
+					return
+				}
+			}
+		}()
+		return watches
 	})
+}
 
-	healthChan := s.healthProvider.Subscribe()
-	defer s.healthProvider.Unsubscribe(healthChan)
+// watchedID returns the ID of the node watch selects, its first copy when it names a resource by kind and name
+func watchedID(watch *types.NodeWatch) string {
+	if watch.ID != "" {
+		return watch.ID
+	}
+	var namespace *string
+	if watch.Namespace != "" {
+		namespace = &watch.Namespace
+	}
+	return hierarchy.NodeID(watch.Kind, namespace, watch.Name)
+}
+
+// handleNamespaceStream streams the subtree of the namespace in the path, so a dashboard for one team
+// is rebuilt and sent only when that namespace changes rather than on every change in the cluster
+func (s *Server) handleNamespaceStream(w http.ResponseWriter, r *http.Request) {
+	s.followNode(w, r, watchOnly(hierarchy.NodeID(types.ResourceKindNamespace, nil, r.PathValue("namespace"))))
+}
+
+// handleHierarchyStream streams the whole hierarchy whenever it changes, e.g. for a follower replicating
+// this instance
+func (s *Server) handleHierarchyStream(w http.ResponseWriter, r *http.Request) {
+	s.followNode(w, r, watchOnly(wholeHierarchy))
+}
+
+// watchOnly follows the node with id for as long as the client stays connected, ignoring its messages
+func watchOnly(id string) nodeWatcher {
+	return func(conn *websocket.Conn, _ <-chan struct{}) <-chan string {
+		watches := make(chan string, 1)
+		watches <- id
+		go func() {
+			defer close(watches)
+			for {
+				if _, _, err := conn.ReadMessage(); err != nil {
+					return
+				}
+			}
+		}()
+		return watches
+	}
+}
+
+// nodeWatcher reads which node to follow from conn and closes the returned channel when the client goes
+// away. It stops sending once done is closed.
+type nodeWatcher func(conn *websocket.Conn, done <-chan struct{}) <-chan string
 
-	if err := s.writeMessage(conn, s.healthProvider.GetAllHealthData()); err != nil {
-		fmt.Printf("WebSocket initial write error: %v\n", err)
+// followNode upgrades the request to a WebSocket and sends a NodeUpdate of the node last received from watch,
+// or the whole hierarchy when that is wholeHierarchy
+func (s *Server) followNode(w http.ResponseWriter, r *http.Request, watch nodeWatcher) {
+	conn, err := upgrader.Upgrade(w, r, nil)
+	if err != nil {
+		http.Error(w, fmt.Sprintf("WebSocket upgrade error: %v", err), http.StatusBadRequest)
+		return
+	}
+	defer conn.Close()
+
+	subscriber := s.nodes.subscribe()
+	defer s.nodes.unsubscribe(subscriber)
+	connection := s.clients.connect(r.URL.Path, remoteIP(r), func() int { return len(subscriber.updates) })
+	defer s.clients.disconnect(connection)
+	s.heartbeat(conn, connection)
+
+	done := make(chan struct{})
+	defer close(done)
+	watches := watch(conn, done)
+
+	pingTicker := time.NewTicker(pingPeriod)
+	defer pingTicker.Stop()
+
+	var last []byte
+	for {
+		select {
+		case id, ok := <-watches:
+			if !ok {
+				return
+			}
+			last = nil
+			connection.filter(id)
+			s.nodes.watch(subscriber, id)
+		case data := <-subscriber.updates:
+			if bytes.Equal(data, last) {
+				continue
+			}
+			last = data
+			if err := s.writePayload(conn, data); err != nil {
+				return
+			}
+			connection.sent()
+		case <-pingTicker.C:
+			if err := s.ping(conn); err != nil {
+				return
+			}
+		case <-r.Context().Done():
+			return
+		}
+	}
+}
+
+// stream upgrades the request to a WebSocket and writes initial, then every update, until the client goes away
+func stream[T any](s *Server, w http.ResponseWriter, r *http.Request, initial T, updates <-chan T) {
+	conn, err := upgrader.Upgrade(w, r, nil)
+	if err != nil {
+		http.Error(w, fmt.Sprintf("WebSocket upgrade error: %v", err), http.StatusBadRequest)
+		return
+	}
+	relay(s, conn, r, initial, updates)
+}
+
+// relay writes initial, then every update, to an upgraded connection until the client goes away
+func relay[T any](s *Server, conn *websocket.Conn, r *http.Request, initial T, updates <-chan T) {
+	logger := log.FromContext(r.Context()).WithValues("path", r.URL.Path)
+	defer func() {
+		logger.V(1).Info("WebSocket connection closed")
+		conn.Close()
+	}()
+
+	logger.V(1).Info("WebSocket connection established")
+
+	connection := s.clients.connect(r.URL.Path, remoteIP(r), func() int { return len(updates) })
+	defer s.clients.disconnect(connection)
+	s.heartbeat(conn, connection)
+
+	if err := s.writeMessage(conn, initial); err != nil {
+		logger.Error(err, "WebSocket initial write failed")
 		return
 	}
+	connection.sent()
 
 	go func() {
 		for {
 			_, _, err := conn.ReadMessage()
 			if err != nil {
-				fmt.Printf("WebSocket read error: %v\n", err)
+				logger.V(1).Info("WebSocket read ended", "reason", err.Error())
 				return
 			}
 		}
@@ -126,15 +1413,15 @@ func (s *Server) handleWebSocket(w http.ResponseWriter, r *http.Request) {
 
 	for {
 		select {
-		case data := <-healthChan:
+		case data := <-updates:
 			if err := s.writeMessage(conn, data); err != nil {
-				fmt.Printf("WebSocket write error: %v\n", err)
+				logger.Error(err, "WebSocket write failed")
 				return
 			}
+			connection.sent()
 		case <-pingTicker.C:
-			conn.SetWriteDeadline(time.Now().Add(writeWait))
-			if err := conn.WriteMessage(websocket.PingMessage, nil); err != nil {
-				fmt.Printf("WebSocket ping error: %v\n", err)
+			if err := s.ping(conn); err != nil {
+				logger.Error(err, "WebSocket ping failed")
 				return
 			}
 		case <-r.Context().Done():
@@ -144,8 +1431,48 @@ func (s *Server) handleWebSocket(w http.ResponseWriter, r *http.Request) {
 }
 
 func (s *Server) writeMessage(conn *websocket.Conn, data any) error {
-	conn.SetWriteDeadline(time.Now().Add(writeWait))
-	return conn.WriteJSON(data)
+	payload, err := json.Marshal(data)
+	if err != nil {
+		return err
+	}
+	if s.anonymizer != nil {
+		payload, err = s.anonymizer.JSON(payload)
+		if err != nil {
+			return err
+		}
+	}
+	return s.writePayload(conn, payload)
+}
+
+// handleReady reports the latest apiserver probe, with 503 while the apiserver is slow or failing
+func (s *Server) handleReady(w http.ResponseWriter, r *http.Request) {
+	status := s.apiProbeProvider.Status()
+	w.Header().Set("Content-Type", "application/json")
+	if !status.Ready {
+		w.WriteHeader(http.StatusServiceUnavailable)
+	}
+	if err := json.NewEncoder(w).Encode(status); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+}
+
+// handleUIConfig serves the frontend options, revalidated on every load so a changed flag is picked up
+func (s *Server) handleUIConfig(w http.ResponseWriter, r *http.Request) {
+	config := s.uiConfig
+	if config.DefaultNamespaces == nil {
+		config.DefaultNamespaces = []string{}
+	}
+	if config.Features == nil {
+		config.Features = map[string]bool{}
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	w.Header().Set("Cache-Control", revalidateCacheControl)
+	if err := json.NewEncoder(w).Encode(config); err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
 }
 
 func (s *Server) handleHealth(w http.ResponseWriter, r *http.Request) {
@@ -165,9 +1492,3 @@ func (s *Server) handleHealth(w http.ResponseWriter, r *http.Request) {
 		"message": "ready",
 	})
 }
-
-func (s *Server) staticFileHandler(fileServer http.Handler) http.HandlerFunc {
-	return func(w http.ResponseWriter, r *http.Request) {
-		fileServer.ServeHTTP(w, r)
-	}
-}
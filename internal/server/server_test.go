@@ -0,0 +1,188 @@
+package server
+
+import (
+	"context"
+	"errors"
+	"net"
+	"net/http"
+	"net/http/httptest"
+	"slices"
+	"testing"
+	"time"
+)
+
+func TestNew(t *testing.T) {
+	tests := []struct {
+		name          string
+		server        *Server
+		wantAddr      string
+		wantShutdown  time.Duration
+		wantStaticDir string
+	}{
+		{
+			name:         "defaults",
+			server:       New(fakeHealth{}),
+			wantAddr:     ":8080",
+			wantShutdown: 5 * time.Second,
+		},
+		{
+			name:         "port",
+			server:       New(fakeHealth{}, WithPort(9090)),
+			wantAddr:     ":9090",
+			wantShutdown: 5 * time.Second,
+		},
+		{
+			name:         "address replaces the port",
+			server:       New(fakeHealth{}, WithPort(9090), WithAddr("127.0.0.1:9000")),
+			wantAddr:     "127.0.0.1:9000",
+			wantShutdown: 5 * time.Second,
+		},
+		{
+			name:         "shutdown timeout",
+			server:       New(fakeHealth{}, WithShutdownTimeout(time.Second)),
+			wantAddr:     ":8080",
+			wantShutdown: time.Second,
+		},
+		{
+			name:          "NewServer wrapper",
+			server:        NewServer(fakeHealth{}, "dist", 9090, WithShutdownTimeout(time.Second)),
+			wantAddr:      ":9090",
+			wantShutdown:  time.Second,
+			wantStaticDir: "dist",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if tt.server.addr != tt.wantAddr {
+				t.Errorf("TestNew() addr = %q, want %q", tt.server.addr, tt.wantAddr)
+			}
+			if tt.server.shutdownTimeout != tt.wantShutdown {
+				t.Errorf("TestNew() shutdownTimeout = %v, want %v", tt.server.shutdownTimeout, tt.wantShutdown)
+			}
+			if tt.server.staticDir != tt.wantStaticDir {
+				t.Errorf("TestNew() staticDir = %q, want %q", tt.server.staticDir, tt.wantStaticDir)
+			}
+		})
+	}
+}
+
+func TestServer_Handler_RoutesAndMiddleware(t *testing.T) {
+	trace := func(name string) Middleware {
+		return func(next http.Handler) http.Handler {
+			return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+				w.Header().Add("X-Trace", name)
+				next.ServeHTTP(w, r)
+			})
+		}
+	}
+	custom := http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		w.Header().Add("X-Trace", "handler")
+		w.Write([]byte("custom")) //nolint:errcheck
+	})
+	handler := New(fakeHealth{},
+		WithRoute("/custom", custom),
+		WithMiddleware(trace("outer"), trace("middle")),
+		WithMiddleware(trace("inner")),
+	).Handler()
+
+	tests := []struct {
+		name       string
+		path       string
+		wantStatus int
+		wantTrace  []string
+		wantBody   string
+	}{
+		{
+			name:       "custom route",
+			path:       "/custom",
+			wantStatus: http.StatusOK,
+			wantTrace:  []string{"outer", "middle", "inner", "handler"},
+			wantBody:   "custom",
+		},
+		{
+			name:       "built-in route",
+			path:       "/state",
+			wantStatus: http.StatusOK,
+			wantTrace:  []string{"outer", "middle", "inner"},
+		},
+		{
+			name:       "versioned alias",
+			path:       "/api/v1/state",
+			wantStatus: http.StatusOK,
+			wantTrace:  []string{"outer", "middle", "inner"},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			recorder := httptest.NewRecorder()
+			handler.ServeHTTP(recorder, httptest.NewRequest(http.MethodGet, tt.path, nil))
+
+			if recorder.Code != tt.wantStatus {
+				t.Fatalf("TestServer_Handler_RoutesAndMiddleware() status = %v, want %v", recorder.Code, tt.wantStatus)
+			}
+			if got := recorder.Header().Values("X-Trace"); !slices.Equal(got, tt.wantTrace) {
+				t.Errorf("TestServer_Handler_RoutesAndMiddleware() trace = %v, want %v", got, tt.wantTrace)
+			}
+			if tt.wantBody != "" && recorder.Body.String() != tt.wantBody {
+				t.Errorf("TestServer_Handler_RoutesAndMiddleware() body = %q, want %q", recorder.Body.String(), tt.wantBody)
+			}
+		})
+	}
+}
+
+func TestServer_Serve_Shutdown(t *testing.T) {
+	tests := []struct {
+		name    string
+		hold    time.Duration
+		timeout time.Duration
+		wantErr error
+	}{
+		{
+			name:    "in-flight requests finish within the timeout",
+			hold:    50 * time.Millisecond,
+			timeout: 5 * time.Second,
+		},
+		{
+			name:    "requests outliving the timeout are cut off",
+			hold:    time.Minute,
+			timeout: 50 * time.Millisecond,
+			wantErr: context.DeadlineExceeded,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			listener, err := net.Listen("tcp", "127.0.0.1:0")
+			if err != nil {
+				t.Fatalf("TestServer_Serve_Shutdown() listen error = %v", err)
+			}
+			started := make(chan struct{})
+			release := make(chan struct{})
+			defer close(release)
+			slow := http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+				close(started)
+				select {
+				case <-time.After(tt.hold):
+				case <-release:
+				}
+			})
+			srv := New(fakeHealth{}, WithListener(listener), WithShutdownTimeout(tt.timeout), WithRoute("/slow", slow))
+
+			ctx, cancel := context.WithCancel(context.Background())
+			defer cancel()
+			served := make(chan error, 1)
+			go func() { served <- srv.Serve(ctx) }()
+			go http.Get("http://" + listener.Addr().String() + "/slow") //nolint:errcheck
+
+			<-started
+			cancel()
+			start := time.Now()
+			err = <-served
+			if !errors.Is(err, tt.wantErr) || (err == nil) != (tt.wantErr == nil) {
+				t.Fatalf("TestServer_Serve_Shutdown() error = %v, want %v", err, tt.wantErr)
+			}
+			if elapsed := time.Since(start); elapsed >= tt.hold+tt.timeout {
+				t.Errorf("TestServer_Serve_Shutdown() took %v, want less than %v", elapsed, tt.hold+tt.timeout)
+			}
+		})
+	}
+}
@@ -0,0 +1,72 @@
+package server
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+	"time"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+type fakeSnapshots struct {
+	snapshot *types.Snapshot
+	err      error
+}
+
+func (f fakeSnapshots) Snapshot(context.Context) (*types.Snapshot, error) { return f.snapshot, f.err }
+
+func TestHandleSnapshot(t *testing.T) {
+	snapshot := &types.Snapshot{
+		Version:   types.SnapshotVersion,
+		Timestamp: time.Date(2025, 1, 2, 3, 4, 5, 0, time.UTC),
+		Hierarchy: []types.HierarchyNode{{Kind: types.ResourceKindNamespace, Name: "shop"}},
+		Health:    []*types.ServiceHealthInfo{{ServiceName: "web", Namespace: "shop"}},
+	}
+	encoded, err := json.Marshal(snapshot)
+	if err != nil {
+		t.Fatalf("TestHandleSnapshot() marshal error = %v", err)
+	}
+
+	tests := []struct {
+		name            string
+		provider        fakeSnapshots
+		wantStatus      int
+		wantDisposition string
+		wantBody        string
+	}{
+		{
+			name:            "snapshot is served as a timestamped attachment",
+			provider:        fakeSnapshots{snapshot: snapshot},
+			wantStatus:      http.StatusOK,
+			wantDisposition: `attachment; filename="constellation-snapshot-20250102T030405Z.json"`,
+			wantBody:        string(encoded) + "\n",
+		},
+		{
+			name:       "failed snapshots are a server error",
+			provider:   fakeSnapshots{err: errors.New("cache not synced")},
+			wantStatus: http.StatusInternalServerError,
+			wantBody:   "cache not synced\n",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			handler := New(fakeHealth{}, WithSnapshotProvider(tt.provider)).Handler()
+			recorder := httptest.NewRecorder()
+			handler.ServeHTTP(recorder, httptest.NewRequest(http.MethodGet, "/api/v1/snapshot", nil))
+
+			if recorder.Code != tt.wantStatus {
+				t.Errorf("TestHandleSnapshot() status = %v, want %v", recorder.Code, tt.wantStatus)
+			}
+			if got := recorder.Header().Get("Content-Disposition"); got != tt.wantDisposition {
+				t.Errorf("TestHandleSnapshot() Content-Disposition = %q, want %q", got, tt.wantDisposition)
+			}
+			if got := recorder.Body.String(); got != tt.wantBody {
+				t.Errorf("TestHandleSnapshot() body = %q, want %q", got, tt.wantBody)
+			}
+		})
+	}
+}
@@ -0,0 +1,27 @@
+package server
+
+import (
+	"fmt"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+
+	"github.com/kdwils/constellation/internal/synthetic"
+)
+
+// BenchmarkHandleState measures encoding /state with a health result for every Service
+func BenchmarkHandleState(b *testing.B) {
+	for _, pods := range synthetic.Sizes {
+		handler := New(staticHealth(synthetic.Health(pods))).Handler()
+		b.Run(fmt.Sprintf("pods=%d", pods), func(b *testing.B) {
+			b.ReportAllocs()
+			for b.Loop() {
+				recorder := httptest.NewRecorder()
+				handler.ServeHTTP(recorder, httptest.NewRequest(http.MethodGet, "/state", nil))
+				if recorder.Code != http.StatusOK {
+					b.Fatalf("BenchmarkHandleState() status = %d, want %d", recorder.Code, http.StatusOK)
+				}
+			}
+		})
+	}
+}
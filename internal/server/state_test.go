@@ -0,0 +1,69 @@
+package server
+
+import (
+	"context"
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"reflect"
+	"testing"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+type staticHealth []*types.ServiceHealthInfo
+
+func (h staticHealth) GetAllHealthData() []*types.ServiceHealthInfo { return h }
+func (staticHealth) Subscribe() chan []*types.ServiceHealthInfo     { return make(chan []*types.ServiceHealthInfo) }
+func (staticHealth) Unsubscribe(chan []*types.ServiceHealthInfo)    {}
+
+type fakeSync types.SyncStatus
+
+func (f fakeSync) SyncStatus(context.Context) types.SyncStatus { return types.SyncStatus(f) }
+
+func TestHandleState_Sync(t *testing.T) {
+	health := staticHealth{{ServiceName: "web", Namespace: "shop", Status: types.HealthStatusHealthy}}
+
+	tests := []struct {
+		name       string
+		status     types.SyncStatus
+		wantStatus int
+		want       types.State
+	}{
+		{
+			name:       "synced",
+			wantStatus: http.StatusOK,
+			want:       types.State{Services: health},
+		},
+		{
+			name:       "syncing",
+			status:     types.SyncStatus{Syncing: true},
+			wantStatus: http.StatusServiceUnavailable,
+			want:       types.State{Services: []*types.ServiceHealthInfo{}, Syncing: true},
+		},
+		{
+			name:       "a persisted snapshot is served while syncing",
+			status:     types.SyncStatus{Syncing: true, Stale: true},
+			wantStatus: http.StatusOK,
+			want:       types.State{Services: health, Syncing: true, Stale: true},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			handler := New(health, WithSyncStatusProvider(fakeSync(tt.status))).Handler()
+			recorder := httptest.NewRecorder()
+			handler.ServeHTTP(recorder, httptest.NewRequest(http.MethodGet, "/state", nil))
+
+			if recorder.Code != tt.wantStatus {
+				t.Fatalf("TestHandleState_Sync() status = %d, want %d", recorder.Code, tt.wantStatus)
+			}
+			var got types.State
+			if err := json.NewDecoder(recorder.Body).Decode(&got); err != nil {
+				t.Fatalf("TestHandleState_Sync() decode error = %v", err)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestHandleState_Sync() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,99 @@
+package server
+
+import (
+	"encoding/json"
+	"net/http"
+	"os"
+	"path"
+	"path/filepath"
+	"strings"
+)
+
+const (
+	// assetsPrefix is where Vite writes content hashed bundles, which never change under the same name
+	assetsPrefix = "/assets/"
+	indexFile    = "index.html"
+
+	immutableCacheControl  = "public, max-age=31536000, immutable"
+	revalidateCacheControl = "no-cache"
+)
+
+// encodings are the precompressed variants served in order of preference, e.g. app.js.br next to app.js
+var encodings = []struct {
+	name      string
+	extension string
+}{
+	{name: "br", extension: ".br"},
+	{name: "gzip", extension: ".gz"},
+}
+
+// staticHandler serves the built frontend from dir. Hashed assets are cached forever and everything else
+// is revalidated, so a deploy is picked up on the next load. Paths without a file extension that match
+// no file are client-side routes and get index.html, while a missing asset is a 404 rather than HTML.
+func staticHandler(dir string) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		name := path.Clean("/" + r.URL.Path)
+		info, err := os.Stat(filepath.Join(dir, filepath.FromSlash(name)))
+		if err != nil || info.IsDir() {
+			if path.Ext(name) != "" {
+				http.NotFound(w, r)
+				return
+			}
+			name = "/" + indexFile
+		}
+
+		w.Header().Set("Cache-Control", revalidateCacheControl)
+		if strings.HasPrefix(name, assetsPrefix) {
+			w.Header().Set("Cache-Control", immutableCacheControl)
+		}
+		serveFile(w, r, dir, name)
+	})
+}
+
+// notFoundHandler answers every path with a JSON 404, in place of the frontend in API-only mode
+func notFoundHandler() http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		w.Header().Set("Content-Type", "application/json")
+		w.WriteHeader(http.StatusNotFound)
+		json.NewEncoder(w).Encode(map[string]string{ //nolint:errcheck
+			"message": "no route for " + r.URL.Path,
+		})
+	})
+}
+
+// serveFile serves name, or its precompressed variant when the client accepts that encoding. The content
+// type is taken from the uncompressed name.
+func serveFile(w http.ResponseWriter, r *http.Request, dir, name string) {
+	w.Header().Add("Vary", "Accept-Encoding")
+	accepted := r.Header.Get("Accept-Encoding")
+	for _, encoding := range encodings {
+		if !strings.Contains(accepted, encoding.name) {
+			continue
+		}
+		file, err := os.Open(filepath.Join(dir, filepath.FromSlash(name+encoding.extension)))
+		if err != nil {
+			continue
+		}
+		defer file.Close()
+		info, err := file.Stat()
+		if err != nil {
+			continue
+		}
+		w.Header().Set("Content-Encoding", encoding.name)
+		http.ServeContent(w, r, name, info.ModTime(), file)
+		return
+	}
+
+	file, err := os.Open(filepath.Join(dir, filepath.FromSlash(name)))
+	if err != nil {
+		http.NotFound(w, r)
+		return
+	}
+	defer file.Close()
+	info, err := file.Stat()
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+	http.ServeContent(w, r, name, info.ModTime(), file)
+}
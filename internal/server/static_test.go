@@ -0,0 +1,165 @@
+package server
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func TestStaticHandler(t *testing.T) {
+	dir := t.TempDir()
+	files := map[string]string{
+		"index.html":             "<html></html>",
+		"vite.svg":               "<svg></svg>",
+		"assets/index-abc.js":    "console.log()",
+		"assets/index-abc.js.br": "compressed",
+	}
+	for name, content := range files {
+		path := filepath.Join(dir, filepath.FromSlash(name))
+		if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+			t.Fatalf("TestStaticHandler() mkdir error = %v", err)
+		}
+		if err := os.WriteFile(path, []byte(content), 0o644); err != nil {
+			t.Fatalf("TestStaticHandler() write error = %v", err)
+		}
+	}
+
+	tests := []struct {
+		name             string
+		path             string
+		acceptEncoding   string
+		wantStatus       int
+		wantBody         string
+		wantCacheControl string
+		wantEncoding     string
+	}{
+		{
+			name:             "index is revalidated",
+			path:             "/",
+			wantStatus:       http.StatusOK,
+			wantBody:         "<html></html>",
+			wantCacheControl: "no-cache",
+		},
+		{
+			name:             "client side route falls back to index",
+			path:             "/namespaces/default",
+			wantStatus:       http.StatusOK,
+			wantBody:         "<html></html>",
+			wantCacheControl: "no-cache",
+		},
+		{
+			name:             "hashed asset is immutable",
+			path:             "/assets/index-abc.js",
+			wantStatus:       http.StatusOK,
+			wantBody:         "console.log()",
+			wantCacheControl: "public, max-age=31536000, immutable",
+		},
+		{
+			name:             "precompressed asset is served when accepted",
+			path:             "/assets/index-abc.js",
+			acceptEncoding:   "gzip, br",
+			wantStatus:       http.StatusOK,
+			wantBody:         "compressed",
+			wantCacheControl: "public, max-age=31536000, immutable",
+			wantEncoding:     "br",
+		},
+		{
+			name:             "unhashed file is revalidated",
+			path:             "/vite.svg",
+			wantStatus:       http.StatusOK,
+			wantBody:         "<svg></svg>",
+			wantCacheControl: "no-cache",
+		},
+		{
+			name:       "missing asset is not found",
+			path:       "/assets/missing.js",
+			wantStatus: http.StatusNotFound,
+			wantBody:   "404 page not found\n",
+		},
+		{
+			name:             "path traversal stays in the directory",
+			path:             "/../../etc/passwd",
+			wantStatus:       http.StatusOK,
+			wantBody:         "<html></html>",
+			wantCacheControl: "no-cache",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			request := httptest.NewRequest(http.MethodGet, "/", nil)
+			request.URL.Path = tt.path
+			request.Header.Set("Accept-Encoding", tt.acceptEncoding)
+			recorder := httptest.NewRecorder()
+
+			staticHandler(dir).ServeHTTP(recorder, request)
+
+			if recorder.Code != tt.wantStatus {
+				t.Errorf("TestStaticHandler() status = %v, want %v", recorder.Code, tt.wantStatus)
+			}
+			if got := recorder.Body.String(); got != tt.wantBody {
+				t.Errorf("TestStaticHandler() body = %q, want %q", got, tt.wantBody)
+			}
+			if got := recorder.Header().Get("Cache-Control"); got != tt.wantCacheControl {
+				t.Errorf("TestStaticHandler() Cache-Control = %q, want %q", got, tt.wantCacheControl)
+			}
+			if got := recorder.Header().Get("Content-Encoding"); got != tt.wantEncoding {
+				t.Errorf("TestStaticHandler() Content-Encoding = %q, want %q", got, tt.wantEncoding)
+			}
+		})
+	}
+}
+
+func TestAPIOnly(t *testing.T) {
+	dir := t.TempDir()
+	if err := os.WriteFile(filepath.Join(dir, "index.html"), []byte("<html></html>"), 0o644); err != nil {
+		t.Fatalf("TestAPIOnly() write error = %v", err)
+	}
+	health := staticHealth{{ServiceName: "web", Namespace: "shop", Status: types.HealthStatusHealthy}}
+	handler := New(health, WithStaticDir(dir), WithAPIOnly()).Handler()
+
+	tests := []struct {
+		name       string
+		path       string
+		wantStatus int
+		wantBody   string
+	}{
+		{
+			name:       "index is not served",
+			path:       "/",
+			wantStatus: http.StatusNotFound,
+			wantBody:   `{"message":"no route for /"}` + "\n",
+		},
+		{
+			name:       "client side route is not found",
+			path:       "/namespaces/default",
+			wantStatus: http.StatusNotFound,
+			wantBody:   `{"message":"no route for /namespaces/default"}` + "\n",
+		},
+		{
+			name:       "api routes are served",
+			path:       "/healthz",
+			wantStatus: http.StatusOK,
+			wantBody:   `{"message":"ready"}` + "\n",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			recorder := httptest.NewRecorder()
+			handler.ServeHTTP(recorder, httptest.NewRequest(http.MethodGet, tt.path, nil))
+
+			if recorder.Code != tt.wantStatus {
+				t.Errorf("TestAPIOnly() status = %v, want %v", recorder.Code, tt.wantStatus)
+			}
+			if got := recorder.Body.String(); got != tt.wantBody {
+				t.Errorf("TestAPIOnly() body = %q, want %q", got, tt.wantBody)
+			}
+			if got := recorder.Header().Get("Content-Type"); got != "application/json" {
+				t.Errorf("TestAPIOnly() Content-Type = %q, want %q", got, "application/json")
+			}
+		})
+	}
+}
@@ -0,0 +1,269 @@
+package server
+
+import (
+	"context"
+	"encoding/json"
+	"net/http/httptest"
+	"net/netip"
+	"reflect"
+	"strings"
+	"testing"
+
+	"github.com/gorilla/websocket"
+	"github.com/kdwils/constellation/internal/anonymize"
+	"github.com/kdwils/constellation/internal/graphql"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+type fakeHealth struct{}
+
+func (fakeHealth) GetAllHealthData() []*types.ServiceHealthInfo { return nil }
+func (fakeHealth) Subscribe() chan []*types.ServiceHealthInfo   { return make(chan []*types.ServiceHealthInfo) }
+func (fakeHealth) Unsubscribe(chan []*types.ServiceHealthInfo)  {}
+
+type fakeHierarchy []types.HierarchyNode
+
+func (f fakeHierarchy) GetHierarchy(context.Context) ([]types.HierarchyNode, error) { return f, nil }
+
+func TestHandleNamespaceStream(t *testing.T) {
+	nodes := fakeHierarchy{
+		{Kind: types.ResourceKindNamespace, Name: "payments", ID: "Namespace/payments"},
+		{Kind: types.ResourceKindNamespace, Name: "shop", ID: "Namespace/shop", Relatives: []types.HierarchyNode{
+			{Kind: types.ResourceKindService, Name: "web", ID: "Service/shop/web", ParentID: "Namespace/shop"},
+		}},
+	}
+
+	tests := []struct {
+		name      string
+		namespace string
+		want      types.NodeUpdate
+	}{
+		{
+			name:      "namespace subtree is sent",
+			namespace: "shop",
+			want: types.NodeUpdate{ID: "Namespace/shop", Node: &types.HierarchyNode{
+				Kind: types.ResourceKindNamespace, Name: "shop", ID: "Namespace/shop", Relatives: []types.HierarchyNode{
+					{Kind: types.ResourceKindService, Name: "web", ID: "Service/shop/web", ParentID: "Namespace/shop"},
+				},
+			}},
+		},
+		{
+			name:      "missing namespace has no node",
+			namespace: "billing",
+			want:      types.NodeUpdate{ID: "Namespace/billing"},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			server := httptest.NewServer(New(fakeHealth{}, WithHierarchyProvider(nodes)).Handler())
+			defer server.Close()
+
+			url := "ws" + strings.TrimPrefix(server.URL, "http") + "/state/namespaces/" + tt.namespace + "/stream"
+			conn, _, err := websocket.DefaultDialer.Dial(url, nil)
+			if err != nil {
+				t.Fatalf("TestHandleNamespaceStream() dial error = %v", err)
+			}
+			defer conn.Close() //nolint:errcheck
+
+			var got types.NodeUpdate
+			if err := conn.ReadJSON(&got); err != nil {
+				t.Fatalf("TestHandleNamespaceStream() read error = %v", err)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestHandleNamespaceStream() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestHandleHierarchyStream(t *testing.T) {
+	tests := []struct {
+		name  string
+		nodes fakeHierarchy
+		want  []types.HierarchyNode
+	}{
+		{
+			name: "whole hierarchy is sent",
+			nodes: fakeHierarchy{
+				{Kind: types.ResourceKindNamespace, Name: "payments", ID: "Namespace/payments"},
+				{Kind: types.ResourceKindNamespace, Name: "shop", ID: "Namespace/shop", Relatives: []types.HierarchyNode{
+					{Kind: types.ResourceKindService, Name: "web", ID: "Namespace/shop|Service/shop/web", ParentID: "Namespace/shop"},
+				}},
+			},
+			want: []types.HierarchyNode{
+				{Kind: types.ResourceKindNamespace, Name: "payments", ID: "Namespace/payments"},
+				{Kind: types.ResourceKindNamespace, Name: "shop", ID: "Namespace/shop", Relatives: []types.HierarchyNode{
+					{Kind: types.ResourceKindService, Name: "web", ID: "Namespace/shop|Service/shop/web", ParentID: "Namespace/shop"},
+				}},
+			},
+		},
+		{
+			name:  "empty hierarchy",
+			nodes: fakeHierarchy{},
+			want:  []types.HierarchyNode{},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			server := httptest.NewServer(New(fakeHealth{}, WithHierarchyProvider(tt.nodes)).Handler())
+			defer server.Close()
+
+			conn, _, err := websocket.DefaultDialer.Dial("ws"+strings.TrimPrefix(server.URL, "http")+"/ws/hierarchy", nil)
+			if err != nil {
+				t.Fatalf("TestHandleHierarchyStream() dial error = %v", err)
+			}
+			defer conn.Close() //nolint:errcheck
+
+			var got []types.HierarchyNode
+			if err := conn.ReadJSON(&got); err != nil {
+				t.Fatalf("TestHandleHierarchyStream() read error = %v", err)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestHandleHierarchyStream() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestFollowNode_Anonymized(t *testing.T) {
+	shop := "shop"
+	nodes := fakeHierarchy{
+		{Kind: types.ResourceKindNamespace, Name: "shop", ID: "Namespace/shop", Relatives: []types.HierarchyNode{{
+			Kind:       types.ResourceKindService,
+			Name:       "web",
+			Namespace:  &shop,
+			ID:         "Namespace/shop|Service/shop/web",
+			ParentID:   "Namespace/shop",
+			Hostnames:  []string{"shop.example.com"},
+			ClusterIPs: []string{"10.96.0.12"},
+		}}},
+	}
+	a := anonymize.New(anonymize.Config{Key: "test"})
+	name := a.Name
+	anonymizedShop := name("shop")
+	service := types.HierarchyNode{
+		Kind:       types.ResourceKindService,
+		Name:       name("web"),
+		Namespace:  &anonymizedShop,
+		ID:         "Namespace/" + name("shop") + "|Service/" + name("shop") + "/" + name("web"),
+		ParentID:   "Namespace/" + name("shop"),
+		Hostnames:  []string{name("shop.example.com")},
+		ClusterIPs: []string{a.IP(netip.MustParseAddr("10.96.0.12")).String()},
+	}
+	namespace := types.HierarchyNode{
+		Kind:      types.ResourceKindNamespace,
+		Name:      name("shop"),
+		ID:        "Namespace/" + name("shop"),
+		Relatives: []types.HierarchyNode{service},
+	}
+
+	tests := []struct {
+		name  string
+		path  string
+		watch *types.NodeWatch
+		got   func() any
+		want  any
+	}{
+		{
+			name: "hierarchy stream",
+			path: "/ws/hierarchy",
+			got:  func() any { return &[]types.HierarchyNode{} },
+			want: &[]types.HierarchyNode{namespace},
+		},
+		{
+			name: "namespace stream follows the pseudonym",
+			path: "/state/namespaces/" + name("shop") + "/stream",
+			got:  func() any { return &types.NodeUpdate{} },
+			want: &types.NodeUpdate{ID: namespace.ID, Node: &namespace},
+		},
+		{
+			name:  "node stream follows the anonymized id",
+			path:  "/ws/node",
+			watch: &types.NodeWatch{ID: service.ID},
+			got:   func() any { return &types.NodeUpdate{} },
+			want:  &types.NodeUpdate{ID: service.ID, Node: &service},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			handler := New(fakeHealth{}, WithHierarchyProvider(nodes), WithAnonymizer(a)).Handler()
+			server := httptest.NewServer(handler)
+			defer server.Close()
+
+			conn, _, err := websocket.DefaultDialer.Dial("ws"+strings.TrimPrefix(server.URL, "http")+tt.path, nil)
+			if err != nil {
+				t.Fatalf("TestFollowNode_Anonymized() dial error = %v", err)
+			}
+			defer conn.Close() //nolint:errcheck
+			if tt.watch != nil {
+				if err := conn.WriteJSON(types.WatchRequest{Watch: tt.watch}); err != nil {
+					t.Fatalf("TestFollowNode_Anonymized() write error = %v", err)
+				}
+			}
+
+			_, payload, err := conn.ReadMessage()
+			if err != nil {
+				t.Fatalf("TestFollowNode_Anonymized() read error = %v", err)
+			}
+			for _, value := range []string{"shop", "web", "10.96.0.12"} {
+				if strings.Contains(string(payload), value) {
+					t.Errorf("TestFollowNode_Anonymized() message %s contains %q", payload, value)
+				}
+			}
+			got := tt.got()
+			if err := json.Unmarshal(payload, got); err != nil {
+				t.Fatalf("TestFollowNode_Anonymized() unmarshal error = %v", err)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestFollowNode_Anonymized() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestHandleGraphQLStream(t *testing.T) {
+	health := staticHealth{
+		{ServiceName: "web", Namespace: "shop", Status: types.HealthStatusHealthy},
+		{ServiceName: "api", Namespace: "admin", Status: types.HealthStatusUnhealthy},
+	}
+
+	tests := []struct {
+		name    string
+		request graphql.Request
+		want    string
+	}{
+		{
+			name:    "current health is resolved first",
+			request: graphql.Request{Query: `subscription { health(namespace: "shop") { service_name status } }`},
+			want:    `{"data":{"health":[{"service_name":"web","status":"healthy"}]}}`,
+		},
+		{
+			name:    "queries are rejected",
+			request: graphql.Request{Query: `{ hierarchy { name } }`},
+			want:    `{"errors":[{"message":"only subscription operations can be streamed"}]}`,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			server := httptest.NewServer(New(health).Handler())
+			defer server.Close()
+
+			conn, _, err := websocket.DefaultDialer.Dial("ws"+strings.TrimPrefix(server.URL, "http")+"/ws/graphql", nil)
+			if err != nil {
+				t.Fatalf("TestHandleGraphQLStream() dial error = %v", err)
+			}
+			defer conn.Close() //nolint:errcheck
+
+			if err := conn.WriteJSON(tt.request); err != nil {
+				t.Fatalf("TestHandleGraphQLStream() write error = %v", err)
+			}
+			_, got, err := conn.ReadMessage()
+			if err != nil {
+				t.Fatalf("TestHandleGraphQLStream() read error = %v", err)
+			}
+			if string(got) != tt.want {
+				t.Errorf("TestHandleGraphQLStream() = %s, want %s", got, tt.want)
+			}
+		})
+	}
+}
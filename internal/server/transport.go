@@ -0,0 +1,44 @@
+package server
+
+import (
+	"context"
+	"net"
+	"net/http"
+	"time"
+)
+
+// HTTPConfig tunes the connections the server accepts, for proxies that drop or refuse the defaults.
+// Zero values keep net/http's defaults.
+type HTTPConfig struct {
+	// HTTP2 serves cleartext HTTP/2 (h2c) to clients that ask for it with prior knowledge, next to HTTP/1.1.
+	// WebSocket streams always upgrade over HTTP/1.1.
+	HTTP2 bool
+	// MaxConcurrentStreams caps the streams open at once on each HTTP/2 connection
+	MaxConcurrentStreams int
+	// DisableKeepAlives closes every connection after its first response
+	DisableKeepAlives bool
+	// IdleTimeout is how long a keep-alive connection may sit idle between requests
+	IdleTimeout time.Duration
+	// TCPKeepAlive is the interval between TCP keep-alive probes on accepted connections; negative disables
+	// them. It is not applied to a listener passed with WithListener.
+	TCPKeepAlive time.Duration
+}
+
+// apply sets the config on httpServer before it starts serving
+func (c HTTPConfig) apply(httpServer *http.Server) {
+	httpServer.IdleTimeout = c.IdleTimeout
+	httpServer.SetKeepAlivesEnabled(!c.DisableKeepAlives)
+	if !c.HTTP2 {
+		return
+	}
+	httpServer.Protocols = new(http.Protocols)
+	httpServer.Protocols.SetHTTP1(true)
+	httpServer.Protocols.SetUnencryptedHTTP2(true)
+	httpServer.HTTP2 = &http.HTTP2Config{MaxConcurrentStreams: c.MaxConcurrentStreams}
+}
+
+// listen binds addr over TCP with the configured keep-alive interval
+func (c HTTPConfig) listen(addr string) (net.Listener, error) {
+	config := net.ListenConfig{KeepAlive: c.TCPKeepAlive}
+	return config.Listen(context.Background(), "tcp", addr)
+}
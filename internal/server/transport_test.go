@@ -0,0 +1,72 @@
+package server
+
+import (
+	"context"
+	"net"
+	"net/http"
+	"testing"
+)
+
+func TestHTTPConfig(t *testing.T) {
+	health := staticHealth{{ServiceName: "web", Namespace: "shop"}}
+
+	tests := []struct {
+		name      string
+		config    HTTPConfig
+		client    func(*http.Protocols)
+		wantProto string
+		wantClose bool
+	}{
+		{
+			name:      "defaults serve HTTP/1.1",
+			client:    func(p *http.Protocols) { p.SetHTTP1(true) },
+			wantProto: "HTTP/1.1",
+		},
+		{
+			name:      "HTTP/1.1 is still served with HTTP/2 on",
+			config:    HTTPConfig{HTTP2: true},
+			client:    func(p *http.Protocols) { p.SetHTTP1(true) },
+			wantProto: "HTTP/1.1",
+		},
+		{
+			name:      "h2c with prior knowledge",
+			config:    HTTPConfig{HTTP2: true, MaxConcurrentStreams: 10},
+			client:    func(p *http.Protocols) { p.SetUnencryptedHTTP2(true) },
+			wantProto: "HTTP/2.0",
+		},
+		{
+			name:      "keep-alives off close the connection",
+			config:    HTTPConfig{DisableKeepAlives: true},
+			client:    func(p *http.Protocols) { p.SetHTTP1(true) },
+			wantProto: "HTTP/1.1",
+			wantClose: true,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			listener, err := net.Listen("tcp", "127.0.0.1:0")
+			if err != nil {
+				t.Fatalf("TestHTTPConfig() listen error = %v", err)
+			}
+			ctx, cancel := context.WithCancel(context.Background())
+			t.Cleanup(cancel)
+			go New(health, WithListener(listener), WithHTTPConfig(tt.config)).Serve(ctx) //nolint:errcheck
+
+			transport := &http.Transport{Protocols: new(http.Protocols)}
+			tt.client(transport.Protocols)
+			t.Cleanup(transport.CloseIdleConnections)
+			resp, err := (&http.Client{Transport: transport}).Get("http://" + listener.Addr().String() + "/healthz")
+			if err != nil {
+				t.Fatalf("TestHTTPConfig() request error = %v", err)
+			}
+			resp.Body.Close() //nolint:errcheck
+
+			if resp.Proto != tt.wantProto {
+				t.Errorf("TestHTTPConfig() proto = %v, want %v", resp.Proto, tt.wantProto)
+			}
+			if resp.Close != tt.wantClose {
+				t.Errorf("TestHTTPConfig() close = %v, want %v", resp.Close, tt.wantClose)
+			}
+		})
+	}
+}
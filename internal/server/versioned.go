@@ -0,0 +1,25 @@
+package server
+
+import (
+	"fmt"
+	"net/http"
+)
+
+// apiPrefix is the path every JSON route is served under
+const apiPrefix = "/api/v1"
+
+// versioned registers JSON routes under apiPrefix and keeps their unprefixed paths as deprecated aliases
+type versioned struct {
+	mux *http.ServeMux
+}
+
+// Handle serves handler at apiPrefix+pattern and at pattern, marking responses to the old path deprecated
+// with a link to the route that replaces it
+func (v versioned) Handle(pattern string, handler http.Handler) {
+	v.mux.Handle(apiPrefix+pattern, handler)
+	v.mux.Handle(pattern, http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		w.Header().Set("Deprecation", "true")
+		w.Header().Set("Link", fmt.Sprintf("<%s%s>; rel=\"successor-version\"", apiPrefix, r.URL.Path))
+		handler.ServeHTTP(w, r)
+	}))
+}
@@ -0,0 +1,63 @@
+package server
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"testing"
+)
+
+func TestVersioned(t *testing.T) {
+	mux := http.NewServeMux()
+	versioned{mux}.Handle("/hierarchy/{namespace}", http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		w.Write([]byte(r.PathValue("namespace"))) //nolint:errcheck
+	}))
+
+	tests := []struct {
+		name            string
+		path            string
+		wantStatus      int
+		wantBody        string
+		wantDeprecation string
+		wantLink        string
+	}{
+		{
+			name:       "versioned path",
+			path:       "/api/v1/hierarchy/default",
+			wantStatus: http.StatusOK,
+			wantBody:   "default",
+		},
+		{
+			name:            "old path is a deprecated alias",
+			path:            "/hierarchy/default",
+			wantStatus:      http.StatusOK,
+			wantBody:        "default",
+			wantDeprecation: "true",
+			wantLink:        `</api/v1/hierarchy/default>; rel="successor-version"`,
+		},
+		{
+			name:       "other versions are not served",
+			path:       "/api/v2/hierarchy/default",
+			wantStatus: http.StatusNotFound,
+			wantBody:   "404 page not found\n",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			recorder := httptest.NewRecorder()
+			mux.ServeHTTP(recorder, httptest.NewRequest(http.MethodGet, tt.path, nil))
+
+			if recorder.Code != tt.wantStatus {
+				t.Errorf("TestVersioned() status = %v, want %v", recorder.Code, tt.wantStatus)
+			}
+			if got := recorder.Body.String(); got != tt.wantBody {
+				t.Errorf("TestVersioned() body = %q, want %q", got, tt.wantBody)
+			}
+			if got := recorder.Header().Get("Deprecation"); got != tt.wantDeprecation {
+				t.Errorf("TestVersioned() Deprecation = %q, want %q", got, tt.wantDeprecation)
+			}
+			if got := recorder.Header().Get("Link"); got != tt.wantLink {
+				t.Errorf("TestVersioned() Link = %q, want %q", got, tt.wantLink)
+			}
+		})
+	}
+}
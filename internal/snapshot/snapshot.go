@@ -0,0 +1,85 @@
+package snapshot
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"os"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// Provider serves a previously exported snapshot without any cluster connection
+type Provider struct {
+	snapshot *types.Snapshot
+}
+
+// Load reads a snapshot file written by the /snapshot endpoint
+func Load(path string) (*Provider, error) {
+	snapshot, err := Read(path)
+	if err != nil {
+		return nil, err
+	}
+	return &Provider{snapshot: snapshot}, nil
+}
+
+// Read decodes the snapshot file at path, rejecting snapshots written in another format version
+func Read(path string) (*types.Snapshot, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read snapshot: %w", err)
+	}
+
+	var snapshot types.Snapshot
+	if err := json.Unmarshal(data, &snapshot); err != nil {
+		return nil, fmt.Errorf("failed to decode snapshot: %w", err)
+	}
+	if snapshot.Version != types.SnapshotVersion {
+		return nil, fmt.Errorf("unsupported snapshot version %d, want %d", snapshot.Version, types.SnapshotVersion)
+	}
+	return &snapshot, nil
+}
+
+// Write saves snapshot to path through a temporary file, so a crash mid-write never leaves a truncated
+// snapshot behind
+func Write(path string, snapshot *types.Snapshot) error {
+	data, err := json.Marshal(snapshot)
+	if err != nil {
+		return fmt.Errorf("failed to encode snapshot: %w", err)
+	}
+
+	tmp := path + ".tmp"
+	if err := os.WriteFile(tmp, data, 0o600); err != nil {
+		return fmt.Errorf("failed to write snapshot: %w", err)
+	}
+	if err := os.Rename(tmp, path); err != nil {
+		return fmt.Errorf("failed to write snapshot: %w", err)
+	}
+	return nil
+}
+
+// GetAllHealthData returns the health data captured in the snapshot
+func (p *Provider) GetAllHealthData() []*types.ServiceHealthInfo {
+	return p.snapshot.Health
+}
+
+// Subscribe returns a channel that never receives updates since a snapshot is static
+func (p *Provider) Subscribe() chan []*types.ServiceHealthInfo {
+	return make(chan []*types.ServiceHealthInfo, 1)
+}
+
+// Unsubscribe closes a channel returned by Subscribe
+func (p *Provider) Unsubscribe(ch chan []*types.ServiceHealthInfo) {
+	close(ch)
+}
+
+// GetHierarchy rebuilds the hierarchy from the captured objects
+func (p *Provider) GetHierarchy(ctx context.Context) ([]types.HierarchyNode, error) {
+	return hierarchy.Build(p.snapshot.Objects, p.snapshot.Health), nil
+}
+
+// Snapshot returns the loaded snapshot unchanged
+func (p *Provider) Snapshot(ctx context.Context) (*types.Snapshot, error) {
+	return p.snapshot, nil
+}
@@ -0,0 +1,98 @@
+package snapshot_test
+
+import (
+	"context"
+	"os"
+	"path/filepath"
+	"reflect"
+	"testing"
+	"time"
+
+	"github.com/kdwils/constellation/internal/snapshot"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func TestLoad(t *testing.T) {
+	dir := t.TempDir()
+	write := func(name, content string) string {
+		path := filepath.Join(dir, name)
+		if err := os.WriteFile(path, []byte(content), 0o600); err != nil {
+			t.Fatalf("TestLoad() write error = %v", err)
+		}
+		return path
+	}
+	at := time.Date(2025, 1, 1, 0, 0, 0, 0, time.UTC)
+	health := []*types.ServiceHealthInfo{{ServiceName: "web", Namespace: "shop", Status: types.HealthStatusHealthy}}
+
+	tests := []struct {
+		name       string
+		path       string
+		want       *types.Snapshot
+		wantHealth []*types.ServiceHealthInfo
+		wantErr    string
+	}{
+		{
+			name: "snapshot written by the endpoint",
+			path: write("valid.json", `{"version":1,"timestamp":"2025-01-01T00:00:00Z",`+
+				`"hierarchy":[{"kind":"Namespace","name":"shop"}],`+
+				`"health":[{"service_name":"web","namespace":"shop","status":"healthy"}],"objects":{}}`),
+			want: &types.Snapshot{
+				Version:   types.SnapshotVersion,
+				Timestamp: at,
+				Hierarchy: []types.HierarchyNode{{Kind: types.ResourceKindNamespace, Name: "shop"}},
+				Health:    health,
+			},
+			wantHealth: health,
+		},
+		{
+			name:    "missing file",
+			path:    filepath.Join(dir, "missing.json"),
+			wantErr: "failed to read snapshot: open " + filepath.Join(dir, "missing.json") + ": no such file or directory",
+		},
+		{
+			name:    "empty file",
+			path:    write("empty.json", ""),
+			wantErr: "failed to decode snapshot: unexpected end of JSON input",
+		},
+		{
+			name:    "not a snapshot",
+			path:    write("invalid.json", "hierarchy: []"),
+			wantErr: "failed to decode snapshot: invalid character 'h' looking for beginning of value",
+		},
+		{
+			name:    "unknown version",
+			path:    write("future.json", `{"version":2,"hierarchy":[]}`),
+			wantErr: "unsupported snapshot version 2, want 1",
+		},
+		{
+			name:    "unversioned",
+			path:    write("unversioned.json", `{"hierarchy":[]}`),
+			wantErr: "unsupported snapshot version 0, want 1",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			provider, err := snapshot.Load(tt.path)
+			if tt.wantErr != "" {
+				if err == nil || err.Error() != tt.wantErr {
+					t.Fatalf("TestLoad() error = %v, want %v", err, tt.wantErr)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("TestLoad() error = %v", err)
+			}
+
+			got, err := provider.Snapshot(context.Background())
+			if err != nil {
+				t.Fatalf("TestLoad() Snapshot error = %v", err)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestLoad() Snapshot() = %+v, want %+v", got, tt.want)
+			}
+			if health := provider.GetAllHealthData(); !reflect.DeepEqual(health, tt.wantHealth) {
+				t.Errorf("TestLoad() GetAllHealthData() = %+v, want %+v", health, tt.wantHealth)
+			}
+		})
+	}
+}
@@ -0,0 +1,166 @@
+package snapshot
+
+import (
+	"context"
+	"errors"
+	"io/fs"
+	"sync"
+	"time"
+
+	"sigs.k8s.io/controller-runtime/pkg/log"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// persistInterval is how often the live snapshot is written, bounding how stale a warm start can be
+// beyond the downtime itself
+const persistInterval = time.Minute
+
+// HealthSource is the live health checker
+type HealthSource interface {
+	GetAllHealthData() []*types.ServiceHealthInfo
+	Subscribe() chan []*types.ServiceHealthInfo
+	Unsubscribe(chan []*types.ServiceHealthInfo)
+}
+
+// HierarchySource is the live hierarchy, which is also what gets persisted
+type HierarchySource interface {
+	GetHierarchy(ctx context.Context) ([]types.HierarchyNode, error)
+	Snapshot(ctx context.Context) (*types.Snapshot, error)
+}
+
+// SyncSource reports whether the live state has finished its initial sync
+type SyncSource interface {
+	SyncStatus(ctx context.Context) types.SyncStatus
+}
+
+// Warm serves the snapshot persisted before the last restart, marked stale, until the live providers
+// have synced, and persists the live snapshot for the next restart
+type Warm struct {
+	path      string
+	health    HealthSource
+	hierarchy HierarchySource
+	status    SyncSource
+
+	mu        sync.RWMutex
+	persisted *types.Snapshot
+
+	liveOnce sync.Once
+	live     chan struct{}
+}
+
+// NewWarm creates a Warm persisting to path in front of the live providers
+func NewWarm(path string, health HealthSource, nodes HierarchySource, status SyncSource) *Warm {
+	return &Warm{
+		path:      path,
+		health:    health,
+		hierarchy: nodes,
+		status:    status,
+		live:      make(chan struct{}),
+	}
+}
+
+// Load reads the persisted snapshot to serve until MarkLive. A missing file is not an error, there is
+// just nothing to warm from on the first start.
+func (w *Warm) Load() error {
+	persisted, err := Read(w.path)
+	if errors.Is(err, fs.ErrNotExist) {
+		return nil
+	}
+	if err != nil {
+		return err
+	}
+
+	w.mu.Lock()
+	defer w.mu.Unlock()
+	select {
+	case <-w.live:
+	default:
+		w.persisted = persisted
+	}
+	return nil
+}
+
+// MarkLive switches to the live providers once they have synced
+func (w *Warm) MarkLive() {
+	w.liveOnce.Do(func() {
+		w.mu.Lock()
+		defer w.mu.Unlock()
+		close(w.live)
+		w.persisted = nil
+	})
+}
+
+// stale returns the persisted snapshot while it is still served in place of the live providers
+func (w *Warm) stale() *types.Snapshot {
+	w.mu.RLock()
+	defer w.mu.RUnlock()
+	return w.persisted
+}
+
+// Persist writes the live snapshot to the file every minute once live, and a final time when ctx is done
+func (w *Warm) Persist(ctx context.Context) {
+	logger := log.FromContext(ctx).WithName("snapshot")
+	select {
+	case <-ctx.Done():
+		return
+	case <-w.live:
+	}
+
+	ticker := time.NewTicker(persistInterval)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-ctx.Done():
+			if err := w.save(context.WithoutCancel(ctx)); err != nil {
+				logger.Error(err, "failed to persist snapshot on shutdown", "path", w.path)
+			}
+			return
+		case <-ticker.C:
+			if err := w.save(ctx); err != nil {
+				logger.Error(err, "failed to persist snapshot", "path", w.path)
+			}
+		}
+	}
+}
+
+func (w *Warm) save(ctx context.Context) error {
+	snapshot, err := w.hierarchy.Snapshot(ctx)
+	if err != nil {
+		return err
+	}
+	return Write(w.path, snapshot)
+}
+
+// GetAllHealthData returns the persisted health until live
+func (w *Warm) GetAllHealthData() []*types.ServiceHealthInfo {
+	if persisted := w.stale(); persisted != nil {
+		return persisted.Health
+	}
+	return w.health.GetAllHealthData()
+}
+
+// Subscribe subscribes to the live health checker, whose updates replace the persisted health
+func (w *Warm) Subscribe() chan []*types.ServiceHealthInfo {
+	return w.health.Subscribe()
+}
+
+// Unsubscribe removes a subscription from the live health checker
+func (w *Warm) Unsubscribe(ch chan []*types.ServiceHealthInfo) {
+	w.health.Unsubscribe(ch)
+}
+
+// GetHierarchy returns the persisted hierarchy until live
+func (w *Warm) GetHierarchy(ctx context.Context) ([]types.HierarchyNode, error) {
+	if persisted := w.stale(); persisted != nil {
+		return persisted.Hierarchy, nil
+	}
+	return w.hierarchy.GetHierarchy(ctx)
+}
+
+// SyncStatus returns the live sync status, marked stale while the persisted snapshot is served
+func (w *Warm) SyncStatus(ctx context.Context) types.SyncStatus {
+	status := w.status.SyncStatus(ctx)
+	status.Stale = w.stale() != nil
+	return status
+}
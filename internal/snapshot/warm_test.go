@@ -0,0 +1,105 @@
+package snapshot_test
+
+import (
+	"context"
+	"path/filepath"
+	"reflect"
+	"testing"
+
+	"github.com/kdwils/constellation/internal/snapshot"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// liveState stands in for the health checker, hierarchy provider and sync tracker
+type liveState struct {
+	health []*types.ServiceHealthInfo
+	nodes  []types.HierarchyNode
+}
+
+func (l liveState) GetAllHealthData() []*types.ServiceHealthInfo { return l.health }
+func (liveState) Subscribe() chan []*types.ServiceHealthInfo     { return make(chan []*types.ServiceHealthInfo) }
+func (liveState) Unsubscribe(chan []*types.ServiceHealthInfo)    {}
+
+func (l liveState) GetHierarchy(context.Context) ([]types.HierarchyNode, error) { return l.nodes, nil }
+
+func (l liveState) Snapshot(context.Context) (*types.Snapshot, error) {
+	return &types.Snapshot{Version: types.SnapshotVersion, Hierarchy: l.nodes, Health: l.health}, nil
+}
+
+func (liveState) SyncStatus(context.Context) types.SyncStatus { return types.SyncStatus{Syncing: true} }
+
+func TestWarm(t *testing.T) {
+	persisted := &types.Snapshot{
+		Version:   types.SnapshotVersion,
+		Hierarchy: []types.HierarchyNode{{Kind: types.ResourceKindNamespace, Name: "shop"}},
+		Health:    []*types.ServiceHealthInfo{{ServiceName: "web", Namespace: "shop"}},
+	}
+	live := liveState{
+		health: []*types.ServiceHealthInfo{{ServiceName: "api", Namespace: "shop"}},
+		nodes:  []types.HierarchyNode{{Kind: types.ResourceKindNamespace, Name: "payments"}},
+	}
+
+	tests := []struct {
+		name       string
+		persisted  *types.Snapshot
+		live       bool
+		wantHealth []*types.ServiceHealthInfo
+		wantNodes  []types.HierarchyNode
+		wantStatus types.SyncStatus
+	}{
+		{
+			name:       "persisted snapshot is served stale",
+			persisted:  persisted,
+			wantHealth: persisted.Health,
+			wantNodes:  persisted.Hierarchy,
+			wantStatus: types.SyncStatus{Syncing: true, Stale: true},
+		},
+		{
+			name:       "live state replaces the persisted snapshot",
+			persisted:  persisted,
+			live:       true,
+			wantHealth: live.health,
+			wantNodes:  live.nodes,
+			wantStatus: types.SyncStatus{Syncing: true},
+		},
+		{
+			name:       "nothing persisted",
+			wantHealth: live.health,
+			wantNodes:  live.nodes,
+			wantStatus: types.SyncStatus{Syncing: true},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			path := filepath.Join(t.TempDir(), "snapshot.json")
+			if tt.persisted != nil {
+				if err := snapshot.Write(path, tt.persisted); err != nil {
+					t.Fatalf("TestWarm() Write error = %v", err)
+				}
+			}
+
+			warm := snapshot.NewWarm(path, live, live, live)
+			if err := warm.Load(); err != nil {
+				t.Fatalf("TestWarm() Load error = %v", err)
+			}
+			if tt.live {
+				warm.MarkLive()
+			}
+
+			ctx := context.Background()
+			if got := warm.GetAllHealthData(); !reflect.DeepEqual(got, tt.wantHealth) {
+				t.Errorf("TestWarm() GetAllHealthData() = %+v, want %+v", got, tt.wantHealth)
+			}
+			nodes, err := warm.GetHierarchy(ctx)
+			if err != nil {
+				t.Fatalf("TestWarm() GetHierarchy error = %v", err)
+			}
+			if !reflect.DeepEqual(nodes, tt.wantNodes) {
+				t.Errorf("TestWarm() GetHierarchy() = %+v, want %+v", nodes, tt.wantNodes)
+			}
+			if got := warm.SyncStatus(ctx); !reflect.DeepEqual(got, tt.wantStatus) {
+				t.Errorf("TestWarm() SyncStatus() = %+v, want %+v", got, tt.wantStatus)
+			}
+		})
+	}
+}
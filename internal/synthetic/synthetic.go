@@ -0,0 +1,121 @@
+// Package synthetic generates clusters of any size: pods grouped ten to a Service and a hundred to a
+// namespace, every pod with a liveness probe the reconcilers turn into a health check. The same size
+// always yields the same objects, so benchmark runs compare like with like.
+package synthetic
+
+import (
+	"fmt"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"k8s.io/apimachinery/pkg/util/intstr"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const (
+	// PodsPerService is how many pods each Service selects
+	PodsPerService = 10
+	// ServicesPerNamespace is how many Services each namespace holds
+	ServicesPerNamespace = 10
+
+	containerPort = 8080
+)
+
+// Sizes are the cluster sizes, in pods, the benchmarks run at
+var Sizes = []int{100, 1000, 10000}
+
+// Objects returns a cluster of pods pods
+func Objects(pods int) types.ClusterObjects {
+	services := (pods + PodsPerService - 1) / PodsPerService
+	namespaces := (services + ServicesPerNamespace - 1) / ServicesPerNamespace
+
+	objects := types.ClusterObjects{
+		Namespaces: make([]corev1.Namespace, 0, namespaces),
+		Services:   make([]corev1.Service, 0, services),
+		Pods:       make([]corev1.Pod, 0, pods),
+	}
+	for i := range namespaces {
+		objects.Namespaces = append(objects.Namespaces, corev1.Namespace{
+			ObjectMeta: metav1.ObjectMeta{Name: namespace(i)},
+		})
+	}
+	for i := range services {
+		objects.Services = append(objects.Services, Service(i))
+	}
+	for i := range pods {
+		objects.Pods = append(objects.Pods, Pod(i))
+	}
+	return objects
+}
+
+// Health returns a healthy result for every Service in a cluster of pods pods
+func Health(pods int) []*types.ServiceHealthInfo {
+	services := (pods + PodsPerService - 1) / PodsPerService
+	checked := time.Date(2025, time.January, 1, 0, 0, 0, 0, time.UTC)
+
+	health := make([]*types.ServiceHealthInfo, 0, services)
+	for i := range services {
+		service := Service(i)
+		health = append(health, &types.ServiceHealthInfo{
+			ServiceName: service.Name,
+			Namespace:   service.Namespace,
+			LastCheck:   checked,
+			Status:      types.HealthStatusHealthy,
+			Uptime:      100,
+		})
+	}
+	return health
+}
+
+// Service returns the i-th Service
+func Service(i int) corev1.Service {
+	name := serviceName(i)
+	return corev1.Service{
+		ObjectMeta: metav1.ObjectMeta{Name: name, Namespace: namespace(i / ServicesPerNamespace)},
+		Spec: corev1.ServiceSpec{
+			Selector:  map[string]string{"app": name},
+			ClusterIP: fmt.Sprintf("10.96.%d.%d", i/250, i%250+1),
+			Ports:     []corev1.ServicePort{{Name: "http", Port: 80, TargetPort: intstr.FromInt32(containerPort)}},
+		},
+	}
+}
+
+// Pod returns the i-th pod, selected by Service i/PodsPerService
+func Pod(i int) corev1.Pod {
+	service := i / PodsPerService
+	return corev1.Pod{
+		ObjectMeta: metav1.ObjectMeta{
+			Name:      fmt.Sprintf("%s-%d", serviceName(service), i%PodsPerService),
+			Namespace: namespace(service / ServicesPerNamespace),
+			Labels:    map[string]string{"app": serviceName(service)},
+		},
+		Spec: corev1.PodSpec{
+			NodeName: fmt.Sprintf("node-%d", i%10),
+			Containers: []corev1.Container{{
+				Name:  "app",
+				Image: "registry.example.com/app:1.0.0",
+				Ports: []corev1.ContainerPort{{ContainerPort: containerPort}},
+				LivenessProbe: &corev1.Probe{
+					ProbeHandler: corev1.ProbeHandler{
+						HTTPGet: &corev1.HTTPGetAction{Path: "/healthz", Port: intstr.FromInt32(containerPort)},
+					},
+					PeriodSeconds: 10,
+				},
+			}},
+		},
+		Status: corev1.PodStatus{
+			Phase: corev1.PodRunning,
+			PodIP: fmt.Sprintf("10.244.%d.%d", i/250, i%250+1),
+		},
+	}
+}
+
+func namespace(i int) string {
+	return fmt.Sprintf("ns-%d", i)
+}
+
+func serviceName(i int) string {
+	return fmt.Sprintf("svc-%d", i)
+}
@@ -0,0 +1,52 @@
+package synthetic_test
+
+import (
+	"testing"
+
+	"k8s.io/apimachinery/pkg/labels"
+
+	"github.com/kdwils/constellation/internal/synthetic"
+)
+
+func TestObjects(t *testing.T) {
+	tests := []struct {
+		name           string
+		pods           int
+		wantNamespaces int
+		wantServices   int
+	}{
+		{name: "partial service", pods: 25, wantNamespaces: 1, wantServices: 3},
+		{name: "full namespaces", pods: 200, wantNamespaces: 2, wantServices: 20},
+		{name: "empty", pods: 0, wantNamespaces: 0, wantServices: 0},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			objects := synthetic.Objects(tt.pods)
+			if len(objects.Namespaces) != tt.wantNamespaces {
+				t.Errorf("TestObjects() namespaces = %d, want %d", len(objects.Namespaces), tt.wantNamespaces)
+			}
+			if len(objects.Services) != tt.wantServices {
+				t.Errorf("TestObjects() services = %d, want %d", len(objects.Services), tt.wantServices)
+			}
+			if len(objects.Pods) != tt.pods {
+				t.Errorf("TestObjects() pods = %d, want %d", len(objects.Pods), tt.pods)
+			}
+			if health := synthetic.Health(tt.pods); len(health) != tt.wantServices {
+				t.Errorf("TestObjects() health = %d, want %d", len(health), tt.wantServices)
+			}
+
+			for _, pod := range objects.Pods {
+				selecting := 0
+				for _, service := range objects.Services {
+					selector := labels.SelectorFromSet(service.Spec.Selector)
+					if service.Namespace == pod.Namespace && selector.Matches(labels.Set(pod.Labels)) {
+						selecting++
+					}
+				}
+				if selecting != 1 {
+					t.Fatalf("TestObjects() pod %s/%s selected by %d services, want 1", pod.Namespace, pod.Name, selecting)
+				}
+			}
+		})
+	}
+}
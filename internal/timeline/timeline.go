@@ -0,0 +1,297 @@
+// Package timeline keeps the last state transitions of each node: pod phase changes, Service endpoint
+// count changes and HTTPRoutes attaching to and detaching from Gateways, so "what happened to this thing"
+// can be answered without an external event store.
+package timeline
+
+import (
+	"context"
+	"fmt"
+	"slices"
+	"strings"
+	"sync"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	discoveryv1 "k8s.io/api/discovery/v1"
+	toolscache "k8s.io/client-go/tools/cache"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	"sigs.k8s.io/controller-runtime/pkg/log"
+	"sigs.k8s.io/gateway-api/apis/v1beta1"
+
+	"github.com/kdwils/constellation/internal/endpoints"
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/types"
+	"github.com/kdwils/constellation/internal/versions"
+)
+
+const defaultLength = 20
+
+// Config sets how much history is kept
+type Config struct {
+	// Length is how many transitions are kept per node, defaulting to 20
+	Length int
+}
+
+// slice is the Service an EndpointSlice belongs to and the endpoints it holds
+type slice struct {
+	service string
+	counts  types.EndpointCounts
+}
+
+// Tracker follows pods, EndpointSlices and HTTPRoutes from their informers. It is a ResourceEventHandler.
+type Tracker struct {
+	informers types.Informers
+	config    Config
+	now       func() time.Time
+	versions  versions.Versions
+
+	mu        sync.RWMutex
+	phases    map[string]string
+	slices    map[string]slice
+	endpoints map[string]types.EndpointCounts
+	gateways  map[string][]string
+	// timelines are keyed by nodeKey and hold at most config.Length transitions, oldest first
+	timelines map[string][]types.Transition
+}
+
+// NewTracker creates a Tracker that follows the resources seen by the informers in informers
+func NewTracker(informers types.Informers, config Config, opts ...TrackerOpt) *Tracker {
+	if config.Length <= 0 {
+		config.Length = defaultLength
+	}
+
+	t := &Tracker{
+		informers: informers,
+		config:    config,
+		now:       time.Now,
+		phases:    make(map[string]string),
+		slices:    make(map[string]slice),
+		endpoints: make(map[string]types.EndpointCounts),
+		gateways:  make(map[string][]string),
+		timelines: make(map[string][]types.Transition),
+	}
+
+	for _, opt := range opts {
+		opt(t)
+	}
+
+	return t
+}
+
+type TrackerOpt func(*Tracker)
+
+// WithClock replaces the clock transitions are timed by
+func WithClock(now func() time.Time) TrackerOpt {
+	return func(t *Tracker) {
+		t.now = now
+	}
+}
+
+// WithVersions watches HTTPRoutes in the version negotiated with the apiserver rather than v1beta1
+func WithVersions(served versions.Versions) TrackerOpt {
+	return func(t *Tracker) {
+		t.versions = served
+	}
+}
+
+// Start follows pod, EndpointSlice and HTTPRoute events until ctx is cancelled
+func (t *Tracker) Start(ctx context.Context) {
+	logger := log.FromContext(ctx).WithName("timeline")
+	for _, obj := range []client.Object{&corev1.Pod{}, &discoveryv1.EndpointSlice{}, t.versions.Object("httproutes")} {
+		informer, err := t.informers.GetInformer(ctx, obj)
+		if err != nil {
+			logger.Error(err, "failed to get informer", "kind", fmt.Sprintf("%T", obj))
+			return
+		}
+		registration, err := informer.AddEventHandler(t)
+		if err != nil {
+			logger.Error(err, "failed to watch events", "kind", fmt.Sprintf("%T", obj))
+			return
+		}
+		defer func() {
+			if err := informer.RemoveEventHandler(registration); err != nil {
+				logger.Error(err, "failed to stop watching events")
+			}
+		}()
+	}
+
+	<-ctx.Done()
+}
+
+// OnAdd records the state of a resource. Resources listed when the informers start have no history yet,
+// so their state is recorded without a transition.
+func (t *Tracker) OnAdd(obj any, isInInitialList bool) {
+	t.observe(obj, !isInInitialList)
+}
+
+// OnUpdate records a transition when the state of a resource changed
+func (t *Tracker) OnUpdate(_, newObj any) {
+	t.observe(newObj, true)
+}
+
+// OnDelete forgets a pod or HTTPRoute and its timeline, detaching the route from its Gateways. A deleted
+// EndpointSlice takes its endpoints off its Service.
+func (t *Tracker) OnDelete(obj any) {
+	if tombstone, ok := obj.(toolscache.DeletedFinalStateUnknown); ok {
+		obj = tombstone.Obj
+	}
+	if route, ok := versions.HTTPRoute(obj); ok {
+		obj = route
+	}
+
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	now := t.now()
+	switch o := obj.(type) {
+	case *corev1.Pod:
+		podKey := nodeKey(types.ResourceKindPod, o.Namespace, o.Name)
+		delete(t.phases, podKey)
+		delete(t.timelines, podKey)
+	case *discoveryv1.EndpointSlice:
+		removed, exists := t.slices[o.Namespace+"/"+o.Name]
+		if !exists {
+			return
+		}
+		delete(t.slices, o.Namespace+"/"+o.Name)
+		t.recount(removed.service, now, true)
+	case *v1beta1.HTTPRoute:
+		routeKey := nodeKey(types.ResourceKindHTTPRoute, o.Namespace, o.Name)
+		t.attach(o.Namespace+"/"+o.Name, routeKey, nil, now, true)
+		delete(t.gateways, routeKey)
+		delete(t.timelines, routeKey)
+	}
+}
+
+func (t *Tracker) observe(obj any, live bool) {
+	if route, ok := versions.HTTPRoute(obj); ok {
+		obj = route
+	}
+
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	now := t.now()
+	switch o := obj.(type) {
+	case *corev1.Pod:
+		podKey := nodeKey(types.ResourceKindPod, o.Namespace, o.Name)
+		phase := string(o.Status.Phase)
+		previous, exists := t.phases[podKey]
+		t.phases[podKey] = phase
+		if exists && previous == phase {
+			return
+		}
+		if live {
+			t.record(podKey, types.Transition{Type: types.TransitionTypePhase, From: previous, To: phase, Time: now})
+		}
+	case *discoveryv1.EndpointSlice:
+		service, exists := o.Labels[discoveryv1.LabelServiceName]
+		if !exists {
+			return
+		}
+		serviceKey := nodeKey(types.ResourceKindService, o.Namespace, service)
+		previous, tracked := t.slices[o.Namespace+"/"+o.Name]
+		t.slices[o.Namespace+"/"+o.Name] = slice{
+			service: serviceKey,
+			counts:  endpoints.Count([]discoveryv1.EndpointSlice{*o})[o.Namespace+"/"+service],
+		}
+		if tracked && previous.service != serviceKey {
+			t.recount(previous.service, now, live)
+		}
+		t.recount(serviceKey, now, live)
+	case *v1beta1.HTTPRoute:
+		routeKey := nodeKey(types.ResourceKindHTTPRoute, o.Namespace, o.Name)
+		t.attach(o.Namespace+"/"+o.Name, routeKey, hierarchy.ParentGateways(*o), now, live)
+	}
+}
+
+// recount totals the endpoints of serviceKey's EndpointSlices, recording a transition when they changed
+func (t *Tracker) recount(serviceKey string, now time.Time, live bool) {
+	var total types.EndpointCounts
+	remaining := false
+	for _, s := range t.slices {
+		if s.service != serviceKey {
+			continue
+		}
+		remaining = true
+		total.Ready += s.counts.Ready
+		total.NotReady += s.counts.NotReady
+	}
+
+	previous, exists := t.endpoints[serviceKey]
+	t.endpoints[serviceKey] = total
+	if !remaining {
+		delete(t.endpoints, serviceKey)
+	}
+	if exists && previous == total {
+		return
+	}
+	if !live {
+		return
+	}
+	t.record(serviceKey, types.Transition{
+		Type: types.TransitionTypeEndpoints,
+		From: formatCounts(previous, exists),
+		To:   formatCounts(total, true),
+		Time: now,
+	})
+}
+
+// attach records the Gateways a route was attached to and detached from on both the route's and each
+// Gateway's timeline
+func (t *Tracker) attach(route, routeKey string, gateways []string, now time.Time, live bool) {
+	previous := t.gateways[routeKey]
+	t.gateways[routeKey] = gateways
+	if !live {
+		return
+	}
+
+	for _, gateway := range previous {
+		if slices.Contains(gateways, gateway) {
+			continue
+		}
+		t.record(routeKey, types.Transition{Type: types.TransitionTypeRouteDetached, From: gateway, Time: now})
+		t.record(gatewayKey(gateway), types.Transition{Type: types.TransitionTypeRouteDetached, From: route, Time: now})
+	}
+	for _, gateway := range gateways {
+		if slices.Contains(previous, gateway) {
+			continue
+		}
+		t.record(routeKey, types.Transition{Type: types.TransitionTypeRouteAttached, To: gateway, Time: now})
+		t.record(gatewayKey(gateway), types.Transition{Type: types.TransitionTypeRouteAttached, To: route, Time: now})
+	}
+}
+
+// record appends transition to the timeline of key, dropping the oldest once config.Length are kept
+func (t *Tracker) record(key string, transition types.Transition) {
+	timeline := append(t.timelines[key], transition)
+	if len(timeline) > t.config.Length {
+		timeline = timeline[len(timeline)-t.config.Length:]
+	}
+	t.timelines[key] = timeline
+}
+
+// Timeline returns the transitions recorded for the node of kind in namespace, oldest first. kind is
+// matched case-insensitively.
+func (t *Tracker) Timeline(kind types.ResourceKind, namespace, name string) []types.Transition {
+	t.mu.RLock()
+	defer t.mu.RUnlock()
+
+	return append(make([]types.Transition, 0), t.timelines[nodeKey(kind, namespace, name)]...)
+}
+
+func formatCounts(counts types.EndpointCounts, exists bool) string {
+	if !exists {
+		return ""
+	}
+	return fmt.Sprintf("%d ready, %d not ready", counts.Ready, counts.NotReady)
+}
+
+func gatewayKey(gateway string) string {
+	namespace, name, _ := strings.Cut(gateway, "/")
+	return nodeKey(types.ResourceKindGateway, namespace, name)
+}
+
+func nodeKey(kind types.ResourceKind, namespace, name string) string {
+	return strings.ToLower(string(kind)) + "/" + namespace + "/" + name
+}
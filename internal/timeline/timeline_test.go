@@ -0,0 +1,167 @@
+package timeline_test
+
+import (
+	"reflect"
+	"testing"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	discoveryv1 "k8s.io/api/discovery/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	toolscache "k8s.io/client-go/tools/cache"
+	"k8s.io/utils/ptr"
+	"sigs.k8s.io/gateway-api/apis/v1beta1"
+
+	"github.com/kdwils/constellation/internal/timeline"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func pod(phase corev1.PodPhase) *corev1.Pod {
+	return &corev1.Pod{
+		ObjectMeta: metav1.ObjectMeta{Name: "web-0", Namespace: "shop"},
+		Status:     corev1.PodStatus{Phase: phase},
+	}
+}
+
+func endpointSlice(name string, ready ...bool) *discoveryv1.EndpointSlice {
+	slice := &discoveryv1.EndpointSlice{ObjectMeta: metav1.ObjectMeta{
+		Name:      name,
+		Namespace: "shop",
+		Labels:    map[string]string{discoveryv1.LabelServiceName: "web"},
+	}}
+	for _, r := range ready {
+		slice.Endpoints = append(slice.Endpoints, discoveryv1.Endpoint{Conditions: discoveryv1.EndpointConditions{Ready: ptr.To(r)}})
+	}
+	return slice
+}
+
+func route(gateways ...string) *v1beta1.HTTPRoute {
+	r := &v1beta1.HTTPRoute{ObjectMeta: metav1.ObjectMeta{Name: "web", Namespace: "shop"}}
+	for _, gateway := range gateways {
+		r.Spec.ParentRefs = append(r.Spec.ParentRefs, v1beta1.ParentReference{Name: v1beta1.ObjectName(gateway)})
+	}
+	return r
+}
+
+func TestTracker(t *testing.T) {
+	start := time.Date(2025, 1, 2, 3, 0, 0, 0, time.UTC)
+
+	type event struct {
+		after   time.Duration
+		initial bool
+		update  bool
+		delete  bool
+		obj     any
+	}
+	type lookup struct {
+		kind      types.ResourceKind
+		namespace string
+		name      string
+	}
+	tests := []struct {
+		name   string
+		length int
+		events []event
+		lookup lookup
+		want   []types.Transition
+	}{
+		{
+			name: "pod phase changes are recorded",
+			events: []event{
+				{initial: true, obj: pod(corev1.PodPending)},
+				{after: time.Second, update: true, obj: pod(corev1.PodPending)},
+				{after: 2 * time.Second, update: true, obj: pod(corev1.PodRunning)},
+				{after: 3 * time.Second, update: true, obj: pod(corev1.PodFailed)},
+			},
+			lookup: lookup{kind: "pod", namespace: "shop", name: "web-0"},
+			want: []types.Transition{
+				{Type: types.TransitionTypePhase, From: "Pending", To: "Running", Time: start.Add(2 * time.Second)},
+				{Type: types.TransitionTypePhase, From: "Running", To: "Failed", Time: start.Add(3 * time.Second)},
+			},
+		},
+		{
+			name:   "only the last transitions are kept",
+			length: 1,
+			events: []event{
+				{obj: pod(corev1.PodPending)},
+				{after: time.Second, update: true, obj: pod(corev1.PodRunning)},
+			},
+			lookup: lookup{kind: types.ResourceKindPod, namespace: "shop", name: "web-0"},
+			want: []types.Transition{
+				{Type: types.TransitionTypePhase, From: "Pending", To: "Running", Time: start.Add(time.Second)},
+			},
+		},
+		{
+			name: "a deleted pod forgets its timeline",
+			events: []event{
+				{obj: pod(corev1.PodPending)},
+				{after: time.Second, delete: true, obj: toolscache.DeletedFinalStateUnknown{Key: "shop/web-0", Obj: pod(corev1.PodPending)}},
+			},
+			lookup: lookup{kind: types.ResourceKindPod, namespace: "shop", name: "web-0"},
+			want:   []types.Transition{},
+		},
+		{
+			name: "endpoint counts are totalled across a service's slices",
+			events: []event{
+				{initial: true, obj: endpointSlice("web-a", true)},
+				{after: time.Second, obj: endpointSlice("web-b", true, false)},
+				{after: 2 * time.Second, update: true, obj: endpointSlice("web-b", true, false)},
+				{after: 3 * time.Second, delete: true, obj: endpointSlice("web-a", true)},
+				{after: 4 * time.Second, delete: true, obj: endpointSlice("web-b", true, false)},
+			},
+			lookup: lookup{kind: types.ResourceKindService, namespace: "shop", name: "web"},
+			want: []types.Transition{
+				{Type: types.TransitionTypeEndpoints, From: "1 ready, 0 not ready", To: "2 ready, 1 not ready", Time: start.Add(time.Second)},
+				{Type: types.TransitionTypeEndpoints, From: "2 ready, 1 not ready", To: "1 ready, 1 not ready", Time: start.Add(3 * time.Second)},
+				{Type: types.TransitionTypeEndpoints, From: "1 ready, 1 not ready", To: "0 ready, 0 not ready", Time: start.Add(4 * time.Second)},
+			},
+		},
+		{
+			name: "routes attaching and detaching are recorded on the route",
+			events: []event{
+				{initial: true, obj: route("public")},
+				{after: time.Second, update: true, obj: route("public", "internal")},
+				{after: 2 * time.Second, update: true, obj: route("internal")},
+			},
+			lookup: lookup{kind: types.ResourceKindHTTPRoute, namespace: "shop", name: "web"},
+			want: []types.Transition{
+				{Type: types.TransitionTypeRouteAttached, To: "shop/internal", Time: start.Add(time.Second)},
+				{Type: types.TransitionTypeRouteDetached, From: "shop/public", Time: start.Add(2 * time.Second)},
+			},
+		},
+		{
+			name: "routes attaching and detaching are recorded on the gateway",
+			events: []event{
+				{obj: route("public")},
+				{after: time.Second, delete: true, obj: route("public")},
+			},
+			lookup: lookup{kind: types.ResourceKindGateway, namespace: "shop", name: "public"},
+			want: []types.Transition{
+				{Type: types.TransitionTypeRouteAttached, To: "shop/web", Time: start},
+				{Type: types.TransitionTypeRouteDetached, From: "shop/web", Time: start.Add(time.Second)},
+			},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			now := start
+			tracker := timeline.NewTracker(nil, timeline.Config{Length: tt.length}, timeline.WithClock(func() time.Time { return now }))
+			for _, e := range tt.events {
+				now = start.Add(e.after)
+				switch {
+				case e.delete:
+					tracker.OnDelete(e.obj)
+				case e.update:
+					tracker.OnUpdate(nil, e.obj)
+				default:
+					tracker.OnAdd(e.obj, e.initial)
+				}
+			}
+
+			got := tracker.Timeline(tt.lookup.kind, tt.lookup.namespace, tt.lookup.name)
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestTracker() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
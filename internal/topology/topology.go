@@ -0,0 +1,125 @@
+// Package topology exports Prometheus gauges derived from the hierarchy, blackbox style, so existing
+// Alertmanager rules can catch a Service without ready endpoints or a route without a working backend.
+package topology
+
+import (
+	"context"
+	"time"
+
+	"github.com/prometheus/client_golang/prometheus"
+	"sigs.k8s.io/controller-runtime/pkg/log"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// collectTimeout bounds how long a scrape waits for the hierarchy to be built
+const collectTimeout = 10 * time.Second
+
+var (
+	readyEndpointsDesc = prometheus.NewDesc(
+		"constellation_service_ready_endpoints",
+		"Ready endpoints behind the Service.",
+		[]string{"namespace", "service"}, nil,
+	)
+	notReadyEndpointsDesc = prometheus.NewDesc(
+		"constellation_service_not_ready_endpoints",
+		"Endpoints behind the Service that are not ready.",
+		[]string{"namespace", "service"}, nil,
+	)
+	serviceHealthyDesc = prometheus.NewDesc(
+		"constellation_service_healthy",
+		"1 when the Service's last health check passed, 0 otherwise. Only set for health checked Services.",
+		[]string{"namespace", "service"}, nil,
+	)
+	routeResolvableDesc = prometheus.NewDesc(
+		"constellation_route_resolvable",
+		"1 when at least one backend Service of the HTTPRoute exists and has a ready endpoint, 0 otherwise.",
+		[]string{"namespace", "route"}, nil,
+	)
+)
+
+// HierarchyProvider builds the hierarchy the gauges are read from
+type HierarchyProvider interface {
+	GetHierarchy(ctx context.Context) ([]types.HierarchyNode, error)
+}
+
+// Collector is a prometheus.Collector that builds the hierarchy on every scrape
+type Collector struct {
+	provider HierarchyProvider
+}
+
+// NewCollector creates a collector reading from provider
+func NewCollector(provider HierarchyProvider) *Collector {
+	return &Collector{provider: provider}
+}
+
+// Describe sends the descriptors of every gauge
+func (c *Collector) Describe(ch chan<- *prometheus.Desc) {
+	ch <- readyEndpointsDesc
+	ch <- notReadyEndpointsDesc
+	ch <- serviceHealthyDesc
+	ch <- routeResolvableDesc
+}
+
+// Collect sends the gauges of every Service and HTTPRoute. A Service reached through several routes
+// is reported once. Nothing is sent when the hierarchy cannot be built.
+func (c *Collector) Collect(ch chan<- prometheus.Metric) {
+	ctx, cancel := context.WithTimeout(context.Background(), collectTimeout)
+	defer cancel()
+
+	nodes, err := c.provider.GetHierarchy(ctx)
+	if err != nil {
+		log.Log.WithName("topology").Error(err, "failed to build hierarchy for metrics")
+		return
+	}
+	collect(ch, nodes, make(map[string]bool))
+}
+
+func collect(ch chan<- prometheus.Metric, nodes []types.HierarchyNode, seen map[string]bool) {
+	for _, node := range nodes {
+		collect(ch, node.Relatives, seen)
+		id := hierarchy.ResourceID(&node)
+		if seen[id] {
+			continue
+		}
+		seen[id] = true
+
+		namespace := ""
+		if node.Namespace != nil {
+			namespace = *node.Namespace
+		}
+		switch node.Kind {
+		case types.ResourceKindService:
+			collectService(ch, node, namespace)
+		case types.ResourceKindHTTPRoute:
+			ch <- prometheus.MustNewConstMetric(routeResolvableDesc, prometheus.GaugeValue, resolvable(node), namespace, node.Name)
+		}
+	}
+}
+
+func collectService(ch chan<- prometheus.Metric, node types.HierarchyNode, namespace string) {
+	if node.Endpoints != nil {
+		ch <- prometheus.MustNewConstMetric(readyEndpointsDesc, prometheus.GaugeValue,
+			float64(node.Endpoints.Ready), namespace, node.Name)
+		ch <- prometheus.MustNewConstMetric(notReadyEndpointsDesc, prometheus.GaugeValue,
+			float64(node.Endpoints.NotReady), namespace, node.Name)
+	}
+	if node.HealthInfo != nil {
+		healthy := 0.0
+		if node.HealthInfo.Status == types.HealthStatusHealthy {
+			healthy = 1
+		}
+		ch <- prometheus.MustNewConstMetric(serviceHealthyDesc, prometheus.GaugeValue, healthy, namespace, node.Name)
+	}
+}
+
+// resolvable is 1 when a backend Service of route has a ready endpoint
+func resolvable(route types.HierarchyNode) float64 {
+	for _, backend := range route.Relatives {
+		if backend.Kind == types.ResourceKindService && backend.Endpoints != nil && backend.Endpoints.Ready > 0 {
+			return 1
+		}
+	}
+	return 0
+}
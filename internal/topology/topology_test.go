@@ -0,0 +1,109 @@
+package topology_test
+
+import (
+	"context"
+	"errors"
+	"reflect"
+	"sort"
+	"strconv"
+	"strings"
+	"testing"
+
+	"github.com/prometheus/client_golang/prometheus"
+	"k8s.io/utils/ptr"
+
+	"github.com/kdwils/constellation/internal/topology"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+type fakeProvider struct {
+	nodes []types.HierarchyNode
+	err   error
+}
+
+func (f fakeProvider) GetHierarchy(context.Context) ([]types.HierarchyNode, error) {
+	return f.nodes, f.err
+}
+
+func TestCollector(t *testing.T) {
+	web := types.HierarchyNode{
+		Kind:       types.ResourceKindService,
+		Name:       "web",
+		Namespace:  ptr.To("shop"),
+		ID:         "Service/shop/web",
+		Endpoints:  &types.EndpointCounts{Ready: 2, NotReady: 1},
+		HealthInfo: &types.ServiceHealthInfo{Status: types.HealthStatusHealthy},
+	}
+	db := types.HierarchyNode{
+		Kind:      types.ResourceKindService,
+		Name:      "db",
+		Namespace: ptr.To("shop"),
+		ID:        "Service/shop/db",
+		Endpoints: &types.EndpointCounts{NotReady: 1},
+	}
+
+	tests := []struct {
+		name     string
+		provider fakeProvider
+		want     []string
+	}{
+		{
+			name: "services and routes are reported once",
+			provider: fakeProvider{nodes: []types.HierarchyNode{{
+				Kind: types.ResourceKindNamespace,
+				Name: "shop",
+				ID:   "Namespace/shop",
+				Relatives: []types.HierarchyNode{
+					{Kind: types.ResourceKindHTTPRoute, Name: "storefront", Namespace: ptr.To("shop"), ID: "HTTPRoute/shop/storefront",
+						Relatives: []types.HierarchyNode{web}},
+					{Kind: types.ResourceKindHTTPRoute, Name: "admin", Namespace: ptr.To("shop"), ID: "HTTPRoute/shop/admin",
+						Relatives: []types.HierarchyNode{db}},
+					{Kind: types.ResourceKindHTTPRoute, Name: "orphan", Namespace: ptr.To("shop"), ID: "HTTPRoute/shop/orphan"},
+					web,
+				},
+			}}},
+			want: []string{
+				`constellation_route_resolvable{namespace="shop",route="admin"} 0`,
+				`constellation_route_resolvable{namespace="shop",route="orphan"} 0`,
+				`constellation_route_resolvable{namespace="shop",route="storefront"} 1`,
+				`constellation_service_healthy{namespace="shop",service="web"} 1`,
+				`constellation_service_not_ready_endpoints{namespace="shop",service="db"} 1`,
+				`constellation_service_not_ready_endpoints{namespace="shop",service="web"} 1`,
+				`constellation_service_ready_endpoints{namespace="shop",service="db"} 0`,
+				`constellation_service_ready_endpoints{namespace="shop",service="web"} 2`,
+			},
+		},
+		{
+			name:     "nothing is reported when the hierarchy fails",
+			provider: fakeProvider{err: errors.New("cache not synced")},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			registry := prometheus.NewPedanticRegistry()
+			if err := registry.Register(topology.NewCollector(tt.provider)); err != nil {
+				t.Fatalf("TestCollector() register error = %v", err)
+			}
+			families, err := registry.Gather()
+			if err != nil {
+				t.Fatalf("TestCollector() gather error = %v", err)
+			}
+
+			var got []string
+			for _, family := range families {
+				for _, metric := range family.GetMetric() {
+					labels := make([]string, 0, len(metric.GetLabel()))
+					for _, label := range metric.GetLabel() {
+						labels = append(labels, label.GetName()+`="`+label.GetValue()+`"`)
+					}
+					got = append(got, family.GetName()+"{"+strings.Join(labels, ",")+"} "+
+						strconv.FormatFloat(metric.GetGauge().GetValue(), 'g', -1, 64))
+				}
+			}
+			sort.Strings(got)
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestCollector() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,265 @@
+// Package traffic queries Prometheus for per-service request metrics and attaches them to the hierarchy.
+package traffic
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"math"
+	"net/http"
+	"net/url"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"k8s.io/utils/ptr"
+	"sigs.k8s.io/controller-runtime/pkg/log"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// Default queries assume the common http_requests_total and http_request_duration_seconds metrics.
+// Every query must return an instant vector labelled by namespace and service.
+const (
+	DefaultRequestRateQuery = `sum by (namespace, service) (rate(http_requests_total[5m]))`
+	DefaultErrorRateQuery   = `sum by (namespace, service) (rate(http_requests_total{code=~"5.."}[5m])) / sum by (namespace, service) (rate(http_requests_total[5m]))`
+	DefaultLatencyQuery     = `histogram_quantile(0.99, sum by (namespace, service, le) (rate(http_request_duration_seconds_bucket[5m])))`
+
+	defaultInterval = 30 * time.Second
+)
+
+// HTTPClient interface for dependency injection during tests
+type HTTPClient interface {
+	Do(*http.Request) (*http.Response, error)
+}
+
+// Config selects the Prometheus server and the queries run against it
+type Config struct {
+	// URL of the Prometheus server, e.g. http://prometheus.monitoring:9090
+	URL string
+	// Interval between refreshes, defaulting to 30s
+	Interval time.Duration
+	// RequestRateQuery returns requests per second; empty uses DefaultRequestRateQuery
+	RequestRateQuery string
+	// ErrorRateQuery returns the fraction of failed requests; empty uses DefaultErrorRateQuery
+	ErrorRateQuery string
+	// LatencyQuery returns a latency in seconds; empty uses DefaultLatencyQuery
+	LatencyQuery string
+}
+
+// Collector periodically refreshes traffic metrics and attaches them to Service and HTTPRoute nodes
+type Collector struct {
+	config     Config
+	httpClient HTTPClient
+
+	mu      sync.RWMutex
+	metrics map[string]types.TrafficMetrics
+}
+
+// NewCollector creates a Collector, filling unset config fields with defaults
+func NewCollector(config Config, opts ...CollectorOpt) *Collector {
+	if config.Interval == 0 {
+		config.Interval = defaultInterval
+	}
+	if config.RequestRateQuery == "" {
+		config.RequestRateQuery = DefaultRequestRateQuery
+	}
+	if config.ErrorRateQuery == "" {
+		config.ErrorRateQuery = DefaultErrorRateQuery
+	}
+	if config.LatencyQuery == "" {
+		config.LatencyQuery = DefaultLatencyQuery
+	}
+
+	c := &Collector{
+		config:     config,
+		httpClient: http.DefaultClient,
+		metrics:    make(map[string]types.TrafficMetrics),
+	}
+
+	for _, opt := range opts {
+		opt(c)
+	}
+
+	return c
+}
+
+type CollectorOpt func(*Collector)
+
+func WithHTTPClient(client HTTPClient) CollectorOpt {
+	return func(c *Collector) {
+		c.httpClient = client
+	}
+}
+
+// Start refreshes metrics on the configured interval until ctx is cancelled
+func (c *Collector) Start(ctx context.Context) {
+	logger := log.FromContext(ctx).WithName("traffic")
+	ticker := time.NewTicker(c.config.Interval)
+	defer ticker.Stop()
+
+	for {
+		if err := c.Refresh(ctx); err != nil {
+			logger.Error(err, "failed to refresh traffic metrics", "prometheus", c.config.URL)
+		}
+
+		select {
+		case <-ctx.Done():
+			return
+		case <-ticker.C:
+		}
+	}
+}
+
+// Refresh runs every query once and replaces the stored metrics.
+// Results from a failed query are dropped rather than keeping stale values.
+func (c *Collector) Refresh(ctx context.Context) error {
+	metrics := make(map[string]types.TrafficMetrics)
+	var errs []string
+
+	queries := []struct {
+		query string
+		set   func(m *types.TrafficMetrics, v float64)
+	}{
+		{c.config.RequestRateQuery, func(m *types.TrafficMetrics, v float64) { m.RequestRate = ptr.To(v) }},
+		{c.config.ErrorRateQuery, func(m *types.TrafficMetrics, v float64) { m.ErrorRate = ptr.To(v) }},
+		{c.config.LatencyQuery, func(m *types.TrafficMetrics, v float64) { m.LatencySeconds = ptr.To(v) }},
+	}
+	for _, q := range queries {
+		samples, err := c.query(ctx, q.query)
+		if err != nil {
+			errs = append(errs, err.Error())
+			continue
+		}
+		for key, value := range samples {
+			m := metrics[key]
+			q.set(&m, value)
+			metrics[key] = m
+		}
+	}
+
+	c.mu.Lock()
+	c.metrics = metrics
+	c.mu.Unlock()
+
+	if len(errs) > 0 {
+		return fmt.Errorf("%s", strings.Join(errs, "; "))
+	}
+	return nil
+}
+
+// Get returns the metrics for a single service
+func (c *Collector) Get(namespace, service string) (types.TrafficMetrics, bool) {
+	c.mu.RLock()
+	defer c.mu.RUnlock()
+	m, ok := c.metrics[namespace+"/"+service]
+	return m, ok
+}
+
+// Enrich attaches metrics to Service nodes and aggregates them onto HTTPRoute and TrafficSplit nodes:
+// request rates are summed, error rates weighted by request rate, and the slowest latency kept.
+func (c *Collector) Enrich(nodes []types.HierarchyNode) {
+	hierarchy.Walk(nodes, func(node *types.HierarchyNode) {
+		switch node.Kind {
+		case types.ResourceKindService:
+			if node.Namespace == nil {
+				return
+			}
+			if m, ok := c.Get(*node.Namespace, node.Name); ok {
+				node.Traffic = &m
+			}
+		case types.ResourceKindHTTPRoute, types.ResourceKindTrafficSplit:
+			node.Traffic = aggregate(node.Relatives)
+		}
+	})
+}
+
+func aggregate(backends []types.HierarchyNode) *types.TrafficMetrics {
+	var total types.TrafficMetrics
+	var weightedErrors, weight float64
+	found := false
+
+	for _, backend := range backends {
+		m := backend.Traffic
+		if m == nil {
+			continue
+		}
+		found = true
+		if m.RequestRate != nil {
+			total.RequestRate = ptr.To(ptr.Deref(total.RequestRate, 0) + *m.RequestRate)
+		}
+		if m.ErrorRate != nil && m.RequestRate != nil {
+			weightedErrors += *m.ErrorRate * *m.RequestRate
+			weight += *m.RequestRate
+		}
+		if m.LatencySeconds != nil && *m.LatencySeconds > ptr.Deref(total.LatencySeconds, 0) {
+			total.LatencySeconds = ptr.To(*m.LatencySeconds)
+		}
+	}
+
+	if !found {
+		return nil
+	}
+	if weight > 0 {
+		total.ErrorRate = ptr.To(weightedErrors / weight)
+	}
+	return &total
+}
+
+type queryResponse struct {
+	Status string `json:"status"`
+	Error  string `json:"error"`
+	Data   struct {
+		ResultType string `json:"resultType"`
+		Result     []struct {
+			Metric map[string]string `json:"metric"`
+			Value  [2]any            `json:"value"`
+		} `json:"result"`
+	} `json:"data"`
+}
+
+// query runs an instant query and returns the samples keyed by namespace/service
+func (c *Collector) query(ctx context.Context, query string) (map[string]float64, error) {
+	endpoint := strings.TrimSuffix(c.config.URL, "/") + "/api/v1/query?" + url.Values{"query": {query}}.Encode()
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, endpoint, nil)
+	if err != nil {
+		return nil, err
+	}
+
+	resp, err := c.httpClient.Do(req)
+	if err != nil {
+		return nil, fmt.Errorf("prometheus query failed: %w", err)
+	}
+	defer resp.Body.Close() //nolint:errcheck
+
+	var body queryResponse
+	if err := json.NewDecoder(resp.Body).Decode(&body); err != nil {
+		return nil, fmt.Errorf("failed to decode prometheus response: %w", err)
+	}
+	if body.Status != "success" {
+		return nil, fmt.Errorf("prometheus query %q failed: %s", query, body.Error)
+	}
+	if body.Data.ResultType != "vector" {
+		return nil, fmt.Errorf("prometheus query %q returned %s, want vector", query, body.Data.ResultType)
+	}
+
+	samples := make(map[string]float64, len(body.Data.Result))
+	for _, result := range body.Data.Result {
+		namespace, service := result.Metric["namespace"], result.Metric["service"]
+		if namespace == "" || service == "" {
+			continue
+		}
+		raw, ok := result.Value[1].(string)
+		if !ok {
+			continue
+		}
+		value, err := strconv.ParseFloat(raw, 64)
+		if err != nil || math.IsNaN(value) || math.IsInf(value, 0) {
+			continue
+		}
+		samples[namespace+"/"+service] = value
+	}
+	return samples, nil
+}
@@ -0,0 +1,110 @@
+package traffic_test
+
+import (
+	"context"
+	"fmt"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+
+	"k8s.io/utils/ptr"
+
+	"github.com/kdwils/constellation/internal/traffic"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+func vector(samples ...string) string {
+	result := ""
+	for i, s := range samples {
+		if i > 0 {
+			result += ","
+		}
+		result += s
+	}
+	return fmt.Sprintf(`{"status":"success","data":{"resultType":"vector","result":[%s]}}`, result)
+}
+
+func sample(namespace, service, value string) string {
+	return fmt.Sprintf(`{"metric":{"namespace":%q,"service":%q},"value":[1700000000,%q]}`, namespace, service, value)
+}
+
+func TestCollector_Enrich(t *testing.T) {
+	responses := map[string]string{
+		"rate":    vector(sample("shop", "cart", "30"), sample("shop", "payments", "10")),
+		"errors":  vector(sample("shop", "cart", "0.1"), sample("shop", "payments", "NaN")),
+		"latency": vector(sample("shop", "cart", "0.2"), sample("shop", "payments", "0.5")),
+	}
+	prom := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		fmt.Fprint(w, responses[r.URL.Query().Get("query")]) //nolint:errcheck
+	}))
+	defer prom.Close()
+
+	collector := traffic.NewCollector(traffic.Config{
+		URL:              prom.URL,
+		RequestRateQuery: "rate",
+		ErrorRateQuery:   "errors",
+		LatencyQuery:     "latency",
+	})
+	if err := collector.Refresh(context.Background()); err != nil {
+		t.Fatalf("TestCollector_Enrich() refresh error = %v", err)
+	}
+
+	nodes := []types.HierarchyNode{{
+		Kind:      types.ResourceKindHTTPRoute,
+		Name:      "storefront",
+		Namespace: ptr.To("shop"),
+		Relatives: []types.HierarchyNode{
+			{Kind: types.ResourceKindService, Name: "cart", Namespace: ptr.To("shop")},
+			{Kind: types.ResourceKindService, Name: "payments", Namespace: ptr.To("shop")},
+			{Kind: types.ResourceKindService, Name: "unmetered", Namespace: ptr.To("shop")},
+		},
+	}}
+	collector.Enrich(nodes)
+
+	tests := []struct {
+		name string
+		got  *types.TrafficMetrics
+		want *types.TrafficMetrics
+	}{
+		{
+			name: "service with all metrics",
+			got:  nodes[0].Relatives[0].Traffic,
+			want: &types.TrafficMetrics{RequestRate: ptr.To(30.0), ErrorRate: ptr.To(0.1), LatencySeconds: ptr.To(0.2)},
+		},
+		{
+			name: "NaN sample is dropped",
+			got:  nodes[0].Relatives[1].Traffic,
+			want: &types.TrafficMetrics{RequestRate: ptr.To(10.0), LatencySeconds: ptr.To(0.5)},
+		},
+		{
+			name: "service without samples",
+			got:  nodes[0].Relatives[2].Traffic,
+			want: nil,
+		},
+		{
+			name: "route aggregates backends",
+			got:  nodes[0].Traffic,
+			want: &types.TrafficMetrics{RequestRate: ptr.To(40.0), ErrorRate: ptr.To(0.1), LatencySeconds: ptr.To(0.5)},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if format(tt.got) != format(tt.want) {
+				t.Errorf("TestCollector_Enrich() = %s, want %s", format(tt.got), format(tt.want))
+			}
+		})
+	}
+}
+
+func format(m *types.TrafficMetrics) string {
+	if m == nil {
+		return "nil"
+	}
+	value := func(v *float64) string {
+		if v == nil {
+			return "-"
+		}
+		return fmt.Sprintf("%g", *v)
+	}
+	return fmt.Sprintf("rate=%s errors=%s latency=%s", value(m.RequestRate), value(m.ErrorRate), value(m.LatencySeconds))
+}
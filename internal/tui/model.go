@@ -0,0 +1,278 @@
+package tui
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+type row struct {
+	node        types.HierarchyNode
+	path        string
+	depth       int
+	hasChildren bool
+}
+
+// Action is what a key press asks of the viewer beyond changing the view
+type Action int
+
+const (
+	ActionNone Action = iota
+	ActionQuit
+	ActionReload
+)
+
+// Model is the view state of the tree browser, kept separate from terminal I/O so it can be tested
+type Model struct {
+	nodes     []types.HierarchyNode
+	collapsed map[string]bool
+	cursor    int
+	search    string
+	searching bool
+	err       error
+}
+
+// NewModel creates an empty Model
+func NewModel() *Model {
+	return &Model{collapsed: make(map[string]bool)}
+}
+
+// SetHierarchy replaces the displayed tree, keeping collapse state for paths that still exist
+func (m *Model) SetHierarchy(nodes []types.HierarchyNode, err error) {
+	m.err = err
+	if err != nil {
+		return
+	}
+	m.nodes = nodes
+	m.clampCursor()
+}
+
+// HandleKey applies a single key press and returns the action it asks for
+func (m *Model) HandleKey(key string) Action {
+	if m.searching {
+		m.handleSearchKey(key)
+		return ActionNone
+	}
+
+	switch key {
+	case "q", "\x03":
+		return ActionQuit
+	case "r":
+		return ActionReload
+	case "j", "\x1b[B":
+		m.cursor++
+	case "k", "\x1b[A":
+		m.cursor--
+	case "\r", " ":
+		m.toggle()
+	case "/":
+		m.searching = true
+		m.search = ""
+	case "\x1b":
+		m.search = ""
+	}
+	m.clampCursor()
+	return ActionNone
+}
+
+func (m *Model) handleSearchKey(key string) {
+	switch key {
+	case "\r", "\x1b":
+		m.searching = false
+	case "\x7f":
+		if len(m.search) > 0 {
+			m.search = m.search[:len(m.search)-1]
+		}
+	default:
+		if len(key) == 1 && key[0] >= ' ' {
+			m.search += key
+		}
+	}
+	m.cursor = 0
+}
+
+func (m *Model) toggle() {
+	rows := m.rows()
+	if len(rows) == 0 || !rows[m.cursor].hasChildren {
+		return
+	}
+	path := rows[m.cursor].path
+	m.collapsed[path] = !m.collapsed[path]
+}
+
+func (m *Model) clampCursor() {
+	rows := len(m.rows())
+	if m.cursor >= rows {
+		m.cursor = rows - 1
+	}
+	if m.cursor < 0 {
+		m.cursor = 0
+	}
+}
+
+// rows flattens the visible part of the tree. While a search is active, only nodes whose
+// subtree contains a name match are shown, fully expanded.
+func (m *Model) rows() []row {
+	var rows []row
+	var walk func(nodes []types.HierarchyNode, parent string, depth int)
+	walk = func(nodes []types.HierarchyNode, parent string, depth int) {
+		for _, node := range nodes {
+			if m.search != "" && !subtreeMatches(node, m.search) {
+				continue
+			}
+			path := fmt.Sprintf("%s/%s:%s", parent, node.Kind, node.Name)
+			rows = append(rows, row{node: node, path: path, depth: depth, hasChildren: len(node.Relatives) > 0})
+			if m.search == "" && m.collapsed[path] {
+				continue
+			}
+			walk(node.Relatives, path, depth+1)
+		}
+	}
+	walk(m.nodes, "", 0)
+	return rows
+}
+
+func subtreeMatches(node types.HierarchyNode, search string) bool {
+	if strings.Contains(strings.ToLower(node.Name), strings.ToLower(search)) {
+		return true
+	}
+	for _, relative := range node.Relatives {
+		if subtreeMatches(relative, search) {
+			return true
+		}
+	}
+	return false
+}
+
+// View renders the tree pane above a detail pane for the selected node
+func (m *Model) View(width, height int) string {
+	var b strings.Builder
+	rows := m.rows()
+
+	detail := m.detail(rows)
+	treeHeight := height - len(detail) - 2
+	if treeHeight < 1 {
+		treeHeight = 1
+	}
+
+	start := 0
+	if m.cursor >= treeHeight {
+		start = m.cursor - treeHeight + 1
+	}
+	for i := start; i < len(rows) && i < start+treeHeight; i++ {
+		r := rows[i]
+		marker := "  "
+		if r.hasChildren {
+			marker = "▾ "
+			if m.collapsed[r.path] && m.search == "" {
+				marker = "▸ "
+			}
+		}
+		line := truncate(strings.Repeat("  ", r.depth)+marker+label(r.node), width)
+		if i == m.cursor {
+			line = "\x1b[7m" + line + "\x1b[0m"
+		}
+		b.WriteString(line + "\r\n")
+	}
+	for i := len(rows) - start; i < treeHeight; i++ {
+		b.WriteString("\r\n")
+	}
+
+	b.WriteString(truncate(strings.Repeat("─", width), width) + "\r\n")
+	for _, line := range detail {
+		b.WriteString(truncate(line, width) + "\r\n")
+	}
+	b.WriteString(truncate(m.statusLine(), width))
+	return b.String()
+}
+
+func (m *Model) statusLine() string {
+	if m.err != nil {
+		return fmt.Sprintf("error: %v", m.err)
+	}
+	if m.searching {
+		return "/" + m.search
+	}
+	if m.search != "" {
+		return fmt.Sprintf("filter: %s (esc to clear)  j/k move  enter toggle  / search  r reload  q quit", m.search)
+	}
+	return "j/k move  enter toggle  / search  r reload  q quit"
+}
+
+func (m *Model) detail(rows []row) []string {
+	if len(rows) == 0 {
+		return []string{"no resources"}
+	}
+	node := rows[m.cursor].node
+
+	lines := []string{fmt.Sprintf("%s %s", node.Kind, node.Name)}
+	if node.Namespace != nil {
+		lines = append(lines, "namespace: "+*node.Namespace)
+	}
+	if node.Phase != nil {
+		lines = append(lines, "phase: "+*node.Phase)
+	}
+	if node.Terminating {
+		lines = append(lines, terminating(node))
+	}
+	if node.ServiceType != nil {
+		lines = append(lines, "type: "+*node.ServiceType)
+	}
+	if len(node.Hostnames) > 0 {
+		lines = append(lines, "hostnames: "+strings.Join(node.Hostnames, ", "))
+	}
+	if len(node.PortMappings) > 0 {
+		lines = append(lines, "ports: "+strings.Join(node.PortMappings, ", "))
+	}
+	if len(node.PodIPs) > 0 {
+		lines = append(lines, "pod ips: "+strings.Join(node.PodIPs, ", "))
+	}
+	if len(node.Labels) > 0 {
+		lines = append(lines, "labels: "+formatLabels(node.Labels))
+	}
+	if node.HealthInfo != nil {
+		lines = append(lines, fmt.Sprintf("health: %s (%.1f%% uptime)", node.HealthInfo.Status, node.HealthInfo.Uptime))
+	}
+	return lines
+}
+
+func label(node types.HierarchyNode) string {
+	text := fmt.Sprintf("%s %s", node.Kind, node.Name)
+	if node.Terminating {
+		return fmt.Sprintf("%s [%s]", text, terminating(node))
+	}
+	if node.HealthInfo != nil {
+		return fmt.Sprintf("%s [%s]", text, node.HealthInfo.Status)
+	}
+	if node.Phase != nil {
+		return fmt.Sprintf("%s [%s]", text, *node.Phase)
+	}
+	return text
+}
+
+// terminating describes a deleted node with the grace period it has left, when it has one
+func terminating(node types.HierarchyNode) string {
+	if node.GracePeriodSeconds == nil {
+		return "Terminating"
+	}
+	return fmt.Sprintf("Terminating, %ds left", *node.GracePeriodSeconds)
+}
+
+func formatLabels(labels map[string]string) string {
+	pairs := make([]string, 0, len(labels))
+	for k, v := range labels {
+		pairs = append(pairs, k+"="+v)
+	}
+	sort.Strings(pairs)
+	return strings.Join(pairs, ", ")
+}
+
+func truncate(s string, width int) string {
+	runes := []rune(s)
+	if width <= 0 || len(runes) <= width {
+		return s
+	}
+	return string(runes[:width])
+}
@@ -0,0 +1,290 @@
+package tui_test
+
+import (
+	"errors"
+	"strings"
+	"testing"
+
+	"k8s.io/utils/ptr"
+
+	"github.com/kdwils/constellation/internal/tui"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const width = 80
+
+func testHierarchy() []types.HierarchyNode {
+	return []types.HierarchyNode{
+		{
+			Kind: types.ResourceKindNamespace,
+			Name: "shop",
+			Relatives: []types.HierarchyNode{
+				{
+					Kind:        types.ResourceKindService,
+					Name:        "web",
+					Namespace:   ptr.To("shop"),
+					ServiceType: ptr.To("ClusterIP"),
+					Relatives: []types.HierarchyNode{
+						{Kind: types.ResourceKindPod, Name: "web-0", Namespace: ptr.To("shop"), Phase: ptr.To("Running")},
+					},
+				},
+			},
+		},
+		{
+			Kind: types.ResourceKindNamespace,
+			Name: "blog",
+			Relatives: []types.HierarchyNode{
+				{Kind: types.ResourceKindService, Name: "api", Namespace: ptr.To("blog")},
+			},
+		},
+	}
+}
+
+func selected(line string) string {
+	return "\x1b[7m" + line + "\x1b[0m"
+}
+
+func TestModel_View(t *testing.T) {
+	separator := strings.Repeat("─", width)
+	help := "j/k move  enter toggle  / search  r reload  q quit"
+
+	tests := []struct {
+		name   string
+		keys   []string
+		err    error
+		height int
+		want   []string
+	}{
+		{
+			name:   "initial tree selects the first node",
+			height: 10,
+			want: []string{
+				selected("▾ Namespace shop"),
+				"  ▾ Service web",
+				"      Pod web-0 [Running]",
+				"▾ Namespace blog",
+				"    Service api",
+				"",
+				"",
+				separator,
+				"Namespace shop",
+				help,
+			},
+		},
+		{
+			name:   "enter collapses the selected node",
+			keys:   []string{"j", "\r"},
+			height: 10,
+			want: []string{
+				"▾ Namespace shop",
+				selected("  ▸ Service web"),
+				"▾ Namespace blog",
+				"    Service api",
+				"",
+				separator,
+				"Service web",
+				"namespace: shop",
+				"type: ClusterIP",
+				help,
+			},
+		},
+		{
+			name:   "enter expands a collapsed node",
+			keys:   []string{"j", "\r", "\r"},
+			height: 10,
+			want: []string{
+				"▾ Namespace shop",
+				selected("  ▾ Service web"),
+				"      Pod web-0 [Running]",
+				"▾ Namespace blog",
+				"    Service api",
+				separator,
+				"Service web",
+				"namespace: shop",
+				"type: ClusterIP",
+				help,
+			},
+		},
+		{
+			name:   "cursor stops at the last node",
+			keys:   []string{"k", "j", "j", "j", "j", "j", "j"},
+			height: 10,
+			want: []string{
+				"▾ Namespace shop",
+				"  ▾ Service web",
+				"      Pod web-0 [Running]",
+				"▾ Namespace blog",
+				selected("    Service api"),
+				"",
+				separator,
+				"Service api",
+				"namespace: blog",
+				help,
+			},
+		},
+		{
+			name:   "tree scrolls to keep the cursor visible",
+			keys:   []string{"j", "j", "j"},
+			height: 6,
+			want: []string{
+				"  ▾ Service web",
+				"      Pod web-0 [Running]",
+				selected("▾ Namespace blog"),
+				separator,
+				"Namespace blog",
+				help,
+			},
+		},
+		{
+			name:   "typing a search shows the prompt and filters the tree",
+			keys:   []string{"/", "w", "e", "b"},
+			height: 10,
+			want: []string{
+				selected("▾ Namespace shop"),
+				"  ▾ Service web",
+				"      Pod web-0 [Running]",
+				"",
+				"",
+				"",
+				"",
+				separator,
+				"Namespace shop",
+				"/web",
+			},
+		},
+		{
+			name:   "confirmed search keeps the filter and expands collapsed matches",
+			keys:   []string{"j", "j", "j", "\r", "/", "a", "p", "x", "\x7f", "i", "\r"},
+			height: 10,
+			want: []string{
+				selected("▾ Namespace blog"),
+				"    Service api",
+				"",
+				"",
+				"",
+				"",
+				"",
+				separator,
+				"Namespace blog",
+				"filter: api (esc to clear)  " + help,
+			},
+		},
+		{
+			name:   "escape clears the filter",
+			keys:   []string{"/", "a", "p", "i", "\r", "\x1b"},
+			height: 10,
+			want: []string{
+				selected("▾ Namespace shop"),
+				"  ▾ Service web",
+				"      Pod web-0 [Running]",
+				"▾ Namespace blog",
+				"    Service api",
+				"",
+				"",
+				separator,
+				"Namespace shop",
+				help,
+			},
+		},
+		{
+			name:   "load error keeps the last tree",
+			err:    errors.New("stream closed"),
+			height: 10,
+			want: []string{
+				selected("▾ Namespace shop"),
+				"  ▾ Service web",
+				"      Pod web-0 [Running]",
+				"▾ Namespace blog",
+				"    Service api",
+				"",
+				"",
+				separator,
+				"Namespace shop",
+				"error: stream closed",
+			},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			model := tui.NewModel()
+			model.SetHierarchy(testHierarchy(), nil)
+			if tt.err != nil {
+				model.SetHierarchy(nil, tt.err)
+			}
+			for _, key := range tt.keys {
+				if action := model.HandleKey(key); action != tui.ActionNone {
+					t.Fatalf("TestModel_View() HandleKey(%q) = %v, want %v", key, action, tui.ActionNone)
+				}
+			}
+
+			want := strings.Join(tt.want, "\r\n")
+			if got := model.View(width, tt.height); got != want {
+				t.Errorf("TestModel_View() = \n%q, want \n%q", got, want)
+			}
+		})
+	}
+}
+
+func TestModel_View_Empty(t *testing.T) {
+	model := tui.NewModel()
+	model.SetHierarchy(nil, nil)
+
+	want := strings.Join([]string{"", "", "", "", "", "", "", strings.Repeat("─", width), "no resources",
+		"j/k move  enter toggle  / search  r reload  q quit"}, "\r\n")
+	if got := model.View(width, 10); got != want {
+		t.Errorf("TestModel_View_Empty() = \n%q, want \n%q", got, want)
+	}
+}
+
+func TestModel_HandleKey(t *testing.T) {
+	tests := []struct {
+		name string
+		keys []string
+		want tui.Action
+	}{
+		{
+			name: "q quits",
+			keys: []string{"q"},
+			want: tui.ActionQuit,
+		},
+		{
+			name: "ctrl-c quits",
+			keys: []string{"\x03"},
+			want: tui.ActionQuit,
+		},
+		{
+			name: "r reloads",
+			keys: []string{"r"},
+			want: tui.ActionReload,
+		},
+		{
+			name: "q is typed into a search",
+			keys: []string{"/", "q"},
+			want: tui.ActionNone,
+		},
+		{
+			name: "q quits after the search is confirmed",
+			keys: []string{"/", "q", "\r", "q"},
+			want: tui.ActionQuit,
+		},
+		{
+			name: "arrow keys move",
+			keys: []string{"\x1b[B"},
+			want: tui.ActionNone,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			model := tui.NewModel()
+			model.SetHierarchy(testHierarchy(), nil)
+
+			var got tui.Action
+			for _, key := range tt.keys {
+				got = model.HandleKey(key)
+			}
+			if got != tt.want {
+				t.Errorf("TestModel_HandleKey() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,138 @@
+// Package tui implements an interactive terminal viewer for the resource hierarchy, for
+// sessions where a browser is not available.
+package tui
+
+import (
+	"context"
+	"fmt"
+	"io"
+	"os"
+	"strings"
+	"time"
+
+	"golang.org/x/term"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const (
+	// fallbackInterval reloads the tree from a server even without updates, for changes that do not touch
+	// health, e.g. a pod being added behind a healthy Service
+	fallbackInterval = 30 * time.Second
+
+	// enterScreen switches to the alternate screen and hides the cursor; exitScreen reverses it
+	enterScreen = "\x1b[?1049h\x1b[?25l"
+	exitScreen  = "\x1b[?25h\x1b[?1049l"
+)
+
+// HierarchySource supplies the tree shown in the viewer
+type HierarchySource interface {
+	GetHierarchy(ctx context.Context) ([]types.HierarchyNode, error)
+}
+
+// UpdateSource is a HierarchySource that signals when the tree may have changed, such as a server's
+// WebSocket stream. The channel is closed when the stream drops.
+type UpdateSource interface {
+	Updates(ctx context.Context) (<-chan struct{}, error)
+}
+
+// Run takes over the terminal attached to in and out until the user quits or ctx is cancelled.
+// A source that implements UpdateSource is reloaded on every update and every fallbackInterval; any
+// other source, which reads the apiserver directly, is only reloaded when r is pressed.
+func Run(ctx context.Context, source HierarchySource, in, out *os.File) error {
+	fd := int(in.Fd())
+	if !term.IsTerminal(fd) {
+		return fmt.Errorf("tui requires an interactive terminal")
+	}
+
+	state, err := term.MakeRaw(fd)
+	if err != nil {
+		return fmt.Errorf("failed to enter raw mode: %w", err)
+	}
+	defer term.Restore(fd, state) //nolint:errcheck
+
+	if _, err := fmt.Fprint(out, enterScreen); err != nil {
+		return err
+	}
+	defer fmt.Fprint(out, exitScreen) //nolint:errcheck
+
+	ctx, cancel := context.WithCancel(ctx)
+	defer cancel()
+	keys := make(chan string)
+	go readKeys(ctx, in, keys)
+
+	model := NewModel()
+	model.SetHierarchy(source.GetHierarchy(ctx))
+
+	var updates <-chan struct{}
+	var fallback <-chan time.Time
+	if live, ok := source.(UpdateSource); ok {
+		// a stream that fails to open leaves the fallback reload
+		updates, _ = live.Updates(ctx)
+		ticker := time.NewTicker(fallbackInterval)
+		defer ticker.Stop()
+		fallback = ticker.C
+	}
+
+	for {
+		if err := draw(out, model); err != nil {
+			return err
+		}
+
+		select {
+		case <-ctx.Done():
+			return nil
+		case _, ok := <-updates:
+			if !ok {
+				updates = nil
+				continue
+			}
+			model.SetHierarchy(source.GetHierarchy(ctx))
+		case <-fallback:
+			model.SetHierarchy(source.GetHierarchy(ctx))
+		case key, ok := <-keys:
+			if !ok {
+				return nil
+			}
+			switch model.HandleKey(key) {
+			case ActionQuit:
+				return nil
+			case ActionReload:
+				model.SetHierarchy(source.GetHierarchy(ctx))
+			}
+		}
+	}
+}
+
+func draw(out *os.File, model *Model) error {
+	width, height, err := term.GetSize(int(out.Fd()))
+	if err != nil {
+		width, height = 80, 24
+	}
+	_, err = fmt.Fprint(out, "\x1b[H\x1b[2J"+model.View(width, height))
+	return err
+}
+
+// readKeys emits escape sequences such as arrow keys whole and everything else one rune at a time.
+// It stops at the first key read after ctx is cancelled.
+func readKeys(ctx context.Context, in io.Reader, keys chan<- string) {
+	defer close(keys)
+	buf := make([]byte, 16)
+	for {
+		n, err := in.Read(buf)
+		if err != nil {
+			return
+		}
+		pressed := []string{string(buf[:n])}
+		if buf[0] != 0x1b {
+			pressed = strings.Split(string(buf[:n]), "")
+		}
+		for _, key := range pressed {
+			select {
+			case keys <- key:
+			case <-ctx.Done():
+				return
+			}
+		}
+	}
+}
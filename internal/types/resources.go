@@ -1,9 +1,13 @@
 package types
 
 import (
+	"encoding/json"
 	"time"
 
 	corev1 "k8s.io/api/core/v1"
+	discoveryv1 "k8s.io/api/discovery/v1"
+	networkingv1 "k8s.io/api/networking/v1"
+	schedulingv1 "k8s.io/api/scheduling/v1"
 	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
 	"sigs.k8s.io/gateway-api/apis/v1beta1"
 )
@@ -11,16 +15,36 @@ import (
 type ResourceKind string
 
 const (
-	ResourceKindNamespace ResourceKind = "Namespace"
-	ResourceKindService   ResourceKind = "Service"
-	ResourceKindPod       ResourceKind = "Pod"
-	ResourceKindHTTPRoute ResourceKind = "HTTPRoute"
+	ResourceKindNamespace    ResourceKind = "Namespace"
+	ResourceKindService      ResourceKind = "Service"
+	ResourceKindPod          ResourceKind = "Pod"
+	ResourceKindHTTPRoute    ResourceKind = "HTTPRoute"
+	ResourceKindTrafficSplit ResourceKind = "TrafficSplit"
+	ResourceKindTrack        ResourceKind = "Track"
+	ResourceKindNode         ResourceKind = "Node"
+	ResourceKindGateway      ResourceKind = "Gateway"
+	ResourceKindApplication  ResourceKind = "Application"
+	ResourceKindDeployment   ResourceKind = "Deployment"
+	ResourceKindStatefulSet  ResourceKind = "StatefulSet"
+	ResourceKindHostname     ResourceKind = "Hostname"
+	// ResourceKindTruncated stands in for the children of a root left out by the size guard
+	ResourceKindTruncated ResourceKind = "Truncated"
 )
 
 func (r ResourceKind) String() string {
 	return string(r)
 }
 
+// HierarchyRoot selects the kind of node the hierarchy is grouped under
+type HierarchyRoot string
+
+const (
+	HierarchyRootNamespace HierarchyRoot = "namespace"
+	HierarchyRootGateway   HierarchyRoot = "gateway"
+	HierarchyRootApp       HierarchyRoot = "app"
+	HierarchyRootNode      HierarchyRoot = "node"
+)
+
 type ContainerPortInfo struct {
 	Port     int32   `json:"port"`
 	Name     *string `json:"name,omitempty"`
@@ -45,6 +69,10 @@ type ResourceMetadata struct {
 	Group           string              `json:"group,omitempty"`
 	DisplayName     string              `json:"display_name,omitempty"`
 	Ignore          bool                `json:"ignore,omitempty"`
+	TrafficPolicy   *TrafficPolicy      `json:"traffic_policy,omitempty"`
+	IPFamilies      []string            `json:"ip_families,omitempty"`
+	IPFamilyPolicy  *string             `json:"ip_family_policy,omitempty"`
+	Addresses       map[string][]string `json:"addresses,omitempty"`
 }
 
 type Resource struct {
@@ -57,28 +85,722 @@ type Resource struct {
 
 // HierarchyNode represents a resource with its child resources
 type HierarchyNode struct {
-	Kind            ResourceKind        `json:"kind"`
-	Name            string              `json:"name"`
-	Namespace       *string             `json:"namespace,omitempty"`
-	Relatives       []HierarchyNode     `json:"relatives,omitempty"`
-	Hostnames       []string            `json:"hostnames,omitempty"`
-	Selectors       map[string]string   `json:"selectors,omitempty"`
-	Ports           []int32             `json:"ports,omitempty"`
-	PortMappings    []string            `json:"port_mappings,omitempty"`
-	TargetPorts     []int32             `json:"target_ports,omitempty"`
-	TargetPortNames []string            `json:"target_port_names,omitempty"`
-	ContainerPorts  []ContainerPortInfo `json:"container_ports,omitempty"`
-	Labels          map[string]string   `json:"labels,omitempty"`
-	Phase           *string             `json:"phase,omitempty"`
-	BackendRefs     []string            `json:"backend_refs,omitempty"`
-	ServiceType     *string             `json:"service_type,omitempty"`
-	ClusterIPs      []string            `json:"cluster_ips,omitempty"`
-	ExternalIPs     []string            `json:"external_ips,omitempty"`
-	PodIPs          []string            `json:"pod_ips,omitempty"`
-	Group           string              `json:"group,omitempty"`
-	DisplayName     string              `json:"display_name,omitempty"`
-	Ignore          bool                `json:"ignore,omitempty"`
-	HealthInfo      *ServiceHealthInfo  `json:"health_info,omitempty"`
+	Kind                ResourceKind         `json:"kind"`
+	Name                string               `json:"name"`
+	Namespace           *string              `json:"namespace,omitempty"`
+	Relatives           []HierarchyNode      `json:"relatives,omitempty"`
+	Hostnames           []string             `json:"hostnames,omitempty"`
+	Selectors           map[string]string    `json:"selectors,omitempty"`
+	Ports               []int32              `json:"ports,omitempty"`
+	PortMappings        []string             `json:"port_mappings,omitempty"`
+	TargetPorts         []int32              `json:"target_ports,omitempty"`
+	TargetPortNames     []string             `json:"target_port_names,omitempty"`
+	ContainerPorts      []ContainerPortInfo  `json:"container_ports,omitempty"`
+	Labels              map[string]string    `json:"labels,omitempty"`
+	Phase               *string              `json:"phase,omitempty"`
+	BackendRefs         []string             `json:"backend_refs,omitempty"`
+	ServiceType         *string              `json:"service_type,omitempty"`
+	ClusterIPs          []string             `json:"cluster_ips,omitempty"`
+	ExternalIPs         []string             `json:"external_ips,omitempty"`
+	PodIPs              []string             `json:"pod_ips,omitempty"`
+	Group               string               `json:"group,omitempty"`
+	DisplayName         string               `json:"display_name,omitempty"`
+	Ignore              bool                 `json:"ignore,omitempty"`
+	HealthInfo          *ServiceHealthInfo   `json:"health_info,omitempty"`
+	Traffic             *TrafficMetrics      `json:"traffic,omitempty"`
+	Usage               *ResourceUsage       `json:"usage,omitempty"`
+	RestartAnomaly      bool                 `json:"restart_anomaly,omitempty"`
+	DNSNames            []string             `json:"dns_names,omitempty"`
+	DNSRecords          []DNSRecord          `json:"dns_records,omitempty"`
+	HostnameConflicts   []string             `json:"hostname_conflicts,omitempty"`
+	Certificates        []CertificateInfo    `json:"certificates,omitempty"`
+	ImagePulls          []ContainerImagePull `json:"image_pulls,omitempty"`
+	ImagePullSecrets    bool                 `json:"image_pull_secrets,omitempty"`
+	Sidecars            []string             `json:"sidecars,omitempty"`
+	Meshed              bool                 `json:"meshed,omitempty"`
+	PriorityClassName   string               `json:"priority_class_name,omitempty"`
+	Priority            *int32               `json:"priority,omitempty"`
+	Preemptible         bool                 `json:"preemptible,omitempty"`
+	PrioritySummary     *PrioritySummary     `json:"priority_summary,omitempty"`
+	Disruptions         []Disruption         `json:"disruptions,omitempty"`
+	UnschedulableReason string               `json:"unschedulable_reason,omitempty"`
+	Probes              []ProbeInfo          `json:"probes,omitempty"`
+	SecurityFlags       []SecurityFlag       `json:"security_flags,omitempty"`
+	SecuritySummary     map[SecurityFlag]int `json:"security_summary,omitempty"`
+	Warnings            []Warning            `json:"warnings,omitempty"`
+	Rules               []RouteRule          `json:"rules,omitempty"`
+	Weight              *int32               `json:"weight,omitempty"`
+	TrafficPercent      *float64             `json:"traffic_percent,omitempty"`
+	Controllers         []string             `json:"controllers,omitempty"`
+	Endpoints           *EndpointCounts      `json:"endpoints,omitempty"`
+	TrafficPolicy       *TrafficPolicy       `json:"traffic_policy,omitempty"`
+	IPFamilies          []string             `json:"ip_families,omitempty"`
+	IPFamilyPolicy      *string              `json:"ip_family_policy,omitempty"`
+	Addresses           map[string][]string  `json:"addresses,omitempty"`
+	OS                  string               `json:"os,omitempty"`
+	Arch                string               `json:"arch,omitempty"`
+	Annotations         map[string]string    `json:"annotations,omitempty"`
+	Counts              *ResourceCounts      `json:"counts,omitempty"`
+	Owner               string               `json:"owner,omitempty"`
+	NodeName            string               `json:"node_name,omitempty"`
+	Gateways            []string             `json:"gateways,omitempty"`
+	Count               int                  `json:"count,omitempty"`
+	Terminating         bool                 `json:"terminating,omitempty"`
+	TerminationDeadline *metav1.Time         `json:"termination_deadline,omitempty"`
+	GracePeriodSeconds  *int64               `json:"grace_period_seconds,omitempty"`
+	Rollout             *Rollout             `json:"rollout,omitempty"`
+	Track               string               `json:"track,omitempty"`
+	ReplicaDrift        []ReplicaDrift       `json:"replica_drift,omitempty"`
+	Markers             []Marker             `json:"markers,omitempty"`
+	Badges              []string             `json:"badges,omitempty"`
+	ID                  string               `json:"id"`
+	ParentID            string               `json:"parent_id,omitempty"`
+}
+
+// TrafficMetrics are request metrics queried from Prometheus for a Service or HTTPRoute
+type TrafficMetrics struct {
+	RequestRate    *float64 `json:"request_rate,omitempty"`
+	ErrorRate      *float64 `json:"error_rate,omitempty"`
+	LatencySeconds *float64 `json:"latency_seconds,omitempty"`
+}
+
+// DNSRecord is an external DNS record implied by a resource's hostnames or external-dns annotations
+type DNSRecord struct {
+	Hostname string   `json:"hostname"`
+	Type     string   `json:"type,omitempty"`
+	Targets  []string `json:"targets,omitempty"`
+	Source   string   `json:"source"`
+}
+
+// CertificateInfo describes the TLS certificate a Gateway listener serves for a route hostname
+type CertificateInfo struct {
+	Hostname     string    `json:"hostname"`
+	Secret       string    `json:"secret"`
+	Issuer       string    `json:"issuer"`
+	NotAfter     time.Time `json:"not_after"`
+	DNSNames     []string  `json:"dns_names,omitempty"`
+	Covered      bool      `json:"covered"`
+	ExpiringSoon bool      `json:"expiring_soon,omitempty"`
+}
+
+// ResourceUsage is the live CPU and memory consumption of a pod as reported by metrics-server
+type ResourceUsage struct {
+	CPUMillicores int64     `json:"cpu_millicores"`
+	MemoryBytes   int64     `json:"memory_bytes"`
+	Timestamp     time.Time `json:"timestamp"`
+}
+
+type AlertType string
+
+const (
+	AlertTypeRestartAnomaly      AlertType = "restart_anomaly"
+	AlertTypeCertificateExpiring AlertType = "certificate_expiring"
+	AlertTypeCertificateMismatch AlertType = "certificate_mismatch"
+	AlertTypeReplicaDrift        AlertType = "replica_drift"
+)
+
+// Alert is a condition on a single resource that needs attention
+type Alert struct {
+	Type      AlertType    `json:"type"`
+	Kind      ResourceKind `json:"kind"`
+	Namespace string       `json:"namespace"`
+	Name      string       `json:"name"`
+	Message   string       `json:"message"`
+	Since     time.Time    `json:"since"`
+}
+
+// PrioritySummary counts a namespace's pods by priority class. Preemptible pods have a lower
+// priority than some class that is allowed to preempt them.
+type PrioritySummary struct {
+	Classes     map[string]int `json:"classes"`
+	Preemptible int            `json:"preemptible"`
+	Lowest      *int32         `json:"lowest,omitempty"`
+}
+
+// RouteRule is one HTTPRoute rule's matches and the weighted backends it forwards to
+type RouteRule struct {
+	Matches  []RouteMatch   `json:"matches,omitempty"`
+	Backends []RouteBackend `json:"backends,omitempty"`
+}
+
+// RouteMatch is one HTTPRoute match condition
+type RouteMatch struct {
+	PathType    string   `json:"path_type,omitempty"`
+	Path        string   `json:"path,omitempty"`
+	Method      string   `json:"method,omitempty"`
+	Headers     []string `json:"headers,omitempty"`
+	QueryParams []string `json:"query_params,omitempty"`
+}
+
+// RouteBackend is a backendRef with its weight, which defaults to 1
+type RouteBackend struct {
+	Name      string `json:"name"`
+	Namespace string `json:"namespace,omitempty"`
+	Port      *int32 `json:"port,omitempty"`
+	Weight    int32  `json:"weight"`
+}
+
+type WarningType string
+
+const (
+	WarningTypePortMismatch  WarningType = "port_mismatch"
+	WarningTypeRouteConflict WarningType = "route_conflict"
+)
+
+// Warning is a likely misconfiguration found while building a node
+type Warning struct {
+	Type    WarningType `json:"type"`
+	Message string      `json:"message"`
+}
+
+// TrafficPolicy holds the Service settings that change how traffic is spread across its endpoints:
+// client IP session affinity, node-local traffic policies and topology aware routing
+type TrafficPolicy struct {
+	SessionAffinity               string `json:"session_affinity,omitempty"`
+	SessionAffinityTimeoutSeconds *int32 `json:"session_affinity_timeout_seconds,omitempty"`
+	InternalTrafficPolicy         string `json:"internal_traffic_policy,omitempty"`
+	ExternalTrafficPolicy         string `json:"external_traffic_policy,omitempty"`
+	TrafficDistribution           string `json:"traffic_distribution,omitempty"`
+	TopologyMode                  string `json:"topology_mode,omitempty"`
+}
+
+// IPLookup is the result of looking up an IPv4 or IPv6 address, normalized to its canonical form
+type IPLookup struct {
+	IP     string    `json:"ip"`
+	Family string    `json:"family"`
+	Owners []IPOwner `json:"owners"`
+}
+
+// SelectorTest is a label selector to try against the cluster, either in kubectl's -l syntax or as the
+// LabelSelector of a Deployment or NetworkPolicy. Namespace limits it to one namespace.
+type SelectorTest struct {
+	Namespace     string                `json:"namespace,omitempty"`
+	Selector      string                `json:"selector,omitempty"`
+	LabelSelector *metav1.LabelSelector `json:"label_selector,omitempty"`
+}
+
+// SelectorMatches are the Pods and Services whose labels a SelectorTest matched. Selector is the
+// selector as it was understood, in kubectl's -l syntax.
+type SelectorMatches struct {
+	Selector string           `json:"selector"`
+	Pods     []SelectedObject `json:"pods"`
+	Services []SelectedObject `json:"services"`
+}
+
+// SelectedObject is an object matched by a label selector, with the labels it matched on
+type SelectedObject struct {
+	Namespace string            `json:"namespace"`
+	Name      string            `json:"name"`
+	Labels    map[string]string `json:"labels,omitempty"`
+}
+
+// IPOwner is an object holding a looked up address. Source says which of its addresses matched,
+// e.g. pod_ip, cluster_ip, or a node address type such as InternalIP.
+type IPOwner struct {
+	Kind      ResourceKind `json:"kind"`
+	Namespace string       `json:"namespace,omitempty"`
+	Name      string       `json:"name"`
+	Source    string       `json:"source"`
+}
+
+// ResourceCounts is the number of pods and services in a namespace, ignored resources excluded
+type ResourceCounts struct {
+	Pods     int `json:"pods"`
+	Services int `json:"services"`
+}
+
+// ResourceRef identifies a resource in the hierarchy
+type ResourceRef struct {
+	Kind      ResourceKind `json:"kind"`
+	Namespace string       `json:"namespace,omitempty"`
+	Name      string       `json:"name"`
+}
+
+// OwnerGroup is every resource attributed to one owner
+type OwnerGroup struct {
+	Owner     string        `json:"owner"`
+	Resources []ResourceRef `json:"resources"`
+}
+
+// RankedResource is one entry of a /stats ranking, with the count it is ranked by
+type RankedResource struct {
+	Kind      ResourceKind `json:"kind"`
+	Namespace string       `json:"namespace,omitempty"`
+	Name      string       `json:"name"`
+	Count     int          `json:"count"`
+}
+
+// Stats is the /stats response: the largest resources of each ranked kind, largest first
+type Stats struct {
+	NamespacesByPods    []RankedResource `json:"namespaces_by_pods"`
+	ServicesByEndpoints []RankedResource `json:"services_by_endpoints"`
+	RoutesByBackends    []RankedResource `json:"routes_by_backends"`
+}
+
+// LabelFacet is one label key with the number of resources carrying each of its values
+type LabelFacet struct {
+	Key    string       `json:"key"`
+	Count  int          `json:"count"`
+	Values []LabelValue `json:"values"`
+}
+
+// LabelValue is one value of a label key and how many resources carry it
+type LabelValue struct {
+	Value string `json:"value"`
+	Count int    `json:"count"`
+}
+
+// NodeWatch selects the node a /ws/node client follows; Namespace is empty for cluster scoped kinds.
+// ID picks one copy of a resource that appears under several parents and takes precedence over the rest.
+type NodeWatch struct {
+	ID        string       `json:"id,omitempty"`
+	Kind      ResourceKind `json:"kind,omitempty"`
+	Namespace string       `json:"namespace,omitempty"`
+	Name      string       `json:"name,omitempty"`
+}
+
+// WatchRequest is the message a /ws/node client sends to pick the node it follows
+type WatchRequest struct {
+	Watch *NodeWatch `json:"watch"`
+}
+
+// NodeUpdate is the current subtree of a followed node. Node is unset while the node does not exist.
+type NodeUpdate struct {
+	ID   string         `json:"id"`
+	Node *HierarchyNode `json:"node,omitempty"`
+}
+
+// ClientInfo describes a connected WebSocket client. Queued counts updates waiting to be written to it,
+// so a client that stays above zero is not keeping up with the broadcast.
+type ClientInfo struct {
+	ID           uint64        `json:"id"`
+	Stream       string        `json:"stream"`
+	Remote       string        `json:"remote"`
+	ConnectedAt  time.Time     `json:"connected_at"`
+	Filter       string        `json:"filter,omitempty"`
+	MessagesSent int           `json:"messages_sent"`
+	LastSentAt   time.Time     `json:"last_sent_at,omitzero"`
+	LastPongAt   time.Time     `json:"last_pong_at,omitzero"`
+	PingRTT      time.Duration `json:"ping_rtt,omitempty"`
+	Queued       int           `json:"queued"`
+}
+
+// MessageChunk is one part of a WebSocket message split to stay under the server's size limit.
+// Clients decode and concatenate Data from Index 0 to Total-1 of an ID and parse the result as the message.
+type MessageChunk struct {
+	ID    uint64 `json:"id"`
+	Index int    `json:"index"`
+	Total int    `json:"total"`
+	Data  []byte `json:"data"`
+}
+
+// ChunkedMessage wraps a MessageChunk so clients can tell it from a whole message
+type ChunkedMessage struct {
+	Chunk MessageChunk `json:"chunk"`
+}
+
+// KindSync is the most recent change constellation observed for one kind of resource
+type KindSync struct {
+	LastEventTime   time.Time `json:"last_event_time"`
+	ResourceVersion string    `json:"resource_version,omitempty"`
+}
+
+// SyncStatus reports how current the served data is. Degraded is set when any watch is down,
+// Syncing until the initial state has been built, and Stale while the snapshot persisted before a
+// restart is served in its place.
+type SyncStatus struct {
+	Kinds    map[ResourceKind]KindSync `json:"kinds,omitempty"`
+	Degraded bool                      `json:"degraded"`
+	Syncing  bool                      `json:"syncing,omitempty"`
+	Stale    bool                      `json:"stale,omitempty"`
+}
+
+// WatchStatus reports whether an optional resource watch is enabled
+type WatchStatus struct {
+	Resource string `json:"resource"`
+	Enabled  bool   `json:"enabled"`
+}
+
+// StoreStatus describes the cached objects of one resource, for debugging stale state.
+// ResourceVersions maps namespace/name to each object's resourceVersion when requested.
+type StoreStatus struct {
+	Resource                string            `json:"resource"`
+	Enabled                 bool              `json:"enabled"`
+	Synced                  bool              `json:"synced"`
+	Stopped                 bool              `json:"stopped"`
+	LastSyncResourceVersion string            `json:"last_sync_resource_version,omitempty"`
+	Count                   int               `json:"count"`
+	ResourceVersions        map[string]string `json:"resource_versions,omitempty"`
+}
+
+// StoreMemory estimates the memory held by the cached objects of one resource: Count objects of
+// AverageBytes each, averaged over Sampled of them
+type StoreMemory struct {
+	Resource       string `json:"resource"`
+	Enabled        bool   `json:"enabled"`
+	Synced         bool   `json:"synced"`
+	Count          int    `json:"count"`
+	Sampled        int    `json:"sampled"`
+	AverageBytes   int    `json:"average_bytes"`
+	EstimatedBytes int64  `json:"estimated_bytes"`
+}
+
+// RuntimeDiagnostics is the /debug/tasks response. MutexWait totals the time goroutines have spent
+// waiting on locks since the process started, so compare two readings to see current contention.
+type RuntimeDiagnostics struct {
+	Goroutines int                 `json:"goroutines"`
+	GOMAXPROCS int                 `json:"gomaxprocs"`
+	MutexWait  time.Duration       `json:"mutex_wait"`
+	HeapBytes  uint64              `json:"heap_bytes"`
+	GCCycles   uint32              `json:"gc_cycles"`
+	Streams    []StreamDiagnostics `json:"streams"`
+}
+
+// StreamDiagnostics totals the clients of one WebSocket stream and the updates queued for them
+type StreamDiagnostics struct {
+	Stream    string `json:"stream"`
+	Clients   int    `json:"clients"`
+	Queued    int    `json:"queued"`
+	MaxQueued int    `json:"max_queued"`
+}
+
+// UIConfig is the /config.json response: per-install options the frontend reads when it loads, so it is
+// customised without a rebuild. Features are named flags such as showSystemNamespaces.
+type UIConfig struct {
+	Title             string          `json:"title"`
+	RefreshInterval   time.Duration   `json:"refresh_interval,omitempty"`
+	DefaultNamespaces []string        `json:"default_namespaces"`
+	Features          map[string]bool `json:"features"`
+}
+
+// Capabilities are what the caller of /me may do
+type Capabilities struct {
+	// Identity names the caller, anonymous unless Authenticated
+	Identity             string `json:"identity"`
+	Authenticated        bool   `json:"authenticated"`
+	CanUseAdmin          bool   `json:"can_use_admin"`
+	CanResync            bool   `json:"can_resync"`
+	CanViewAllNamespaces bool   `json:"can_view_all_namespaces"`
+	CanPinFavorites      bool   `json:"can_pin_favorites"`
+	CanPostAnnotations   bool   `json:"can_post_annotations"`
+}
+
+// AuditEntry is a change made through the API by an identified caller, as listed at /admin/audit
+type AuditEntry struct {
+	Time     time.Time `json:"time"`
+	Identity string    `json:"identity"`
+	Action   string    `json:"action"`
+	Method   string    `json:"method"`
+	Path     string    `json:"path"`
+	Status   int       `json:"status"`
+	Client   string    `json:"client"`
+}
+
+// Favorite is a node pinned on the dashboard, as posted to /favorites
+type Favorite struct {
+	ID string `json:"id"`
+}
+
+// ResyncResult reports the watches a resync restarted and the Services it reconciled again
+type ResyncResult struct {
+	RestartedWatches   []string `json:"restarted_watches"`
+	ReconciledServices int      `json:"reconciled_services"`
+}
+
+// State is the /state response: the health of every service and how current the data behind it is
+type State struct {
+	Services []*ServiceHealthInfo      `json:"services"`
+	Kinds    map[ResourceKind]KindSync `json:"kinds,omitempty"`
+	Degraded bool                      `json:"degraded"`
+	Syncing  bool                      `json:"syncing,omitempty"`
+	Stale    bool                      `json:"stale,omitempty"`
+}
+
+// EndpointCounts is the readiness of the endpoints behind a Service, taken from its EndpointSlices
+type EndpointCounts struct {
+	Ready    int `json:"ready"`
+	NotReady int `json:"not_ready"`
+}
+
+// ServiceEndpoints is one Service's endpoint readiness, as streamed at /ws/endpoints
+type ServiceEndpoints struct {
+	Namespace string `json:"namespace"`
+	Name      string `json:"name"`
+	Ready     int    `json:"ready"`
+	NotReady  int    `json:"not_ready"`
+}
+
+type SecurityFlag string
+
+const (
+	SecurityFlagRunAsRoot      SecurityFlag = "run_as_root"
+	SecurityFlagPrivileged     SecurityFlag = "privileged"
+	SecurityFlagHostNetwork    SecurityFlag = "host_network"
+	SecurityFlagHostPID        SecurityFlag = "host_pid"
+	SecurityFlagHostIPC        SecurityFlag = "host_ipc"
+	SecurityFlagMissingLimits  SecurityFlag = "missing_limits"
+	SecurityFlagAutomountToken SecurityFlag = "automount_service_account_token"
+)
+
+type ProbeType string
+
+const (
+	ProbeTypeStartup   ProbeType = "startup"
+	ProbeTypeLiveness  ProbeType = "liveness"
+	ProbeTypeReadiness ProbeType = "readiness"
+)
+
+// ProbeInfo is the effective configuration of one container probe, with unset timings defaulted
+type ProbeInfo struct {
+	Container           string    `json:"container"`
+	Type                ProbeType `json:"type"`
+	Handler             string    `json:"handler"`
+	Path                string    `json:"path,omitempty"`
+	Port                string    `json:"port,omitempty"`
+	Command             []string  `json:"command,omitempty"`
+	InitialDelaySeconds int32     `json:"initial_delay_seconds"`
+	PeriodSeconds       int32     `json:"period_seconds"`
+	TimeoutSeconds      int32     `json:"timeout_seconds"`
+	SuccessThreshold    int32     `json:"success_threshold"`
+	FailureThreshold    int32     `json:"failure_threshold"`
+}
+
+// Disruption is a pod terminated by an eviction, a scheduler preemption or the OOM killer
+type Disruption struct {
+	Reason    string    `json:"reason"`
+	Pod       string    `json:"pod"`
+	Container string    `json:"container,omitempty"`
+	Message   string    `json:"message,omitempty"`
+	Time      time.Time `json:"time"`
+}
+
+// WorkloadDisruptions is the recent disruption history of the pods of one workload
+type WorkloadDisruptions struct {
+	Kind        string       `json:"kind"`
+	Namespace   string       `json:"namespace"`
+	Name        string       `json:"name"`
+	Disruptions []Disruption `json:"disruptions"`
+}
+
+// APIProbe is the latency of the latest list and watch against the apiserver for one kind
+type APIProbe struct {
+	Kind         ResourceKind  `json:"kind"`
+	ListLatency  time.Duration `json:"list_latency"`
+	WatchLatency time.Duration `json:"watch_latency"`
+	Error        string        `json:"error,omitempty"`
+}
+
+// APIServerStatus reports whether the apiserver is answering constellation promptly. Ready is false
+// until the first probe and whenever a probe fails or is slower than the threshold.
+type APIServerStatus struct {
+	Ready  bool       `json:"ready"`
+	Time   time.Time  `json:"time,omitzero"`
+	Probes []APIProbe `json:"probes"`
+}
+
+// NamespaceChurn counts the pods created and deleted in one namespace per bucket, oldest bucket first
+type NamespaceChurn struct {
+	Namespace string `json:"namespace"`
+	Added     []int  `json:"added"`
+	Deleted   []int  `json:"deleted"`
+	Total     int    `json:"total"`
+}
+
+// ChurnReport is a heatmap of pod churn with a row per namespace and a column per bucket. Start is when
+// the oldest bucket began; namespaces are ordered by total churn, highest first.
+type ChurnReport struct {
+	Start         time.Time        `json:"start"`
+	BucketSeconds int              `json:"bucket_seconds"`
+	Namespaces    []NamespaceChurn `json:"namespaces"`
+}
+
+// Rollout is the Deployment revision a pod was created from, with the change-cause annotation recorded
+// for it. StartedAt is when the revision was seen to start rolling, or its ReplicaSet's creation time
+// when it was already rolling at startup.
+type Rollout struct {
+	Deployment  string    `json:"deployment"`
+	Revision    int64     `json:"revision"`
+	ChangeCause string    `json:"change_cause,omitempty"`
+	StartedAt   time.Time `json:"started_at"`
+}
+
+// ReplicaDrift is a workload with fewer ready pods than the replicas it asks for, since Since
+type ReplicaDrift struct {
+	Kind    ResourceKind `json:"kind"`
+	Name    string       `json:"name"`
+	Desired int32        `json:"desired"`
+	Ready   int32        `json:"ready"`
+	Since   time.Time    `json:"since"`
+}
+
+type EventType string
+
+const (
+	EventTypeRolloutStarted EventType = "rollout_started"
+	EventTypePodReplaced    EventType = "pod_replaced"
+)
+
+// Event is a change in the cluster worth correlating with the topology, such as a new revision rolling
+type Event struct {
+	Type        EventType    `json:"type"`
+	Namespace   string       `json:"namespace"`
+	Name        string       `json:"name"`
+	Message     string       `json:"message"`
+	Rollout     *Rollout     `json:"rollout,omitempty"`
+	Replacement *Replacement `json:"replacement,omitempty"`
+	Time        time.Time    `json:"time"`
+}
+
+// Replacement links a pod to the one it replaced: both were created by the same controller from the same
+// pod template, so the new pod is the old one recreated rather than an unrelated addition
+type Replacement struct {
+	OwnerKind    string `json:"owner_kind"`
+	Owner        string `json:"owner"`
+	TemplateHash string `json:"template_hash"`
+	Previous     string `json:"previous"`
+	PreviousUID  string `json:"previous_uid"`
+	Current      string `json:"current"`
+	CurrentUID   string `json:"current_uid"`
+}
+
+// Marker is external context attached to a node at a point in time, such as a CI deployment or an
+// incident, received as a CloudEvent. Data is the event's data as JSON, a string when it was not JSON.
+type Marker struct {
+	ID     string          `json:"id"`
+	Source string          `json:"source"`
+	Type   string          `json:"type"`
+	Data   json.RawMessage `json:"data,omitempty"`
+	Time   time.Time       `json:"time"`
+}
+
+// Annotation is a Marker and the node it is attached to, as streamed at /ws/annotations
+type Annotation struct {
+	Kind      ResourceKind `json:"kind"`
+	Namespace string       `json:"namespace,omitempty"`
+	Name      string       `json:"name"`
+	Marker    Marker       `json:"marker"`
+}
+
+// NodeRef identifies a resource in the dependency graph by the ID the hierarchy gives it
+type NodeRef struct {
+	ID        string       `json:"id"`
+	Kind      ResourceKind `json:"kind"`
+	Namespace string       `json:"namespace,omitempty"`
+	Name      string       `json:"name"`
+}
+
+// Impact is the blast radius of a resource: Upstream are the resources that depend on it, such as the
+// routes and hostnames served by a pod, and Downstream those it depends on, such as the pods a route reaches
+type Impact struct {
+	Target     NodeRef   `json:"target"`
+	Upstream   []NodeRef `json:"upstream"`
+	Downstream []NodeRef `json:"downstream"`
+}
+
+// Paths are the shortest chains of dependencies connecting two resources, each running from the dependent
+// resource to the one it depends on, e.g. hostname, HTTPRoute, Service, Pod, Node. Related is false when
+// neither resource depends on the other.
+type Paths struct {
+	From    NodeRef     `json:"from"`
+	To      NodeRef     `json:"to"`
+	Related bool        `json:"related"`
+	Paths   [][]NodeRef `json:"paths"`
+}
+
+type TransitionType string
+
+const (
+	TransitionTypePhase         TransitionType = "phase"
+	TransitionTypeEndpoints     TransitionType = "endpoints"
+	TransitionTypeRouteAttached TransitionType = "route_attached"
+	TransitionTypeRouteDetached TransitionType = "route_detached"
+)
+
+// Transition is one change to the state of a node, from From to To. Route transitions name the Gateway
+// on an HTTPRoute's timeline and the HTTPRoute on a Gateway's.
+type Transition struct {
+	Type TransitionType `json:"type"`
+	From string         `json:"from,omitempty"`
+	To   string         `json:"to,omitempty"`
+	Time time.Time      `json:"time"`
+}
+
+// ImageSummary is one container image reference and where it runs across the cluster
+type ImageSummary struct {
+	Image      string   `json:"image"`
+	Registry   string   `json:"registry"`
+	Repository string   `json:"repository"`
+	Tag        string   `json:"tag,omitempty"`
+	Digests    []string `json:"digests,omitempty"`
+	PodCount   int      `json:"pod_count"`
+	Namespaces []string `json:"namespaces"`
+}
+
+// ContainerImagePull is how a container pulls its image and the kubelet's pull failure, if any
+type ContainerImagePull struct {
+	Container  string `json:"container"`
+	Image      string `json:"image"`
+	PullPolicy string `json:"pull_policy"`
+	Reason     string `json:"reason,omitempty"`
+	Message    string `json:"message,omitempty"`
+}
+
+// ImagePullFailure is a container that is currently failing to pull its image
+type ImagePullFailure struct {
+	Namespace      string `json:"namespace"`
+	Pod            string `json:"pod"`
+	Container      string `json:"container"`
+	Image          string `json:"image"`
+	Reason         string `json:"reason"`
+	Message        string `json:"message,omitempty"`
+	HasPullSecrets bool   `json:"has_pull_secrets"`
+}
+
+// ImagePullSummary counts image pull failures across the cluster
+type ImagePullSummary struct {
+	Pods     int                `json:"pods"`
+	Reasons  map[string]int     `json:"reasons"`
+	Failures []ImagePullFailure `json:"failures"`
+}
+
+// ReachQuery asks whether traffic from a pod, or pods matching a selector, may reach a pod or service port
+type ReachQuery struct {
+	From     Endpoint `json:"from"`
+	To       Endpoint `json:"to"`
+	Port     int32    `json:"port"`
+	Protocol string   `json:"protocol,omitempty"`
+}
+
+// Endpoint names a Pod or Service, or for the source of a ReachQuery the labels of a hypothetical pod
+type Endpoint struct {
+	Kind      ResourceKind      `json:"kind,omitempty"`
+	Namespace string            `json:"namespace"`
+	Name      string            `json:"name,omitempty"`
+	Selector  map[string]string `json:"selector,omitempty"`
+}
+
+// ReachResult is the NetworkPolicy verdict for a ReachQuery. Traffic is allowed when at least one
+// destination pod may receive it from the source.
+type ReachResult struct {
+	Allowed bool            `json:"allowed"`
+	Targets []TargetVerdict `json:"targets"`
+}
+
+// TargetVerdict is the verdict for one destination pod and the container port the traffic lands on
+type TargetVerdict struct {
+	Pod     string        `json:"pod"`
+	Port    int32         `json:"port"`
+	Allowed bool          `json:"allowed"`
+	Egress  PolicyVerdict `json:"egress"`
+	Ingress PolicyVerdict `json:"ingress"`
+}
+
+// PolicyVerdict lists the policies that isolate a pod in one direction and those that permit the traffic
+type PolicyVerdict struct {
+	Allowed   bool     `json:"allowed"`
+	Isolated  bool     `json:"isolated"`
+	AllowedBy []string `json:"allowed_by,omitempty"`
+	DeniedBy  []string `json:"denied_by,omitempty"`
 }
 
 type ClusterState struct {
@@ -91,6 +813,32 @@ type Connection struct {
 	Target string `json:"target"`
 }
 
+// ClusterObjects holds the raw Kubernetes objects the hierarchy is built from
+type ClusterObjects struct {
+	Namespaces      []corev1.Namespace           `json:"namespaces"`
+	Services        []corev1.Service             `json:"services"`
+	Pods            []corev1.Pod                 `json:"pods"`
+	HTTPRoutes      []v1beta1.HTTPRoute          `json:"httproutes"`
+	PriorityClasses []schedulingv1.PriorityClass `json:"priorityclasses,omitempty"`
+	Nodes           []corev1.Node                `json:"nodes,omitempty"`
+	NetworkPolicies []networkingv1.NetworkPolicy `json:"networkpolicies,omitempty"`
+	Gateways        []v1beta1.Gateway            `json:"gateways,omitempty"`
+	GatewayClasses  []v1beta1.GatewayClass       `json:"gatewayclasses,omitempty"`
+	EndpointSlices  []discoveryv1.EndpointSlice  `json:"endpointslices,omitempty"`
+}
+
+// SnapshotVersion is the format of the snapshots this build writes and the only one it reads
+const SnapshotVersion = 1
+
+// Snapshot is a self-contained, point-in-time export of everything constellation serves
+type Snapshot struct {
+	Version   int                  `json:"version"`
+	Timestamp time.Time            `json:"timestamp"`
+	Hierarchy []HierarchyNode      `json:"hierarchy"`
+	Health    []*ServiceHealthInfo `json:"health"`
+	Objects   ClusterObjects       `json:"objects"`
+}
+
 type ResourceSpec interface {
 	GetKind() ResourceKind
 }
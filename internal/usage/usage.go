@@ -0,0 +1,213 @@
+// Package usage reads live pod and node resource consumption from metrics-server and attaches it to the
+// hierarchy.
+package usage
+
+import (
+	"context"
+	"fmt"
+	"sync"
+	"time"
+
+	"k8s.io/apimachinery/pkg/api/resource"
+	"k8s.io/apimachinery/pkg/apis/meta/v1/unstructured"
+	"k8s.io/apimachinery/pkg/runtime/schema"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	"sigs.k8s.io/controller-runtime/pkg/log"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/types"
+)
+
+const defaultInterval = 30 * time.Second
+
+// the metrics are read as unstructured so the metrics.k8s.io types are not a dependency
+var (
+	podMetricsList  = schema.GroupVersionKind{Group: "metrics.k8s.io", Version: "v1beta1", Kind: "PodMetricsList"}
+	nodeMetricsList = schema.GroupVersionKind{Group: "metrics.k8s.io", Version: "v1beta1", Kind: "NodeMetricsList"}
+)
+
+// Collector periodically reads PodMetrics and NodeMetrics and attaches the summed container usage to Pod
+// nodes and the usage of each Node to its node
+type Collector struct {
+	reader   client.Reader
+	interval time.Duration
+
+	mu    sync.RWMutex
+	pods  map[string]types.ResourceUsage
+	nodes map[string]types.ResourceUsage
+}
+
+// NewCollector creates a Collector. reader should bypass the cache, e.g. the manager's API reader,
+// since metrics are not watchable.
+func NewCollector(reader client.Reader, opts ...CollectorOpt) *Collector {
+	c := &Collector{
+		reader:   reader,
+		interval: defaultInterval,
+		pods:     make(map[string]types.ResourceUsage),
+		nodes:    make(map[string]types.ResourceUsage),
+	}
+
+	for _, opt := range opts {
+		opt(c)
+	}
+
+	return c
+}
+
+type CollectorOpt func(*Collector)
+
+func WithInterval(interval time.Duration) CollectorOpt {
+	return func(c *Collector) {
+		c.interval = interval
+	}
+}
+
+// +kubebuilder:rbac:groups=metrics.k8s.io,resources=pods;nodes,verbs=get;list
+
+// Start refreshes usage on the configured interval until ctx is cancelled
+func (c *Collector) Start(ctx context.Context) {
+	logger := log.FromContext(ctx).WithName("usage")
+	ticker := time.NewTicker(c.interval)
+	defer ticker.Stop()
+
+	for {
+		if err := c.Refresh(ctx); err != nil {
+			logger.Error(err, "failed to refresh usage, is metrics-server installed?")
+		}
+
+		select {
+		case <-ctx.Done():
+			return
+		case <-ticker.C:
+		}
+	}
+}
+
+// Refresh lists PodMetrics and NodeMetrics once and replaces the stored usage
+func (c *Collector) Refresh(ctx context.Context) error {
+	pods, err := c.list(ctx, podMetricsList)
+	if err != nil {
+		return fmt.Errorf("failed to list pod metrics: %w", err)
+	}
+	nodes, err := c.list(ctx, nodeMetricsList)
+	if err != nil {
+		return fmt.Errorf("failed to list node metrics: %w", err)
+	}
+
+	c.mu.Lock()
+	c.pods = pods
+	c.nodes = nodes
+	c.mu.Unlock()
+	return nil
+}
+
+// list reads the usage of every item of a metrics list, keyed by namespace/name, or name for the
+// cluster scoped NodeMetrics
+func (c *Collector) list(ctx context.Context, gvk schema.GroupVersionKind) (map[string]types.ResourceUsage, error) {
+	list := &unstructured.UnstructuredList{}
+	list.SetGroupVersionKind(gvk)
+	if err := c.reader.List(ctx, list); err != nil {
+		return nil, err
+	}
+
+	result := make(map[string]types.ResourceUsage, len(list.Items))
+	for _, item := range list.Items {
+		key := item.GetName()
+		if item.GetNamespace() != "" {
+			key = item.GetNamespace() + "/" + key
+		}
+		usage, err := parseUsage(item)
+		if err != nil {
+			return nil, fmt.Errorf("invalid metrics for %s: %w", key, err)
+		}
+		result[key] = usage
+	}
+	return result, nil
+}
+
+// parseUsage reads the usage of a PodMetrics, summed over its containers, or of a NodeMetrics
+func parseUsage(item unstructured.Unstructured) (types.ResourceUsage, error) {
+	var usage types.ResourceUsage
+
+	timestamp, _, _ := unstructured.NestedString(item.Object, "timestamp")
+	if timestamp != "" {
+		parsed, err := time.Parse(time.RFC3339, timestamp)
+		if err != nil {
+			return usage, err
+		}
+		usage.Timestamp = parsed
+	}
+
+	containers, found, err := unstructured.NestedSlice(item.Object, "containers")
+	if err != nil {
+		return usage, err
+	}
+	if !found {
+		return usage, addUsage(&usage, item.Object)
+	}
+	for _, container := range containers {
+		fields, ok := container.(map[string]any)
+		if !ok {
+			continue
+		}
+		if err := addUsage(&usage, fields); err != nil {
+			return usage, err
+		}
+	}
+	return usage, nil
+}
+
+// addUsage adds the cpu and memory quantities under fields' usage to usage
+func addUsage(usage *types.ResourceUsage, fields map[string]any) error {
+	cpu, _, _ := unstructured.NestedString(fields, "usage", "cpu")
+	memory, _, _ := unstructured.NestedString(fields, "usage", "memory")
+
+	if cpu != "" {
+		quantity, err := resource.ParseQuantity(cpu)
+		if err != nil {
+			return err
+		}
+		usage.CPUMillicores += quantity.MilliValue()
+	}
+	if memory != "" {
+		quantity, err := resource.ParseQuantity(memory)
+		if err != nil {
+			return err
+		}
+		usage.MemoryBytes += quantity.Value()
+	}
+	return nil
+}
+
+// Get returns the usage of a single pod
+func (c *Collector) Get(namespace, pod string) (types.ResourceUsage, bool) {
+	c.mu.RLock()
+	defer c.mu.RUnlock()
+	usage, ok := c.pods[namespace+"/"+pod]
+	return usage, ok
+}
+
+// GetNode returns the usage of a single Node
+func (c *Collector) GetNode(name string) (types.ResourceUsage, bool) {
+	c.mu.RLock()
+	defer c.mu.RUnlock()
+	usage, ok := c.nodes[name]
+	return usage, ok
+}
+
+// Enrich attaches usage to Pod and Node nodes
+func (c *Collector) Enrich(nodes []types.HierarchyNode) {
+	hierarchy.Walk(nodes, func(node *types.HierarchyNode) {
+		var usage types.ResourceUsage
+		var ok bool
+		switch {
+		case node.Kind == types.ResourceKindPod && node.Namespace != nil:
+			usage, ok = c.Get(*node.Namespace, node.Name)
+		case node.Kind == types.ResourceKindNode:
+			usage, ok = c.GetNode(node.Name)
+		}
+		if ok {
+			node.Usage = &usage
+		}
+	})
+}
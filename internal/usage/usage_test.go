@@ -0,0 +1,153 @@
+package usage_test
+
+import (
+	"context"
+	"errors"
+	"reflect"
+	"testing"
+	"time"
+
+	"k8s.io/apimachinery/pkg/apis/meta/v1/unstructured"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	"sigs.k8s.io/controller-runtime/pkg/client/fake"
+	"sigs.k8s.io/controller-runtime/pkg/client/interceptor"
+
+	"github.com/kdwils/constellation/internal/types"
+	"github.com/kdwils/constellation/internal/usage"
+)
+
+// metricsServer serves items as the metrics.k8s.io list of their kind
+func metricsServer(items map[string][]map[string]any) interceptor.Funcs {
+	return interceptor.Funcs{
+		List: func(ctx context.Context, c client.WithWatch, list client.ObjectList, opts ...client.ListOption) error {
+			metrics, ok := list.(*unstructured.UnstructuredList)
+			if !ok {
+				return c.List(ctx, list, opts...)
+			}
+			kind := metrics.GroupVersionKind().Kind
+			if _, served := items[kind]; !served {
+				return errors.New("the server could not find the requested resource")
+			}
+			for _, item := range items[kind] {
+				metrics.Items = append(metrics.Items, unstructured.Unstructured{Object: item})
+			}
+			return nil
+		},
+	}
+}
+
+func podMetrics(namespace, name string, containers ...map[string]any) map[string]any {
+	list := make([]any, 0, len(containers))
+	for _, container := range containers {
+		list = append(list, container)
+	}
+	return map[string]any{
+		"metadata":   map[string]any{"namespace": namespace, "name": name},
+		"timestamp":  "2025-01-01T00:00:00Z",
+		"containers": list,
+	}
+}
+
+func containerUsage(name, cpu, memory string) map[string]any {
+	return map[string]any{"name": name, "usage": map[string]any{"cpu": cpu, "memory": memory}}
+}
+
+func nodeMetrics(name, cpu, memory string) map[string]any {
+	return map[string]any{
+		"metadata":  map[string]any{"name": name},
+		"timestamp": "2025-01-01T00:00:00Z",
+		"usage":     map[string]any{"cpu": cpu, "memory": memory},
+	}
+}
+
+func TestCollector_Enrich(t *testing.T) {
+	at := time.Date(2025, 1, 1, 0, 0, 0, 0, time.UTC)
+	shop := "shop"
+
+	tests := []struct {
+		name    string
+		items   map[string][]map[string]any
+		nodes   []types.HierarchyNode
+		want    []types.HierarchyNode
+		wantErr string
+	}{
+		{
+			name: "pod usage is summed over its containers",
+			items: map[string][]map[string]any{
+				"PodMetricsList": {podMetrics("shop", "web-0",
+					containerUsage("web", "250m", "128Mi"), containerUsage("proxy", "1000000n", "64Ki"))},
+				"NodeMetricsList": {},
+			},
+			nodes: []types.HierarchyNode{{Kind: types.ResourceKindPod, Name: "web-0", Namespace: &shop}},
+			want: []types.HierarchyNode{{
+				Kind:      types.ResourceKindPod,
+				Name:      "web-0",
+				Namespace: &shop,
+				Usage:     &types.ResourceUsage{CPUMillicores: 251, MemoryBytes: 128<<20 + 64<<10, Timestamp: at},
+			}},
+		},
+		{
+			name: "node usage is attached to its node",
+			items: map[string][]map[string]any{
+				"PodMetricsList":  {},
+				"NodeMetricsList": {nodeMetrics("node-a", "2", "4Gi")},
+			},
+			nodes: []types.HierarchyNode{{
+				Kind:      types.ResourceKindNode,
+				Name:      "node-a",
+				Relatives: []types.HierarchyNode{{Kind: types.ResourceKindPod, Name: "web-0", Namespace: &shop}},
+			}},
+			want: []types.HierarchyNode{{
+				Kind:      types.ResourceKindNode,
+				Name:      "node-a",
+				Usage:     &types.ResourceUsage{CPUMillicores: 2000, MemoryBytes: 4 << 30, Timestamp: at},
+				Relatives: []types.HierarchyNode{{Kind: types.ResourceKindPod, Name: "web-0", Namespace: &shop}},
+			}},
+		},
+		{
+			name: "pods in another namespace are not matched",
+			items: map[string][]map[string]any{
+				"PodMetricsList":  {podMetrics("other", "web-0", containerUsage("web", "250m", "128Mi"))},
+				"NodeMetricsList": {},
+			},
+			nodes: []types.HierarchyNode{{Kind: types.ResourceKindPod, Name: "web-0", Namespace: &shop}},
+			want:  []types.HierarchyNode{{Kind: types.ResourceKindPod, Name: "web-0", Namespace: &shop}},
+		},
+		{
+			name: "invalid quantities fail the refresh",
+			items: map[string][]map[string]any{
+				"PodMetricsList":  {podMetrics("shop", "web-0", containerUsage("web", "lots", "128Mi"))},
+				"NodeMetricsList": {},
+			},
+			wantErr: "failed to list pod metrics: invalid metrics for shop/web-0: quantities must match the regular " +
+				"expression '^([+-]?[0-9.]+)([eEinumkKMGTP]*[-+]?[0-9]*)$'",
+		},
+		{
+			name:    "missing node metrics fail the refresh",
+			items:   map[string][]map[string]any{"PodMetricsList": {}},
+			wantErr: "failed to list node metrics: the server could not find the requested resource",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			reader := fake.NewClientBuilder().WithInterceptorFuncs(metricsServer(tt.items)).Build()
+			collector := usage.NewCollector(reader)
+
+			err := collector.Refresh(context.Background())
+			if tt.wantErr != "" {
+				if err == nil || err.Error() != tt.wantErr {
+					t.Fatalf("TestCollector_Enrich() error = %v, want %v", err, tt.wantErr)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("TestCollector_Enrich() error = %v", err)
+			}
+
+			collector.Enrich(tt.nodes)
+			if !reflect.DeepEqual(tt.nodes, tt.want) {
+				t.Errorf("TestCollector_Enrich() = %+v, want %+v", tt.nodes, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,177 @@
+// Package versions negotiates the API version each Gateway API kind is read in, from the versions the
+// apiserver serves, so constellation keeps working across clusters that serve only v1 or only v1beta1.
+// Objects are always handed on as v1beta1, whose spec and status are the v1 types.
+package versions
+
+import (
+	"fmt"
+
+	apierrors "k8s.io/apimachinery/pkg/api/errors"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	gatewayv1 "sigs.k8s.io/gateway-api/apis/v1"
+	"sigs.k8s.io/gateway-api/apis/v1beta1"
+)
+
+// fallback is read when discovery has not been asked, or the apiserver serves none of the versions
+const fallback = "v1beta1"
+
+// preference lists the versions that can be read, most preferred first
+var preference = []string{"v1", "v1beta1"}
+
+// typed is the object and list type of a resource in one version
+type typed struct {
+	object func() client.Object
+	list   func() client.ObjectList
+}
+
+// resources are the negotiated resources and their types in each version
+var resources = map[string]map[string]typed{
+	"httproutes": {
+		"v1": {
+			object: func() client.Object { return &gatewayv1.HTTPRoute{} },
+			list:   func() client.ObjectList { return &gatewayv1.HTTPRouteList{} },
+		},
+		"v1beta1": {
+			object: func() client.Object { return &v1beta1.HTTPRoute{} },
+			list:   func() client.ObjectList { return &v1beta1.HTTPRouteList{} },
+		},
+	},
+	"gateways": {
+		"v1": {
+			object: func() client.Object { return &gatewayv1.Gateway{} },
+			list:   func() client.ObjectList { return &gatewayv1.GatewayList{} },
+		},
+		"v1beta1": {
+			object: func() client.Object { return &v1beta1.Gateway{} },
+			list:   func() client.ObjectList { return &v1beta1.GatewayList{} },
+		},
+	},
+	"gatewayclasses": {
+		"v1": {
+			object: func() client.Object { return &gatewayv1.GatewayClass{} },
+			list:   func() client.ObjectList { return &gatewayv1.GatewayClassList{} },
+		},
+		"v1beta1": {
+			object: func() client.Object { return &v1beta1.GatewayClass{} },
+			list:   func() client.ObjectList { return &v1beta1.GatewayClassList{} },
+		},
+	},
+}
+
+// Discovery reports the resources the apiserver serves in a group version, e.g. client-go's
+// discovery.DiscoveryClient
+type Discovery interface {
+	ServerResourcesForGroupVersion(groupVersion string) (*metav1.APIResourceList, error)
+}
+
+// Versions is the version each Gateway API resource is read in. The zero value reads v1beta1.
+type Versions struct {
+	served map[string]string
+}
+
+// Negotiate asks discovery which versions of the Gateway API resources are served and picks the most
+// preferred of them for each resource. Resources that are not served at all keep v1beta1, so listing
+// them still fails with a no match error when the CRDs are missing.
+func Negotiate(d Discovery) (Versions, error) {
+	v := Versions{served: make(map[string]string)}
+	for _, version := range preference {
+		list, err := d.ServerResourcesForGroupVersion(gatewayv1.GroupName + "/" + version)
+		if apierrors.IsNotFound(err) {
+			continue
+		}
+		if err != nil {
+			return Versions{}, fmt.Errorf("failed to discover %s/%s: %w", gatewayv1.GroupName, version, err)
+		}
+		for _, resource := range list.APIResources {
+			if _, negotiated := resources[resource.Name]; !negotiated {
+				continue
+			}
+			if _, picked := v.served[resource.Name]; picked {
+				continue
+			}
+			v.served[resource.Name] = version
+		}
+	}
+	return v, nil
+}
+
+// Version returns the version resource is read in
+func (v Versions) Version(resource string) string {
+	version, picked := v.served[resource]
+	if !picked {
+		return fallback
+	}
+	return version
+}
+
+// Object returns an empty object of resource in its negotiated version, e.g. for starting its informer
+func (v Versions) Object(resource string) client.Object {
+	return resources[resource][v.Version(resource)].object()
+}
+
+// List returns an empty list of resource in its negotiated version, to be read back with HTTPRoutes,
+// Gateways or GatewayClasses
+func (v Versions) List(resource string) client.ObjectList {
+	return resources[resource][v.Version(resource)].list()
+}
+
+// HTTPRoute returns obj as a v1beta1 HTTPRoute when it is an HTTPRoute of either version
+func HTTPRoute(obj any) (*v1beta1.HTTPRoute, bool) {
+	switch o := obj.(type) {
+	case *v1beta1.HTTPRoute:
+		return o, true
+	case *gatewayv1.HTTPRoute:
+		return &v1beta1.HTTPRoute{ObjectMeta: o.ObjectMeta, Spec: o.Spec, Status: o.Status}, true
+	}
+	return nil, false
+}
+
+// HTTPRoutes returns the items of an HTTPRoute list of either version as v1beta1
+func HTTPRoutes(list client.ObjectList) []v1beta1.HTTPRoute {
+	switch l := list.(type) {
+	case *v1beta1.HTTPRouteList:
+		return l.Items
+	case *gatewayv1.HTTPRouteList:
+		routes := make([]v1beta1.HTTPRoute, 0, len(l.Items))
+		for _, route := range l.Items {
+			routes = append(routes, v1beta1.HTTPRoute{ObjectMeta: route.ObjectMeta, Spec: route.Spec, Status: route.Status})
+		}
+		return routes
+	}
+	return nil
+}
+
+// Gateways returns the items of a Gateway list of either version as v1beta1
+func Gateways(list client.ObjectList) []v1beta1.Gateway {
+	switch l := list.(type) {
+	case *v1beta1.GatewayList:
+		return l.Items
+	case *gatewayv1.GatewayList:
+		gateways := make([]v1beta1.Gateway, 0, len(l.Items))
+		for _, gateway := range l.Items {
+			gateways = append(gateways, v1beta1.Gateway{
+				ObjectMeta: gateway.ObjectMeta,
+				Spec:       gateway.Spec,
+				Status:     gateway.Status,
+			})
+		}
+		return gateways
+	}
+	return nil
+}
+
+// GatewayClasses returns the items of a GatewayClass list of either version as v1beta1
+func GatewayClasses(list client.ObjectList) []v1beta1.GatewayClass {
+	switch l := list.(type) {
+	case *v1beta1.GatewayClassList:
+		return l.Items
+	case *gatewayv1.GatewayClassList:
+		classes := make([]v1beta1.GatewayClass, 0, len(l.Items))
+		for _, class := range l.Items {
+			classes = append(classes, v1beta1.GatewayClass{ObjectMeta: class.ObjectMeta, Spec: class.Spec, Status: class.Status})
+		}
+		return classes
+	}
+	return nil
+}
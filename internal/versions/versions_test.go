@@ -0,0 +1,155 @@
+package versions_test
+
+import (
+	"errors"
+	"reflect"
+	"testing"
+
+	apierrors "k8s.io/apimachinery/pkg/api/errors"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"k8s.io/apimachinery/pkg/runtime/schema"
+	gatewayv1 "sigs.k8s.io/gateway-api/apis/v1"
+	"sigs.k8s.io/gateway-api/apis/v1beta1"
+
+	"github.com/kdwils/constellation/internal/versions"
+)
+
+// fakeDiscovery serves the resources listed under each group version
+type fakeDiscovery struct {
+	served map[string][]string
+	err    error
+}
+
+func (f fakeDiscovery) ServerResourcesForGroupVersion(groupVersion string) (*metav1.APIResourceList, error) {
+	if f.err != nil {
+		return nil, f.err
+	}
+	names, ok := f.served[groupVersion]
+	if !ok {
+		return nil, apierrors.NewNotFound(schema.GroupResource{}, groupVersion)
+	}
+	list := &metav1.APIResourceList{GroupVersion: groupVersion}
+	for _, name := range names {
+		list.APIResources = append(list.APIResources, metav1.APIResource{Name: name})
+	}
+	return list, nil
+}
+
+func TestNegotiate(t *testing.T) {
+	errDiscovery := errors.New("connection refused")
+
+	tests := []struct {
+		name      string
+		discovery fakeDiscovery
+		want      map[string]string
+		wantErr   error
+	}{
+		{
+			name: "v1 is preferred where both are served",
+			discovery: fakeDiscovery{served: map[string][]string{
+				"gateway.networking.k8s.io/v1":      {"httproutes", "httproutes/status", "gateways", "gatewayclasses"},
+				"gateway.networking.k8s.io/v1beta1": {"httproutes", "gateways", "gatewayclasses", "referencegrants"},
+			}},
+			want: map[string]string{"httproutes": "v1", "gateways": "v1", "gatewayclasses": "v1"},
+		},
+		{
+			name: "kinds only served as v1beta1 keep it",
+			discovery: fakeDiscovery{served: map[string][]string{
+				"gateway.networking.k8s.io/v1":      {"gatewayclasses"},
+				"gateway.networking.k8s.io/v1beta1": {"httproutes", "gateways", "gatewayclasses"},
+			}},
+			want: map[string]string{"httproutes": "v1beta1", "gateways": "v1beta1", "gatewayclasses": "v1"},
+		},
+		{
+			name: "a cluster that stopped serving v1beta1 is read as v1",
+			discovery: fakeDiscovery{served: map[string][]string{
+				"gateway.networking.k8s.io/v1": {"httproutes", "gateways", "gatewayclasses"},
+			}},
+			want: map[string]string{"httproutes": "v1", "gateways": "v1", "gatewayclasses": "v1"},
+		},
+		{
+			name:      "without the CRDs v1beta1 is read",
+			discovery: fakeDiscovery{},
+			want:      map[string]string{"httproutes": "v1beta1", "gateways": "v1beta1", "gatewayclasses": "v1beta1"},
+		},
+		{
+			name:      "discovery failures are returned",
+			discovery: fakeDiscovery{err: errDiscovery},
+			want:      map[string]string{"httproutes": "v1beta1", "gateways": "v1beta1", "gatewayclasses": "v1beta1"},
+			wantErr:   errDiscovery,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := versions.Negotiate(tt.discovery)
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("TestNegotiate() error = %v, want %v", err, tt.wantErr)
+			}
+			picked := make(map[string]string)
+			for resource := range tt.want {
+				picked[resource] = got.Version(resource)
+			}
+			if !reflect.DeepEqual(picked, tt.want) {
+				t.Errorf("TestNegotiate() = %v, want %v", picked, tt.want)
+			}
+		})
+	}
+}
+
+func TestHTTPRoutes(t *testing.T) {
+	hostnames := []gatewayv1.Hostname{"shop.example.com"}
+	want := []v1beta1.HTTPRoute{{
+		ObjectMeta: metav1.ObjectMeta{Name: "storefront", Namespace: "shop"},
+		Spec:       v1beta1.HTTPRouteSpec{Hostnames: hostnames},
+	}}
+
+	tests := []struct {
+		name     string
+		versions versions.Versions
+		fill     func(list any)
+	}{
+		{
+			name:     "v1beta1 is read as is",
+			versions: versions.Versions{},
+			fill: func(list any) {
+				list.(*v1beta1.HTTPRouteList).Items = want
+			},
+		},
+		{
+			name: "v1 is converted to v1beta1",
+			versions: mustNegotiate(t, fakeDiscovery{served: map[string][]string{
+				"gateway.networking.k8s.io/v1": {"httproutes"},
+			}}),
+			fill: func(list any) {
+				list.(*gatewayv1.HTTPRouteList).Items = []gatewayv1.HTTPRoute{{
+					ObjectMeta: metav1.ObjectMeta{Name: "storefront", Namespace: "shop"},
+					Spec:       gatewayv1.HTTPRouteSpec{Hostnames: hostnames},
+				}}
+			},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			list := tt.versions.List("httproutes")
+			tt.fill(list)
+
+			got := versions.HTTPRoutes(list)
+			if !reflect.DeepEqual(got, want) {
+				t.Errorf("TestHTTPRoutes() = %+v, want %+v", got, want)
+			}
+			route, ok := versions.HTTPRoute(tt.versions.Object("httproutes"))
+			if !ok || route == nil {
+				t.Errorf("TestHTTPRoutes() HTTPRoute() = %v, %v, want a route", route, ok)
+			}
+		})
+	}
+}
+
+func mustNegotiate(t *testing.T, d fakeDiscovery) versions.Versions {
+	t.Helper()
+	v, err := versions.Negotiate(d)
+	if err != nil {
+		t.Fatalf("Negotiate() error = %v", err)
+	}
+	return v
+}
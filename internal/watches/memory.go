@@ -0,0 +1,94 @@
+package watches
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+
+	"k8s.io/apimachinery/pkg/api/meta"
+	"sigs.k8s.io/controller-runtime/pkg/cache"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// sampleSize caps how many objects of a store are encoded to estimate their average size
+const sampleSize = 100
+
+// ErrAlwaysWatched is returned when shrinking a resource whose informer feeds the controllers
+var ErrAlwaysWatched = errors.New("resource is always watched")
+
+// Memory estimates the memory held by the store behind resource as its object count times the average
+// JSON size of up to 100 objects spread across it. JSON is larger than the decoded objects, so treat the
+// estimate as an upper bound for comparing stores and spotting growth. Disabled and unsynced stores are
+// reported without an estimate.
+func (t *Toggles) Memory(ctx context.Context, resource string) (types.StoreMemory, error) {
+	status, err := t.Inspect(ctx, resource, false)
+	if err != nil {
+		return types.StoreMemory{}, err
+	}
+
+	memory := types.StoreMemory{Resource: resource, Enabled: status.Enabled, Synced: status.Synced}
+	if !status.Synced {
+		return memory, nil
+	}
+
+	list := t.list(resource)
+	if err := t.cache.List(ctx, list); err != nil {
+		return types.StoreMemory{}, fmt.Errorf("failed to list %s: %w", resource, err)
+	}
+	items, err := meta.ExtractList(list)
+	if err != nil {
+		return types.StoreMemory{}, err
+	}
+	memory.Count = len(items)
+	if len(items) == 0 {
+		return memory, nil
+	}
+
+	step := max(1, len(items)/sampleSize)
+	total := 0
+	for i := 0; i < len(items) && memory.Sampled < sampleSize; i += step {
+		encoded, err := json.Marshal(items[i])
+		if err != nil {
+			return types.StoreMemory{}, fmt.Errorf("failed to encode %s: %w", resource, err)
+		}
+		total += len(encoded)
+		memory.Sampled++
+	}
+	memory.AverageBytes = total / memory.Sampled
+	memory.EstimatedBytes = int64(memory.AverageBytes) * int64(memory.Count)
+	return memory, nil
+}
+
+// Shrink drops the informer behind an optional resource and starts a new one without waiting for it to
+// list, so a store suspected of bloating is rebuilt from the apiserver. The always watched kinds feed the
+// controllers, whose event handlers would be lost with their informer, so they cannot be shrunk.
+func (t *Toggles) Shrink(ctx context.Context, resource string) error {
+	store, ok := stores[resource]
+	if !ok {
+		return fmt.Errorf("%w %q, expected one of %v", ErrUnknownResource, resource, Stores())
+	}
+	if !store.optional {
+		return fmt.Errorf("%w %q, only %v can be shrunk", ErrAlwaysWatched, resource, Resources())
+	}
+
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	if t.disabled[resource] {
+		return nil
+	}
+	object := t.object(resource)
+	if err := t.cache.RemoveInformer(ctx, object); err != nil {
+		return fmt.Errorf("failed to stop %s informer: %w", resource, err)
+	}
+	_, err := t.cache.GetInformer(ctx, object, cache.BlockUntilSynced(false))
+	if meta.IsNoMatchError(err) {
+		return nil
+	}
+	if err != nil {
+		return fmt.Errorf("failed to start %s informer: %w", resource, err)
+	}
+	return nil
+}
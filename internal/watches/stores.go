@@ -0,0 +1,113 @@
+package watches
+
+import (
+	"context"
+	"fmt"
+	"sort"
+
+	"k8s.io/apimachinery/pkg/api/meta"
+	"sigs.k8s.io/controller-runtime/pkg/cache"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// lastSyncer is implemented by client-go's shared informers behind the manager's cache
+type lastSyncer interface {
+	LastSyncResourceVersion() string
+}
+
+// Stores lists every resource the hierarchy reads, in name order
+func Stores() []string {
+	resources := make([]string, 0, len(stores))
+	for resource := range stores {
+		resources = append(resources, resource)
+	}
+	sort.Strings(resources)
+	return resources
+}
+
+// Inspect reports the readiness of the informer behind resource and how many objects it holds,
+// with each object's resourceVersion when objects is set. A disabled resource is reported without
+// starting its informer, and objects are only counted once the informer has synced.
+func (t *Toggles) Inspect(ctx context.Context, resource string, objects bool) (types.StoreStatus, error) {
+	if _, ok := stores[resource]; !ok {
+		return types.StoreStatus{}, fmt.Errorf("%w %q, expected one of %v", ErrUnknownResource, resource, Stores())
+	}
+
+	t.mu.RLock()
+	defer t.mu.RUnlock()
+
+	status := types.StoreStatus{Resource: resource, Enabled: !t.disabled[resource]}
+	if !status.Enabled {
+		return status, nil
+	}
+
+	informer, err := t.cache.GetInformer(ctx, t.object(resource), cache.BlockUntilSynced(false))
+	if meta.IsNoMatchError(err) {
+		// the Gateway API CRDs are not installed, so there is nothing to watch
+		return status, nil
+	}
+	if err != nil {
+		return types.StoreStatus{}, fmt.Errorf("failed to get %s informer: %w", resource, err)
+	}
+	status.Synced = informer.HasSynced()
+	status.Stopped = informer.IsStopped()
+	if syncer, ok := informer.(lastSyncer); ok {
+		status.LastSyncResourceVersion = syncer.LastSyncResourceVersion()
+	}
+	if !status.Synced {
+		return status, nil
+	}
+
+	list := t.list(resource)
+	if err := t.cache.List(ctx, list); err != nil {
+		return types.StoreStatus{}, fmt.Errorf("failed to list %s: %w", resource, err)
+	}
+	items, err := meta.ExtractList(list)
+	if err != nil {
+		return types.StoreStatus{}, err
+	}
+	status.Count = len(items)
+	if !objects {
+		return status, nil
+	}
+
+	status.ResourceVersions = make(map[string]string, len(items))
+	for _, item := range items {
+		object, ok := item.(client.Object)
+		if !ok {
+			continue
+		}
+		status.ResourceVersions[client.ObjectKeyFromObject(object).String()] = object.GetResourceVersion()
+	}
+	return status, nil
+}
+
+// Restart replaces the informers of the enabled optional watches, forcing a fresh list from the
+// apiserver. The always watched kinds feed the controllers, whose event handlers would be lost with
+// their informer, so they are left running.
+func (t *Toggles) Restart(ctx context.Context) ([]string, error) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	restarted := []string{}
+	for _, resource := range Resources() {
+		if t.disabled[resource] {
+			continue
+		}
+		object := t.object(resource)
+		if err := t.cache.RemoveInformer(ctx, object); err != nil {
+			return restarted, fmt.Errorf("failed to stop %s informer: %w", resource, err)
+		}
+		_, err := t.cache.GetInformer(ctx, object, cache.BlockUntilSynced(false))
+		if meta.IsNoMatchError(err) {
+			continue
+		}
+		if err != nil {
+			return restarted, fmt.Errorf("failed to start %s informer: %w", resource, err)
+		}
+		restarted = append(restarted, resource)
+	}
+	return restarted, nil
+}
@@ -0,0 +1,196 @@
+// Package watches switches the optional resource watches behind the hierarchy on and off at runtime.
+// Disabling a kind stops its informer and leaves it out of the hierarchy, e.g. HTTPRoutes in a cluster
+// without Gateway API. Pods, Services and EndpointSlices feed the health check controllers and the
+// endpoint stream, and namespaces root the tree, so they are always watched.
+package watches
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"sort"
+	"sync"
+
+	corev1 "k8s.io/api/core/v1"
+	discoveryv1 "k8s.io/api/discovery/v1"
+	networkingv1 "k8s.io/api/networking/v1"
+	schedulingv1 "k8s.io/api/scheduling/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"sigs.k8s.io/controller-runtime/pkg/cache"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+
+	"github.com/kdwils/constellation/internal/types"
+	"github.com/kdwils/constellation/internal/versions"
+)
+
+// ErrUnknownResource is returned for a resource that is not one of the optional watches
+var ErrUnknownResource = errors.New("unknown resource")
+
+// store is a resource the hierarchy lists, with the object its informer watches and its list type.
+// Gateway API resources are negotiated, taking their types from the version the apiserver serves, and
+// namespaces are watched as metadata only.
+type store struct {
+	object     func() client.Object
+	list       func() client.ObjectList
+	optional   bool
+	negotiated bool
+}
+
+var stores = map[string]store{
+	"namespaces": {
+		object: func() client.Object {
+			namespace := &metav1.PartialObjectMetadata{}
+			namespace.SetGroupVersionKind(corev1.SchemeGroupVersion.WithKind("Namespace"))
+			return namespace
+		},
+		list: func() client.ObjectList {
+			namespaces := &metav1.PartialObjectMetadataList{}
+			namespaces.SetGroupVersionKind(corev1.SchemeGroupVersion.WithKind("NamespaceList"))
+			return namespaces
+		},
+	},
+	"services": {
+		object: func() client.Object { return &corev1.Service{} },
+		list:   func() client.ObjectList { return &corev1.ServiceList{} },
+	},
+	"pods": {
+		object: func() client.Object { return &corev1.Pod{} },
+		list:   func() client.ObjectList { return &corev1.PodList{} },
+	},
+	"endpointslices": {
+		object: func() client.Object { return &discoveryv1.EndpointSlice{} },
+		list:   func() client.ObjectList { return &discoveryv1.EndpointSliceList{} },
+	},
+	"httproutes": {
+		optional:   true,
+		negotiated: true,
+	},
+	"gateways": {
+		optional:   true,
+		negotiated: true,
+	},
+	"gatewayclasses": {
+		optional:   true,
+		negotiated: true,
+	},
+	"networkpolicies": {
+		object:   func() client.Object { return &networkingv1.NetworkPolicy{} },
+		list:     func() client.ObjectList { return &networkingv1.NetworkPolicyList{} },
+		optional: true,
+	},
+	"priorityclasses": {
+		object:   func() client.Object { return &schedulingv1.PriorityClass{} },
+		list:     func() client.ObjectList { return &schedulingv1.PriorityClassList{} },
+		optional: true,
+	},
+	"nodes": {
+		object:   func() client.Object { return &corev1.Node{} },
+		list:     func() client.ObjectList { return &corev1.NodeList{} },
+		optional: true,
+	},
+}
+
+// Cache starts, stops and reads from the informer behind each kind, e.g. the manager's cache
+type Cache interface {
+	client.Reader
+	types.Informers
+	RemoveInformer(ctx context.Context, obj client.Object) error
+}
+
+// Toggles records which optional watches are enabled
+type Toggles struct {
+	cache    Cache
+	versions versions.Versions
+
+	mu       sync.RWMutex
+	disabled map[string]bool
+}
+
+// NewToggles creates toggles with every optional watch enabled except the resources in disabled, watching
+// the Gateway API resources in the versions in served
+func NewToggles(informerCache Cache, served versions.Versions, disabled ...string) (*Toggles, error) {
+	t := &Toggles{
+		cache:    informerCache,
+		versions: served,
+		disabled: make(map[string]bool),
+	}
+	for _, resource := range disabled {
+		if !stores[resource].optional {
+			return nil, fmt.Errorf("%w %q, expected one of %v", ErrUnknownResource, resource, Resources())
+		}
+		t.disabled[resource] = true
+	}
+	return t, nil
+}
+
+// Resources lists the optional watches in name order
+func Resources() []string {
+	var resources []string
+	for resource, store := range stores {
+		if store.optional {
+			resources = append(resources, resource)
+		}
+	}
+	sort.Strings(resources)
+	return resources
+}
+
+// IfEnabled runs list unless resource is disabled. Resources that are always watched are enabled.
+// A concurrent Set waits for list to return, so a list cannot restart an informer as it is removed.
+func (t *Toggles) IfEnabled(resource string, list func() error) error {
+	t.mu.RLock()
+	defer t.mu.RUnlock()
+	if t.disabled[resource] {
+		return nil
+	}
+	return list()
+}
+
+// Set enables or disables the watch on resource. Disabling removes the informer so its reflector stops;
+// enabling starts a new one without waiting for its initial list.
+func (t *Toggles) Set(ctx context.Context, resource string, enabled bool) error {
+	store := stores[resource]
+	if !store.optional {
+		return fmt.Errorf("%w %q, expected one of %v", ErrUnknownResource, resource, Resources())
+	}
+
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	if !enabled {
+		t.disabled[resource] = true
+		return t.cache.RemoveInformer(ctx, t.object(resource))
+	}
+	delete(t.disabled, resource)
+	_, err := t.cache.GetInformer(ctx, t.object(resource), cache.BlockUntilSynced(false))
+	return err
+}
+
+// Status reports every optional watch and whether it is enabled
+func (t *Toggles) Status() []types.WatchStatus {
+	t.mu.RLock()
+	defer t.mu.RUnlock()
+
+	resources := Resources()
+	statuses := make([]types.WatchStatus, 0, len(resources))
+	for _, resource := range resources {
+		statuses = append(statuses, types.WatchStatus{Resource: resource, Enabled: !t.disabled[resource]})
+	}
+	return statuses
+}
+
+// object returns an empty object of resource, in its negotiated version for Gateway API resources
+func (t *Toggles) object(resource string) client.Object {
+	if stores[resource].negotiated {
+		return t.versions.Object(resource)
+	}
+	return stores[resource].object()
+}
+
+// list returns an empty list of resource, in its negotiated version for Gateway API resources
+func (t *Toggles) list(resource string) client.ObjectList {
+	if stores[resource].negotiated {
+		return t.versions.List(resource)
+	}
+	return stores[resource].list()
+}
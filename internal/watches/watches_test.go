@@ -0,0 +1,366 @@
+package watches_test
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"reflect"
+	"testing"
+
+	corev1 "k8s.io/api/core/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"sigs.k8s.io/controller-runtime/pkg/cache"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	"sigs.k8s.io/controller-runtime/pkg/client/fake"
+
+	"github.com/kdwils/constellation/internal/types"
+	"github.com/kdwils/constellation/internal/versions"
+	"github.com/kdwils/constellation/internal/watches"
+)
+
+type fakeInformer struct {
+	cache.Informer
+	synced bool
+}
+
+func (f fakeInformer) HasSynced() bool                 { return f.synced }
+func (f fakeInformer) IsStopped() bool                 { return false }
+func (f fakeInformer) LastSyncResourceVersion() string { return "100" }
+
+type fakeInformers struct {
+	client.Reader
+	informer cache.Informer
+	started  []string
+	removed  []string
+}
+
+func (f *fakeInformers) GetInformer(_ context.Context, obj client.Object, _ ...cache.InformerGetOption) (cache.Informer, error) {
+	f.started = append(f.started, typeName(obj))
+	return f.informer, nil
+}
+
+func (f *fakeInformers) RemoveInformer(_ context.Context, obj client.Object) error {
+	f.removed = append(f.removed, typeName(obj))
+	return nil
+}
+
+func typeName(obj client.Object) string {
+	return reflect.TypeOf(obj).Elem().Name()
+}
+
+func TestToggles_Set(t *testing.T) {
+	tests := []struct {
+		name        string
+		disabled    []string
+		resource    string
+		enabled     bool
+		wantErr     error
+		wantStarted []string
+		wantRemoved []string
+		wantListed  bool
+	}{
+		{
+			name:        "disabling removes the informer",
+			resource:    "httproutes",
+			wantRemoved: []string{"HTTPRoute"},
+		},
+		{
+			name:        "enabling starts the informer",
+			disabled:    []string{"nodes"},
+			resource:    "nodes",
+			enabled:     true,
+			wantStarted: []string{"Node"},
+			wantListed:  true,
+		},
+		{
+			name:       "always watched resources cannot be toggled",
+			resource:   "pods",
+			wantErr:    watches.ErrUnknownResource,
+			wantListed: true,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			informers := &fakeInformers{}
+			toggles, err := watches.NewToggles(informers, versions.Versions{}, tt.disabled...)
+			if err != nil {
+				t.Fatalf("TestToggles_Set() NewToggles error = %v", err)
+			}
+
+			err = toggles.Set(context.Background(), tt.resource, tt.enabled)
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("TestToggles_Set() error = %v, want %v", err, tt.wantErr)
+			}
+			if !reflect.DeepEqual(informers.started, tt.wantStarted) {
+				t.Errorf("TestToggles_Set() started = %v, want %v", informers.started, tt.wantStarted)
+			}
+			if !reflect.DeepEqual(informers.removed, tt.wantRemoved) {
+				t.Errorf("TestToggles_Set() removed = %v, want %v", informers.removed, tt.wantRemoved)
+			}
+
+			listed := false
+			if err := toggles.IfEnabled(tt.resource, func() error {
+				listed = true
+				return nil
+			}); err != nil {
+				t.Fatalf("TestToggles_Set() IfEnabled error = %v", err)
+			}
+			if listed != tt.wantListed {
+				t.Errorf("TestToggles_Set() listed = %v, want %v", listed, tt.wantListed)
+			}
+		})
+	}
+}
+
+func TestToggles_Status(t *testing.T) {
+	tests := []struct {
+		name     string
+		disabled []string
+		want     []types.WatchStatus
+		wantErr  error
+	}{
+		{
+			name:     "disabled resources are reported",
+			disabled: []string{"gateways", "httproutes"},
+			want: []types.WatchStatus{
+				{Resource: "gatewayclasses", Enabled: true},
+				{Resource: "gateways"},
+				{Resource: "httproutes"},
+				{Resource: "networkpolicies", Enabled: true},
+				{Resource: "nodes", Enabled: true},
+				{Resource: "priorityclasses", Enabled: true},
+			},
+		},
+		{
+			name:     "unknown resource is rejected",
+			disabled: []string{"deployments"},
+			wantErr:  watches.ErrUnknownResource,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			toggles, err := watches.NewToggles(&fakeInformers{}, versions.Versions{}, tt.disabled...)
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("TestToggles_Status() error = %v, want %v", err, tt.wantErr)
+			}
+			if err != nil {
+				return
+			}
+			if got := toggles.Status(); !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestToggles_Status() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestToggles_Inspect(t *testing.T) {
+	pod := &corev1.Pod{ObjectMeta: metav1.ObjectMeta{Name: "web-0", Namespace: "default", ResourceVersion: "42"}}
+
+	tests := []struct {
+		name     string
+		disabled []string
+		resource string
+		synced   bool
+		objects  bool
+		want     types.StoreStatus
+		wantErr  error
+	}{
+		{
+			name:     "synced store is counted",
+			resource: "pods",
+			synced:   true,
+			want:     types.StoreStatus{Resource: "pods", Enabled: true, Synced: true, LastSyncResourceVersion: "100", Count: 1},
+		},
+		{
+			name:     "objects add resource versions",
+			resource: "pods",
+			synced:   true,
+			objects:  true,
+			want: types.StoreStatus{
+				Resource:                "pods",
+				Enabled:                 true,
+				Synced:                  true,
+				LastSyncResourceVersion: "100",
+				Count:                   1,
+				ResourceVersions:        map[string]string{"default/web-0": "42"},
+			},
+		},
+		{
+			name:     "unsynced store is not listed",
+			resource: "pods",
+			want:     types.StoreStatus{Resource: "pods", Enabled: true, LastSyncResourceVersion: "100"},
+		},
+		{
+			name:     "disabled store is not started",
+			disabled: []string{"nodes"},
+			resource: "nodes",
+			want:     types.StoreStatus{Resource: "nodes"},
+		},
+		{
+			name:     "unknown store",
+			resource: "deployments",
+			wantErr:  watches.ErrUnknownResource,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			informers := &fakeInformers{
+				Reader:   fake.NewClientBuilder().WithObjects(pod).Build(),
+				informer: fakeInformer{synced: tt.synced},
+			}
+			toggles, err := watches.NewToggles(informers, versions.Versions{}, tt.disabled...)
+			if err != nil {
+				t.Fatalf("TestToggles_Inspect() NewToggles error = %v", err)
+			}
+
+			got, err := toggles.Inspect(context.Background(), tt.resource, tt.objects)
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("TestToggles_Inspect() error = %v, want %v", err, tt.wantErr)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestToggles_Inspect() = %+v, want %+v", got, tt.want)
+			}
+			if tt.disabled != nil && informers.started != nil {
+				t.Errorf("TestToggles_Inspect() started %v for a disabled store", informers.started)
+			}
+		})
+	}
+}
+
+func TestToggles_Memory(t *testing.T) {
+	pods := []client.Object{
+		&corev1.Pod{ObjectMeta: metav1.ObjectMeta{Name: "web-0", Namespace: "default"}},
+		&corev1.Pod{ObjectMeta: metav1.ObjectMeta{Name: "web-1", Namespace: "default", Labels: map[string]string{"app": "web"}}},
+	}
+	reader := fake.NewClientBuilder().WithObjects(pods...).Build()
+
+	var listed corev1.PodList
+	if err := reader.List(context.Background(), &listed); err != nil {
+		t.Fatalf("TestToggles_Memory() List error = %v", err)
+	}
+	total := 0
+	for i := range listed.Items {
+		encoded, err := json.Marshal(&listed.Items[i])
+		if err != nil {
+			t.Fatalf("TestToggles_Memory() Marshal error = %v", err)
+		}
+		total += len(encoded)
+	}
+	average := total / len(listed.Items)
+
+	tests := []struct {
+		name     string
+		disabled []string
+		resource string
+		synced   bool
+		want     types.StoreMemory
+		wantErr  error
+	}{
+		{
+			name:     "synced store is estimated from its objects",
+			resource: "pods",
+			synced:   true,
+			want: types.StoreMemory{
+				Resource:       "pods",
+				Enabled:        true,
+				Synced:         true,
+				Count:          2,
+				Sampled:        2,
+				AverageBytes:   average,
+				EstimatedBytes: int64(average) * 2,
+			},
+		},
+		{
+			name:     "empty store has no estimate",
+			resource: "nodes",
+			synced:   true,
+			want:     types.StoreMemory{Resource: "nodes", Enabled: true, Synced: true},
+		},
+		{
+			name:     "unsynced store is not listed",
+			resource: "pods",
+			want:     types.StoreMemory{Resource: "pods", Enabled: true},
+		},
+		{
+			name:     "disabled store is not estimated",
+			disabled: []string{"nodes"},
+			resource: "nodes",
+			want:     types.StoreMemory{Resource: "nodes"},
+		},
+		{
+			name:     "unknown store",
+			resource: "deployments",
+			wantErr:  watches.ErrUnknownResource,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			informers := &fakeInformers{Reader: reader, informer: fakeInformer{synced: tt.synced}}
+			toggles, err := watches.NewToggles(informers, versions.Versions{}, tt.disabled...)
+			if err != nil {
+				t.Fatalf("TestToggles_Memory() NewToggles error = %v", err)
+			}
+
+			got, err := toggles.Memory(context.Background(), tt.resource)
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("TestToggles_Memory() error = %v, want %v", err, tt.wantErr)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestToggles_Memory() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestToggles_Shrink(t *testing.T) {
+	tests := []struct {
+		name        string
+		disabled    []string
+		resource    string
+		wantErr     error
+		wantStarted []string
+		wantRemoved []string
+	}{
+		{
+			name:        "optional store is dropped and listed again",
+			resource:    "nodes",
+			wantStarted: []string{"Node"},
+			wantRemoved: []string{"Node"},
+		},
+		{
+			name:     "disabled store holds nothing to drop",
+			disabled: []string{"nodes"},
+			resource: "nodes",
+		},
+		{
+			name:     "always watched store is kept",
+			resource: "pods",
+			wantErr:  watches.ErrAlwaysWatched,
+		},
+		{
+			name:     "unknown store",
+			resource: "deployments",
+			wantErr:  watches.ErrUnknownResource,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			informers := &fakeInformers{}
+			toggles, err := watches.NewToggles(informers, versions.Versions{}, tt.disabled...)
+			if err != nil {
+				t.Fatalf("TestToggles_Shrink() NewToggles error = %v", err)
+			}
+
+			err = toggles.Shrink(context.Background(), tt.resource)
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("TestToggles_Shrink() error = %v, want %v", err, tt.wantErr)
+			}
+			if !reflect.DeepEqual(informers.started, tt.wantStarted) {
+				t.Errorf("TestToggles_Shrink() started = %v, want %v", informers.started, tt.wantStarted)
+			}
+			if !reflect.DeepEqual(informers.removed, tt.wantRemoved) {
+				t.Errorf("TestToggles_Shrink() removed = %v, want %v", informers.removed, tt.wantRemoved)
+			}
+		})
+	}
+}
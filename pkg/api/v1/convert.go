@@ -0,0 +1,206 @@
+package v1
+
+import (
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// Structs whose fields are all builtin or shared types convert directly, so a change to the internal
+// type's fields fails to compile here instead of silently changing the wire schema.
+
+func fromContainerPort(p types.ContainerPortInfo) ContainerPortInfo { return ContainerPortInfo(p) }
+func toContainerPort(p ContainerPortInfo) types.ContainerPortInfo   { return types.ContainerPortInfo(p) }
+func fromTrafficMetrics(m types.TrafficMetrics) TrafficMetrics      { return TrafficMetrics(m) }
+func toTrafficMetrics(m TrafficMetrics) types.TrafficMetrics        { return types.TrafficMetrics(m) }
+func fromResourceUsage(u types.ResourceUsage) ResourceUsage         { return ResourceUsage(u) }
+func toResourceUsage(u ResourceUsage) types.ResourceUsage           { return types.ResourceUsage(u) }
+func fromDNSRecord(r types.DNSRecord) DNSRecord                     { return DNSRecord(r) }
+func toDNSRecord(r DNSRecord) types.DNSRecord                       { return types.DNSRecord(r) }
+func fromCertificate(c types.CertificateInfo) CertificateInfo       { return CertificateInfo(c) }
+func toCertificate(c CertificateInfo) types.CertificateInfo         { return types.CertificateInfo(c) }
+func fromImagePull(p types.ContainerImagePull) ContainerImagePull   { return ContainerImagePull(p) }
+func toImagePull(p ContainerImagePull) types.ContainerImagePull     { return types.ContainerImagePull(p) }
+func fromPrioritySummary(s types.PrioritySummary) PrioritySummary   { return PrioritySummary(s) }
+func toPrioritySummary(s PrioritySummary) types.PrioritySummary     { return types.PrioritySummary(s) }
+func fromDisruption(d types.Disruption) Disruption                  { return Disruption(d) }
+func toDisruption(d Disruption) types.Disruption                    { return types.Disruption(d) }
+func fromRouteMatch(m types.RouteMatch) RouteMatch                  { return RouteMatch(m) }
+func toRouteMatch(m RouteMatch) types.RouteMatch                    { return types.RouteMatch(m) }
+func fromRouteBackend(b types.RouteBackend) RouteBackend            { return RouteBackend(b) }
+func toRouteBackend(b RouteBackend) types.RouteBackend              { return types.RouteBackend(b) }
+func fromEndpointCounts(c types.EndpointCounts) EndpointCounts      { return EndpointCounts(c) }
+func toEndpointCounts(c EndpointCounts) types.EndpointCounts        { return types.EndpointCounts(c) }
+func fromTrafficPolicy(p types.TrafficPolicy) TrafficPolicy         { return TrafficPolicy(p) }
+func toTrafficPolicy(p TrafficPolicy) types.TrafficPolicy           { return types.TrafficPolicy(p) }
+func fromResourceCounts(c types.ResourceCounts) ResourceCounts      { return ResourceCounts(c) }
+func toResourceCounts(c ResourceCounts) types.ResourceCounts        { return types.ResourceCounts(c) }
+func fromRollout(r types.Rollout) Rollout                           { return Rollout(r) }
+func toRollout(r Rollout) types.Rollout                             { return types.Rollout(r) }
+func fromMarker(m types.Marker) Marker                              { return Marker(m) }
+func toMarker(m Marker) types.Marker                                { return types.Marker(m) }
+func fromSecurityFlag(f types.SecurityFlag) SecurityFlag            { return SecurityFlag(f) }
+func toSecurityFlag(f SecurityFlag) types.SecurityFlag              { return types.SecurityFlag(f) }
+
+func fromHealthCheck(e types.HealthCheckEntry) HealthCheckEntry {
+	return HealthCheckEntry{
+		Timestamp:    e.Timestamp,
+		Status:       HealthStatus(e.Status),
+		Latency:      e.Latency,
+		Error:        e.Error,
+		URL:          e.URL,
+		Method:       e.Method,
+		ResponseCode: e.ResponseCode,
+	}
+}
+
+func toHealthCheck(e HealthCheckEntry) types.HealthCheckEntry {
+	return types.HealthCheckEntry{
+		Timestamp:    e.Timestamp,
+		Status:       types.HealthStatus(e.Status),
+		Latency:      e.Latency,
+		Error:        e.Error,
+		URL:          e.URL,
+		Method:       e.Method,
+		ResponseCode: e.ResponseCode,
+	}
+}
+
+func fromHealth(h types.ServiceHealthInfo) ServiceHealthInfo {
+	return ServiceHealthInfo{
+		ServiceName: h.ServiceName,
+		Namespace:   h.Namespace,
+		LastCheck:   h.LastCheck,
+		Status:      HealthStatus(h.Status),
+		Uptime:      h.Uptime,
+		History:     convertAll(h.History, fromHealthCheck),
+		URL:         h.URL,
+	}
+}
+
+func toHealth(h ServiceHealthInfo) types.ServiceHealthInfo {
+	return types.ServiceHealthInfo{
+		ServiceName: h.ServiceName,
+		Namespace:   h.Namespace,
+		LastCheck:   h.LastCheck,
+		Status:      types.HealthStatus(h.Status),
+		Uptime:      h.Uptime,
+		History:     convertAll(h.History, toHealthCheck),
+		URL:         h.URL,
+	}
+}
+
+func fromProbe(p types.ProbeInfo) ProbeInfo {
+	return ProbeInfo{
+		Container:           p.Container,
+		Type:                ProbeType(p.Type),
+		Handler:             p.Handler,
+		Path:                p.Path,
+		Port:                p.Port,
+		Command:             p.Command,
+		InitialDelaySeconds: p.InitialDelaySeconds,
+		PeriodSeconds:       p.PeriodSeconds,
+		TimeoutSeconds:      p.TimeoutSeconds,
+		SuccessThreshold:    p.SuccessThreshold,
+		FailureThreshold:    p.FailureThreshold,
+	}
+}
+
+func toProbe(p ProbeInfo) types.ProbeInfo {
+	return types.ProbeInfo{
+		Container:           p.Container,
+		Type:                types.ProbeType(p.Type),
+		Handler:             p.Handler,
+		Path:                p.Path,
+		Port:                p.Port,
+		Command:             p.Command,
+		InitialDelaySeconds: p.InitialDelaySeconds,
+		PeriodSeconds:       p.PeriodSeconds,
+		TimeoutSeconds:      p.TimeoutSeconds,
+		SuccessThreshold:    p.SuccessThreshold,
+		FailureThreshold:    p.FailureThreshold,
+	}
+}
+
+func fromWarning(w types.Warning) Warning {
+	return Warning{Type: WarningType(w.Type), Message: w.Message}
+}
+
+func toWarning(w Warning) types.Warning {
+	return types.Warning{Type: types.WarningType(w.Type), Message: w.Message}
+}
+
+func fromRouteRule(r types.RouteRule) RouteRule {
+	return RouteRule{
+		Matches:  convertAll(r.Matches, fromRouteMatch),
+		Backends: convertAll(r.Backends, fromRouteBackend),
+	}
+}
+
+func toRouteRule(r RouteRule) types.RouteRule {
+	return types.RouteRule{
+		Matches:  convertAll(r.Matches, toRouteMatch),
+		Backends: convertAll(r.Backends, toRouteBackend),
+	}
+}
+
+func fromReplicaDrift(d types.ReplicaDrift) ReplicaDrift {
+	return ReplicaDrift{
+		Kind:    ResourceKind(d.Kind),
+		Name:    d.Name,
+		Desired: d.Desired,
+		Ready:   d.Ready,
+		Since:   d.Since,
+	}
+}
+
+func toReplicaDrift(d ReplicaDrift) types.ReplicaDrift {
+	return types.ReplicaDrift{
+		Kind:    types.ResourceKind(d.Kind),
+		Name:    d.Name,
+		Desired: d.Desired,
+		Ready:   d.Ready,
+		Since:   d.Since,
+	}
+}
+
+func fromSecuritySummary(summary map[types.SecurityFlag]int) map[SecurityFlag]int {
+	if summary == nil {
+		return nil
+	}
+	result := make(map[SecurityFlag]int, len(summary))
+	for flag, count := range summary {
+		result[SecurityFlag(flag)] = count
+	}
+	return result
+}
+
+func toSecuritySummary(summary map[SecurityFlag]int) map[types.SecurityFlag]int {
+	if summary == nil {
+		return nil
+	}
+	result := make(map[types.SecurityFlag]int, len(summary))
+	for flag, count := range summary {
+		result[types.SecurityFlag(flag)] = count
+	}
+	return result
+}
+
+// convertAll converts every item, keeping nil slices nil so omitempty fields stay omitted
+func convertAll[S, D any](items []S, convert func(S) D) []D {
+	if items == nil {
+		return nil
+	}
+	result := make([]D, 0, len(items))
+	for _, item := range items {
+		result = append(result, convert(item))
+	}
+	return result
+}
+
+// convertPtr converts the value behind item, or returns nil when it is nil
+func convertPtr[S, D any](item *S, convert func(S) D) *D {
+	if item == nil {
+		return nil
+	}
+	converted := convert(*item)
+	return &converted
+}
@@ -0,0 +1,245 @@
+// Package v1 is the stable wire schema served under /api/v1. It is kept apart from the internal types so
+// those can change shape without breaking API clients; a field is only added here, never renamed or removed.
+package v1
+
+import (
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+
+	"github.com/kdwils/constellation/internal/types"
+)
+
+// Node is a resource in the hierarchy with its child resources
+type Node struct {
+	Kind                ResourceKind         `json:"kind"`
+	Name                string               `json:"name"`
+	Namespace           *string              `json:"namespace,omitempty"`
+	Relatives           []Node               `json:"relatives,omitempty"`
+	Hostnames           []string             `json:"hostnames,omitempty"`
+	Selectors           map[string]string    `json:"selectors,omitempty"`
+	Ports               []int32              `json:"ports,omitempty"`
+	PortMappings        []string             `json:"port_mappings,omitempty"`
+	TargetPorts         []int32              `json:"target_ports,omitempty"`
+	TargetPortNames     []string             `json:"target_port_names,omitempty"`
+	ContainerPorts      []ContainerPortInfo  `json:"container_ports,omitempty"`
+	Labels              map[string]string    `json:"labels,omitempty"`
+	Phase               *string              `json:"phase,omitempty"`
+	BackendRefs         []string             `json:"backend_refs,omitempty"`
+	ServiceType         *string              `json:"service_type,omitempty"`
+	ClusterIPs          []string             `json:"cluster_ips,omitempty"`
+	ExternalIPs         []string             `json:"external_ips,omitempty"`
+	PodIPs              []string             `json:"pod_ips,omitempty"`
+	Group               string               `json:"group,omitempty"`
+	DisplayName         string               `json:"display_name,omitempty"`
+	Ignore              bool                 `json:"ignore,omitempty"`
+	HealthInfo          *ServiceHealthInfo   `json:"health_info,omitempty"`
+	Traffic             *TrafficMetrics      `json:"traffic,omitempty"`
+	Usage               *ResourceUsage       `json:"usage,omitempty"`
+	RestartAnomaly      bool                 `json:"restart_anomaly,omitempty"`
+	DNSNames            []string             `json:"dns_names,omitempty"`
+	DNSRecords          []DNSRecord          `json:"dns_records,omitempty"`
+	HostnameConflicts   []string             `json:"hostname_conflicts,omitempty"`
+	Certificates        []CertificateInfo    `json:"certificates,omitempty"`
+	ImagePulls          []ContainerImagePull `json:"image_pulls,omitempty"`
+	ImagePullSecrets    bool                 `json:"image_pull_secrets,omitempty"`
+	Sidecars            []string             `json:"sidecars,omitempty"`
+	Meshed              bool                 `json:"meshed,omitempty"`
+	PriorityClassName   string               `json:"priority_class_name,omitempty"`
+	Priority            *int32               `json:"priority,omitempty"`
+	Preemptible         bool                 `json:"preemptible,omitempty"`
+	PrioritySummary     *PrioritySummary     `json:"priority_summary,omitempty"`
+	Disruptions         []Disruption         `json:"disruptions,omitempty"`
+	UnschedulableReason string               `json:"unschedulable_reason,omitempty"`
+	Probes              []ProbeInfo          `json:"probes,omitempty"`
+	SecurityFlags       []SecurityFlag       `json:"security_flags,omitempty"`
+	SecuritySummary     map[SecurityFlag]int `json:"security_summary,omitempty"`
+	Warnings            []Warning            `json:"warnings,omitempty"`
+	Rules               []RouteRule          `json:"rules,omitempty"`
+	Weight              *int32               `json:"weight,omitempty"`
+	TrafficPercent      *float64             `json:"traffic_percent,omitempty"`
+	Controllers         []string             `json:"controllers,omitempty"`
+	Endpoints           *EndpointCounts      `json:"endpoints,omitempty"`
+	TrafficPolicy       *TrafficPolicy       `json:"traffic_policy,omitempty"`
+	IPFamilies          []string             `json:"ip_families,omitempty"`
+	IPFamilyPolicy      *string              `json:"ip_family_policy,omitempty"`
+	Addresses           map[string][]string  `json:"addresses,omitempty"`
+	OS                  string               `json:"os,omitempty"`
+	Arch                string               `json:"arch,omitempty"`
+	Annotations         map[string]string    `json:"annotations,omitempty"`
+	Counts              *ResourceCounts      `json:"counts,omitempty"`
+	Owner               string               `json:"owner,omitempty"`
+	NodeName            string               `json:"node_name,omitempty"`
+	Gateways            []string             `json:"gateways,omitempty"`
+	Count               int                  `json:"count,omitempty"`
+	Terminating         bool                 `json:"terminating,omitempty"`
+	TerminationDeadline *metav1.Time         `json:"termination_deadline,omitempty"`
+	GracePeriodSeconds  *int64               `json:"grace_period_seconds,omitempty"`
+	Rollout             *Rollout             `json:"rollout,omitempty"`
+	Track               string               `json:"track,omitempty"`
+	ReplicaDrift        []ReplicaDrift       `json:"replica_drift,omitempty"`
+	Markers             []Marker             `json:"markers,omitempty"`
+	Badges              []string             `json:"badges,omitempty"`
+	ID                  string               `json:"id"`
+	ParentID            string               `json:"parent_id,omitempty"`
+}
+
+// FromNodes converts hierarchy nodes and their relatives to their wire form
+func FromNodes(nodes []types.HierarchyNode) []Node {
+	return convertAll(nodes, FromNode)
+}
+
+// FromNode converts a hierarchy node and its relatives to its wire form
+func FromNode(node types.HierarchyNode) Node {
+	return Node{
+		Kind:                ResourceKind(node.Kind),
+		Name:                node.Name,
+		Namespace:           node.Namespace,
+		Relatives:           FromNodes(node.Relatives),
+		Hostnames:           node.Hostnames,
+		Selectors:           node.Selectors,
+		Ports:               node.Ports,
+		PortMappings:        node.PortMappings,
+		TargetPorts:         node.TargetPorts,
+		TargetPortNames:     node.TargetPortNames,
+		ContainerPorts:      convertAll(node.ContainerPorts, fromContainerPort),
+		Labels:              node.Labels,
+		Phase:               node.Phase,
+		BackendRefs:         node.BackendRefs,
+		ServiceType:         node.ServiceType,
+		ClusterIPs:          node.ClusterIPs,
+		ExternalIPs:         node.ExternalIPs,
+		PodIPs:              node.PodIPs,
+		Group:               node.Group,
+		DisplayName:         node.DisplayName,
+		Ignore:              node.Ignore,
+		HealthInfo:          convertPtr(node.HealthInfo, fromHealth),
+		Traffic:             convertPtr(node.Traffic, fromTrafficMetrics),
+		Usage:               convertPtr(node.Usage, fromResourceUsage),
+		RestartAnomaly:      node.RestartAnomaly,
+		DNSNames:            node.DNSNames,
+		DNSRecords:          convertAll(node.DNSRecords, fromDNSRecord),
+		HostnameConflicts:   node.HostnameConflicts,
+		Certificates:        convertAll(node.Certificates, fromCertificate),
+		ImagePulls:          convertAll(node.ImagePulls, fromImagePull),
+		ImagePullSecrets:    node.ImagePullSecrets,
+		Sidecars:            node.Sidecars,
+		Meshed:              node.Meshed,
+		PriorityClassName:   node.PriorityClassName,
+		Priority:            node.Priority,
+		Preemptible:         node.Preemptible,
+		PrioritySummary:     convertPtr(node.PrioritySummary, fromPrioritySummary),
+		Disruptions:         convertAll(node.Disruptions, fromDisruption),
+		UnschedulableReason: node.UnschedulableReason,
+		Probes:              convertAll(node.Probes, fromProbe),
+		SecurityFlags:       convertAll(node.SecurityFlags, fromSecurityFlag),
+		SecuritySummary:     fromSecuritySummary(node.SecuritySummary),
+		Warnings:            convertAll(node.Warnings, fromWarning),
+		Rules:               convertAll(node.Rules, fromRouteRule),
+		Weight:              node.Weight,
+		TrafficPercent:      node.TrafficPercent,
+		Controllers:         node.Controllers,
+		Endpoints:           convertPtr(node.Endpoints, fromEndpointCounts),
+		TrafficPolicy:       convertPtr(node.TrafficPolicy, fromTrafficPolicy),
+		IPFamilies:          node.IPFamilies,
+		IPFamilyPolicy:      node.IPFamilyPolicy,
+		Addresses:           node.Addresses,
+		OS:                  node.OS,
+		Arch:                node.Arch,
+		Annotations:         node.Annotations,
+		Counts:              convertPtr(node.Counts, fromResourceCounts),
+		Owner:               node.Owner,
+		NodeName:            node.NodeName,
+		Gateways:            node.Gateways,
+		Count:               node.Count,
+		Terminating:         node.Terminating,
+		TerminationDeadline: node.TerminationDeadline,
+		GracePeriodSeconds:  node.GracePeriodSeconds,
+		Rollout:             convertPtr(node.Rollout, fromRollout),
+		Track:               node.Track,
+		ReplicaDrift:        convertAll(node.ReplicaDrift, fromReplicaDrift),
+		Markers:             convertAll(node.Markers, fromMarker),
+		Badges:              node.Badges,
+		ID:                  node.ID,
+		ParentID:            node.ParentID,
+	}
+}
+
+// ToNodes converts nodes read from the API and their relatives back to hierarchy nodes
+func ToNodes(nodes []Node) []types.HierarchyNode {
+	return convertAll(nodes, ToNode)
+}
+
+// ToNode converts a node read from the API and its relatives back to a hierarchy node
+func ToNode(node Node) types.HierarchyNode {
+	return types.HierarchyNode{
+		Kind:                types.ResourceKind(node.Kind),
+		Name:                node.Name,
+		Namespace:           node.Namespace,
+		Relatives:           ToNodes(node.Relatives),
+		Hostnames:           node.Hostnames,
+		Selectors:           node.Selectors,
+		Ports:               node.Ports,
+		PortMappings:        node.PortMappings,
+		TargetPorts:         node.TargetPorts,
+		TargetPortNames:     node.TargetPortNames,
+		ContainerPorts:      convertAll(node.ContainerPorts, toContainerPort),
+		Labels:              node.Labels,
+		Phase:               node.Phase,
+		BackendRefs:         node.BackendRefs,
+		ServiceType:         node.ServiceType,
+		ClusterIPs:          node.ClusterIPs,
+		ExternalIPs:         node.ExternalIPs,
+		PodIPs:              node.PodIPs,
+		Group:               node.Group,
+		DisplayName:         node.DisplayName,
+		Ignore:              node.Ignore,
+		HealthInfo:          convertPtr(node.HealthInfo, toHealth),
+		Traffic:             convertPtr(node.Traffic, toTrafficMetrics),
+		Usage:               convertPtr(node.Usage, toResourceUsage),
+		RestartAnomaly:      node.RestartAnomaly,
+		DNSNames:            node.DNSNames,
+		DNSRecords:          convertAll(node.DNSRecords, toDNSRecord),
+		HostnameConflicts:   node.HostnameConflicts,
+		Certificates:        convertAll(node.Certificates, toCertificate),
+		ImagePulls:          convertAll(node.ImagePulls, toImagePull),
+		ImagePullSecrets:    node.ImagePullSecrets,
+		Sidecars:            node.Sidecars,
+		Meshed:              node.Meshed,
+		PriorityClassName:   node.PriorityClassName,
+		Priority:            node.Priority,
+		Preemptible:         node.Preemptible,
+		PrioritySummary:     convertPtr(node.PrioritySummary, toPrioritySummary),
+		Disruptions:         convertAll(node.Disruptions, toDisruption),
+		UnschedulableReason: node.UnschedulableReason,
+		Probes:              convertAll(node.Probes, toProbe),
+		SecurityFlags:       convertAll(node.SecurityFlags, toSecurityFlag),
+		SecuritySummary:     toSecuritySummary(node.SecuritySummary),
+		Warnings:            convertAll(node.Warnings, toWarning),
+		Rules:               convertAll(node.Rules, toRouteRule),
+		Weight:              node.Weight,
+		TrafficPercent:      node.TrafficPercent,
+		Controllers:         node.Controllers,
+		Endpoints:           convertPtr(node.Endpoints, toEndpointCounts),
+		TrafficPolicy:       convertPtr(node.TrafficPolicy, toTrafficPolicy),
+		IPFamilies:          node.IPFamilies,
+		IPFamilyPolicy:      node.IPFamilyPolicy,
+		Addresses:           node.Addresses,
+		OS:                  node.OS,
+		Arch:                node.Arch,
+		Annotations:         node.Annotations,
+		Counts:              convertPtr(node.Counts, toResourceCounts),
+		Owner:               node.Owner,
+		NodeName:            node.NodeName,
+		Gateways:            node.Gateways,
+		Count:               node.Count,
+		Terminating:         node.Terminating,
+		TerminationDeadline: node.TerminationDeadline,
+		GracePeriodSeconds:  node.GracePeriodSeconds,
+		Rollout:             convertPtr(node.Rollout, toRollout),
+		Track:               node.Track,
+		ReplicaDrift:        convertAll(node.ReplicaDrift, toReplicaDrift),
+		Markers:             convertAll(node.Markers, toMarker),
+		Badges:              node.Badges,
+		ID:                  node.ID,
+		ParentID:            node.ParentID,
+	}
+}
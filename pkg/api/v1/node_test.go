@@ -0,0 +1,203 @@
+package v1_test
+
+import (
+	"encoding/json"
+	"reflect"
+	"testing"
+	"time"
+
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+
+	"github.com/kdwils/constellation/internal/types"
+	apiv1 "github.com/kdwils/constellation/pkg/api/v1"
+)
+
+func ptr[T any](v T) *T { return &v }
+
+// fullNode sets every field of a HierarchyNode, so a field FromNode or ToNode drops fails the round trip
+func fullNode() types.HierarchyNode {
+	at := time.Date(2025, 1, 1, 0, 0, 0, 0, time.UTC)
+	return types.HierarchyNode{
+		Kind:      types.ResourceKindPod,
+		Name:      "web-0",
+		Namespace: ptr("shop"),
+		Relatives: []types.HierarchyNode{{
+			Kind: types.ResourceKindService,
+			Name: "web",
+			ID:   "Pod/shop/web-0|Service/shop/web",
+		}},
+		Hostnames:       []string{"shop.example.com"},
+		Selectors:       map[string]string{"app": "web"},
+		Ports:           []int32{80},
+		PortMappings:    []string{"80:8080"},
+		TargetPorts:     []int32{8080},
+		TargetPortNames: []string{"http"},
+		ContainerPorts:  []types.ContainerPortInfo{{Port: 8080, Name: ptr("http"), Protocol: ptr("TCP")}},
+		Labels:          map[string]string{"app": "web"},
+		Phase:           ptr("Running"),
+		BackendRefs:     []string{"web"},
+		ServiceType:     ptr("ClusterIP"),
+		ClusterIPs:      []string{"10.0.0.1"},
+		ExternalIPs:     []string{"203.0.113.1"},
+		PodIPs:          []string{"10.1.0.1"},
+		Group:           "frontend",
+		DisplayName:     "Web",
+		Ignore:          true,
+		HealthInfo: &types.ServiceHealthInfo{
+			ServiceName: "web",
+			Namespace:   "shop",
+			LastCheck:   at,
+			Status:      types.HealthStatusHealthy,
+			Uptime:      99.5,
+			History: []types.HealthCheckEntry{{
+				Timestamp:    at,
+				Status:       types.HealthStatusUnhealthy,
+				Latency:      time.Second,
+				Error:        "timeout",
+				URL:          "http://web",
+				Method:       "GET",
+				ResponseCode: 503,
+			}},
+			URL: "http://web",
+		},
+		Traffic:           &types.TrafficMetrics{RequestRate: ptr(1.5), ErrorRate: ptr(0.1), LatencySeconds: ptr(0.2)},
+		Usage:             &types.ResourceUsage{CPUMillicores: 250, MemoryBytes: 1 << 20, Timestamp: at},
+		RestartAnomaly:    true,
+		DNSNames:          []string{"web.shop.svc"},
+		DNSRecords:        []types.DNSRecord{{Hostname: "shop.example.com", Type: "A", Targets: []string{"1.2.3.4"}}},
+		HostnameConflicts: []string{"shop.example.com"},
+		Certificates: []types.CertificateInfo{{
+			Hostname:     "shop.example.com",
+			Secret:       "shop-tls",
+			Issuer:       "letsencrypt",
+			NotAfter:     at,
+			DNSNames:     []string{"shop.example.com"},
+			Covered:      true,
+			ExpiringSoon: true,
+		}},
+		ImagePulls: []types.ContainerImagePull{{
+			Container:  "web",
+			Image:      "nginx",
+			PullPolicy: "Always",
+			Reason:     "ErrImagePull",
+			Message:    "not found",
+		}},
+		ImagePullSecrets:  true,
+		Sidecars:          []string{"istio-proxy"},
+		Meshed:            true,
+		PriorityClassName: "high",
+		Priority:          ptr(int32(1000)),
+		Preemptible:       true,
+		PrioritySummary:   &types.PrioritySummary{Classes: map[string]int{"high": 1}, Preemptible: 1, Lowest: ptr(int32(0))},
+		Disruptions: []types.Disruption{{
+			Reason:    "OOMKilled",
+			Pod:       "web-0",
+			Container: "web",
+			Message:   "out of memory",
+			Time:      at,
+		}},
+		UnschedulableReason: "Insufficient cpu",
+		Probes: []types.ProbeInfo{{
+			Container:           "web",
+			Type:                types.ProbeTypeReadiness,
+			Handler:             "httpGet",
+			Path:                "/healthz",
+			Port:                "8080",
+			Command:             []string{"true"},
+			InitialDelaySeconds: 1,
+			PeriodSeconds:       10,
+			TimeoutSeconds:      1,
+			SuccessThreshold:    1,
+			FailureThreshold:    3,
+		}},
+		SecurityFlags:   []types.SecurityFlag{types.SecurityFlagPrivileged},
+		SecuritySummary: map[types.SecurityFlag]int{types.SecurityFlagRunAsRoot: 2},
+		Warnings:        []types.Warning{{Type: types.WarningTypePortMismatch, Message: "port 80 is not exposed"}},
+		Rules: []types.RouteRule{{
+			Matches: []types.RouteMatch{{
+				PathType:    "PathPrefix",
+				Path:        "/",
+				Method:      "GET",
+				Headers:     []string{"x-canary=true"},
+				QueryParams: []string{"debug=1"},
+			}},
+			Backends: []types.RouteBackend{{Name: "web", Namespace: "shop", Port: ptr(int32(80)), Weight: 1}},
+		}},
+		Weight:         ptr(int32(90)),
+		TrafficPercent: ptr(90.0),
+		Controllers:    []string{"ReplicaSet/web"},
+		Endpoints:      &types.EndpointCounts{Ready: 2, NotReady: 1},
+		TrafficPolicy: &types.TrafficPolicy{
+			SessionAffinity:               "ClientIP",
+			SessionAffinityTimeoutSeconds: ptr(int32(60)),
+			InternalTrafficPolicy:         "Local",
+			ExternalTrafficPolicy:         "Local",
+			TrafficDistribution:           "PreferClose",
+			TopologyMode:                  "Auto",
+		},
+		IPFamilies:          []string{"IPv4"},
+		IPFamilyPolicy:      ptr("SingleStack"),
+		Addresses:           map[string][]string{"InternalIP": {"10.0.0.2"}},
+		OS:                  "linux",
+		Arch:                "amd64",
+		Annotations:         map[string]string{"team": "shop"},
+		Counts:              &types.ResourceCounts{Pods: 3, Services: 1},
+		Owner:               "shop-team",
+		NodeName:            "node-a",
+		Gateways:            []string{"shop/public"},
+		Count:               2,
+		Terminating:         true,
+		TerminationDeadline: &metav1.Time{Time: at},
+		GracePeriodSeconds:  ptr(int64(30)),
+		Rollout:             &types.Rollout{Deployment: "web", Revision: 3, ChangeCause: "bump", StartedAt: at},
+		Track:               "canary",
+		ReplicaDrift: []types.ReplicaDrift{{
+			Kind:    types.ResourceKindDeployment,
+			Name:    "web",
+			Desired: 3,
+			Ready:   2,
+			Since:   at,
+		}},
+		Markers: []types.Marker{{
+			ID:     "deploy-1",
+			Source: "ci",
+			Type:   "deploy",
+			Data:   json.RawMessage(`{"sha":"abc"}`),
+			Time:   at,
+		}},
+		Badges:   []string{"new"},
+		ID:       "Pod/shop/web-0",
+		ParentID: "Namespace/shop",
+	}
+}
+
+func TestFromNode(t *testing.T) {
+	tests := []struct {
+		name string
+		node types.HierarchyNode
+	}{
+		{name: "every field", node: fullNode()},
+		{name: "empty node", node: types.HierarchyNode{}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			wire := apiv1.FromNode(tt.node)
+
+			got, err := json.Marshal(wire)
+			if err != nil {
+				t.Fatalf("TestFromNode() marshal error = %v", err)
+			}
+			want, err := json.Marshal(tt.node)
+			if err != nil {
+				t.Fatalf("TestFromNode() marshal error = %v", err)
+			}
+			if string(got) != string(want) {
+				t.Errorf("TestFromNode() encodes %s, want %s", got, want)
+			}
+
+			if back := apiv1.ToNode(wire); !reflect.DeepEqual(back, tt.node) {
+				t.Errorf("TestFromNode() round trip = %+v, want %+v", back, tt.node)
+			}
+		})
+	}
+}
@@ -0,0 +1,201 @@
+package v1
+
+import (
+	"encoding/json"
+	"time"
+)
+
+// ResourceKind is the Kubernetes or constellation kind of a node, e.g. Service or Hostname
+type ResourceKind string
+
+// ContainerPortInfo is a port a pod's container listens on
+type ContainerPortInfo struct {
+	Port     int32   `json:"port"`
+	Name     *string `json:"name,omitempty"`
+	Protocol *string `json:"protocol,omitempty"`
+}
+
+// HealthStatus is the outcome of a Service's health checks: healthy, unhealthy or unknown
+type HealthStatus string
+
+// HealthCheckEntry is one health check of a Service
+type HealthCheckEntry struct {
+	Timestamp    time.Time     `json:"timestamp"`
+	Status       HealthStatus  `json:"status"`
+	Latency      time.Duration `json:"latency"`
+	Error        string        `json:"error,omitempty"`
+	URL          string        `json:"url"`
+	Method       string        `json:"method"`
+	ResponseCode int           `json:"response_code,omitempty"`
+}
+
+// ServiceHealthInfo is a Service's current health and recent check history
+type ServiceHealthInfo struct {
+	ServiceName string             `json:"service_name"`
+	Namespace   string             `json:"namespace"`
+	LastCheck   time.Time          `json:"last_check"`
+	Status      HealthStatus       `json:"status"`
+	Uptime      float64            `json:"uptime"`
+	History     []HealthCheckEntry `json:"history"`
+	URL         string             `json:"url"`
+}
+
+// TrafficMetrics are request metrics queried from Prometheus for a Service or HTTPRoute
+type TrafficMetrics struct {
+	RequestRate    *float64 `json:"request_rate,omitempty"`
+	ErrorRate      *float64 `json:"error_rate,omitempty"`
+	LatencySeconds *float64 `json:"latency_seconds,omitempty"`
+}
+
+// ResourceUsage is the live CPU and memory consumption of a pod or node as reported by metrics-server
+type ResourceUsage struct {
+	CPUMillicores int64     `json:"cpu_millicores"`
+	MemoryBytes   int64     `json:"memory_bytes"`
+	Timestamp     time.Time `json:"timestamp"`
+}
+
+// DNSRecord is an external DNS record implied by a resource's hostnames or external-dns annotations
+type DNSRecord struct {
+	Hostname string   `json:"hostname"`
+	Type     string   `json:"type,omitempty"`
+	Targets  []string `json:"targets,omitempty"`
+	Source   string   `json:"source"`
+}
+
+// CertificateInfo describes the TLS certificate a Gateway listener serves for a route hostname
+type CertificateInfo struct {
+	Hostname     string    `json:"hostname"`
+	Secret       string    `json:"secret"`
+	Issuer       string    `json:"issuer"`
+	NotAfter     time.Time `json:"not_after"`
+	DNSNames     []string  `json:"dns_names,omitempty"`
+	Covered      bool      `json:"covered"`
+	ExpiringSoon bool      `json:"expiring_soon,omitempty"`
+}
+
+// ContainerImagePull is how a container pulls its image and the kubelet's pull failure, if any
+type ContainerImagePull struct {
+	Container  string `json:"container"`
+	Image      string `json:"image"`
+	PullPolicy string `json:"pull_policy"`
+	Reason     string `json:"reason,omitempty"`
+	Message    string `json:"message,omitempty"`
+}
+
+// PrioritySummary counts a namespace's pods by priority class
+type PrioritySummary struct {
+	Classes     map[string]int `json:"classes"`
+	Preemptible int            `json:"preemptible"`
+	Lowest      *int32         `json:"lowest,omitempty"`
+}
+
+// Disruption is a pod terminated by an eviction, a scheduler preemption or the OOM killer
+type Disruption struct {
+	Reason    string    `json:"reason"`
+	Pod       string    `json:"pod"`
+	Container string    `json:"container,omitempty"`
+	Message   string    `json:"message,omitempty"`
+	Time      time.Time `json:"time"`
+}
+
+// ProbeType is the kind of a container probe: startup, liveness or readiness
+type ProbeType string
+
+// ProbeInfo is the effective configuration of one container probe, with unset timings defaulted
+type ProbeInfo struct {
+	Container           string    `json:"container"`
+	Type                ProbeType `json:"type"`
+	Handler             string    `json:"handler"`
+	Path                string    `json:"path,omitempty"`
+	Port                string    `json:"port,omitempty"`
+	Command             []string  `json:"command,omitempty"`
+	InitialDelaySeconds int32     `json:"initial_delay_seconds"`
+	PeriodSeconds       int32     `json:"period_seconds"`
+	TimeoutSeconds      int32     `json:"timeout_seconds"`
+	SuccessThreshold    int32     `json:"success_threshold"`
+	FailureThreshold    int32     `json:"failure_threshold"`
+}
+
+// SecurityFlag is a risky pod setting, e.g. privileged or run_as_root
+type SecurityFlag string
+
+// WarningType is the kind of misconfiguration a Warning reports
+type WarningType string
+
+// Warning is a likely misconfiguration found while building a node
+type Warning struct {
+	Type    WarningType `json:"type"`
+	Message string      `json:"message"`
+}
+
+// RouteRule is one HTTPRoute rule's matches and the weighted backends it forwards to
+type RouteRule struct {
+	Matches  []RouteMatch   `json:"matches,omitempty"`
+	Backends []RouteBackend `json:"backends,omitempty"`
+}
+
+// RouteMatch is one HTTPRoute match condition
+type RouteMatch struct {
+	PathType    string   `json:"path_type,omitempty"`
+	Path        string   `json:"path,omitempty"`
+	Method      string   `json:"method,omitempty"`
+	Headers     []string `json:"headers,omitempty"`
+	QueryParams []string `json:"query_params,omitempty"`
+}
+
+// RouteBackend is a backendRef with its weight
+type RouteBackend struct {
+	Name      string `json:"name"`
+	Namespace string `json:"namespace,omitempty"`
+	Port      *int32 `json:"port,omitempty"`
+	Weight    int32  `json:"weight"`
+}
+
+// EndpointCounts is the readiness of the endpoints behind a Service
+type EndpointCounts struct {
+	Ready    int `json:"ready"`
+	NotReady int `json:"not_ready"`
+}
+
+// TrafficPolicy holds the Service settings that change how traffic is spread across its endpoints
+type TrafficPolicy struct {
+	SessionAffinity               string `json:"session_affinity,omitempty"`
+	SessionAffinityTimeoutSeconds *int32 `json:"session_affinity_timeout_seconds,omitempty"`
+	InternalTrafficPolicy         string `json:"internal_traffic_policy,omitempty"`
+	ExternalTrafficPolicy         string `json:"external_traffic_policy,omitempty"`
+	TrafficDistribution           string `json:"traffic_distribution,omitempty"`
+	TopologyMode                  string `json:"topology_mode,omitempty"`
+}
+
+// ResourceCounts is the number of pods and services in a namespace
+type ResourceCounts struct {
+	Pods     int `json:"pods"`
+	Services int `json:"services"`
+}
+
+// Rollout is the Deployment revision a pod was created from
+type Rollout struct {
+	Deployment  string    `json:"deployment"`
+	Revision    int64     `json:"revision"`
+	ChangeCause string    `json:"change_cause,omitempty"`
+	StartedAt   time.Time `json:"started_at"`
+}
+
+// ReplicaDrift is a workload with fewer ready pods than the replicas it asks for, since Since
+type ReplicaDrift struct {
+	Kind    ResourceKind `json:"kind"`
+	Name    string       `json:"name"`
+	Desired int32        `json:"desired"`
+	Ready   int32        `json:"ready"`
+	Since   time.Time    `json:"since"`
+}
+
+// Marker is external context attached to a node at a point in time, such as a CI deployment or an
+// incident
+type Marker struct {
+	ID     string          `json:"id"`
+	Source string          `json:"source"`
+	Type   string          `json:"type"`
+	Data   json.RawMessage `json:"data,omitempty"`
+	Time   time.Time       `json:"time"`
+}
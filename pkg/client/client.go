@@ -0,0 +1,205 @@
+// Package client is a typed client for the constellation API, for services and tests that consume it
+// without decoding responses by hand.
+package client
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"net/url"
+	"strings"
+
+	"github.com/gorilla/websocket"
+	"github.com/kdwils/constellation/internal/types"
+	apiv1 "github.com/kdwils/constellation/pkg/api/v1"
+)
+
+// Client calls the API of a constellation server
+type Client struct {
+	baseURL    *url.URL
+	httpClient *http.Client
+	dialer     *websocket.Dialer
+}
+
+type ClientOpt func(*Client)
+
+// WithHTTPClient sends requests with httpClient instead of http.DefaultClient
+func WithHTTPClient(httpClient *http.Client) ClientOpt {
+	return func(c *Client) {
+		c.httpClient = httpClient
+	}
+}
+
+// WithDialer opens WebSocket streams with dialer instead of websocket.DefaultDialer
+func WithDialer(dialer *websocket.Dialer) ClientOpt {
+	return func(c *Client) {
+		c.dialer = dialer
+	}
+}
+
+// New creates a client for the server at baseURL, e.g. http://constellation.monitoring:8080
+func New(baseURL string, opts ...ClientOpt) (*Client, error) {
+	parsed, err := url.Parse(strings.TrimSuffix(baseURL, "/"))
+	if err != nil {
+		return nil, fmt.Errorf("invalid base url: %w", err)
+	}
+	if parsed.Scheme != "http" && parsed.Scheme != "https" {
+		return nil, fmt.Errorf("invalid base url %q: scheme must be http or https", baseURL)
+	}
+
+	c := &Client{
+		baseURL:    parsed,
+		httpClient: http.DefaultClient,
+		dialer:     websocket.DefaultDialer,
+	}
+	for _, opt := range opts {
+		opt(c)
+	}
+	return c, nil
+}
+
+// GetState returns the health of every service and the sync status of each watched kind
+func (c *Client) GetState(ctx context.Context) (types.State, error) {
+	var state types.State
+	err := c.get(ctx, "/api/v1/state", nil, &state)
+	return state, err
+}
+
+// Search returns the hierarchy cut down to the resources matching a label selector,
+// e.g. "tier in (web,api),!canary"
+func (c *Client) Search(ctx context.Context, selector string) ([]apiv1.Node, error) {
+	var nodes []apiv1.Node
+	err := c.get(ctx, "/api/v1/hierarchy", url.Values{"selector": {selector}}, &nodes)
+	return nodes, err
+}
+
+// GetSnapshot returns the hierarchy, health data and raw objects the server holds at one point in time
+func (c *Client) GetSnapshot(ctx context.Context) (types.Snapshot, error) {
+	var snapshot types.Snapshot
+	err := c.get(ctx, "/api/v1/snapshot", nil, &snapshot)
+	return snapshot, err
+}
+
+// StreamUpdates calls update with the health of every service, first on connecting and then on every
+// change, until ctx is done, the connection fails or update returns an error. Chunked messages are
+// reassembled before they are decoded.
+func (c *Client) StreamUpdates(ctx context.Context, update func([]*types.ServiceHealthInfo) error) error {
+	return c.stream(ctx, "/ws", func(payload []byte) error {
+		var services []*types.ServiceHealthInfo
+		if err := json.Unmarshal(payload, &services); err != nil {
+			return fmt.Errorf("decode update: %w", err)
+		}
+		return update(services)
+	})
+}
+
+// StreamHierarchy calls update with the whole hierarchy, first on connecting and then whenever it
+// changes, until ctx is done, the connection fails or update returns an error
+func (c *Client) StreamHierarchy(ctx context.Context, update func([]types.HierarchyNode) error) error {
+	return c.stream(ctx, "/ws/hierarchy", func(payload []byte) error {
+		var nodes []types.HierarchyNode
+		if err := json.Unmarshal(payload, &nodes); err != nil {
+			return fmt.Errorf("decode hierarchy: %w", err)
+		}
+		return update(nodes)
+	})
+}
+
+// stream reads the WebSocket at path, calling message with every whole or reassembled message
+func (c *Client) stream(ctx context.Context, path string, message func([]byte) error) error {
+	streamURL := *c.baseURL
+	streamURL.Scheme = strings.Replace(streamURL.Scheme, "http", "ws", 1)
+	streamURL.Path += path
+
+	conn, _, err := c.dialer.DialContext(ctx, streamURL.String(), nil)
+	if err != nil {
+		return fmt.Errorf("dial %s: %w", streamURL.String(), err)
+	}
+	defer conn.Close() //nolint:errcheck
+
+	stop := context.AfterFunc(ctx, func() { conn.Close() }) //nolint:errcheck
+	defer stop()
+
+	var assembler chunks
+	for {
+		_, payload, err := conn.ReadMessage()
+		if ctx.Err() != nil {
+			return ctx.Err()
+		}
+		if err != nil {
+			return err
+		}
+
+		payload, err = assembler.add(payload)
+		if err != nil {
+			return err
+		}
+		if payload == nil {
+			continue
+		}
+		if err := message(payload); err != nil {
+			return err
+		}
+	}
+}
+
+// chunks reassembles a message the server split over its size limit
+type chunks struct {
+	id   uint64
+	data [][]byte
+}
+
+// add returns payload when it is a whole message, the reassembled message when payload is its last
+// chunk, and nil while chunks are still missing
+func (c *chunks) add(payload []byte) ([]byte, error) {
+	if !bytes.HasPrefix(bytes.TrimSpace(payload), []byte("{")) {
+		return payload, nil
+	}
+
+	var message types.ChunkedMessage
+	if err := json.Unmarshal(payload, &message); err != nil {
+		return nil, fmt.Errorf("decode chunk: %w", err)
+	}
+	chunk := message.Chunk
+	if chunk.Index == 0 {
+		c.id = chunk.ID
+		c.data = make([][]byte, 0, chunk.Total)
+	}
+	if chunk.ID != c.id || chunk.Index != len(c.data) {
+		return nil, fmt.Errorf("chunk %d of message %d out of order", chunk.Index, chunk.ID)
+	}
+
+	c.data = append(c.data, chunk.Data)
+	if len(c.data) < chunk.Total {
+		return nil, nil
+	}
+	return bytes.Join(c.data, nil), nil
+}
+
+func (c *Client) get(ctx context.Context, path string, query url.Values, v any) error {
+	requestURL := *c.baseURL
+	requestURL.Path += path
+	requestURL.RawQuery = query.Encode()
+
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, requestURL.String(), nil)
+	if err != nil {
+		return err
+	}
+	resp, err := c.httpClient.Do(req)
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close() //nolint:errcheck
+
+	if resp.StatusCode != http.StatusOK {
+		body, _ := io.ReadAll(io.LimitReader(resp.Body, 1024))
+		return fmt.Errorf("GET %s: %s: %s", path, resp.Status, strings.TrimSpace(string(body)))
+	}
+	if err := json.NewDecoder(resp.Body).Decode(v); err != nil {
+		return fmt.Errorf("decode %s: %w", path, err)
+	}
+	return nil
+}
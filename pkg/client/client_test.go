@@ -0,0 +1,287 @@
+package client_test
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"net/http"
+	"net/http/httptest"
+	"reflect"
+	"testing"
+
+	"github.com/gorilla/websocket"
+	"github.com/kdwils/constellation/internal/types"
+	apiv1 "github.com/kdwils/constellation/pkg/api/v1"
+	"github.com/kdwils/constellation/pkg/client"
+)
+
+var errStop = errors.New("stop")
+
+func TestClient_GetState(t *testing.T) {
+	tests := []struct {
+		name    string
+		status  int
+		body    string
+		want    types.State
+		wantErr string
+	}{
+		{
+			name:   "state is decoded",
+			status: http.StatusOK,
+			body:   `{"services":[{"service_name":"web","namespace":"default"}],"degraded":true}`,
+			want: types.State{
+				Services: []*types.ServiceHealthInfo{{ServiceName: "web", Namespace: "default"}},
+				Degraded: true,
+			},
+		},
+		{
+			name:    "error status is returned",
+			status:  http.StatusTooManyRequests,
+			body:    "rate limit exceeded\n",
+			wantErr: "GET /api/v1/state: 429 Too Many Requests: rate limit exceeded",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+				if r.URL.Path != "/api/v1/state" {
+					http.NotFound(w, r)
+					return
+				}
+				w.WriteHeader(tt.status)
+				w.Write([]byte(tt.body)) //nolint:errcheck
+			}))
+			defer server.Close()
+
+			c, err := client.New(server.URL)
+			if err != nil {
+				t.Fatalf("TestClient_GetState() New error = %v", err)
+			}
+			got, err := c.GetState(context.Background())
+			if tt.wantErr != "" {
+				if err == nil || err.Error() != tt.wantErr {
+					t.Fatalf("TestClient_GetState() error = %v, want %v", err, tt.wantErr)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("TestClient_GetState() error = %v", err)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestClient_GetState() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestClient_Search(t *testing.T) {
+	var selector string
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		selector = r.URL.Query().Get("selector")
+		w.Write([]byte(`[{"kind":"Namespace","name":"default","id":"namespace/default","relatives":[` + //nolint:errcheck
+			`{"kind":"Pod","name":"web-0","id":"pod/default/web-0","parent_id":"namespace/default"}]}]`))
+	}))
+	defer server.Close()
+
+	c, err := client.New(server.URL + "/")
+	if err != nil {
+		t.Fatalf("TestClient_Search() New error = %v", err)
+	}
+	got, err := c.Search(context.Background(), "app=web")
+	if err != nil {
+		t.Fatalf("TestClient_Search() error = %v", err)
+	}
+
+	want := []apiv1.Node{{
+		Kind: types.ResourceKindNamespace,
+		Name: "default",
+		ID:   "namespace/default",
+		Relatives: []apiv1.Node{
+			{Kind: types.ResourceKindPod, Name: "web-0", ID: "pod/default/web-0", ParentID: "namespace/default"},
+		},
+	}}
+	if !reflect.DeepEqual(got, want) {
+		t.Errorf("TestClient_Search() = %+v, want %+v", got, want)
+	}
+	if selector != "app=web" {
+		t.Errorf("TestClient_Search() selector = %q, want %q", selector, "app=web")
+	}
+}
+
+func TestClient_GetSnapshot(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.URL.Path != "/api/v1/snapshot" {
+			http.NotFound(w, r)
+			return
+		}
+		w.Write([]byte(`{"hierarchy":[{"kind":"Namespace","name":"default"}],` + //nolint:errcheck
+			`"health":[{"service_name":"web","namespace":"default"}]}`))
+	}))
+	defer server.Close()
+
+	c, err := client.New(server.URL)
+	if err != nil {
+		t.Fatalf("TestClient_GetSnapshot() New error = %v", err)
+	}
+	got, err := c.GetSnapshot(context.Background())
+	if err != nil {
+		t.Fatalf("TestClient_GetSnapshot() error = %v", err)
+	}
+
+	want := types.Snapshot{
+		Hierarchy: []types.HierarchyNode{{Kind: types.ResourceKindNamespace, Name: "default"}},
+		Health:    []*types.ServiceHealthInfo{{ServiceName: "web", Namespace: "default"}},
+	}
+	if !reflect.DeepEqual(got, want) {
+		t.Errorf("TestClient_GetSnapshot() = %+v, want %+v", got, want)
+	}
+}
+
+func TestClient_StreamUpdates(t *testing.T) {
+	whole := `[{"service_name":"web","namespace":"default"}]`
+	chunk := func(index int, data string) string {
+		message, _ := json.Marshal(types.ChunkedMessage{
+			Chunk: types.MessageChunk{ID: 7, Index: index, Total: 2, Data: []byte(data)},
+		})
+		return string(message)
+	}
+
+	tests := []struct {
+		name     string
+		messages []string
+		want     [][]*types.ServiceHealthInfo
+		wantErr  string
+	}{
+		{
+			name:     "whole messages are decoded",
+			messages: []string{whole, `[]`},
+			want: [][]*types.ServiceHealthInfo{
+				{{ServiceName: "web", Namespace: "default"}},
+				{},
+			},
+		},
+		{
+			name:     "chunks are reassembled",
+			messages: []string{chunk(0, whole[:10]), chunk(1, whole[10:])},
+			want: [][]*types.ServiceHealthInfo{
+				{{ServiceName: "web", Namespace: "default"}},
+			},
+		},
+		{
+			name:     "chunk out of order",
+			messages: []string{chunk(1, whole[10:])},
+			wantErr:  "chunk 1 of message 7 out of order",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			upgrader := websocket.Upgrader{}
+			server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+				conn, err := upgrader.Upgrade(w, r, nil)
+				if err != nil {
+					return
+				}
+				defer conn.Close() //nolint:errcheck
+				for _, message := range tt.messages {
+					conn.WriteMessage(websocket.TextMessage, []byte(message)) //nolint:errcheck
+				}
+				conn.ReadMessage() //nolint:errcheck
+			}))
+			defer server.Close()
+
+			c, err := client.New(server.URL)
+			if err != nil {
+				t.Fatalf("TestClient_StreamUpdates() New error = %v", err)
+			}
+
+			var got [][]*types.ServiceHealthInfo
+			err = c.StreamUpdates(context.Background(), func(services []*types.ServiceHealthInfo) error {
+				got = append(got, services)
+				if len(got) == len(tt.want) {
+					return errStop
+				}
+				return nil
+			})
+			if tt.wantErr != "" {
+				if err == nil || err.Error() != tt.wantErr {
+					t.Fatalf("TestClient_StreamUpdates() error = %v, want %v", err, tt.wantErr)
+				}
+				return
+			}
+			if !errors.Is(err, errStop) {
+				t.Fatalf("TestClient_StreamUpdates() error = %v, want %v", err, errStop)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestClient_StreamUpdates() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestClient_StreamHierarchy(t *testing.T) {
+	tests := []struct {
+		name     string
+		messages []string
+		want     [][]types.HierarchyNode
+		wantErr  string
+	}{
+		{
+			name:     "every hierarchy is decoded",
+			messages: []string{`[{"kind":"Namespace","name":"default","id":"Namespace/default"}]`, `[]`},
+			want: [][]types.HierarchyNode{
+				{{Kind: types.ResourceKindNamespace, Name: "default", ID: "Namespace/default"}},
+				{},
+			},
+		},
+		{
+			name:     "malformed hierarchy",
+			messages: []string{`"default"`},
+			wantErr:  "decode hierarchy: json: cannot unmarshal string into Go value of type []types.HierarchyNode",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			upgrader := websocket.Upgrader{}
+			mux := http.NewServeMux()
+			mux.HandleFunc("/ws/hierarchy", func(w http.ResponseWriter, r *http.Request) {
+				conn, err := upgrader.Upgrade(w, r, nil)
+				if err != nil {
+					return
+				}
+				defer conn.Close() //nolint:errcheck
+				for _, message := range tt.messages {
+					conn.WriteMessage(websocket.TextMessage, []byte(message)) //nolint:errcheck
+				}
+				conn.ReadMessage() //nolint:errcheck
+			})
+			server := httptest.NewServer(mux)
+			defer server.Close()
+
+			c, err := client.New(server.URL)
+			if err != nil {
+				t.Fatalf("TestClient_StreamHierarchy() New error = %v", err)
+			}
+
+			var got [][]types.HierarchyNode
+			err = c.StreamHierarchy(context.Background(), func(nodes []types.HierarchyNode) error {
+				got = append(got, nodes)
+				if len(got) == len(tt.want) {
+					return errStop
+				}
+				return nil
+			})
+			if tt.wantErr != "" {
+				if err == nil || err.Error() != tt.wantErr {
+					t.Fatalf("TestClient_StreamHierarchy() error = %v, want %v", err, tt.wantErr)
+				}
+				return
+			}
+			if !errors.Is(err, errStop) {
+				t.Fatalf("TestClient_StreamHierarchy() error = %v, want %v", err, errStop)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("TestClient_StreamHierarchy() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,57 @@
+package constellation
+
+import (
+	"context"
+	"fmt"
+
+	corev1 "k8s.io/api/core/v1"
+	ctrl "sigs.k8s.io/controller-runtime"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	"sigs.k8s.io/controller-runtime/pkg/log"
+
+	"github.com/kdwils/constellation/internal/controller"
+	"github.com/kdwils/constellation/internal/types"
+	"github.com/kdwils/constellation/internal/watches"
+)
+
+// admin backs the /admin API with the watch toggles and a reconciler for resyncing Services
+type admin struct {
+	*watches.Toggles
+	services *controller.ServiceReconciler
+}
+
+// Resync restarts the optional watches so they list afresh, then reconciles every Service again so
+// health targets match the cache even if an event was missed
+func (a *admin) Resync(ctx context.Context) (types.ResyncResult, error) {
+	restarted, err := a.Restart(ctx)
+	if err != nil {
+		return types.ResyncResult{}, err
+	}
+
+	reconciled, err := reconcileServices(ctx, a.services)
+	if err != nil {
+		return types.ResyncResult{}, fmt.Errorf("failed to list services: %w", err)
+	}
+	return types.ResyncResult{RestartedWatches: restarted, ReconciledServices: reconciled}, nil
+}
+
+// reconcileServices reconciles every Service in the cache and returns how many were reconciled.
+// A Service that fails to reconcile is logged and left to the controller's retries.
+func reconcileServices(ctx context.Context, reconciler *controller.ServiceReconciler) (int, error) {
+	var services corev1.ServiceList
+	if err := reconciler.List(ctx, &services); err != nil {
+		return 0, err
+	}
+
+	logger := log.FromContext(ctx)
+	reconciled := 0
+	for i := range services.Items {
+		request := ctrl.Request{NamespacedName: client.ObjectKeyFromObject(&services.Items[i])}
+		if _, err := reconciler.Reconcile(ctx, request); err != nil {
+			logger.Error(err, "failed to reconcile service", "service", request.NamespacedName)
+			continue
+		}
+		reconciled++
+	}
+	return reconciled, nil
+}
@@ -0,0 +1,662 @@
+// Package constellation embeds the constellation dashboard into another controller-runtime program,
+// such as an existing operator binary.
+//
+// Register it with the program's manager and it starts alongside the manager's other runnables:
+//
+//	if _, err := constellation.SetupWithManager(mgr, constellation.Options{Port: 8080}); err != nil {
+//		return err
+//	}
+//	return mgr.Start(ctx)
+//
+// The hierarchy builder is also exposed directly through Build for programs that already hold
+// the cluster objects and only want the derived tree.
+package constellation
+
+import (
+	"context"
+	"fmt"
+	"net"
+	"net/http"
+
+	corev1 "k8s.io/api/core/v1"
+	"k8s.io/client-go/discovery"
+	ctrl "sigs.k8s.io/controller-runtime"
+	"sigs.k8s.io/controller-runtime/pkg/cache"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	"sigs.k8s.io/controller-runtime/pkg/log"
+	"sigs.k8s.io/controller-runtime/pkg/metrics"
+
+	"github.com/kdwils/constellation/internal/annotations"
+	"github.com/kdwils/constellation/internal/anonymize"
+	"github.com/kdwils/constellation/internal/apiprobe"
+	"github.com/kdwils/constellation/internal/badges"
+	"github.com/kdwils/constellation/internal/broadcast"
+	"github.com/kdwils/constellation/internal/certs"
+	"github.com/kdwils/constellation/internal/churn"
+	"github.com/kdwils/constellation/internal/controller"
+	"github.com/kdwils/constellation/internal/disruptions"
+	"github.com/kdwils/constellation/internal/drift"
+	"github.com/kdwils/constellation/internal/endpoints"
+	"github.com/kdwils/constellation/internal/favorites"
+	"github.com/kdwils/constellation/internal/freshness"
+	"github.com/kdwils/constellation/internal/healthcheck"
+	"github.com/kdwils/constellation/internal/hierarchy"
+	"github.com/kdwils/constellation/internal/ratelimit"
+	"github.com/kdwils/constellation/internal/replacements"
+	"github.com/kdwils/constellation/internal/restarts"
+	"github.com/kdwils/constellation/internal/rollouts"
+	"github.com/kdwils/constellation/internal/scrub"
+	"github.com/kdwils/constellation/internal/server"
+	"github.com/kdwils/constellation/internal/snapshot"
+	"github.com/kdwils/constellation/internal/timeline"
+	"github.com/kdwils/constellation/internal/topology"
+	"github.com/kdwils/constellation/internal/traffic"
+	"github.com/kdwils/constellation/internal/types"
+	"github.com/kdwils/constellation/internal/usage"
+	"github.com/kdwils/constellation/internal/versions"
+	"github.com/kdwils/constellation/internal/watches"
+)
+
+type (
+	// HierarchyNode is a resource together with the resources traffic flows to from it
+	HierarchyNode = types.HierarchyNode
+	// ServiceHealthInfo is the active health check history of a single service
+	ServiceHealthInfo = types.ServiceHealthInfo
+	// ClusterObjects are the raw Kubernetes objects the hierarchy is built from
+	ClusterObjects = types.ClusterObjects
+	// Snapshot is a point-in-time export of the hierarchy, health data and raw objects
+	Snapshot = types.Snapshot
+	// Middleware wraps every dashboard route, e.g. to add the embedding program's authentication
+	Middleware = server.Middleware
+	// PrometheusConfig selects the Prometheus server and queries used for the traffic overlay
+	PrometheusConfig = traffic.Config
+	// RestartAnomalyConfig controls when a pod is flagged for restarting too often
+	RestartAnomalyConfig = restarts.Config
+	// CertificateConfig controls how often route certificates are inspected and when they are flagged
+	CertificateConfig = certs.Config
+	// DisruptionConfig controls how much eviction, preemption and OOM kill history is kept per workload
+	DisruptionConfig = disruptions.Config
+	// ChurnConfig sets the bucket size and length of the pod churn window
+	ChurnConfig = churn.Config
+	// RolloutConfig sets how many rollout events are kept
+	RolloutConfig = rollouts.Config
+	// ReplicaDriftConfig controls how long a workload may run short of ready replicas before it is alerted on
+	ReplicaDriftConfig = drift.Config
+	// PodReplacementConfig controls how long a terminating pod waits for its replacement
+	PodReplacementConfig = replacements.Config
+	// TimelineConfig sets how many state transitions are kept per node
+	TimelineConfig = timeline.Config
+	// AnnotationConfig sets how many CloudEvent markers are kept per node
+	AnnotationConfig = annotations.Config
+	// APIProbeConfig controls how often the apiserver is probed and the latency above which it is slow
+	APIProbeConfig = apiprobe.Config
+	// RetryBackoff is how long a failed Service or Pod reconcile waits before it is retried
+	RetryBackoff = controller.Backoff
+	// RateLimitConfig limits API requests per client IP and caps how many are served at once
+	RateLimitConfig = ratelimit.Config
+	// Alert is a condition on a single resource that needs attention
+	Alert = types.Alert
+	// UIConfig holds the per-install frontend options served at /config.json
+	UIConfig = types.UIConfig
+	// AnonymizeConfig holds the key names, hostnames and IPs are turned into pseudonyms with
+	AnonymizeConfig = anonymize.Config
+	// BadgeConfig holds the rules badges are attached to nodes by
+	BadgeConfig = badges.Config
+	// BadgeRule attaches a badge to every node whose labels match a selector
+	BadgeRule = badges.Rule
+	// FavoritesConfig names the ConfigMap pinned nodes are persisted in
+	FavoritesConfig = favorites.Config
+	// BroadcastConfig selects the Redis or NATS server health updates are relayed between replicas through
+	BroadcastConfig = broadcast.Config
+	// HTTPConfig tunes HTTP/2, keep-alives and timeouts on the dashboard's connections
+	HTTPConfig = server.HTTPConfig
+)
+
+// Options configures an embedded constellation instance
+type Options struct {
+	// Port the dashboard server listens on, defaulting to 8080
+	Port int
+	// Addr is an explicit host:port to bind, e.g. 127.0.0.1:8080; it takes precedence over Port
+	Addr string
+	// Listener is an already bound listener to serve on; it takes precedence over Addr and Port
+	Listener net.Listener
+	// HTTP tunes the dashboard's connections for proxies in front of it; the zero value keeps net/http's
+	// defaults
+	HTTP HTTPConfig
+	// StaticDir holds the built frontend; leave empty to serve only the API
+	StaticDir string
+	// APIOnly serves no frontend, whatever StaticDir says, and answers unknown routes with a JSON 404
+	APIOnly bool
+	// Routes are extra handlers served next to the dashboard, keyed by http.ServeMux pattern
+	Routes map[string]http.Handler
+	// Middleware wraps every route; the first entry is the outermost
+	Middleware []Middleware
+	// WatchHealthChecks enables the HealthCheck custom resource controller.
+	// The HealthCheck CRD and scheme must be installed when enabled.
+	WatchHealthChecks bool
+	// Prometheus enables the traffic metrics overlay on Service and HTTPRoute nodes when set
+	Prometheus *PrometheusConfig
+	// PodUsage attaches live CPU and memory usage from metrics-server to Pod nodes.
+	// metrics-server must be installed when enabled.
+	PodUsage bool
+	// RestartAnomaly flags pods that restart too often and reports them at /alerts when set
+	RestartAnomaly *RestartAnomalyConfig
+	// ClusterDomain is used for the DNS names shown on Services and Pods, defaulting to cluster.local
+	ClusterDomain string
+	// Certificates inspects the TLS certificates behind route hostnames when set.
+	// This needs read access to the Secrets referenced by Gateway listeners.
+	Certificates *CertificateConfig
+	// Disruptions tracks evicted, preempted and OOM killed pods and serves them at /disruptions when set
+	Disruptions *DisruptionConfig
+	// Churn counts pod creations and deletions per namespace and serves them as a heatmap at /churn when set
+	Churn *ChurnConfig
+	// Rollouts attaches the Deployment revision and change-cause a pod runs to Pod nodes and serves
+	// rollouts as they start at /events when set
+	Rollouts *RolloutConfig
+	// ReplicaDrift lists Deployments and StatefulSets with fewer ready pods than desired replicas on their
+	// Namespace node and reports those that stay short at /alerts when set
+	ReplicaDrift *ReplicaDriftConfig
+	// PodReplacements serves a pod_replaced event at /events linking a pod recreated by its controller to
+	// the pod it replaces when set
+	PodReplacements *PodReplacementConfig
+	// Timeline keeps the last pod phase, Service endpoint and HTTPRoute attachment transitions of each node
+	// and serves them at /resources/{kind}/{namespace}/{name}/timeline when set
+	Timeline *TimelineConfig
+	// Annotations accepts CloudEvents at POST /annotations and attaches them as markers to the node their
+	// subject names, streaming new markers at /ws/annotations, when set
+	Annotations *AnnotationConfig
+	// APIProbes measures list and watch latency against the apiserver when set, serving it at /readyz,
+	// failing the manager's readiness check while the apiserver is slow and exporting it as metrics
+	APIProbes *APIProbeConfig
+	// OwnerKeys are the label and annotation keys a resource's owner is read from, in order of
+	// preference, defaulting to owner and team. Resources are grouped by owner at /owners.
+	OwnerKeys []string
+	// ScrubAnnotations are annotation keys, such as ones embedding tokens, left out of every listed object,
+	// snapshot and hierarchy node in addition to kubectl's last applied configuration. A key ending in *
+	// matches every annotation with that prefix.
+	ScrubAnnotations []string
+	// RateLimit limits the dashboard API per client IP and caps concurrent requests when set
+	RateLimit *RateLimitConfig
+	// AccessLog logs every dashboard request with its route, status, latency and client IP
+	AccessLog bool
+	// MaxChildren caps the children of each hierarchy root served at /hierarchy, summarising the rest
+	// in a Truncated node. The rest is paged through /hierarchy/{namespace}. Unlimited when 0.
+	MaxChildren int
+	// DisabledWatches are optional resources left unwatched and out of the hierarchy, e.g. httproutes
+	// in a cluster without Gateway API. See watches.Resources for the resources that may be disabled.
+	DisabledWatches []string
+	// AdminToken serves the /admin API for switching watches, resyncing and inspecting the cache,
+	// requiring it as a bearer token. The API is off when empty.
+	AdminToken string
+	// IdentityHeader names callers by the user an authenticating proxy sets in this header, e.g.
+	// X-Forwarded-User, in /me and the /admin/audit trail of changes made through the API. Read only
+	// while the /admin API is on.
+	IdentityHeader string
+	// MessageSizeLimit splits WebSocket messages larger than this many bytes into chunks the client
+	// reassembles, for proxies that drop large frames. Messages are never split when 0.
+	MessageSizeLimit int
+	// RetryBackoff replaces controller-runtime's default retry delay for failed reconciles when set,
+	// e.g. a larger Base to spare a managed control plane when many objects fail at once
+	RetryBackoff *RetryBackoff
+	// TopologyMetrics registers gauges derived from the hierarchy, such as constellation_route_resolvable,
+	// with the controller-runtime metrics registry served on the manager's metrics endpoint
+	TopologyMetrics bool
+	// UI customises the frontend through /config.json, e.g. its title and the namespaces shown first.
+	// The defaults are served when nil.
+	UI *UIConfig
+	// Anonymize replaces names, hostnames and IPs in every API response with stable pseudonyms when set,
+	// so screenshots and snapshots can be shared publicly
+	Anonymize *AnonymizeConfig
+	// Badges tags every node whose labels match a rule's selector with its badge, e.g. stateful for
+	// tier=db, when set
+	Badges *BadgeConfig
+	// Favorites serves the nodes pinned on the dashboard at /favorites and enables the ?favorites=true
+	// filter when set. Pins are persisted in the named ConfigMap, or only kept in memory without a name.
+	Favorites *FavoritesConfig
+	// Broadcast relays health updates between replicas through Redis or NATS when set. The replica
+	// holding the manager's leader lease publishes and every replica streams what is published, so
+	// WebSocket clients see the same updates whichever replica the load balancer picked.
+	Broadcast *BroadcastConfig
+	// SnapshotFile persists the snapshot to this path every minute and on shutdown when set. After a
+	// restart the persisted snapshot is served, with /state marked stale, until the cache has synced.
+	SnapshotFile string
+}
+
+// Constellation is an embedded dashboard instance
+type Constellation struct {
+	healthChecker *healthcheck.HealthChecker
+	hierarchy     *hierarchy.Provider
+	server        *server.Server
+	traffic       *traffic.Collector
+	usage         *usage.Collector
+	restarts      *restarts.Tracker
+	certs         *certs.Inspector
+	disruptions   *disruptions.Tracker
+	churn         *churn.Tracker
+	rollouts      *rollouts.Tracker
+	drift         *drift.Tracker
+	replacements  *replacements.Tracker
+	timeline      *timeline.Tracker
+	apiProbes     *apiprobe.Prober
+	endpoints     *endpoints.Watcher
+	cache         cache.Cache
+	services      *controller.ServiceReconciler
+	healthGate    *controller.RegistryGate
+	sync          *freshness.Tracker
+	favorites     *favorites.Store
+	broadcast     *broadcast.Health
+	elected       <-chan struct{}
+	warm          *snapshot.Warm
+}
+
+// SetupWithManager registers the constellation reconcilers with mgr and adds the health checker
+// and dashboard server as a runnable, so both start and stop with the manager. The Gateway API kinds
+// are read in the version the apiserver serves, so mgr's scheme needs both their v1 and v1beta1 types.
+func SetupWithManager(mgr ctrl.Manager, opts Options) (*Constellation, error) {
+	healthChecker := healthcheck.NewHealthChecker()
+	var health server.HealthDataProvider = healthChecker
+	var healthRelay *broadcast.Health
+	if opts.Broadcast != nil {
+		backend, err := broadcast.New(*opts.Broadcast)
+		if err != nil {
+			return nil, err
+		}
+		healthRelay = broadcast.NewHealth(healthChecker, backend, opts.Broadcast.Channel)
+		health = healthRelay
+	}
+	syncTracker := freshness.NewTracker(mgr.GetCache(), map[types.ResourceKind]client.Object{
+		types.ResourceKindService: &corev1.Service{},
+		types.ResourceKindPod:     &corev1.Pod{},
+	})
+
+	// the reconcilers' changes are held back until the initial sync has registered every Service
+	healthGate := controller.NewRegistryGate(healthChecker)
+	serviceReconciler := controller.NewServiceReconciler(mgr, healthGate)
+	serviceReconciler.Sync = syncTracker
+	serviceReconciler.Backoff = opts.RetryBackoff
+	if err := serviceReconciler.SetupWithManager(mgr); err != nil {
+		return nil, fmt.Errorf("unable to create service controller: %w", err)
+	}
+
+	podReconciler := controller.NewPodReconciler(mgr, healthGate)
+	podReconciler.Sync = syncTracker
+	podReconciler.Backoff = opts.RetryBackoff
+	if err := podReconciler.SetupWithManager(mgr); err != nil {
+		return nil, fmt.Errorf("unable to create pod controller: %w", err)
+	}
+
+	if opts.WatchHealthChecks {
+		healthCheckReconciler := &controller.HealthCheckReconciler{
+			Client:        mgr.GetClient(),
+			Scheme:        mgr.GetScheme(),
+			HealthChecker: healthChecker,
+		}
+		if err := healthCheckReconciler.SetupWithManager(mgr); err != nil {
+			return nil, fmt.Errorf("unable to create healthcheck controller: %w", err)
+		}
+	}
+
+	discoveryClient, err := discovery.NewDiscoveryClientForConfig(mgr.GetConfig())
+	if err != nil {
+		return nil, fmt.Errorf("unable to create discovery client: %w", err)
+	}
+	served, err := versions.Negotiate(discoveryClient)
+	if err != nil {
+		return nil, fmt.Errorf("unable to negotiate gateway api versions: %w", err)
+	}
+
+	watchToggles, err := watches.NewToggles(mgr.GetCache(), served, opts.DisabledWatches...)
+	if err != nil {
+		return nil, err
+	}
+
+	providerOpts := []hierarchy.ProviderOpt{
+		hierarchy.WithListOptions(
+			hierarchy.WithWatchToggles(watchToggles),
+			hierarchy.WithVersions(served),
+			hierarchy.WithScrubber(scrub.New(opts.ScrubAnnotations...)),
+		),
+		hierarchy.WithBuildOptions(hierarchy.WithScrubbedAnnotations(opts.ScrubAnnotations...)),
+	}
+	if opts.ClusterDomain != "" {
+		providerOpts = append(providerOpts, hierarchy.WithBuildOptions(hierarchy.WithClusterDomain(opts.ClusterDomain)))
+	}
+	if len(opts.OwnerKeys) > 0 {
+		providerOpts = append(providerOpts, hierarchy.WithBuildOptions(hierarchy.WithOwnerKeys(opts.OwnerKeys...)))
+	}
+	var trafficCollector *traffic.Collector
+	if opts.Prometheus != nil {
+		trafficCollector = traffic.NewCollector(*opts.Prometheus)
+		providerOpts = append(providerOpts, hierarchy.WithEnricher(trafficCollector))
+	}
+	var usageCollector *usage.Collector
+	if opts.PodUsage {
+		usageCollector = usage.NewCollector(mgr.GetAPIReader())
+		providerOpts = append(providerOpts, hierarchy.WithEnricher(usageCollector))
+	}
+
+	var restartTracker *restarts.Tracker
+	if opts.RestartAnomaly != nil {
+		restartTracker = restarts.NewTracker(mgr.GetClient(), *opts.RestartAnomaly)
+		providerOpts = append(providerOpts, hierarchy.WithEnricher(restartTracker))
+	}
+
+	var certInspector *certs.Inspector
+	if opts.Certificates != nil {
+		certInspector = certs.NewInspector(mgr.GetAPIReader(), *opts.Certificates, certs.WithVersions(served))
+		providerOpts = append(providerOpts, hierarchy.WithEnricher(certInspector))
+	}
+
+	var disruptionTracker *disruptions.Tracker
+	if opts.Disruptions != nil {
+		disruptionTracker = disruptions.NewTracker(mgr.GetClient(), mgr.GetAPIReader(), *opts.Disruptions)
+		providerOpts = append(providerOpts, hierarchy.WithEnricher(disruptionTracker))
+	}
+
+	var rolloutTracker *rollouts.Tracker
+	if opts.Rollouts != nil {
+		rolloutTracker = rollouts.NewTracker(mgr.GetCache(), *opts.Rollouts)
+		providerOpts = append(providerOpts, hierarchy.WithEnricher(rolloutTracker))
+	}
+
+	var driftTracker *drift.Tracker
+	if opts.ReplicaDrift != nil {
+		driftTracker = drift.NewTracker(mgr.GetCache(), *opts.ReplicaDrift)
+		providerOpts = append(providerOpts, hierarchy.WithEnricher(driftTracker))
+	}
+
+	var annotationStore *annotations.Store
+	if opts.Annotations != nil {
+		annotationStore = annotations.NewStore(*opts.Annotations)
+		providerOpts = append(providerOpts, hierarchy.WithEnricher(annotationStore))
+	}
+
+	if opts.Badges != nil {
+		badgeRules, err := badges.New(*opts.Badges)
+		if err != nil {
+			return nil, err
+		}
+		providerOpts = append(providerOpts, hierarchy.WithEnricher(badgeRules))
+	}
+
+	hierarchyProvider := hierarchy.NewProvider(mgr.GetClient(), health, providerOpts...)
+	if opts.TopologyMetrics {
+		if err := metrics.Registry.Register(topology.NewCollector(hierarchyProvider)); err != nil {
+			return nil, fmt.Errorf("unable to register topology metrics: %w", err)
+		}
+	}
+	var warm *snapshot.Warm
+	var nodes server.HierarchyProvider = hierarchyProvider
+	var syncStatus server.SyncStatusProvider = syncTracker
+	if opts.SnapshotFile != "" {
+		warm = snapshot.NewWarm(opts.SnapshotFile, health, hierarchyProvider, syncTracker)
+		health, nodes, syncStatus = warm, warm, warm
+	}
+	endpointWatcher := endpoints.NewWatcher(mgr.GetClient(), endpoints.Config{})
+	serverOpts := []server.ServerOpt{
+		server.WithStaticDir(opts.StaticDir),
+		server.WithHierarchyProvider(nodes),
+		server.WithSnapshotProvider(hierarchyProvider),
+		server.WithEndpointProvider(endpointWatcher),
+		server.WithSyncStatusProvider(syncStatus),
+	}
+	if opts.APIOnly {
+		serverOpts = append(serverOpts, server.WithAPIOnly())
+	}
+	if restartTracker != nil {
+		serverOpts = append(serverOpts, server.WithAlertProvider(restartTracker))
+	}
+	if certInspector != nil {
+		serverOpts = append(serverOpts, server.WithAlertProvider(certInspector))
+	}
+	if driftTracker != nil {
+		serverOpts = append(serverOpts, server.WithAlertProvider(driftTracker))
+	}
+	if disruptionTracker != nil {
+		serverOpts = append(serverOpts, server.WithDisruptionProvider(disruptionTracker))
+	}
+	var prober *apiprobe.Prober
+	if opts.APIProbes != nil {
+		probeClient, err := client.NewWithWatch(mgr.GetConfig(), client.Options{
+			Scheme: mgr.GetScheme(),
+			Mapper: mgr.GetRESTMapper(),
+		})
+		if err != nil {
+			return nil, fmt.Errorf("unable to create apiserver probe client: %w", err)
+		}
+		prober = apiprobe.NewProber(probeClient, *opts.APIProbes)
+		if err := metrics.Registry.Register(prober); err != nil {
+			return nil, fmt.Errorf("unable to register apiserver probe metrics: %w", err)
+		}
+		if err := mgr.AddReadyzCheck("apiserver", prober.Check); err != nil {
+			return nil, fmt.Errorf("unable to add apiserver readiness check: %w", err)
+		}
+		serverOpts = append(serverOpts, server.WithAPIProbeProvider(prober))
+	}
+	var churnTracker *churn.Tracker
+	if opts.Churn != nil {
+		churnTracker = churn.NewTracker(mgr.GetCache(), *opts.Churn)
+		serverOpts = append(serverOpts, server.WithChurnProvider(churnTracker))
+	}
+	if rolloutTracker != nil {
+		serverOpts = append(serverOpts, server.WithEventProvider(rolloutTracker))
+	}
+	var replacementTracker *replacements.Tracker
+	if opts.PodReplacements != nil {
+		replacementTracker = replacements.NewTracker(mgr.GetCache(), *opts.PodReplacements)
+		serverOpts = append(serverOpts, server.WithEventProvider(replacementTracker))
+	}
+	var timelineTracker *timeline.Tracker
+	if opts.Timeline != nil {
+		timelineTracker = timeline.NewTracker(mgr.GetCache(), *opts.Timeline, timeline.WithVersions(served))
+		serverOpts = append(serverOpts, server.WithTimelineProvider(timelineTracker))
+	}
+	if annotationStore != nil {
+		serverOpts = append(serverOpts, server.WithAnnotationProvider(annotationStore))
+	}
+	if opts.RateLimit != nil {
+		serverOpts = append(serverOpts, server.WithRateLimit(*opts.RateLimit))
+	}
+	if opts.AccessLog {
+		serverOpts = append(serverOpts, server.WithAccessLog())
+	}
+	if opts.MaxChildren > 0 {
+		serverOpts = append(serverOpts, server.WithMaxChildren(opts.MaxChildren))
+	}
+	if opts.MessageSizeLimit > 0 {
+		serverOpts = append(serverOpts, server.WithMessageSizeLimit(opts.MessageSizeLimit))
+	}
+	if opts.UI != nil {
+		serverOpts = append(serverOpts, server.WithUIConfig(*opts.UI))
+	}
+	if opts.Anonymize != nil {
+		serverOpts = append(serverOpts, server.WithAnonymizer(anonymize.New(*opts.Anonymize)))
+	}
+	var favoriteStore *favorites.Store
+	if opts.Favorites != nil {
+		var backend favorites.Backend
+		if opts.Favorites.Name != "" {
+			backend = favorites.NewConfigMap(mgr.GetAPIReader(), mgr.GetClient(), *opts.Favorites)
+		}
+		favoriteStore = favorites.NewStore(backend)
+		serverOpts = append(serverOpts, server.WithFavoritesProvider(favoriteStore))
+	}
+	if opts.AdminToken != "" {
+		adminProvider := &admin{Toggles: watchToggles, services: controller.NewServiceReconciler(mgr, healthGate)}
+		serverOpts = append(serverOpts, server.WithAdminProvider(adminProvider, opts.AdminToken))
+	}
+	if opts.IdentityHeader != "" {
+		serverOpts = append(serverOpts, server.WithIdentityHeader(opts.IdentityHeader))
+	}
+	if opts.Port != 0 {
+		serverOpts = append(serverOpts, server.WithPort(opts.Port))
+	}
+	if opts.Addr != "" {
+		serverOpts = append(serverOpts, server.WithAddr(opts.Addr))
+	}
+	if opts.Listener != nil {
+		serverOpts = append(serverOpts, server.WithListener(opts.Listener))
+	}
+	serverOpts = append(serverOpts, server.WithHTTPConfig(opts.HTTP))
+	for pattern, handler := range opts.Routes {
+		serverOpts = append(serverOpts, server.WithRoute(pattern, handler))
+	}
+	serverOpts = append(serverOpts, server.WithMiddleware(opts.Middleware...))
+	srv := server.New(health, serverOpts...)
+
+	c := &Constellation{
+		healthChecker: healthChecker,
+		hierarchy:     hierarchyProvider,
+		server:        srv,
+		traffic:       trafficCollector,
+		usage:         usageCollector,
+		restarts:      restartTracker,
+		certs:         certInspector,
+		disruptions:   disruptionTracker,
+		churn:         churnTracker,
+		rollouts:      rolloutTracker,
+		drift:         driftTracker,
+		replacements:  replacementTracker,
+		timeline:      timelineTracker,
+		apiProbes:     prober,
+		endpoints:     endpointWatcher,
+		cache:         mgr.GetCache(),
+		services:      controller.NewServiceReconciler(mgr, healthChecker),
+		healthGate:    healthGate,
+		sync:          syncTracker,
+		favorites:     favoriteStore,
+		broadcast:     healthRelay,
+		elected:       mgr.Elected(),
+		warm:          warm,
+	}
+
+	if err := mgr.Add(c); err != nil {
+		return nil, fmt.Errorf("unable to add constellation to manager: %w", err)
+	}
+
+	return c, nil
+}
+
+// Start runs the health checker, endpoint watcher and dashboard server until the context is cancelled
+func (c *Constellation) Start(ctx context.Context) error {
+	if c.warm != nil {
+		if err := c.warm.Load(); err != nil {
+			log.FromContext(ctx).Error(err, "failed to load the persisted snapshot, serving nothing until synced")
+		}
+		go c.warm.Persist(ctx)
+	}
+	go c.syncInitialState(ctx)
+	go c.healthChecker.Start(ctx)
+	go c.endpoints.Start(ctx)
+	if c.broadcast != nil {
+		go c.broadcast.Start(ctx, c.elected)
+	}
+	if c.traffic != nil {
+		go c.traffic.Start(ctx)
+	}
+	if c.usage != nil {
+		go c.usage.Start(ctx)
+	}
+	if c.restarts != nil {
+		go c.restarts.Start(ctx)
+	}
+	if c.certs != nil {
+		go c.certs.Start(ctx)
+	}
+	if c.disruptions != nil {
+		go c.disruptions.Start(ctx)
+	}
+	if c.churn != nil {
+		go c.churn.Start(ctx)
+	}
+	if c.rollouts != nil {
+		go c.rollouts.Start(ctx)
+	}
+	if c.drift != nil {
+		go c.drift.Start(ctx)
+	}
+	if c.replacements != nil {
+		go c.replacements.Start(ctx)
+	}
+	if c.timeline != nil {
+		go c.timeline.Start(ctx)
+	}
+	if c.apiProbes != nil {
+		go c.apiProbes.Start(ctx)
+	}
+	if c.favorites != nil {
+		if err := c.favorites.Load(ctx); err != nil {
+			log.FromContext(ctx).Error(err, "failed to load favorites, they are read again on the next pin")
+		}
+	}
+	return c.server.Serve(ctx)
+}
+
+// syncInitialState reconciles every Service once the cache has synced, so /state reports syncing
+// rather than a partial set of health targets while the controllers work through their first events.
+// The controllers' changes queued meanwhile are replayed afterwards, since they may be newer than
+// the listed objects. A Service that fails to reconcile is left to the controller's retries.
+func (c *Constellation) syncInitialState(ctx context.Context) {
+	logger := log.FromContext(ctx).WithName("initial-sync")
+	if !c.cache.WaitForCacheSync(ctx) {
+		return
+	}
+
+	if _, err := reconcileServices(log.IntoContext(ctx, logger), c.services); err != nil {
+		logger.Error(err, "failed to list services")
+	}
+	c.healthGate.Open()
+	c.sync.MarkSynced()
+	if c.warm != nil {
+		c.warm.MarkLive()
+	}
+}
+
+// NeedLeaderElection reports false so every replica serves the dashboard
+func (c *Constellation) NeedLeaderElection() bool {
+	return false
+}
+
+// Handler returns the dashboard routes for mounting on an existing HTTP server
+func (c *Constellation) Handler() http.Handler {
+	return c.server.Handler()
+}
+
+// GetHierarchy returns the current resource hierarchy
+func (c *Constellation) GetHierarchy(ctx context.Context) ([]HierarchyNode, error) {
+	return c.hierarchy.GetHierarchy(ctx)
+}
+
+// GetAllHealthData returns the current health check results for every service
+func (c *Constellation) GetAllHealthData() []*ServiceHealthInfo {
+	return c.healthChecker.GetAllHealthData()
+}
+
+// Snapshot captures the hierarchy, health data and raw objects at a single point in time
+func (c *Constellation) Snapshot(ctx context.Context) (*Snapshot, error) {
+	return c.hierarchy.Snapshot(ctx)
+}
+
+// BuildOpt configures Build
+type BuildOpt = hierarchy.BuildOpt
+
+// WithClusterDomain sets the domain used for in-cluster DNS names, defaulting to cluster.local
+func WithClusterDomain(domain string) BuildOpt {
+	return hierarchy.WithClusterDomain(domain)
+}
+
+// WithOwnerKeys sets the label and annotation keys a resource's owner is read from, in order of preference
+func WithOwnerKeys(keys ...string) BuildOpt {
+	return hierarchy.WithOwnerKeys(keys...)
+}
+
+// WithScrubbedAnnotations sets the annotation keys left out of the hierarchy in addition to kubectl's last
+// applied configuration
+func WithScrubbedAnnotations(keys ...string) BuildOpt {
+	return hierarchy.WithScrubbedAnnotations(keys...)
+}
+
+// Build assembles the resource hierarchy from raw cluster objects and optional health data
+func Build(objects ClusterObjects, health []*ServiceHealthInfo, opts ...BuildOpt) []HierarchyNode {
+	return hierarchy.Build(objects, health, opts...)
+}
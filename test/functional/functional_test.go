@@ -0,0 +1,233 @@
+//go:build functional
+
+// Package functional runs constellation against a real apiserver in a k3s container, which is killed and
+// restarted mid-run to check the watchers relist and converge. Run with make test-functional; it needs
+// docker.
+package functional_test
+
+import (
+	"context"
+	"fmt"
+	"net"
+	"os"
+	"os/exec"
+	"strconv"
+	"strings"
+	"testing"
+	"time"
+
+	corev1 "k8s.io/api/core/v1"
+	metav1 "k8s.io/apimachinery/pkg/apis/meta/v1"
+	"k8s.io/apimachinery/pkg/runtime"
+	utilruntime "k8s.io/apimachinery/pkg/util/runtime"
+	clientgoscheme "k8s.io/client-go/kubernetes/scheme"
+	"k8s.io/client-go/rest"
+	"k8s.io/client-go/tools/clientcmd"
+	ctrl "sigs.k8s.io/controller-runtime"
+	"sigs.k8s.io/controller-runtime/pkg/client"
+	"sigs.k8s.io/controller-runtime/pkg/log/zap"
+	metricsserver "sigs.k8s.io/controller-runtime/pkg/metrics/server"
+	gatewayv1 "sigs.k8s.io/gateway-api/apis/v1"
+	gatewayv1beta1 "sigs.k8s.io/gateway-api/apis/v1beta1"
+
+	"github.com/kdwils/constellation/internal/hierarchy"
+	constellationclient "github.com/kdwils/constellation/pkg/client"
+	"github.com/kdwils/constellation/pkg/constellation"
+)
+
+const (
+	// defaultImage is the k3s image run, overridden with CONSTELLATION_K3S_IMAGE
+	defaultImage  = "rancher/k3s:v1.33.1-k3s1"
+	containerName = "constellation-functional"
+	namespace     = "functional"
+
+	startTimeout    = 2 * time.Minute
+	convergeTimeout = 2 * time.Minute
+)
+
+func TestConverges(t *testing.T) {
+	cfg := startK3s(t)
+	ctrl.SetLogger(zap.New(zap.WriteTo(os.Stderr), zap.UseDevMode(true)))
+
+	scheme := runtime.NewScheme()
+	utilruntime.Must(clientgoscheme.AddToScheme(scheme))
+	utilruntime.Must(gatewayv1.Install(scheme))
+	utilruntime.Must(gatewayv1beta1.Install(scheme))
+
+	kube, err := client.New(cfg, client.Options{Scheme: scheme})
+	if err != nil {
+		t.Fatalf("TestConverges() client error = %v", err)
+	}
+	ctx, cancel := context.WithCancel(context.Background())
+	t.Cleanup(cancel)
+	if err := kube.Create(ctx, &corev1.Namespace{ObjectMeta: metav1.ObjectMeta{Name: namespace}}); err != nil {
+		t.Fatalf("TestConverges() create namespace error = %v", err)
+	}
+
+	dashboard := startConstellation(ctx, t, cfg, scheme)
+
+	tests := []struct {
+		name string
+		flap bool
+	}{
+		{name: "steady", flap: false},
+		{name: "flap", flap: true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			before, after := tt.name+"-before", tt.name+"-after"
+			createService(ctx, t, kube, before)
+			converge(ctx, t, dashboard, map[string]bool{before: true})
+
+			if tt.flap {
+				docker(t, "restart", "--time", "0", containerName)
+				waitReady(t)
+			}
+
+			createService(ctx, t, kube, after)
+			service := &corev1.Service{ObjectMeta: metav1.ObjectMeta{Namespace: namespace, Name: before}}
+			if err := kube.Delete(ctx, service); err != nil {
+				t.Fatalf("TestConverges() delete %s error = %v", before, err)
+			}
+			converge(ctx, t, dashboard, map[string]bool{before: false, after: true})
+		})
+	}
+}
+
+// startK3s runs a k3s server on a fixed loopback port, so the port survives the container restarting,
+// and returns a config for its apiserver
+func startK3s(t *testing.T) *rest.Config {
+	t.Helper()
+	image := os.Getenv("CONSTELLATION_K3S_IMAGE")
+	if image == "" {
+		image = defaultImage
+	}
+	port := freePort(t)
+
+	exec.Command("docker", "rm", "--force", containerName).Run() //nolint:errcheck
+	docker(t, "run", "--detach", "--privileged", "--name", containerName,
+		"--publish", fmt.Sprintf("127.0.0.1:%d:6443", port), image,
+		"server", "--disable=traefik", "--disable=metrics-server")
+	t.Cleanup(func() { exec.Command("docker", "rm", "--force", containerName).Run() }) //nolint:errcheck
+	waitReady(t)
+
+	kubeconfig := docker(t, "exec", containerName, "cat", "/etc/rancher/k3s/k3s.yaml")
+	kubeconfig = strings.ReplaceAll(kubeconfig, "127.0.0.1:6443", "127.0.0.1:"+strconv.Itoa(port))
+	cfg, err := clientcmd.RESTConfigFromKubeConfig([]byte(kubeconfig))
+	if err != nil {
+		t.Fatalf("startK3s() kubeconfig error = %v", err)
+	}
+	return cfg
+}
+
+// startConstellation runs constellation in a manager against cfg and returns a client for its API. The
+// Gateway API kinds are left unwatched since k3s does not install their CRDs.
+func startConstellation(ctx context.Context, t *testing.T, cfg *rest.Config,
+	scheme *runtime.Scheme) *constellationclient.Client {
+	t.Helper()
+	mgr, err := ctrl.NewManager(cfg, ctrl.Options{
+		Scheme:                 scheme,
+		Metrics:                metricsserver.Options{BindAddress: "0"},
+		HealthProbeBindAddress: "0",
+	})
+	if err != nil {
+		t.Fatalf("startConstellation() manager error = %v", err)
+	}
+
+	listener, err := net.Listen("tcp", "127.0.0.1:0")
+	if err != nil {
+		t.Fatalf("startConstellation() listen error = %v", err)
+	}
+	_, err = constellation.SetupWithManager(mgr, constellation.Options{
+		Listener:        listener,
+		DisabledWatches: []string{"httproutes", "gateways", "gatewayclasses"},
+	})
+	if err != nil {
+		t.Fatalf("startConstellation() setup error = %v", err)
+	}
+	go mgr.Start(ctx) //nolint:errcheck
+
+	dashboard, err := constellationclient.New("http://" + listener.Addr().String())
+	if err != nil {
+		t.Fatalf("startConstellation() client error = %v", err)
+	}
+	return dashboard
+}
+
+func createService(ctx context.Context, t *testing.T, kube client.Client, name string) {
+	t.Helper()
+	service := &corev1.Service{
+		ObjectMeta: metav1.ObjectMeta{Namespace: namespace, Name: name},
+		Spec: corev1.ServiceSpec{
+			Selector: map[string]string{"app": name},
+			Ports:    []corev1.ServicePort{{Port: 80}},
+		},
+	}
+	if err := kube.Create(ctx, service); err != nil {
+		t.Fatalf("createService() %s error = %v", name, err)
+	}
+}
+
+// converge waits until the hierarchy has every Service in want set to true and none set to false
+func converge(ctx context.Context, t *testing.T, dashboard *constellationclient.Client, want map[string]bool) {
+	t.Helper()
+	eventually(t, convergeTimeout, func() error {
+		snapshot, err := dashboard.GetSnapshot(ctx)
+		if err != nil {
+			return err
+		}
+		for name, present := range want {
+			id := "Service/" + namespace + "/" + name
+			if found := hierarchy.FindNode(snapshot.Hierarchy, id) != nil; found != present {
+				return fmt.Errorf("%s present = %t, want %t", id, found, present)
+			}
+		}
+		return nil
+	})
+}
+
+// waitReady waits for the apiserver in the container to report ready
+func waitReady(t *testing.T) {
+	t.Helper()
+	eventually(t, startTimeout, func() error {
+		output, err := exec.Command("docker", "exec", containerName, "kubectl", "get", "--raw", "/readyz").CombinedOutput()
+		if err != nil {
+			return fmt.Errorf("%w: %s", err, output)
+		}
+		return nil
+	})
+}
+
+func eventually(t *testing.T, timeout time.Duration, check func() error) {
+	t.Helper()
+	deadline := time.Now().Add(timeout)
+	for {
+		err := check()
+		if err == nil {
+			return
+		}
+		if time.Now().After(deadline) {
+			t.Fatalf("not converged after %s: %v", timeout, err)
+		}
+		time.Sleep(time.Second)
+	}
+}
+
+func docker(t *testing.T, args ...string) string {
+	t.Helper()
+	output, err := exec.Command("docker", args...).CombinedOutput()
+	if err != nil {
+		t.Fatalf("docker %s error = %v: %s", strings.Join(args, " "), err, output)
+	}
+	return string(output)
+}
+
+func freePort(t *testing.T) int {
+	t.Helper()
+	listener, err := net.Listen("tcp", "127.0.0.1:0")
+	if err != nil {
+		t.Fatalf("freePort() error = %v", err)
+	}
+	defer listener.Close() //nolint:errcheck
+	return listener.Addr().(*net.TCPAddr).Port
+}
@@ -0,0 +1,131 @@
+//go:build soak
+
+// Package soak replays hours of pod events through the pipeline at full speed and checks the heap settles,
+// to catch leaks from the paths that copy the hierarchy and health data on every update. Run with
+// make test-soak; CONSTELLATION_SOAK_HOURS sets how much cluster time is replayed.
+package soak_test
+
+import (
+	"context"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"runtime"
+	"strconv"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/gorilla/websocket"
+
+	"github.com/kdwils/constellation/internal/loadgen"
+	"github.com/kdwils/constellation/internal/server"
+)
+
+const (
+	pods = 1000
+	// rate is the events per second of cluster time replayed, matching loadgen's default churn
+	rate = 50
+	// checkpoint is the cluster time between heap samples
+	checkpoint = 10 * time.Minute
+
+	// heapGrowth and heapSlack bound the live heap at each checkpoint against the first, which is taken
+	// once the caches and buffers have reached their working size
+	heapGrowth = 1.25
+	heapSlack  = 8 << 20
+	// goroutineSlack allows for the health checker and server goroutines that come and go between samples
+	goroutineSlack = 20
+)
+
+func TestHeapSettles(t *testing.T) {
+	hours := 2.0
+	if value := os.Getenv("CONSTELLATION_SOAK_HOURS"); value != "" {
+		parsed, err := strconv.ParseFloat(value, 64)
+		if err != nil {
+			t.Fatalf("TestHeapSettles() CONSTELLATION_SOAK_HOURS error = %v", err)
+		}
+		hours = parsed
+	}
+
+	ctx, cancel := context.WithCancel(context.Background())
+	t.Cleanup(cancel)
+
+	generator := loadgen.New(loadgen.Config{Pods: pods, Churn: rate})
+	go generator.HealthChecker.Start(ctx)
+	if _, err := generator.Sync(ctx); err != nil {
+		t.Fatalf("TestHeapSettles() sync error = %v", err)
+	}
+
+	srv := server.New(generator,
+		server.WithHierarchyProvider(generator),
+		server.WithSnapshotProvider(generator),
+	)
+	httpServer := httptest.NewServer(srv.Handler())
+	t.Cleanup(httpServer.Close)
+	watch(ctx, t, httpServer.URL)
+
+	events := int(checkpoint.Seconds()) * rate
+	checkpoints := int(hours * float64(time.Hour) / float64(checkpoint))
+	var baseHeap uint64
+	var baseGoroutines int
+	for i := range checkpoints {
+		if err := generator.Apply(ctx, events); err != nil {
+			t.Fatalf("TestHeapSettles() apply error = %v", err)
+		}
+		read(t, httpServer.URL+"/api/v1/state")
+		read(t, httpServer.URL+"/api/v1/snapshot")
+
+		heap, goroutines := sample()
+		t.Logf("%s of cluster time: heap %d MiB, %d goroutines", time.Duration(i+1)*checkpoint, heap>>20, goroutines)
+		if i == 0 {
+			baseHeap, baseGoroutines = heap, goroutines
+			continue
+		}
+		if limit := uint64(float64(baseHeap)*heapGrowth) + heapSlack; heap > limit {
+			t.Errorf("TestHeapSettles() heap = %d bytes after %s, want at most %d", heap,
+				time.Duration(i+1)*checkpoint, limit)
+		}
+		if goroutines > baseGoroutines+goroutineSlack {
+			t.Errorf("TestHeapSettles() goroutines = %d after %s, want at most %d", goroutines,
+				time.Duration(i+1)*checkpoint, baseGoroutines+goroutineSlack)
+		}
+	}
+}
+
+// watch keeps a websocket client reading the state stream, so each update is also copied out to a
+// subscriber
+func watch(ctx context.Context, t *testing.T, base string) {
+	t.Helper()
+	conn, _, err := websocket.DefaultDialer.DialContext(ctx, "ws"+strings.TrimPrefix(base, "http")+"/ws", nil)
+	if err != nil {
+		t.Fatalf("watch() dial error = %v", err)
+	}
+	t.Cleanup(func() { conn.Close() }) //nolint:errcheck
+	go func() {
+		for {
+			if _, _, err := conn.NextReader(); err != nil {
+				return
+			}
+		}
+	}()
+}
+
+func read(t *testing.T, url string) {
+	t.Helper()
+	resp, err := http.Get(url)
+	if err != nil {
+		t.Fatalf("read() %s error = %v", url, err)
+	}
+	defer resp.Body.Close() //nolint:errcheck
+	if resp.StatusCode != http.StatusOK {
+		t.Fatalf("read() %s status = %d, want %d", url, resp.StatusCode, http.StatusOK)
+	}
+}
+
+// sample returns the live heap after a full collection and the number of goroutines
+func sample() (uint64, int) {
+	runtime.GC()
+	var stats runtime.MemStats
+	runtime.ReadMemStats(&stats)
+	return stats.HeapAlloc, runtime.NumGoroutine()
+}